@@ -0,0 +1,48 @@
+/*!
+ * # 邻接边标签缓存基准测试
+ *
+ * 在标签分布倾斜的中心顶点上按标签查询邻接边，
+ * 比较开启与未开启 `enable_adjacent_label_cache` 时的耗时。
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use graph_api_lib::{EdgeSearch, Graph};
+use graph_api_test::{Edge, EdgeLabel};
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+
+/// 构建一个中心顶点：绝大多数边为 `Created`，少量为 `Knows`，没有 `Language`
+fn skewed_hub(degree: usize) -> (SlotMapGraph<(), Edge>, VertexId) {
+    let mut graph = SlotMapGraph::new();
+    let hub = graph.add_vertex(());
+    for i in 0..degree {
+        let leaf = graph.add_vertex(());
+        let edge = if i % 100 == 0 { Edge::Knows { since: i as i32 } } else { Edge::Created };
+        graph.add_edge(hub, leaf, edge);
+    }
+    (graph, hub)
+}
+
+fn bench_label_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("adjacent_label_search");
+    for degree in [1_000, 10_000] {
+        let (mut graph, hub) = skewed_hub(degree);
+
+        for (name, label) in [("zero_match", EdgeLabel::Language), ("rare", EdgeLabel::Knows)] {
+            let search = EdgeSearch::label(label);
+
+            graph.disable_adjacent_label_cache();
+            group.bench_with_input(BenchmarkId::new(format!("{name}/no_cache"), degree), &graph, |b, graph| {
+                b.iter(|| graph.edges(black_box(hub), &search).count())
+            });
+
+            graph.enable_adjacent_label_cache();
+            group.bench_with_input(BenchmarkId::new(format!("{name}/cache"), degree), &graph, |b, graph| {
+                b.iter(|| graph.edges(black_box(hub), &search).count())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_label_search);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+/*!
+ * # 批量删除顶点基准测试
+ *
+ * 图由若干个内部稠密连接的簇组成，删除其中一整个簇，
+ * 比较逐个 `remove_vertex` 与一次 [`SlotMapGraph::remove_vertices`] 的耗时。
+ * 逐个删除时簇内的每条边都要从两个端点的邻接表中平移删除，批量删除则整体释放这些邻接表。
+ */
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use graph_api_lib::{Graph, SupportsElementRemoval};
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+
+/// `clusters` 个各有 `size` 个顶点的簇，每个顶点向簇内 `size / 4` 个顶点连边，
+/// 另向下一个簇连一条边；返回图和第一个簇的顶点
+fn clustered_graph(clusters: usize, size: usize) -> (SlotMapGraph<(), ()>, Vec<VertexId>) {
+    let mut graph = SlotMapGraph::new();
+    let vertices: Vec<_> = (0..clusters * size).map(|_| graph.add_vertex(())).collect();
+    for cluster in 0..clusters {
+        let base = cluster * size;
+        for i in 0..size {
+            for step in 1..=size / 4 {
+                graph.add_edge(vertices[base + i], vertices[base + (i + step * 3) % size], ());
+            }
+            graph.add_edge(vertices[base + i], vertices[(base + size + i) % vertices.len()], ());
+        }
+    }
+    (graph, vertices[..size].to_vec())
+}
+
+fn bench_remove_cluster(c: &mut Criterion) {
+    let mut group = c.benchmark_group("remove_cluster");
+    for size in [64, 256] {
+        group.bench_function(BenchmarkId::new("sequential", size), |b| {
+            b.iter_batched(
+                || clustered_graph(16, size),
+                |(mut graph, victims)| {
+                    for vertex_id in victims {
+                        graph.remove_vertex(vertex_id);
+                    }
+                    graph
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(BenchmarkId::new("batched", size), |b| {
+            b.iter_batched(
+                || clustered_graph(16, size),
+                |(mut graph, victims)| {
+                    graph.remove_vertices(&victims);
+                    graph
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_remove_cluster);
+criterion_main!(benches);
@@ -0,0 +1,36 @@
+/*!
+ * # 只读邻接结构基准测试
+ *
+ * 在一个拥有大量出边的中心顶点上查询 `has_edge`，
+ * 比较动态图的线性扫描与 [`FrozenGraph`] 的二分查找。
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use graph_api_lib::Graph;
+use pi_slotmap_graph::SlotMapGraph;
+
+fn bench_hub_has_edge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hub_has_edge");
+    for degree in [100, 10_000] {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let hub = graph.add_vertex(());
+        let leaves: Vec<_> = (0..degree).map(|_| graph.add_vertex(())).collect();
+        for &leaf in &leaves {
+            graph.add_edge(hub, leaf, ());
+        }
+        // 查询最后加入的邻居，动态图需要扫描整个出边列表
+        let target = *leaves.last().unwrap();
+        let frozen = graph.to_static();
+
+        group.bench_with_input(BenchmarkId::new("dynamic", degree), &graph, |b, graph| {
+            b.iter(|| graph.has_edge(black_box(hub), black_box(target)))
+        });
+        group.bench_with_input(BenchmarkId::new("frozen", degree), &frozen, |b, frozen| {
+            b.iter(|| frozen.has_edge(black_box(hub), black_box(target)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_hub_has_edge);
+criterion_main!(benches);
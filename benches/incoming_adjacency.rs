@@ -0,0 +1,50 @@
+/*!
+ * # 入边遍历基准测试
+ *
+ * 在入度很高的汇聚顶点上遍历全部入边，
+ * 比较 `Graph::edges` 的入边搜索、`incoming_edges` 与直接借用 `incoming_adjacency` 切片的耗时。
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use graph_api_lib::{EdgeReference, EdgeSearch, Graph};
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+
+/// 构建一个汇聚顶点：`degree` 个源顶点各有一条边指向它
+fn sink(degree: usize) -> (SlotMapGraph<(), u32>, VertexId) {
+    let mut graph = SlotMapGraph::new();
+    let sink = graph.add_vertex(());
+    for i in 0..degree {
+        let source = graph.add_vertex(());
+        graph.add_edge(source, sink, i as u32);
+    }
+    (graph, sink)
+}
+
+fn bench_incoming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("incoming_traversal");
+    for degree in [100, 10_000] {
+        let (graph, sink) = sink(degree);
+        let search = EdgeSearch::scan().incoming();
+
+        group.bench_with_input(BenchmarkId::new("edge_search", degree), &graph, |b, graph| {
+            b.iter(|| graph.edges(black_box(sink), &search).filter(|e| e.tail() != sink).count())
+        });
+        group.bench_with_input(BenchmarkId::new("incoming_edges", degree), &graph, |b, graph| {
+            b.iter(|| graph.incoming_edges(black_box(sink)).filter(|e| e.tail() != sink).count())
+        });
+        group.bench_with_input(BenchmarkId::new("incoming_adjacency", degree), &graph, |b, graph| {
+            b.iter(|| {
+                graph
+                    .incoming_adjacency(black_box(sink))
+                    .iter()
+                    .filter_map(|id| graph.edge_from(*id))
+                    .filter(|tail| *tail != sink)
+                    .count()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_incoming);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+/*!
+ * # 标签查询基准测试
+ *
+ * 在 1M 个顶点中按只占 1% 的标签查询，
+ * 比较 `vertices` 的标签查询与全扫描后按标签过滤的耗时。
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use graph_api_lib::{Element, Graph, VertexReference, VertexSearch};
+use graph_api_test::{Project, Vertex, VertexLabel};
+use pi_slotmap_graph::SlotMapGraph;
+
+/// 构建 `count` 个顶点，每 100 个中有一个 `Project`，其余为 `Rust`
+fn sparse_label(count: usize) -> SlotMapGraph<Vertex, ()> {
+    let mut graph = SlotMapGraph::new();
+    for i in 0..count {
+        let vertex = if i % 100 == 0 { Vertex::Project(Project { name: i.to_string() }) } else { Vertex::Rust };
+        graph.add_vertex(vertex);
+    }
+    graph
+}
+
+fn bench_label_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("label_search_1pct");
+    group.sample_size(20);
+    let graph = sparse_label(1_000_000);
+    let search = VertexSearch::Label { label: VertexLabel::Project, limit: None };
+
+    group.bench_function("naive_scan", |b| {
+        b.iter(|| {
+            graph
+                .vertices(&VertexSearch::scan())
+                .filter(|vertex| vertex.weight().label() == black_box(VertexLabel::Project))
+                .count()
+        })
+    });
+    group.bench_function("label_prefilter", |b| b.iter(|| graph.vertices(black_box(&search)).count()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_label_search);
+criterion_main!(benches);
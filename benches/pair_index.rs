@@ -0,0 +1,42 @@
+/*!
+ * # 端点对索引基准测试
+ *
+ * 在出度为 10k 的中心顶点上逐个判断与各叶子是否相连，
+ * 比较开启与未开启 `enable_pair_index` 时 `has_edge` 的耗时。
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use graph_api_lib::Graph;
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+
+/// 构建一个中心顶点，指向 `degree` 个叶子
+fn hub(degree: usize) -> (SlotMapGraph<(), ()>, VertexId, Vec<VertexId>) {
+    let mut graph = SlotMapGraph::new();
+    let hub = graph.add_vertex(());
+    let leaves: Vec<_> = (0..degree).map(|_| graph.add_vertex(())).collect();
+    for leaf in &leaves {
+        graph.add_edge(hub, *leaf, ());
+    }
+    (graph, hub, leaves)
+}
+
+fn bench_has_edge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hub_has_edge");
+    let degree = 10_000;
+    let (mut graph, hub, leaves) = hub(degree);
+    // 每隔 100 个叶子抽样一次，同时查询存在与不存在的方向
+    let probes: Vec<_> = leaves.iter().step_by(100).copied().collect();
+
+    group.bench_with_input(BenchmarkId::new("scan", degree), &graph, |b, graph| {
+        b.iter(|| probes.iter().filter(|leaf| graph.has_edge(black_box(hub), **leaf) && !graph.has_edge(**leaf, hub)).count())
+    });
+
+    graph.enable_pair_index();
+    group.bench_with_input(BenchmarkId::new("pair_index", degree), &graph, |b, graph| {
+        b.iter(|| probes.iter().filter(|leaf| graph.has_edge(black_box(hub), **leaf) && !graph.has_edge(**leaf, hub)).count())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_has_edge);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+/*!
+ * # 槽位布局基准测试
+ *
+ * 在按顺序插入的环状稀疏图上做广度优先搜索，
+ * 比较原始插入顺序（局部性最好）与 `rebuilt_shuffled` 随机布局（局部性最差）的耗时。
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use graph_api_lib::Graph;
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// 构建 `n` 个顶点的图，每个顶点指向其后的 4 个顶点，插入顺序与遍历顺序一致
+fn local_graph(n: usize) -> SlotMapGraph<u64, ()> {
+    let mut graph = SlotMapGraph::new();
+    let vertices: Vec<_> = (0..n as u64).map(|i| graph.add_vertex(i)).collect();
+    for (i, &from) in vertices.iter().enumerate() {
+        for step in 1..=4 {
+            graph.add_edge(from, vertices[(i + step) % n], ());
+        }
+    }
+    graph
+}
+
+/// 广度优先搜索，累加访问到的顶点数据以读取每个顶点的槽位
+fn bfs_sum(graph: &SlotMapGraph<u64, ()>, start: VertexId) -> u64 {
+    graph.bfs(start).map(|(vertex, _)| graph.vertex_weight(vertex).copied().unwrap_or(0)).sum()
+}
+
+fn bench_layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bfs_layout");
+    let n = 200_000;
+    let graph = local_graph(n);
+    let start = graph.vertex_ids().next().unwrap();
+
+    group.bench_with_input(BenchmarkId::new("inserted", n), &graph, |b, graph| b.iter(|| bfs_sum(graph, black_box(start))));
+    for seed in [1, 2, 3] {
+        let (shuffled, map) = graph.rebuilt_shuffled(&mut StdRng::seed_from_u64(seed));
+        let start = map[&start];
+        group.bench_with_input(BenchmarkId::new(format!("shuffled/{seed}"), n), &shuffled, |b, graph| {
+            b.iter(|| bfs_sum(graph, black_box(start)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_layout);
+criterion_main!(benches);
@@ -0,0 +1,70 @@
+/*!
+ * # 访问标记基准测试
+ *
+ * 在随机稀疏图上从同一起点做广度优先搜索，
+ * 比较以 `HashSet<VertexId>` 与 [`VertexIdSet`] 作为访问标记的耗时。
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use graph_api_lib::Graph;
+use pi_slotmap_graph::collections::VertexIdSet;
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+use std::collections::{HashSet, VecDeque};
+
+/// 构建 `n` 个顶点、每个顶点 4 条随机出边的图
+fn random_graph(n: usize) -> (SlotMapGraph<(), ()>, VertexId) {
+    let mut graph = SlotMapGraph::new();
+    let vertices: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+    for &from in &vertices {
+        for _ in 0..4 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            graph.add_edge(from, vertices[state as usize % n], ());
+        }
+    }
+    (graph, vertices[0])
+}
+
+/// 以 `visit` 作为访问标记做广度优先搜索，返回访问到的顶点数
+fn bfs(graph: &SlotMapGraph<(), ()>, start: VertexId, mut visit: impl FnMut(VertexId) -> bool) -> usize {
+    let mut queue = VecDeque::from([start]);
+    visit(start);
+    let mut count = 0;
+    while let Some(vertex) = queue.pop_front() {
+        count += 1;
+        for edge_id in graph.outgoing_adjacency(vertex) {
+            if let Some(to) = graph.edge_to(*edge_id) {
+                if visit(to) {
+                    queue.push_back(to);
+                }
+            }
+        }
+    }
+    count
+}
+
+fn bench_visited(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bfs_visited");
+    for n in [1_000, 100_000] {
+        let (graph, start) = random_graph(n);
+
+        group.bench_with_input(BenchmarkId::new("hash_set", n), &graph, |b, graph| {
+            b.iter(|| {
+                let mut visited = HashSet::new();
+                bfs(graph, black_box(start), |v| visited.insert(v))
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("vertex_id_set", n), &graph, |b, graph| {
+            b.iter(|| {
+                let mut visited: VertexIdSet = graph.empty_vertex_set();
+                bfs(graph, black_box(start), |v| visited.insert(v))
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_visited);
+criterion_main!(benches);
@@ -0,0 +1,123 @@
+/*!
+ * # 依赖解析示例
+ *
+ * 从内置的包清单构建依赖图（边 `包 -> 依赖`），然后：
+ *
+ * - 用拓扑排序给出安装顺序（依赖先于依赖它的包）
+ * - 存在循环依赖时用 `find_cycle` 列出完整的环
+ * - 用 `descendants` + `induced_subgraph` 取出某个包的依赖闭包，并以 DOT 格式输出
+ *
+ * 运行：`cargo run --example dependency_resolver`
+ */
+
+use graph_api_lib::{Element, Graph};
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+use std::collections::HashMap;
+
+/// 每行 `包: 依赖, 依赖`
+pub const MANIFEST: &str = "
+app: http, json, log
+http: net, log
+json: alloc
+net: alloc, log
+log: alloc
+alloc:
+bench: app, json
+";
+
+/// 含循环依赖的清单
+pub const CYCLIC_MANIFEST: &str = "
+app: http
+http: tls
+tls: crypto
+crypto: http
+";
+
+/// 包
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Package {
+    pub name: String,
+}
+
+impl Element for Package {
+    type Label = ();
+
+    fn label(&self) -> Self::Label {}
+}
+
+/// 依赖图及包名到顶点的映射
+pub struct Dependencies {
+    pub graph: SlotMapGraph<Package, ()>,
+    pub ids: HashMap<String, VertexId>,
+}
+
+impl Dependencies {
+    /// 解析清单，只出现在依赖列表中的包也会加入图中
+    pub fn parse(manifest: &str) -> Self {
+        let mut deps = Dependencies {
+            graph: SlotMapGraph::new(),
+            ids: HashMap::new(),
+        };
+        for line in manifest.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (name, requires) = line.split_once(':').expect("line has the form `name: deps`");
+            let package = deps.package(name.trim());
+            for dependency in requires.split(',').map(str::trim).filter(|dep| !dep.is_empty()) {
+                let dependency = deps.package(dependency);
+                deps.graph.add_edge(package, dependency, ());
+            }
+        }
+        deps
+    }
+
+    fn package(&mut self, name: &str) -> VertexId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.graph.add_vertex(Package { name: name.to_string() });
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+
+    fn name(&self, id: VertexId) -> &str {
+        &self.graph.vertex_weight(id).expect("package exists").name
+    }
+
+    /// 安装顺序；存在循环依赖时返回环上的包名，最后一个包依赖第一个
+    pub fn install_order(&self) -> Result<Vec<String>, Vec<String>> {
+        match self.graph.topological_sort() {
+            // 边从包指向依赖，拓扑序的逆序让依赖排在前面
+            Ok(order) => Ok(order.into_iter().rev().map(|id| self.name(id).to_string()).collect()),
+            Err(_) => {
+                let cycle = self.graph.find_cycle().expect("topological sort failed on a cycle");
+                Err(cycle.into_iter().map(|id| self.name(id).to_string()).collect())
+            }
+        }
+    }
+
+    /// `name` 及其全部直接和间接依赖组成的子图的 DOT 文本
+    pub fn closure_dot(&self, name: &str) -> Option<String> {
+        let root = *self.ids.get(name)?;
+        let mut closure: Vec<VertexId> = self.graph.descendants(root).into_iter().collect();
+        // 按包名排序，输出与哈希顺序无关
+        closure.sort_by(|a, b| self.name(*a).cmp(self.name(*b)));
+        closure.insert(0, root);
+        let sub = self.graph.induced_subgraph(closure);
+        Some(sub.graph.to_dot_with(|_, package| package.name.clone(), |_, _| String::new()))
+    }
+}
+
+fn main() {
+    let deps = Dependencies::parse(MANIFEST);
+    match deps.install_order() {
+        Ok(order) => println!("install order: {}", order.join(" -> ")),
+        Err(cycle) => println!("dependency cycle: {}", cycle.join(" -> ")),
+    }
+    println!();
+    print!("{}", deps.closure_dot("http").expect("http is in the manifest"));
+    println!();
+
+    let cyclic = Dependencies::parse(CYCLIC_MANIFEST);
+    if let Err(cycle) = cyclic.install_order() {
+        println!("dependency cycle: {} -> {}", cycle.join(" -> "), cycle[0]);
+    }
+}
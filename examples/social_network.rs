@@ -0,0 +1,131 @@
+/*!
+ * # 社交网络示例
+ *
+ * 以派生宏声明带索引的顶点和边类型，端到端演示索引系统：
+ *
+ * - 标签索引：按顶点种类（人 / 城市）查找
+ * - 哈希索引：按姓名精确查找
+ * - 范围索引：按年龄区间查找
+ * - 边标签：沿 `Knows` 边查找好友，沿 `LivesIn` 边查找所在城市
+ *
+ * 运行：`cargo run --example social_network`
+ */
+
+use graph_api_derive::{EdgeExt, VertexExt};
+use graph_api_lib::{EdgeReference, EdgeSearch, Graph, VertexReference};
+use pi_slotmap_graph::{SlotMapGraph, VertexId};
+use std::ops::Range;
+
+/// 顶点：人或城市
+#[derive(Debug, Clone, VertexExt)]
+pub enum Node {
+    Person {
+        #[index(hash)]
+        name: String,
+        #[index(range)]
+        age: u64,
+    },
+    City {
+        #[index(hash)]
+        name: String,
+    },
+}
+
+/// 边：认识某人，或住在某城市
+#[derive(Debug, Clone, EdgeExt)]
+pub enum Link {
+    Knows { since: u32 },
+    LivesIn,
+}
+
+pub type SocialGraph = SlotMapGraph<Node, Link>;
+
+/// 构建示例网络
+pub fn build() -> SocialGraph {
+    let mut graph = SocialGraph::new();
+    let person = |name: &str, age| Node::Person { name: name.to_string(), age };
+    let city = |name: &str| Node::City { name: name.to_string() };
+
+    let alice = graph.add_vertex(person("Alice", 28));
+    let bob = graph.add_vertex(person("Bob", 35));
+    let carol = graph.add_vertex(person("Carol", 42));
+    let dave = graph.add_vertex(person("Dave", 19));
+    let paris = graph.add_vertex(city("Paris"));
+    let lyon = graph.add_vertex(city("Lyon"));
+
+    graph.add_edge(alice, bob, Link::Knows { since: 2015 });
+    graph.add_edge(alice, carol, Link::Knows { since: 2020 });
+    graph.add_edge(bob, dave, Link::Knows { since: 2022 });
+    graph.add_edge(carol, alice, Link::Knows { since: 2020 });
+    for (who, place) in [(alice, paris), (bob, lyon), (carol, paris), (dave, lyon)] {
+        graph.add_edge(who, place, Link::LivesIn);
+    }
+    graph
+}
+
+/// 顶点的名字
+fn name(graph: &SocialGraph, id: VertexId) -> String {
+    match graph.vertex_weight(id) {
+        Some(Node::Person { name, .. } | Node::City { name }) => name.clone(),
+        None => String::new(),
+    }
+}
+
+fn sorted(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names
+}
+
+/// 全部城市（标签索引）
+pub fn cities(graph: &SocialGraph) -> Vec<String> {
+    sorted(graph.vertices(&Node::city()).map(|city| name(graph, city.id())).collect())
+}
+
+/// 按姓名查找人（哈希索引）
+pub fn find_person(graph: &SocialGraph, person: &str) -> Option<VertexId> {
+    graph.vertices(&Node::person_by_name(person)).map(|vertex| vertex.id()).next()
+}
+
+/// 年龄在区间内的人（范围索引）
+pub fn people_aged(graph: &SocialGraph, ages: Range<u64>) -> Vec<String> {
+    sorted(graph.vertices(&Node::person_by_age_range(ages)).map(|person| name(graph, person.id())).collect())
+}
+
+/// 某人认识的人及相识的年份（边标签）
+pub fn friends_of(graph: &SocialGraph, person: VertexId) -> Vec<(String, u32)> {
+    let mut friends: Vec<_> = graph
+        .edges(person, &EdgeSearch::label(LinkLabel::Knows).outgoing())
+        .filter_map(|edge| match edge.weight() {
+            Link::Knows { since } => Some((name(graph, edge.head()), *since)),
+            Link::LivesIn => None,
+        })
+        .collect();
+    friends.sort();
+    friends
+}
+
+/// 与某人住在同一城市的其他人
+pub fn neighbours_of(graph: &SocialGraph, person: VertexId) -> Vec<String> {
+    let lives_in = EdgeSearch::label(LinkLabel::LivesIn);
+    let mut neighbours = Vec::new();
+    for home in graph.edges(person, &lives_in.clone().outgoing()) {
+        for resident in graph.edges(home.head(), &lives_in.clone().incoming()) {
+            if resident.tail() != person {
+                neighbours.push(name(graph, resident.tail()));
+            }
+        }
+    }
+    sorted(neighbours)
+}
+
+fn main() {
+    let graph = build();
+    println!("cities: {:?}", cities(&graph));
+    println!("aged 20-40: {:?}", people_aged(&graph, 20..40));
+
+    let alice = find_person(&graph, "Alice").expect("Alice is in the network");
+    for (friend, since) in friends_of(&graph, alice) {
+        println!("Alice has known {} since {}", friend, since);
+    }
+    println!("Alice's neighbours: {:?}", neighbours_of(&graph, alice));
+}
@@ -0,0 +1,25 @@
+//! 任意字节反序列化为变更集并应用到副本：只能返回错误或得到一张一致的图，不能 panic
+
+#![no_main]
+
+use graph_api_lib::Graph;
+use libfuzzer_sys::fuzz_target;
+use pi_slotmap_graph::changelog::{ChangeSet, IdMap};
+use pi_slotmap_graph::SlotMapGraph;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(changes) = serde_json::from_slice::<ChangeSet<u32, u32>>(data) else {
+        return;
+    };
+    // 副本预先带几个顶点和边，让删除记录有机会命中已有元素
+    let mut replica: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    let vertices: Vec<_> = (0..4).map(|weight| replica.add_vertex(weight)).collect();
+    for (i, pair) in vertices.windows(2).enumerate() {
+        replica.add_edge(pair[0], pair[1], i as u32);
+    }
+
+    let mut ids = IdMap::new();
+    if changes.apply(&mut replica, &mut ids).is_ok() {
+        assert_eq!(replica.validate(), Ok(()));
+    }
+});
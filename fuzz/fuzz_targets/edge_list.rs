@@ -0,0 +1,17 @@
+//! 任意字节输入边列表导入：只能返回错误或一张一致的图，不能 panic，也不能超出导入上限
+
+#![no_main]
+
+use graph_api_lib::Graph;
+use libfuzzer_sys::fuzz_target;
+use pi_slotmap_graph::import::{from_edge_list_reader, ImportLimits};
+
+const LIMITS: ImportLimits = ImportLimits { max_line_len: 256, max_vertices: 4096, max_edges: 16384 };
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(graph) = from_edge_list_reader(data, &LIMITS) {
+        assert!(graph.vertex_count() <= LIMITS.max_vertices);
+        assert!(graph.edge_count() <= LIMITS.max_edges);
+        assert_eq!(graph.validate(), Ok(()));
+    }
+});
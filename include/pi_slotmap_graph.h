@@ -0,0 +1,42 @@
+/*
+ * pi_slotmap_graph C 接口，与 src/ffi.rs 保持一致（需启用 `ffi` 特性）。
+ *
+ * 图的顶点、边数据均为 uint64_t；顶点和边 ID 以 uint64_t 传递，SMG_INVALID_ID 不是任何有效 ID。
+ * 返回状态的函数以 SMG_OK 表示成功，其余为负的错误码。
+ */
+#ifndef PI_SLOTMAP_GRAPH_H
+#define PI_SLOTMAP_GRAPH_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define SMG_OK 0
+#define SMG_NULL_HANDLE (-1)
+#define SMG_NOT_FOUND (-2)
+#define SMG_NULL_BUFFER (-3)
+#define SMG_PANIC (-4)
+#define SMG_INVALID_ID ((uint64_t)0xFFFFFFFFu)
+
+/* 不透明的图句柄 */
+typedef struct SmgGraph SmgGraph;
+
+SmgGraph *smg_new(void);
+void smg_free(SmgGraph *handle);
+
+uint64_t smg_add_vertex(SmgGraph *handle, uint64_t weight);
+uint64_t smg_add_edge(SmgGraph *handle, uint64_t from, uint64_t to, uint64_t weight);
+int32_t smg_remove_vertex(SmgGraph *handle, uint64_t vertex_id);
+
+/* 至多写入 cap 个出边 ID，返回出度；返回值大于 cap 时缓冲区不足 */
+int64_t smg_out_edges(const SmgGraph *handle, uint64_t vertex_id, uint64_t *out_buf, size_t cap);
+int32_t smg_vertex_weight(const SmgGraph *handle, uint64_t vertex_id, uint64_t *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PI_SLOTMAP_GRAPH_H */
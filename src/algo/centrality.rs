@@ -0,0 +1,370 @@
+/*!
+ * # 中心性 (Centrality)
+ *
+ * - [`betweenness_centrality`](SlotMapGraph::betweenness_centrality)：Brandes 算法求介数中心性，
+ *   按有向、无权处理，O(V·E)。结果未归一化：顶点的值为经过它的最短路径占比之和
+ *   （对每个有序顶点对 `s != t` 计算，`s`、`t` 自身不计）。平行边视为不同的路径，自环被忽略
+ * - [`pagerank`](SlotMapGraph::pagerank)：幂迭代求 PageRank，出度为 0 的顶点把分值平均分给所有顶点，
+ *   结果之和为 1；平行边按条数计入权重
+ *
+ * ## 工作区
+ *
+ * 两个算法都需要若干与 V、E 成正比的数组。反复运行时把 [`BrandesWorkspace`] 或
+ * [`PageRankWorkspace`] 传给 `*_with_workspace` 版本，数组和结果表都在工作区中重复使用，
+ * 只在图变大时扩容；结果以工作区中 `HashMap` 的引用返回。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::algo::centrality::BrandesWorkspace;
+ *
+ * let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+ * let a = graph.add_vertex(());
+ * let b = graph.add_vertex(());
+ * let c = graph.add_vertex(());
+ * graph.add_edge(a, b, ());
+ * graph.add_edge(b, c, ());
+ *
+ * // 唯一一条长度大于 1 的最短路径 a -> b -> c 经过 b
+ * assert_eq!(graph.betweenness_centrality()[&b], 1.0);
+ *
+ * let mut workspace = BrandesWorkspace::default();
+ * assert_eq!(graph.betweenness_centrality_with_workspace(&mut workspace)[&a], 0.0);
+ *
+ * let ranks = graph.pagerank(0.85, 100, 1e-9);
+ * assert!(ranks[&c] > ranks[&b] && ranks[&b] > ranks[&a]);
+ * ```
+ */
+
+use super::dense::DenseAdjacency;
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::{HashMap, VecDeque};
+
+/// 未到达的顶点的距离
+const UNREACHED: u32 = u32::MAX;
+
+/// 介数中心性（Brandes 算法）的可复用工作区
+#[derive(Debug, Clone)]
+pub struct BrandesWorkspace<K: Key = DefaultKey> {
+    adjacency: DenseAdjacency<K>,
+    /// 最短路径条数
+    sigma: Vec<f64>,
+    distance: Vec<u32>,
+    /// 依赖值
+    delta: Vec<f64>,
+    centrality: Vec<f64>,
+    /// 按距离非递减排列的已到达顶点
+    order: Vec<u32>,
+    queue: VecDeque<u32>,
+    scores: HashMap<VertexId<K>, f64>,
+}
+
+impl<K: Key> Default for BrandesWorkspace<K> {
+    fn default() -> Self {
+        Self::with_capacity(0, 0)
+    }
+}
+
+impl<K: Key> BrandesWorkspace<K> {
+    /// 预先容纳 `vertices` 个顶点和 `edges` 条边的工作区
+    pub fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self {
+            adjacency: DenseAdjacency::with_capacity(vertices, edges),
+            sigma: Vec::with_capacity(vertices),
+            distance: Vec::with_capacity(vertices),
+            delta: Vec::with_capacity(vertices),
+            centrality: Vec::with_capacity(vertices),
+            order: Vec::with_capacity(vertices),
+            queue: VecDeque::with_capacity(vertices),
+            scores: HashMap::with_capacity(vertices),
+        }
+    }
+
+    /// 清空内容和上次的结果，保留已分配的内存
+    pub fn reset(&mut self) {
+        self.adjacency.reset();
+        self.sigma.clear();
+        self.distance.clear();
+        self.delta.clear();
+        self.centrality.clear();
+        self.order.clear();
+        self.queue.clear();
+        self.scores.clear();
+    }
+
+    /// 不重新分配时可容纳的顶点数
+    pub fn vertex_capacity(&self) -> usize {
+        self.adjacency.vertex_capacity()
+    }
+
+    /// 在已填充的邻接表上运行 Brandes 算法，结果写入 `scores`
+    fn run(&mut self) {
+        let Self { adjacency, sigma, distance, delta, centrality, order, queue, scores } = self;
+        let len = adjacency.len();
+        sigma.resize(len, 0.0);
+        distance.resize(len, UNREACHED);
+        delta.resize(len, 0.0);
+        centrality.resize(len, 0.0);
+
+        for source in 0..len {
+            sigma.fill(0.0);
+            distance.fill(UNREACHED);
+            delta.fill(0.0);
+            order.clear();
+            sigma[source] = 1.0;
+            distance[source] = 0;
+            queue.push_back(source as u32);
+
+            while let Some(v) = queue.pop_front() {
+                let v = v as usize;
+                order.push(v as u32);
+                for &w in adjacency.outgoing(v) {
+                    let w = w as usize;
+                    if distance[w] == UNREACHED {
+                        distance[w] = distance[v] + 1;
+                        queue.push_back(w as u32);
+                    }
+                    if distance[w] == distance[v] + 1 {
+                        sigma[w] += sigma[v];
+                    }
+                }
+            }
+            // 按距离从远到近累积依赖值，前驱由入边中距离少 1 的顶点给出
+            for &w in order.iter().rev() {
+                let w = w as usize;
+                for &v in adjacency.incoming(w) {
+                    let v = v as usize;
+                    if distance[v] != UNREACHED && distance[v] + 1 == distance[w] {
+                        delta[v] += sigma[v] / sigma[w] * (1.0 + delta[w]);
+                    }
+                }
+                if w != source {
+                    centrality[w] += delta[w];
+                }
+            }
+        }
+        scores.extend(adjacency.ids().iter().copied().zip(centrality.iter().copied()));
+    }
+}
+
+/// PageRank 的可复用工作区
+#[derive(Debug, Clone)]
+pub struct PageRankWorkspace<K: Key = DefaultKey> {
+    adjacency: DenseAdjacency<K>,
+    ranks: Vec<f64>,
+    next: Vec<f64>,
+    scores: HashMap<VertexId<K>, f64>,
+}
+
+impl<K: Key> Default for PageRankWorkspace<K> {
+    fn default() -> Self {
+        Self::with_capacity(0, 0)
+    }
+}
+
+impl<K: Key> PageRankWorkspace<K> {
+    /// 预先容纳 `vertices` 个顶点和 `edges` 条边的工作区
+    pub fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self {
+            adjacency: DenseAdjacency::with_capacity(vertices, edges),
+            ranks: Vec::with_capacity(vertices),
+            next: Vec::with_capacity(vertices),
+            scores: HashMap::with_capacity(vertices),
+        }
+    }
+
+    /// 清空内容和上次的结果，保留已分配的内存
+    pub fn reset(&mut self) {
+        self.adjacency.reset();
+        self.ranks.clear();
+        self.next.clear();
+        self.scores.clear();
+    }
+
+    /// 不重新分配时可容纳的顶点数
+    pub fn vertex_capacity(&self) -> usize {
+        self.adjacency.vertex_capacity()
+    }
+
+    /// 在已填充的邻接表上做幂迭代，结果写入 `scores`
+    fn run(&mut self, damping: f64, max_iterations: usize, tolerance: f64) {
+        let Self { adjacency, ranks, next, scores } = self;
+        let len = adjacency.len();
+        if len == 0 {
+            return;
+        }
+        let uniform = 1.0 / len as f64;
+        ranks.resize(len, uniform);
+        next.resize(len, 0.0);
+
+        for _ in 0..max_iterations {
+            let dangling: f64 = (0..len).filter(|&v| adjacency.outgoing(v).is_empty()).map(|v| ranks[v]).sum();
+            let base = (1.0 - damping) * uniform + damping * dangling * uniform;
+            for (v, rank) in next.iter_mut().enumerate() {
+                let inflow: f64 = adjacency
+                    .incoming(v)
+                    .iter()
+                    .map(|&u| ranks[u as usize] / adjacency.outgoing(u as usize).len() as f64)
+                    .sum();
+                *rank = base + damping * inflow;
+            }
+            let change: f64 = ranks.iter().zip(next.iter()).map(|(old, new)| (old - new).abs()).sum();
+            std::mem::swap(ranks, next);
+            if change < tolerance {
+                break;
+            }
+        }
+        scores.extend(adjacency.ids().iter().copied().zip(ranks.iter().copied()));
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 每个顶点的介数中心性（有向、无权、未归一化）
+    pub fn betweenness_centrality(&self) -> HashMap<VertexId<K>, f64> {
+        let mut workspace = BrandesWorkspace::with_capacity(self.vertex_count(), self.edge_count());
+        self.betweenness_centrality_with_workspace(&mut workspace);
+        std::mem::take(&mut workspace.scores)
+    }
+
+    /// 使用 `workspace` 求介数中心性，返回保存在工作区中的结果
+    pub fn betweenness_centrality_with_workspace<'ws>(
+        &self,
+        workspace: &'ws mut BrandesWorkspace<K>,
+    ) -> &'ws HashMap<VertexId<K>, f64> {
+        workspace.reset();
+        workspace.adjacency.load(self, true);
+        workspace.run();
+        &workspace.scores
+    }
+
+    /// 每个顶点的 PageRank
+    ///
+    /// `damping` 为沿边跳转的概率（通常为 0.85）；迭代至多 `max_iterations` 轮，
+    /// 两轮之间分值变化的绝对值之和小于 `tolerance` 时提前结束。
+    pub fn pagerank(&self, damping: f64, max_iterations: usize, tolerance: f64) -> HashMap<VertexId<K>, f64> {
+        let mut workspace = PageRankWorkspace::with_capacity(self.vertex_count(), self.edge_count());
+        self.pagerank_with_workspace(&mut workspace, damping, max_iterations, tolerance);
+        std::mem::take(&mut workspace.scores)
+    }
+
+    /// 使用 `workspace` 求 PageRank，参数同 [`pagerank`](Self::pagerank)，返回保存在工作区中的结果
+    pub fn pagerank_with_workspace<'ws>(
+        &self,
+        workspace: &'ws mut PageRankWorkspace<K>,
+        damping: f64,
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> &'ws HashMap<VertexId<K>, f64> {
+        workspace.reset();
+        workspace.adjacency.load(self, true);
+        workspace.run(damping, max_iterations, tolerance);
+        &workspace.scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::Graph;
+
+    fn assert_close(left: f64, right: f64) {
+        assert!((left - right).abs() < 1e-9, "{left} != {right}");
+    }
+
+    /// 伪随机的有向图，含平行边和自环
+    fn random_graph(n: usize, edges: usize, seed: u64) -> SlotMapGraph<(), ()> {
+        let mut graph = SlotMapGraph::new();
+        let ids: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+        let mut state = seed;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % n
+        };
+        for _ in 0..edges {
+            let (from, to) = (next(), next());
+            graph.add_edge(ids[from], ids[to], ());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_betweenness_known_values() {
+        // 星形：中心双向连接 4 个叶子，叶子之间的 12 条最短路径都经过中心
+        let mut star: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let hub = star.add_vertex(());
+        let leaves: Vec<_> = (0..4).map(|_| star.add_vertex(())).collect();
+        for &leaf in &leaves {
+            star.add_edge(hub, leaf, ());
+            star.add_edge(leaf, hub, ());
+        }
+        star.add_edge(hub, hub, ());
+        let scores = star.betweenness_centrality();
+        assert_close(scores[&hub], 12.0);
+        assert_close(scores[&leaves[0]], 0.0);
+
+        // 菱形 a -> {b, c} -> d：两条等长路径各分一半
+        let mut diamond: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| diamond.add_vertex(()));
+        for (from, to) in [(a, b), (a, c), (b, d), (c, d)] {
+            diamond.add_edge(from, to, ());
+        }
+        let scores = diamond.betweenness_centrality();
+        assert_close(scores[&b], 0.5);
+        assert_close(scores[&c], 0.5);
+        assert!(SlotMapGraph::<(), ()>::new().betweenness_centrality().is_empty());
+    }
+
+    #[test]
+    fn test_pagerank_known_values() {
+        // 有向环上各顶点分值相同
+        let mut ring: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..5).map(|_| ring.add_vertex(())).collect();
+        for i in 0..5 {
+            ring.add_edge(ids[i], ids[(i + 1) % 5], ());
+        }
+        for rank in ring.pagerank(0.85, 100, 1e-12).values() {
+            assert_close(*rank, 0.2);
+        }
+
+        // 含出度为 0 的顶点时分值之和仍为 1
+        let graph = random_graph(30, 40, 7);
+        let ranks = graph.pagerank(0.85, 200, 1e-12);
+        assert_eq!(ranks.len(), 30);
+        assert_close(ranks.values().sum(), 1.0);
+        assert!(SlotMapGraph::<(), ()>::new().pagerank(0.85, 10, 1e-9).is_empty());
+    }
+
+    #[test]
+    fn test_reused_workspaces_match_fresh_runs() {
+        let mut brandes = BrandesWorkspace::with_capacity(8, 8);
+        let mut pagerank = PageRankWorkspace::default();
+        // 先小后大的两张不同的图，工作区按需扩容
+        for (n, edges, seed) in [(20, 60, 1), (300, 1_200, 2)] {
+            let graph = random_graph(n, edges, seed);
+
+            let fresh = graph.betweenness_centrality();
+            let reused = graph.betweenness_centrality_with_workspace(&mut brandes);
+            assert_eq!(reused, &fresh);
+            assert!(brandes.vertex_capacity() >= n);
+
+            let fresh = graph.pagerank(0.85, 50, 1e-10);
+            let reused = graph.pagerank_with_workspace(&mut pagerank, 0.85, 50, 1e-10);
+            assert_eq!(reused, &fresh);
+            assert!(pagerank.vertex_capacity() >= n);
+        }
+        brandes.reset();
+        assert!(brandes.vertex_capacity() >= 300);
+    }
+}
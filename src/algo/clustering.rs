@@ -0,0 +1,148 @@
+/*!
+ * # 三角形与聚类系数 (Triangles and Clustering)
+ *
+ * 社交网络分析常用的局部结构统计。所有计算都把图视为无向简单图：
+ * 邻居取自 [`distinct_neighbors`](SlotMapGraph::distinct_neighbors)，
+ * 忽略边的方向，平行边、反向边只计一次，自环不计入。
+ *
+ * ## 三角形计数
+ *
+ * 按 (去重度数, 遍历位置) 为顶点排序，每个顶点只保留排序更靠后的邻居，
+ * 再对每条保留下来的边求两端保留邻居的交集。每个三角形只在其排序最靠前的顶点上计数一次，
+ * 且高度数顶点的保留邻居较少，时间复杂度 O(E^1.5)。
+ *
+ * ## 聚类系数
+ *
+ * 顶点 `v` 的局部聚类系数为其邻居之间实际存在的边数与可能边数 `k(k-1)/2` 之比，
+ * 邻居少于两个时定义为 `0.0`。平均聚类系数对所有顶点取平均，空图为 `0.0`。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::Key;
+use std::collections::{HashMap, HashSet};
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 无向意义下的三角形数量，每个三角形计一次
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(a, b, ());
+    /// graph.add_edge(b, c, ());
+    /// graph.add_edge(a, c, ());
+    ///
+    /// assert_eq!(graph.triangle_count(), 1);
+    /// assert_eq!(graph.clustering_coefficient(a), 1.0);
+    /// ```
+    pub fn triangle_count(&self) -> usize {
+        let neighbors: HashMap<VertexId<K>, HashSet<VertexId<K>>> =
+            self.vertex_ids().map(|v| (v, self.distinct_neighbors(v))).collect();
+
+        let mut ordered: Vec<VertexId<K>> = self.vertex_ids().collect();
+        ordered.sort_by_key(|v| neighbors[v].len());
+        let rank: HashMap<VertexId<K>, usize> = ordered.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+
+        // 每个顶点排序更靠后的邻居
+        let forward: HashMap<VertexId<K>, HashSet<VertexId<K>>> = neighbors
+            .iter()
+            .map(|(v, adjacent)| (*v, adjacent.iter().copied().filter(|u| rank[u] > rank[v]).collect()))
+            .collect();
+
+        forward
+            .values()
+            .map(|later| later.iter().map(|u| forward[u].intersection(later).count()).sum::<usize>())
+            .sum()
+    }
+
+    /// 顶点的局部聚类系数
+    ///
+    /// 去重邻居少于两个或顶点不存在时返回 `0.0`。
+    pub fn clustering_coefficient(&self, vertex_id: VertexId<K>) -> f64 {
+        let neighbors = self.distinct_neighbors(vertex_id);
+        let k = neighbors.len();
+        if k < 2 {
+            return 0.0;
+        }
+        // 邻居之间的每条边从两端各数一次
+        let links: usize = neighbors
+            .iter()
+            .map(|u| self.distinct_neighbors(*u).intersection(&neighbors).count())
+            .sum();
+        links as f64 / (k * (k - 1)) as f64
+    }
+
+    /// 所有顶点局部聚类系数的平均值
+    ///
+    /// 空图返回 `0.0`。
+    pub fn average_clustering(&self) -> f64 {
+        let vertices = self.vertex_count();
+        if vertices == 0 {
+            return 0.0;
+        }
+        let total: f64 = self.vertex_ids().map(|v| self.clustering_coefficient(v)).sum();
+        total / vertices as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SlotMapGraph;
+    use graph_api_lib::Graph;
+
+    #[test]
+    fn test_triangle_with_parallel_and_reverse_edges() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add_vertex(()));
+        for (from, to) in [(a, b), (b, c), (c, a), (b, a), (a, b), (c, c)] {
+            graph.add_edge(from, to, ());
+        }
+
+        assert_eq!(graph.triangle_count(), 1);
+        assert_eq!(graph.clustering_coefficient(b), 1.0);
+        assert_eq!(graph.average_clustering(), 1.0);
+    }
+
+    #[test]
+    fn test_complete_graph_k4() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let vertices = [(); 4].map(|_| graph.add_vertex(()));
+        for (i, from) in vertices.iter().enumerate() {
+            for to in &vertices[i + 1..] {
+                graph.add_edge(*from, *to, ());
+            }
+        }
+
+        assert_eq!(graph.triangle_count(), 4);
+        assert!(vertices.iter().all(|v| graph.clustering_coefficient(*v) == 1.0));
+    }
+
+    #[test]
+    fn test_star_has_no_triangles() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let hub = graph.add_vertex(());
+        let leaves = [(); 5].map(|_| graph.add_vertex(()));
+        for leaf in leaves {
+            graph.add_edge(hub, leaf, ());
+        }
+
+        assert_eq!(graph.triangle_count(), 0);
+        assert_eq!(graph.clustering_coefficient(hub), 0.0);
+        assert!(leaves.iter().all(|v| graph.clustering_coefficient(*v) == 0.0));
+        assert_eq!(graph.average_clustering(), 0.0);
+        assert_eq!(SlotMapGraph::<(), ()>::new().average_clustering(), 0.0);
+    }
+}
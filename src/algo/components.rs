@@ -0,0 +1,318 @@
+/*!
+ * # 连通分量 (Connected Components)
+ *
+ * [`strongly_connected_components`](SlotMapGraph::strongly_connected_components) 以非递归的
+ * Tarjan 算法求出全部强连通分量，O(V + E)，深图不会栈溢出。
+ *
+ * 分量按 Tarjan 算法完成的顺序产生，即缩点图的逆拓扑序：一个分量只可能有边指向排在它之前的分量。
+ * 没有环经过的顶点各自构成单独的分量。
+ *
+ * [`weakly_connected_components`](SlotMapGraph::weakly_connected_components) 忽略边的方向求弱连通分量，O(V + E)。
+ * 分量按各自首个顶点的存储顺序排列，分量内的顶点也按存储顺序排列。
+ *
+ * ## 工作区
+ *
+ * 算法需要若干与 V、E 成正比的数组。对同一张或不同的图反复运行（例如每帧一次）时，
+ * 把 [`SccWorkspace`] 传给 [`strongly_connected_components_with_workspace`](SlotMapGraph::strongly_connected_components_with_workspace)，
+ * 这些数组以及结果都保存在工作区中重复使用，只在图变大时扩容。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::algo::components::SccWorkspace;
+ *
+ * let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+ * let a = graph.add_vertex(());
+ * let b = graph.add_vertex(());
+ * let c = graph.add_vertex(());
+ * graph.add_edge(a, b, ());
+ * graph.add_edge(b, a, ());
+ * graph.add_edge(b, c, ());
+ *
+ * assert_eq!(graph.strongly_connected_components().len(), 2);
+ *
+ * let mut workspace = SccWorkspace::with_capacity(3, 3);
+ * let sizes: Vec<usize> = graph.strongly_connected_components_with_workspace(&mut workspace).map(<[_]>::len).collect();
+ * assert_eq!(sizes, vec![1, 2]);
+ * ```
+ */
+
+use super::dense::DenseAdjacency;
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+
+/// 未访问的顶点的序号
+const UNVISITED: u32 = u32::MAX;
+
+/// 强连通分量算法的可复用工作区
+#[derive(Debug, Clone)]
+pub struct SccWorkspace<K: Key = DefaultKey> {
+    adjacency: DenseAdjacency<K>,
+    /// 访问序号
+    index: Vec<u32>,
+    /// 可回溯到的最小序号
+    low: Vec<u32>,
+    on_stack: Vec<bool>,
+    stack: Vec<u32>,
+    /// 模拟递归的调用栈：顶点及下一条待检查的出边
+    calls: Vec<(u32, usize)>,
+    /// 各分量的顶点依次排列
+    members: Vec<VertexId<K>>,
+    /// 每个分量在 `members` 中的结束位置
+    ends: Vec<usize>,
+}
+
+impl<K: Key> Default for SccWorkspace<K> {
+    fn default() -> Self {
+        Self::with_capacity(0, 0)
+    }
+}
+
+impl<K: Key> SccWorkspace<K> {
+    /// 预先容纳 `vertices` 个顶点和 `edges` 条边的工作区
+    pub fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self {
+            adjacency: DenseAdjacency::with_capacity(vertices, edges),
+            index: Vec::with_capacity(vertices),
+            low: Vec::with_capacity(vertices),
+            on_stack: Vec::with_capacity(vertices),
+            stack: Vec::with_capacity(vertices),
+            calls: Vec::with_capacity(vertices),
+            members: Vec::with_capacity(vertices),
+            ends: Vec::with_capacity(vertices),
+        }
+    }
+
+    /// 清空内容和上次的结果，保留已分配的内存
+    pub fn reset(&mut self) {
+        self.adjacency.reset();
+        self.index.clear();
+        self.low.clear();
+        self.on_stack.clear();
+        self.stack.clear();
+        self.calls.clear();
+        self.members.clear();
+        self.ends.clear();
+    }
+
+    /// 不重新分配时可容纳的顶点数
+    pub fn vertex_capacity(&self) -> usize {
+        self.adjacency.vertex_capacity()
+    }
+
+    /// 上次运行得到的分量
+    pub fn components(&self) -> impl ExactSizeIterator<Item = &[VertexId<K>]> + '_ {
+        self.ends.iter().enumerate().map(|(i, &end)| {
+            let start = if i == 0 { 0 } else { self.ends[i - 1] };
+            &self.members[start..end]
+        })
+    }
+
+    /// 在已填充的邻接表上运行 Tarjan 算法
+    fn run(&mut self) {
+        let Self { adjacency, index, low, on_stack, stack, calls, members, ends } = self;
+        let len = adjacency.len();
+        index.resize(len, UNVISITED);
+        low.resize(len, 0);
+        on_stack.resize(len, false);
+
+        let mut counter = 0u32;
+        for root in 0..len {
+            if index[root] != UNVISITED {
+                continue;
+            }
+            index[root] = counter;
+            low[root] = counter;
+            counter += 1;
+            stack.push(root as u32);
+            on_stack[root] = true;
+            calls.push((root as u32, 0));
+
+            while let Some((vertex, cursor)) = calls.last_mut() {
+                let v = *vertex as usize;
+                if let Some(&w) = adjacency.outgoing(v).get(*cursor) {
+                    *cursor += 1;
+                    let w = w as usize;
+                    if index[w] == UNVISITED {
+                        index[w] = counter;
+                        low[w] = counter;
+                        counter += 1;
+                        stack.push(w as u32);
+                        on_stack[w] = true;
+                        calls.push((w as u32, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    low[parent as usize] = low[parent as usize].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    while let Some(w) = stack.pop() {
+                        on_stack[w as usize] = false;
+                        members.push(adjacency.ids()[w as usize]);
+                        if w as usize == v {
+                            break;
+                        }
+                    }
+                    ends.push(members.len());
+                }
+            }
+        }
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 全部强连通分量，按缩点图的逆拓扑序排列
+    pub fn strongly_connected_components(&self) -> Vec<Vec<VertexId<K>>> {
+        let mut workspace = SccWorkspace::with_capacity(self.vertex_count(), self.edge_count());
+        self.strongly_connected_components_with_workspace(&mut workspace).map(<[_]>::to_vec).collect()
+    }
+
+    /// 全部弱连通分量，孤立顶点各自构成单独的分量
+    pub fn weakly_connected_components(&self) -> Vec<Vec<VertexId<K>>> {
+        let mut adjacency = DenseAdjacency::with_capacity(self.vertex_count(), self.edge_count());
+        adjacency.load(self, true);
+        let len = adjacency.len();
+        let mut owner = vec![UNVISITED; len];
+        let mut count = 0;
+        let mut queue = Vec::new();
+        for root in 0..len {
+            if owner[root] != UNVISITED {
+                continue;
+            }
+            owner[root] = count;
+            queue.push(root as u32);
+            while let Some(v) = queue.pop() {
+                let v = v as usize;
+                for &w in adjacency.outgoing(v).iter().chain(adjacency.incoming(v)) {
+                    if owner[w as usize] == UNVISITED {
+                        owner[w as usize] = count;
+                        queue.push(w);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        let mut components = vec![Vec::new(); count as usize];
+        for (&vertex_id, &component) in adjacency.ids().iter().zip(&owner) {
+            components[component as usize].push(vertex_id);
+        }
+        components
+    }
+
+    /// 使用 `workspace` 求强连通分量，结果保存在工作区中，顺序同
+    /// [`strongly_connected_components`](Self::strongly_connected_components)
+    pub fn strongly_connected_components_with_workspace<'ws>(
+        &self,
+        workspace: &'ws mut SccWorkspace<K>,
+    ) -> impl ExactSizeIterator<Item = &'ws [VertexId<K>]> + 'ws {
+        workspace.reset();
+        workspace.adjacency.load(self, false);
+        workspace.run();
+        workspace.components()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::Graph;
+    use std::collections::BTreeSet;
+
+    /// 与顺序无关的分量集合
+    fn normalized(components: impl IntoIterator<Item = Vec<VertexId>>) -> BTreeSet<Vec<u64>> {
+        components
+            .into_iter()
+            .map(|component| {
+                let mut ids: Vec<u64> = component.iter().map(VertexId::as_ffi).collect();
+                ids.sort();
+                ids
+            })
+            .collect()
+    }
+
+    /// `n` 个顶点：每 `cycle` 个组成一个环，相邻的环之间有单向边
+    fn ring_chain(n: usize, cycle: usize) -> (SlotMapGraph<(), ()>, Vec<VertexId>) {
+        let mut graph = SlotMapGraph::new();
+        let ids: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+        for start in (0..n).step_by(cycle) {
+            let end = (start + cycle).min(n);
+            for i in start..end {
+                graph.add_edge(ids[i], ids[if i + 1 == end { start } else { i + 1 }], ());
+            }
+            if end < n {
+                graph.add_edge(ids[start], ids[end], ());
+            }
+        }
+        (graph, ids)
+    }
+
+    #[test]
+    fn test_components_in_reverse_topological_order() {
+        let (mut graph, ids) = ring_chain(10, 4);
+        graph.add_edge(ids[9], ids[9], ());
+        let components = graph.strongly_connected_components();
+        assert_eq!(
+            normalized(components.clone()),
+            normalized([ids[0..4].to_vec(), ids[4..8].to_vec(), ids[8..10].to_vec()])
+        );
+        // 下游的分量先完成
+        assert!(components[0].contains(&ids[8]));
+        assert!(components[2].contains(&ids[0]));
+
+        let mut dag: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let chain: Vec<_> = (0..5).map(|_| dag.add_vertex(())).collect();
+        for pair in chain.windows(2) {
+            dag.add_edge(pair[0], pair[1], ());
+        }
+        let singles: Vec<_> = dag.strongly_connected_components().into_iter().flatten().collect();
+        assert_eq!(singles, chain.into_iter().rev().collect::<Vec<_>>());
+        assert!(SlotMapGraph::<(), ()>::new().strongly_connected_components().is_empty());
+    }
+
+    #[test]
+    fn test_weakly_connected_components() {
+        let (mut graph, ids) = ring_chain(10, 4);
+        let lonely = graph.add_vertex(());
+        let tail = graph.add_vertex(());
+        let head = graph.add_vertex(());
+        graph.add_edge(head, tail, ());
+        graph.add_edge(tail, tail, ());
+
+        // 方向相反的边也把顶点连在一起，分量内按存储顺序排列
+        assert_eq!(graph.weakly_connected_components(), vec![ids, vec![lonely], vec![tail, head]]);
+        assert!(SlotMapGraph::<(), ()>::new().weakly_connected_components().is_empty());
+    }
+
+    #[test]
+    fn test_reused_workspace_matches_fresh_runs() {
+        let mut workspace = SccWorkspace::with_capacity(4, 4);
+        // 先小后大的两张不同的图，工作区按需扩容
+        for (n, cycle) in [(12, 3), (5_000, 7)] {
+            let (graph, _) = ring_chain(n, cycle);
+            let fresh = graph.strongly_connected_components();
+            let reused: Vec<Vec<VertexId>> =
+                graph.strongly_connected_components_with_workspace(&mut workspace).map(<[_]>::to_vec).collect();
+            assert_eq!(reused, fresh);
+            assert_eq!(workspace.components().len(), n.div_ceil(cycle));
+            assert!(workspace.vertex_capacity() >= n);
+        }
+        workspace.reset();
+        assert_eq!(workspace.components().len(), 0);
+        assert!(workspace.vertex_capacity() >= 5_000);
+    }
+}
@@ -0,0 +1,122 @@
+/*!
+ * # 稠密邻接表 (Dense Adjacency)
+ *
+ * 算法工作区共用的邻接表：顶点按存储顺序编号为 `0..V`，出边和入边的端点以编号存放在 CSR 数组中，
+ * 算法的其余状态即可用按编号索引的 `Vec` 保存，不做哈希。
+ * 每次运行前由 [`load`](DenseAdjacency::load) 从图重新填充，缓冲区只增不减。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+
+/// 槽位下标
+fn slot<K: Key>(vertex_id: VertexId<K>) -> usize {
+    vertex_id.key().data().index() as usize
+}
+
+/// 以稠密编号表示的出边与入边邻接表
+#[derive(Debug, Clone)]
+pub(crate) struct DenseAdjacency<K: Key = DefaultKey> {
+    /// 编号到顶点 ID
+    ids: Vec<VertexId<K>>,
+    /// 槽位下标到编号
+    slots: Vec<u32>,
+    out_offsets: Vec<usize>,
+    out_targets: Vec<u32>,
+    in_offsets: Vec<usize>,
+    in_sources: Vec<u32>,
+}
+
+impl<K: Key> Default for DenseAdjacency<K> {
+    fn default() -> Self {
+        Self::with_capacity(0, 0)
+    }
+}
+
+impl<K: Key> DenseAdjacency<K> {
+    /// 预先容纳 `vertices` 个顶点和 `edges` 条边
+    pub(crate) fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self {
+            ids: Vec::with_capacity(vertices),
+            slots: Vec::with_capacity(vertices),
+            out_offsets: Vec::with_capacity(vertices + 1),
+            out_targets: Vec::with_capacity(edges),
+            in_offsets: Vec::with_capacity(vertices + 1),
+            in_sources: Vec::with_capacity(edges),
+        }
+    }
+
+    /// 清空内容，保留缓冲区
+    pub(crate) fn reset(&mut self) {
+        self.ids.clear();
+        self.slots.clear();
+        self.out_offsets.clear();
+        self.out_targets.clear();
+        self.in_offsets.clear();
+        self.in_sources.clear();
+    }
+
+    /// 不重新分配时可容纳的顶点数
+    pub(crate) fn vertex_capacity(&self) -> usize {
+        self.ids.capacity()
+    }
+
+    /// 从图填充；`incoming` 为 `false` 时不建立入边
+    pub(crate) fn load<Vertex, Edge>(&mut self, graph: &SlotMapGraph<Vertex, Edge, K>, incoming: bool)
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        self.reset();
+        self.ids.extend(graph.vertex_ids());
+        for (index, &vertex_id) in self.ids.iter().enumerate() {
+            let slot = slot(vertex_id);
+            if slot >= self.slots.len() {
+                self.slots.resize(slot + 1, u32::MAX);
+            }
+            self.slots[slot] = index as u32;
+        }
+        for &vertex_id in &self.ids {
+            self.out_offsets.push(self.out_targets.len());
+            for &edge_id in graph.outgoing_adjacency(vertex_id) {
+                if let Some((_, to)) = graph.edge_endpoints(edge_id) {
+                    self.out_targets.push(self.slots[slot(to)]);
+                }
+            }
+            if incoming {
+                self.in_offsets.push(self.in_sources.len());
+                for &edge_id in graph.incoming_adjacency(vertex_id) {
+                    if let Some((from, _)) = graph.edge_endpoints(edge_id) {
+                        self.in_sources.push(self.slots[slot(from)]);
+                    }
+                }
+            }
+        }
+        self.out_offsets.push(self.out_targets.len());
+        if incoming {
+            self.in_offsets.push(self.in_sources.len());
+        }
+    }
+
+    /// 顶点数
+    pub(crate) fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// 全部顶点 ID，按编号排列
+    pub(crate) fn ids(&self) -> &[VertexId<K>] {
+        &self.ids
+    }
+
+    /// 出边终点的编号，平行边重复出现
+    pub(crate) fn outgoing(&self, index: usize) -> &[u32] {
+        &self.out_targets[self.out_offsets[index]..self.out_offsets[index + 1]]
+    }
+
+    /// 入边起点的编号，需要以 `incoming = true` 填充
+    pub(crate) fn incoming(&self, index: usize) -> &[u32] {
+        &self.in_sources[self.in_offsets[index]..self.in_offsets[index + 1]]
+    }
+}
@@ -0,0 +1,265 @@
+/*!
+ * # 支配树 (Dominator Tree)
+ *
+ * 用于控制流图分析：从入口到顶点 `b` 的每条路径都经过 `a` 时，称 `a` 支配 `b`。
+ *
+ * ## 算法
+ *
+ * Cooper–Harvey–Kennedy 迭代算法：按逆后序反复用前驱的支配者求交，直到不再变化。
+ * 顶点以后序编号表示，求交时沿支配树向上移动编号较小的一侧。
+ * 支配边界在求出直接支配者后，对每个汇合点（多个前驱）沿前驱向上遍历得到。
+ *
+ * 从入口不可达的顶点不出现在结果中，涉及它们的查询返回 `None`、`false` 或空结果。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::{HashMap, HashSet};
+
+/// 以某个入口计算出的支配树
+#[derive(Debug, Clone)]
+pub struct DominatorTree<K: Key = DefaultKey> {
+    /// 入口顶点
+    entry: VertexId<K>,
+    /// 每个可达顶点的直接支配者，入口映射到自身
+    idom: HashMap<VertexId<K>, VertexId<K>>,
+    /// 每个可达顶点的支配边界
+    frontiers: HashMap<VertexId<K>, HashSet<VertexId<K>>>,
+}
+
+impl<K: Key> DominatorTree<K> {
+    /// 入口顶点
+    pub fn entry(&self) -> VertexId<K> {
+        self.entry
+    }
+
+    /// 顶点是否从入口可达
+    pub fn contains(&self, vertex_id: VertexId<K>) -> bool {
+        self.idom.contains_key(&vertex_id)
+    }
+
+    /// 直接支配者，入口和不可达顶点返回 `None`
+    pub fn immediate_dominator(&self, vertex_id: VertexId<K>) -> Option<VertexId<K>> {
+        self.idom.get(&vertex_id).copied().filter(|idom| *idom != vertex_id)
+    }
+
+    /// `a` 是否支配 `b`（每个可达顶点都支配自身）
+    pub fn dominates(&self, a: VertexId<K>, b: VertexId<K>) -> bool {
+        if !self.contains(a) || !self.contains(b) {
+            return false;
+        }
+        let mut current = b;
+        loop {
+            if current == a {
+                return true;
+            }
+            match self.immediate_dominator(current) {
+                Some(idom) => current = idom,
+                None => return false,
+            }
+        }
+    }
+
+    /// 支配边界：`v` 支配某个前驱但不严格支配其本身的顶点
+    pub fn dominator_frontier(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = VertexId<K>> + '_ {
+        self.frontiers.get(&vertex_id).into_iter().flatten().copied()
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 以 `entry` 为入口计算支配树
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let entry = graph.add_vertex(());
+    /// let then = graph.add_vertex(());
+    /// let other = graph.add_vertex(());
+    /// let join = graph.add_vertex(());
+    /// graph.add_edge(entry, then, ());
+    /// graph.add_edge(entry, other, ());
+    /// graph.add_edge(then, join, ());
+    /// graph.add_edge(other, join, ());
+    ///
+    /// let tree = graph.dominators(entry);
+    /// assert_eq!(tree.immediate_dominator(join), Some(entry));
+    /// assert!(!tree.dominates(then, join));
+    /// assert_eq!(tree.dominator_frontier(then).collect::<Vec<_>>(), vec![join]);
+    /// ```
+    pub fn dominators(&self, entry: VertexId<K>) -> DominatorTree<K> {
+        let mut tree = DominatorTree {
+            entry,
+            idom: HashMap::new(),
+            frontiers: HashMap::new(),
+        };
+        if !self.vertices.contains(entry) {
+            return tree;
+        }
+
+        // 后序编号：入口编号最大
+        let postorder = self.postorder_from(entry);
+        let number: HashMap<VertexId<K>, usize> = postorder.iter().enumerate().map(|(i, v)| (*v, i)).collect();
+        let predecessors: Vec<Vec<usize>> = postorder
+            .iter()
+            .map(|v| {
+                self.edges
+                    .edges_to(*v)
+                    .filter_map(|edge_id| self.edges.get_connection(edge_id))
+                    .filter_map(|info| number.get(&info.from()).copied())
+                    .collect()
+            })
+            .collect();
+
+        let entry_number = postorder.len() - 1;
+        let mut doms: Vec<Option<usize>> = vec![None; postorder.len()];
+        doms[entry_number] = Some(entry_number);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in (0..entry_number).rev() {
+                let mut new_idom = None;
+                for &p in &predecessors[b] {
+                    if doms[p].is_none() {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => p,
+                        Some(current) => intersect(&doms, p, current),
+                    });
+                }
+                if new_idom.is_some() && doms[b] != new_idom {
+                    doms[b] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        for (b, dom) in doms.iter().enumerate() {
+            let idom = dom.expect("every vertex in postorder is reachable");
+            tree.idom.insert(postorder[b], postorder[idom]);
+            tree.frontiers.entry(postorder[b]).or_default();
+        }
+        for (b, preds) in predecessors.iter().enumerate() {
+            if preds.len() < 2 {
+                continue;
+            }
+            let idom = doms[b].expect("every vertex in postorder is reachable");
+            for &p in preds {
+                let mut runner = p;
+                while runner != idom {
+                    tree.frontiers.entry(postorder[runner]).or_default().insert(postorder[b]);
+                    runner = doms[runner].expect("every vertex in postorder is reachable");
+                }
+            }
+        }
+        tree
+    }
+
+    /// 从 `entry` 沿出边深度优先遍历得到的后序序列
+    fn postorder_from(&self, entry: VertexId<K>) -> Vec<VertexId<K>> {
+        let mut order = Vec::new();
+        let mut visited = self.empty_vertex_set();
+        visited.insert(entry);
+        // (顶点, 其出边列表中下一个待访问的位置)
+        let mut stack = vec![(entry, 0usize)];
+        while let Some((vertex, next)) = stack.last_mut() {
+            let outgoing = self.outgoing_adjacency(*vertex);
+            match outgoing.get(*next) {
+                Some(edge_id) => {
+                    *next += 1;
+                    if let Some(info) = self.edges.get_connection(*edge_id) {
+                        if visited.insert(info.to()) {
+                            stack.push((info.to(), 0));
+                        }
+                    }
+                }
+                None => {
+                    order.push(*vertex);
+                    stack.pop();
+                }
+            }
+        }
+        order
+    }
+}
+
+/// 沿支配树把两个后序编号向上移动到公共支配者
+fn intersect(doms: &[Option<usize>], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while a < b {
+            a = doms[a].expect("intersect only visits processed vertices");
+        }
+        while b < a {
+            b = doms[b].expect("intersect only visits processed vertices");
+        }
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::Graph;
+
+    #[test]
+    fn test_loop_cfg_idoms_and_frontiers() {
+        // entry -> a, entry -> b, a -> c, b -> c, c -> d, d -> c, d -> e, a -> e
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [entry, a, b, c, d, e] = [(); 6].map(|_| graph.add_vertex(()));
+        for (from, to) in [(entry, a), (entry, b), (a, c), (b, c), (c, d), (d, c), (d, e), (a, e)] {
+            graph.add_edge(from, to, ());
+        }
+
+        let tree = graph.dominators(entry);
+        assert_eq!(tree.immediate_dominator(entry), None);
+        assert_eq!(tree.immediate_dominator(a), Some(entry));
+        assert_eq!(tree.immediate_dominator(b), Some(entry));
+        assert_eq!(tree.immediate_dominator(c), Some(entry));
+        assert_eq!(tree.immediate_dominator(d), Some(c));
+        assert_eq!(tree.immediate_dominator(e), Some(entry));
+
+        assert!(tree.dominates(c, d));
+        assert!(tree.dominates(d, d));
+        assert!(!tree.dominates(a, c));
+
+        let frontier = |v| tree.dominator_frontier(v).collect::<HashSet<_>>();
+        assert_eq!(frontier(a), HashSet::from([c, e]));
+        assert_eq!(frontier(b), HashSet::from([c]));
+        assert_eq!(frontier(c), HashSet::from([c, e]));
+        assert_eq!(frontier(d), HashSet::from([c, e]));
+        assert!(frontier(entry).is_empty());
+    }
+
+    #[test]
+    fn test_diamond_and_unreachable() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [entry, left, right, join, orphan] = [(); 5].map(|_| graph.add_vertex(()));
+        for (from, to) in [(entry, left), (entry, right), (left, join), (right, join), (orphan, join)] {
+            graph.add_edge(from, to, ());
+        }
+
+        let tree = graph.dominators(entry);
+        assert_eq!(tree.entry(), entry);
+        assert_eq!(tree.immediate_dominator(join), Some(entry));
+        assert!(tree.dominates(entry, join));
+        assert!(!tree.dominates(left, join));
+
+        // 不可达顶点不参与计算
+        assert!(!tree.contains(orphan));
+        assert_eq!(tree.immediate_dominator(orphan), None);
+        assert!(!tree.dominates(orphan, join));
+        assert_eq!(tree.dominator_frontier(orphan).count(), 0);
+    }
+}
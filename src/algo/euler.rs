@@ -0,0 +1,181 @@
+/*!
+ * # 欧拉路径 (Eulerian Path)
+ *
+ * 在有向图上求恰好经过每条边一次的路径（欧拉路径），起点与终点相同时即为欧拉回路。
+ *
+ * ## 存在条件
+ *
+ * - 所有顶点出度等于入度（回路），或恰有一个顶点出度比入度多 1 作为起点、
+ *   一个顶点入度比出度多 1 作为终点，其余顶点出入度相等（路径）
+ * - 所有关联至少一条边的顶点连通；孤立顶点不影响结果
+ *
+ * ## 算法
+ *
+ * 先检查出入度条件，再用 Hierholzer 算法沿未使用的出边前进，走到死路时回退并记录边。
+ * 得到的边数少于图中边数说明非孤立顶点不连通。
+ * 边以 [`EdgeId`] 区分，平行边和自环都会各自出现一次。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::Key;
+use std::collections::HashMap;
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 求经过每条边恰好一次的边序列
+    ///
+    /// 不存在欧拉路径时返回 `None`；没有边的图返回空序列。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// let ab = graph.add_edge(a, b, ());
+    /// let bc = graph.add_edge(b, c, ());
+    ///
+    /// assert_eq!(graph.eulerian_path(), Some(vec![ab, bc]));
+    ///
+    /// // a 有两条出边而没有入边，不再存在欧拉路径
+    /// graph.add_edge(a, c, ());
+    /// assert_eq!(graph.eulerian_path(), None);
+    /// ```
+    pub fn eulerian_path(&self) -> Option<Vec<EdgeId<K>>> {
+        // 每个顶点的出度减入度
+        let mut balance: HashMap<VertexId<K>, i64> = HashMap::new();
+        let mut first = None;
+        for (_, _, info) in self.edges.iter_with_connections() {
+            first.get_or_insert(info.from());
+            *balance.entry(info.from()).or_default() += 1;
+            *balance.entry(info.to()).or_default() -= 1;
+        }
+        let Some(mut start) = first else {
+            return Some(Vec::new());
+        };
+
+        let (mut sources, mut sinks) = (0, 0);
+        for (vertex, diff) in balance {
+            match diff {
+                0 => {}
+                1 => {
+                    sources += 1;
+                    start = vertex;
+                }
+                -1 => sinks += 1,
+                _ => return None,
+            }
+        }
+        if (sources, sinks) != (0, 0) && (sources, sinks) != (1, 1) {
+            return None;
+        }
+
+        // Hierholzer：栈中保存 (当前顶点, 到达它的边)，`next` 记录每个顶点下一条未使用的出边
+        let mut next: HashMap<VertexId<K>, usize> = HashMap::new();
+        let mut stack: Vec<(VertexId<K>, Option<EdgeId<K>>)> = vec![(start, None)];
+        let mut trail = Vec::with_capacity(self.edges.len());
+        while let Some(&(vertex, arrived_by)) = stack.last() {
+            let outgoing = self.outgoing_adjacency(vertex);
+            let position = next.entry(vertex).or_default();
+            match outgoing.get(*position) {
+                Some(&edge_id) => {
+                    *position += 1;
+                    let info = self.edges.get_connection(edge_id)?;
+                    stack.push((info.to(), Some(edge_id)));
+                }
+                None => {
+                    stack.pop();
+                    trail.extend(arrived_by);
+                }
+            }
+        }
+
+        if trail.len() != self.edges.len() {
+            return None;
+        }
+        trail.reverse();
+        Some(trail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::Graph;
+    use std::collections::HashSet;
+
+    /// 检查边序列首尾相接且每条边恰好出现一次
+    fn assert_eulerian(graph: &SlotMapGraph<(), ()>, trail: &[EdgeId]) {
+        assert_eq!(trail.len(), graph.edges.len());
+        assert_eq!(trail.iter().collect::<HashSet<_>>().len(), trail.len());
+        for pair in trail.windows(2) {
+            assert_eq!(graph.edge_to(pair[0]), graph.edge_from(pair[1]));
+        }
+    }
+
+    fn endpoints(graph: &SlotMapGraph<(), ()>, trail: &[EdgeId]) -> (VertexId, VertexId) {
+        let (first, _) = graph.edge_endpoints(trail[0]).unwrap();
+        let (_, last) = graph.edge_endpoints(trail[trail.len() - 1]).unwrap();
+        (first, last)
+    }
+
+    #[test]
+    fn test_eulerian_circuit() {
+        // 两个共享顶点 a 的环，外加一对平行边和一个自环
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.add_vertex(()));
+        for (from, to) in [(a, b), (b, c), (c, a), (a, d), (d, a), (b, c), (c, b), (d, d)] {
+            graph.add_edge(from, to, ());
+        }
+        graph.add_vertex(());
+
+        let trail = graph.eulerian_path().unwrap();
+        assert_eulerian(&graph, &trail);
+        let (first, last) = endpoints(&graph, &trail);
+        assert_eq!(first, last);
+    }
+
+    #[test]
+    fn test_eulerian_path_only() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add_vertex(()));
+        for (from, to) in [(b, c), (a, b), (b, a), (a, b)] {
+            graph.add_edge(from, to, ());
+        }
+
+        let trail = graph.eulerian_path().unwrap();
+        assert_eulerian(&graph, &trail);
+        assert_eq!(endpoints(&graph, &trail), (a, c));
+    }
+
+    #[test]
+    fn test_no_eulerian_path() {
+        // 出入度不满足条件
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.add_vertex(()));
+        graph.add_edge(a, b, ());
+        graph.add_edge(a, c, ());
+        graph.add_edge(a, d, ());
+        assert_eq!(graph.eulerian_path(), None);
+
+        // 出入度平衡但由两个不相连的环组成
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.add_vertex(()));
+        for (from, to) in [(a, b), (b, a), (c, d), (d, c)] {
+            graph.add_edge(from, to, ());
+        }
+        assert_eq!(graph.eulerian_path(), None);
+
+        assert_eq!(SlotMapGraph::<(), ()>::new().eulerian_path(), Some(Vec::new()));
+    }
+}
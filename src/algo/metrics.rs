@@ -0,0 +1,233 @@
+/*!
+ * # 网络指标 (Network Metrics)
+ *
+ * 描述图整体结构的常用统计量。所有指标都返回 `f64`，
+ * 在分母为零的图（空图、没有边的图等）上定义为 `0.0` 而不是 NaN。
+ *
+ * ## 指标
+ *
+ * - **密度**: `E / (V(V-1))`，按有向简单图计算；存在平行边或自环时可能大于 1
+ * - **平均度**: 所有顶点 `degree()` 的平均值，自环只计一次
+ * - **互惠率**: 非自环边中，反向边 `b -> a` 也存在的 `a -> b` 边所占比例
+ * - **自环率**: 自环边占全部边的比例
+ * - **加权度（强度）**: 顶点关联边的代价之和，与 `degree()` 一样自环在总和中只计一次，
+ *   但同时计入出向和入向的加权度
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Direction, EdgeReference, Element};
+use pi_slotmap::Key;
+use std::collections::{HashMap, HashSet};
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 有向图密度 `E / (V(V-1))`
+    ///
+    /// 顶点少于两个时返回 `0.0`。O(1)。
+    pub fn density(&self) -> f64 {
+        let vertices = self.vertex_count() as f64;
+        if vertices < 2.0 {
+            return 0.0;
+        }
+        self.edge_count() as f64 / (vertices * (vertices - 1.0))
+    }
+
+    /// 平均度，即所有顶点 `degree()` 的平均值
+    ///
+    /// 空图返回 `0.0`。O(V)。
+    pub fn average_degree(&self) -> f64 {
+        let vertices = self.vertex_count();
+        if vertices == 0 {
+            return 0.0;
+        }
+        let total: usize = self.vertex_ids().map(|v| self.degree(v)).sum();
+        total as f64 / vertices as f64
+    }
+
+    /// 互惠率：非自环边中，反向边也存在的边所占比例
+    ///
+    /// 平行边各自计数。没有非自环边时返回 `0.0`。
+    /// 单遍收集所有 `(起点, 终点)` 对后逐边查询反向对，O(E) 时间和空间。
+    pub fn reciprocity(&self) -> f64 {
+        let pairs: HashSet<_> = self
+            .edges
+            .iter_with_connections()
+            .map(|(_, _, info)| (info.from(), info.to()))
+            .filter(|(from, to)| from != to)
+            .collect();
+
+        let (mut total, mut reciprocal) = (0usize, 0usize);
+        for (_, _, info) in self.edges.iter_with_connections() {
+            if info.from() == info.to() {
+                continue;
+            }
+            total += 1;
+            if pairs.contains(&(info.to(), info.from())) {
+                reciprocal += 1;
+            }
+        }
+        if total == 0 {
+            return 0.0;
+        }
+        reciprocal as f64 / total as f64
+    }
+
+    /// 自环率：自环边占全部边的比例
+    ///
+    /// 没有边时返回 `0.0`。O(E)。
+    pub fn self_loop_ratio(&self) -> f64 {
+        let edges = self.edge_count();
+        if edges == 0 {
+            return 0.0;
+        }
+        let self_loops = self
+            .edges
+            .iter_with_connections()
+            .filter(|(_, _, info)| info.from() == info.to())
+            .count();
+        self_loops as f64 / edges as f64
+    }
+
+    /// 出边代价之和，O(出度)
+    pub fn weighted_out_degree(&self, vertex_id: VertexId<K>, cost: impl Fn(EdgeId<K>, &Edge) -> f64) -> f64 {
+        self.outgoing_adjacency(vertex_id)
+            .iter()
+            .filter_map(|id| self.edge_weight(*id).map(|weight| cost(*id, weight)))
+            .sum()
+    }
+
+    /// 入边代价之和，O(入度)
+    pub fn weighted_in_degree(&self, vertex_id: VertexId<K>, cost: impl Fn(EdgeId<K>, &Edge) -> f64) -> f64 {
+        self.incoming_adjacency(vertex_id)
+            .iter()
+            .filter_map(|id| self.edge_weight(*id).map(|weight| cost(*id, weight)))
+            .sum()
+    }
+
+    /// 全部关联边代价之和，自环只计一次，O(度)
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), f64> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// graph.add_edge(a, b, 1.5);
+    /// graph.add_edge(b, a, 2.0);
+    /// graph.add_edge(a, a, 4.0);
+    ///
+    /// assert_eq!(graph.weighted_out_degree(a, |_, w| *w), 5.5);
+    /// assert_eq!(graph.weighted_in_degree(a, |_, w| *w), 6.0);
+    /// assert_eq!(graph.weighted_degree(a, |_, w| *w), 7.5);
+    /// ```
+    pub fn weighted_degree(&self, vertex_id: VertexId<K>, cost: impl Fn(EdgeId<K>, &Edge) -> f64) -> f64 {
+        self.adjacent_edges(vertex_id).map(|edge| cost(edge.id(), edge.weight())).sum()
+    }
+
+    /// 一次遍历全部边，求每个顶点在 `direction` 方向上的加权度
+    ///
+    /// 结果包含所有顶点，没有关联边的顶点为 `0.0`。
+    /// `Direction::All` 与 [`weighted_degree`](Self::weighted_degree) 一致，自环只计一次。O(V + E)。
+    pub fn strength_map(
+        &self,
+        direction: Direction,
+        cost: impl Fn(EdgeId<K>, &Edge) -> f64,
+    ) -> HashMap<VertexId<K>, f64> {
+        let mut strengths: HashMap<VertexId<K>, f64> = self.vertex_ids().map(|v| (v, 0.0)).collect();
+        for (id, weight, info) in self.edges.iter_with_connections() {
+            let value = cost(id, weight);
+            let (from, to) = (info.from(), info.to());
+            if matches!(direction, Direction::Outgoing | Direction::All) {
+                *strengths.entry(from).or_insert(0.0) += value;
+            }
+            if direction == Direction::Incoming || (direction == Direction::All && from != to) {
+                *strengths.entry(to).or_insert(0.0) += value;
+            }
+        }
+        strengths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SlotMapGraph;
+    use graph_api_lib::{Direction, Graph};
+
+    #[test]
+    fn test_metrics_on_small_graph() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let c = graph.add_vertex(());
+        let d = graph.add_vertex(());
+        graph.add_edge(a, b, ());
+        graph.add_edge(b, a, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(c, d, ());
+        graph.add_edge(d, d, ());
+
+        assert_eq!(graph.density(), 5.0 / 12.0);
+        // 度数：a = 2, b = 3, c = 2, d = 2（自环计一次）
+        assert_eq!(graph.average_degree(), 9.0 / 4.0);
+        // 四条非自环边中只有 a <-> b 互为反向
+        assert_eq!(graph.reciprocity(), 0.5);
+        assert_eq!(graph.self_loop_ratio(), 0.2);
+    }
+
+    #[test]
+    fn test_weighted_degrees() {
+        let mut graph: SlotMapGraph<(), f64> = SlotMapGraph::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add_vertex(()));
+        graph.add_edge(a, b, 1.0);
+        graph.add_edge(a, b, 2.0);
+        graph.add_edge(b, c, 4.0);
+        graph.add_edge(c, a, 8.0);
+        graph.add_edge(c, c, 16.0);
+        let cost = |_, weight: &f64| *weight;
+
+        assert_eq!(graph.weighted_out_degree(a, cost), 3.0);
+        assert_eq!(graph.weighted_in_degree(b, cost), 3.0);
+        // 自环同时计入 c 的出向和入向加权度，总和中只计一次
+        assert_eq!(graph.weighted_out_degree(c, cost), 24.0);
+        assert_eq!(graph.weighted_in_degree(c, cost), 20.0);
+        assert_eq!(graph.weighted_degree(c, cost), 28.0);
+
+        for direction in [Direction::Outgoing, Direction::Incoming, Direction::All] {
+            let strengths = graph.strength_map(direction, cost);
+            assert_eq!(strengths.len(), 3);
+            for v in [a, b, c] {
+                let expected = match direction {
+                    Direction::Outgoing => graph.weighted_out_degree(v, cost),
+                    Direction::Incoming => graph.weighted_in_degree(v, cost),
+                    Direction::All => graph.weighted_degree(v, cost),
+                };
+                assert_eq!(strengths[&v], expected);
+            }
+        }
+
+        // 按边 ID 计算代价，例如只统计某一条平行边
+        let first = graph.outgoing_adjacency(a)[0];
+        assert_eq!(graph.weighted_out_degree(a, |id, _| if id == first { 1.0 } else { 0.0 }), 1.0);
+    }
+
+    #[test]
+    fn test_metrics_on_empty_graphs() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let metrics = |g: &SlotMapGraph<(), ()>| [g.density(), g.average_degree(), g.reciprocity(), g.self_loop_ratio()];
+        assert_eq!(metrics(&graph), [0.0; 4]);
+
+        // 单个顶点和只有自环的图同样不产生 NaN
+        let a = graph.add_vertex(());
+        assert_eq!(metrics(&graph), [0.0; 4]);
+        graph.add_edge(a, a, ());
+        assert_eq!(metrics(&graph), [0.0, 1.0, 0.0, 1.0]);
+    }
+}
@@ -0,0 +1,45 @@
+/*!
+ * # 图算法 (Algorithms)
+ *
+ * 本模块以 `SlotMapGraph` 固有方法的形式提供常用的图算法，
+ * 所有算法都只依赖存储层的公共接口，不修改图结构。
+ *
+ * ## 组件
+ *
+ * - [`metrics`]：密度、平均度、互惠率、加权度等网络指标
+ * - [`clustering`]：无向三角形计数与局部/平均聚类系数
+ * - [`components`]：强连通分量（Tarjan 算法，可复用工作区）与弱连通分量
+ * - [`centrality`]：介数中心性（Brandes 算法）与 PageRank，可复用工作区
+ * - [`path`]：以有序边ID表示的路径，可推导顶点序列、校验连通性并累计代价
+ * - [`traversal`]：多源 BFS / Dijkstra、单向与双向 BFS 最短路径、有界 BFS / Dijkstra
+ * - [`shortest_path`]：Dijkstra 点对点最短路径与 Yen 算法的前 k 条无环最短路径
+ * - [`coloring`]：按任意顺序、度数优先或 DSATUR 的贪心图着色
+ * - [`dominators`]：控制流图的支配树、直接支配者与支配边界
+ * - [`euler`]：有向图的欧拉路径与欧拉回路（Hierholzer 算法）
+ * - [`morphism`]：按顶点映射检查两图之间的同态与同构
+ * - [`partition`]：按顶点分配把图拆成多个子图并列出割边，复制顶点集的导出子图，按弱连通分量拆分
+ * - [`reachability`]：后代/祖先闭包查询及按图版本失效的闭包缓存
+ * - [`topo`]：Kahn 拓扑排序、有向环的查找及随边增删局部重排的增量拓扑序（Pearce–Kelly 算法）
+ * - [`sampling`]：顶点/边的均匀与加权随机采样，按边权重采样的稀疏化预览（需启用 `rand` 特性）
+ * - [`walk`]：均匀、加权及带重启的随机游走（需启用 `rand` 特性）
+ */
+
+pub mod centrality;
+pub mod clustering;
+pub mod coloring;
+pub mod components;
+mod dense;
+pub mod dominators;
+pub mod euler;
+pub mod metrics;
+pub mod morphism;
+pub mod partition;
+pub mod path;
+pub mod reachability;
+pub mod shortest_path;
+pub mod topo;
+pub mod traversal;
+#[cfg(feature = "rand")]
+pub mod sampling;
+#[cfg(feature = "rand")]
+pub mod walk;
@@ -0,0 +1,367 @@
+/*!
+ * # 图态射 (Graph Morphism)
+ *
+ * 由一张图生成另一张图（例如合并顶点的优化、重建）之后，用顶点映射验证变换保持了结构：
+ *
+ * - [`is_homomorphism`](SlotMapGraph::is_homomorphism)：每个顶点都有像，
+ *   每条边 `a -> b` 在目标图中都有边 `map[a] -> map[b]`。
+ *   允许多个顶点映射到同一顶点、多条边映射到同一条边（商图）
+ * - [`is_isomorphism`](SlotMapGraph::is_isomorphism)：在此基础上要求映射是双射，
+ *   且两侧的边一一对应：平行边的数量一致，目标图没有多出的边
+ *
+ * 带 `_with` 后缀的版本另外用闭包比较对应顶点和边的数据。
+ * 检查按存储顺序进行，返回遇到的第一个 [`MorphismViolation`]。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use std::collections::HashMap;
+ *
+ * // a -> b -> c 映射到 x -> y，b 与 c 合并
+ * let mut source: SlotMapGraph<(), ()> = SlotMapGraph::new();
+ * let a = source.add_vertex(());
+ * let b = source.add_vertex(());
+ * let c = source.add_vertex(());
+ * source.add_edge(a, b, ());
+ * source.add_edge(b, c, ());
+ *
+ * let mut target: SlotMapGraph<(), ()> = SlotMapGraph::new();
+ * let x = target.add_vertex(());
+ * let y = target.add_vertex(());
+ * target.add_edge(x, y, ());
+ * target.add_edge(y, y, ());
+ *
+ * let map = HashMap::from([(a, x), (b, y), (c, y)]);
+ * assert_eq!(source.is_homomorphism(&target, &map), Ok(()));
+ * assert!(source.is_isomorphism(&target, &map).is_err());
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+
+/// 映射不保持结构的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MorphismViolation<K: Key = DefaultKey> {
+    /// 源图中的顶点没有映射
+    UnmappedVertex(VertexId<K>),
+    /// 顶点的像不在目标图中
+    ImageNotFound {
+        /// 源图中的顶点
+        vertex: VertexId<K>,
+        /// 映射到的顶点
+        image: VertexId<K>,
+    },
+    /// 顶点与其像的数据不匹配
+    VertexMismatch {
+        /// 源图中的顶点
+        vertex: VertexId<K>,
+        /// 映射到的顶点
+        image: VertexId<K>,
+    },
+    /// 源图中的边在目标图中没有对应的边
+    MissingEdgeImage {
+        /// 源图中的边
+        edge: EdgeId<K>,
+        /// 起点的像
+        from: VertexId<K>,
+        /// 终点的像
+        to: VertexId<K>,
+    },
+    /// 两个顶点映射到同一顶点（同构要求单射）
+    NotInjective {
+        /// 先映射到 `image` 的顶点
+        first: VertexId<K>,
+        /// 后映射到 `image` 的顶点
+        second: VertexId<K>,
+        /// 共同的像
+        image: VertexId<K>,
+    },
+    /// 目标图中的顶点不是任何顶点的像（同构要求满射）
+    NotSurjective(VertexId<K>),
+    /// 目标图中的边不对应源图中的任何边（同构要求边一一对应）
+    ExtraEdge(EdgeId<K>),
+}
+
+impl<K: Key> Display for MorphismViolation<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MorphismViolation::UnmappedVertex(vertex) => write!(f, "Vertex {} has no image", vertex),
+            MorphismViolation::ImageNotFound { vertex, image } => {
+                write!(f, "Image {} of vertex {} is not in the target graph", image, vertex)
+            }
+            MorphismViolation::VertexMismatch { vertex, image } => {
+                write!(f, "Vertex {} does not match its image {}", vertex, image)
+            }
+            MorphismViolation::MissingEdgeImage { edge, from, to } => {
+                write!(f, "Edge {} has no image {} -> {} in the target graph", edge, from, to)
+            }
+            MorphismViolation::NotInjective { first, second, image } => {
+                write!(f, "Vertices {} and {} both map to {}", first, second, image)
+            }
+            MorphismViolation::NotSurjective(image) => write!(f, "Target vertex {} is not an image", image),
+            MorphismViolation::ExtraEdge(edge) => write!(f, "Target edge {} has no preimage", edge),
+        }
+    }
+}
+
+impl<K: Key> std::error::Error for MorphismViolation<K> {}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 检查 `map` 是从本图到 `other` 的同态，不比较数据
+    ///
+    /// `map` 中不属于本图顶点的条目被忽略。O(V + E·d)，d 为目标图中起点像的出度。
+    pub fn is_homomorphism<V2, E2>(
+        &self,
+        other: &SlotMapGraph<V2, E2, K>,
+        map: &HashMap<VertexId<K>, VertexId<K>>,
+    ) -> Result<(), MorphismViolation<K>>
+    where
+        V2: Element,
+        E2: Element,
+    {
+        self.is_homomorphism_with(other, map, |_, _| true, |_, _| true)
+    }
+
+    /// 检查 `map` 是从本图到 `other` 的同态，并用 `vertex_match` / `edge_match` 比较对应元素的数据
+    ///
+    /// 边 `a -> b` 只要在 `map[a] -> map[b]` 的平行边中有一条数据匹配即可。
+    pub fn is_homomorphism_with<V2, E2>(
+        &self,
+        other: &SlotMapGraph<V2, E2, K>,
+        map: &HashMap<VertexId<K>, VertexId<K>>,
+        vertex_match: impl Fn(&Vertex, &V2) -> bool,
+        edge_match: impl Fn(&Edge, &E2) -> bool,
+    ) -> Result<(), MorphismViolation<K>>
+    where
+        V2: Element,
+        E2: Element,
+    {
+        self.check_vertex_images(other, map, &vertex_match)?;
+        for (edge, weight, info) in self.edges.iter_with_connections() {
+            let (from, to) = (map[&info.from()], map[&info.to()]);
+            let found = other
+                .edges
+                .edges_between(from, to)
+                .any(|image| other.edges.get(image).is_some_and(|image_weight| edge_match(weight, image_weight)));
+            if !found {
+                return Err(MorphismViolation::MissingEdgeImage { edge, from, to });
+            }
+        }
+        Ok(())
+    }
+
+    /// 检查 `map` 是从本图到 `other` 的同构，不比较数据
+    ///
+    /// 除同态的条件外，要求映射是顶点间的双射，且边一一对应（包括平行边的数量）。
+    pub fn is_isomorphism<V2, E2>(
+        &self,
+        other: &SlotMapGraph<V2, E2, K>,
+        map: &HashMap<VertexId<K>, VertexId<K>>,
+    ) -> Result<(), MorphismViolation<K>>
+    where
+        V2: Element,
+        E2: Element,
+    {
+        self.is_isomorphism_with(other, map, |_, _| true, |_, _| true)
+    }
+
+    /// 检查 `map` 是从本图到 `other` 的同构，并比较对应元素的数据
+    ///
+    /// 平行边按存储顺序贪心配对：每条边取第一条尚未配对且数据匹配的边作为像。
+    /// `edge_match` 是等价关系时贪心配对总能找到存在的一一对应。
+    pub fn is_isomorphism_with<V2, E2>(
+        &self,
+        other: &SlotMapGraph<V2, E2, K>,
+        map: &HashMap<VertexId<K>, VertexId<K>>,
+        vertex_match: impl Fn(&Vertex, &V2) -> bool,
+        edge_match: impl Fn(&Edge, &E2) -> bool,
+    ) -> Result<(), MorphismViolation<K>>
+    where
+        V2: Element,
+        E2: Element,
+    {
+        self.check_vertex_images(other, map, &vertex_match)?;
+
+        let mut preimages: HashMap<VertexId<K>, VertexId<K>> = HashMap::with_capacity(self.vertices.len());
+        for vertex in self.vertices.keys() {
+            let image = map[&vertex];
+            if let Some(&first) = preimages.get(&image) {
+                return Err(MorphismViolation::NotInjective { first, second: vertex, image });
+            }
+            preimages.insert(image, vertex);
+        }
+        if let Some(image) = other.vertices.keys().find(|image| !preimages.contains_key(image)) {
+            return Err(MorphismViolation::NotSurjective(image));
+        }
+
+        let mut used: HashSet<EdgeId<K>> = HashSet::with_capacity(self.edges.len());
+        for (edge, weight, info) in self.edges.iter_with_connections() {
+            let (from, to) = (map[&info.from()], map[&info.to()]);
+            let image = other.edges.edges_between(from, to).find(|image| {
+                !used.contains(image)
+                    && other.edges.get(*image).is_some_and(|image_weight| edge_match(weight, image_weight))
+            });
+            match image {
+                Some(image) => {
+                    used.insert(image);
+                }
+                None => return Err(MorphismViolation::MissingEdgeImage { edge, from, to }),
+            }
+        }
+        match other.edges.keys().find(|edge| !used.contains(edge)) {
+            Some(extra) => Err(MorphismViolation::ExtraEdge(extra)),
+            None => Ok(()),
+        }
+    }
+
+    /// 检查每个顶点都有在 `other` 中且数据匹配的像
+    fn check_vertex_images<V2, E2>(
+        &self,
+        other: &SlotMapGraph<V2, E2, K>,
+        map: &HashMap<VertexId<K>, VertexId<K>>,
+        vertex_match: &impl Fn(&Vertex, &V2) -> bool,
+    ) -> Result<(), MorphismViolation<K>>
+    where
+        V2: Element,
+        E2: Element,
+    {
+        for (vertex, weight) in self.vertices.iter() {
+            let Some(&image) = map.get(&vertex) else {
+                return Err(MorphismViolation::UnmappedVertex(vertex));
+            };
+            let Some(image_weight) = other.vertices.get(image) else {
+                return Err(MorphismViolation::ImageNotFound { vertex, image });
+            };
+            if !vertex_match(weight, image_weight) {
+                return Err(MorphismViolation::VertexMismatch { vertex, image });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+
+    /// 两个模块各含两个函数的调用图，及把函数合并为模块的商图
+    #[test]
+    fn test_quotient_homomorphism() {
+        let mut calls: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let f: Vec<_> = (0..4).map(|i| calls.add_vertex(i / 2)).collect();
+        calls.add_edge(f[0], f[1], ());
+        calls.add_edge(f[1], f[2], ());
+        calls.add_edge(f[0], f[3], ());
+        calls.add_edge(f[3], f[2], ());
+
+        let mut modules: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let m0 = modules.add_vertex(0);
+        let m1 = modules.add_vertex(1);
+        modules.add_edge(m0, m0, ());
+        modules.add_edge(m0, m1, ());
+        modules.add_edge(m1, m1, ());
+
+        let map = HashMap::from([(f[0], m0), (f[1], m0), (f[2], m1), (f[3], m1)]);
+        assert_eq!(calls.is_homomorphism(&modules, &map), Ok(()));
+        assert_eq!(calls.is_homomorphism_with(&modules, &map, |a, b| a == b, |_, _| true), Ok(()));
+        assert_eq!(
+            calls.is_isomorphism(&modules, &map),
+            Err(MorphismViolation::NotInjective { first: f[0], second: f[1], image: m0 })
+        );
+
+        // 数据不一致的映射
+        let swapped = HashMap::from([(f[0], m1), (f[1], m1), (f[2], m0), (f[3], m0)]);
+        assert_eq!(
+            calls.is_homomorphism_with(&modules, &swapped, |a, b| a == b, |_, _| true),
+            Err(MorphismViolation::VertexMismatch { vertex: f[0], image: m1 })
+        );
+    }
+
+    #[test]
+    fn test_missing_edge_image() {
+        let mut source: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let a = source.add_vertex(());
+        let b = source.add_vertex(());
+        let c = source.add_vertex(());
+        source.add_edge(a, b, ());
+        let bc = source.add_edge(b, c, ());
+
+        let mut target: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let x = target.add_vertex(());
+        let y = target.add_vertex(());
+        let z = target.add_vertex(());
+        target.add_edge(x, y, ());
+        // 方向相反，不构成 b -> c 的像
+        target.add_edge(z, y, ());
+
+        let map = HashMap::from([(a, x), (b, y), (c, z)]);
+        let violation = source.is_homomorphism(&target, &map).unwrap_err();
+        assert_eq!(violation, MorphismViolation::MissingEdgeImage { edge: bc, from: y, to: z });
+        assert!(violation.to_string().contains(&bc.to_string()), "{violation}");
+
+        let partial = HashMap::from([(a, x), (b, y)]);
+        assert_eq!(source.is_homomorphism(&target, &partial), Err(MorphismViolation::UnmappedVertex(c)));
+        let mut other: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let foreign = (0..5).map(|_| other.add_vertex(())).last().unwrap();
+        let dangling = HashMap::from([(a, x), (b, y), (c, foreign)]);
+        assert_eq!(
+            source.is_homomorphism(&target, &dangling),
+            Err(MorphismViolation::ImageNotFound { vertex: c, image: foreign })
+        );
+    }
+
+    #[test]
+    fn test_isomorphism_built_in_different_orders() {
+        let count = 4;
+        let edges = [(0, 1, 5), (1, 2, 7), (2, 0, 1), (2, 3, 2), (2, 3, 2), (3, 3, 9)];
+
+        let mut left: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let left_ids: Vec<_> = (0..count as u32).map(|i| left.add_vertex(i)).collect();
+        for &(from, to, weight) in &edges {
+            left.add_edge(left_ids[from], left_ids[to], weight);
+        }
+
+        // 逆序添加顶点和边
+        let mut right: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let mut right_ids = vec![None; count];
+        for i in (0..count).rev() {
+            right_ids[i] = Some(right.add_vertex(i as u32));
+        }
+        let right_ids: Vec<_> = right_ids.into_iter().map(Option::unwrap).collect();
+        for &(from, to, weight) in edges.iter().rev() {
+            right.add_edge(right_ids[from], right_ids[to], weight);
+        }
+
+        let map: HashMap<_, _> = left_ids.iter().copied().zip(right_ids.iter().copied()).collect();
+        assert_eq!(left.is_isomorphism_with(&right, &map, |a, b| a == b, |a, b| a == b), Ok(()));
+
+        // 少一条平行边：正方向仍是同态，反方向有多出的边
+        let parallel = left.find_edge(left_ids[2], left_ids[3]).unwrap();
+        left.remove_edge(parallel);
+        assert_eq!(left.is_homomorphism(&right, &map), Ok(()));
+        assert!(matches!(left.is_isomorphism(&right, &map), Err(MorphismViolation::ExtraEdge(_))));
+        left.add_edge(left_ids[2], left_ids[3], 2);
+
+        // 边数据不同
+        let edge = left.find_edge(left_ids[0], left_ids[1]).unwrap();
+        *left.edge_weight_mut(edge).unwrap() = 6;
+        assert_eq!(left.is_isomorphism(&right, &map), Ok(()));
+        assert_eq!(
+            left.is_isomorphism_with(&right, &map, |a, b| a == b, |a, b| a == b),
+            Err(MorphismViolation::MissingEdgeImage { edge, from: right_ids[0], to: right_ids[1] })
+        );
+    }
+}
@@ -0,0 +1,350 @@
+/*!
+ * # 图划分 (Partitioning)
+ *
+ * 按调用方给出的顶点分配把图拆成 `parts` 个互不相交的子图，例如把图分片到多个工作线程。
+ *
+ * ## 规则
+ *
+ * - 每个顶点恰好复制到其所属分片，分片内的顶点和边都是新的 ID，新旧 ID 的对照保存在结果中
+ * - 两个端点在同一分片的边复制到该分片，包括自环
+ * - 两个端点在不同分片的边为割边，不复制到任何分片，只记录 `(边, 起点分片, 终点分片)`
+ * - 顶点按原图的槽位顺序加入分片，边与割边也按原图的槽位顺序处理
+ *
+ * ## 导出子图
+ *
+ * [`induced_subgraph`](SlotMapGraph::induced_subgraph) 只复制给定的顶点，以及两个端点都在其中的边，
+ * 例如某个包及其全部依赖组成的子图。ID 对照同样保存在结果中。
+ *
+ * ## 按连通分量拆分
+ *
+ * [`split_components`](SlotMapGraph::split_components) 把每个弱连通分量复制为单独的图，
+ * 按分量大小降序排列（大小相同时按首个顶点的存储顺序）。分量之间没有边，
+ * 因此只需遍历一次全部边，不必对每个分量分别求导出子图。
+ */
+
+use crate::error::GraphError;
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, Graph};
+use pi_slotmap::{DefaultKey, Key};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// [`SlotMapGraph::partition`] 的结果
+#[derive(Debug)]
+pub struct PartitionResult<Vertex, Edge, K = DefaultKey>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 各分片的子图
+    pub parts: Vec<SlotMapGraph<Vertex, Edge, K>>,
+    /// 各分片中原顶点 ID 到新顶点 ID 的映射
+    pub vertex_maps: Vec<HashMap<VertexId<K>, VertexId<K>>>,
+    /// 各分片中原边 ID 到新边 ID 的映射
+    pub edge_maps: Vec<HashMap<EdgeId<K>, EdgeId<K>>>,
+    /// 割边：(原边 ID, 起点所在分片, 终点所在分片)
+    pub cut_edges: Vec<(EdgeId<K>, usize, usize)>,
+}
+
+/// [`SlotMapGraph::split_components`] 的一项：分量的图及原顶点 ID 到新顶点 ID 的映射
+pub type ComponentGraph<Vertex, Edge, K = DefaultKey> = (SlotMapGraph<Vertex, Edge, K>, HashMap<VertexId<K>, VertexId<K>>);
+
+/// [`SlotMapGraph::induced_subgraph`] 的结果，开启 `rand` 特性时也是 `sparsify` 的结果
+#[derive(Debug)]
+pub struct InducedSubgraph<Vertex, Edge, K = DefaultKey>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 导出子图
+    pub graph: SlotMapGraph<Vertex, Edge, K>,
+    /// 原顶点 ID 到新顶点 ID 的映射
+    pub vertex_map: HashMap<VertexId<K>, VertexId<K>>,
+    /// 原边 ID 到新边 ID 的映射
+    pub edge_map: HashMap<EdgeId<K>, EdgeId<K>>,
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element + Clone,
+    Edge: Element + Clone,
+    K: Key,
+{
+    /// 按 `assign` 给出的分片编号把图拆成 `parts` 个子图
+    ///
+    /// # 错误
+    ///
+    /// 某个顶点的分片编号不小于 `parts` 时返回 [`GraphError::InvalidPart`]，此时不构建任何子图。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(0);
+    /// let b = graph.add_vertex(1);
+    /// let ab = graph.add_edge(a, b, ());
+    ///
+    /// let result = graph.partition(2, |_, weight| *weight as usize).unwrap();
+    /// assert_eq!(result.parts[0].vertex_count(), 1);
+    /// assert_eq!(result.cut_edges, vec![(ab, 0, 1)]);
+    /// ```
+    pub fn partition(
+        &self,
+        parts: usize,
+        assign: impl Fn(VertexId<K>, &Vertex) -> usize,
+    ) -> Result<PartitionResult<Vertex, Edge, K>, GraphError<K>> {
+        let mut owner = HashMap::with_capacity(self.vertex_count());
+        for (vertex, weight) in self.vertices.iter() {
+            let part = assign(vertex, weight);
+            if part >= parts {
+                return Err(GraphError::InvalidPart { vertex, part, parts });
+            }
+            owner.insert(vertex, part);
+        }
+
+        let mut result = PartitionResult {
+            parts: (0..parts).map(|_| SlotMapGraph::with_key()).collect(),
+            vertex_maps: vec![HashMap::new(); parts],
+            edge_maps: vec![HashMap::new(); parts],
+            cut_edges: Vec::new(),
+        };
+        for (vertex, weight) in self.vertices.iter() {
+            let part = owner[&vertex];
+            let copy = result.parts[part].add_vertex(weight.clone());
+            result.vertex_maps[part].insert(vertex, copy);
+        }
+        for (edge, weight, info) in self.edges.iter_with_connections() {
+            let (from, to) = (owner[&info.from()], owner[&info.to()]);
+            if from != to {
+                result.cut_edges.push((edge, from, to));
+                continue;
+            }
+            let map = &result.vertex_maps[from];
+            let copy = result.parts[from].add_edge(map[&info.from()], map[&info.to()], weight.clone());
+            result.edge_maps[from].insert(edge, copy);
+        }
+        Ok(result)
+    }
+
+    /// 复制 `vertices` 及两个端点都在其中的边组成的子图
+    ///
+    /// 不存在的顶点和重复的顶点被忽略。顶点按 `vertices` 中的顺序加入，
+    /// 边按各顶点出边列表的顺序加入，O(所选顶点数 + 其出度之和)。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(0);
+    /// let b = graph.add_vertex(1);
+    /// let c = graph.add_vertex(2);
+    /// let ab = graph.add_edge(a, b, ());
+    /// graph.add_edge(b, c, ());
+    ///
+    /// let sub = graph.induced_subgraph([a, b]);
+    /// assert_eq!(sub.graph.vertex_count(), 2);
+    /// assert_eq!(sub.graph.edge_count(), 1);
+    /// assert_eq!(sub.graph.edge_endpoints(sub.edge_map[&ab]), Some((sub.vertex_map[&a], sub.vertex_map[&b])));
+    /// ```
+    pub fn induced_subgraph(&self, vertices: impl IntoIterator<Item = VertexId<K>>) -> InducedSubgraph<Vertex, Edge, K> {
+        let mut result = InducedSubgraph {
+            graph: SlotMapGraph::with_key(),
+            vertex_map: HashMap::new(),
+            edge_map: HashMap::new(),
+        };
+        let mut selected = Vec::new();
+        for vertex in vertices {
+            let Some(weight) = self.vertices.get(vertex) else {
+                continue;
+            };
+            if !result.vertex_map.contains_key(&vertex) {
+                result.vertex_map.insert(vertex, result.graph.add_vertex(weight.clone()));
+                selected.push(vertex);
+            }
+        }
+        for vertex in selected {
+            for &edge in self.outgoing_adjacency(vertex) {
+                let Some((weight, info)) = self.edges.get_with_info(edge) else {
+                    continue;
+                };
+                if let Some(&to) = result.vertex_map.get(&info.to()) {
+                    let copy = result.graph.add_edge(result.vertex_map[&vertex], to, weight.clone());
+                    result.edge_map.insert(edge, copy);
+                }
+            }
+        }
+        result
+    }
+
+    /// 把每个弱连通分量复制为单独的图，按顶点数降序排列
+    ///
+    /// 分量内的顶点按原图的存储顺序加入，边按原图的槽位顺序加入，O(V + E)。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(0);
+    /// let b = graph.add_vertex(1);
+    /// let c = graph.add_vertex(2);
+    /// graph.add_edge(c, b, ());
+    ///
+    /// let components = graph.split_components();
+    /// assert_eq!(components.len(), 2);
+    /// assert_eq!(components[0].0.vertex_count(), 2);
+    /// assert_eq!(components[1].0.vertex_weight(components[1].1[&a]), Some(&0));
+    /// ```
+    pub fn split_components(&self) -> Vec<ComponentGraph<Vertex, Edge, K>> {
+        let mut components = self.weakly_connected_components();
+        components.sort_by_key(|members| Reverse(members.len()));
+
+        let mut owner = HashMap::with_capacity(self.vertex_count());
+        let mut result: Vec<ComponentGraph<Vertex, Edge, K>> = Vec::with_capacity(components.len());
+        for (index, members) in components.into_iter().enumerate() {
+            let mut graph = SlotMapGraph::with_key();
+            let mut map = HashMap::with_capacity(members.len());
+            for vertex in members {
+                if let Some(weight) = self.vertices.get(vertex) {
+                    map.insert(vertex, graph.add_vertex(weight.clone()));
+                    owner.insert(vertex, index);
+                }
+            }
+            result.push((graph, map));
+        }
+        for (_, weight, info) in self.edges.iter_with_connections() {
+            let (graph, map) = &mut result[owner[&info.from()]];
+            graph.add_edge(map[&info.from()], map[&info.to()], weight.clone());
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::SupportsElementRemoval;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_two_part_split() {
+        // 两个三角形 {a, b, c} 与 {d, e, f}，由 c -> d 和 e -> a 相连，b 上有自环
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let [a, b, c, d, e, f] = [0, 1, 2, 3, 4, 5].map(|name| graph.add_vertex(name));
+        for (i, (from, to)) in [(a, b), (b, c), (c, a), (d, e), (e, f), (f, d), (b, b)].into_iter().enumerate() {
+            graph.add_edge(from, to, i as u32);
+        }
+        let cd = graph.add_edge(c, d, 7);
+        let ea = graph.add_edge(e, a, 8);
+
+        let result = graph.partition(2, |_, name| usize::from(*name >= 3)).unwrap();
+        assert_eq!(result.cut_edges, vec![(cd, 0, 1), (ea, 1, 0)]);
+
+        for (part, names, weights) in [(0, [0, 1, 2], [0, 1, 2, 6]), (1, [3, 4, 5], [3, 4, 5, 6])] {
+            let sub = &result.parts[part];
+            let mut got: Vec<u32> = sub.all_vertices().map(|(_, name)| *name).collect();
+            got.sort();
+            assert_eq!(got, names);
+            let edges: HashSet<u32> = sub.all_edges().map(|(_, weight, ..)| *weight).collect();
+            let expected: HashSet<u32> = weights.into_iter().filter(|&w| part == 0 || w != 6).collect();
+            assert_eq!(edges, expected);
+            assert_eq!(sub.validate(), Ok(()));
+        }
+
+        // 映射指向子图中对应的元素
+        let sub = &result.parts[0];
+        let (new_b, new_loop) = (result.vertex_maps[0][&b], result.edge_maps[0].values().copied());
+        assert_eq!(sub.vertex_weight(new_b), Some(&1));
+        assert_eq!(new_loop.filter(|id| sub.edge_endpoints(*id) == Some((new_b, new_b))).count(), 1);
+        assert!(!result.vertex_maps[1].contains_key(&a));
+        assert_eq!(result.edge_maps.iter().map(HashMap::len).sum::<usize>(), 7);
+    }
+
+    #[test]
+    fn test_induced_subgraph() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let [a, b, c, d] = [0, 1, 2, 3].map(|name| graph.add_vertex(name));
+        let ab = graph.add_edge(a, b, 0);
+        let bb = graph.add_edge(b, b, 1);
+        graph.add_edge(b, c, 2);
+        let da = graph.add_edge(d, a, 3);
+        let ba = graph.add_edge(b, a, 4);
+        let removed = graph.add_vertex(9);
+        graph.remove_vertex(removed);
+
+        let sub = graph.induced_subgraph([b, a, removed, d, b]);
+        assert_eq!(sub.graph.vertex_count(), 3);
+        assert!(sub.graph.vertex_ids().eq([b, a, d].map(|id| sub.vertex_map[&id])));
+        let mut copied: Vec<_> = sub.edge_map.keys().copied().collect();
+        copied.sort_by_key(|id| id.as_ffi());
+        assert_eq!(copied, vec![ab, bb, da, ba]);
+        for (edge, copy) in &sub.edge_map {
+            let (from, to) = graph.edge_endpoints(*edge).unwrap();
+            assert_eq!(sub.graph.edge_endpoints(*copy), Some((sub.vertex_map[&from], sub.vertex_map[&to])));
+            assert_eq!(sub.graph.edge_weight(*copy), graph.edge_weight(*edge));
+        }
+        assert_eq!(sub.graph.validate(), Ok(()));
+        assert_eq!(graph.induced_subgraph([]).graph.vertex_count(), 0);
+    }
+
+    #[test]
+    fn test_split_components() {
+        // 三个分量交错加入：五个顶点的链（含自环）、三个顶点的环、孤立顶点
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..9).map(|name| graph.add_vertex(name)).collect();
+        let (lonely, ring, chain) = ([ids[0]], [ids[1], ids[4], ids[7]], [ids[2], ids[3], ids[5], ids[6], ids[8]]);
+        for pair in chain.windows(2) {
+            graph.add_edge(pair[1], pair[0], 0);
+        }
+        graph.add_edge(chain[2], chain[2], 1);
+        for i in 0..3 {
+            graph.add_edge(ring[i], ring[(i + 1) % 3], 2);
+        }
+
+        let components = graph.split_components();
+        let shape: Vec<_> = components.iter().map(|(sub, _)| (sub.vertex_count(), sub.edge_count())).collect();
+        assert_eq!(shape, vec![(5, 5), (3, 3), (1, 0)]);
+        for ((sub, map), members) in components.iter().zip([&chain[..], &ring[..], &lonely[..]]) {
+            let mut covered: Vec<_> = map.keys().copied().collect();
+            covered.sort_by_key(|id| id.as_ffi());
+            assert_eq!(covered, members);
+            assert!(sub.vertex_ids().eq(members.iter().map(|id| map[id])));
+            for &vertex in members {
+                assert_eq!(sub.vertex_weight(map[&vertex]), graph.vertex_weight(vertex));
+            }
+            assert_eq!(sub.validate(), Ok(()));
+        }
+        // 映射的并集恰好覆盖每个原顶点一次
+        let mut union: Vec<_> = components.iter().flat_map(|(_, map)| map.keys().copied()).collect();
+        union.sort_by_key(|id| id.as_ffi());
+        assert_eq!(union, ids);
+
+        let (sub, map) = &components[0];
+        let (from, to) = (map[&chain[1]], map[&chain[0]]);
+        assert_eq!(sub.edges_between(from, to).count(), 1);
+        assert!(SlotMapGraph::<u32, u32>::new().split_components().is_empty());
+    }
+
+    #[test]
+    fn test_assignment_out_of_range() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        graph.add_vertex(0);
+        let bad = graph.add_vertex(3);
+        assert_eq!(
+            graph.partition(3, |_, weight| *weight as usize).unwrap_err(),
+            GraphError::InvalidPart { vertex: bad, part: 3, parts: 3 }
+        );
+        assert!(SlotMapGraph::<u32, ()>::new().partition(0, |_, _| 0).unwrap().parts.is_empty());
+    }
+}
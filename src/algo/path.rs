@@ -0,0 +1,185 @@
+/*!
+ * # 路径 (Path)
+ *
+ * 以有序的边ID列表表示一条路径，按需从图中推导顶点序列、校验连通性和累计代价，
+ * 调用方不必在 `Vec<VertexId>` 与 `Vec<EdgeId>` 两种表示之间手工转换。
+ *
+ * ## 约定
+ *
+ * - 路径方向与边方向一致：第 i 条边的终点是第 i+1 条边的起点
+ * - 空路径不含任何顶点，并视为有效
+ * - 路径只保存ID，图修改后需要重新调用 [`Path::is_valid`] 确认路径仍然成立
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+
+/// 由有序边ID组成的路径
+///
+/// # 示例
+///
+/// ```rust
+/// use graph_api_lib::Graph;
+/// use pi_slotmap_graph::SlotMapGraph;
+/// use pi_slotmap_graph::algo::path::Path;
+///
+/// let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+/// let a = graph.add_vertex(());
+/// let b = graph.add_vertex(());
+/// let c = graph.add_vertex(());
+/// let ab = graph.add_edge(a, b, 3);
+/// let bc = graph.add_edge(b, c, 4);
+///
+/// let path = Path::new(vec![ab, bc]);
+/// assert!(path.is_valid(&graph));
+/// assert_eq!(path.vertices(&graph), vec![a, b, c]);
+/// assert_eq!(path.total_cost(&graph, |weight| *weight), 7);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Path<K: Key = DefaultKey> {
+    /// 按经过顺序排列的边
+    edges: Vec<EdgeId<K>>,
+}
+
+impl<K: Key> Path<K> {
+    /// 由有序边ID创建路径
+    pub fn new(edges: Vec<EdgeId<K>>) -> Self {
+        Self { edges }
+    }
+
+    /// 路径上的边
+    pub fn edges(&self) -> &[EdgeId<K>] {
+        &self.edges
+    }
+
+    /// 路径上的边数
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// 路径是否不含任何边
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// 边顺序反转后的路径
+    ///
+    /// 只反转经过顺序，不改变边本身的方向，
+    /// 因此有向图上的结果通常不满足 [`is_valid`](Self::is_valid)，适用于按无向语义回溯路径。
+    pub fn reversed(&self) -> Self {
+        Self {
+            edges: self.edges.iter().rev().copied().collect(),
+        }
+    }
+
+    /// 路径经过的顶点：第一条边的起点，以及每条边的终点
+    ///
+    /// 遇到图中不存在的边时在此处截断。
+    pub fn vertices<Vertex, Edge>(&self, graph: &SlotMapGraph<Vertex, Edge, K>) -> Vec<VertexId<K>>
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        let mut vertices = Vec::with_capacity(self.edges.len() + 1);
+        for edge_id in &self.edges {
+            let Some(info) = graph.edges.get_connection(*edge_id) else {
+                break;
+            };
+            if vertices.is_empty() {
+                vertices.push(info.from());
+            }
+            vertices.push(info.to());
+        }
+        vertices
+    }
+
+    /// 路径是否仍然成立：所有边都存在，且相邻两条边首尾相接
+    pub fn is_valid<Vertex, Edge>(&self, graph: &SlotMapGraph<Vertex, Edge, K>) -> bool
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        let mut previous_to = None;
+        for edge_id in &self.edges {
+            let Some(info) = graph.edges.get_connection(*edge_id) else {
+                return false;
+            };
+            if previous_to.is_some_and(|to| to != info.from()) {
+                return false;
+            }
+            previous_to = Some(info.to());
+        }
+        true
+    }
+
+    /// 按 `cost` 累计路径上每条边的代价
+    ///
+    /// 图中不存在的边不计入，结果在溢出时饱和为 `u64::MAX`。
+    pub fn total_cost<Vertex, Edge, F>(&self, graph: &SlotMapGraph<Vertex, Edge, K>, cost: F) -> u64
+    where
+        Vertex: Element,
+        Edge: Element,
+        F: Fn(&Edge) -> u64,
+    {
+        self.edges
+            .iter()
+            .filter_map(|edge_id| graph.edge_weight(*edge_id))
+            .fold(0u64, |total, weight| total.saturating_add(cost(weight)))
+    }
+}
+
+impl<K: Key> From<Vec<EdgeId<K>>> for Path<K> {
+    fn from(edges: Vec<EdgeId<K>>) -> Self {
+        Self::new(edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+
+    #[test]
+    fn test_three_edge_path() {
+        let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        let edges: Vec<_> = ids.windows(2).zip([2, 5, 11]).map(|(pair, cost)| graph.add_edge(pair[0], pair[1], cost)).collect();
+
+        let path = Path::from(edges.clone());
+        assert_eq!(path.len(), 3);
+        assert!(path.is_valid(&graph));
+        assert_eq!(path.vertices(&graph), ids);
+        assert_eq!(path.total_cost(&graph, |cost| *cost), 18);
+        assert_eq!(path.total_cost(&graph, |_| u64::MAX), u64::MAX);
+
+        // 有向边反转顺序后不再首尾相接
+        let reversed = path.reversed();
+        assert_eq!(reversed.edges().first(), edges.last());
+        assert!(!reversed.is_valid(&graph));
+
+        assert!(Path::<DefaultKey>::default().is_valid(&graph));
+        assert!(Path::<DefaultKey>::default().vertices(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_path_invalid_after_edge_removed() {
+        let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let c = graph.add_vertex(());
+        let ab = graph.add_edge(a, b, 1);
+        let bc = graph.add_edge(b, c, 1);
+        let path = Path::new(vec![ab, bc]);
+
+        graph.remove_edge(bc);
+        assert!(!path.is_valid(&graph));
+        assert_eq!(path.vertices(&graph), vec![a, b]);
+        assert_eq!(path.total_cost(&graph, |cost| *cost), 1);
+
+        // 首尾不相接的路径无效
+        let ca = graph.add_edge(c, a, 1);
+        assert!(!Path::new(vec![ab, ca]).is_valid(&graph));
+    }
+}
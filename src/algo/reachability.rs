@@ -0,0 +1,270 @@
+/*!
+ * # 可达性 (Reachability)
+ *
+ * 查询顶点的全部后代（沿出边可达的顶点）和全部祖先（沿入边可达的顶点），
+ * 典型用途是依赖 DAG 上的"X 的全部传递依赖"和"依赖 X 的全部顶点"。
+ *
+ * ## 结果约定
+ *
+ * 结果不包含查询顶点自身，即使它位于环上；查询顶点不存在时返回空集合。
+ *
+ * ## 缓存
+ *
+ * [`ReachabilityCache`] 记录每个已查询顶点的闭包。计算新顶点的闭包时，
+ * 遇到已缓存的顶点直接并入其闭包而不再展开，因此重复和相邻的查询都能摊还开销。
+ * 缓存记录图的 [`version`](SlotMapGraph::version)，图结构改变后的第一次查询会清空缓存；
+ * 只沿可见图层中的边扩展，修改边的图层或可见图层后同样清空。
+ * 一个缓存只应用于同一张图。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::{Direction, Element};
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::{HashMap, HashSet};
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 沿出边可达的全部顶点
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let app = graph.add_vertex(());
+    /// let lib = graph.add_vertex(());
+    /// let core = graph.add_vertex(());
+    /// graph.add_edge(app, lib, ());
+    /// graph.add_edge(lib, core, ());
+    ///
+    /// assert_eq!(graph.descendants(app).len(), 2);
+    /// assert!(graph.ancestors(core).contains(&app));
+    /// ```
+    pub fn descendants(&self, vertex_id: VertexId<K>) -> HashSet<VertexId<K>> {
+        self.closure(vertex_id, Direction::Outgoing, &HashMap::new())
+    }
+
+    /// 沿入边可达的全部顶点
+    pub fn ancestors(&self, vertex_id: VertexId<K>) -> HashSet<VertexId<K>> {
+        self.closure(vertex_id, Direction::Incoming, &HashMap::new())
+    }
+
+    /// 计算 `vertex_id` 在 `direction` 方向上的闭包，`known` 中已有闭包的顶点不再展开
+    fn closure(
+        &self,
+        vertex_id: VertexId<K>,
+        direction: Direction,
+        known: &HashMap<VertexId<K>, HashSet<VertexId<K>>>,
+    ) -> HashSet<VertexId<K>> {
+        let mut reached = HashSet::new();
+        if !self.vertices.contains(vertex_id) {
+            return reached;
+        }
+
+        let mut stack = vec![vertex_id];
+        while let Some(current) = stack.pop() {
+            for neighbor in self.neighbors_in(current, direction) {
+                if !reached.insert(neighbor) {
+                    continue;
+                }
+                match known.get(&neighbor) {
+                    Some(closure) => reached.extend(closure.iter().copied()),
+                    None => stack.push(neighbor),
+                }
+            }
+        }
+        reached.remove(&vertex_id);
+        reached
+    }
+
+    /// `direction` 方向上的直接邻居
+    fn neighbors_in(&self, vertex_id: VertexId<K>, direction: Direction) -> impl Iterator<Item = VertexId<K>> + '_ {
+        let edges = match direction {
+            Direction::Incoming => self.incoming_adjacency(vertex_id),
+            _ => self.outgoing_adjacency(vertex_id),
+        };
+        edges.iter().filter(|edge_id| self.edge_visible(**edge_id)).filter_map(move |edge_id| {
+            let (from, to) = self.edges.endpoints(*edge_id)?;
+            Some(if direction == Direction::Incoming { from } else { to })
+        })
+    }
+}
+
+/// 按顶点缓存后代与祖先闭包
+///
+/// # 示例
+///
+/// ```rust
+/// use graph_api_lib::Graph;
+/// use pi_slotmap_graph::SlotMapGraph;
+/// use pi_slotmap_graph::algo::reachability::ReachabilityCache;
+///
+/// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+/// let a = graph.add_vertex(());
+/// let b = graph.add_vertex(());
+/// graph.add_edge(a, b, ());
+///
+/// let mut cache = ReachabilityCache::new();
+/// assert_eq!(cache.descendants(&graph, a).len(), 1);
+///
+/// // 图结构改变后缓存自动失效
+/// let c = graph.add_vertex(());
+/// graph.add_edge(b, c, ());
+/// assert_eq!(cache.descendants(&graph, a).len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReachabilityCache<K: Key = DefaultKey> {
+    /// 缓存内容对应的图版本号和图层修改次数
+    version: Option<(u64, u64)>,
+    /// 已计算的后代闭包
+    descendants: HashMap<VertexId<K>, HashSet<VertexId<K>>>,
+    /// 已计算的祖先闭包
+    ancestors: HashMap<VertexId<K>, HashSet<VertexId<K>>>,
+}
+
+impl<K: Key> Default for ReachabilityCache<K> {
+    fn default() -> Self {
+        Self {
+            version: None,
+            descendants: HashMap::new(),
+            ancestors: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Key> ReachabilityCache<K> {
+    /// 创建空缓存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `vertex_id` 的后代，未缓存时计算并记录
+    pub fn descendants<Vertex, Edge>(
+        &mut self,
+        graph: &SlotMapGraph<Vertex, Edge, K>,
+        vertex_id: VertexId<K>,
+    ) -> &HashSet<VertexId<K>>
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        self.sync(graph);
+        Self::lookup(&mut self.descendants, graph, vertex_id, Direction::Outgoing)
+    }
+
+    /// `vertex_id` 的祖先，未缓存时计算并记录
+    pub fn ancestors<Vertex, Edge>(
+        &mut self,
+        graph: &SlotMapGraph<Vertex, Edge, K>,
+        vertex_id: VertexId<K>,
+    ) -> &HashSet<VertexId<K>>
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        self.sync(graph);
+        Self::lookup(&mut self.ancestors, graph, vertex_id, Direction::Incoming)
+    }
+
+    /// 已缓存的闭包数量
+    pub fn len(&self) -> usize {
+        self.descendants.len() + self.ancestors.len()
+    }
+
+    /// 是否没有缓存任何闭包
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空缓存
+    pub fn clear(&mut self) {
+        self.version = None;
+        self.descendants.clear();
+        self.ancestors.clear();
+    }
+
+    /// 图版本变化时丢弃全部缓存
+    fn sync<Vertex, Edge>(&mut self, graph: &SlotMapGraph<Vertex, Edge, K>)
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        let version = (graph.version(), graph.layers.generation());
+        if self.version != Some(version) {
+            self.clear();
+            self.version = Some(version);
+        }
+    }
+
+    fn lookup<'a, Vertex, Edge>(
+        closures: &'a mut HashMap<VertexId<K>, HashSet<VertexId<K>>>,
+        graph: &SlotMapGraph<Vertex, Edge, K>,
+        vertex_id: VertexId<K>,
+        direction: Direction,
+    ) -> &'a HashSet<VertexId<K>>
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        if !closures.contains_key(&vertex_id) {
+            let closure = graph.closure(vertex_id, direction, closures);
+            closures.insert(vertex_id, closure);
+        }
+        &closures[&vertex_id]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::Graph;
+
+    /// 菱形 DAG：top -> left, top -> right, left -> bottom, right -> bottom
+    fn diamond() -> (SlotMapGraph<(), ()>, [VertexId; 4]) {
+        let mut graph = SlotMapGraph::new();
+        let [top, left, right, bottom] = [(); 4].map(|_| graph.add_vertex(()));
+        graph.add_edge(top, left, ());
+        graph.add_edge(top, right, ());
+        graph.add_edge(left, bottom, ());
+        graph.add_edge(right, bottom, ());
+        (graph, [top, left, right, bottom])
+    }
+
+    #[test]
+    fn test_diamond_closures() {
+        let (graph, [top, left, right, bottom]) = diamond();
+
+        assert_eq!(graph.descendants(top), HashSet::from([left, right, bottom]));
+        assert_eq!(graph.descendants(left), HashSet::from([bottom]));
+        assert!(graph.descendants(bottom).is_empty());
+        assert_eq!(graph.ancestors(bottom), HashSet::from([top, left, right]));
+        assert!(graph.ancestors(top).is_empty());
+    }
+
+    #[test]
+    fn test_cache_hits_and_invalidation() {
+        let (mut graph, [top, left, right, bottom]) = diamond();
+        let mut cache = ReachabilityCache::new();
+
+        // 先缓存 left 的闭包，计算 top 时直接复用
+        assert_eq!(cache.descendants(&graph, left), &HashSet::from([bottom]));
+        let first = cache.descendants(&graph, top).clone();
+        assert_eq!(first, graph.descendants(top));
+        assert_eq!(cache.descendants(&graph, top), &first);
+        assert_eq!(cache.ancestors(&graph, bottom), &graph.ancestors(bottom));
+        assert_eq!(cache.len(), 3);
+
+        let leaf = graph.add_vertex(());
+        graph.add_edge(bottom, leaf, ());
+        assert_eq!(cache.descendants(&graph, top), &HashSet::from([left, right, bottom, leaf]));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.ancestors(&graph, leaf).contains(&top));
+    }
+}
@@ -0,0 +1,537 @@
+/*!
+ * # 随机采样 (Random Sampling)
+ *
+ * 为蒙特卡洛类分析提供顶点和边的随机采样。
+ *
+ * ## 算法
+ *
+ * - **单个元素**: 均匀采样先按 `len` 取随机下标；加权采样单遍累积权重
+ * - **无放回采样**: 水塘抽样（Algorithm R），单遍扫描键，O(n) 时间、O(k) 空间
+ * - **加权无放回采样**: Efraimidis-Spirakis（A-Res），键值 `u^(1/w)` 取前 k 大
+ *
+ * 权重函数返回非正数、NaN 或无穷大的元素不会被加权采样选中。
+ *
+ * ## 稀疏化
+ *
+ * [`sparsify`](SlotMapGraph::sparsify) 按权重无放回地采样 `k` 条边，
+ * 把这些边及其端点复制为新图，用于生成大图的预览。结果中保存新旧 ID 的对照。
+ *
+ * ## 乱序重建
+ *
+ * [`rebuilt_shuffled`](SlotMapGraph::rebuilt_shuffled) 以随机顺序重新插入全部顶点和边，
+ * 打乱槽位布局和邻接表顺序，用于在基准测试中测量插入顺序带来的局部性之外的最差缓存表现。
+ */
+
+use crate::algo::partition::InducedSubgraph;
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, Graph};
+use pi_slotmap::Key;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 均匀随机选取一个顶点
+    ///
+    /// 图为空时返回 `None`。
+    pub fn random_vertex<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<VertexId<K>> {
+        let len = self.vertex_count();
+        if len == 0 {
+            return None;
+        }
+        let index = rng.random_range(0..len);
+        self.vertex_ids().nth(index)
+    }
+
+    /// 均匀随机选取一条边
+    ///
+    /// 图中没有边时返回 `None`。
+    pub fn random_edge<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<EdgeId<K>> {
+        let len = self.edge_count();
+        if len == 0 {
+            return None;
+        }
+        let index = rng.random_range(0..len);
+        self.edge_ids().nth(index)
+    }
+
+    /// 无放回地均匀采样 `k` 个顶点
+    ///
+    /// 使用水塘抽样单遍扫描所有顶点；`k` 大于顶点数时返回全部顶点。
+    /// 结果顺序不保证。
+    pub fn sample_vertices<R: Rng + ?Sized>(&self, k: usize, rng: &mut R) -> Vec<VertexId<K>> {
+        reservoir_sample(self.vertex_ids(), k, rng)
+    }
+
+    /// 无放回地均匀采样 `k` 条边
+    ///
+    /// 语义同 [`sample_vertices`](Self::sample_vertices)。
+    pub fn sample_edges<R: Rng + ?Sized>(&self, k: usize, rng: &mut R) -> Vec<EdgeId<K>> {
+        reservoir_sample(self.edge_ids(), k, rng)
+    }
+
+    /// 按权重随机选取一个顶点
+    ///
+    /// 每个顶点被选中的概率与 `weight(&vertex)` 成正比。
+    /// 没有正权重顶点时返回 `None`。
+    pub fn random_vertex_weighted<R, F>(&self, rng: &mut R, weight: F) -> Option<VertexId<K>>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Vertex) -> f64,
+    {
+        weighted_pick(self.all_vertices().map(|(id, v)| (id, weight(v))), rng)
+    }
+
+    /// 按权重随机选取一条边
+    ///
+    /// 语义同 [`random_vertex_weighted`](Self::random_vertex_weighted)。
+    pub fn random_edge_weighted<R, F>(&self, rng: &mut R, weight: F) -> Option<EdgeId<K>>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Edge) -> f64,
+    {
+        weighted_pick(self.all_edges().map(|(id, e, ..)| (id, weight(e))), rng)
+    }
+
+    /// 按权重无放回地采样 `k` 个顶点
+    ///
+    /// 只有正权重的顶点参与采样，因此结果可能少于 `k` 个。
+    pub fn sample_vertices_weighted<R, F>(&self, k: usize, rng: &mut R, weight: F) -> Vec<VertexId<K>>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Vertex) -> f64,
+    {
+        weighted_reservoir_sample(self.all_vertices().map(|(id, v)| (id, weight(v))), k, rng)
+    }
+
+    /// 按权重无放回地采样 `k` 条边，权重由 `weight(边 ID, &边数据)` 给出
+    ///
+    /// 单遍扫描全部边，其余语义同 [`sample_vertices_weighted`](Self::sample_vertices_weighted)。
+    pub fn sample_edges_weighted<R, F>(&self, k: usize, weight: F, rng: &mut R) -> Vec<EdgeId<K>>
+    where
+        R: Rng + ?Sized,
+        F: Fn(EdgeId<K>, &Edge) -> f64,
+    {
+        weighted_reservoir_sample(self.all_edges().map(|(id, e, ..)| (id, weight(id, e))), k, rng)
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element + Clone,
+    Edge: Element + Clone,
+    K: Key,
+{
+    /// 按权重采样 `k` 条边，复制这些边及其端点组成的子图
+    ///
+    /// 采样方式同 [`sample_edges_weighted`](Self::sample_edges_weighted)，`k` 不小于边数时复制全部正权重的边。
+    /// 未被采样的边即使两个端点都在子图中也不会复制；没有被采样边关联的顶点不会复制。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(0);
+    /// let b = graph.add_vertex(1);
+    /// let c = graph.add_vertex(2);
+    /// let ab = graph.add_edge(a, b, 1);
+    /// graph.add_edge(b, c, 0);
+    ///
+    /// let preview = graph.sparsify(10, |_, weight| *weight as f64, &mut StdRng::seed_from_u64(1));
+    /// assert_eq!(preview.graph.edge_count(), 1);
+    /// assert_eq!(preview.graph.vertex_count(), 2);
+    /// assert!(preview.edge_map.contains_key(&ab));
+    /// ```
+    pub fn sparsify<R, F>(&self, k: usize, weight: F, rng: &mut R) -> InducedSubgraph<Vertex, Edge, K>
+    where
+        R: Rng + ?Sized,
+        F: Fn(EdgeId<K>, &Edge) -> f64,
+    {
+        let sampled = self.sample_edges_weighted(k, weight, rng);
+        let mut result = InducedSubgraph {
+            graph: SlotMapGraph::with_key(),
+            vertex_map: HashMap::new(),
+            edge_map: HashMap::with_capacity(sampled.len()),
+        };
+        for edge in sampled {
+            let Some((weight, info)) = self.edges.get_with_info(edge) else {
+                continue;
+            };
+            let mut copy_vertex = |vertex: VertexId<K>| {
+                *result
+                    .vertex_map
+                    .entry(vertex)
+                    .or_insert_with(|| result.graph.add_vertex(self.vertices.get(vertex).cloned().expect("edge endpoint exists")))
+            };
+            let (from, to) = (copy_vertex(info.from()), copy_vertex(info.to()));
+            let copy = result.graph.add_edge(from, to, weight.clone());
+            result.edge_map.insert(edge, copy);
+        }
+        result
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element + Clone,
+    Edge: Element + Clone,
+    K: Key,
+{
+    /// 以随机顺序重新插入全部顶点和边，返回新图和旧顶点 ID 到新顶点 ID 的映射
+    ///
+    /// 新图的槽位顺序即顶点和边的插入顺序，各顶点出边、入边列表的顺序也随之打乱。
+    /// 只复制顶点和边的数据，可选索引、图层、批次标签等设置不会复制。
+    /// 同一个种子的随机数生成器总是得到相同的布局。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(1);
+    /// let b = graph.add_vertex(2);
+    /// graph.add_edge(a, b, ());
+    ///
+    /// let (shuffled, map) = graph.rebuilt_shuffled(&mut StdRng::seed_from_u64(7));
+    /// assert_eq!(shuffled.vertex_count(), 2);
+    /// assert!(shuffled.has_edge(map[&a], map[&b]));
+    /// ```
+    pub fn rebuilt_shuffled<R: Rng + ?Sized>(&self, rng: &mut R) -> (SlotMapGraph<Vertex, Edge, K>, HashMap<VertexId<K>, VertexId<K>>) {
+        let mut vertices: Vec<_> = self.vertex_ids().collect();
+        let mut edges: Vec<_> = self.edge_ids().collect();
+        vertices.shuffle(rng);
+        edges.shuffle(rng);
+
+        let mut graph = SlotMapGraph::with_capacity_and_key(vertices.len(), edges.len());
+        let mut vertex_map = HashMap::with_capacity(vertices.len());
+        for vertex in vertices {
+            let weight = self.vertices.get(vertex).cloned().expect("listed vertex exists");
+            vertex_map.insert(vertex, graph.add_vertex(weight));
+        }
+        for edge in edges {
+            let (weight, info) = self.edges.get_with_info(edge).expect("listed edge exists");
+            graph.add_edge(vertex_map[&info.from()], vertex_map[&info.to()], weight.clone());
+        }
+        (graph, vertex_map)
+    }
+}
+
+/// 权重是否可参与加权采样
+#[inline]
+fn usable_weight(w: f64) -> bool {
+    w.is_finite() && w > 0.0
+}
+
+/// 水塘抽样（Algorithm R）
+fn reservoir_sample<T, I, R>(iter: I, k: usize, rng: &mut R) -> Vec<T>
+where
+    I: Iterator<Item = T>,
+    R: Rng + ?Sized,
+{
+    let mut reservoir = Vec::with_capacity(k.min(iter.size_hint().0));
+    if k == 0 {
+        return reservoir;
+    }
+    for (seen, item) in iter.enumerate() {
+        if seen < k {
+            reservoir.push(item);
+        } else {
+            let j = rng.random_range(0..=seen);
+            if j < k {
+                reservoir[j] = item;
+            }
+        }
+    }
+    reservoir
+}
+
+/// 单遍加权选取：第 i 个元素以 `w_i / Σw` 的概率替换当前结果
+pub(super) fn weighted_pick<T, I, R>(iter: I, rng: &mut R) -> Option<T>
+where
+    I: Iterator<Item = (T, f64)>,
+    R: Rng + ?Sized,
+{
+    let mut total = 0.0;
+    let mut chosen = None;
+    for (item, w) in iter {
+        if !usable_weight(w) {
+            continue;
+        }
+        total += w;
+        if rng.random::<f64>() * total < w {
+            chosen = Some(item);
+        }
+    }
+    chosen
+}
+
+/// 加权水塘抽样的堆元素，按键值排序
+struct Keyed<T> {
+    key: f64,
+    item: T,
+}
+
+impl<T> PartialEq for Keyed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T> Eq for Keyed<T> {}
+
+impl<T> PartialOrd for Keyed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Keyed<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.total_cmp(&other.key)
+    }
+}
+
+/// Efraimidis-Spirakis 加权无放回采样
+fn weighted_reservoir_sample<T, I, R>(iter: I, k: usize, rng: &mut R) -> Vec<T>
+where
+    I: Iterator<Item = (T, f64)>,
+    R: Rng + ?Sized,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+    // 最小堆，堆顶是当前保留元素中键值最小的
+    let mut heap: BinaryHeap<Reverse<Keyed<T>>> = BinaryHeap::with_capacity(k);
+    for (item, w) in iter {
+        if !usable_weight(w) {
+            continue;
+        }
+        let key = rng.random::<f64>().powf(1.0 / w);
+        if heap.len() < k {
+            heap.push(Reverse(Keyed { key, item }));
+        } else if heap.peek().is_some_and(|min| key > min.0.key) {
+            heap.pop();
+            heap.push(Reverse(Keyed { key, item }));
+        }
+    }
+    heap.into_iter().map(|Reverse(keyed)| keyed.item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SlotMapGraph;
+    use graph_api_lib::{EdgeReference, Graph, SupportsElementRemoval, VertexReference};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    fn build_graph() -> SlotMapGraph<u32, u32> {
+        let mut graph = SlotMapGraph::new();
+        let ids: Vec<_> = (0..4).map(|i| graph.add_vertex(i)).collect();
+        graph.add_edge(ids[0], ids[1], 1);
+        graph.add_edge(ids[1], ids[2], 2);
+        graph.add_edge(ids[2], ids[3], 3);
+        graph
+    }
+
+    #[test]
+    fn test_sampling_deterministic_with_seed() {
+        let graph = build_graph();
+
+        let mut rng1 = StdRng::seed_from_u64(42);
+        let mut rng2 = StdRng::seed_from_u64(42);
+
+        let a: Vec<_> = (0..10).map(|_| graph.random_vertex(&mut rng1)).collect();
+        let b: Vec<_> = (0..10).map(|_| graph.random_vertex(&mut rng2)).collect();
+        assert_eq!(a, b);
+
+        assert_eq!(graph.sample_vertices(2, &mut rng1), graph.sample_vertices(2, &mut rng2));
+        assert_eq!(graph.random_edge(&mut rng1), graph.random_edge(&mut rng2));
+    }
+
+    #[test]
+    fn test_uniform_sampling_hits_every_element() {
+        let graph = build_graph();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let vertices: HashSet<_> = (0..200).filter_map(|_| graph.random_vertex(&mut rng)).collect();
+        assert_eq!(vertices.len(), graph.vertex_count());
+
+        let edges: HashSet<_> = (0..200).filter_map(|_| graph.random_edge(&mut rng)).collect();
+        assert_eq!(edges.len(), graph.edge_count());
+
+        let sampled: HashSet<_> = (0..200)
+            .flat_map(|_| graph.sample_vertices(1, &mut rng))
+            .collect();
+        assert_eq!(sampled.len(), graph.vertex_count());
+    }
+
+    #[test]
+    fn test_sample_more_than_available() {
+        let graph = build_graph();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let all: HashSet<_> = graph.sample_vertices(100, &mut rng).into_iter().collect();
+        let expected: HashSet<_> = graph.all_vertices().map(|(id, _)| id).collect();
+        assert_eq!(all, expected);
+
+        assert_eq!(graph.sample_edges(100, &mut rng).len(), graph.edge_count());
+        assert!(graph.sample_vertices(0, &mut rng).is_empty());
+
+        // 无放回：不会出现重复
+        let two = graph.sample_vertices(2, &mut rng);
+        assert_eq!(two.len(), 2);
+        assert_ne!(two[0], two[1]);
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert!(graph.random_vertex(&mut rng).is_none());
+        assert!(graph.random_edge(&mut rng).is_none());
+        assert!(graph.sample_vertices(3, &mut rng).is_empty());
+        assert!(graph.random_vertex_weighted(&mut rng, |_| 1.0).is_none());
+    }
+
+    #[test]
+    fn test_weighted_sampling() {
+        let graph = build_graph();
+        let mut rng = StdRng::seed_from_u64(3);
+
+        // 只有权重为正的顶点会被选中
+        for _ in 0..50 {
+            let id = graph.random_vertex_weighted(&mut rng, |v| if *v == 2 { 1.0 } else { 0.0 }).unwrap();
+            assert_eq!(*graph.vertex(id).unwrap().weight(), 2);
+        }
+        assert!(graph.random_vertex_weighted(&mut rng, |_| 0.0).is_none());
+        assert!(graph.random_edge_weighted(&mut rng, |_| f64::NAN).is_none());
+
+        // 重权重元素应当占绝大多数
+        let mut heavy = 0;
+        for _ in 0..500 {
+            let id = graph.random_edge_weighted(&mut rng, |e| if *e == 3 { 100.0 } else { 1.0 }).unwrap();
+            if *graph.edge(id).unwrap().weight() == 3 {
+                heavy += 1;
+            }
+        }
+        assert!(heavy > 400);
+
+        // 加权无放回采样
+        let picked = graph.sample_vertices_weighted(2, &mut rng, |v| *v as f64);
+        assert_eq!(picked.len(), 2);
+        assert!(picked.iter().all(|id| *graph.vertex(*id).unwrap().weight() != 0));
+
+        let picked = graph.sample_edges_weighted(10, |_, _| 1.0, &mut rng);
+        assert_eq!(picked.len(), graph.edge_count());
+    }
+
+    #[test]
+    fn test_sample_edges_weighted_deterministic_with_seed() {
+        let graph = build_graph();
+        let sample = |seed| graph.sample_edges_weighted(2, |_, e| *e as f64, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(sample(9), sample(9));
+        assert_eq!(sample(9).len(), 2);
+
+        let a = graph.sparsify(2, |_, e| *e as f64, &mut StdRng::seed_from_u64(9));
+        let b = graph.sparsify(2, |_, e| *e as f64, &mut StdRng::seed_from_u64(9));
+        assert_eq!(a.edge_map, b.edge_map);
+        assert_eq!(a.vertex_map, b.vertex_map);
+    }
+
+    #[test]
+    fn test_sparsify_all_and_zero_weight_edges() {
+        let graph = build_graph();
+        let mut rng = StdRng::seed_from_u64(5);
+
+        // k 不小于边数时复制全部边
+        let all = graph.sparsify(graph.edge_count(), |_, _| 1.0, &mut rng);
+        assert_eq!(all.graph.edge_count(), graph.edge_count());
+        assert_eq!(all.graph.vertex_count(), graph.vertex_count());
+        for (old, new) in &all.edge_map {
+            let (from, to) = graph.edge_endpoints(*old).unwrap();
+            assert_eq!(all.graph.edge_endpoints(*new), Some((all.vertex_map[&from], all.vertex_map[&to])));
+            assert_eq!(all.graph.edge(*new).unwrap().weight(), graph.edge(*old).unwrap().weight());
+        }
+
+        // 权重为零的边从不被采样，只有其端点的顶点也不会复制
+        for _ in 0..100 {
+            let sampled = graph.sample_edges_weighted(3, |_, e| if *e == 2 { 0.0 } else { 1.0 }, &mut rng);
+            assert_eq!(sampled.len(), 2);
+            assert!(sampled.iter().all(|id| *graph.edge(*id).unwrap().weight() != 2));
+        }
+        let preview = graph.sparsify(10, |_, e| if *e == 1 { 1.0 } else { 0.0 }, &mut rng);
+        assert_eq!((preview.graph.vertex_count(), preview.graph.edge_count()), (2, 1));
+        assert!(graph.sparsify(10, |_, _| 0.0, &mut rng).graph.vertex_count() == 0);
+
+        // 权重也可以按边 ID 给出
+        let target = graph.edge_ids().next().unwrap();
+        assert_eq!(graph.sample_edges_weighted(10, |id, _| if id == target { 1.0 } else { 0.0 }, &mut rng), vec![target]);
+    }
+
+    #[test]
+    fn test_sample_edges_weighted_prefers_heavy_edges() {
+        let graph = build_graph();
+        let mut counts = [0; 4];
+        for seed in 0..2000 {
+            for id in graph.sample_edges_weighted(1, |_, e| (*e * *e) as f64, &mut StdRng::seed_from_u64(seed)) {
+                counts[*graph.edge(id).unwrap().weight() as usize] += 1;
+            }
+        }
+        // 权重 1 : 4 : 9，期望次数约为 143 : 571 : 1286
+        assert!(counts[1] < counts[2] && counts[2] < counts[3]);
+        assert!(counts[3] > 1000);
+    }
+
+    #[test]
+    fn test_rebuilt_shuffled_preserves_structure() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let vertices: Vec<_> = (0..50).map(|i| graph.add_vertex(i)).collect();
+        for i in 0..120 {
+            graph.add_edge(vertices[i % 50], vertices[(i * 7 + 3) % 50], i as u32);
+        }
+        graph.remove_vertex(vertices[10]);
+
+        let (shuffled, map) = graph.rebuilt_shuffled(&mut StdRng::seed_from_u64(1));
+        assert_eq!((shuffled.vertex_count(), shuffled.edge_count()), (graph.vertex_count(), graph.edge_count()));
+        for (old, new) in &map {
+            assert_eq!(graph.vertex(*old).unwrap().weight(), shuffled.vertex(*new).unwrap().weight());
+        }
+        let mut expected: Vec<_> = graph.all_edges().map(|(_, weight, from, to)| (map[&from], map[&to], *weight)).collect();
+        let mut actual: Vec<_> = shuffled.all_edges().map(|(_, weight, from, to)| (from, to, *weight)).collect();
+        expected.sort();
+        actual.sort();
+        assert_eq!(expected, actual);
+        assert_eq!(shuffled.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_rebuilt_shuffled_order_depends_on_seed() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        graph.extend(0..50);
+        let order = |seed| {
+            let (shuffled, _) = graph.rebuilt_shuffled(&mut StdRng::seed_from_u64(seed));
+            shuffled.vertex_ids().map(|id| *shuffled.vertex(id).unwrap().weight()).collect::<Vec<_>>()
+        };
+        assert_eq!(order(3), order(3));
+        assert_ne!(order(3), order(4));
+        assert_ne!(order(3), (0..50).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,264 @@
+/*!
+ * # 加权最短路径 (Weighted Shortest Paths)
+ *
+ * 基于 Dijkstra 的点对点最短路径，以及 Yen 算法求前 k 条无环最短路径。
+ *
+ * ## 约定
+ *
+ * - 边代价由调用方的 `cost(&edge)` 给出，必须非负；累计代价溢出时饱和为 `u64::MAX`
+ * - 路径沿边的方向前进，返回的顶点序列包含起点和终点
+ * - 平行边视为不同的路径：两条路径的边序列不同即视为不同，即使经过的顶点相同
+ *
+ * ## Yen 算法
+ *
+ * 依次以上一条最短路径的每个顶点作为偏离点（spur node），
+ * 屏蔽与已找到路径共享同一前缀的下一条边以及前缀上的其他顶点，
+ * 从偏离点重新求最短路径并与前缀拼接成候选路径。
+ * 屏蔽通过传给 Dijkstra 的顶点/边过滤谓词完成，不修改图。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::Key;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// Dijkstra 求 `start` 到 `goal` 的最小代价路径
+    ///
+    /// 返回总代价和顶点序列；`start == goal` 时为 `(0, [start])`，不可达或顶点不存在时返回 `None`。
+    pub fn dijkstra_shortest_path<F>(&self, start: VertexId<K>, goal: VertexId<K>, cost: F) -> Option<(u64, Vec<VertexId<K>>)>
+    where
+        F: Fn(&Edge) -> u64,
+    {
+        let (total, edges) = self.shortest_edge_path(start, goal, &cost, |_| true, |_| true)?;
+        Some((total, self.edge_path_vertices(start, &edges)))
+    }
+
+    /// Yen 算法求 `start` 到 `goal` 的前 `k` 条无环最短路径
+    ///
+    /// 结果按总代价升序排列，代价相同的路径按被发现的先后排列。
+    /// 不同的无环路径少于 `k` 条时返回全部路径。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(a, c, 5);
+    /// graph.add_edge(a, b, 1);
+    /// graph.add_edge(b, c, 1);
+    ///
+    /// let paths = graph.k_shortest_paths(a, c, 3, |cost| *cost);
+    /// assert_eq!(paths, vec![(2, vec![a, b, c]), (5, vec![a, c])]);
+    /// ```
+    pub fn k_shortest_paths<F>(&self, start: VertexId<K>, goal: VertexId<K>, k: usize, cost: F) -> Vec<(u64, Vec<VertexId<K>>)>
+    where
+        F: Fn(&Edge) -> u64,
+    {
+        if k == 0 {
+            return Vec::new();
+        }
+        let Some(first) = self.shortest_edge_path(start, goal, &cost, |_| true, |_| true) else {
+            return Vec::new();
+        };
+        let mut found: Vec<(u64, Vec<EdgeId<K>>)> = vec![first];
+
+        // 候选路径按 (代价, 发现顺序) 出堆
+        let mut candidates: BinaryHeap<Reverse<(u64, usize)>> = BinaryHeap::new();
+        let mut candidate_paths: Vec<(u64, Vec<EdgeId<K>>)> = Vec::new();
+        let mut seen: HashSet<Vec<EdgeId<K>>> = HashSet::from([found[0].1.clone()]);
+
+        while found.len() < k {
+            let previous = found[found.len() - 1].1.clone();
+            let previous_vertices = self.edge_path_vertices(start, &previous);
+
+            for spur_index in 0..previous.len() {
+                let spur = previous_vertices[spur_index];
+                let root = &previous[..spur_index];
+
+                // 屏蔽已找到路径在相同前缀之后的下一条边
+                let banned_edges: HashSet<EdgeId<K>> = found
+                    .iter()
+                    .filter(|(_, path)| path.len() > spur_index && path[..spur_index] == *root)
+                    .map(|(_, path)| path[spur_index])
+                    .collect();
+                // 屏蔽前缀上除偏离点以外的顶点，保证结果无环
+                let banned_vertices: HashSet<VertexId<K>> = previous_vertices[..spur_index].iter().copied().collect();
+
+                let Some((spur_cost, spur_path)) = self.shortest_edge_path(
+                    spur,
+                    goal,
+                    &cost,
+                    |vertex| !banned_vertices.contains(&vertex),
+                    |edge| !banned_edges.contains(&edge),
+                ) else {
+                    continue;
+                };
+
+                let mut path = root.to_vec();
+                path.extend(spur_path);
+                if seen.insert(path.clone()) {
+                    let root_cost = self.edge_path_cost(root, &cost);
+                    candidates.push(Reverse((root_cost.saturating_add(spur_cost), candidate_paths.len())));
+                    candidate_paths.push((root_cost.saturating_add(spur_cost), path));
+                }
+            }
+
+            let Some(Reverse((_, index))) = candidates.pop() else {
+                break;
+            };
+            found.push(std::mem::take(&mut candidate_paths[index]));
+        }
+
+        found
+            .into_iter()
+            .map(|(total, edges)| (total, self.edge_path_vertices(start, &edges)))
+            .collect()
+    }
+
+    /// 只经过满足谓词的顶点和边的 Dijkstra，返回总代价和边序列
+    fn shortest_edge_path<F, VF, EF>(
+        &self,
+        start: VertexId<K>,
+        goal: VertexId<K>,
+        cost: &F,
+        vertex_allowed: VF,
+        edge_allowed: EF,
+    ) -> Option<(u64, Vec<EdgeId<K>>)>
+    where
+        F: Fn(&Edge) -> u64,
+        VF: Fn(VertexId<K>) -> bool,
+        EF: Fn(EdgeId<K>) -> bool,
+    {
+        if !self.vertices.contains(start) || !self.vertices.contains(goal) {
+            return None;
+        }
+
+        // 每个顶点当前最优的 (代价, 到达它的边)
+        let mut best: HashMap<VertexId<K>, (u64, Option<EdgeId<K>>)> = HashMap::from([(start, (0, None))]);
+        // 堆中按 (代价, 入堆顺序) 排序，顶点存放在 `queued` 中
+        let mut heap = BinaryHeap::from([Reverse((0u64, 0usize))]);
+        let mut queued = vec![start];
+
+        while let Some(Reverse((distance, index))) = heap.pop() {
+            let vertex = queued[index];
+            if best[&vertex].0 != distance {
+                continue;
+            }
+            if vertex == goal {
+                let mut edges = Vec::new();
+                let mut current = goal;
+                while let (_, Some(edge_id)) = best[&current] {
+                    edges.push(edge_id);
+                    current = self.edges.get_connection(edge_id)?.from();
+                }
+                edges.reverse();
+                return Some((distance, edges));
+            }
+            for edge_id in self.edges.edges_from(vertex) {
+                if !edge_allowed(edge_id) {
+                    continue;
+                }
+                let Some((weight, info)) = self.edges.get_with_info(edge_id) else {
+                    continue;
+                };
+                let next = info.to();
+                if !vertex_allowed(next) {
+                    continue;
+                }
+                let total = distance.saturating_add(cost(weight));
+                if best.get(&next).is_none_or(|&(known, _)| total < known) {
+                    best.insert(next, (total, Some(edge_id)));
+                    heap.push(Reverse((total, queued.len())));
+                    queued.push(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// 由起点和边序列得到顶点序列
+    fn edge_path_vertices(&self, start: VertexId<K>, edges: &[EdgeId<K>]) -> Vec<VertexId<K>> {
+        let mut vertices = Vec::with_capacity(edges.len() + 1);
+        vertices.push(start);
+        vertices.extend(edges.iter().filter_map(|edge_id| self.edges.get_connection(*edge_id)).map(|info| info.to()));
+        vertices
+    }
+
+    /// 边序列的累计代价
+    fn edge_path_cost<F>(&self, edges: &[EdgeId<K>], cost: &F) -> u64
+    where
+        F: Fn(&Edge) -> u64,
+    {
+        edges
+            .iter()
+            .filter_map(|edge_id| self.edge_weight(*edge_id))
+            .fold(0u64, |total, weight| total.saturating_add(cost(weight)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SlotMapGraph;
+    use graph_api_lib::Graph;
+
+    #[test]
+    fn test_k_shortest_paths_in_cost_order() {
+        let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+        let s = graph.add_vertex(());
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let t = graph.add_vertex(());
+        // 三条简单路径：s-a-t (2)、s-a-b-t (4)、s-b-t (6)，外加一个不影响结果的环
+        graph.add_edge(s, a, 1);
+        graph.add_edge(a, t, 1);
+        graph.add_edge(a, b, 1);
+        graph.add_edge(s, b, 4);
+        graph.add_edge(b, t, 2);
+        graph.add_edge(b, s, 1);
+
+        let paths = graph.k_shortest_paths(s, t, 3, |cost| *cost);
+        assert_eq!(paths, vec![(2, vec![s, a, t]), (4, vec![s, a, b, t]), (6, vec![s, b, t])]);
+
+        // k 大于简单路径数时只返回全部路径
+        assert_eq!(graph.k_shortest_paths(s, t, 10, |cost| *cost).len(), 3);
+        assert!(graph.k_shortest_paths(s, t, 0, |cost| *cost).is_empty());
+        assert_eq!(graph.dijkstra_shortest_path(s, t, |cost| *cost), Some((2, vec![s, a, t])));
+    }
+
+    #[test]
+    fn test_k_shortest_paths_unreachable_and_trivial() {
+        let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph.add_edge(b, a, 1);
+
+        assert!(graph.k_shortest_paths(a, b, 3, |cost| *cost).is_empty());
+        assert_eq!(graph.dijkstra_shortest_path(a, b, |cost| *cost), None);
+        assert_eq!(graph.k_shortest_paths(a, a, 3, |cost| *cost), vec![(0, vec![a])]);
+    }
+
+    #[test]
+    fn test_parallel_edges_are_distinct_paths() {
+        let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph.add_edge(a, b, 3);
+        graph.add_edge(a, b, 1);
+
+        let paths = graph.k_shortest_paths(a, b, 5, |cost| *cost);
+        assert_eq!(paths, vec![(1, vec![a, b]), (3, vec![a, b])]);
+    }
+}
@@ -0,0 +1,526 @@
+/*!
+ * # 拓扑排序 (Topological Order)
+ *
+ * - [`topological_sort`](SlotMapGraph::topological_sort)：Kahn 算法一次性求出全部顶点的拓扑序，O(V + E)
+ * - [`IncrementalTopoOrder`]：在图逐步变化时维护拓扑序（Pearce–Kelly 算法），
+ *   适合每帧只改动少量边的任务图
+ *
+ * 含环的图没有拓扑序，返回 [`CycleError`]；[`find_cycle`](SlotMapGraph::find_cycle) 列出一个完整的环。
+ *
+ * ## 增量维护
+ *
+ * [`IncrementalTopoOrder`] 为每个顶点记录一个序号，保证每条边 `from -> to` 都满足
+ * `from` 的序号小于 `to` 的序号。新增边已满足该条件时 O(1) 返回；否则只在
+ * 两个端点序号之间受影响的顶点上做前向和后向搜索并重新分配它们的序号，
+ * 开销与受影响区域成正比而不是与全图成正比。
+ *
+ * 序号由调用方按图的变化同步：
+ *
+ * - [`on_vertex_added`](IncrementalTopoOrder::on_vertex_added)：新顶点排在末尾
+ * - [`on_vertex_removed`](IncrementalTopoOrder::on_vertex_removed)：移除顶点的序号
+ * - [`on_edge_added`](IncrementalTopoOrder::on_edge_added)：必要时局部重排，新边会形成环时
+ *   返回 [`CycleError`] 且不修改序号。可以在边插入图之前或之后调用，
+ *   返回错误时调用方不应插入该边（或将其删除）
+ * - [`on_edge_removed`](IncrementalTopoOrder::on_edge_removed)：删除边不会破坏已有顺序，无需重排
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 图中存在环，无法排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError<K: Key = DefaultKey> {
+    /// 无法排序的边的起点
+    pub from: VertexId<K>,
+    /// 无法排序的边的终点
+    pub to: VertexId<K>,
+}
+
+impl<K: Key> std::fmt::Display for CycleError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Edge {} -> {} closes a cycle", self.from, self.to)
+    }
+}
+
+impl<K: Key> std::error::Error for CycleError<K> {}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 全部顶点的一个拓扑序
+    ///
+    /// 入度为 0 的顶点按遍历顺序依次输出。图中有环（包括自环）时返回 [`CycleError`]，
+    /// 其中的边连接两个无法排序的顶点。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(b, c, ());
+    /// graph.add_edge(a, b, ());
+    /// assert_eq!(graph.topological_sort(), Ok(vec![a, b, c]));
+    ///
+    /// graph.add_edge(c, a, ());
+    /// assert!(graph.topological_sort().is_err());
+    /// ```
+    pub fn topological_sort(&self) -> Result<Vec<VertexId<K>>, CycleError<K>> {
+        let mut in_degree: HashMap<VertexId<K>, usize> = self
+            .vertex_ids()
+            .map(|id| (id, self.incoming_adjacency(id).len()))
+            .collect();
+        let mut ready: VecDeque<_> = self.vertex_ids().filter(|id| in_degree[id] == 0).collect();
+        let mut order = Vec::with_capacity(in_degree.len());
+
+        while let Some(vertex_id) = ready.pop_front() {
+            order.push(vertex_id);
+            for edge_id in self.outgoing_adjacency(vertex_id) {
+                let Some((_, to)) = self.edge_endpoints(*edge_id) else {
+                    continue;
+                };
+                let degree = in_degree.get_mut(&to).expect("edge target is a vertex");
+                *degree -= 1;
+                if *degree == 0 {
+                    ready.push_back(to);
+                }
+            }
+        }
+
+        if order.len() == in_degree.len() {
+            return Ok(order);
+        }
+        // 剩余顶点都还有来自剩余顶点的入边
+        let to = self.vertex_ids().find(|id| in_degree[id] > 0).expect("unsorted vertex remains");
+        let from = self
+            .incoming_adjacency(to)
+            .iter()
+            .filter_map(|edge_id| self.edge_endpoints(*edge_id))
+            .map(|(from, _)| from)
+            .find(|from| in_degree[from] > 0)
+            .expect("unsorted vertex has an unsorted predecessor");
+        Err(CycleError { from, to })
+    }
+
+    /// 图中的一个有向环，按边的方向列出环上的顶点，最后一个顶点有边回到第一个；无环时返回 `None`
+    ///
+    /// [`CycleError`] 只指出环上的一条边，需要向用户展示完整的环（例如循环依赖）时使用本方法。
+    /// 按顶点和出边的存储顺序做深度优先搜索，返回遇到的第一个环，O(V + E)。自环返回单个顶点。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(a, b, ());
+    /// graph.add_edge(b, c, ());
+    /// assert_eq!(graph.find_cycle(), None);
+    ///
+    /// graph.add_edge(c, b, ());
+    /// assert_eq!(graph.find_cycle(), Some(vec![b, c]));
+    /// ```
+    pub fn find_cycle(&self) -> Option<Vec<VertexId<K>>> {
+        // true：在当前搜索路径上；false：已搜索完毕
+        let mut on_path: HashMap<VertexId<K>, bool> = HashMap::with_capacity(self.vertex_count());
+        for root in self.vertex_ids() {
+            if on_path.contains_key(&root) {
+                continue;
+            }
+            on_path.insert(root, true);
+            // 路径上的顶点及其下一条待检查的出边
+            let mut path: Vec<(VertexId<K>, usize)> = vec![(root, 0)];
+            while let Some((vertex_id, cursor)) = path.last_mut() {
+                let Some(edge_id) = self.outgoing_adjacency(*vertex_id).get(*cursor) else {
+                    on_path.insert(*vertex_id, false);
+                    path.pop();
+                    continue;
+                };
+                *cursor += 1;
+                let Some((_, to)) = self.edge_endpoints(*edge_id) else {
+                    continue;
+                };
+                match on_path.get(&to) {
+                    None => {
+                        on_path.insert(to, true);
+                        path.push((to, 0));
+                    }
+                    Some(true) => {
+                        let start = path.iter().position(|(vertex_id, _)| *vertex_id == to).expect("vertex on path");
+                        return Some(path[start..].iter().map(|(vertex_id, _)| *vertex_id).collect());
+                    }
+                    Some(false) => {}
+                }
+            }
+        }
+        None
+    }
+}
+
+/// 随图的增删维护的拓扑序
+///
+/// # 示例
+///
+/// ```rust
+/// use graph_api_lib::Graph;
+/// use pi_slotmap_graph::SlotMapGraph;
+/// use pi_slotmap_graph::algo::topo::IncrementalTopoOrder;
+///
+/// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+/// let a = graph.add_vertex(());
+/// let b = graph.add_vertex(());
+/// let mut order = IncrementalTopoOrder::new(&graph).unwrap();
+///
+/// // b -> a 需要把 b 移到 a 之前
+/// assert!(order.on_edge_added(&graph, b, a).is_ok());
+/// graph.add_edge(b, a, ());
+/// assert!(order.precedes(b, a));
+///
+/// // a -> b 会形成环，被拒绝
+/// assert!(order.on_edge_added(&graph, a, b).is_err());
+/// assert_eq!(order.iter().collect::<Vec<_>>(), vec![b, a]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalTopoOrder<K: Key = DefaultKey> {
+    /// 顶点的序号
+    ord: HashMap<VertexId<K>, usize>,
+    /// 序号到顶点，已删除顶点留下空位
+    slots: Vec<Option<VertexId<K>>>,
+}
+
+impl<K: Key> Default for IncrementalTopoOrder<K> {
+    fn default() -> Self {
+        Self {
+            ord: HashMap::new(),
+            slots: Vec::new(),
+        }
+    }
+}
+
+impl<K: Key> IncrementalTopoOrder<K> {
+    /// 以图当前的拓扑序初始化，图中有环时返回 [`CycleError`]
+    pub fn new<Vertex, Edge>(graph: &SlotMapGraph<Vertex, Edge, K>) -> Result<Self, CycleError<K>>
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        let mut order = Self::default();
+        for vertex_id in graph.topological_sort()? {
+            order.on_vertex_added(vertex_id);
+        }
+        Ok(order)
+    }
+
+    /// 按拓扑序遍历顶点
+    pub fn iter(&self) -> impl Iterator<Item = VertexId<K>> + '_ {
+        self.slots.iter().flatten().copied()
+    }
+
+    /// 顶点的序号，未记录的顶点返回 `None`
+    ///
+    /// 序号只用于比较先后，不保证连续。
+    pub fn position(&self, vertex_id: VertexId<K>) -> Option<usize> {
+        self.ord.get(&vertex_id).copied()
+    }
+
+    /// `a` 是否排在 `b` 之前，任一顶点未记录时返回 `false`
+    pub fn precedes(&self, a: VertexId<K>, b: VertexId<K>) -> bool {
+        matches!((self.position(a), self.position(b)), (Some(a), Some(b)) if a < b)
+    }
+
+    /// 记录的顶点数量
+    pub fn len(&self) -> usize {
+        self.ord.len()
+    }
+
+    /// 是否没有记录任何顶点
+    pub fn is_empty(&self) -> bool {
+        self.ord.is_empty()
+    }
+
+    /// 新顶点没有关联边，排在末尾；已记录的顶点不变
+    pub fn on_vertex_added(&mut self, vertex_id: VertexId<K>) {
+        if !self.ord.contains_key(&vertex_id) {
+            self.ord.insert(vertex_id, self.slots.len());
+            self.slots.push(Some(vertex_id));
+        }
+    }
+
+    /// 移除顶点的序号，空位过半时重新编号
+    pub fn on_vertex_removed(&mut self, vertex_id: VertexId<K>) {
+        let Some(position) = self.ord.remove(&vertex_id) else {
+            return;
+        };
+        self.slots[position] = None;
+        if self.ord.len() * 2 < self.slots.len() {
+            self.slots.retain(Option::is_some);
+            for (position, slot) in self.slots.iter().enumerate() {
+                self.ord.insert(slot.expect("retained slots are occupied"), position);
+            }
+        }
+    }
+
+    /// 删除边不会使已有顺序失效，无需处理
+    pub fn on_edge_removed(&mut self, _from: VertexId<K>, _to: VertexId<K>) {}
+
+    /// 新增边 `from -> to` 后维护拓扑序
+    ///
+    /// 未记录的端点先按 [`on_vertex_added`](Self::on_vertex_added) 排在末尾。
+    /// 新边会形成环（包括自环）时返回 [`CycleError`]，序号保持不变。
+    pub fn on_edge_added<Vertex, Edge>(
+        &mut self,
+        graph: &SlotMapGraph<Vertex, Edge, K>,
+        from: VertexId<K>,
+        to: VertexId<K>,
+    ) -> Result<(), CycleError<K>>
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        if from == to {
+            return Err(CycleError { from, to });
+        }
+        self.on_vertex_added(from);
+        self.on_vertex_added(to);
+        let lower = self.ord[&to];
+        let upper = self.ord[&from];
+        if upper < lower {
+            return Ok(());
+        }
+
+        // 前向：to 的后代中序号不超过 upper 的顶点，遇到 from 说明成环
+        let forward = self.affected(to, |w| w <= upper, |id| outgoing(graph, id));
+        if forward.contains(&from) {
+            return Err(CycleError { from, to });
+        }
+        // 后向：from 的祖先中序号大于 lower 的顶点
+        let backward = self.affected(from, |w| w > lower, |id| incoming(graph, id));
+
+        // 把受影响顶点原有的序号按 "祖先在前、后代在后" 重新分配
+        let mut moved = self.sorted_by_ord(backward);
+        moved.extend(self.sorted_by_ord(forward));
+        let mut positions: Vec<_> = moved.iter().map(|id| self.ord[id]).collect();
+        positions.sort_unstable();
+        for (vertex_id, position) in moved.into_iter().zip(positions) {
+            self.ord.insert(vertex_id, position);
+            self.slots[position] = Some(vertex_id);
+        }
+        Ok(())
+    }
+
+    /// 从 `start` 出发沿 `next` 搜索序号满足 `within` 的已记录顶点，结果包含 `start`
+    fn affected<I>(
+        &self,
+        start: VertexId<K>,
+        within: impl Fn(usize) -> bool,
+        next: impl Fn(VertexId<K>) -> I,
+    ) -> HashSet<VertexId<K>>
+    where
+        I: Iterator<Item = VertexId<K>>,
+    {
+        let mut visited = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(current) = stack.pop() {
+            for neighbor in next(current) {
+                let in_range = self.ord.get(&neighbor).is_some_and(|&position| within(position));
+                if in_range && visited.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        visited
+    }
+
+    fn sorted_by_ord(&self, vertices: HashSet<VertexId<K>>) -> Vec<VertexId<K>> {
+        let mut vertices: Vec<_> = vertices.into_iter().collect();
+        vertices.sort_unstable_by_key(|id| self.ord[id]);
+        vertices
+    }
+}
+
+fn outgoing<Vertex, Edge, K>(graph: &SlotMapGraph<Vertex, Edge, K>, vertex_id: VertexId<K>) -> impl Iterator<Item = VertexId<K>> + '_
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    graph
+        .outgoing_adjacency(vertex_id)
+        .iter()
+        .filter_map(|edge_id| graph.edge_endpoints(*edge_id))
+        .map(|(_, to)| to)
+}
+
+fn incoming<Vertex, Edge, K>(graph: &SlotMapGraph<Vertex, Edge, K>, vertex_id: VertexId<K>) -> impl Iterator<Item = VertexId<K>> + '_
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    graph
+        .incoming_adjacency(vertex_id)
+        .iter()
+        .filter_map(|edge_id| graph.edge_endpoints(*edge_id))
+        .map(|(from, _)| from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+
+    /// 每条边的起点都排在终点之前，且顺序覆盖全部顶点
+    fn assert_valid(graph: &SlotMapGraph<u32, ()>, order: &IncrementalTopoOrder) {
+        let full = graph.topological_sort().expect("graph stays acyclic");
+        let listed: Vec<_> = order.iter().collect();
+        assert_eq!(listed.len(), full.len());
+        assert_eq!(listed.into_iter().collect::<HashSet<_>>(), full.into_iter().collect());
+        for (_, _, from, to) in graph.all_edges() {
+            assert!(order.precedes(from, to), "{from} -> {to} out of order");
+        }
+    }
+
+    #[test]
+    fn test_full_sort_rejects_cycles() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let a = graph.add_vertex(0);
+        let b = graph.add_vertex(1);
+        assert_eq!(graph.topological_sort(), Ok(vec![a, b]));
+        graph.add_edge(b, b, ());
+        assert_eq!(graph.topological_sort(), Err(CycleError { from: b, to: b }));
+        assert!(IncrementalTopoOrder::new(&graph).is_err());
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..6).map(|i| graph.add_vertex(i)).collect();
+        // 菱形 0 -> {1, 2} -> 3 无环，多条路径到达同一顶点不算环
+        for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)] {
+            graph.add_edge(ids[from], ids[to], ());
+        }
+        assert_eq!(graph.find_cycle(), None);
+
+        graph.add_edge(ids[4], ids[1], ());
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle, vec![ids[1], ids[3], ids[4]]);
+        for (i, &from) in cycle.iter().enumerate() {
+            assert!(graph.has_edge(from, cycle[(i + 1) % cycle.len()]));
+        }
+
+        let mut looped: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let only = looped.add_vertex(0);
+        looped.add_edge(only, only, ());
+        assert_eq!(looped.find_cycle(), Some(vec![only]));
+    }
+
+    #[test]
+    fn test_insertions_keep_valid_order() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let vertices: Vec<_> = (0..8).map(|i| graph.add_vertex(i)).collect();
+        let mut order = IncrementalTopoOrder::new(&graph).unwrap();
+
+        // 逆着初始顺序连边，迫使每次插入都重排
+        let edges = [(7, 6), (6, 5), (5, 0), (4, 3), (3, 7), (2, 4), (1, 2), (0, 1), (7, 0), (2, 5)];
+        let mut rejected = Vec::new();
+        for (from, to) in edges {
+            let (from, to) = (vertices[from], vertices[to]);
+            match order.on_edge_added(&graph, from, to) {
+                Ok(()) => {
+                    graph.add_edge(from, to, ());
+                }
+                Err(err) => rejected.push(err),
+            }
+            assert_valid(&graph, &order);
+        }
+        // 0 -> 1 -> 2 -> 4 -> 3 -> 7 -> 6 -> 5 -> 0 在 (0, 1) 处闭合
+        assert_eq!(rejected, vec![CycleError { from: vertices[0], to: vertices[1] }]);
+
+        // 删除顶点和边后序号仍然有效，新顶点可以继续参与排序
+        graph.remove_vertex(vertices[3]);
+        order.on_vertex_removed(vertices[3]);
+        let e = graph.find_edge(vertices[7], vertices[6]).unwrap();
+        graph.remove_edge(e);
+        order.on_edge_removed(vertices[7], vertices[6]);
+        assert_valid(&graph, &order);
+
+        let late = graph.add_vertex(8);
+        order.on_vertex_added(late);
+        order.on_edge_added(&graph, late, vertices[4]).unwrap();
+        graph.add_edge(late, vertices[4], ());
+        assert_valid(&graph, &order);
+        assert!(order.on_edge_added(&graph, vertices[4], late).is_err());
+        assert_valid(&graph, &order);
+    }
+
+    #[test]
+    fn test_rejections_match_full_sort() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let vertices: Vec<_> = (0..30).map(|i| graph.add_vertex(i)).collect();
+        let mut order = IncrementalTopoOrder::new(&graph).unwrap();
+
+        // 固定种子的线性同余序列，结果可复现
+        let mut seed = 7u64;
+        let mut next = |bound: usize| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % bound
+        };
+        for _ in 0..300 {
+            let (from, to) = (vertices[next(30)], vertices[next(30)]);
+            let edge = graph.add_edge(from, to, ());
+            let closes_cycle = graph.topological_sort().is_err();
+            assert_eq!(order.on_edge_added(&graph, from, to).is_err(), closes_cycle);
+            if closes_cycle {
+                graph.remove_edge(edge);
+            }
+            assert_valid(&graph, &order);
+        }
+    }
+
+    #[test]
+    fn test_edge_checked_after_insertion() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let [a, b, c] = [0, 1, 2].map(|i| graph.add_vertex(i));
+        let mut order = IncrementalTopoOrder::new(&graph).unwrap();
+
+        graph.add_edge(c, a, ());
+        order.on_edge_added(&graph, c, a).unwrap();
+        graph.add_edge(b, c, ());
+        order.on_edge_added(&graph, b, c).unwrap();
+        assert_eq!(order.iter().collect::<Vec<_>>(), vec![b, c, a]);
+
+        // 已插入图中的成环边同样被拒绝，调用方负责删除
+        let back = graph.add_edge(a, b, ());
+        assert_eq!(order.on_edge_added(&graph, a, b), Err(CycleError { from: a, to: b }));
+        graph.remove_edge(back);
+        assert_valid(&graph, &order);
+    }
+
+    #[test]
+    fn test_removal_compacts_slots() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let vertices: Vec<_> = (0..10).map(|i| graph.add_vertex(i)).collect();
+        let mut order = IncrementalTopoOrder::new(&graph).unwrap();
+        for &vertex_id in &vertices[..6] {
+            graph.remove_vertex(vertex_id);
+            order.on_vertex_removed(vertex_id);
+        }
+        assert_eq!(order.len(), 4);
+        assert_eq!(order.position(vertices[9]), Some(3));
+        assert_eq!(order.iter().collect::<Vec<_>>(), vertices[6..].to_vec());
+    }
+}
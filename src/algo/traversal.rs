@@ -0,0 +1,600 @@
+/*!
+ * # 遍历 (Traversal)
+ *
+ * 基于出边的广度优先搜索与最短路径算法。
+ * 只沿可见图层中的边扩展，见 [`set_visible_layers`](SlotMapGraph::set_visible_layers)。
+ *
+ * ## 点对点最短路径
+ *
+ * - [`bfs_shortest_path`](SlotMapGraph::bfs_shortest_path)：单向 BFS，按跳数计算
+ * - [`bidirectional_shortest_path`](SlotMapGraph::bidirectional_shortest_path)：
+ *   从起点沿出边、从终点沿入边交替扩展，两侧相遇后拼接路径。
+ *   每次扩展较小的一侧的一整层，访问的顶点数通常远少于单向 BFS
+ *
+ * 两者返回的路径长度相同；存在多条等长路径时，选出的路径可能不同。
+ *
+ * ## 多源搜索
+ *
+ * 计算每个可达顶点到一组种子中最近种子的距离，以及该最近种子，
+ * 用于设施选址、Voronoi 划分等场景。实现上把所有种子以距离 0 同时放入队列（或堆），
+ * 因此总复杂度与单源搜索相同。
+ *
+ * - 距离相同时，顶点归属 `seeds` 中位置靠前的种子；顶点ID本身没有顺序，以位置作为确定的优先级
+ * - 图中不存在的种子被忽略，重复的种子以第一次出现为准
+ * - 不可达的顶点不会出现在结果中
+ *
+ * ## 有界搜索
+ *
+ * 交互式查询只关心起点附近的一小部分图，例如"3 跳以内"或"总代价 500 以内"：
+ *
+ * - [`bfs`](SlotMapGraph::bfs)：返回按层产生 `(顶点, 跳数)` 的惰性迭代器，
+ *   [`Bfs::with_max_depth`] 限制最大跳数，边界上的顶点仍被产生但不再向外扩展
+ * - [`dijkstra_bounded`](SlotMapGraph::dijkstra_bounded)：总代价超过预算的顶点不入堆，
+ *   只探索预算以内的部分
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::{Direction, Element};
+use pi_slotmap::Key;
+use std::cmp::{Ordering, Reverse};
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+/// 沿出边的广度优先遍历，按层产生 `(顶点, 跳数)`，由 [`SlotMapGraph::bfs`] 创建
+///
+/// 同一层内按出边列表的顺序产生，每个顶点只产生一次。
+pub struct Bfs<'graph, Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    graph: &'graph SlotMapGraph<Vertex, Edge, K>,
+    queue: VecDeque<(VertexId<K>, u32)>,
+    visited: HashSet<VertexId<K>>,
+    max_depth: Option<u32>,
+}
+
+impl<Vertex, Edge, K> Bfs<'_, Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 只遍历到第 `max_depth` 层，该层的顶点仍被产生但不再扩展其出边
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(u32::try_from(max_depth).unwrap_or(u32::MAX));
+        self
+    }
+}
+
+impl<Vertex, Edge, K> Iterator for Bfs<'_, Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    type Item = (VertexId<K>, u32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (vertex, depth) = self.queue.pop_front()?;
+        if self.max_depth.is_none_or(|max| depth < max) {
+            for edge_id in self.graph.edges.edges_from(vertex).filter(|edge_id| self.graph.edge_visible(*edge_id)) {
+                let Some(info) = self.graph.edges.get_connection(edge_id) else {
+                    continue;
+                };
+                if self.visited.insert(info.to()) {
+                    self.queue.push_back((info.to(), depth + 1));
+                }
+            }
+        }
+        Some((vertex, depth))
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 多源广度优先搜索
+    ///
+    /// 返回每个可达顶点到最近种子的跳数和该种子，种子自身的跳数为 0。O(V + E)。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(a, b, ());
+    /// graph.add_edge(c, b, ());
+    ///
+    /// let nearest = graph.bfs_multi_source(&[a, c]);
+    /// // b 到两个种子的距离相同，归属靠前的 a
+    /// assert_eq!(nearest[&b], (1, a));
+    /// assert_eq!(nearest[&c], (0, c));
+    /// ```
+    pub fn bfs_multi_source(&self, seeds: &[VertexId<K>]) -> HashMap<VertexId<K>, (u32, VertexId<K>)> {
+        let mut nearest = HashMap::new();
+        let mut queue = VecDeque::new();
+        for &seed in seeds {
+            if !self.vertices.contains(seed) {
+                continue;
+            }
+            if let Entry::Vacant(entry) = nearest.entry(seed) {
+                entry.insert((0, seed));
+                queue.push_back(seed);
+            }
+        }
+
+        // 队列中每一层的顶点按所属种子的位置排列，先出队的顶点先占据下一层的邻居
+        while let Some(current) = queue.pop_front() {
+            let (distance, seed) = nearest[&current];
+            for edge_id in self.edges.edges_from(current).filter(|edge_id| self.edge_visible(*edge_id)) {
+                let Some(info) = self.edges.get_connection(edge_id) else {
+                    continue;
+                };
+                if let Entry::Vacant(entry) = nearest.entry(info.to()) {
+                    entry.insert((distance + 1, seed));
+                    queue.push_back(info.to());
+                }
+            }
+        }
+        nearest
+    }
+
+    /// 单向 BFS 求 `start` 到 `goal` 的最短路径（按跳数）
+    ///
+    /// 返回的路径包含两个端点；`start == goal` 时为 `[start]`，不可达或顶点不存在时返回 `None`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(a, b, ());
+    /// graph.add_edge(b, c, ());
+    ///
+    /// assert_eq!(graph.bfs_shortest_path(a, c), Some(vec![a, b, c]));
+    /// assert_eq!(graph.bidirectional_shortest_path(a, c), Some(vec![a, b, c]));
+    /// assert_eq!(graph.bidirectional_shortest_path(c, a), None);
+    /// ```
+    pub fn bfs_shortest_path(&self, start: VertexId<K>, goal: VertexId<K>) -> Option<Vec<VertexId<K>>> {
+        if !self.vertices.contains(start) || !self.vertices.contains(goal) {
+            return None;
+        }
+        let mut parents = HashMap::from([(start, start)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            if current == goal {
+                return Some(trace_back(&parents, goal));
+            }
+            for edge_id in self.edges.edges_from(current).filter(|edge_id| self.edge_visible(*edge_id)) {
+                let Some(info) = self.edges.get_connection(edge_id) else {
+                    continue;
+                };
+                if let Entry::Vacant(entry) = parents.entry(info.to()) {
+                    entry.insert(current);
+                    queue.push_back(info.to());
+                }
+            }
+        }
+        None
+    }
+
+    /// 双向 BFS 求 `start` 到 `goal` 的最短路径（按跳数）
+    ///
+    /// 语义同 [`bfs_shortest_path`](Self::bfs_shortest_path)，路径长度与其相同。
+    pub fn bidirectional_shortest_path(&self, start: VertexId<K>, goal: VertexId<K>) -> Option<Vec<VertexId<K>>> {
+        if !self.vertices.contains(start) || !self.vertices.contains(goal) {
+            return None;
+        }
+        if start == goal {
+            return Some(vec![start]);
+        }
+
+        // 每侧记录已访问顶点的 (父顶点, 深度)，父顶点指向本侧的出发点
+        let mut forward = HashMap::from([(start, (start, 0u32))]);
+        let mut backward = HashMap::from([(goal, (goal, 0u32))]);
+        let mut forward_frontier = vec![start];
+        let mut backward_frontier = vec![goal];
+
+        while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+            let meeting = if forward_frontier.len() <= backward_frontier.len() {
+                self.expand_level(&mut forward_frontier, &mut forward, &backward, Direction::Outgoing)
+            } else {
+                self.expand_level(&mut backward_frontier, &mut backward, &forward, Direction::Incoming)
+            };
+            if let Some(meeting) = meeting {
+                let mut path = vec![meeting];
+                let mut current = meeting;
+                while current != start {
+                    current = forward[&current].0;
+                    path.push(current);
+                }
+                path.reverse();
+                current = meeting;
+                while current != goal {
+                    current = backward[&current].0;
+                    path.push(current);
+                }
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// 把 `frontier` 扩展一整层，返回使两侧总深度最小的相遇顶点
+    fn expand_level(
+        &self,
+        frontier: &mut Vec<VertexId<K>>,
+        visited: &mut HashMap<VertexId<K>, (VertexId<K>, u32)>,
+        other: &HashMap<VertexId<K>, (VertexId<K>, u32)>,
+        direction: Direction,
+    ) -> Option<VertexId<K>> {
+        let mut next = Vec::new();
+        let mut meeting: Option<(u32, VertexId<K>)> = None;
+        for current in std::mem::take(frontier) {
+            let depth = visited[&current].1 + 1;
+            let edges = match direction {
+                Direction::Incoming => self.incoming_adjacency(current),
+                _ => self.outgoing_adjacency(current),
+            };
+            for &edge_id in edges.iter().filter(|edge_id| self.edge_visible(**edge_id)) {
+                let Some((from, to)) = self.edges.endpoints(edge_id) else {
+                    continue;
+                };
+                let neighbor = if direction == Direction::Incoming { from } else { to };
+                let Entry::Vacant(entry) = visited.entry(neighbor) else {
+                    continue;
+                };
+                entry.insert((current, depth));
+                next.push(neighbor);
+                if let Some(&(_, other_depth)) = other.get(&neighbor) {
+                    let total = depth + other_depth;
+                    if meeting.is_none_or(|(best, _)| total < best) {
+                        meeting = Some((total, neighbor));
+                    }
+                }
+            }
+        }
+        *frontier = next;
+        meeting.map(|(_, vertex)| vertex)
+    }
+
+    /// 从 `start` 沿出边广度优先遍历，产生 `(顶点, 跳数)`，起点的跳数为 0
+    ///
+    /// 遍历是惰性的，配合 [`Bfs::with_max_depth`] 只探索起点附近的层。起点不存在时为空。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(a, b, ());
+    /// graph.add_edge(b, c, ());
+    ///
+    /// assert_eq!(graph.bfs(a).collect::<Vec<_>>(), vec![(a, 0), (b, 1), (c, 2)]);
+    /// assert_eq!(graph.bfs(a).with_max_depth(1).collect::<Vec<_>>(), vec![(a, 0), (b, 1)]);
+    /// ```
+    pub fn bfs(&self, start: VertexId<K>) -> Bfs<'_, Vertex, Edge, K> {
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        if self.vertices.contains(start) {
+            queue.push_back((start, 0));
+            visited.insert(start);
+        }
+        Bfs {
+            graph: self,
+            queue,
+            visited,
+            max_depth: None,
+        }
+    }
+
+    /// 代价有上限的 Dijkstra 最短路径
+    ///
+    /// 返回从 `start` 出发、最小总代价不超过 `max_cost` 的每个顶点及其代价（含起点，代价为 0）。
+    /// 超出预算的顶点不会入堆，探索范围只与预算以内的部分有关。`cost` 返回每条边的非负代价，
+    /// 代价溢出时饱和为 `u64::MAX`。
+    pub fn dijkstra_bounded<F>(&self, start: VertexId<K>, max_cost: u64, cost: F) -> HashMap<VertexId<K>, u64>
+    where
+        F: Fn(&Edge) -> u64,
+    {
+        let mut best: HashMap<VertexId<K>, u64> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        if self.vertices.contains(start) {
+            best.insert(start, 0);
+            heap.push(Reverse(Candidate { cost: 0, rank: 0, vertex: start }));
+        }
+
+        while let Some(Reverse(Candidate { cost: distance, vertex, .. })) = heap.pop() {
+            if best[&vertex] != distance {
+                // 已被更优的候选取代
+                continue;
+            }
+            for edge_id in self.edges.edges_from(vertex).filter(|edge_id| self.edge_visible(*edge_id)) {
+                let Some((weight, info)) = self.edges.get_with_info(edge_id) else {
+                    continue;
+                };
+                let next = distance.saturating_add(cost(weight));
+                if next > max_cost {
+                    continue;
+                }
+                if best.get(&info.to()).is_none_or(|&known| next < known) {
+                    best.insert(info.to(), next);
+                    heap.push(Reverse(Candidate { cost: next, rank: 0, vertex: info.to() }));
+                }
+            }
+        }
+        best
+    }
+
+    /// 多源 Dijkstra 最短路径
+    ///
+    /// `cost` 返回每条边的非负代价。返回每个可达顶点到最近种子的最小总代价和该种子，
+    /// 代价溢出时饱和为 `u64::MAX`。O((V + E) log V)。
+    pub fn dijkstra_multi_source<F>(&self, seeds: &[VertexId<K>], cost: F) -> HashMap<VertexId<K>, (u64, VertexId<K>)>
+    where
+        F: Fn(&Edge) -> u64,
+    {
+        // 每个顶点当前最优的 (代价, 种子位置, 种子)
+        let mut best: HashMap<VertexId<K>, (u64, usize, VertexId<K>)> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        for (rank, &seed) in seeds.iter().enumerate() {
+            if !self.vertices.contains(seed) {
+                continue;
+            }
+            if let Entry::Vacant(entry) = best.entry(seed) {
+                entry.insert((0, rank, seed));
+                heap.push(Reverse(Candidate { cost: 0, rank, vertex: seed }));
+            }
+        }
+
+        while let Some(Reverse(Candidate { cost: distance, rank, vertex })) = heap.pop() {
+            let (best_cost, best_rank, seed) = best[&vertex];
+            if (best_cost, best_rank) != (distance, rank) {
+                // 已被更优的候选取代
+                continue;
+            }
+            for edge_id in self.edges.edges_from(vertex).filter(|edge_id| self.edge_visible(*edge_id)) {
+                let Some((weight, info)) = self.edges.get_with_info(edge_id) else {
+                    continue;
+                };
+                let next = (distance.saturating_add(cost(weight)), rank);
+                let improved = best.get(&info.to()).is_none_or(|&(cost, rank, _)| next < (cost, rank));
+                if improved {
+                    best.insert(info.to(), (next.0, next.1, seed));
+                    heap.push(Reverse(Candidate { cost: next.0, rank: next.1, vertex: info.to() }));
+                }
+            }
+        }
+
+        best.into_iter().map(|(vertex, (cost, _, seed))| (vertex, (cost, seed))).collect()
+    }
+}
+
+/// 沿父顶点从 `end` 回溯到根（父顶点为自身的顶点），返回根到 `end` 的路径
+fn trace_back<K: Key>(parents: &HashMap<VertexId<K>, VertexId<K>>, end: VertexId<K>) -> Vec<VertexId<K>> {
+    let mut path = vec![end];
+    let mut current = end;
+    while parents[&current] != current {
+        current = parents[&current];
+        path.push(current);
+    }
+    path.reverse();
+    path
+}
+
+/// 堆中的候选顶点，按 (代价, 种子位置) 排序；单源搜索的种子位置恒为 0
+struct Candidate<K: Key> {
+    cost: u64,
+    rank: usize,
+    vertex: VertexId<K>,
+}
+
+impl<K: Key> PartialEq for Candidate<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Key> Eq for Candidate<K> {}
+
+impl<K: Key> PartialOrd for Candidate<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Key> Ord for Candidate<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.cost, self.rank).cmp(&(other.cost, other.rank))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SlotMapGraph, VertexId};
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+
+    /// 双向连接的路径图 v0 - v1 - ... - v(n-1)
+    fn path_graph(n: usize) -> (SlotMapGraph<(), u64>, Vec<VertexId>) {
+        let mut graph = SlotMapGraph::new();
+        let ids: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+        for pair in ids.windows(2) {
+            graph.add_edge(pair[0], pair[1], 1);
+            graph.add_edge(pair[1], pair[0], 1);
+        }
+        (graph, ids)
+    }
+
+    #[test]
+    fn test_bfs_partitions_path_between_two_seeds() {
+        let (mut graph, ids) = path_graph(5);
+        let isolated = graph.add_vertex(());
+        let (left, right) = (ids[0], ids[4]);
+
+        let nearest = graph.bfs_multi_source(&[left, right]);
+        assert_eq!(nearest.len(), 5);
+        assert_eq!(nearest[&ids[1]], (1, left));
+        // 中点与两端距离相同，归属靠前的种子
+        assert_eq!(nearest[&ids[2]], (2, left));
+        assert_eq!(nearest[&ids[3]], (1, right));
+        assert!(!nearest.contains_key(&isolated));
+
+        let nearest = graph.bfs_multi_source(&[right, left, right]);
+        assert_eq!(nearest[&ids[2]], (2, right));
+        assert!(graph.bfs_multi_source(&[]).is_empty());
+    }
+
+    /// 固定种子的 xorshift 随机数，保证测试可复现
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+    }
+
+    fn assert_valid_path(graph: &SlotMapGraph<(), u64>, path: &[VertexId]) {
+        for pair in path.windows(2) {
+            assert!(graph.has_edge(pair[0], pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_matches_bfs_on_random_graphs() {
+        let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..30 {
+            let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+            let n = 2 + rng.below(30);
+            let ids: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+            for _ in 0..rng.below(3 * n) {
+                graph.add_edge(ids[rng.below(n)], ids[rng.below(n)], 1);
+            }
+
+            for _ in 0..20 {
+                let (start, goal) = (ids[rng.below(n)], ids[rng.below(n)]);
+                let expected = graph.bfs_shortest_path(start, goal);
+                let actual = graph.bidirectional_shortest_path(start, goal);
+                assert_eq!(actual.as_ref().map(Vec::len), expected.as_ref().map(Vec::len));
+                if let Some(path) = actual {
+                    assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+                    assert_valid_path(&graph, &path);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shortest_path_disconnected() {
+        let (mut graph, ids) = path_graph(3);
+        let isolated = graph.add_vertex(());
+        assert_eq!(graph.bidirectional_shortest_path(ids[0], isolated), None);
+        assert_eq!(graph.bfs_shortest_path(isolated, ids[0]), None);
+        assert_eq!(graph.bidirectional_shortest_path(isolated, isolated), Some(vec![isolated]));
+        assert_eq!(graph.bidirectional_shortest_path(ids[2], ids[0]), Some(vec![ids[2], ids[1], ids[0]]));
+
+        graph.remove_vertex(isolated);
+        assert_eq!(graph.bidirectional_shortest_path(isolated, isolated), None);
+    }
+
+    #[test]
+    fn test_dijkstra_multi_source() {
+        let (mut graph, ids) = path_graph(5);
+        let (left, right) = (ids[0], ids[4]);
+
+        let nearest = graph.dijkstra_multi_source(&[left, right], |cost| *cost);
+        assert_eq!(nearest[&ids[2]], (2, left));
+        assert_eq!(nearest[&ids[3]], (1, right));
+
+        // 加一条从右端出发的捷径，中点改为归属右端
+        graph.add_edge(right, ids[2], 0);
+        let nearest = graph.dijkstra_multi_source(&[left, right], |cost| *cost);
+        assert_eq!(nearest[&ids[2]], (0, right));
+        assert_eq!(nearest[&ids[1]], (1, left));
+
+        // 有向边只能沿出边方向到达
+        let sink = graph.add_vertex(());
+        graph.add_edge(sink, left, 1);
+        let nearest = graph.dijkstra_multi_source(&[right], |cost| *cost);
+        assert!(!nearest.contains_key(&sink));
+        assert_eq!(nearest[&left], (2, right));
+    }
+
+    #[test]
+    fn test_bfs_max_depth_on_tree() {
+        // 完全二叉树，共 4 层
+        let mut graph: SlotMapGraph<u32, u64> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..15).map(|i| graph.add_vertex(i)).collect();
+        for child in 1..15 {
+            graph.add_edge(ids[(child - 1) / 2], ids[child], 1);
+        }
+
+        let levels: Vec<_> = graph.bfs(ids[0]).with_max_depth(2).collect();
+        assert_eq!(levels.len(), 7);
+        for (vertex, depth) in &levels {
+            let index = ids.iter().position(|id| id == vertex).unwrap();
+            let expected = (index + 1).ilog2();
+            assert_eq!(*depth, expected);
+        }
+        assert!(levels.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(graph.bfs(ids[0]).count(), 15);
+        assert_eq!(graph.bfs(ids[0]).with_max_depth(0).collect::<Vec<_>>(), vec![(ids[0], 0)]);
+        // 子树内的遍历，环不会重复产生顶点
+        graph.add_edge(ids[6], ids[2], 1);
+        assert_eq!(graph.bfs(ids[2]).with_max_depth(5).count(), 7);
+
+        graph.remove_vertex(ids[1]);
+        assert_eq!(graph.bfs(ids[1]).count(), 0);
+    }
+
+    #[test]
+    fn test_dijkstra_bounded_budget_edges() {
+        let mut graph: SlotMapGraph<(), u64> = SlotMapGraph::new();
+        let start = graph.add_vertex(());
+        let exact = graph.add_vertex(());
+        let over = graph.add_vertex(());
+        let via = graph.add_vertex(());
+        let cheap_path = graph.add_vertex(());
+        graph.add_edge(start, exact, 500);
+        graph.add_edge(start, over, 501);
+        // 较长但更便宜的路径把顶点拉回预算以内
+        graph.add_edge(start, via, 100);
+        graph.add_edge(via, cheap_path, 400);
+        graph.add_edge(start, cheap_path, 900);
+        graph.add_edge(exact, over, 1);
+
+        let reached = graph.dijkstra_bounded(start, 500, |cost| *cost);
+        assert_eq!(reached.len(), 4);
+        assert_eq!(reached[&start], 0);
+        assert_eq!(reached[&exact], 500);
+        assert_eq!(reached[&cheap_path], 500);
+        assert!(!reached.contains_key(&over));
+
+        let unbounded = graph.dijkstra_multi_source(&[start], |cost| *cost);
+        assert_eq!(unbounded[&over].0, 501);
+        assert_eq!(graph.dijkstra_bounded(start, 0, |cost| *cost).len(), 1);
+    }
+}
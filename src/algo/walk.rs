@@ -0,0 +1,183 @@
+/*!
+ * # 随机游走 (Random Walk)
+ *
+ * 为 DeepWalk / node2vec 等嵌入与采样流水线生成顶点序列。
+ *
+ * ## 算法
+ *
+ * - **均匀游走**: 每步在当前顶点的出边中均匀选取一条，借助邻接表 O(1) 完成
+ * - **加权游走**: 按边权重函数的比例选取出边，每步 O(d)
+ * - **带重启的游走**: 每步以给定概率跳回起点（Random Walk with Restart）
+ *
+ * 游走到没有（可用）出边的顶点时提前结束。
+ */
+
+use crate::algo::sampling::weighted_pick;
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::Key;
+use rand::Rng;
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 从 `start` 出发沿均匀随机的出边游走
+    ///
+    /// 返回的序列以 `start` 开头，最多包含 `length` 个顶点；
+    /// 到达汇点（没有出边的顶点）时提前结束。`start` 不存在时返回空序列。
+    pub fn random_walk<R: Rng + ?Sized>(&self, start: VertexId<K>, length: usize, rng: &mut R) -> Vec<VertexId<K>> {
+        let mut walk = Vec::with_capacity(length);
+        if length == 0 || !self.vertices.contains(start) {
+            return walk;
+        }
+        walk.push(start);
+
+        let mut current = start;
+        while walk.len() < length {
+            let outgoing = match self.edges.adjacency(current) {
+                Some(adj) if !adj.outgoing().is_empty() => adj.outgoing(),
+                _ => break,
+            };
+            let edge_id = outgoing[rng.random_range(0..outgoing.len())];
+            match self.edges.get_connection(edge_id) {
+                Some(info) => current = info.to(),
+                None => break,
+            }
+            walk.push(current);
+        }
+        walk
+    }
+
+    /// 按边权重游走，并以 `restart_probability` 的概率在每步跳回起点
+    ///
+    /// 出边被选中的概率与 `weight(edge_id, &edge)` 成正比，
+    /// 非正数、NaN 或无穷大的权重视为不可通行。
+    /// `restart_probability` 会被限制在 `[0, 1]` 内；为 `1.0` 时序列由 `start` 重复组成。
+    /// 其余语义同 [`random_walk`](Self::random_walk)。
+    pub fn random_walk_weighted<R, F>(
+        &self,
+        start: VertexId<K>,
+        length: usize,
+        restart_probability: f64,
+        rng: &mut R,
+        weight: F,
+    ) -> Vec<VertexId<K>>
+    where
+        R: Rng + ?Sized,
+        F: Fn(EdgeId<K>, &Edge) -> f64,
+    {
+        let mut walk = Vec::with_capacity(length);
+        if length == 0 || !self.vertices.contains(start) {
+            return walk;
+        }
+        walk.push(start);
+
+        let restart_probability = if restart_probability.is_nan() {
+            0.0
+        } else {
+            restart_probability.clamp(0.0, 1.0)
+        };
+
+        let mut current = start;
+        while walk.len() < length {
+            if rng.random_bool(restart_probability) {
+                current = start;
+                walk.push(current);
+                continue;
+            }
+
+            let candidates = self.edges.edges_from(current).filter_map(|edge_id| {
+                self.edges
+                    .get_with_info(edge_id)
+                    .map(|(edge, info)| (info.to(), weight(edge_id, edge)))
+            });
+            match weighted_pick(candidates, rng) {
+                Some(next) => current = next,
+                None => break,
+            }
+            walk.push(current);
+        }
+        walk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SlotMapGraph;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_random_walk_deterministic_with_seed() {
+        let (graph, ids) = crate::graph! { a -> b, a -> c, b -> a, b -> c, c -> a };
+
+        let walk1 = graph.random_walk(ids["a"], 20, &mut StdRng::seed_from_u64(42));
+        let walk2 = graph.random_walk(ids["a"], 20, &mut StdRng::seed_from_u64(42));
+        assert_eq!(walk1, walk2);
+
+        // 图中没有汇点，游走会走满长度，且每一步都沿着已有的边
+        assert_eq!(walk1.len(), 20);
+        assert_eq!(walk1[0], ids["a"]);
+        assert!(walk1.windows(2).all(|pair| graph.has_edge(pair[0], pair[1])));
+    }
+
+    #[test]
+    fn test_random_walk_stops_at_sink() {
+        let (graph, ids) = crate::graph! { a -> b, b -> c };
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert_eq!(graph.random_walk(ids["a"], 10, &mut rng), vec![ids["a"], ids["b"], ids["c"]]);
+        assert_eq!(graph.random_walk(ids["c"], 10, &mut rng), vec![ids["c"]]);
+        assert!(graph.random_walk(ids["a"], 0, &mut rng).is_empty());
+
+        let walk = graph.random_walk_weighted(ids["a"], 10, 0.0, &mut rng, |_, _| 1.0);
+        assert_eq!(walk, vec![ids["a"], ids["b"], ids["c"]]);
+    }
+
+    #[test]
+    fn test_random_walk_missing_start() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let v = graph.add_vertex(1);
+        graph.remove_vertex(v);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert!(graph.random_walk(v, 5, &mut rng).is_empty());
+        assert!(graph.random_walk_weighted(v, 5, 0.5, &mut rng, |_, _| 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_random_walk_weighted() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(0);
+        let b = graph.add_vertex(1);
+        let c = graph.add_vertex(2);
+        graph.add_edge(a, b, 0);
+        graph.add_edge(a, c, 1);
+        graph.add_edge(b, a, 1);
+        graph.add_edge(c, a, 1);
+
+        // 权重为 0 的边不可通行，游走只能在 a、c 之间往返
+        let mut rng = StdRng::seed_from_u64(5);
+        let walk = graph.random_walk_weighted(a, 11, 0.0, &mut rng, |_, e| *e as f64);
+        assert_eq!(walk.len(), 11);
+        assert!(!walk.contains(&b));
+
+        // 没有可用出边时视为汇点
+        let walk = graph.random_walk_weighted(a, 5, 0.0, &mut rng, |_, _| 0.0);
+        assert_eq!(walk, vec![a]);
+    }
+
+    #[test]
+    fn test_random_walk_full_restart() {
+        let (graph, ids) = crate::graph! { a -> b, b -> c, c -> a };
+        let mut rng = StdRng::seed_from_u64(9);
+
+        let walk = graph.random_walk_weighted(ids["b"], 8, 1.0, &mut rng, |_, _| 1.0);
+        assert_eq!(walk, vec![ids["b"]; 8]);
+    }
+}
@@ -0,0 +1,219 @@
+/*!
+ * # 有容量上限的图 (Bounded Graph)
+ *
+ * [`BoundedSlotMapGraph`] 在构造时给定顶点和边的数量上限，并按上限预先分配槽位存储。
+ * 达到上限后插入返回 [`GraphError::CapacityExceeded`]，图保持不变；
+ * 删除元素后腾出的名额可以再次使用。适合要求启动后不再扩容的实时场景。
+ *
+ * ## 分配保证
+ *
+ * 构造时按上限预留顶点和边的 SlotMap 槽位存储、邻接表，以及顶点和边的标签索引，
+ * 元素数量在上限内时这些结构不会重新分配。每个标签分组都按上限预留，
+ * 标签种类多时占用的内存相应增加。
+ *
+ * ## 读取接口
+ *
+ * 通过 `Deref` 提供 [`SlotMapGraph`] 的全部只读接口；
+ * 增删元素只能经由本类型的方法进行，以保证上限始终有效。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use pi_slotmap_graph::{BoundedSlotMapGraph, GraphError};
+ *
+ * let mut graph: BoundedSlotMapGraph<u32, ()> = BoundedSlotMapGraph::new(2, 1);
+ * let a = graph.add_vertex(1).unwrap();
+ * let b = graph.add_vertex(2).unwrap();
+ * assert_eq!(graph.add_vertex(3), Err(GraphError::CapacityExceeded { limit: 2 }));
+ *
+ * graph.add_edge(a, b, ()).unwrap();
+ * assert!(graph.has_edge(a, b));
+ * ```
+ */
+
+use crate::error::GraphError;
+use crate::graph::{RemovePolicy, SlotMapGraph};
+use crate::id::{EdgeId, VertexId};
+use crate::index::{HashIndex, VertexLabelIndex};
+use graph_api_lib::{Element, Graph, SupportsElementRemoval};
+use pi_slotmap::{DefaultKey, Key};
+use std::ops::Deref;
+
+/// 有顶点和边数量上限的图
+#[derive(Debug)]
+pub struct BoundedSlotMapGraph<Vertex, Edge, K = DefaultKey>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 内部的图，槽位存储按上限预先分配
+    graph: SlotMapGraph<Vertex, Edge, K>,
+    /// 顶点数量上限
+    max_vertices: usize,
+    /// 边数量上限
+    max_edges: usize,
+}
+
+impl<Vertex, Edge> BoundedSlotMapGraph<Vertex, Edge>
+where
+    Vertex: Element,
+    Edge: Element,
+{
+    /// 创建最多容纳 `max_vertices` 个顶点、`max_edges` 条边的空图
+    pub fn new(max_vertices: usize, max_edges: usize) -> Self {
+        Self::with_key(max_vertices, max_edges)
+    }
+}
+
+impl<Vertex, Edge, K> BoundedSlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 创建使用自定义键类型、最多容纳 `max_vertices` 个顶点、`max_edges` 条边的空图
+    pub fn with_key(max_vertices: usize, max_edges: usize) -> Self {
+        let mut graph = SlotMapGraph::with_capacity_and_key(max_vertices, max_edges);
+        graph.vertex_labels = VertexLabelIndex::with_capacity(max_vertices);
+        graph.edge_labels = HashIndex::with_capacities(max_edges, max_edges);
+        Self {
+            graph,
+            max_vertices,
+            max_edges,
+        }
+    }
+
+    /// 顶点数量上限
+    pub fn max_vertices(&self) -> usize {
+        self.max_vertices
+    }
+
+    /// 边数量上限
+    pub fn max_edges(&self) -> usize {
+        self.max_edges
+    }
+
+    /// 添加顶点
+    ///
+    /// # 错误
+    ///
+    /// 顶点数量已达上限时返回 [`GraphError::CapacityExceeded`]。
+    pub fn add_vertex(&mut self, vertex: Vertex) -> Result<VertexId<K>, GraphError<K>> {
+        if self.graph.vertex_count() >= self.max_vertices {
+            return Err(GraphError::CapacityExceeded { limit: self.max_vertices });
+        }
+        Ok(self.graph.add_vertex(vertex))
+    }
+
+    /// 添加一条 `from -> to` 的边
+    ///
+    /// # 错误
+    ///
+    /// - 端点不存在时返回 [`GraphError::VertexNotFound`]
+    /// - 边数量已达上限时返回 [`GraphError::CapacityExceeded`]
+    pub fn add_edge(&mut self, from: VertexId<K>, to: VertexId<K>, edge: Edge) -> Result<EdgeId<K>, GraphError<K>> {
+        for vertex in [from, to] {
+            if !self.graph.contains_vertex(vertex) {
+                return Err(GraphError::VertexNotFound(vertex));
+            }
+        }
+        if self.graph.edge_count() >= self.max_edges {
+            return Err(GraphError::CapacityExceeded { limit: self.max_edges });
+        }
+        Ok(self.graph.add_edge(from, to, edge))
+    }
+
+    /// 删除顶点及其关联边，顶点不存在或被固定时返回 `None`
+    pub fn remove_vertex(&mut self, vertex_id: VertexId<K>) -> Option<Vertex> {
+        self.graph.remove_vertex(vertex_id)
+    }
+
+    /// 按给定策略删除顶点，见 [`SlotMapGraph::remove_vertex_policy`]
+    pub fn remove_vertex_policy(&mut self, vertex_id: VertexId<K>, policy: RemovePolicy) -> Result<Vertex, GraphError<K>> {
+        self.graph.remove_vertex_policy(vertex_id, policy)
+    }
+
+    /// 删除边
+    pub fn remove_edge(&mut self, edge_id: EdgeId<K>) -> Option<Edge> {
+        self.graph.remove_edge(edge_id)
+    }
+
+    /// 获取顶点数据的可变引用
+    pub fn vertex_weight_mut(&mut self, vertex_id: VertexId<K>) -> Option<&mut Vertex> {
+        self.graph.vertex_weight_mut(vertex_id)
+    }
+
+    /// 获取边数据的可变引用
+    pub fn edge_weight_mut(&mut self, edge_id: EdgeId<K>) -> Option<&mut Edge> {
+        self.graph.edge_weight_mut(edge_id)
+    }
+
+    /// 取出内部的图，之后不再受上限约束
+    pub fn into_inner(self) -> SlotMapGraph<Vertex, Edge, K> {
+        self.graph
+    }
+}
+
+impl<Vertex, Edge, K> Deref for BoundedSlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    type Target = SlotMapGraph<Vertex, Edge, K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_to_cap_without_reallocation() {
+        let mut graph: BoundedSlotMapGraph<u32, u32> = BoundedSlotMapGraph::new(8, 16);
+        let capacities = (graph.vertices.slot_capacity(), graph.edges.slot_capacity());
+        let adjacency = graph.edges.adjacency_capacity();
+
+        let vertices: Vec<_> = (0..8).map(|i| graph.add_vertex(i).unwrap()).collect();
+        assert_eq!(graph.add_vertex(8), Err(GraphError::CapacityExceeded { limit: 8 }));
+        assert_eq!(graph.vertex_count(), 8);
+
+        let mut edges = Vec::new();
+        for i in 0..16 {
+            edges.push(graph.add_edge(vertices[i % 8], vertices[(i + 1) % 8], i as u32).unwrap());
+        }
+        let version = graph.version();
+        assert_eq!(
+            graph.add_edge(vertices[0], vertices[1], 16),
+            Err(GraphError::CapacityExceeded { limit: 16 })
+        );
+        assert_eq!((graph.edge_count(), graph.version()), (16, version));
+        assert_eq!((graph.vertices.slot_capacity(), graph.edges.slot_capacity()), capacities);
+        assert_eq!(graph.edges.adjacency_capacity(), adjacency);
+
+        // 删除后腾出的名额可以再次使用
+        assert_eq!(graph.remove_edge(edges[3]), Some(3));
+        graph.add_edge(vertices[0], vertices[0], 17).unwrap();
+        assert_eq!(graph.remove_vertex(vertices[7]), Some(7));
+        assert_eq!(graph.edge_count(), 12);
+        let again = graph.add_vertex(9).unwrap();
+        graph.add_edge(again, vertices[0], 18).unwrap();
+        assert_eq!((graph.vertices.slot_capacity(), graph.edges.slot_capacity()), capacities);
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_add_edge_to_missing_vertex() {
+        let mut graph: BoundedSlotMapGraph<(), ()> = BoundedSlotMapGraph::new(2, 0);
+        let a = graph.add_vertex(()).unwrap();
+        let b = graph.add_vertex(()).unwrap();
+        graph.remove_vertex(b);
+        assert_eq!(graph.add_edge(a, b, ()), Err(GraphError::VertexNotFound(b)));
+        assert_eq!(graph.add_edge(a, a, ()), Err(GraphError::CapacityExceeded { limit: 0 }));
+        assert_eq!(graph.into_inner().vertex_count(), 1);
+    }
+}
@@ -0,0 +1,238 @@
+/*!
+ * # 内容哈希 (Canonical Content Hash)
+ *
+ * 判断两张独立构建的图内容是否相同（顶点数据、边数据和连接方式，忽略 ID）时，
+ * 先把图整理成与 ID 和插入顺序无关的规范形式，再对其求哈希：
+ *
+ * 1. 每个顶点的初始键为（标签序号, 数据哈希）
+ * 2. 按 Weisfeiler–Lehman 方式细化：每轮把顶点的键与其出边、入边上（边数据哈希, 邻居键）的
+ *    有序列表合并为新键，直到键的种类数不再增加
+ * 3. 顶点按（标签序号, 数据哈希, 细化后的键）排序，键相同的顶点共用同一个规范序号
+ * 4. 边按（起点序号, 终点序号, 边数据哈希）排序
+ *
+ * 按顺序把顶点数据和（起点序号, 终点序号, 边数据）写入哈希器。
+ * 整个过程不依赖 ID，因此**内容相同的图哈希相同**；反之不成立：细化无法区分的顶点
+ * （例如正则图中的顶点）共用序号，**哈希相同不代表同构**，病态的对称结构可能得到相同的哈希。
+ * 需要确认时可再用 [`is_isomorphism`](SlotMapGraph::is_isomorphism) 检查。
+ *
+ * [`content_digest`](SlotMapGraph::content_digest) 使用内置的 FNV-1a 哈希器，
+ * 同一平台上跨进程、跨运行结果稳定（前提是数据类型的 `Hash` 实现本身是确定的）。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut left: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+ * let a = left.add_vertex(1);
+ * let b = left.add_vertex(2);
+ * left.add_edge(a, b, 10);
+ *
+ * let mut right: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+ * let b = right.add_vertex(2);
+ * let a = right.add_vertex(1);
+ * right.add_edge(a, b, 10);
+ * assert_eq!(left.content_digest(), right.content_digest());
+ *
+ * right.add_edge(b, a, 10);
+ * assert_ne!(left.content_digest(), right.content_digest());
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::{Element, Label};
+use pi_slotmap::Key;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// FNV-1a 哈希器，结果不依赖随机种子
+#[derive(Debug, Clone, Copy)]
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn stable_hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 顶点的规范排序键：（标签序号, 数据哈希, 细化后的键）
+type CanonicalKey = (usize, u64, u64);
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element + Hash,
+    Edge: Element + Hash,
+    K: Key,
+{
+    /// 把图的规范形式写入 `state`，与 ID 和插入顺序无关
+    ///
+    /// 内容相同的图写入相同的内容；写入相同不代表同构，见[模块文档](crate::canonical)。
+    /// 复杂度为 O(R·(V + E log E))，R 为细化轮数，不超过顶点数。
+    pub fn content_hash<H: Hasher>(&self, state: &mut H) {
+        let vertices: Vec<_> = self.vertices.iter().collect();
+        let position: HashMap<_, _> = vertices.iter().enumerate().map(|(index, (id, _))| (*id, index)).collect();
+        let edges: Vec<_> = self
+            .edges
+            .iter_with_connections()
+            .map(|(_, weight, info)| (position[&info.from()], position[&info.to()], stable_hash(weight), weight))
+            .collect();
+
+        let keys = self.canonical_keys(&vertices, &edges);
+        let mut distinct: Vec<CanonicalKey> = keys.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        let rank = |index: usize| distinct.binary_search(&keys[index]).unwrap_or_default();
+
+        let mut order: Vec<usize> = (0..vertices.len()).collect();
+        order.sort_by_key(|&index| keys[index]);
+        state.write_usize(vertices.len());
+        for index in order {
+            state.write_usize(keys[index].0);
+            vertices[index].1.hash(state);
+        }
+
+        let mut canonical_edges: Vec<_> = edges
+            .iter()
+            .map(|&(from, to, weight_hash, weight)| (rank(from), rank(to), weight_hash, weight))
+            .collect();
+        canonical_edges.sort_by_key(|&(from, to, weight_hash, _)| (from, to, weight_hash));
+        state.write_usize(canonical_edges.len());
+        for (from, to, _, weight) in canonical_edges {
+            state.write_usize(from);
+            state.write_usize(to);
+            weight.hash(state);
+        }
+    }
+
+    /// 以内置的稳定哈希器计算的 [`content_hash`](Self::content_hash)
+    pub fn content_digest(&self) -> u64 {
+        let mut hasher = StableHasher::default();
+        self.content_hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// 细化每个顶点的排序键，`edges` 中的端点为 `vertices` 中的位置
+    fn canonical_keys(&self, vertices: &[(VertexId<K>, &Vertex)], edges: &[(usize, usize, u64, &Edge)]) -> Vec<CanonicalKey> {
+        let base: Vec<(usize, u64)> = vertices
+            .iter()
+            .map(|(_, weight)| (weight.label().ordinal(), stable_hash(*weight)))
+            .collect();
+        let mut outgoing: Vec<Vec<(u64, usize)>> = vec![Vec::new(); vertices.len()];
+        let mut incoming: Vec<Vec<(u64, usize)>> = vec![Vec::new(); vertices.len()];
+        for &(from, to, weight_hash, _) in edges {
+            outgoing[from].push((weight_hash, to));
+            incoming[to].push((weight_hash, from));
+        }
+
+        let mut colors: Vec<u64> = base.iter().map(stable_hash).collect();
+        let mut classes = colors.iter().collect::<HashSet<_>>().len();
+        for _ in 0..vertices.len() {
+            let neighbours = |list: &[(u64, usize)], colors: &[u64]| {
+                let mut described: Vec<(u64, u64)> = list.iter().map(|&(weight_hash, other)| (weight_hash, colors[other])).collect();
+                described.sort_unstable();
+                described
+            };
+            let refined: Vec<u64> = (0..vertices.len())
+                .map(|index| {
+                    stable_hash(&(colors[index], neighbours(&outgoing[index], &colors), neighbours(&incoming[index], &colors)))
+                })
+                .collect();
+            let refined_classes = refined.iter().collect::<HashSet<_>>().len();
+            colors = refined;
+            if refined_classes <= classes {
+                break;
+            }
+            classes = refined_classes;
+        }
+
+        base.into_iter().zip(colors).map(|((ordinal, weight_hash), color)| (ordinal, weight_hash, color)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+
+    fn build(order: &[usize], edges: &[(usize, usize, u32)]) -> SlotMapGraph<u32, u32> {
+        let mut graph = SlotMapGraph::new();
+        let mut ids = vec![None; order.len()];
+        for &index in order {
+            ids[index] = Some(graph.add_vertex(index as u32 % 3));
+        }
+        for &(from, to, weight) in edges {
+            graph.add_edge(ids[from].unwrap(), ids[to].unwrap(), weight);
+        }
+        graph
+    }
+
+    const EDGES: [(usize, usize, u32); 8] = [(0, 1, 1), (1, 2, 1), (2, 3, 2), (3, 4, 1), (4, 5, 1), (5, 0, 2), (1, 4, 7), (3, 3, 0)];
+
+    #[test]
+    fn test_same_content_hashes_equal() {
+        let forward = build(&[0, 1, 2, 3, 4, 5], &EDGES);
+        let mut reversed_edges = EDGES;
+        reversed_edges.reverse();
+        let shuffled = build(&[4, 2, 5, 0, 3, 1], &reversed_edges);
+        assert_eq!(forward.content_digest(), shuffled.content_digest());
+
+        // 留有空槽位不影响结果
+        let mut reused = build(&[5, 4, 3, 2, 1, 0], &EDGES);
+        let extra = reused.add_vertex(9);
+        reused.remove_vertex(extra);
+        assert_eq!(forward.content_digest(), reused.content_digest());
+
+        // 对称的有向环：细化无法区分各顶点，它们共用一个规范序号
+        let cycle: Vec<_> = (0..4).map(|i| (i, (i + 1) % 4, 5)).collect();
+        let mut ring = SlotMapGraph::<u32, u32>::new();
+        let ring_ids: Vec<_> = (0..4).map(|_| ring.add_vertex(0)).collect();
+        for &(from, to, weight) in &cycle {
+            ring.add_edge(ring_ids[from], ring_ids[to], weight);
+        }
+        let mut rotated = SlotMapGraph::<u32, u32>::new();
+        let rotated_ids: Vec<_> = (0..4).map(|_| rotated.add_vertex(0)).collect();
+        for &(from, to, weight) in cycle.iter().rev() {
+            rotated.add_edge(rotated_ids[(from + 2) % 4], rotated_ids[(to + 2) % 4], weight);
+        }
+        assert_eq!(ring.content_digest(), rotated.content_digest());
+    }
+
+    #[test]
+    fn test_changes_alter_digest() {
+        let original = build(&[0, 1, 2, 3, 4, 5], &EDGES).content_digest();
+
+        let mut changed_weight = EDGES;
+        changed_weight[6].2 = 8;
+        assert_ne!(build(&[0, 1, 2, 3, 4, 5], &changed_weight).content_digest(), original);
+
+        let mut reversed = EDGES;
+        reversed[6] = (4, 1, 7);
+        assert_ne!(build(&[0, 1, 2, 3, 4, 5], &reversed).content_digest(), original);
+
+        let mut graph = build(&[0, 1, 2, 3, 4, 5], &EDGES);
+        let vertex = graph.vertex_ids().next().unwrap();
+        *graph.vertex_weight_mut(vertex).unwrap() += 1;
+        assert_ne!(graph.content_digest(), original);
+    }
+}
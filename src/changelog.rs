@@ -0,0 +1,558 @@
+/*!
+ * # 变更日志 (Change Log)
+ *
+ * 持有旧副本的客户端只需要接收变化的部分。开启变更日志后，图按
+ * [`version`](SlotMapGraph::version) 记录每次结构变化（增删顶点和边、清空）：
+ *
+ * - [`enable_change_log`](SlotMapGraph::enable_change_log)：开启日志，可限制保留的条目数，超出时丢弃最早的条目
+ * - [`trim_change_log`](SlotMapGraph::trim_change_log)：手动丢弃不再需要的早期条目
+ * - [`changes_since`](SlotMapGraph::changes_since)：汇总某个版本之后的净变化为 [`ChangeSet`]，
+ *   该版本之后的条目已被丢弃时返回 `None`，客户端需要完整重新同步
+ * - [`ChangeSet::apply`]：把变更集应用到副本，新建元素在副本中的 ID 记录在 [`IdMap`] 中
+ *
+ * ## 净变化
+ *
+ * 变更集只包含净结果：期间新增又删除的元素不出现，新增元素携带生成变更集时的数据。
+ * 修改已有元素的数据不改变版本号，也不会被记录，需要另行同步。
+ *
+ * ## 序列化
+ *
+ * 开启 `serde` 特性后 [`ChangeSet`] 实现 `Serialize` / `Deserialize`，
+ * ID 按 [`as_ffi`](VertexId::as_ffi) 的整数形式编码。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::changelog::IdMap;
+ *
+ * let build = || {
+ *     let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+ *     let root = graph.add_vertex(0);
+ *     (graph, root)
+ * };
+ * let (mut server, root) = build();
+ * let (mut replica, _) = build();
+ * server.enable_change_log(None);
+ *
+ * let synced = server.version();
+ * let leaf = server.add_vertex(1);
+ * server.add_edge(root, leaf, 10);
+ *
+ * let changes = server.changes_since(synced).unwrap();
+ * let mut ids = IdMap::new();
+ * changes.apply(&mut replica, &mut ids).unwrap();
+ * assert_eq!(replica.vertex_weight(ids.vertex(leaf)), Some(&1));
+ * assert_eq!(replica.out_degree(root), 1);
+ * ```
+ */
+
+use crate::error::GraphError;
+use crate::graph::{RemovePolicy, SlotMapGraph};
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, Graph, SupportsClear, SupportsElementRemoval};
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// 一次结构变化
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change<K: Key = DefaultKey> {
+    /// 添加了顶点
+    VertexAdded(VertexId<K>),
+    /// 删除了顶点
+    VertexRemoved(VertexId<K>),
+    /// 添加了边
+    EdgeAdded(EdgeId<K>),
+    /// 删除了边
+    EdgeRemoved(EdgeId<K>),
+    /// 清空了整张图
+    Cleared,
+}
+
+/// 按版本号记录的结构变化
+#[derive(Debug, Clone)]
+pub struct ChangeLog<K: Key = DefaultKey> {
+    /// (变化后的版本号, 变化)，按版本号递增
+    entries: VecDeque<(u64, Change<K>)>,
+    /// 最多保留的条目数，`None` 表示不限
+    capacity: Option<usize>,
+    /// 该版本之后的变化都在日志中
+    floor: u64,
+}
+
+impl<K: Key> ChangeLog<K> {
+    fn new(capacity: Option<usize>, floor: u64) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+            floor,
+        }
+    }
+
+    /// 能够回答 `changes_since` 的最早版本号
+    pub fn floor(&self) -> u64 {
+        self.floor
+    }
+
+    /// 最多保留的条目数，`None` 表示不限
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    /// 保留的条目数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 是否没有条目
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 按版本号遍历保留的条目
+    pub fn iter(&self) -> impl Iterator<Item = (u64, Change<K>)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    fn record(&mut self, version: u64, change: Change<K>) {
+        if self.capacity == Some(0) {
+            self.floor = version;
+            return;
+        }
+        if Some(self.entries.len()) == self.capacity {
+            self.pop_front();
+        }
+        self.entries.push_back((version, change));
+    }
+
+    /// 丢弃版本号不超过 `version` 的条目
+    fn trim(&mut self, version: u64) {
+        while self.entries.front().is_some_and(|(entry, _)| *entry <= version) {
+            self.pop_front();
+        }
+        self.floor = self.floor.max(version);
+    }
+
+    fn pop_front(&mut self) {
+        if let Some((version, _)) = self.entries.pop_front() {
+            self.floor = version;
+        }
+    }
+}
+
+/// 某个版本之后的净结构变化
+///
+/// 应用顺序为：清空（如有）、删除边、删除顶点、添加顶点、添加边。
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "V: serde::Serialize, E: serde::Serialize",
+        deserialize = "V: serde::Deserialize<'de>, E: serde::Deserialize<'de>"
+    ))
+)]
+pub struct ChangeSet<V, E, K: Key = DefaultKey> {
+    /// 变更集的起始版本
+    pub since: u64,
+    /// 生成变更集时图的版本
+    pub version: u64,
+    /// 期间是否清空过整张图，为真时下面的删除列表为空
+    pub cleared: bool,
+    /// 删除的边
+    pub removed_edges: Vec<EdgeId<K>>,
+    /// 删除的顶点
+    pub removed_vertices: Vec<VertexId<K>>,
+    /// 添加的顶点及其数据，按添加顺序
+    pub added_vertices: Vec<(VertexId<K>, V)>,
+    /// 添加的边，按添加顺序
+    pub added_edges: Vec<AddedEdge<E, K>>,
+}
+
+/// 变更集中新增的一条边
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(serialize = "E: serde::Serialize", deserialize = "E: serde::Deserialize<'de>"))
+)]
+pub struct AddedEdge<E, K: Key = DefaultKey> {
+    /// 边在源图中的 ID
+    pub id: EdgeId<K>,
+    /// 起点在源图中的 ID
+    pub from: VertexId<K>,
+    /// 终点在源图中的 ID
+    pub to: VertexId<K>,
+    /// 边数据
+    pub weight: E,
+}
+
+impl<V, E, K: Key> ChangeSet<V, E, K> {
+    /// 是否没有任何变化
+    pub fn is_empty(&self) -> bool {
+        !self.cleared
+            && self.removed_edges.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_vertices.is_empty()
+            && self.added_edges.is_empty()
+    }
+
+    /// 把变化应用到副本
+    ///
+    /// 源图 ID 经 `id_map` 转换为副本 ID，未记录的 ID 视为两侧相同（副本由相同的操作序列构建）。
+    /// 新增元素在副本中的 ID 写入 `id_map`，被删除元素的记录从中移除。
+    ///
+    /// # 错误
+    ///
+    /// 要删除的顶点或新边的端点在副本中不存在时返回 [`GraphError::VertexNotFound`]，
+    /// 此时副本不做任何修改；要删除的边已不存在时直接跳过。
+    /// 被删除的顶点在副本中被固定时返回 [`GraphError::VertexPinned`]。
+    pub fn apply(&self, replica: &mut SlotMapGraph<V, E, K>, id_map: &mut IdMap<K>) -> Result<(), GraphError<K>>
+    where
+        V: Element + Clone,
+        E: Element + Clone,
+    {
+        self.check(replica, id_map)?;
+
+        if self.cleared {
+            SupportsClear::clear(replica);
+            id_map.clear();
+        }
+        for &edge_id in &self.removed_edges {
+            SupportsElementRemoval::remove_edge(replica, id_map.edge(edge_id));
+            id_map.edges.remove(&edge_id);
+        }
+        for &vertex_id in &self.removed_vertices {
+            replica.remove_vertex_policy(id_map.vertex(vertex_id), RemovePolicy::Cascade)?;
+            id_map.vertices.remove(&vertex_id);
+        }
+        for (vertex_id, weight) in &self.added_vertices {
+            let local = replica.add_vertex(weight.clone());
+            id_map.vertices.insert(*vertex_id, local);
+        }
+        for edge in &self.added_edges {
+            let local = replica.add_edge(id_map.vertex(edge.from), id_map.vertex(edge.to), edge.weight.clone());
+            id_map.edges.insert(edge.id, local);
+        }
+        Ok(())
+    }
+
+    /// 确认要删除的顶点和新边端点都能在副本中找到
+    fn check(&self, replica: &SlotMapGraph<V, E, K>, id_map: &IdMap<K>) -> Result<(), GraphError<K>>
+    where
+        V: Element,
+        E: Element,
+    {
+        if !self.cleared {
+            if let Some(&vertex_id) = self
+                .removed_vertices
+                .iter()
+                .find(|vertex_id| !replica.contains_vertex(id_map.vertex(**vertex_id)))
+            {
+                return Err(GraphError::VertexNotFound(vertex_id));
+            }
+        }
+
+        let removed: HashSet<_> = self.removed_vertices.iter().copied().collect();
+        let added: HashSet<_> = self.added_vertices.iter().map(|(vertex_id, _)| *vertex_id).collect();
+        for edge in &self.added_edges {
+            for vertex_id in [edge.from, edge.to] {
+                let present = added.contains(&vertex_id)
+                    || (!self.cleared && !removed.contains(&vertex_id) && replica.contains_vertex(id_map.vertex(vertex_id)));
+                if !present {
+                    return Err(GraphError::VertexNotFound(vertex_id));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 源图 ID 到副本 ID 的映射
+#[derive(Debug, Clone, Default)]
+pub struct IdMap<K: Key = DefaultKey> {
+    vertices: HashMap<VertexId<K>, VertexId<K>>,
+    edges: HashMap<EdgeId<K>, EdgeId<K>>,
+}
+
+impl<K: Key> IdMap<K> {
+    /// 创建空映射
+    pub fn new() -> Self {
+        Self {
+            vertices: HashMap::new(),
+            edges: HashMap::new(),
+        }
+    }
+
+    /// 源图顶点在副本中的 ID，未记录时原样返回
+    pub fn vertex(&self, source: VertexId<K>) -> VertexId<K> {
+        self.vertices.get(&source).copied().unwrap_or(source)
+    }
+
+    /// 源图边在副本中的 ID，未记录时原样返回
+    pub fn edge(&self, source: EdgeId<K>) -> EdgeId<K> {
+        self.edges.get(&source).copied().unwrap_or(source)
+    }
+
+    /// 记录的映射数量
+    pub fn len(&self) -> usize {
+        self.vertices.len() + self.edges.len()
+    }
+
+    /// 是否没有记录任何映射
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 清空映射
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 开启变更日志，`capacity` 限制保留的条目数，`None` 表示不限
+    ///
+    /// 已开启时只修改容量，超出的早期条目被丢弃。
+    pub fn enable_change_log(&mut self, capacity: Option<usize>) {
+        let version = self.version();
+        let log = self.change_log.get_or_insert_with(|| ChangeLog::new(capacity, version));
+        log.capacity = capacity;
+        if let Some(capacity) = capacity {
+            while log.entries.len() > capacity {
+                log.pop_front();
+            }
+        }
+    }
+
+    /// 关闭变更日志并丢弃全部条目
+    pub fn disable_change_log(&mut self) {
+        self.change_log = None;
+    }
+
+    /// 当前的变更日志，未开启时返回 `None`
+    pub fn change_log(&self) -> Option<&ChangeLog<K>> {
+        self.change_log.as_ref()
+    }
+
+    /// 丢弃版本号不超过 `version` 的条目，之后 `changes_since` 只接受不早于 `version` 的版本
+    pub fn trim_change_log(&mut self, version: u64) {
+        let version = version.min(self.version());
+        if let Some(log) = self.change_log.as_mut() {
+            log.trim(version);
+        }
+    }
+
+    /// `version` 之后的净结构变化
+    ///
+    /// 日志未开启、`version` 早于日志的 [`floor`](ChangeLog::floor) 或晚于当前版本时返回 `None`。
+    pub fn changes_since(&self, version: u64) -> Option<ChangeSet<Vertex, Edge, K>>
+    where
+        Vertex: Clone,
+        Edge: Clone,
+    {
+        let log = self.change_log.as_ref()?;
+        if version < log.floor || version > self.version() {
+            return None;
+        }
+
+        let mut window: Vec<_> = log.iter().filter(|(entry, _)| *entry > version).map(|(_, change)| change).collect();
+        let cleared = match window.iter().rposition(|change| *change == Change::Cleared) {
+            Some(last_clear) => {
+                window.drain(..=last_clear);
+                true
+            }
+            None => false,
+        };
+
+        // 期间新增的元素若已删除则两侧都不出现；期间删除的元素若不是期间新增的，则在起始版本中存在
+        let mut added_vertices = Vec::new();
+        let mut added_edges = Vec::new();
+        let mut removed_vertices = Vec::new();
+        let mut removed_edges = Vec::new();
+        for change in &window {
+            match *change {
+                Change::VertexAdded(vertex_id) => added_vertices.push(vertex_id),
+                Change::EdgeAdded(edge_id) => added_edges.push(edge_id),
+                Change::VertexRemoved(vertex_id) => removed_vertices.push(vertex_id),
+                Change::EdgeRemoved(edge_id) => removed_edges.push(edge_id),
+                Change::Cleared => unreachable!("window starts after the last clear"),
+            }
+        }
+        let new_vertices: HashSet<_> = added_vertices.iter().copied().collect();
+        let new_edges: HashSet<_> = added_edges.iter().copied().collect();
+
+        Some(ChangeSet {
+            since: version,
+            version: self.version(),
+            cleared,
+            removed_edges: removed_edges.into_iter().filter(|edge_id| !new_edges.contains(edge_id)).collect(),
+            removed_vertices: removed_vertices
+                .into_iter()
+                .filter(|vertex_id| !new_vertices.contains(vertex_id))
+                .collect(),
+            added_vertices: added_vertices
+                .into_iter()
+                .filter_map(|vertex_id| Some((vertex_id, self.vertex_weight(vertex_id)?.clone())))
+                .collect(),
+            added_edges: added_edges
+                .into_iter()
+                .filter_map(|id| {
+                    let (from, to) = self.edge_endpoints(id)?;
+                    let weight = self.edge_weight(id)?.clone();
+                    Some(AddedEdge { id, from, to, weight })
+                })
+                .collect(),
+        })
+    }
+
+    /// 记录一次结构变化，在版本号递增之后调用
+    pub(crate) fn record_change(&mut self, change: Change<K>) {
+        let version = self.version();
+        if let Some(log) = self.change_log.as_mut() {
+            log.record(version, change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_same_structure;
+
+    /// 源图和副本都由这一组操作构建，ID 相同
+    fn base() -> (SlotMapGraph<u32, u32>, [VertexId; 3]) {
+        let mut graph = SlotMapGraph::new();
+        let [a, b, c] = [1, 2, 3].map(|weight| graph.add_vertex(weight));
+        graph.add_edge(a, b, 12);
+        graph.add_edge(b, c, 23);
+        (graph, [a, b, c])
+    }
+
+    #[test]
+    fn test_changeset_syncs_replica() {
+        let (mut server, [a, b, c]) = base();
+        let (mut replica, _) = base();
+        server.enable_change_log(None);
+        // 让副本中新元素的 ID 与源图不同，检验 ID 映射
+        let scratch = replica.add_vertex(0);
+        replica.remove_vertex(scratch);
+
+        let v0 = server.version();
+        let d = server.add_vertex(4);
+        server.add_edge(c, d, 34);
+        server.add_edge(d, a, 41);
+        server.remove_vertex(b);
+        let temp = server.add_vertex(99);
+        server.add_edge(temp, a, 0);
+        server.remove_vertex(temp);
+
+        let changes = server.changes_since(v0).unwrap();
+        assert!(!changes.cleared);
+        assert_eq!(changes.removed_vertices, vec![b]);
+        assert_eq!(changes.removed_edges.len(), 2);
+        assert_eq!(changes.added_vertices, vec![(d, 4)]);
+        assert_eq!(changes.added_edges.len(), 2);
+
+        let mut ids = IdMap::new();
+        changes.apply(&mut replica, &mut ids).unwrap();
+        assert_same_structure!(replica, server);
+        assert_ne!(ids.vertex(d), d);
+        assert_eq!(replica.vertex_weight(ids.vertex(d)), Some(&4));
+
+        // 第二轮变化引用第一轮新建的元素
+        let v1 = server.version();
+        let e = server.add_vertex(5);
+        server.add_edge(d, e, 45);
+        let da = server.find_edge(d, a).unwrap();
+        server.remove_edge(da);
+        server.changes_since(v1).unwrap().apply(&mut replica, &mut ids).unwrap();
+        assert_same_structure!(replica, server);
+        assert!(!replica.has_edge(ids.vertex(d), a));
+        assert_eq!(replica.validate(), Ok(()));
+
+        assert!(server.changes_since(server.version()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_clear_and_failed_apply() {
+        let (mut server, _) = base();
+        let (mut replica, _) = base();
+        server.enable_change_log(None);
+        let v0 = server.version();
+        SupportsClear::clear(&mut server);
+        let x = server.add_vertex(7);
+        server.add_edge(x, x, 77);
+
+        let changes = server.changes_since(v0).unwrap();
+        assert!(changes.cleared);
+        let mut ids = IdMap::new();
+        changes.apply(&mut replica, &mut ids).unwrap();
+        assert_same_structure!(replica, server);
+
+        // 副本缺少端点时不做任何修改
+        let (mut source, [a, b, _]) = base();
+        source.enable_change_log(None);
+        let v1 = source.version();
+        source.add_edge(b, a, 21);
+        let (mut stale, _) = base();
+        stale.remove_vertex(a);
+        let version = stale.version();
+        assert_eq!(
+            source.changes_since(v1).unwrap().apply(&mut stale, &mut IdMap::new()),
+            Err(GraphError::VertexNotFound(a))
+        );
+        assert_eq!(stale.version(), version);
+    }
+
+    #[test]
+    fn test_bounded_log_forces_resync() {
+        let (mut graph, [a, b, _]) = base();
+        assert!(graph.changes_since(graph.version()).is_none());
+
+        graph.enable_change_log(Some(2));
+        let v0 = graph.version();
+        assert!(graph.changes_since(v0 - 1).is_none());
+        assert!(graph.changes_since(v0 + 1).is_none());
+
+        graph.add_edge(a, b, 1);
+        let v1 = graph.version();
+        graph.add_edge(a, b, 2);
+        assert!(graph.changes_since(v0).is_some());
+        // 第三条变化挤掉第一条，v0 之后的变化不再完整
+        graph.add_edge(a, b, 3);
+        assert!(graph.changes_since(v0).is_none());
+        assert_eq!(graph.changes_since(v1).unwrap().added_edges.len(), 2);
+
+        graph.enable_change_log(None);
+        graph.trim_change_log(v1 + 1);
+        assert_eq!(graph.change_log().unwrap().len(), 1);
+        assert!(graph.changes_since(v1).is_none());
+        assert_eq!(graph.changes_since(v1 + 1).unwrap().added_edges.len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_changeset_serde_roundtrip() {
+        let (mut server, [a, _, c]) = base();
+        let (mut replica, _) = base();
+        server.enable_change_log(None);
+        let v0 = server.version();
+        let d = server.add_vertex(4);
+        server.add_edge(c, d, 34);
+        server.remove_vertex(a);
+
+        let json = serde_json::to_string(&server.changes_since(v0).unwrap()).unwrap();
+        let changes: ChangeSet<u32, u32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(changes, server.changes_since(v0).unwrap());
+        changes.apply(&mut replica, &mut IdMap::new()).unwrap();
+        assert_same_structure!(replica, server);
+    }
+}
@@ -0,0 +1,302 @@
+/*!
+ * # ID 集合 (ID Sets)
+ *
+ * 以 SlotMap 槽位下标为位置的顶点/边 ID 集合，用于替代算法中作为访问标记的 `HashSet`。
+ *
+ * ## 存储
+ *
+ * 每个槽位占一个比特用于快速判断，另记录写入该槽位的完整键，
+ * 因此 `insert`、`contains`、`remove` 都只需按下标访问数组，不做哈希。
+ * 槽位被删除后重用时，新旧 ID 的版本号不同，新 ID 不会因旧 ID 在集合中而被误判为已存在。
+ * 同一槽位同时只保存一个版本：插入新版本会替换已失效的旧版本，
+ * 除此之外与存放有效 ID 的 `HashSet` 行为一致。
+ *
+ * 空间与图的槽位数成正比，而不是与集合中的元素数成正比：
+ * 适合访问大部分顶点的遍历，只标记少量顶点时 `HashSet` 更省内存。
+ * 集合会按需扩容，[`SlotMapGraph::empty_vertex_set`] 按图当前的容量预先分配。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+
+/// 按槽位下标存放键的集合
+#[derive(Debug, Clone)]
+struct SlotSet<K: Key> {
+    /// 每个槽位是否在集合中
+    words: Vec<u64>,
+    /// 每个槽位最近一次写入的键
+    keys: Vec<K>,
+    /// 集合中的元素数量
+    len: usize,
+}
+
+impl<K: Key> SlotSet<K> {
+    fn with_slots(slots: usize) -> Self {
+        Self {
+            words: vec![0; slots.div_ceil(64)],
+            keys: vec![K::default(); slots],
+            len: 0,
+        }
+    }
+
+    fn slot(key: K) -> usize {
+        key.data().index() as usize
+    }
+
+    fn insert(&mut self, key: K) -> bool {
+        let slot = Self::slot(key);
+        if slot >= self.keys.len() {
+            self.keys.resize((slot + 1).next_power_of_two(), K::default());
+            self.words.resize(self.keys.len().div_ceil(64), 0);
+        }
+        let (word, bit) = (slot / 64, 1u64 << (slot % 64));
+        if self.words[word] & bit != 0 {
+            if self.keys[slot] == key {
+                return false;
+            }
+            // 同一槽位的旧版本被替换
+            self.len -= 1;
+        }
+        self.words[word] |= bit;
+        self.keys[slot] = key;
+        self.len += 1;
+        true
+    }
+
+    fn contains(&self, key: K) -> bool {
+        let slot = Self::slot(key);
+        slot < self.keys.len() && self.words[slot / 64] & (1 << (slot % 64)) != 0 && self.keys[slot] == key
+    }
+
+    fn remove(&mut self, key: K) -> bool {
+        if !self.contains(key) {
+            return false;
+        }
+        let slot = Self::slot(key);
+        self.words[slot / 64] &= !(1 << (slot % 64));
+        self.len -= 1;
+        true
+    }
+
+    fn clear(&mut self) {
+        self.words.fill(0);
+        self.len = 0;
+    }
+
+    /// 按槽位下标升序产生集合中的键
+    fn iter(&self) -> impl Iterator<Item = K> + '_ {
+        self.words.iter().enumerate().flat_map(move |(index, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(self.keys[index * 64 + bit])
+            })
+        })
+    }
+}
+
+macro_rules! id_set {
+    ($(#[$meta:meta])* $name:ident, $id:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name<K: Key = DefaultKey> {
+            slots: SlotSet<K>,
+        }
+
+        impl<K: Key> Default for $name<K> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<K: Key> $name<K> {
+            /// 创建空集合
+            pub fn new() -> Self {
+                Self::with_slots(0)
+            }
+
+            /// 创建预先容纳 `slots` 个槽位的空集合
+            pub fn with_slots(slots: usize) -> Self {
+                Self {
+                    slots: SlotSet::with_slots(slots),
+                }
+            }
+
+            /// 插入 ID，已存在时返回 `false`
+            pub fn insert(&mut self, id: $id<K>) -> bool {
+                self.slots.insert(id.key())
+            }
+
+            /// 是否包含 ID
+            pub fn contains(&self, id: $id<K>) -> bool {
+                self.slots.contains(id.key())
+            }
+
+            /// 移除 ID，不存在时返回 `false`
+            pub fn remove(&mut self, id: $id<K>) -> bool {
+                self.slots.remove(id.key())
+            }
+
+            /// 元素数量
+            pub fn len(&self) -> usize {
+                self.slots.len
+            }
+
+            /// 是否为空
+            pub fn is_empty(&self) -> bool {
+                self.slots.len == 0
+            }
+
+            /// 清空集合，保留已分配的空间
+            pub fn clear(&mut self) {
+                self.slots.clear();
+            }
+
+            /// 按槽位下标升序遍历
+            pub fn iter(&self) -> impl Iterator<Item = $id<K>> + '_ {
+                self.slots.iter().map($id::new)
+            }
+        }
+
+        impl<K: Key> Extend<$id<K>> for $name<K> {
+            fn extend<I: IntoIterator<Item = $id<K>>>(&mut self, iter: I) {
+                for id in iter {
+                    self.insert(id);
+                }
+            }
+        }
+
+        impl<K: Key> FromIterator<$id<K>> for $name<K> {
+            fn from_iter<I: IntoIterator<Item = $id<K>>>(iter: I) -> Self {
+                let mut set = Self::new();
+                set.extend(iter);
+                set
+            }
+        }
+    };
+}
+
+id_set!(
+    /// 以槽位下标为位置的顶点 ID 集合
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    ///
+    /// let mut visited = graph.empty_vertex_set();
+    /// assert!(visited.insert(a));
+    /// assert!(!visited.insert(a));
+    /// assert!(visited.contains(a) && !visited.contains(b));
+    /// assert_eq!(visited.iter().collect::<Vec<_>>(), vec![a]);
+    /// ```
+    VertexIdSet,
+    VertexId
+);
+
+id_set!(
+    /// 以槽位下标为位置的边 ID 集合
+    EdgeIdSet,
+    EdgeId
+);
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 按当前顶点容量预先分配的空顶点集合
+    pub fn empty_vertex_set(&self) -> VertexIdSet<K> {
+        VertexIdSet::with_slots(self.vertices.slot_capacity())
+    }
+
+    /// 按当前边容量预先分配的空边集合
+    pub fn empty_edge_set(&self) -> EdgeIdSet<K> {
+        EdgeIdSet::with_slots(self.edges.slot_capacity())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_matches_hash_set_under_random_operations() {
+        // 固定种子的 xorshift，保证测试可复现
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % n
+        };
+
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let mut ids: Vec<_> = (0..64).map(|_| graph.add_vertex(())).collect();
+        let mut set = graph.empty_vertex_set();
+        let mut expected = HashSet::new();
+
+        for _ in 0..5_000 {
+            let id = ids[next(ids.len())];
+            match next(4) {
+                0 | 1 => assert_eq!(set.insert(id), expected.insert(id)),
+                2 => assert_eq!(set.remove(id), expected.remove(&id)),
+                _ => {
+                    // 删除顶点后槽位被新顶点重用
+                    graph.remove_vertex(id);
+                    set.remove(id);
+                    expected.remove(&id);
+                    ids.retain(|v| *v != id);
+                    ids.push(graph.add_vertex(()));
+                }
+            }
+            assert_eq!(set.contains(id), expected.contains(&id));
+            assert_eq!(set.len(), expected.len());
+        }
+        assert_eq!(set.iter().collect::<HashSet<_>>(), expected);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_stale_id_in_reused_slot() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let edge = graph.add_edge(a, b, ());
+
+        let mut edges: EdgeIdSet = [edge].into_iter().collect();
+        let mut vertices = VertexIdSet::new();
+        vertices.insert(a);
+        graph.remove_vertex(a);
+        let reused = graph.add_vertex(());
+        assert_eq!(reused.key().data().index(), a.key().data().index());
+
+        assert!(!vertices.contains(reused));
+        assert!(!vertices.remove(reused));
+        // 新版本替换同一槽位中的旧版本
+        assert!(vertices.insert(reused));
+        assert!(!vertices.contains(a));
+        assert_eq!(vertices.len(), 1);
+
+        assert!(edges.contains(edge));
+        assert!(edges.remove(edge));
+        assert!(edges.is_empty());
+    }
+}
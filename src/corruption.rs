@@ -0,0 +1,143 @@
+/*!
+ * # 破坏注入 (Corruption Injection)
+ *
+ * **仅供测试。** 开启 `test-internals` 特性（默认关闭）后，
+ * [`corrupt_for_test`](SlotMapGraph::corrupt_for_test) 绕过正常的增删路径直接修改底层容器，
+ * 构造公共接口无法产生的不一致状态，用于测试 [`validate`](SlotMapGraph::validate)、
+ * [`repair`](SlotMapGraph::repair) 等诊断与恢复功能，集成测试不必访问私有字段。
+ * 正式构建不要开启该特性。
+ *
+ * | 破坏 | 修改 | `validate` 报告 |
+ * |------|------|-----------------|
+ * | [`DanglingEdgeEndpoint`](CorruptionKind::DanglingEdgeEndpoint) | 只从顶点存储删除顶点 | [`GraphError::DanglingEdge`] |
+ * | [`OrphanConnectionEntry`](CorruptionKind::OrphanConnectionEntry) | 出边列表中加入不存在的边 | [`GraphError::AdjacencyMismatch`] |
+ * | [`StaleIndexEntry`](CorruptionKind::StaleIndexEntry) | 只从边存储删除边 | [`GraphError::LabelIndexMismatch`] |
+ * | [`WrongEdgeInfoId`](CorruptionKind::WrongEdgeInfoId) | 改写连接信息中的边 ID | [`GraphError::EdgeInfoMismatch`] |
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::{GraphError, SlotMapGraph};
+ * use pi_slotmap_graph::corruption::CorruptionKind;
+ *
+ * let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+ * let a = graph.add_vertex(());
+ * let b = graph.add_vertex(());
+ * let edge = graph.add_edge(a, b, ());
+ *
+ * assert!(graph.corrupt_for_test(CorruptionKind::DanglingEdgeEndpoint(b)));
+ * assert_eq!(graph.validate(), Err(GraphError::DanglingEdge { edge, vertex: b }));
+ * assert_eq!(graph.repair(), vec![edge]);
+ * assert_eq!(graph.validate(), Ok(()));
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+
+/// 注入的不一致状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CorruptionKind<K: Key = DefaultKey> {
+    /// 只从顶点存储中删除顶点，其关联边、邻接表和索引保持不变，关联边成为悬空边
+    DanglingEdgeEndpoint(VertexId<K>),
+    /// 在顶点的出边列表末尾加入一条不存在的边，度数计数器不变
+    OrphanConnectionEntry(VertexId<K>),
+    /// 从边存储和邻接表中删除边，但保留其边标签索引项
+    StaleIndexEntry(EdgeId<K>),
+    /// 把边的连接信息中记录的边 ID 改为空 ID
+    WrongEdgeInfoId(EdgeId<K>),
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 注入 `kind` 描述的不一致状态，仅供测试
+    ///
+    /// 目标顶点或边不存在时不做修改并返回 `false`。注入后除 `validate` 与 `repair` 外，
+    /// 其他方法的行为没有保证。
+    pub fn corrupt_for_test(&mut self, kind: CorruptionKind<K>) -> bool {
+        match kind {
+            CorruptionKind::DanglingEdgeEndpoint(vertex_id) => self.vertices.remove(vertex_id).is_some(),
+            CorruptionKind::OrphanConnectionEntry(vertex_id) => {
+                if !self.vertices.contains(vertex_id) {
+                    return false;
+                }
+                self.edges.push_unchecked_outgoing(vertex_id, EdgeId::default());
+                true
+            }
+            CorruptionKind::StaleIndexEntry(edge_id) => self.edges.remove(edge_id).is_some(),
+            CorruptionKind::WrongEdgeInfoId(edge_id) => self.edges.overwrite_info_id(edge_id, EdgeId::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::GraphError;
+    use graph_api_lib::Graph;
+
+    /// a -> b、b -> c、c -> c、a -> c，并开启端点对索引
+    fn sample() -> (SlotMapGraph<u32, u32>, [VertexId; 3], [EdgeId; 4]) {
+        let mut graph = SlotMapGraph::new();
+        graph.enable_pair_index();
+        let [a, b, c] = [0, 1, 2].map(|name| graph.add_vertex(name));
+        let edges = [(a, b), (b, c), (c, c), (a, c)].map(|(from, to)| graph.add_edge(from, to, 0));
+        (graph, [a, b, c], edges)
+    }
+
+    #[test]
+    fn test_each_corruption_is_reported_and_repaired() {
+        let (_, [a, b, _], [ab, bc, cc, _]) = sample();
+        let cases = [
+            (CorruptionKind::DanglingEdgeEndpoint(b), GraphError::DanglingEdge { edge: ab, vertex: b }),
+            (CorruptionKind::OrphanConnectionEntry(a), GraphError::AdjacencyMismatch { vertex: a }),
+            (CorruptionKind::StaleIndexEntry(bc), GraphError::LabelIndexMismatch { edge: bc }),
+            (CorruptionKind::WrongEdgeInfoId(cc), GraphError::EdgeInfoMismatch { edge: cc }),
+        ];
+        for (kind, expected) in cases {
+            let (mut graph, ..) = sample();
+            assert_eq!(graph.validate(), Ok(()));
+            assert!(graph.corrupt_for_test(kind));
+            assert_eq!(graph.validate(), Err(expected));
+
+            let removed = graph.repair();
+            assert_eq!(graph.validate(), Ok(()), "{kind:?}");
+            // 只有悬空边被删除，其余的边仍可按标签和端点查到
+            let dangling = if let CorruptionKind::DanglingEdgeEndpoint(_) = kind { vec![ab, bc] } else { Vec::new() };
+            assert_eq!(removed, dangling);
+            assert_eq!(graph.edges_with_label(()).count(), graph.edge_count());
+            for edge in graph.edge_ids().collect::<Vec<_>>() {
+                let (from, to) = graph.edge_endpoints(edge).unwrap();
+                assert_eq!(graph.find_edge(from, to), Some(edge));
+            }
+            assert_eq!(graph.vertices_with_label(()).count(), graph.vertex_count());
+        }
+    }
+
+    #[test]
+    fn test_repair_keeps_consistent_graph_and_adjacency_order() {
+        let (mut graph, [a, _, c], [ab, _, cc, ac]) = sample();
+        assert!(graph.repair().is_empty());
+        assert_eq!(graph.outgoing_adjacency(a), &[ab, ac]);
+
+        assert!(graph.corrupt_for_test(CorruptionKind::OrphanConnectionEntry(c)));
+        assert!(graph.corrupt_for_test(CorruptionKind::WrongEdgeInfoId(ac)));
+        assert!(graph.repair().is_empty());
+        assert_eq!(graph.outgoing_adjacency(a), &[ab, ac]);
+        assert_eq!(graph.outgoing_adjacency(c), &[cc]);
+        assert_eq!(graph.degree(c), 3);
+
+        let missing = VertexId::default();
+        assert!(!graph.corrupt_for_test(CorruptionKind::DanglingEdgeEndpoint(missing)));
+        assert!(!graph.corrupt_for_test(CorruptionKind::OrphanConnectionEntry(missing)));
+        assert!(!graph.corrupt_for_test(CorruptionKind::StaleIndexEntry(EdgeId::default())));
+        assert_eq!(graph.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,178 @@
+/*!
+ * # 操作计数 (Operation Counters)
+ *
+ * 可选的 [`GraphMetrics`] 记录一个长期运行的图实例上发生过的结构修改：
+ * 增删顶点和边的次数、清空次数，以及顶点数和边数曾经达到的峰值。
+ *
+ * 计数默认关闭，由 [`SlotMapGraph::enable_metrics`] 开启。
+ * 关闭时每次修改只多一次 `Option` 判断，开启后只做整数自增和比较。
+ *
+ * ## 计数规则
+ *
+ * - 删除顶点时级联删除的边逐条计入 `edges_removed`
+ * - 清空只计入 `clears`，被清空的顶点和边不计入删除次数
+ * - 修改权重、调整边顺序不计数
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::{Graph, SupportsElementRemoval};
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+ * graph.enable_metrics();
+ * let a = graph.add_vertex(());
+ * let b = graph.add_vertex(());
+ * graph.add_edge(a, b, ());
+ * graph.remove_vertex(a);
+ *
+ * let metrics = graph.metrics().unwrap();
+ * assert_eq!((metrics.vertices_added, metrics.vertices_removed), (2, 1));
+ * assert_eq!((metrics.edges_added, metrics.edges_removed), (1, 1));
+ * assert_eq!((metrics.peak_vertices, metrics.peak_edges), (2, 1));
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use graph_api_lib::Element;
+use pi_slotmap::Key;
+
+/// 图结构修改的累计计数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GraphMetrics {
+    /// 添加顶点的次数
+    pub vertices_added: u64,
+    /// 删除顶点的次数
+    pub vertices_removed: u64,
+    /// 添加边的次数
+    pub edges_added: u64,
+    /// 删除边的次数，包括删除顶点时级联删除的边
+    pub edges_removed: u64,
+    /// 清空图的次数
+    pub clears: u64,
+    /// 顶点数量的峰值
+    pub peak_vertices: usize,
+    /// 边数量的峰值
+    pub peak_edges: usize,
+}
+
+impl GraphMetrics {
+    /// 以当前的顶点数和边数作为峰值起点
+    fn starting_at(vertices: usize, edges: usize) -> Self {
+        Self {
+            peak_vertices: vertices,
+            peak_edges: edges,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn vertex_added(&mut self, vertices: usize) {
+        self.vertices_added += 1;
+        self.peak_vertices = self.peak_vertices.max(vertices);
+    }
+
+    pub(crate) fn vertex_removed(&mut self) {
+        self.vertices_removed += 1;
+    }
+
+    pub(crate) fn edge_added(&mut self, edges: usize) {
+        self.edges_added += 1;
+        self.peak_edges = self.peak_edges.max(edges);
+    }
+
+    pub(crate) fn edge_removed(&mut self) {
+        self.edges_removed += 1;
+    }
+
+    pub(crate) fn cleared(&mut self) {
+        self.clears += 1;
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 开启操作计数，已开启时保留现有计数
+    ///
+    /// 峰值从当前的顶点数和边数开始记录。
+    pub fn enable_metrics(&mut self) {
+        if self.metrics.is_none() {
+            self.metrics = Some(GraphMetrics::starting_at(self.vertex_count(), self.edge_count()));
+        }
+    }
+
+    /// 关闭操作计数并丢弃已有计数
+    pub fn disable_metrics(&mut self) {
+        self.metrics = None;
+    }
+
+    /// 当前的操作计数，未开启时返回 `None`
+    pub fn metrics(&self) -> Option<&GraphMetrics> {
+        self.metrics.as_ref()
+    }
+
+    /// 计数归零，峰值重置为当前的顶点数和边数；未开启时不做任何事
+    pub fn reset_metrics(&mut self) {
+        if self.metrics.is_some() {
+            self.metrics = Some(GraphMetrics::starting_at(self.vertex_count(), self.edge_count()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsClear, SupportsElementRemoval};
+
+    #[test]
+    fn test_scripted_operations() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let before = graph.add_vertex(());
+        assert!(graph.metrics().is_none());
+
+        graph.enable_metrics();
+        assert_eq!(graph.metrics().unwrap().peak_vertices, 1);
+
+        let vertices: Vec<_> = (0..4).map(|_| graph.add_vertex(())).collect();
+        for window in vertices.windows(2) {
+            graph.add_edge(window[0], window[1], ());
+        }
+        graph.add_edge(before, vertices[0], ());
+        let loop_edge = graph.add_edge(vertices[1], vertices[1], ());
+        graph.remove_edge(loop_edge);
+        // 重复删除不计数
+        graph.remove_edge(loop_edge);
+        // 级联删除两条边
+        graph.remove_vertex(vertices[3]);
+        graph.remove_vertex(vertices[0]);
+        graph.vertex_weight_mut(before);
+
+        assert_eq!(
+            *graph.metrics().unwrap(),
+            GraphMetrics {
+                vertices_added: 4,
+                vertices_removed: 2,
+                edges_added: 5,
+                edges_removed: 4,
+                clears: 0,
+                peak_vertices: 5,
+                peak_edges: 5,
+            }
+        );
+
+        SupportsClear::clear(&mut graph);
+        graph.add_vertex(());
+        let metrics = graph.metrics().unwrap();
+        assert_eq!((metrics.clears, metrics.vertices_added, metrics.peak_vertices), (1, 5, 5));
+
+        graph.reset_metrics();
+        assert_eq!(*graph.metrics().unwrap(), GraphMetrics::starting_at(1, 0));
+
+        graph.disable_metrics();
+        graph.add_vertex(());
+        assert!(graph.metrics().is_none());
+    }
+}
@@ -0,0 +1,190 @@
+/*!
+ * # 延迟删除 (Deferred Removal)
+ *
+ * 遍历 `graph.edges(v, ..)` 等迭代器时图被不可变借用，无法同时删除元素，
+ * 通常只能先把 ID 收集到 `Vec` 中再逐个删除。
+ * [`SlotMapGraph::with_deferred_removals`] 把这一步收进图内部：
+ * 回调中通过 [`DeferredGraph`] 读取图并登记待删除的元素，回调返回后统一删除。
+ *
+ * ## 删除顺序
+ *
+ * 先按登记顺序删除边，再删除顶点（连同其剩余的关联边）。
+ * 重复登记或已不存在的元素会被忽略。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::{EdgeReference, EdgeSearch, Graph};
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+ * let hub = graph.add_vertex(());
+ * for weight in 0..4 {
+ *     let leaf = graph.add_vertex(());
+ *     graph.add_edge(hub, leaf, weight);
+ * }
+ *
+ * graph.with_deferred_removals(|graph| {
+ *     for edge in graph.edges(hub, &EdgeSearch::scan().outgoing()) {
+ *         if edge.weight() % 2 == 0 {
+ *             graph.queue_remove_edge(edge.id());
+ *         }
+ *     }
+ * });
+ * assert_eq!(graph.out_degree(hub), 2);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, SupportsElementRemoval};
+use pi_slotmap::{DefaultKey, Key};
+use std::cell::RefCell;
+use std::ops::Deref;
+
+/// 延迟删除模式下的图视图
+///
+/// 通过 `Deref` 提供图的全部只读接口，删除操作只登记到内部缓冲区，
+/// 在 [`SlotMapGraph::with_deferred_removals`] 的回调返回后执行。
+#[derive(Debug)]
+pub struct DeferredGraph<'graph, Vertex, Edge, K = DefaultKey>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 被借用的图
+    graph: &'graph SlotMapGraph<Vertex, Edge, K>,
+    /// 待删除的边
+    edges: RefCell<Vec<EdgeId<K>>>,
+    /// 待删除的顶点
+    vertices: RefCell<Vec<VertexId<K>>>,
+}
+
+impl<Vertex, Edge, K> DeferredGraph<'_, Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 登记待删除的边
+    pub fn queue_remove_edge(&self, edge_id: EdgeId<K>) {
+        self.edges.borrow_mut().push(edge_id);
+    }
+
+    /// 登记待删除的顶点，其关联边会一起删除
+    pub fn queue_remove_vertex(&self, vertex_id: VertexId<K>) {
+        self.vertices.borrow_mut().push(vertex_id);
+    }
+
+    /// 已登记的边和顶点数量
+    pub fn queued_len(&self) -> usize {
+        self.edges.borrow().len() + self.vertices.borrow().len()
+    }
+}
+
+impl<Vertex, Edge, K> Deref for DeferredGraph<'_, Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    type Target = SlotMapGraph<Vertex, Edge, K>;
+
+    fn deref(&self) -> &Self::Target {
+        self.graph
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 以延迟删除模式执行 `f`，`f` 返回后删除其中登记的全部元素
+    ///
+    /// 返回 `f` 的返回值。
+    pub fn with_deferred_removals<R>(&mut self, f: impl FnOnce(&DeferredGraph<'_, Vertex, Edge, K>) -> R) -> R {
+        let deferred = DeferredGraph {
+            graph: self,
+            edges: RefCell::new(Vec::new()),
+            vertices: RefCell::new(Vec::new()),
+        };
+        let result = f(&deferred);
+        let (edges, vertices) = (deferred.edges.into_inner(), deferred.vertices.into_inner());
+
+        for edge_id in edges {
+            self.remove_edge_internal(edge_id);
+        }
+        for vertex_id in vertices {
+            SupportsElementRemoval::remove_vertex(self, vertex_id);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{EdgeReference, EdgeSearch, Graph};
+
+    #[test]
+    fn test_remove_every_other_out_edge_while_iterating() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let hub = graph.add_vertex(());
+        for weight in 0..10 {
+            let leaf = graph.add_vertex(());
+            graph.add_edge(hub, leaf, weight);
+        }
+        let source = graph.add_vertex(());
+        let incoming = graph.add_edge(source, hub, 100);
+
+        let removed = graph.with_deferred_removals(|graph| {
+            let mut removed = Vec::new();
+            for (index, edge) in graph.edges(hub, &EdgeSearch::scan().outgoing()).enumerate() {
+                if index % 2 == 0 {
+                    graph.queue_remove_edge(edge.id());
+                    // 重复登记被忽略
+                    graph.queue_remove_edge(edge.id());
+                    removed.push(*edge.weight());
+                }
+            }
+            // 回调返回前图保持不变
+            assert_eq!(graph.edge_count(), 11);
+            removed
+        });
+
+        assert_eq!(removed.len(), 5);
+        assert_eq!(graph.out_degree(hub), 5);
+        assert!(graph.contains_edge(incoming));
+        let mut remaining: Vec<u32> = graph.all_edges().map(|(_, weight, _, _)| *weight).collect();
+        remaining.sort();
+        let mut expected: Vec<u32> = (0..10).filter(|weight| !removed.contains(weight)).chain([100]).collect();
+        expected.sort();
+        assert_eq!(remaining, expected);
+        assert!(graph.validate().is_ok());
+    }
+
+    #[test]
+    fn test_queued_vertex_removal() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..6).map(|weight| graph.add_vertex(weight)).collect();
+        for pair in ids.windows(2) {
+            graph.add_edge(pair[0], pair[1], ());
+        }
+
+        graph.with_deferred_removals(|graph| {
+            for (id, weight) in graph.all_vertices() {
+                if weight % 3 == 0 {
+                    graph.queue_remove_vertex(id);
+                }
+            }
+            assert_eq!(graph.queued_len(), 2);
+        });
+
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+        assert!(graph.validate().is_ok());
+    }
+}
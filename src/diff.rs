@@ -0,0 +1,332 @@
+/*!
+ * # 图比较 (Graph Diff)
+ *
+ * 比较两张图并列出差异，主要供测试断言 [`assert_graph_eq!`](crate::assert_graph_eq)
+ * 和 [`assert_same_structure!`](crate::assert_same_structure) 在失败时输出可读的说明。
+ *
+ * ## 按 ID 比较
+ *
+ * [`SlotMapGraph::diff`] 以 ID 对齐两张图的元素，适合比较同一张图在不同时刻的状态，
+ * 或以相同操作序列构建的两张图：
+ *
+ * - 只在左侧或只在右侧出现的顶点和边，连同其数据和端点
+ * - 两侧都有但数据不同的顶点
+ * - 两侧都有但数据或端点不同的边
+ *
+ * ## 按结构比较
+ *
+ * [`SlotMapGraph::structure_diff`] 忽略 ID，把每个顶点描述为（数据, 出度, 入度），
+ * 每条边描述为（起点数据, 边数据, 终点数据），再按多重集合比较两侧的描述。
+ * 描述使用数据的 `Debug` 输出，因此数据类型无需实现 `Hash` 或 `Eq`。
+ * 这是同构的必要条件：存在数据相同的顶点时，描述一致的两张图仍可能连接方式不同。
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// 差异中的一条边
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeEntry<'a, E, K: Key = DefaultKey> {
+    /// 边 ID
+    pub id: EdgeId<K>,
+    /// 起点
+    pub from: VertexId<K>,
+    /// 终点
+    pub to: VertexId<K>,
+    /// 边数据
+    pub weight: &'a E,
+}
+
+/// 按 ID 对齐的两张图之间的差异
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphDiff<'a, V, E, K: Key = DefaultKey> {
+    /// 只在左侧出现的顶点
+    pub left_only_vertices: Vec<(VertexId<K>, &'a V)>,
+    /// 只在右侧出现的顶点
+    pub right_only_vertices: Vec<(VertexId<K>, &'a V)>,
+    /// 两侧数据不同的顶点：(ID, 左侧数据, 右侧数据)
+    pub changed_vertices: Vec<(VertexId<K>, &'a V, &'a V)>,
+    /// 只在左侧出现的边
+    pub left_only_edges: Vec<EdgeEntry<'a, E, K>>,
+    /// 只在右侧出现的边
+    pub right_only_edges: Vec<EdgeEntry<'a, E, K>>,
+    /// 两侧数据或端点不同的边：(左侧, 右侧)
+    pub changed_edges: Vec<(EdgeEntry<'a, E, K>, EdgeEntry<'a, E, K>)>,
+}
+
+impl<V, E, K: Key> GraphDiff<'_, V, E, K> {
+    /// 两张图是否相同
+    pub fn is_empty(&self) -> bool {
+        self.left_only_vertices.is_empty()
+            && self.right_only_vertices.is_empty()
+            && self.changed_vertices.is_empty()
+            && self.left_only_edges.is_empty()
+            && self.right_only_edges.is_empty()
+            && self.changed_edges.is_empty()
+    }
+}
+
+impl<E: Debug, K: Key> Display for EdgeEntry<'_, E, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {:?} -> {:?} {:?}", self.id, self.from, self.to, self.weight)
+    }
+}
+
+impl<V: Debug, E: Debug, K: Key> Display for GraphDiff<'_, V, E, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (id, weight) in &self.left_only_vertices {
+            writeln!(f, "  vertex only in left:  {:?}: {:?}", id, weight)?;
+        }
+        for (id, weight) in &self.right_only_vertices {
+            writeln!(f, "  vertex only in right: {:?}: {:?}", id, weight)?;
+        }
+        for (id, left, right) in &self.changed_vertices {
+            writeln!(f, "  vertex changed:       {:?}: {:?} != {:?}", id, left, right)?;
+        }
+        for edge in &self.left_only_edges {
+            writeln!(f, "  edge only in left:    {}", edge)?;
+        }
+        for edge in &self.right_only_edges {
+            writeln!(f, "  edge only in right:   {}", edge)?;
+        }
+        for (left, right) in &self.changed_edges {
+            writeln!(f, "  edge changed:         {} != {}", left, right)?;
+        }
+        Ok(())
+    }
+}
+
+/// 忽略 ID 的结构差异，每一项是一个顶点或一条边的描述
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StructureDiff {
+    /// 左侧多出的描述，按字典序排列，重复出现的描述按次数列出
+    pub left_only: Vec<String>,
+    /// 右侧多出的描述
+    pub right_only: Vec<String>,
+}
+
+impl StructureDiff {
+    /// 两张图的结构描述是否一致
+    pub fn is_empty(&self) -> bool {
+        self.left_only.is_empty() && self.right_only.is_empty()
+    }
+}
+
+impl Display for StructureDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for item in &self.left_only {
+            writeln!(f, "  only in left:  {}", item)?;
+        }
+        for item in &self.right_only {
+            writeln!(f, "  only in right: {}", item)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 按 ID 对齐比较两张图，左侧为 `self`
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut left: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let mut right: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let a = left.add_vertex(1);
+    /// right.add_vertex(2);
+    ///
+    /// let diff = left.diff(&right);
+    /// assert_eq!(diff.changed_vertices, vec![(a, &1, &2)]);
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a Self) -> GraphDiff<'a, Vertex, Edge, K>
+    where
+        Vertex: PartialEq,
+        Edge: PartialEq,
+    {
+        let mut diff = GraphDiff {
+            left_only_vertices: Vec::new(),
+            right_only_vertices: Vec::new(),
+            changed_vertices: Vec::new(),
+            left_only_edges: Vec::new(),
+            right_only_edges: Vec::new(),
+            changed_edges: Vec::new(),
+        };
+        for (id, weight) in self.vertices.iter() {
+            match other.vertices.get(id) {
+                None => diff.left_only_vertices.push((id, weight)),
+                Some(theirs) if theirs != weight => diff.changed_vertices.push((id, weight, theirs)),
+                Some(_) => {}
+            }
+        }
+        diff.right_only_vertices = other
+            .vertices
+            .iter()
+            .filter(|(id, _)| !self.vertices.contains(*id))
+            .collect();
+
+        let entry = |id, weight, info: &crate::EdgeInfo<K>| EdgeEntry {
+            id,
+            from: info.from(),
+            to: info.to(),
+            weight,
+        };
+        for (id, weight, info) in self.edges.iter_with_connections() {
+            let ours = entry(id, weight, info);
+            match other.edges.get_with_info(id) {
+                None => diff.left_only_edges.push(ours),
+                Some((theirs, info)) => {
+                    let theirs = entry(id, theirs, info);
+                    if ours != theirs {
+                        diff.changed_edges.push((ours, theirs));
+                    }
+                }
+            }
+        }
+        diff.right_only_edges = other
+            .edges
+            .iter_with_connections()
+            .filter(|(id, _, _)| self.edges.get(*id).is_none())
+            .map(|(id, weight, info)| entry(id, weight, info))
+            .collect();
+        diff
+    }
+
+    /// 忽略 ID 比较两张图的结构描述，左侧为 `self`
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use pi_slotmap_graph::graph;
+    ///
+    /// let (left, _) = graph! { a -> b, b -> c };
+    /// let (right, _) = graph! { x -> y, y -> z };
+    /// assert!(left.structure_diff(&right).is_empty());
+    /// ```
+    pub fn structure_diff(&self, other: &Self) -> StructureDiff
+    where
+        Vertex: Debug,
+        Edge: Debug,
+    {
+        let mut counts: HashMap<String, isize> = HashMap::new();
+        for (graph, sign) in [(self, 1), (other, -1)] {
+            for (id, weight) in graph.vertices.iter() {
+                let item = format!("vertex {:?} (out {}, in {})", weight, graph.out_degree(id), graph.in_degree(id));
+                *counts.entry(item).or_default() += sign;
+            }
+            for (_, weight, info) in graph.edges.iter_with_connections() {
+                let (Some(from), Some(to)) = (graph.vertices.get(info.from()), graph.vertices.get(info.to())) else {
+                    continue;
+                };
+                *counts.entry(format!("edge {:?} -[{:?}]-> {:?}", from, weight, to)).or_default() += sign;
+            }
+        }
+
+        let mut diff = StructureDiff::default();
+        for (item, count) in counts {
+            let side = if count > 0 { &mut diff.left_only } else { &mut diff.right_only };
+            side.extend(std::iter::repeat_n(item, count.unsigned_abs()));
+        }
+        diff.left_only.sort();
+        diff.right_only.sort();
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_graph_eq, assert_same_structure, graph};
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    /// 执行断言并取出 panic 信息
+    fn panic_message(f: impl FnOnce()) -> String {
+        let payload = catch_unwind(AssertUnwindSafe(f)).expect_err("assertion should fail");
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap()
+    }
+
+    fn pair() -> (SlotMapGraph<u32, u32>, SlotMapGraph<u32, u32>) {
+        let build = || graph! {
+            vertices: { a: 1u32, b: 2u32, c: 3u32 },
+            edges: { a -> b: 10u32, b -> c: 20u32 }
+        }
+        .0;
+        (build(), build())
+    }
+
+    #[test]
+    fn test_equal_graphs_pass() {
+        let (left, right) = pair();
+        assert!(left.diff(&right).is_empty());
+        assert_graph_eq!(left, right);
+        assert_same_structure!(left, right);
+    }
+
+    #[test]
+    fn test_diff_reports_offending_ids() {
+        let (left, mut right) = pair();
+        let ids: Vec<_> = right.vertices.keys().collect();
+        let edges: Vec<_> = right.edges.iter().map(|(id, _)| id).collect();
+
+        right.remove_edge(edges[1]);
+        *right.vertex_weight_mut(ids[0]).unwrap() = 7;
+        *right.edge_weight_mut(edges[0]).unwrap() = 11;
+        let extra = right.add_vertex(4);
+
+        let diff = left.diff(&right);
+        assert_eq!(diff.changed_vertices, vec![(ids[0], &1, &7)]);
+        assert_eq!(diff.right_only_vertices, vec![(extra, &4)]);
+        assert_eq!(diff.left_only_edges.len(), 1);
+        assert_eq!(diff.changed_edges.len(), 1);
+
+        let message = panic_message(|| assert_graph_eq!(left, right));
+        assert!(message.contains("graphs differ"));
+        assert!(message.contains(&format!("vertex changed:       {:?}: 1 != 7", ids[0])));
+        assert!(message.contains(&format!("vertex only in right: {:?}: 4", extra)));
+        assert!(message.contains(&format!("edge only in left:    {:?}", edges[1])));
+        assert!(message.contains(&format!("{:?}", edges[0])));
+
+        let message = panic_message(|| assert_graph_eq!(left, right, "after step {}", 3));
+        assert!(message.contains("after step 3"));
+    }
+
+    #[test]
+    fn test_structure_ignores_ids() {
+        let (left, _) = pair();
+        // 顶点按不同顺序、带有空槽位构建
+        let mut right: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let hole = right.add_vertex(0);
+        let c = right.add_vertex(3);
+        let b = right.add_vertex(2);
+        let a = right.add_vertex(1);
+        right.remove_vertex(hole);
+        right.add_edge(b, c, 20);
+        right.add_edge(a, b, 10);
+        assert!(!left.diff(&right).is_empty());
+        assert_same_structure!(left, right);
+
+        right.add_edge(a, b, 10);
+        let diff = left.structure_diff(&right);
+        assert_eq!(diff.right_only.len(), 3);
+        assert!(diff.right_only.contains(&"edge 1 -[10]-> 2".to_string()));
+
+        let message = panic_message(|| assert_same_structure!(left, right));
+        assert!(message.contains("only in right: vertex 1 (out 2, in 0)"));
+    }
+}
@@ -0,0 +1,273 @@
+/*!
+ * # 类型擦除的图 (Type-Erased Graph)
+ *
+ * 插件等无法对顶点、边数据类型泛型化的调用方使用 [`DynGraph`]：
+ * 顶点和边数据以 [`DynValue`]（`Box<dyn Any + Send>`）保存，
+ * 不同插件可以在同一张图中存放各自的具体类型，读取时按类型向下转换。
+ *
+ * ## 接口
+ *
+ * - 增删元素经由本类型的方法进行，`add_vertex` / `add_edge` 接受任意 `Any + Send` 的值
+ * - [`vertex_as`](DynGraph::vertex_as) / [`edge_as`](DynGraph::edge_as) 等按类型读取数据，
+ *   元素不存在或类型不符时返回 `None`，不会 panic
+ * - 通过 `Deref` 提供内部 [`SlotMapGraph`] 的全部只读接口，
+ *   邻接、度数以及 BFS、拓扑排序、强连通分量等只依赖拓扑的算法可以直接调用
+ *
+ * 元素的标签都是 `()`，按标签的查询在类型擦除的图上没有区分作用。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use pi_slotmap_graph::dynamic::DynGraph;
+ *
+ * #[derive(Debug, PartialEq)]
+ * struct Sprite(u32);
+ *
+ * let mut graph = DynGraph::new();
+ * let a = graph.add_vertex(Sprite(7));
+ * let b = graph.add_vertex(String::from("audio"));
+ * graph.add_edge(a, b, 0.5f32).unwrap();
+ *
+ * assert_eq!(graph.vertex_as::<Sprite>(a), Some(&Sprite(7)));
+ * assert_eq!(graph.vertex_as::<Sprite>(b), None);
+ * assert_eq!(graph.topological_sort().unwrap(), vec![a, b]);
+ * ```
+ */
+
+use crate::error::GraphError;
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, Graph, SupportsElementRemoval};
+use pi_slotmap::{DefaultKey, Key};
+use std::any::{type_name, Any};
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+
+/// 类型擦除的顶点或边数据
+pub struct DynValue {
+    /// 装箱的数据
+    value: Box<dyn Any + Send>,
+    /// 装箱前的类型名，用于调试输出
+    type_name: &'static str,
+}
+
+impl DynValue {
+    /// 装箱 `value`
+    pub fn new<T: Any + Send>(value: T) -> Self {
+        Self {
+            value: Box::new(value),
+            type_name: type_name::<T>(),
+        }
+    }
+
+    /// 装箱前的类型名，只用于诊断，格式不保证稳定
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// 数据是否为 `T` 类型
+    pub fn is<T: Any>(&self) -> bool {
+        self.value.is::<T>()
+    }
+
+    /// 按 `T` 类型读取数据，类型不符时返回 `None`
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+
+    /// 按 `T` 类型读取数据的可变引用，类型不符时返回 `None`
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.value.downcast_mut()
+    }
+
+    /// 取出 `T` 类型的数据，类型不符时原样返回
+    pub fn downcast<T: Any>(self) -> Result<T, Self> {
+        let type_name = self.type_name;
+        self.value.downcast().map(|value| *value).map_err(|value| Self { value, type_name })
+    }
+}
+
+impl Debug for DynValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DynValue({})", self.type_name)
+    }
+}
+
+impl Element for DynValue {
+    type Label = ();
+
+    fn label(&self) -> Self::Label {}
+}
+
+/// 顶点和边数据都经过类型擦除的图
+#[derive(Debug, Default)]
+pub struct DynGraph<K: Key = DefaultKey> {
+    /// 内部的图
+    graph: SlotMapGraph<DynValue, DynValue, K>,
+}
+
+impl DynGraph {
+    /// 创建空图
+    pub fn new() -> Self {
+        Self::with_key()
+    }
+}
+
+impl<K: Key> DynGraph<K> {
+    /// 创建使用自定义键类型的空图
+    pub fn with_key() -> Self {
+        Self {
+            graph: SlotMapGraph::with_key(),
+        }
+    }
+
+    /// 添加顶点
+    pub fn add_vertex<T: Any + Send>(&mut self, value: T) -> VertexId<K> {
+        self.graph.add_vertex(DynValue::new(value))
+    }
+
+    /// 添加一条 `from -> to` 的边
+    ///
+    /// # 错误
+    ///
+    /// 端点不存在时返回 [`GraphError::VertexNotFound`]，图保持不变。
+    pub fn add_edge<T: Any + Send>(&mut self, from: VertexId<K>, to: VertexId<K>, value: T) -> Result<EdgeId<K>, GraphError<K>> {
+        for vertex in [from, to] {
+            if !self.graph.contains_vertex(vertex) {
+                return Err(GraphError::VertexNotFound(vertex));
+            }
+        }
+        Ok(self.graph.add_edge(from, to, DynValue::new(value)))
+    }
+
+    /// 删除顶点及其关联边，顶点不存在或被固定时返回 `None`
+    pub fn remove_vertex(&mut self, vertex_id: VertexId<K>) -> Option<DynValue> {
+        self.graph.remove_vertex(vertex_id)
+    }
+
+    /// 删除边
+    pub fn remove_edge(&mut self, edge_id: EdgeId<K>) -> Option<DynValue> {
+        self.graph.remove_edge(edge_id)
+    }
+
+    /// 按 `T` 类型读取顶点数据，顶点不存在或类型不符时返回 `None`
+    pub fn vertex_as<T: Any>(&self, vertex_id: VertexId<K>) -> Option<&T> {
+        self.graph.vertex_weight(vertex_id)?.downcast_ref()
+    }
+
+    /// 按 `T` 类型读取顶点数据的可变引用，顶点不存在或类型不符时返回 `None`
+    pub fn vertex_as_mut<T: Any>(&mut self, vertex_id: VertexId<K>) -> Option<&mut T> {
+        self.graph.vertex_weight_mut(vertex_id)?.downcast_mut()
+    }
+
+    /// 按 `T` 类型读取边数据，边不存在或类型不符时返回 `None`
+    pub fn edge_as<T: Any>(&self, edge_id: EdgeId<K>) -> Option<&T> {
+        self.graph.edge_weight(edge_id)?.downcast_ref()
+    }
+
+    /// 按 `T` 类型读取边数据的可变引用，边不存在或类型不符时返回 `None`
+    pub fn edge_as_mut<T: Any>(&mut self, edge_id: EdgeId<K>) -> Option<&mut T> {
+        self.graph.edge_weight_mut(edge_id)?.downcast_mut()
+    }
+
+    /// 取出内部的图
+    pub fn into_inner(self) -> SlotMapGraph<DynValue, DynValue, K> {
+        self.graph
+    }
+}
+
+impl<K: Key> Deref for DynGraph<K> {
+    type Target = SlotMapGraph<DynValue, DynValue, K>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 插件 A：场景节点，边为权重
+    #[derive(Debug, PartialEq)]
+    struct Node {
+        name: &'static str,
+    }
+
+    /// 插件 B：资源句柄，边为引用计数
+    #[derive(Debug, PartialEq)]
+    struct Asset(u64);
+
+    fn plugin_a(graph: &mut DynGraph) -> [VertexId; 3] {
+        let ids = ["root", "child", "leaf"].map(|name| graph.add_vertex(Node { name }));
+        graph.add_edge(ids[0], ids[1], 1.5f32).unwrap();
+        graph.add_edge(ids[1], ids[2], 2.5f32).unwrap();
+        ids
+    }
+
+    fn plugin_b(graph: &mut DynGraph, owner: VertexId) -> [VertexId; 2] {
+        let ids = [graph.add_vertex(Asset(10)), graph.add_vertex(Asset(20))];
+        for id in ids {
+            graph.add_edge(owner, id, 1u32).unwrap();
+        }
+        ids
+    }
+
+    #[test]
+    fn test_plugins_share_one_graph() {
+        let mut graph = DynGraph::new();
+        let [root, child, leaf] = plugin_a(&mut graph);
+        let [texture, sound] = plugin_b(&mut graph, leaf);
+
+        assert_eq!(graph.vertex_as::<Node>(child), Some(&Node { name: "child" }));
+        assert_eq!(graph.vertex_as::<Asset>(sound), Some(&Asset(20)));
+        graph.vertex_as_mut::<Asset>(texture).unwrap().0 += 1;
+        assert_eq!(graph.vertex_as::<Asset>(texture), Some(&Asset(11)));
+
+        let edge = graph.outgoing_adjacency(root)[0];
+        assert_eq!(graph.edge_as::<f32>(edge), Some(&1.5));
+        *graph.edge_as_mut::<f32>(edge).unwrap() *= 2.0;
+        assert_eq!(graph.edge_as::<f32>(edge), Some(&3.0));
+        let asset_edges: Vec<_> = graph.outgoing_adjacency(leaf).iter().filter_map(|id| graph.edge_as::<u32>(*id)).collect();
+        assert_eq!(asset_edges, [&1, &1]);
+
+        // 只依赖拓扑的算法直接运行在类型擦除的图上
+        assert_eq!(graph.out_degree(leaf), 2);
+        assert_eq!(graph.bfs(root).count(), 5);
+        let order = graph.topological_sort().unwrap();
+        let position = |id| order.iter().position(|v| *v == id).unwrap();
+        assert!(position(root) < position(child) && position(leaf) < position(sound));
+        graph.add_edge(sound, root, ()).unwrap();
+        let components = graph.strongly_connected_components();
+        assert!(components.iter().any(|component| component.len() == 4 && !component.contains(&texture)));
+
+        assert!(graph.remove_vertex(texture).unwrap().downcast::<Asset>().is_ok_and(|asset| asset == Asset(11)));
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_failed_downcasts_return_none() {
+        let mut graph = DynGraph::new();
+        let a = graph.add_vertex(Node { name: "a" });
+        let b = graph.add_vertex(Asset(1));
+        let edge = graph.add_edge(a, b, 7u32).unwrap();
+
+        assert_eq!(graph.vertex_as::<Asset>(a), None);
+        assert_eq!(graph.vertex_as_mut::<Node>(b), None);
+        assert_eq!(graph.edge_as::<u64>(edge), None);
+        assert_eq!(graph.edge_as_mut::<f32>(edge), None);
+        assert!(graph.vertex_weight(a).unwrap().is::<Node>());
+        assert!(graph.vertex_weight(a).unwrap().type_name().ends_with("Node"));
+
+        // 取出失败时原样返回
+        let value = graph.remove_edge(edge).unwrap();
+        let value = value.downcast::<u64>().unwrap_err();
+        assert_eq!(value.downcast::<u32>().ok(), Some(7));
+
+        graph.remove_vertex(b);
+        assert_eq!(graph.vertex_as::<Asset>(b), None);
+        assert_eq!(graph.edge_as::<u32>(edge), None);
+        assert_eq!(graph.add_edge(a, b, ()), Err(GraphError::VertexNotFound(b)));
+        assert_eq!(format!("{:?}", graph.vertex_weight(a).unwrap()), format!("DynValue({})", type_name::<Node>()));
+    }
+}
@@ -0,0 +1,177 @@
+/*!
+ * # 图操作错误 (GraphError)
+ *
+ * 可能失败的图操作（如按策略删除顶点）以及一致性检查返回的错误类型。
+ */
+
+use crate::id::{EdgeId, VertexId};
+use pi_slotmap::{DefaultKey, Key};
+
+/// 图操作的错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphError<K: Key = DefaultKey> {
+    /// 指定的顶点不存在
+    VertexNotFound(VertexId<K>),
+    /// 顶点被固定，删除被拒绝
+    VertexPinned(VertexId<K>),
+    /// 有容量上限的图已满，插入被拒绝
+    CapacityExceeded {
+        /// 被超出的顶点或边数量上限
+        limit: usize,
+    },
+    /// 插入边会使端点的度数超过图设置的上限
+    DegreeLimitExceeded {
+        /// 已达到上限的端点
+        vertex: VertexId<K>,
+        /// 度数上限
+        limit: u32,
+    },
+    /// 添加并连接新顶点时，新顶点自身的边数会超过度数上限；此时新顶点尚未插入，没有 ID
+    NewVertexDegreeExceeded {
+        /// 要连接到新顶点的边数
+        degree: usize,
+        /// 度数上限
+        limit: u32,
+    },
+    /// 顶点被分配到不存在的分片
+    InvalidPart {
+        /// 出错的顶点
+        vertex: VertexId<K>,
+        /// 分配给该顶点的分片编号
+        part: usize,
+        /// 分片总数
+        parts: usize,
+    },
+    /// 顶点仍有关联边，删除被拒绝
+    VertexHasEdges {
+        /// 被拒绝删除的顶点
+        vertex: VertexId<K>,
+        /// 该顶点的关联边数量（自环计一次）
+        edge_count: usize,
+    },
+    /// 边引用了不存在的顶点
+    DanglingEdge {
+        /// 出错的边
+        edge: EdgeId<K>,
+        /// 不存在的端点
+        vertex: VertexId<K>,
+    },
+    /// 顶点的邻接表与边数据不一致
+    AdjacencyMismatch {
+        /// 出错的顶点
+        vertex: VertexId<K>,
+    },
+    /// 顶点的度数计数器与邻接表不一致
+    DegreeMismatch {
+        /// 出错的顶点
+        vertex: VertexId<K>,
+    },
+    /// 边的连接信息中记录的边 ID 与其所在槽位不一致
+    EdgeInfoMismatch {
+        /// 出错的边
+        edge: EdgeId<K>,
+    },
+    /// 边标签索引与边数据不一致：索引中的边不存在或标签不符，或者边没有被索引
+    LabelIndexMismatch {
+        /// 出错的边
+        edge: EdgeId<K>,
+    },
+    /// 已开启的可选边索引（标记、创建顺序、标签缓存、端点对索引、图层、批次标签）仍记录着不存在的边
+    StaleEdgeEntry {
+        /// 出错的边
+        edge: EdgeId<K>,
+    },
+    /// 恢复拓扑快照时，被删除的边没有可用的数据
+    MissingEdgePayload(EdgeId<K>),
+    /// 算法不接受自环，例如图着色
+    SelfLoop {
+        /// 自环边
+        edge: EdgeId<K>,
+        /// 自环所在的顶点
+        vertex: VertexId<K>,
+    },
+    /// 顶点或边的数据中记录的顶点 ID 不存在
+    DanglingWeightRef {
+        /// 持有该引用的元素
+        holder: WeightHolder<K>,
+        /// 不存在的顶点
+        missing: VertexId<K>,
+    },
+    /// 顶点仍被其他元素的数据引用，删除被拒绝
+    VertexReferenced {
+        /// 被拒绝删除的顶点
+        vertex: VertexId<K>,
+        /// 第一个引用该顶点的元素
+        holder: WeightHolder<K>,
+    },
+}
+
+/// 数据中持有顶点引用的元素
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeightHolder<K: Key = DefaultKey> {
+    /// 顶点
+    Vertex(VertexId<K>),
+    /// 边
+    Edge(EdgeId<K>),
+}
+
+impl<K: Key> std::fmt::Display for WeightHolder<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeightHolder::Vertex(vertex) => write!(f, "vertex {}", vertex),
+            WeightHolder::Edge(edge) => write!(f, "edge {}", edge),
+        }
+    }
+}
+
+impl<K: Key> std::fmt::Display for GraphError<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::VertexNotFound(vertex) => write!(f, "Vertex not found: {}", vertex),
+            GraphError::VertexPinned(vertex) => write!(f, "Vertex {} is pinned", vertex),
+            GraphError::CapacityExceeded { limit } => write!(f, "Capacity limit of {} reached", limit),
+            GraphError::DegreeLimitExceeded { vertex, limit } => {
+                write!(f, "Vertex {} already has the maximum degree of {}", vertex, limit)
+            }
+            GraphError::NewVertexDegreeExceeded { degree, limit } => {
+                write!(f, "New vertex would have {} edge(s), above the maximum degree of {}", degree, limit)
+            }
+            GraphError::InvalidPart { vertex, part, parts } => {
+                write!(f, "Vertex {} assigned to part {} of {}", vertex, part, parts)
+            }
+            GraphError::VertexHasEdges { vertex, edge_count } => {
+                write!(f, "Vertex {} still has {} edge(s)", vertex, edge_count)
+            }
+            GraphError::DanglingEdge { edge, vertex } => {
+                write!(f, "Edge {} references missing vertex {}", edge, vertex)
+            }
+            GraphError::AdjacencyMismatch { vertex } => {
+                write!(f, "Adjacency of vertex {} does not match the stored edges", vertex)
+            }
+            GraphError::DegreeMismatch { vertex } => {
+                write!(f, "Degree counters of vertex {} do not match its adjacency", vertex)
+            }
+            GraphError::EdgeInfoMismatch { edge } => {
+                write!(f, "Connection info of edge {} records a different edge id", edge)
+            }
+            GraphError::LabelIndexMismatch { edge } => {
+                write!(f, "Label index entry of edge {} does not match the stored edges", edge)
+            }
+            GraphError::StaleEdgeEntry { edge } => {
+                write!(f, "Optional index still references removed edge {}", edge)
+            }
+            GraphError::MissingEdgePayload(edge) => write!(f, "No payload available to restore edge {}", edge),
+            GraphError::SelfLoop { edge, vertex } => {
+                write!(f, "Edge {} is a self-loop on vertex {}", edge, vertex)
+            }
+            GraphError::DanglingWeightRef { holder, missing } => {
+                write!(f, "Weight of {} references missing vertex {}", holder, missing)
+            }
+            GraphError::VertexReferenced { vertex, holder } => {
+                write!(f, "Vertex {} is still referenced by the weight of {}", vertex, holder)
+            }
+        }
+    }
+}
+
+impl<K: Key> std::error::Error for GraphError<K> {}
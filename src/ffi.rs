@@ -0,0 +1,254 @@
+/*!
+ * # C 接口 (C FFI)
+ *
+ * 开启 `ffi` 特性后提供一组 `extern "C"` 函数，供 C/C++ 工具创建和查看
+ * 顶点、边数据均为 `u64` 的图（[`SmgGraph`]，即 `SlotMapGraph<u64, u64>`）。
+ * 对应的 C 声明见仓库中的 `include/pi_slotmap_graph.h`；以静态库嵌入时可用
+ * `cargo rustc --release --features ffi --crate-type staticlib` 构建。
+ *
+ * ## 约定
+ *
+ * - 图以不透明指针传递，由 [`smg_new`] 创建、[`smg_free`] 释放
+ * - 顶点和边 ID 以 [`VertexId::as_ffi`] / [`EdgeId::as_ffi`] 的 `u64` 跨越边界；
+ *   [`SMG_INVALID_ID`]（空键，`0xFFFF_FFFF`）不是任何有效 ID，返回 ID 的函数以它表示失败
+ * - 返回状态的函数以 [`SMG_OK`] 表示成功，其余为负的错误码
+ * - 所有函数都捕获 panic，panic 不会跨越边界，而是返回 [`SMG_PANIC`] 或 [`SMG_INVALID_ID`]
+ *
+ * ## 错误码
+ *
+ * | 常量 | 值 | 含义 |
+ * |------|----|------|
+ * | [`SMG_OK`] | 0 | 成功 |
+ * | [`SMG_NULL_HANDLE`] | -1 | 图指针为空 |
+ * | [`SMG_NOT_FOUND`] | -2 | 顶点不存在（包括已删除的旧 ID）或被固定 |
+ * | [`SMG_NULL_BUFFER`] | -3 | 输出指针为空 |
+ * | [`SMG_PANIC`] | -4 | 内部发生 panic |
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+#[cfg(doc)]
+use crate::id::EdgeId;
+use graph_api_lib::{Graph, SupportsElementRemoval};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// 成功
+pub const SMG_OK: i32 = 0;
+/// 图指针为空
+pub const SMG_NULL_HANDLE: i32 = -1;
+/// 顶点不存在或被固定
+pub const SMG_NOT_FOUND: i32 = -2;
+/// 输出指针为空
+pub const SMG_NULL_BUFFER: i32 = -3;
+/// 内部发生 panic
+pub const SMG_PANIC: i32 = -4;
+/// 无效 ID：空键（槽位下标为 `u32::MAX`，版本号为 0）的 `as_ffi`，不会分配给任何元素
+pub const SMG_INVALID_ID: u64 = u32::MAX as u64;
+
+/// 经由 C 接口使用的图
+pub type SmgGraph = SlotMapGraph<u64, u64>;
+
+/// 执行 `f`，发生 panic 时返回 `on_panic`
+fn guard<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or(on_panic)
+}
+
+/// 存在的顶点
+fn vertex(graph: &SmgGraph, raw: u64) -> Option<VertexId> {
+    let id = VertexId::from_ffi(raw);
+    graph.contains_vertex(id).then_some(id)
+}
+
+/// 创建空图，返回的指针需以 [`smg_free`] 释放
+#[no_mangle]
+pub extern "C" fn smg_new() -> *mut SmgGraph {
+    guard(std::ptr::null_mut(), || Box::into_raw(Box::new(SmgGraph::new())))
+}
+
+/// 释放 [`smg_new`] 创建的图，空指针被忽略
+///
+/// # Safety
+///
+/// `handle` 为空或来自 [`smg_new`] 且尚未释放；释放后不能再使用。
+#[no_mangle]
+pub unsafe extern "C" fn smg_free(handle: *mut SmgGraph) {
+    if !handle.is_null() {
+        guard((), || drop(Box::from_raw(handle)));
+    }
+}
+
+/// 加入顶点，返回其 ID；`handle` 为空时返回 [`SMG_INVALID_ID`]
+///
+/// # Safety
+///
+/// `handle` 为空或指向存活的图，且没有其他线程同时使用。
+#[no_mangle]
+pub unsafe extern "C" fn smg_add_vertex(handle: *mut SmgGraph, weight: u64) -> u64 {
+    let Some(graph) = handle.as_mut() else {
+        return SMG_INVALID_ID;
+    };
+    guard(SMG_INVALID_ID, || graph.add_vertex(weight).as_ffi())
+}
+
+/// 加入边 `from -> to`，返回其 ID；`handle` 为空或端点不存在时返回 [`SMG_INVALID_ID`]
+///
+/// # Safety
+///
+/// 同 [`smg_add_vertex`]。
+#[no_mangle]
+pub unsafe extern "C" fn smg_add_edge(handle: *mut SmgGraph, from: u64, to: u64, weight: u64) -> u64 {
+    let Some(graph) = handle.as_mut() else {
+        return SMG_INVALID_ID;
+    };
+    guard(SMG_INVALID_ID, || match (vertex(graph, from), vertex(graph, to)) {
+        (Some(from), Some(to)) => graph.add_edge(from, to, weight).as_ffi(),
+        _ => SMG_INVALID_ID,
+    })
+}
+
+/// 删除顶点及其关联边
+///
+/// # Safety
+///
+/// 同 [`smg_add_vertex`]。
+#[no_mangle]
+pub unsafe extern "C" fn smg_remove_vertex(handle: *mut SmgGraph, vertex_id: u64) -> i32 {
+    let Some(graph) = handle.as_mut() else {
+        return SMG_NULL_HANDLE;
+    };
+    guard(SMG_PANIC, || match graph.remove_vertex(VertexId::from_ffi(vertex_id)) {
+        Some(_) => SMG_OK,
+        None => SMG_NOT_FOUND,
+    })
+}
+
+/// 把顶点的出边 ID 按出边顺序写入 `out_buf`，返回出度
+///
+/// 至多写入 `cap` 个；返回值大于 `cap` 时说明缓冲区不足，可按返回值重新分配后再次调用。
+/// `cap` 为 0 时 `out_buf` 可以为空，用于只查询出度。失败时返回负的错误码。
+///
+/// # Safety
+///
+/// 同 [`smg_add_vertex`]；`cap` 不为 0 时 `out_buf` 指向至少 `cap` 个可写的 `u64`。
+#[no_mangle]
+pub unsafe extern "C" fn smg_out_edges(handle: *const SmgGraph, vertex_id: u64, out_buf: *mut u64, cap: usize) -> i64 {
+    let Some(graph) = handle.as_ref() else {
+        return SMG_NULL_HANDLE.into();
+    };
+    if out_buf.is_null() && cap > 0 {
+        return SMG_NULL_BUFFER.into();
+    }
+    guard(SMG_PANIC.into(), || {
+        let Some(vertex_id) = vertex(graph, vertex_id) else {
+            return SMG_NOT_FOUND.into();
+        };
+        let edges = graph.outgoing_adjacency(vertex_id);
+        for (i, edge_id) in edges.iter().take(cap).enumerate() {
+            out_buf.add(i).write(edge_id.as_ffi());
+        }
+        edges.len() as i64
+    })
+}
+
+/// 把顶点的数据写入 `out`
+///
+/// # Safety
+///
+/// 同 [`smg_add_vertex`]；`out` 为空或指向可写的 `u64`。
+#[no_mangle]
+pub unsafe extern "C" fn smg_vertex_weight(handle: *const SmgGraph, vertex_id: u64, out: *mut u64) -> i32 {
+    let Some(graph) = handle.as_ref() else {
+        return SMG_NULL_HANDLE;
+    };
+    if out.is_null() {
+        return SMG_NULL_BUFFER;
+    }
+    guard(SMG_PANIC, || match graph.vertex_weight(VertexId::from_ffi(vertex_id)) {
+        Some(weight) => {
+            out.write(*weight);
+            SMG_OK
+        }
+        None => SMG_NOT_FOUND,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr::{null, null_mut};
+
+    #[test]
+    fn test_round_trip_through_c_abi() {
+        unsafe {
+            let graph = smg_new();
+            let a = smg_add_vertex(graph, 10);
+            let b = smg_add_vertex(graph, 20);
+            let ab = smg_add_edge(graph, a, b, 1);
+            let aa = smg_add_edge(graph, a, a, 2);
+            assert!(![a, b, ab, aa].contains(&SMG_INVALID_ID));
+            assert_eq!((*graph).edge_weight(crate::EdgeId::from_ffi(ab)), Some(&1));
+
+            let mut weight = 0;
+            assert_eq!(smg_vertex_weight(graph, b, &mut weight), SMG_OK);
+            assert_eq!(weight, 20);
+
+            // 缓冲区不足时只写入 cap 个，返回值为出度
+            let mut buf = [SMG_INVALID_ID; 4];
+            assert_eq!(smg_out_edges(graph, a, buf.as_mut_ptr(), 1), 2);
+            assert_eq!(buf[..2], [ab, SMG_INVALID_ID]);
+            assert_eq!(smg_out_edges(graph, a, buf.as_mut_ptr(), buf.len()), 2);
+            assert_eq!(buf[..2], [ab, aa]);
+            assert_eq!(smg_out_edges(graph, a, null_mut(), 0), 2);
+            assert_eq!(smg_out_edges(graph, b, buf.as_mut_ptr(), buf.len()), 0);
+
+            assert_eq!(smg_remove_vertex(graph, b), SMG_OK);
+            assert_eq!(smg_out_edges(graph, a, buf.as_mut_ptr(), buf.len()), 1);
+            assert_eq!(buf[0], aa);
+            smg_free(graph);
+        }
+    }
+
+    #[test]
+    fn test_error_codes() {
+        unsafe {
+            let graph = smg_new();
+            let a = smg_add_vertex(graph, 1);
+            let stale = smg_add_vertex(graph, 2);
+            assert_eq!(smg_remove_vertex(graph, stale), SMG_OK);
+
+            // 已删除的旧 ID 与无效 ID
+            let mut out = 0;
+            for missing in [stale, SMG_INVALID_ID] {
+                assert_eq!(smg_remove_vertex(graph, missing), SMG_NOT_FOUND);
+                assert_eq!(smg_vertex_weight(graph, missing, &mut out), SMG_NOT_FOUND);
+                assert_eq!(smg_out_edges(graph, missing, &mut out, 1), i64::from(SMG_NOT_FOUND));
+                assert_eq!(smg_add_edge(graph, a, missing, 0), SMG_INVALID_ID);
+                assert_eq!(smg_add_edge(graph, missing, a, 0), SMG_INVALID_ID);
+            }
+            assert_eq!(smg_vertex_weight(graph, a, null_mut()), SMG_NULL_BUFFER);
+            assert_eq!(smg_out_edges(graph, a, null_mut(), 1), i64::from(SMG_NULL_BUFFER));
+            assert_eq!((*graph).edge_count(), 0);
+
+            // 空指针
+            assert_eq!(smg_add_vertex(null_mut(), 1), SMG_INVALID_ID);
+            assert_eq!(smg_add_edge(null_mut(), a, a, 1), SMG_INVALID_ID);
+            assert_eq!(smg_remove_vertex(null_mut(), a), SMG_NULL_HANDLE);
+            assert_eq!(smg_out_edges(null(), a, &mut out, 1), i64::from(SMG_NULL_HANDLE));
+            assert_eq!(smg_vertex_weight(null(), a, &mut out), SMG_NULL_HANDLE);
+            smg_free(null_mut());
+            smg_free(graph);
+        }
+    }
+
+    #[test]
+    fn test_invalid_id_is_the_null_key() {
+        assert_eq!(VertexId::<pi_slotmap::DefaultKey>::default().as_ffi(), SMG_INVALID_ID);
+        assert_eq!(crate::EdgeId::<pi_slotmap::DefaultKey>::default().as_ffi(), SMG_INVALID_ID);
+    }
+
+    #[test]
+    fn test_panics_become_error_codes() {
+        assert_eq!(guard(SMG_PANIC, || panic!("boom")), SMG_PANIC);
+        assert_eq!(guard(SMG_PANIC, || SMG_OK), SMG_OK);
+    }
+}
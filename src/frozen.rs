@@ -0,0 +1,227 @@
+/*!
+ * # 只读邻接结构 (Frozen Graph)
+ *
+ * [`FrozenGraph`] 是由 [`SlotMapGraph::to_static`] 一次性构建的只读出边邻接表，
+ * 面向构建后大量只读查询的场景，例如三角形计数、共同邻居和高频的连通性判断。
+ *
+ * ## 存储
+ *
+ * 所有顶点的出边邻居按顶点槽位顺序存放在一个连续数组中（CSR 布局），
+ * 每个顶点的邻居按 ID 排序并去重，平行边只保留一次，自环保留为指向自身的邻居。
+ * 按槽位下标定位顶点，不做哈希。
+ *
+ * | 操作 | 复杂度 |
+ * |------|--------|
+ * | 构建 | O(V + E log E) |
+ * | [`neighbors_slice`](FrozenGraph::neighbors_slice) | O(1) |
+ * | [`has_edge`](FrozenGraph::has_edge) | O(log d) |
+ * | [`common_neighbors`](FrozenGraph::common_neighbors) | O(d1 + d2) |
+ *
+ * 结构只保存连接关系，不保存顶点和边的数据；构建后与原图相互独立，
+ * 原图之后的修改不会反映到结构中。结构不可变，可以在线程间共享只读访问。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+ * let a = graph.add_vertex(());
+ * let b = graph.add_vertex(());
+ * let c = graph.add_vertex(());
+ * graph.add_edge(a, c, ());
+ * graph.add_edge(b, c, ());
+ * graph.add_edge(a, b, ());
+ *
+ * let frozen = graph.to_static();
+ * assert!(frozen.has_edge(a, c) && !frozen.has_edge(c, a));
+ * assert_eq!(frozen.neighbors_slice(a).len(), 2);
+ * assert_eq!(frozen.common_neighbors(a, b), vec![c]);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+use std::cmp::Ordering;
+
+/// 按顶点槽位存放、邻居有序的只读出边邻接表
+#[derive(Debug, Clone)]
+pub struct FrozenGraph<K: Key = DefaultKey> {
+    /// 每个槽位构建时的顶点，空槽位为默认键
+    owners: Vec<VertexId<K>>,
+    /// 槽位 `i` 的邻居位于 `neighbors[offsets[i]..offsets[i + 1]]`
+    offsets: Vec<usize>,
+    /// 全部顶点的有序出边邻居
+    neighbors: Vec<VertexId<K>>,
+    /// 构建时的顶点数量
+    vertex_count: usize,
+}
+
+impl<K: Key> FrozenGraph<K> {
+    /// 构建时的顶点数量
+    pub fn vertex_count(&self) -> usize {
+        self.vertex_count
+    }
+
+    /// 去重后的有向邻接对数量，平行边只计一次
+    pub fn neighbor_count(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// 构建时是否包含该顶点
+    pub fn contains_vertex(&self, vertex_id: VertexId<K>) -> bool {
+        self.slot(vertex_id).is_some()
+    }
+
+    /// 顶点按 ID 排序、去重后的出边邻居，顶点不存在时为空
+    pub fn neighbors_slice(&self, vertex_id: VertexId<K>) -> &[VertexId<K>] {
+        match self.slot(vertex_id) {
+            Some(slot) => &self.neighbors[self.offsets[slot]..self.offsets[slot + 1]],
+            None => &[],
+        }
+    }
+
+    /// 是否存在 `from -> to` 的边，在 `from` 的邻居中二分查找
+    pub fn has_edge(&self, from: VertexId<K>, to: VertexId<K>) -> bool {
+        self.neighbors_slice(from)
+            .binary_search_by_key(&to.as_ffi(), VertexId::as_ffi)
+            .is_ok()
+    }
+
+    /// 两个顶点共同的出边邻居，按 ID 升序
+    ///
+    /// 对两个有序邻居数组做线性归并。
+    pub fn common_neighbors(&self, a: VertexId<K>, b: VertexId<K>) -> Vec<VertexId<K>> {
+        let (left, right) = (self.neighbors_slice(a), self.neighbors_slice(b));
+        let (mut i, mut j) = (0, 0);
+        let mut common = Vec::new();
+        while i < left.len() && j < right.len() {
+            match left[i].as_ffi().cmp(&right[j].as_ffi()) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    common.push(left[i]);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        common
+    }
+
+    /// 构建时仍然有效的顶点所在的槽位
+    fn slot(&self, vertex_id: VertexId<K>) -> Option<usize> {
+        let slot = vertex_id.key().data().index() as usize;
+        (slot < self.owners.len() && self.owners[slot] == vertex_id).then_some(slot)
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 构建只读的有序出边邻接表
+    ///
+    /// 一次性排序，耗时 O(V + E log E)，详见 [`FrozenGraph`]。
+    pub fn to_static(&self) -> FrozenGraph<K> {
+        let slots = self.vertices.slot_capacity();
+        let mut owners = vec![VertexId::default(); slots];
+        let mut adjacency = vec![Vec::new(); slots];
+        for vertex_id in self.vertices.keys() {
+            let slot = vertex_id.key().data().index() as usize;
+            owners[slot] = vertex_id;
+            let targets = &mut adjacency[slot];
+            targets.extend(
+                self.outgoing_adjacency(vertex_id)
+                    .iter()
+                    .filter_map(|edge_id| self.edge_to(*edge_id)),
+            );
+            targets.sort_unstable_by_key(VertexId::as_ffi);
+            targets.dedup();
+        }
+
+        let mut offsets = Vec::with_capacity(slots + 1);
+        let mut neighbors = Vec::with_capacity(adjacency.iter().map(Vec::len).sum());
+        offsets.push(0);
+        for targets in adjacency {
+            neighbors.extend(targets);
+            offsets.push(neighbors.len());
+        }
+        FrozenGraph {
+            owners,
+            offsets,
+            neighbors,
+            vertex_count: self.vertex_count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_matches_dynamic_graph() {
+        // 固定种子的 xorshift，保证测试可复现
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % n
+        };
+
+        for round in 0..20 {
+            let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+            let n = 5 + round * 3;
+            let mut ids: Vec<_> = (0..n).map(|_| graph.add_vertex(())).collect();
+            for _ in 0..n * 4 {
+                graph.add_edge(ids[next(n)], ids[next(n)], ());
+            }
+            // 删除部分顶点，留下空槽位和失效 ID
+            let removed = ids.swap_remove(next(ids.len()));
+            graph.remove_vertex(removed);
+
+            let frozen = graph.to_static();
+            assert_eq!(frozen.vertex_count(), graph.vertex_count());
+            assert!(!frozen.contains_vertex(removed));
+            assert!(frozen.neighbors_slice(removed).is_empty());
+
+            let targets = |v| -> HashSet<_> { graph.outgoing_adjacency(v).iter().filter_map(|e| graph.edge_to(*e)).collect() };
+            for &a in &ids {
+                let slice = frozen.neighbors_slice(a);
+                assert!(slice.windows(2).all(|w| w[0].as_ffi() < w[1].as_ffi()));
+                assert_eq!(slice.iter().copied().collect::<HashSet<_>>(), targets(a));
+                for &b in &ids {
+                    assert_eq!(frozen.has_edge(a, b), graph.has_edge(a, b));
+                    let expected: HashSet<_> = targets(a).intersection(&targets(b)).copied().collect();
+                    assert_eq!(frozen.common_neighbors(a, b).into_iter().collect::<HashSet<_>>(), expected);
+                }
+                assert!(!frozen.has_edge(a, removed));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_edges_and_self_loops() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        graph.add_edge(a, b, ());
+        graph.add_edge(a, b, ());
+        graph.add_edge(a, a, ());
+
+        let frozen = graph.to_static();
+        assert_eq!(frozen.neighbor_count(), 2);
+        assert!(frozen.has_edge(a, a));
+        assert!(frozen.neighbors_slice(b).is_empty());
+        assert_eq!(frozen.common_neighbors(a, a).len(), 2);
+    }
+}
@@ -1,18 +1,32 @@
+use super::changelog::{Change, ChangeLog};
+use super::counters::GraphMetrics;
+use super::error::GraphError;
 use super::id::{EdgeId, VertexId};
 use super::id::edge_info::EdgeInfo;
-use super::storage::{EdgeContainer, VertexContainer};
-use super::index::SimpleVertexQuery;
+#[cfg(feature = "removal-diagnostics")]
+use super::quarantine::{Quarantine, RemovedId};
+use super::storage::{EdgeContainer, EdgeRemovalOrder, VertexContainer};
+use super::index::{AdjacentLabelCache, CreationOrder, DeclaredIndex, EdgeLayers, EdgePairIndex, EdgeTags, HashIndex, IndexEntry, KeyOrder, LabelCache, MarkTable, SimpleVertexQuery, SpatialIndex, VertexLabelIndex};
+use super::index::layers::MAX_LAYERS;
+use super::index::persist::IndexSpec;
+use super::plan::Prefilter;
+use super::index::spatial::Point;
 use graph_api_lib::{
-    EdgeSearch, Element, ElementId, Graph,
-    SupportsClear, SupportsEdgeAdjacentLabelIndex, SupportsEdgeHashIndex, SupportsEdgeLabelIndex,
-    SupportsEdgeRangeIndex, SupportsElementRemoval, SupportsVertexFullTextIndex,
+    Direction, EdgeSearch, Element, ElementId, Graph,
+    SupportsClear, SupportsEdgeAdjacentLabelIndex, SupportsEdgeLabelIndex,
+    SupportsElementRemoval, SupportsVertexFullTextIndex,
     SupportsVertexHashIndex, SupportsVertexLabelIndex, SupportsVertexRangeIndex,
     VertexSearch,
 };
+use pi_slotmap::{DefaultKey, Key};
 use smallbox::{SmallBox, smallbox};
 use smallbox::space::S8;
+use smallvec::SmallVec;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Formatter};
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
+use std::ops::Range;
 
 /// 基于SlotMap的图实现，严格参照graph-api-simplegraph结构
 ///
@@ -43,17 +57,84 @@ use std::marker::PhantomData;
 /// - **自动重用**: 删除空间的智能回收
 /// - **索引优化**: 可选的索引以减少内存开销
 #[derive(Debug)]
-pub struct SlotMapGraph<Vertex, Edge>
+pub struct SlotMapGraph<Vertex, Edge, K = DefaultKey>
 where
     Vertex: Element,
     Edge: Element,
+    K: Key,
 {
     /// 顶点存储容器
-    vertices: VertexContainer<Vertex>,
+    pub(crate) vertices: VertexContainer<Vertex, K>,
     /// 边存储容器
-    edges: EdgeContainer<Edge>,
+    pub(crate) edges: EdgeContainer<Edge, K>,
     /// 简单顶点查询器（用于智能查询）
-    vertex_query: SimpleVertexQuery,
+    pub(crate) vertex_query: SimpleVertexQuery<K>,
+    /// `vertices()` 是否使用 `vertex_query` 选择候选顶点，由 `disable_vertex_query` 关闭
+    pub(crate) vertex_query_enabled: bool,
+    /// 按标签分组的顶点索引，随顶点增删自动维护
+    pub(crate) vertex_labels: VertexLabelIndex<Vertex::Label, VertexId<K>>,
+    /// 元素类型声明的字段索引，随顶点增删和权重修改自动维护
+    pub(crate) declared_index: DeclaredIndex<Vertex::Label, VertexId<K>>,
+    /// 经由 `register_index` 记录的简单查询索引定义
+    pub(crate) index_specs: Vec<IndexSpec>,
+    /// 全图按标签分组的边索引，随边增删自动维护
+    pub(crate) edge_labels: HashIndex<Edge::Label, EdgeId<K>>,
+    /// 经由 `edge_weight_mut` 交出可变引用、暂时移出 `edge_labels` 的边
+    pub(crate) unindexed_edges: HashSet<EdgeId<K>>,
+    /// 可选的邻接边标签缓存，由 `enable_adjacent_label_cache` 开启
+    pub(crate) adjacent_labels: Option<AdjacentLabelCache<Edge::Label, VertexId<K>>>,
+    /// 可选的端点对索引，由 `enable_pair_index` 开启
+    pub(crate) edge_pairs: Option<EdgePairIndex<VertexId<K>, EdgeId<K>>>,
+    /// 可选的元素位标记，由 `enable_marks` 开启
+    pub(crate) marks: Option<MarkTable<K>>,
+    /// 可选的顶点坐标索引，由 `enable_spatial_index` 开启
+    pub(crate) spatial: Option<SpatialIndex<K>>,
+    /// 可选的顶点和边标签缓存，由 `enable_label_cache` 开启
+    pub(crate) label_cache: Option<LabelCache<Vertex::Label, Edge::Label, K>>,
+    /// 可选的创建顺序，由 `enable_creation_order` 开启
+    pub(crate) creation_order: Option<CreationOrder<K>>,
+    /// 边的图层及可见图层掩码，由 `add_edge_in_layer` 和 `set_visible_layers` 设置
+    pub(crate) layers: EdgeLayers<K>,
+    /// 边的批次标签，由 `add_edge_tagged` 设置
+    pub(crate) tags: EdgeTags<K>,
+    /// 可选的操作计数，由 `enable_metrics` 开启
+    pub(crate) metrics: Option<GraphMetrics>,
+    /// 顶点度数上限，由 `set_max_degree` 设置
+    pub(crate) max_degree: Option<u32>,
+    /// 插入边超出度数上限时的处理方式
+    pub(crate) degree_policy: DegreePolicy<K>,
+    /// 被固定、不允许删除的顶点
+    pub(crate) pinned: HashSet<VertexId<K>>,
+    /// 可选的结构变更日志，由 `enable_change_log` 开启
+    pub(crate) change_log: Option<ChangeLog<K>>,
+    /// 最近删除的 ID，用于诊断使用已删除 ID 的查询
+    #[cfg(feature = "removal-diagnostics")]
+    pub(crate) quarantine: Quarantine<K>,
+    /// 结构版本号，每次增删顶点或边时递增
+    version: u64,
+}
+
+/// [`SlotMapGraph::add_vertex_connected`] 返回的新顶点 ID 和新边 ID
+pub type ConnectedVertex<K = DefaultKey> = (VertexId<K>, Vec<EdgeId<K>>);
+
+/// 删除顶点时对其关联边的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RemovePolicy {
+    /// 连同关联边一起删除，`SupportsElementRemoval::remove_vertex` 使用该策略
+    #[default]
+    Cascade,
+    /// 顶点仍有关联边时拒绝删除，图保持不变
+    Restrict,
+}
+
+/// 插入边会使端点超出度数上限时的处理方式，见 [`SlotMapGraph::set_max_degree`]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum DegreePolicy<K: Key = DefaultKey> {
+    /// 可返回错误的入口拒绝插入，图保持不变；`Graph::add_edge` 等无法返回错误的入口照常插入
+    #[default]
+    Reject,
+    /// 照常插入，并以超出上限的端点和上限调用回调
+    Warn(fn(VertexId<K>, u32)),
 }
 
 /// 顶点引用
@@ -75,9 +156,10 @@ where
     }
 }
 
-impl<'graph, Graph> graph_api_lib::VertexReference<'graph, Graph> for VertexReference<'graph, Graph>
+impl<'graph, Graph, K> graph_api_lib::VertexReference<'graph, Graph> for VertexReference<'graph, Graph>
 where
-    Graph: graph_api_lib::Graph<VertexId = VertexId, EdgeId = EdgeId>,
+    K: Key,
+    Graph: graph_api_lib::Graph<VertexId = VertexId<K>, EdgeId = EdgeId<K>>,
 {
     fn id(&self) -> Graph::VertexId {
         self.id
@@ -127,10 +209,11 @@ where
     }
 }
 
-impl<'graph, Graph> graph_api_lib::VertexReference<'graph, Graph>
+impl<'graph, Graph, K> graph_api_lib::VertexReference<'graph, Graph>
     for VertexReferenceMut<'graph, Graph>
 where
-    Graph: graph_api_lib::Graph<VertexId = VertexId, EdgeId = EdgeId>,
+    K: Key,
+    Graph: graph_api_lib::Graph<VertexId = VertexId<K>, EdgeId = EdgeId<K>>,
 {
     fn id(&self) -> Graph::VertexId {
         self.id
@@ -150,10 +233,11 @@ where
     }
 }
 
-impl<'graph, Graph> graph_api_lib::VertexReferenceMut<'graph, Graph>
+impl<'graph, Graph, K> graph_api_lib::VertexReferenceMut<'graph, Graph>
     for VertexReferenceMut<'graph, Graph>
 where
-    Graph: graph_api_lib::Graph<VertexId = VertexId, EdgeId = EdgeId> + 'graph,
+    K: Key,
+    Graph: graph_api_lib::Graph<VertexId = VertexId<K>, EdgeId = EdgeId<K>> + 'graph,
 {
     type MutationListener<'reference> = ();
 
@@ -196,9 +280,45 @@ where
     }
 }
 
-impl<'a, Graph> graph_api_lib::EdgeReference<'a, Graph> for EdgeReference<'a, Graph>
+impl<Graph> EdgeReference<'_, Graph>
+where
+    Graph: graph_api_lib::Graph,
+{
+    /// 边相对于顶点 `vertex_id` 的方向
+    ///
+    /// 顶点是边的起点时返回 `Outgoing`，是终点时返回 `Incoming`；
+    /// 自环同时满足两者，返回 `Outgoing`。边与该顶点无关时返回 `None`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Direction, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let edge = graph.add_edge(a, b, ());
+    ///
+    /// let edge_ref = graph.edge(edge).unwrap();
+    /// assert_eq!(edge_ref.direction_from(a), Some(Direction::Outgoing));
+    /// assert_eq!(edge_ref.direction_from(b), Some(Direction::Incoming));
+    /// ```
+    pub fn direction_from(&self, vertex_id: Graph::VertexId) -> Option<Direction> {
+        if self.from == vertex_id {
+            Some(Direction::Outgoing)
+        } else if self.to == vertex_id {
+            Some(Direction::Incoming)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, Graph, K> graph_api_lib::EdgeReference<'a, Graph> for EdgeReference<'a, Graph>
 where
-    Graph: graph_api_lib::Graph<VertexId = VertexId, EdgeId = EdgeId>,
+    K: Key,
+    Graph: graph_api_lib::Graph<VertexId = VertexId<K>, EdgeId = EdgeId<K>>,
 {
     fn id(&self) -> Graph::EdgeId {
         self.id
@@ -233,6 +353,47 @@ where
     weight: &'graph mut Graph::Edge,
     from: Graph::VertexId,
     to: Graph::VertexId,
+    /// 修改前的标签和相关标签索引，释放时同步标签变化
+    labels: EdgeLabelGuard<'graph, Graph>,
+}
+
+type EdgeLabel<Graph> = <<Graph as graph_api_lib::Graph>::Edge as Element>::Label;
+
+/// 可变边引用释放时需要同步的标签索引
+#[derive(Debug)]
+struct EdgeLabelGuard<'graph, Graph>
+where
+    Graph: graph_api_lib::Graph,
+{
+    /// 修改前的标签
+    old_label: EdgeLabel<Graph>,
+    /// 全图边标签索引
+    pub(crate) edge_labels: &'graph mut HashIndex<EdgeLabel<Graph>, Graph::EdgeId>,
+    /// 启用时的邻接边标签缓存
+    pub(crate) adjacent_labels: Option<&'graph mut AdjacentLabelCache<EdgeLabel<Graph>, Graph::VertexId>>,
+    /// 启用标签缓存时该边的缓存项
+    cached_label: Option<&'graph mut EdgeLabel<Graph>>,
+}
+
+impl<Graph> Drop for EdgeReferenceMut<'_, Graph>
+where
+    Graph: graph_api_lib::Graph,
+{
+    fn drop(&mut self) {
+        let new_label = self.weight.label();
+        let labels = &mut self.labels;
+        if let Some(cached) = labels.cached_label.as_mut() {
+            **cached = new_label;
+        }
+        if new_label == labels.old_label {
+            return;
+        }
+        labels.edge_labels.remove(&labels.old_label, &self.id);
+        labels.edge_labels.insert(new_label, self.id);
+        if let Some(cache) = labels.adjacent_labels.as_mut() {
+            cache.relabel(self.from, self.to, labels.old_label, new_label);
+        }
+    }
 }
 
 impl<Graph> From<EdgeReferenceMut<'_, Graph>> for ElementId<Graph>
@@ -244,9 +405,10 @@ where
     }
 }
 
-impl<Graph> graph_api_lib::EdgeReference<'_, Graph> for EdgeReferenceMut<'_, Graph>
+impl<Graph, K> graph_api_lib::EdgeReference<'_, Graph> for EdgeReferenceMut<'_, Graph>
 where
-    Graph: graph_api_lib::Graph<VertexId = VertexId, EdgeId = EdgeId>,
+    K: Key,
+    Graph: graph_api_lib::Graph<VertexId = VertexId<K>, EdgeId = EdgeId<K>>,
 {
     fn id(&self) -> Graph::EdgeId {
         self.id
@@ -271,9 +433,10 @@ where
     }
 }
 
-impl<Graph> graph_api_lib::EdgeReferenceMut<'_, Graph> for EdgeReferenceMut<'_, Graph>
+impl<Graph, K> graph_api_lib::EdgeReferenceMut<'_, Graph> for EdgeReferenceMut<'_, Graph>
 where
-    Graph: graph_api_lib::Graph<VertexId = VertexId, EdgeId = EdgeId>,
+    K: Key,
+    Graph: graph_api_lib::Graph<VertexId = VertexId<K>, EdgeId = EdgeId<K>>,
 {
     type MutationListener<'reference> = ();
 
@@ -296,24 +459,36 @@ where
 }
 
 /// 顶点迭代器
-pub struct VertexIter<'search, 'graph, Vertex, Edge>
+///
+/// 按候选 ID 的顺序产生顶点，保证：
+///
+/// - 候选中已不存在的 ID（元素已被删除，或槽位已被重用）被跳过，不计入 `limit`
+/// - 带有预筛选时（标签查询），先用 ID 判断是否匹配，不匹配的候选不读取权重、不计入 `limit`
+/// - 最多产生 `limit` 个顶点；达到 `limit` 后不再读取剩余的候选
+/// - 返回 `None` 之后再调用总是返回 `None`（实现 [`FusedIterator`]）
+/// - `size_hint` 的下界为 0（剩余候选可能都已失效），上界不超过剩余名额和剩余候选数
+pub struct VertexIter<'search, 'graph, Vertex, Edge, K>
 where
     Vertex: Element ,
     Edge: Element ,
+    K: Key,
 {
     _phantom: PhantomData<(&'search (), Vertex, Edge)>,
-    vertices: &'graph VertexContainer<Vertex>,
-    keys: SmallBox<dyn Iterator<Item = VertexId> + 'graph, S8>,
+    vertices: &'graph VertexContainer<Vertex, K>,
+    keys: SmallBox<dyn Iterator<Item = VertexId<K>> + 'graph, S8>,
+    /// 读取权重之前排除不匹配的候选顶点
+    prefilter: Option<Prefilter<'graph, K>>,
     count: usize,
     limit: usize,
 }
 
-impl<'graph, Vertex, Edge> Iterator for VertexIter<'_, 'graph, Vertex, Edge>
+impl<'graph, Vertex, Edge, K> Iterator for VertexIter<'_, 'graph, Vertex, Edge, K>
 where
     Vertex: Element,
     Edge: Element,
+    K: Key,
 {
-    type Item = VertexReference<'graph, SlotMapGraph<Vertex, Edge>>;
+    type Item = VertexReference<'graph, SlotMapGraph<Vertex, Edge, K>>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.count >= self.limit {
@@ -321,34 +496,62 @@ where
         }
 
         while let Some(id) = self.keys.next() {
+            if self.prefilter.as_ref().is_some_and(|prefilter| !prefilter(id)) {
+                continue;
+            }
             if let Some(weight) = self.vertices.get(id) {
                 self.count += 1;
                 return Some(VertexReference { id, weight });
             }
         }
+        // 候选耗尽，之后不再访问可能不是 fused 的候选迭代器
+        self.limit = self.count;
         None
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        remaining_hint(self.limit - self.count, self.keys.size_hint())
+    }
+}
+
+impl<Vertex, Edge, K> FusedIterator for VertexIter<'_, '_, Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+/// 剩余 `remaining` 个名额、候选的 `size_hint` 为 `keys` 时迭代器的 `size_hint`
+fn remaining_hint(remaining: usize, keys: (usize, Option<usize>)) -> (usize, Option<usize>) {
+    let upper = keys.1.map_or(remaining, |keys| keys.min(remaining));
+    (0, Some(upper))
 }
 
 /// 边迭代器
-pub struct EdgeIter<'search, 'graph, Vertex, Edge>
+///
+/// 候选 ID 在创建时收集，迭代时逐个查找；跳过已失效的 ID、`limit` 和结束后的行为
+/// 与 [`VertexIter`] 相同。
+pub struct EdgeIter<'search, 'graph, Vertex, Edge, K>
 where
     Vertex: Element ,
     Edge: Element,
+    K: Key,
 {
     _phantom: PhantomData<(&'search (), Vertex, Edge)>,
-    edges: &'graph EdgeContainer<Edge>,
-    keys: std::vec::IntoIter<EdgeId>,
+    edges: &'graph EdgeContainer<Edge, K>,
+    keys: std::vec::IntoIter<EdgeId<K>>,
     count: usize,
     limit: usize,
 }
 
-impl<'graph, Vertex, Edge> Iterator for EdgeIter<'_, 'graph, Vertex, Edge>
+impl<'graph, Vertex, Edge, K> Iterator for EdgeIter<'_, 'graph, Vertex, Edge, K>
 where
     Vertex: Element,
     Edge: Element,
+    K: Key,
 {
-    type Item = EdgeReference<'graph, SlotMapGraph<Vertex, Edge>>;
+    type Item = EdgeReference<'graph, SlotMapGraph<Vertex, Edge, K>>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.count >= self.limit {
@@ -356,36 +559,115 @@ where
         }
 
         while let Some(id) = self.keys.next() {
-            if let Some((weight, conn)) = self.edges.get(id) {
-                // if let Some(conn) = self.edges.get_connection(id) {
-                    self.count += 1;
-                    return Some(EdgeReference {
-                        id,
-                        weight,
-                        from: conn.from(),
-                        to: conn.to(),
-                    });
-                // }
+            if let Some((weight, conn)) = self.edges.get_with_info(id) {
+                self.count += 1;
+                return Some(EdgeReference {
+                    id,
+                    weight,
+                    from: conn.from(),
+                    to: conn.to(),
+                });
             }
         }
+        self.limit = self.count;
         None
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        remaining_hint(self.limit - self.count, self.keys.size_hint())
+    }
+}
+
+impl<Vertex, Edge, K> FusedIterator for EdgeIter<'_, '_, Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+/// 遍历所有顶点，等价于不带限制的全扫描
+///
+/// ```rust
+/// use graph_api_lib::{Graph, VertexReference};
+/// use pi_slotmap_graph::SlotMapGraph;
+///
+/// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+/// graph.extend([1, 2, 3]);
+///
+/// let mut sum = 0;
+/// for vertex in &graph {
+///     sum += *vertex.weight();
+/// }
+/// assert_eq!(sum, 6);
+/// ```
+impl<'graph, Vertex, Edge, K> IntoIterator for &'graph SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    type Item = VertexReference<'graph, SlotMapGraph<Vertex, Edge, K>>;
+    type IntoIter = VertexIter<'graph, 'graph, Vertex, Edge, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        VertexIter {
+            _phantom: PhantomData,
+            vertices: &self.vertices,
+            keys: smallbox!(self.vertices.keys()),
+            prefilter: None,
+            count: 0,
+            limit: usize::MAX,
+        }
+    }
+}
+
+/// 消费图，按顶点产生 `(VertexId, Vertex)`
+///
+/// 顶点数据按值移出，不要求 `Clone`；所有边随图一起被丢弃。
+impl<Vertex, Edge, K> IntoIterator for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    type Item = (VertexId<K>, Vertex);
+    type IntoIter = crate::storage::vertex::IntoIter<Vertex, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vertices.into_iter()
+    }
+}
+
+/// 批量添加顶点
+impl<Vertex, Edge, K> Extend<Vertex> for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    fn extend<I: IntoIterator<Item = Vertex>>(&mut self, iter: I) {
+        for vertex in iter {
+            self.add_vertex(vertex);
+        }
+    }
 }
 
-impl<Vertex, Edge> Default for SlotMapGraph<Vertex, Edge>
+impl<Vertex, Edge, K> Default for SlotMapGraph<Vertex, Edge, K>
 where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
 {
     fn default() -> Self {
-        Self::new()
+        Self::with_key()
     }
 }
 
 impl<Vertex, Edge> SlotMapGraph<Vertex, Edge>
 where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
+    Vertex: Element,
+    Edge: Element,
 {
     /// 创建一个新的空图
     ///
@@ -396,9 +678,9 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::SlotMapGraph;
+    /// use pi_slotmap_graph::SlotMapGraph;
     ///
-    /// let graph: SlotMapGraph<String, i32> = SlotMapGraph::new();
+    /// let graph: SlotMapGraph<u32, f32> = SlotMapGraph::new();
     /// assert_eq!(graph.vertex_count(), 0);
     /// assert_eq!(graph.edge_count(), 0);
     /// ```
@@ -409,717 +691,4801 @@ where
     /// - **空间复杂度**: O(1)
     /// - **内存分配**: 最小化初始分配
     pub fn new() -> Self {
+        Self::with_key()
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 创建使用自定义键类型的空图
+    ///
+    /// 键类型由 `pi_slotmap::new_key_type!` 定义，顶点和边的 ID 都携带该类型，
+    /// 不同键类型的图之间的 ID 无法混用。`new_key_type!` 的展开引用了 `pi_null`，
+    /// 使用方需要同时依赖该 crate。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use pi_slotmap::new_key_type;
+    /// use pi_slotmap_graph::{SlotMapGraph, VertexId};
+    ///
+    /// new_key_type! { struct NodeKey; }
+    ///
+    /// let graph: SlotMapGraph<u32, (), NodeKey> = SlotMapGraph::with_key();
+    /// let ids: Vec<VertexId<NodeKey>> = graph.vertex_ids().collect();
+    /// assert!(ids.is_empty());
+    /// ```
+    pub fn with_key() -> Self {
         Self {
-            vertices: VertexContainer::new(),
-            edges: EdgeContainer::new(),
+            vertices: VertexContainer::with_key(),
+            edges: EdgeContainer::with_key(),
             vertex_query: SimpleVertexQuery::new(),
+            vertex_query_enabled: true,
+            vertex_labels: VertexLabelIndex::new(),
+            declared_index: DeclaredIndex::new(),
+            index_specs: Vec::new(),
+            edge_labels: HashIndex::new(),
+            unindexed_edges: HashSet::new(),
+            adjacent_labels: None,
+            edge_pairs: None,
+            marks: None,
+            spatial: None,
+            label_cache: None,
+            creation_order: None,
+            layers: EdgeLayers::new(),
+            tags: EdgeTags::new(),
+            metrics: None,
+            max_degree: None,
+            degree_policy: DegreePolicy::Reject,
+            pinned: HashSet::new(),
+            change_log: None,
+            #[cfg(feature = "removal-diagnostics")]
+            quarantine: Quarantine::default(),
+            version: 0,
         }
     }
 
+    /// 创建使用自定义键类型、预先分配顶点和边存储的空图
+    ///
+    /// 插入不超过 `vertices` 个顶点、`edges` 条边之前，顶点和边的槽位存储以及邻接表不会重新分配。
+    pub fn with_capacity_and_key(vertices: usize, edges: usize) -> Self {
+        let mut graph = Self::with_key();
+        graph.vertices = VertexContainer::with_capacity_and_key(vertices);
+        graph.edges = EdgeContainer::with_capacity_and_key(edges);
+        graph.edges.reserve_vertices(vertices);
+        graph
+    }
+
+    /// 不重新分配时可容纳的顶点数量
+    pub fn vertex_capacity(&self) -> usize {
+        self.vertices.capacity()
+    }
+
+    /// 不重新分配时可容纳的边数量
+    pub fn edge_capacity(&self) -> usize {
+        self.edges.capacity()
+    }
+
+    /// 清空图，保证保留所有已分配的存储
+    ///
+    /// 与 [`clear`](SupportsClear::clear) 的效果相同，但顶点和边的槽位存储、
+    /// 邻接表以及各顶点出边/入边列表的缓冲区都被保留下来复用，
+    /// 标签索引、简单查询器等哈希表清空后同样保留容量。适合每帧重建的图：
+    /// 重新填充到不超过此前的规模时，顶点、边和邻接表不会重新分配。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// for frame in 0..3 {
+    ///     graph.clear_keep_capacity();
+    ///     let root = graph.add_vertex(frame);
+    ///     for i in 0..100 {
+    ///         let child = graph.add_vertex(i);
+    ///         graph.add_edge(root, child, ());
+    ///     }
+    /// }
+    /// assert_eq!(graph.vertex_count(), 101);
+    /// assert!(graph.vertex_capacity() >= 101);
+    /// ```
+    pub fn clear_keep_capacity(&mut self) {
+        self.edges.clear_keep_capacity();
+        SupportsClear::clear(self);
+    }
+
     /// 获取简单查询器的可变引用
     ///
     /// 提供对智能查询系统的访问权限，可以用于：
     /// - 手动建立索引
     /// - 执行智能查询
     /// - 管理查询缓存
-    pub fn vertex_query_mut(&mut self) -> &mut SimpleVertexQuery {
+    pub fn vertex_query_mut(&mut self) -> &mut SimpleVertexQuery<K> {
         &mut self.vertex_query
     }
 
     /// 获取简单查询器的不可变引用
-    pub fn vertex_query(&self) -> &SimpleVertexQuery {
+    pub fn vertex_query(&self) -> &SimpleVertexQuery<K> {
         &self.vertex_query
     }
 
     /// 为顶点添加字符串索引
     ///
     /// 便利方法，用于为指定的顶点添加字符串值到索引中。
-    pub fn index_vertex_string(&mut self, vertex_id: VertexId, value: &str) {
+    pub fn index_vertex_string(&mut self, vertex_id: VertexId<K>, value: &str) {
         self.vertex_query.insert_string(value, vertex_id);
     }
 
     /// 为顶点添加整数索引
     ///
     /// 便利方法，用于为指定的顶点添加整数值到索引中。
-    pub fn index_vertex_int(&mut self, vertex_id: VertexId, value: i64) {
+    pub fn index_vertex_int(&mut self, vertex_id: VertexId<K>, value: i64) {
         self.vertex_query.insert_int(value, vertex_id);
     }
 
-    /// 获取边的起始顶点
+    /// 一次遍历为所有顶点建立索引
     ///
-    /// 根据给定的边ID，返回该边的起始顶点ID。如果边不存在，返回None。
+    /// `extract` 为每个顶点返回它的索引项，适合批量导入后补建索引。
+    /// 每种索引按 `vertex_count()` 预留空间；重复调用不会产生重复的索引项。
     ///
-    /// # 参数
+    /// # 示例
     ///
-    /// * `edge_id` - 要查询的边的标识符
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    /// use pi_slotmap_graph::index::IndexEntry;
+    /// use smallvec::smallvec;
     ///
-    /// # 返回值
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let alice = graph.add_vertex(25);
+    /// graph.add_vertex(30);
     ///
-    /// * `Some(VertexId)` - 边的起始顶点ID
-    /// * `None` - 边不存在或已被删除
+    /// graph.index_all_vertices(|_, age| smallvec![IndexEntry::Int(*age as i64)]);
+    /// assert_eq!(graph.vertex_query().query_int(25).collect::<Vec<_>>(), vec![alice]);
+    /// assert_eq!(graph.vertex_query().range_int(20..40).count(), 2);
+    /// ```
+    pub fn index_all_vertices<F>(&mut self, extract: F)
+    where
+        F: for<'a> Fn(VertexId<K>, &'a Vertex) -> SmallVec<[IndexEntry<'a>; 2]>,
+    {
+        let entries = self.vertices.iter().map(|(id, vertex)| (id, extract(id, vertex)));
+        self.vertex_query.insert_entries(self.vertices.len(), entries);
+    }
+
+    /// 把边移到顶点邻接表的最前面
+    ///
+    /// 每个顶点的出边和入边默认按插入顺序迭代（`outgoing_edges`、`incoming_edges`
+    /// 以及 `Graph::edges`），先加入的边先产生，且不受删除其他边后槽位复用的影响。
+    /// 该方法把 `edge_id` 提前到 `vertex_id` 的出边和/或入边列表首位，其余边保持相对顺序。
+    ///
+    /// 边与该顶点无关时返回 `false`，不做任何修改。
     ///
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::SlotMapGraph;
+    /// use graph_api_lib::{EdgeReference, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
     ///
-    /// let mut graph = SlotMapGraph::new();
-    /// let v1 = graph.add_vertex("A");
-    /// let v2 = graph.add_vertex("B");
-    /// let edge = graph.add_edge("connects", v1, v2);
+    /// let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+    /// let root = graph.add_vertex(());
+    /// let child = graph.add_vertex(());
+    /// let first = graph.add_edge(root, child, 1);
+    /// let second = graph.add_edge(root, child, 2);
     ///
-    /// assert_eq!(graph.edge_from(edge), Some(v1));
+    /// assert!(graph.move_edge_to_front(root, second));
+    /// let order: Vec<_> = graph.outgoing_edges(root).map(|e| *e.weight()).collect();
+    /// assert_eq!(order, vec![2, 1]);
+    /// # let _ = first;
     /// ```
-    ///
-    /// # 性能特征
-    ///
-    /// - **时间复杂度**: O(1) - 直接通过SlotMap查找
-    /// - **空间复杂度**: O(1) - 不分配额外内存
-    pub fn edge_from(&self, edge_id: EdgeId) -> Option<VertexId> {
-        self.edges.get_connection(edge_id).map(|info| info.from())
+    pub fn move_edge_to_front(&mut self, vertex_id: VertexId<K>, edge_id: EdgeId<K>) -> bool {
+        self.edges.move_to_front(vertex_id, edge_id)
     }
 
-    /// 获取边的目标顶点
-    ///
-    /// 根据给定的边ID，返回该边的目标顶点ID。如果边不存在，返回None。
+    /// 按给定顺序重排顶点的邻接边
     ///
-    /// # 参数
+    /// `order` 中属于该顶点出边的边按给定顺序排在出边最前，入边同理；
+    /// 未列出的边保持原有相对顺序排在其后。
+    /// `order` 含有与该顶点无关或重复的边时返回 `false`，不做任何修改。
+    pub fn reorder_edges(&mut self, vertex_id: VertexId<K>, order: &[EdgeId<K>]) -> bool {
+        self.edges.reorder(vertex_id, order)
+    }
+
+    /// 删除边时邻接表的维护方式
+    pub fn edge_removal_order(&self) -> EdgeRemovalOrder {
+        self.edges.removal_order()
+    }
+
+    /// 设置删除边时邻接表的维护方式
     ///
-    /// * `edge_id` - 要查询的边的标识符
+    /// 默认的 [`EdgeRemovalOrder::Shift`] 保持其余边的插入顺序，删除为 O(度数)；
+    /// [`EdgeRemovalOrder::Swap`] 用末尾的边填补空位，省去平移但会打乱顺序。
+    pub fn set_edge_removal_order(&mut self, order: EdgeRemovalOrder) {
+        self.edges.set_removal_order(order);
+    }
+
+    /// 开启邻接边标签缓存
     ///
-    /// # 返回值
+    /// 为每个顶点维护关联边的标签计数，按标签查询邻接边时，
+    /// 若顶点没有该标签的边则直接返回空迭代器，不再逐条比较边的标签。
+    /// 缓存随 `add_edge`、`remove_edge`、`remove_vertex` 和 `clear` 更新，
+    /// 经由 `edge_mut` 修改的标签在引用释放时同步；
+    /// 经由 `edge_weight_mut` 修改的边会让两个端点的缓存失效，回退到逐边比较。
     ///
-    /// * `Some(VertexId)` - 边的目标顶点ID
-    /// * `None` - 边不存在或已被删除
+    /// 已开启时再次调用会从现有的边重建缓存，同时清除失效标记。
     ///
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::SlotMapGraph;
+    /// use graph_api_lib::{EdgeSearch, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
     ///
-    /// let mut graph = SlotMapGraph::new();
-    /// let v1 = graph.add_vertex("A");
-    /// let v2 = graph.add_vertex("B");
-    /// let edge = graph.add_edge("connects", v1, v2);
+    /// let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+    /// let hub = graph.add_vertex(());
+    /// let leaf = graph.add_vertex(());
+    /// graph.add_edge(hub, leaf, 7);
     ///
-    /// assert_eq!(graph.edge_to(edge), Some(v2));
+    /// graph.enable_adjacent_label_cache();
+    /// assert_eq!(graph.adjacent_label_count(hub, ()), Some(1));
+    /// assert_eq!(graph.edges(hub, &EdgeSearch::scan()).count(), 1);
     /// ```
     ///
     /// # 性能特征
     ///
-    /// - **时间复杂度**: O(1) - 直接通过SlotMap查找
-    /// - **空间复杂度**: O(1) - 不分配额外内存
-    pub fn edge_to(&self, edge_id: EdgeId) -> Option<VertexId> {
-        self.edges.get_connection(edge_id).map(|info| info.to())
+    /// - **开启**: O(E)
+    /// - **增删边**: 额外 O(1) 的哈希表更新
+    pub fn enable_adjacent_label_cache(&mut self) {
+        let mut cache = AdjacentLabelCache::new();
+        for (_, edge, info) in self.edges.iter_with_connections() {
+            cache.insert(info.from(), info.to(), edge.label());
+        }
+        self.adjacent_labels = Some(cache);
     }
 
-    /// 获取从指定顶点出发的所有边
-    ///
-    /// 返回一个迭代器，产生从给定顶点出发的所有有向边。这是一个懒迭代器，
-    /// 按需生成边引用，不会预计算所有结果。
-    ///
-    /// # 参数
-    ///
-    /// * `vertex_id` - 源顶点的标识符
-    ///
-    /// # 返回值
+    /// 关闭邻接边标签缓存并释放其内存
+    pub fn disable_adjacent_label_cache(&mut self) {
+        self.adjacent_labels = None;
+    }
+
+    /// 邻接边标签缓存是否已开启
+    pub fn has_adjacent_label_cache(&self) -> bool {
+        self.adjacent_labels.is_some()
+    }
+
+    /// 开启端点对索引
     ///
-    /// 返回一个迭代器，产生 `EdgeReference` 类型的项，每个项包含：
-    /// - 边的ID
-    /// - 边的权重数据
-    /// - 起始和目标顶点ID
+    /// 按 `(起点, 终点)` 索引全部边，之后 `has_edge`、`find_edge` 和 `edges_between`
+    /// 不再扫描起点的出边列表，适用于在中心顶点上频繁判断两点是否相连的场景。
+    /// 索引随边的增删自动维护；已开启时会按当前的边重建。
     ///
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::SlotMapGraph;
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
     ///
-    /// let mut graph = SlotMapGraph::new();
-    /// let alice = graph.add_vertex("Alice");
-    /// let bob = graph.add_vertex("Bob");
-    /// let charlie = graph.add_vertex("Charlie");
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let hub = graph.add_vertex(());
+    /// let leaf = graph.add_vertex(());
+    /// graph.enable_pair_index();
+    /// let edge = graph.add_edge(hub, leaf, ());
     ///
-    /// let friendship_ab = graph.add_edge("friend", alice, bob);
-    /// let follows_ac = graph.add_edge("follows", alice, charlie);
-    ///
-    /// // 查找Alice的所有出边
-    /// for edge_ref in graph.outgoing_edges(alice) {
-    ///     println!("Edge: {} -> {}", edge_ref.tail(), edge_ref.head());
-    ///     println!("Weight: {}", edge_ref.weight());
-    /// }
+    /// assert!(graph.has_edge(hub, leaf));
+    /// assert_eq!(graph.find_edge(hub, leaf), Some(edge));
+    /// assert!(!graph.has_edge(leaf, hub));
     /// ```
     ///
     /// # 性能特征
     ///
-    /// - **时间复杂度**: O(k) - k为顶点的出度
-    /// - **空间复杂度**: O(1) - 迭代器状态为常数大小
-    /// - **缓存友好**: 连续内存访问模式
-    ///
-    /// # 注意事项
-    ///
-    /// - 返回的迭代器持有图的不可变引用
-    /// - 在迭代期间不能修改图结构
-    /// - 如果顶点不存在，迭代器为空
-    pub fn outgoing_edges(&self, vertex_id: VertexId) -> impl Iterator<Item = EdgeReference<'_, Self>> {
-        self.edges.edges_from(vertex_id).filter_map(move |edge_id| {
-            // if let Some(conn) = self.edges.get_connection(edge_id) {
-                if let Some((weight, conn)) = self.edges.get(edge_id) {
-                    return Some(EdgeReference {
-                        id: edge_id,
-                        weight,
-                        from: conn.from(),
-                        to: conn.to(),
-                    });
-                }
-            // }
-            None
-        })
+    /// - **开启**: O(E)
+    /// - **增删边**: 额外 O(1) 的哈希表更新（删除时另需 O(平行边数)）
+    pub fn enable_pair_index(&mut self) {
+        let mut pairs = EdgePairIndex::new();
+        for (edge_id, _, info) in self.edges.iter_with_connections() {
+            pairs.insert(info.from(), info.to(), edge_id);
+        }
+        self.edge_pairs = Some(pairs);
     }
 
-    /// 获取指向指定顶点的所有边
-    ///
-    /// 返回一个迭代器，产生指向给定顶点的所有有向边。这是一个懒迭代器，
-    /// 按需生成边引用，不会预计算所有结果。
-    ///
-    /// # 参数
-    ///
-    /// * `vertex_id` - 目标顶点的标识符
-    ///
-    /// # 返回值
+    /// 关闭端点对索引并释放其内存
+    pub fn disable_pair_index(&mut self) {
+        self.edge_pairs = None;
+    }
+
+    /// 端点对索引是否已开启
+    pub fn has_pair_index(&self) -> bool {
+        self.edge_pairs.is_some()
+    }
+
+    /// 开启元素位标记
     ///
-    /// 返回一个迭代器，产生 `EdgeReference` 类型的项，每个项包含：
-    /// - 边的ID
-    /// - 边的权重数据
-    /// - 起始和目标顶点ID
+    /// 每个顶点和边可以带一个 `u8` 位标记，用作"已访问"、"选中"等临时状态。
+    /// 元素被删除时其标记随之移除，槽位重用后新元素不会继承旧标记。已开启时不做任何修改。
     ///
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::SlotMapGraph;
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
     ///
-    /// let mut graph = SlotMapGraph::new();
-    /// let alice = graph.add_vertex("Alice");
-    /// let bob = graph.add_vertex("Bob");
-    /// let charlie = graph.add_vertex("Charlie");
+    /// const VISITED: u8 = 1 << 0;
+    /// const SELECTED: u8 = 1 << 1;
     ///
-    /// let friendship_ab = graph.add_edge("friend", alice, bob);
-    /// let follows_cb = graph.add_edge("follows", charlie, bob);
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let edge = graph.add_edge(a, a, ());
+    /// graph.enable_marks();
     ///
-    /// // 查找Bob的所有入边
-    /// for edge_ref in graph.incoming_edges(bob) {
-    ///     println!("Edge: {} -> {}", edge_ref.tail(), edge_ref.head());
-    ///     println!("From: {}", edge_ref.tail());
-    /// }
+    /// assert!(graph.set_mark(a, VISITED | SELECTED));
+    /// assert!(graph.set_mark(edge, SELECTED));
+    /// graph.clear_all_marks(SELECTED);
+    /// assert!(graph.has_mark(a, VISITED) && !graph.has_mark(edge, SELECTED));
+    /// assert_eq!(graph.marked_vertices(VISITED).collect::<Vec<_>>(), vec![a]);
     /// ```
-    ///
-    /// # 性能特征
-    ///
-    /// - **时间复杂度**: O(k) - k为顶点的入度
-    /// - **空间复杂度**: O(1) - 迭代器状态为常数大小
-    /// - **缓存友好**: 连续内存访问模式
-    ///
-    /// # 注意事项
-    ///
-    /// - 返回的迭代器持有图的不可变引用
-    /// - 在迭代期间不能修改图结构
-    /// - 如果顶点不存在，迭代器为空
-    pub fn incoming_edges(&self, vertex_id: VertexId) -> impl Iterator<Item = EdgeReference<'_, Self>> {
-        self.edges.edges_to(vertex_id).filter_map(move |edge_id| {
-            // if let Some(conn) = self.edges.get_connection(edge_id) {
-                if let Some((weight, conn)) = self.edges.get(edge_id) {
-                    return Some(EdgeReference {
-                        id: edge_id,
-                        weight,
-                        from: conn.from(),
-                        to: conn.to(),
-                    });
-                }
-            // }
-            None
-        })
+    pub fn enable_marks(&mut self) {
+        self.marks.get_or_insert_with(MarkTable::new);
     }
 
-    /// 获取与指定顶点相邻的所有边（入边和出边）
-    pub fn adjacent_edges(&self, vertex_id: VertexId) -> impl Iterator<Item = EdgeReference<'_, Self>> {
-        self.edges.edges_adjacent(vertex_id).filter_map(move |edge_id| {
-            // if let Some(conn) = self.edges.get_connection(edge_id) {
-                if let Some((weight, conn)) = self.edges.get(edge_id) {
-                    return Some(EdgeReference {
-                        id: edge_id,
-                        weight,
-                        from: conn.from(),
-                        to: conn.to(),
-                    });
-                }
-            // }
-            None
-        })
+    /// 关闭元素位标记并释放其内存
+    pub fn disable_marks(&mut self) {
+        self.marks = None;
     }
 
-    /// 检查两个顶点之间是否存在边
-    pub fn has_edge(&self, from: VertexId, to: VertexId) -> bool {
-        self.edges.has_edge_between(from, to)
+    /// 元素位标记是否已开启
+    pub fn has_marks(&self) -> bool {
+        self.marks.is_some()
     }
 
-    /// 获取两个顶点之间的所有边
-    pub fn edges_between(&self, from: VertexId, to: VertexId) -> impl Iterator<Item = EdgeReference<'_, Self>> {
-        self.edges.edges_between(from, to).filter_map(move |edge_id| {
-            // if let Some(conn) = self.edges.get_connection(edge_id) {
-                if let Some((weight, conn)) = self.edges.get(edge_id) {
-                    return Some(EdgeReference {
-                        id: edge_id,
-                        weight,
-                        from: conn.from(),
-                        to: conn.to(),
-                    });
-                }
-            // }
-            None
-        })
+    /// 开启标签缓存
+    ///
+    /// 为每个顶点和边保存一份 `label()` 的结果，按标签过滤关联边
+    /// （`edges` 的标签和邻接顶点标签条件、`adjacent_edges_by_label`、`global_edges` 的邻接顶点标签条件）
+    /// 时读取缓存而不是每次调用 `label()`，适用于 `label()` 开销较大的元素类型。
+    /// 缓存随元素增删和经由图的修改自动维护；已开启时会按当前的元素重建。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{EdgeSearch, Graph};
+    /// use graph_api_test::{Edge, EdgeLabel, Vertex};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<Vertex, Edge> = SlotMapGraph::new();
+    /// graph.enable_label_cache();
+    /// let a = graph.add_vertex(Vertex::Rust);
+    /// let b = graph.add_vertex(Vertex::Rust);
+    /// graph.add_edge(a, b, Edge::Created);
+    /// graph.add_edge(a, b, Edge::Knows { since: 2020 });
+    ///
+    /// assert_eq!(graph.edges(a, &EdgeSearch::label(EdgeLabel::Created)).count(), 1);
+    /// ```
+    ///
+    /// # 性能特征
+    ///
+    /// - **开启**: O(V + E) 次 `label()` 调用
+    /// - **增删元素**: 额外 O(1) 的 `SecondaryMap` 更新
+    pub fn enable_label_cache(&mut self) {
+        let mut cache = LabelCache::new();
+        for (vertex_id, weight) in self.vertices.iter() {
+            cache.set_vertex(vertex_id, weight.label());
+        }
+        for (edge_id, weight) in self.edges.iter() {
+            cache.set_edge(edge_id, weight.label());
+        }
+        self.label_cache = Some(cache);
     }
 
-    /// 获取顶点的出度
-    pub fn out_degree(&self, vertex_id: VertexId) -> usize {
-        self.edges.edges_from(vertex_id).count()
+    /// 关闭标签缓存并释放其内存
+    pub fn disable_label_cache(&mut self) {
+        self.label_cache = None;
     }
 
-    /// 获取顶点的入度
-    pub fn in_degree(&self, vertex_id: VertexId) -> usize {
-        self.edges.edges_to(vertex_id).count()
+    /// 标签缓存是否已开启
+    pub fn has_label_cache(&self) -> bool {
+        self.label_cache.is_some()
     }
 
-    /// 获取顶点的度（入度+出度）
-    pub fn degree(&self, vertex_id: VertexId) -> usize {
-        self.edges.edges_adjacent(vertex_id).count()
+    /// 顶点的标签，开启标签缓存且有缓存项时不调用 `label()`
+    fn vertex_label(&self, vertex_id: VertexId<K>, weight: &Vertex) -> Vertex::Label {
+        self.label_cache.as_ref().and_then(|cache| cache.vertex(vertex_id)).unwrap_or_else(|| weight.label())
     }
 
-    /// 获取所有顶点
-    pub fn all_vertices(&self) -> impl Iterator<Item = (VertexId, &Vertex)> {
-        self.vertices.iter()
+    /// 边的标签，开启标签缓存且有缓存项时不调用 `label()`
+    fn edge_label(&self, edge_id: EdgeId<K>, weight: &Edge) -> Edge::Label {
+        self.label_cache.as_ref().and_then(|cache| cache.edge(edge_id)).unwrap_or_else(|| weight.label())
     }
 
-    /// 获取所有边
-    pub fn all_edges(&self) -> impl Iterator<Item = (EdgeId, &Edge, VertexId, VertexId)> {
-        self.edges.iter_with_connections().map(|(id, edge, info)| {
-            (id, edge, info.from(), info.to())
-        })
+    /// 为顶点或边设置 `mask` 中的位
+    ///
+    /// 标记未开启或元素不存在时返回 `false`。
+    pub fn set_mark(&mut self, id: impl Into<ElementId<Self>>, mask: u8) -> bool {
+        let id = id.into();
+        if !self.contains_element(id) {
+            return false;
+        }
+        let Some(marks) = self.marks.as_mut() else {
+            return false;
+        };
+        match id {
+            ElementId::Vertex(vertex_id) => marks.set_vertex(vertex_id, mask),
+            ElementId::Edge(edge_id) => marks.set_edge(edge_id, mask),
+        }
+        true
     }
 
-    /// 获取顶点数量
-    pub fn vertex_count(&self) -> usize {
-        self.vertices.len()
+    /// 清除顶点或边的 `mask` 位
+    pub fn clear_mark(&mut self, id: impl Into<ElementId<Self>>, mask: u8) {
+        let Some(marks) = self.marks.as_mut() else {
+            return;
+        };
+        match id.into() {
+            ElementId::Vertex(vertex_id) => marks.clear_vertex(vertex_id, mask),
+            ElementId::Edge(edge_id) => marks.clear_edge(edge_id, mask),
+        }
     }
 
-    /// 获取边数量
-    pub fn edge_count(&self) -> usize {
-        self.edges.len()
+    /// 顶点或边是否带有 `mask` 中的任一位
+    pub fn has_mark(&self, id: impl Into<ElementId<Self>>, mask: u8) -> bool {
+        let Some(marks) = self.marks.as_ref() else {
+            return false;
+        };
+        match id.into() {
+            ElementId::Vertex(vertex_id) => marks.vertex_has(vertex_id, mask),
+            ElementId::Edge(edge_id) => marks.edge_has(edge_id, mask),
+        }
     }
 
-    /// 检查是否包含指定顶点
-    pub fn contains_vertex(&self, vertex_id: VertexId) -> bool {
-        self.vertices.contains(vertex_id)
+    /// 清除所有顶点和边的 `mask` 位
+    pub fn clear_all_marks(&mut self, mask: u8) {
+        if let Some(marks) = self.marks.as_mut() {
+            marks.clear_all(mask);
+        }
     }
 
-    /// 检查是否包含指定边
-    pub fn contains_edge(&self, edge_id: EdgeId) -> bool {
-        self.edges.contains(edge_id)
+    /// 带有 `mask` 中任一位的顶点，按槽位下标升序
+    pub fn marked_vertices(&self, mask: u8) -> impl Iterator<Item = VertexId<K>> + '_ {
+        self.marks.iter().flat_map(move |marks| marks.vertices(mask))
     }
 
-    /// 检查图是否为空
-    pub fn is_empty(&self) -> bool {
-        self.vertices.is_empty() && self.edges.is_empty()
+    /// 带有 `mask` 中任一位的边，按槽位下标升序
+    pub fn marked_edges(&self, mask: u8) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.marks.iter().flat_map(move |marks| marks.edges(mask))
     }
-}
 
-impl<Vertex, Edge> Graph for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element,
-    Edge: Element,
-{
-    type Vertex = Vertex;
-    type Edge = Edge;
-    type VertexId = VertexId;
-    type EdgeId = EdgeId;
-    type VertexReference<'graph> = VertexReference<'graph, Self> where Self: 'graph;
-    type VertexReferenceMut<'graph> = VertexReferenceMut<'graph, Self> where Self: 'graph;
-    type EdgeReference<'graph> = EdgeReference<'graph, Self> where Self: 'graph;
-    type EdgeReferenceMut<'graph> = EdgeReferenceMut<'graph, Self> where Self: 'graph;
-    type EdgeIter<'search, 'graph> = EdgeIter<'search, 'graph, Vertex, Edge> where Self: 'graph;
-    type VertexIter<'search, 'graph> = VertexIter<'search, 'graph, Vertex, Edge> where Self: 'graph;
+    /// 开启创建顺序
+    ///
+    /// 之后加入的每个顶点和边获得一个单调递增的创建序号，顶点和边共用一个计数器。
+    /// 槽位重用后按槽位顺序无法判断先后，可用 [`creation_seq`](Self::creation_seq) 比较，
+    /// 或用 [`vertices_in_creation_order`](Self::vertices_in_creation_order) 按创建顺序遍历。
+    /// 开启前已存在的元素按槽位顺序依次编号，先顶点后边；已开启时不做任何修改。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Graph, SupportsElementRemoval};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// graph.enable_creation_order();
+    /// let a = graph.add_vertex(1);
+    /// let b = graph.add_vertex(2);
+    /// graph.remove_vertex(a);
+    /// // c 重用 a 的槽位，但创建得比 b 晚
+    /// let c = graph.add_vertex(3);
+    ///
+    /// assert!(graph.creation_seq(b) < graph.creation_seq(c));
+    /// assert_eq!(graph.vertices_in_creation_order().collect::<Vec<_>>(), vec![b, c]);
+    /// ```
+    ///
+    /// # 性能特征
+    ///
+    /// - **开启**: O((V + E) log(V + E))
+    /// - **增删元素**: 额外 O(log n)
+    pub fn enable_creation_order(&mut self) {
+        if self.creation_order.is_some() {
+            return;
+        }
+        let mut order = CreationOrder::new();
+        for (vertex_id, _) in self.vertices.iter() {
+            order.push_vertex(vertex_id);
+        }
+        for (edge_id, _) in self.edges.iter() {
+            order.push_edge(edge_id);
+        }
+        self.creation_order = Some(order);
+    }
 
-    fn add_vertex(&mut self, vertex: Self::Vertex) -> Self::VertexId {
-        let vertex_id = self.vertices.insert(vertex);
+    /// 关闭创建顺序并释放其内存，再次开启时重新编号
+    pub fn disable_creation_order(&mut self) {
+        self.creation_order = None;
+    }
 
-        // 自动构建基础索引（如果可能的话）
-        // 注意：由于我们不知道顶点的内部结构，这里无法自动建立索引
-        // 用户需要手动调用索引方法来构建自定义索引
-        // 在实际使用中，可以通过实现特定的顶点类型来自动索引
+    /// 创建顺序是否已开启
+    pub fn has_creation_order(&self) -> bool {
+        self.creation_order.is_some()
+    }
 
-        vertex_id
+    /// 顶点或边的创建序号，创建顺序未开启或元素不存在时返回 `None`
+    ///
+    /// 序号越小创建越早，顶点和边的序号可以相互比较。
+    pub fn creation_seq(&self, id: impl Into<ElementId<Self>>) -> Option<u64> {
+        let order = self.creation_order.as_ref()?;
+        match id.into() {
+            ElementId::Vertex(vertex_id) => order.vertex(vertex_id),
+            ElementId::Edge(edge_id) => order.edge(edge_id),
+        }
     }
 
-    fn add_edge(
-        &mut self,
-        from: Self::VertexId,
-        to: Self::VertexId,
-        edge: Self::Edge,
-    ) -> Self::EdgeId {
-        let edge_info = EdgeInfo::new(EdgeId::default(), from, to);
-        self.edges.insert(edge, edge_info)
+    /// 按创建顺序遍历顶点，创建顺序未开启时为空
+    pub fn vertices_in_creation_order(&self) -> impl Iterator<Item = VertexId<K>> + '_ {
+        self.creation_order.iter().flat_map(|order| order.vertices())
     }
 
-    fn vertex(&self, id: Self::VertexId) -> Option<Self::VertexReference<'_>> {
-        self.vertices.get(id).map(|weight| VertexReference { id, weight })
+    /// 按创建顺序遍历边，创建顺序未开启时为空
+    pub fn edges_in_creation_order(&self) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.creation_order.iter().flat_map(|order| order.edges())
     }
 
-    fn vertex_mut(&mut self, id: Self::VertexId) -> Option<Self::VertexReferenceMut<'_>> {
-        self.vertices.get_mut(id).map(|weight| VertexReferenceMut { id, weight })
+    /// 添加边并记录批次标签 `tag`，其余同 `add_edge`
+    ///
+    /// 标签不写入边数据，可由 [`edges_with_tag`](Self::edges_with_tag) 列出、
+    /// [`remove_edges_with_tag`](Self::remove_edges_with_tag) 整批删除。
+    /// 单独删除的边随即从批次中移除，见 [`EdgeTags`]。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// graph.add_edge_tagged(a, b, (), 1);
+    /// graph.add_edge_tagged(b, a, (), 1);
+    /// let kept = graph.add_edge_tagged(a, a, (), 2);
+    ///
+    /// // 第 1 次导入被取代
+    /// assert_eq!(graph.remove_edges_with_tag(1), 2);
+    /// assert_eq!(graph.edges_with_tag(2).collect::<Vec<_>>(), vec![kept]);
+    /// assert_eq!(graph.edge_count(), 1);
+    /// ```
+    pub fn add_edge_tagged(&mut self, from: VertexId<K>, to: VertexId<K>, edge: Edge, tag: u64) -> EdgeId<K> {
+        let edge_id = self.add_edge(from, to, edge);
+        self.tags.insert(edge_id, tag);
+        edge_id
     }
 
-    fn vertices<'search>(
-        &self,
-        search: &VertexSearch<'search, Self>,
-    ) -> Self::VertexIter<'search, '_> {
-        // 根据 VertexSearch 类型选择最优查询策略
-        // let keys_iter: SmallBox<dyn Iterator<Item = VertexId> + '_, S8> = match search {
-        //     VertexSearch::Scan { .. } => {
-        //         // 全扫描：遍历所有顶点
-        //         smallbox!(self.vertices.keys())
-        //     }
-        //     VertexSearch::Label { .. } => {
-        //         // 对于简单的实现，由于我们没有按标签的索引，回退到全扫描
-        //         // 在更高级的实现中，这里可以使用按标签的索引
-        //         smallbox!(self.vertices.keys())
-        //     }
-        //     VertexSearch::Index { value, .. } => {
-        //         // 使用智能查询进行索引查找
-        //         smallbox!(self.vertex_query.query_value(value))
-        //     }
-        //     VertexSearch::Range { range, .. } => {
-        //         // 使用智能查询进行范围查找
-        //         smallbox!(self.vertex_query.range_value(range))
-        //     }
-        //     VertexSearch::FullText { search, .. } => {
-        //         // 全文搜索：目前使用字符串查询作为近似实现
-        //         match search {
-        //             graph_api_lib::Value::Str(s) => smallbox!(self.vertex_query.query_string(s)),
-        //             _ => smallbox!(std::iter::empty()),
-        //         }
-        //     }
-        //     _ => {
-        //         // 处理未来可能的新查询类型
-        //         smallbox!(std::iter::empty())
-        //     }
-        // };
-
-        VertexIter::<Vertex, Edge> {
-            _phantom: PhantomData,
-            vertices: &self.vertices,
-            keys: smallbox!(self.vertices.keys()),
-            count: 0,
-            limit: search.limit(),
-        }
+    /// 添加边并记录批次标签 `tag`，并检查度数上限，错误同 [`try_add_edge`](Self::try_add_edge)
+    pub fn try_add_edge_tagged(
+        &mut self,
+        from: VertexId<K>,
+        to: VertexId<K>,
+        edge: Edge,
+        tag: u64,
+    ) -> Result<EdgeId<K>, GraphError<K>> {
+        let edge_id = self.try_add_edge(from, to, edge)?;
+        self.tags.insert(edge_id, tag);
+        Ok(edge_id)
     }
 
-    fn edge(&self, id: Self::EdgeId) -> Option<Self::EdgeReference<'_>> {
-        // if let Some(conn) = self.edges.get_connection(id) {
-            if let Some((weight, conn)) = self.edges.get(id) {
-                return Some(EdgeReference {
-                    id,
-                    weight,
-                    from: conn.from(),
-                    to: conn.to(),
-                });
-            }
-        // }
-        None
+    /// 边的批次标签，边不存在或没有标签时返回 `None`
+    pub fn edge_tag(&self, edge_id: EdgeId<K>) -> Option<u64> {
+        self.tags.tag(edge_id)
     }
 
-    fn edge_mut(&mut self, edge: Self::EdgeId) -> Option<Self::EdgeReferenceMut<'_>> {
-        // let conn = self.edges.get_connection(edge)?;
-       
-        if let Some((weight, conn)) = self.edges.get_mut(edge) {
-             let (from, to) = (conn.from(), conn.to());
-            return Some(EdgeReferenceMut {
-                id: edge,
-                weight,
-                from,
-                to,
-            });
-        }
-        None
+    /// 标签为 `tag` 的边，按插入顺序
+    pub fn edges_with_tag(&self, tag: u64) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.tags.edges(tag)
     }
 
-    fn edges<'search>(
-        &self,
-        vertex: Self::VertexId,
-        search: &EdgeSearch<'search, Self>,
-    ) -> Self::EdgeIter<'search, '_> {
-        use graph_api_lib::Direction;
+    /// 删除标签为 `tag` 的全部边，返回删除的数量
+    ///
+    /// 复杂度为 O(批次大小)，不扫描其他边。
+    pub fn remove_edges_with_tag(&mut self, tag: u64) -> usize {
+        let edges = self.tags.take(tag);
+        edges.into_iter().filter(|edge_id| self.remove_edge(*edge_id).is_some()).count()
+    }
 
-        // 首先获取所有符合条件的边（基于方向）
-        let candidate_edges: Vec<EdgeId> = match search.direction {
-            Direction::Outgoing => self.edges.edges_from(vertex).collect(),
-            Direction::Incoming => self.edges.edges_to(vertex).collect(),
-            Direction::All => self.edges.edges_adjacent(vertex).collect(),
-        };
+    /// 在 `layer` 号图层中添加边，其余同 `add_edge`
+    ///
+    /// 经由 `add_edge` 添加的边属于 0 号图层。图层的可见性由
+    /// [`set_visible_layers`](Self::set_visible_layers) 控制。
+    ///
+    /// # Panics
+    ///
+    /// `layer` 不小于 64 时 panic，此时图不被修改。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{EdgeReference, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// const DATA: u32 = 0;
+    /// const CONTROL: u32 = 1;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge_in_layer(a, b, (), DATA);
+    /// let control = graph.add_edge_in_layer(a, c, (), CONTROL);
+    ///
+    /// graph.set_visible_layers(1 << CONTROL);
+    /// assert_eq!(graph.out_degree(a), 1);
+    /// assert_eq!(graph.outgoing_edges(a).map(|e| e.id()).collect::<Vec<_>>(), vec![control]);
+    /// // all_edges 不受可见性影响
+    /// assert_eq!(graph.all_edges().count(), 2);
+    /// ```
+    pub fn add_edge_in_layer(&mut self, from: VertexId<K>, to: VertexId<K>, edge: Edge, layer: u32) -> EdgeId<K> {
+        assert!(layer < MAX_LAYERS, "edge layer {layer} out of range, must be below {MAX_LAYERS}");
+        let edge_id = self.add_edge(from, to, edge);
+        self.layers.set(edge_id, layer);
+        edge_id
+    }
 
-        // 然后过滤掉不符合标签条件的边
-        let filtered_keys: Vec<EdgeId> = if let Some(target_label) = search.label {
-            candidate_edges
-                .into_iter()
-                .filter(|&edge_id| {
-                    if let Some((edge_weight, _)) = self.edges.get(edge_id) {
-                        edge_weight.label() == target_label
-                    } else {
-                        false
-                    }
-                })
-                .collect()
-        } else {
-            candidate_edges
-        };
+    /// 在 `layer` 号图层中添加边，并检查度数上限，错误同 [`try_add_edge`](Self::try_add_edge)
+    ///
+    /// # Panics
+    ///
+    /// `layer` 不小于 64 时 panic，此时图不被修改。
+    pub fn try_add_edge_in_layer(
+        &mut self,
+        from: VertexId<K>,
+        to: VertexId<K>,
+        edge: Edge,
+        layer: u32,
+    ) -> Result<EdgeId<K>, GraphError<K>> {
+        assert!(layer < MAX_LAYERS, "edge layer {layer} out of range, must be below {MAX_LAYERS}");
+        let edge_id = self.try_add_edge(from, to, edge)?;
+        self.layers.set(edge_id, layer);
+        Ok(edge_id)
+    }
 
-        EdgeIter::<Vertex, Edge> {
-            _phantom: PhantomData,
-            edges: &self.edges,
-            keys: filtered_keys.into_iter(),
-            count: 0,
-            limit: search.limit(),
+    /// 把已有的边移到 `layer` 号图层，边不存在时返回 `false`
+    ///
+    /// # Panics
+    ///
+    /// `layer` 不小于 64 时 panic。
+    pub fn set_edge_layer(&mut self, edge_id: EdgeId<K>, layer: u32) -> bool {
+        if !self.edges.contains(edge_id) {
+            return false;
         }
+        self.layers.set(edge_id, layer);
+        true
     }
 
-    fn clear(&mut self) {
-        self.vertices.clear();
-        self.edges.clear();
+    /// 边所在的图层，边不存在时返回 `None`
+    pub fn edge_layer(&self, edge_id: EdgeId<K>) -> Option<u32> {
+        self.edges.contains(edge_id).then(|| self.layers.layer(edge_id))
     }
-}
-
-// 实现所有支持trait
-impl<Vertex, Edge> SupportsVertexLabelIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
-
-impl<Vertex, Edge> SupportsEdgeLabelIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
-
-impl<Vertex, Edge> SupportsVertexHashIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
-
-impl<Vertex, Edge> SupportsEdgeHashIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
-
-impl<Vertex, Edge> SupportsVertexRangeIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
-
-impl<Vertex, Edge> SupportsEdgeRangeIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
 
-impl<Vertex, Edge> SupportsVertexFullTextIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
+    /// 设置可见图层的位掩码，第 `n` 位对应 `n` 号图层
+    ///
+    /// 默认全部可见。不可见图层中的边不会出现在 `edges`、`outgoing_edges`、`incoming_edges`、
+    /// `adjacent_edges`、`adjacent_edges_by_label`、`adjacent_label_counts`、`distinct_neighbors`、`out_edges_into`、`neighbors_into`、
+    /// 度数方法以及 [`traversal`](crate::algo::traversal) 和 [`reachability`](crate::algo::reachability) 的遍历中；
+    /// 这些度数方法在部分图层不可见时按关联边逐条判断，为 O(度数)。
+    /// `all_edges`、`edge`、`edge_count`、端点查询（`has_edge`、`find_edge`、`edges_between`）、
+    /// 邻接表切片（`outgoing_adjacency`、`incoming_adjacency`）和其余算法不受可见性影响。
+    pub fn set_visible_layers(&mut self, mask: u64) {
+        self.layers.set_visible(mask);
+    }
 
-impl<Vertex, Edge> SupportsEdgeAdjacentLabelIndex for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-}
+    /// 可见图层的位掩码
+    pub fn visible_layers(&self) -> u64 {
+        self.layers.visible()
+    }
 
-impl<Vertex, Edge> SupportsClear for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-    fn clear(&mut self) {
-        self.vertices.clear();
-        self.edges.clear();
+    /// 边所在的图层当前是否可见
+    #[inline]
+    pub(crate) fn edge_visible(&self, edge_id: EdgeId<K>) -> bool {
+        self.layers.is_visible(edge_id)
     }
-}
 
-impl<Vertex, Edge> SupportsElementRemoval for SlotMapGraph<Vertex, Edge>
-where
-    Vertex: Element + Clone,
-    Edge: Element + Clone,
-{
-    fn remove_vertex(&mut self, id: Self::VertexId) -> Option<Self::Vertex> {
-        // 删除顶点时，也需要删除相关的所有边
-        let edges_to_remove: Vec<EdgeId> = self.edges.edges_adjacent(id).collect();
-        for edge_id in edges_to_remove {
-            self.edges.remove(edge_id);
+    /// 开启格子边长为 `cell_size` 的顶点坐标索引
+    ///
+    /// 坐标由 [`index_vertex_position`](Self::index_vertex_position) 写入，顶点被删除时自动移除。
+    /// 已开启时按新的格子边长重新划分已有坐标。
+    ///
+    /// # Panics
+    ///
+    /// `cell_size` 不是正的有限数时 panic。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Graph, SupportsElementRemoval};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// graph.enable_spatial_index(10.0);
+    /// let near = graph.add_vertex(());
+    /// let far = graph.add_vertex(());
+    /// graph.index_vertex_position(near, [1.0, 2.0]);
+    /// graph.index_vertex_position(far, [40.0, 0.0]);
+    ///
+    /// assert_eq!(graph.vertices_within([0.0, 0.0], 5.0).collect::<Vec<_>>(), vec![near]);
+    /// assert_eq!(graph.nearest_vertices([30.0, 0.0], 1)[0].0, far);
+    ///
+    /// graph.remove_vertex(far);
+    /// assert_eq!(graph.nearest_vertices([30.0, 0.0], 1)[0].0, near);
+    /// ```
+    pub fn enable_spatial_index(&mut self, cell_size: f32) {
+        let mut spatial = SpatialIndex::new(cell_size);
+        if let Some(old) = self.spatial.take() {
+            for (id, point) in old.positions() {
+                spatial.insert(point, id);
+            }
         }
-
-        self.vertices.remove(id)
+        self.spatial = Some(spatial);
     }
 
-    fn remove_edge(&mut self, edge: Self::EdgeId) -> Option<Self::Edge> {
-        self.edges.remove(edge).map(|(edge, _)| edge)
+    /// 关闭顶点坐标索引并释放其内存
+    pub fn disable_spatial_index(&mut self) {
+        self.spatial = None;
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use graph_api_lib::{Element, Graph, VertexSearch, VertexReference, EdgeReference};
-    use super::*;
 
-    #[derive(Debug, Clone)]
-    struct TestVertex {
-        name: String,
-        _value: i32,
+    /// 顶点坐标索引，未开启时返回 `None`
+    pub fn spatial_index(&self) -> Option<&SpatialIndex<K>> {
+        self.spatial.as_ref()
     }
 
-    #[derive(Debug, Clone)]
-    struct TestEdge {
-        weight: f64,
+    /// 设置顶点的坐标，替换之前的坐标
+    ///
+    /// 索引未开启、顶点不存在或坐标含 NaN、无穷大时返回 `false`。
+    pub fn index_vertex_position(&mut self, vertex_id: VertexId<K>, position: Point) -> bool {
+        if !self.vertices.contains(vertex_id) {
+            return false;
+        }
+        self.spatial.as_mut().is_some_and(|spatial| spatial.insert(position, vertex_id))
     }
 
-    impl Element for TestVertex {
-        type Label = ();
+    /// 与 `center` 的距离不超过 `radius` 的顶点，顺序不确定；索引未开启时为空
+    pub fn vertices_within(&self, center: Point, radius: f32) -> impl Iterator<Item = VertexId<K>> + '_ {
+        self.spatial.iter().flat_map(move |spatial| spatial.query_radius(center, radius))
+    }
 
-        fn label(&self) -> Self::Label {
-            ()
-        }
+    /// 距 `center` 最近的 `k` 个顶点及其距离，按距离升序；索引未开启时为空
+    pub fn nearest_vertices(&self, center: Point, k: usize) -> Vec<(VertexId<K>, f32)> {
+        self.spatial.as_ref().map_or_else(Vec::new, |spatial| spatial.nearest(center, k))
+    }
+
+    /// 顶点上标签为 `label` 的关联边数量（自环计一次）
+    ///
+    /// 缓存未开启或该顶点的缓存已失效时返回 `None`。
+    pub fn adjacent_label_count(&self, vertex_id: VertexId<K>, label: Edge::Label) -> Option<u32> {
+        self.adjacent_labels.as_ref()?.count(vertex_id, label)
+    }
+
+    /// 获取边的起点和终点
+    ///
+    /// 只做一次查找即返回 `(起点, 终点)`，相当于同时调用 [`edge_from`](Self::edge_from)
+    /// 和 [`edge_to`](Self::edge_to)，也不构造 `EdgeReference`。边不存在时返回 `None`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let edge = graph.add_edge(v1, v2, 10);
+    ///
+    /// assert_eq!(graph.edge_endpoints(edge), Some((v1, v2)));
+    /// ```
+    pub fn edge_endpoints(&self, edge_id: EdgeId<K>) -> Option<(VertexId<K>, VertexId<K>)> {
+        self.edges.endpoints(edge_id)
+    }
+
+    /// 获取边的起始顶点
+    ///
+    /// 根据给定的边ID，返回该边的起始顶点ID。如果边不存在，返回None。
+    ///
+    /// # 参数
+    ///
+    /// * `edge_id` - 要查询的边的标识符
+    ///
+    /// # 返回值
+    ///
+    /// * `Some(VertexId)` - 边的起始顶点ID
+    /// * `None` - 边不存在或已被删除
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let edge = graph.add_edge(v1, v2, 10);
+    ///
+    /// assert_eq!(graph.edge_from(edge), Some(v1));
+    /// ```
+    ///
+    /// # 性能特征
+    ///
+    /// - **时间复杂度**: O(1) - 直接通过SlotMap查找
+    /// - **空间复杂度**: O(1) - 不分配额外内存
+    pub fn edge_from(&self, edge_id: EdgeId<K>) -> Option<VertexId<K>> {
+        self.edges.get_connection(edge_id).map(|info| info.from())
+    }
+
+    /// 获取边的目标顶点
+    ///
+    /// 根据给定的边ID，返回该边的目标顶点ID。如果边不存在，返回None。
+    ///
+    /// # 参数
+    ///
+    /// * `edge_id` - 要查询的边的标识符
+    ///
+    /// # 返回值
+    ///
+    /// * `Some(VertexId)` - 边的目标顶点ID
+    /// * `None` - 边不存在或已被删除
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let v1 = graph.add_vertex(1);
+    /// let v2 = graph.add_vertex(2);
+    /// let edge = graph.add_edge(v1, v2, 10);
+    ///
+    /// assert_eq!(graph.edge_to(edge), Some(v2));
+    /// ```
+    ///
+    /// # 性能特征
+    ///
+    /// - **时间复杂度**: O(1) - 直接通过SlotMap查找
+    /// - **空间复杂度**: O(1) - 不分配额外内存
+    pub fn edge_to(&self, edge_id: EdgeId<K>) -> Option<VertexId<K>> {
+        self.edges.get_connection(edge_id).map(|info| info.to())
+    }
+
+    /// 获取从指定顶点出发的所有边
+    ///
+    /// 返回一个迭代器，产生从给定顶点出发的所有有向边。这是一个懒迭代器，
+    /// 按需生成边引用，不会预计算所有结果。
+    ///
+    /// # 参数
+    ///
+    /// * `vertex_id` - 源顶点的标识符
+    ///
+    /// # 返回值
+    ///
+    /// 返回一个迭代器，产生 `EdgeReference` 类型的项，每个项包含：
+    /// - 边的ID
+    /// - 边的权重数据
+    /// - 起始和目标顶点ID
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{EdgeReference, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let alice = graph.add_vertex(1);
+    /// let bob = graph.add_vertex(2);
+    /// let charlie = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(alice, bob, 10);
+    /// graph.add_edge(alice, charlie, 20);
+    ///
+    /// // 查找Alice的所有出边
+    /// for edge_ref in graph.outgoing_edges(alice) {
+    ///     println!("Edge: {} -> {}", edge_ref.tail(), edge_ref.head());
+    ///     println!("Weight: {}", edge_ref.weight());
+    /// }
+    /// assert_eq!(graph.outgoing_edges(alice).count(), 2);
+    /// ```
+    ///
+    /// # 性能特征
+    ///
+    /// - **时间复杂度**: O(k) - k为顶点的出度
+    /// - **空间复杂度**: O(1) - 迭代器状态为常数大小
+    /// - **缓存友好**: 连续内存访问模式
+    ///
+    /// # 注意事项
+    ///
+    /// - 返回的迭代器持有图的不可变引用
+    /// - 在迭代期间不能修改图结构
+    /// - 如果顶点不存在，迭代器为空
+    /// - 边按顶点邻接表的顺序产生，默认即插入顺序，见 [`move_edge_to_front`](Self::move_edge_to_front)
+    pub fn outgoing_edges(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = EdgeReference<'_, Self>> {
+        self.edges.edges_from(vertex_id).filter(move |edge_id| self.edge_visible(*edge_id)).filter_map(move |edge_id| {
+            if let Some((weight, conn)) = self.edges.get_with_info(edge_id) {
+                return Some(EdgeReference {
+                    id: edge_id,
+                    weight,
+                    from: conn.from(),
+                    to: conn.to(),
+                });
+            }
+            None
+        })
+    }
+
+    /// 获取指向指定顶点的所有边
+    ///
+    /// 返回一个迭代器，产生指向给定顶点的所有有向边。这是一个懒迭代器，
+    /// 按需生成边引用，不会预计算所有结果。
+    ///
+    /// # 参数
+    ///
+    /// * `vertex_id` - 目标顶点的标识符
+    ///
+    /// # 返回值
+    ///
+    /// 返回一个迭代器，产生 `EdgeReference` 类型的项，每个项包含：
+    /// - 边的ID
+    /// - 边的权重数据
+    /// - 起始和目标顶点ID
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{EdgeReference, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let alice = graph.add_vertex(1);
+    /// let bob = graph.add_vertex(2);
+    /// let charlie = graph.add_vertex(3);
+    ///
+    /// graph.add_edge(alice, bob, 10);
+    /// graph.add_edge(charlie, bob, 20);
+    ///
+    /// // 查找Bob的所有入边
+    /// for edge_ref in graph.incoming_edges(bob) {
+    ///     println!("Edge: {} -> {}", edge_ref.tail(), edge_ref.head());
+    ///     assert_eq!(edge_ref.head(), bob);
+    /// }
+    /// assert_eq!(graph.incoming_edges(bob).count(), 2);
+    /// ```
+    ///
+    /// # 性能特征
+    ///
+    /// - **时间复杂度**: O(k) - k为顶点的入度
+    /// - **空间复杂度**: O(1) - 迭代器状态为常数大小
+    /// - **缓存友好**: 连续内存访问模式
+    ///
+    /// # 注意事项
+    ///
+    /// - 返回的迭代器持有图的不可变引用
+    /// - 在迭代期间不能修改图结构
+    /// - 如果顶点不存在，迭代器为空
+    pub fn incoming_edges(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = EdgeReference<'_, Self>> {
+        self.edges.edges_to(vertex_id).filter(move |edge_id| self.edge_visible(*edge_id)).filter_map(move |edge_id| {
+            if let Some((weight, conn)) = self.edges.get_with_info(edge_id) {
+                return Some(EdgeReference {
+                    id: edge_id,
+                    weight,
+                    from: conn.from(),
+                    to: conn.to(),
+                });
+            }
+            None
+        })
+    }
+
+    /// 获取与指定顶点相邻的所有边（入边和出边）
+    pub fn adjacent_edges(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = EdgeReference<'_, Self>> {
+        self.edges.edges_adjacent(vertex_id).filter(move |edge_id| self.edge_visible(*edge_id)).filter_map(move |edge_id| {
+            if let Some((weight, conn)) = self.edges.get_with_info(edge_id) {
+                return Some(EdgeReference {
+                    id: edge_id,
+                    weight,
+                    from: conn.from(),
+                    to: conn.to(),
+                });
+            }
+            None
+        })
+    }
+
+    /// 获取与指定顶点相邻的所有边及其相对于该顶点的方向
+    ///
+    /// 与 [`adjacent_edges`](Self::adjacent_edges) 顺序相同：先出边，再入边。
+    /// 方向由 [`EdgeReference::direction_from`] 给出，自环只产生一次，方向为 `Outgoing`。
+    pub fn adjacent_edges_with_direction(
+        &self,
+        vertex_id: VertexId<K>,
+    ) -> impl Iterator<Item = (Direction, EdgeReference<'_, Self>)> {
+        self.adjacent_edges(vertex_id)
+            .filter_map(move |edge_ref| Some((edge_ref.direction_from(vertex_id)?, edge_ref)))
+    }
+
+    /// 获取指定顶点上标签为 `label`、方向为 `direction` 的关联边
+    ///
+    /// `Direction::All` 与 [`adjacent_edges`](Self::adjacent_edges) 顺序相同，自环只产生一次；
+    /// `Outgoing` 与 `Incoming` 都包含自环。
+    /// 开启邻接边标签缓存（[`enable_adjacent_label_cache`](Self::enable_adjacent_label_cache)）后，
+    /// 顶点上没有该标签的边时立即返回；否则逐条比较边的标签，复杂度为 O(该方向的度数)。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Direction, EdgeReference, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let ab = graph.add_edge(a, b, ());
+    ///
+    /// let ids: Vec<_> = graph.adjacent_edges_by_label(b, (), Direction::Incoming).map(|e| e.id()).collect();
+    /// assert_eq!(ids, vec![ab]);
+    /// assert_eq!(graph.adjacent_edges_by_label(b, (), Direction::Outgoing).count(), 0);
+    /// ```
+    pub fn adjacent_edges_by_label(
+        &self,
+        vertex_id: VertexId<K>,
+        label: Edge::Label,
+        direction: Direction,
+    ) -> impl Iterator<Item = EdgeReference<'_, Self>> {
+        let absent = self.adjacent_labels.as_ref().is_some_and(|cache| !cache.may_contain(vertex_id, label));
+        let (outgoing, incoming) = match direction {
+            _ if absent => (&[][..], &[][..]),
+            Direction::Outgoing => (self.outgoing_adjacency(vertex_id), &[][..]),
+            Direction::Incoming => (&[][..], self.incoming_adjacency(vertex_id)),
+            Direction::All => (self.outgoing_adjacency(vertex_id), self.incoming_adjacency(vertex_id)),
+        };
+        let skip_loops = direction == Direction::All;
+        outgoing
+            .iter()
+            .map(|id| (*id, false))
+            .chain(incoming.iter().map(move |id| (*id, skip_loops)))
+            .filter(move |(edge_id, _)| self.edge_visible(*edge_id))
+            .filter_map(move |(edge_id, skip_loops)| {
+                let (weight, conn) = self.edges.get_with_info(edge_id)?;
+                if self.edge_label(edge_id, weight) != label || (skip_loops && conn.from() == conn.to()) {
+                    return None;
+                }
+                Some(EdgeReference {
+                    id: edge_id,
+                    weight,
+                    from: conn.from(),
+                    to: conn.to(),
+                })
+            })
+    }
+
+    /// 指定顶点上每个标签的关联边数量，自环计一次，顺序不固定
+    ///
+    /// 开启邻接边标签缓存、全部图层可见且顶点未失效时直接读取缓存，否则扫描顶点的可见关联边，
+    /// 按标签缓存读取边标签。
+    pub fn adjacent_label_counts(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = (Edge::Label, usize)> + '_ {
+        let cached = self
+            .adjacent_labels
+            .as_ref()
+            .filter(|_| self.layers.all_visible())
+            .and_then(|cache| cache.labels(vertex_id));
+        let counts: HashMap<Edge::Label, usize> = match cached {
+            Some(labels) => labels.map(|(label, count)| (label, count as usize)).collect(),
+            None => {
+                let mut counts = HashMap::new();
+                for edge in self.adjacent_edges(vertex_id) {
+                    *counts.entry(self.edge_label(edge.id, edge.weight)).or_insert(0) += 1;
+                }
+                counts
+            }
+        };
+        counts.into_iter()
+    }
+
+    /// 检查两个顶点之间是否存在边
+    ///
+    /// 只检查 `from -> to` 方向，不考虑反向的边；需要判断两者是否相连时使用
+    /// [`has_edge_undirected`](Self::has_edge_undirected)。
+    ///
+    /// 开启端点对索引（[`enable_pair_index`](Self::enable_pair_index)）后为 O(1)，否则为 O(起点出度)。
+    pub fn has_edge(&self, from: VertexId<K>, to: VertexId<K>) -> bool {
+        match self.edge_pairs.as_ref() {
+            Some(pairs) => pairs.contains(from, to),
+            None => self.edges.has_edge_between(from, to),
+        }
+    }
+
+    /// 查找一条 `from -> to` 的边
+    ///
+    /// 存在平行边时返回 [`edges_between`](Self::edges_between) 产生的第一条。
+    /// 开启端点对索引后为 O(1)，否则为 O(起点出度)。
+    pub fn find_edge(&self, from: VertexId<K>, to: VertexId<K>) -> Option<EdgeId<K>> {
+        match self.edge_pairs.as_ref() {
+            Some(pairs) => pairs.get(from, to).first().copied(),
+            None => self.edges.get_edge_between(from, to),
+        }
+    }
+
+    /// 检查两个顶点之间是否存在任一方向的边
+    ///
+    /// 与有向的 [`has_edge`](Self::has_edge) 不同，`a -> b` 和 `b -> a` 都视为相连。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// graph.add_edge(a, b, ());
+    ///
+    /// assert!(!graph.has_edge(b, a));
+    /// assert!(graph.has_edge_undirected(b, a));
+    /// ```
+    pub fn has_edge_undirected(&self, a: VertexId<K>, b: VertexId<K>) -> bool {
+        self.edges.has_edge_between_undirected(a, b)
+    }
+
+    /// 获取两个顶点之间任一方向的所有边
+    ///
+    /// 与有向的 [`edges_between`](Self::edges_between) 不同，先产生 `a -> b` 的边，
+    /// 再产生 `b -> a` 的边；`a == b` 时每条自环只产生一次。
+    pub fn edges_between_undirected(&self, a: VertexId<K>, b: VertexId<K>) -> impl Iterator<Item = EdgeReference<'_, Self>> {
+        self.edges.edges_between_undirected(a, b).filter_map(move |edge_id| {
+            let (weight, conn) = self.edges.get_with_info(edge_id)?;
+            Some(EdgeReference {
+                id: edge_id,
+                weight,
+                from: conn.from(),
+                to: conn.to(),
+            })
+        })
+    }
+
+    /// 获取两个顶点之间的所有边
+    ///
+    /// 只包含 `from -> to` 方向的边。
+    ///
+    /// 未开启端点对索引时按起点出边列表的顺序产生，O(起点出度)；
+    /// 开启后按边的插入顺序产生，不受 `move_edge_to_front` 等重排影响，O(结果数量)。
+    pub fn edges_between(&self, from: VertexId<K>, to: VertexId<K>) -> impl Iterator<Item = EdgeReference<'_, Self>> {
+        let ids: Box<dyn Iterator<Item = EdgeId<K>> + '_> = match self.edge_pairs.as_ref() {
+            Some(pairs) => Box::new(pairs.get(from, to).iter().copied()),
+            None => Box::new(self.edges.edges_between(from, to)),
+        };
+        ids.filter_map(move |edge_id| {
+            if let Some((weight, conn)) = self.edges.get_with_info(edge_id) {
+                return Some(EdgeReference {
+                    id: edge_id,
+                    weight,
+                    from: conn.from(),
+                    to: conn.to(),
+                });
+            }
+            None
+        })
+    }
+
+    /// 所有互为反向的边对 `(a -> b, b -> a)`，每对无序顶点只产生一次
+    ///
+    /// 一次遍历全部边，按 [`EdgeInfo::sorted_endpoints`] 分组，O(E)。
+    /// 每对中第一条边从 `sorted_endpoints` 的较小端点出发。
+    /// 两个方向都有平行边时按边 ID（[`EdgeId::as_ffi`]）升序一一配对，多出的边不参与配对；
+    /// 结果按第一条边的 ID 升序排列。自环不计入。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// let ab = graph.add_edge(a, b, ());
+    /// let ba = graph.add_edge(b, a, ());
+    /// let bc = graph.add_edge(b, c, ());
+    ///
+    /// assert_eq!(graph.mutual_edges().collect::<Vec<_>>(), vec![(ab, ba)]);
+    /// assert!(graph.is_mutual(ba));
+    /// assert!(!graph.is_mutual(bc));
+    /// ```
+    pub fn mutual_edges(&self) -> impl Iterator<Item = (EdgeId<K>, EdgeId<K>)> {
+        // (较小端点, 较大端点) -> (正向边, 反向边)
+        type Directions<K> = (Vec<EdgeId<K>>, Vec<EdgeId<K>>);
+        let mut pairs: HashMap<(VertexId<K>, VertexId<K>), Directions<K>> = HashMap::new();
+        for (edge_id, _, info) in self.edges.iter_with_connections() {
+            if info.from() == info.to() {
+                continue;
+            }
+            let endpoints = info.sorted_endpoints();
+            let (forward, backward) = pairs.entry(endpoints).or_default();
+            if info.from() == endpoints.0 {
+                forward.push(edge_id);
+            } else {
+                backward.push(edge_id);
+            }
+        }
+
+        let mut mutual = Vec::new();
+        for (mut forward, mut backward) in pairs.into_values() {
+            if forward.is_empty() || backward.is_empty() {
+                continue;
+            }
+            forward.sort_unstable_by_key(EdgeId::as_ffi);
+            backward.sort_unstable_by_key(EdgeId::as_ffi);
+            mutual.extend(forward.into_iter().zip(backward));
+        }
+        mutual.sort_unstable_by_key(|(first, _)| first.as_ffi());
+        mutual.into_iter()
+    }
+
+    /// 边是否有反向的边，即存在 `to -> from` 的边
+    ///
+    /// 只要反向有任一条边即返回 `true`，不考虑 [`mutual_edges`](Self::mutual_edges) 中平行边的配对；
+    /// 自环和不存在的边返回 `false`。复杂度同 [`has_edge`](Self::has_edge)。
+    pub fn is_mutual(&self, edge_id: EdgeId<K>) -> bool {
+        match self.edges.endpoints(edge_id) {
+            Some((from, to)) => from != to && self.has_edge(to, from),
+            None => false,
+        }
+    }
+
+    /// 获取顶点的出度
+    ///
+    /// 读取邻接表中维护的计数器，O(1)；部分图层不可见时只计可见的边，O(出度)。
+    pub fn out_degree(&self, vertex_id: VertexId<K>) -> usize {
+        if self.layers.all_visible() {
+            return self.edges.out_degree(vertex_id);
+        }
+        self.outgoing_adjacency(vertex_id).iter().filter(|edge_id| self.edge_visible(**edge_id)).count()
+    }
+
+    /// 获取顶点的入度
+    ///
+    /// 读取邻接表中维护的计数器，O(1)；部分图层不可见时只计可见的边，O(入度)。
+    pub fn in_degree(&self, vertex_id: VertexId<K>) -> usize {
+        if self.layers.all_visible() {
+            return self.edges.in_degree(vertex_id);
+        }
+        self.incoming_adjacency(vertex_id).iter().filter(|edge_id| self.edge_visible(**edge_id)).count()
+    }
+
+    /// 获取顶点的度（入度+出度，自环只计一次）
+    ///
+    /// 读取邻接表中维护的计数器，O(1)；部分图层不可见时只计可见的边，O(度数)。
+    pub fn degree(&self, vertex_id: VertexId<K>) -> usize {
+        if self.layers.all_visible() {
+            return self.edges.degree(vertex_id);
+        }
+        self.edges.edges_involving(vertex_id).filter(|edge_id| self.edge_visible(*edge_id)).count()
+    }
+
+    /// 设置顶点度数上限，`None` 表示不限制（默认）
+    ///
+    /// 之后插入边时，若任一端点的度数（入度+出度，自环只计一次）已达到上限，
+    /// 按 [`set_degree_policy`](Self::set_degree_policy) 设置的策略处理：
+    ///
+    /// - [`DegreePolicy::Reject`]（默认）：[`try_add_edge`](Self::try_add_edge)、
+    ///   [`try_add_edge_tagged`](Self::try_add_edge_tagged)、[`try_add_edge_in_layer`](Self::try_add_edge_in_layer)
+    ///   和 [`add_vertex_connected`](Self::add_vertex_connected) 返回 [`GraphError::DegreeLimitExceeded`]；
+    ///   无法返回错误的 `Graph::add_edge`、[`add_edge_tagged`](Self::add_edge_tagged)、
+    ///   [`add_edge_in_layer`](Self::add_edge_in_layer) 不强制上限，照常插入，仅在调试构建中输出提示
+    /// - [`DegreePolicy::Warn`]：照常插入并调用回调
+    ///
+    /// 检查读取度数计数器，O(1)。已超出上限的顶点不受影响，只限制之后的插入。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Graph, SupportsElementRemoval};
+    /// use pi_slotmap_graph::{GraphError, SlotMapGraph};
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// graph.set_max_degree(Some(1));
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// let ab = graph.try_add_edge(a, b, ()).unwrap();
+    /// assert_eq!(graph.try_add_edge(a, c, ()), Err(GraphError::DegreeLimitExceeded { vertex: a, limit: 1 }));
+    ///
+    /// graph.remove_edge(ab);
+    /// assert!(graph.try_add_edge(a, c, ()).is_ok());
+    /// ```
+    pub fn set_max_degree(&mut self, limit: Option<u32>) {
+        self.max_degree = limit;
+    }
+
+    /// 顶点度数上限，未设置时返回 `None`
+    pub fn max_degree(&self) -> Option<u32> {
+        self.max_degree
+    }
+
+    /// 设置插入边超出度数上限时的处理方式
+    pub fn set_degree_policy(&mut self, policy: DegreePolicy<K>) {
+        self.degree_policy = policy;
+    }
+
+    /// 添加一条 `from -> to` 的边，并检查度数上限
+    ///
+    /// # 错误
+    ///
+    /// - 端点不存在时返回 [`GraphError::VertexNotFound`]
+    /// - 策略为 [`DegreePolicy::Reject`] 且端点的度数已达上限时返回
+    ///   [`GraphError::DegreeLimitExceeded`]，图保持不变
+    pub fn try_add_edge(&mut self, from: VertexId<K>, to: VertexId<K>, edge: Edge) -> Result<EdgeId<K>, GraphError<K>> {
+        for vertex in [from, to] {
+            if !self.vertices.contains(vertex) {
+                return Err(GraphError::VertexNotFound(vertex));
+            }
+        }
+        self.check_degree(from, to)?;
+        Ok(self.insert_edge(from, to, edge))
+    }
+
+    /// 按度数上限检查将要插入的 `from -> to`，策略为 `Warn` 时调用回调后放行
+    fn check_degree(&self, from: VertexId<K>, to: VertexId<K>) -> Result<(), GraphError<K>> {
+        let Some(limit) = self.max_degree else {
+            return Ok(());
+        };
+        let endpoints = if from == to { &[from][..] } else { &[from, to][..] };
+        for &vertex in endpoints {
+            if self.edges.degree(vertex) >= limit as usize {
+                match self.degree_policy {
+                    DegreePolicy::Reject => return Err(GraphError::DegreeLimitExceeded { vertex, limit }),
+                    DegreePolicy::Warn(warn) => warn(vertex, limit),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 插入边并维护各索引，不检查度数上限
+    fn insert_edge(&mut self, from: VertexId<K>, to: VertexId<K>, edge: Edge) -> EdgeId<K> {
+        self.sync_edge_labels();
+        let label = edge.label();
+        if let Some(cache) = self.adjacent_labels.as_mut() {
+            cache.insert(from, to, label);
+        }
+        let edge_info = EdgeInfo::new(EdgeId::default(), from, to);
+        let edge_id = self.edges.insert(edge, edge_info);
+        self.edge_labels.insert(label, edge_id);
+        if let Some(cache) = self.label_cache.as_mut() {
+            cache.set_edge(edge_id, label);
+        }
+        if let Some(pairs) = self.edge_pairs.as_mut() {
+            pairs.insert(from, to, edge_id);
+        }
+        if let Some(order) = self.creation_order.as_mut() {
+            order.push_edge(edge_id);
+        }
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.edge_added(self.edges.len());
+        }
+        self.version += 1;
+        self.record_change(Change::EdgeAdded(edge_id));
+        edge_id
+    }
+
+    /// 顶点的出边列表
+    ///
+    /// 直接借用邻接表，不分配内存，顺序与 `edges_from` 相同；顶点没有出边或不存在时返回空切片。O(1)。
+    pub fn outgoing_adjacency(&self, vertex_id: VertexId<K>) -> &[EdgeId<K>] {
+        self.edges.adjacency(vertex_id).map_or(&[], |adj| adj.outgoing())
+    }
+
+    /// 顶点的入边列表
+    ///
+    /// 直接借用邻接表，不分配内存，顺序与 `edges_to` 相同；顶点没有入边或不存在时返回空切片。O(1)。
+    pub fn incoming_adjacency(&self, vertex_id: VertexId<K>) -> &[EdgeId<K>] {
+        self.edges.adjacency(vertex_id).map_or(&[], |adj| adj.incoming())
+    }
+
+    /// 清空 `out` 并填入顶点的出边，返回边数
+    ///
+    /// 顺序与 [`outgoing_edges`](Self::outgoing_edges) 相同，只含可见图层中的边。
+    /// 直接读取邻接表，调用方可在多次调用间复用 `out` 的容量；顶点不存在时 `out` 为空。
+    pub fn out_edges_into(&self, vertex_id: VertexId<K>, out: &mut Vec<EdgeId<K>>) -> usize {
+        out.clear();
+        out.extend(self.outgoing_adjacency(vertex_id).iter().copied().filter(|edge_id| self.edge_visible(*edge_id)));
+        out.len()
+    }
+
+    /// 清空 `out` 并按 `direction` 填入相邻顶点，返回顶点数
+    ///
+    /// 每条可见的关联边产生一个顶点，不去重：`Outgoing` 为出边的终点，`Incoming` 为入边的起点，
+    /// `All` 先出边后入边，自环只产生一次（即顶点自身）。
+    /// 直接读取邻接表，调用方可在多次调用间复用 `out` 的容量；顶点不存在时 `out` 为空。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Direction, Graph};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// let c = graph.add_vertex(());
+    /// graph.add_edge(a, b, ());
+    /// graph.add_edge(c, a, ());
+    ///
+    /// let mut buffer = Vec::new();
+    /// assert_eq!(graph.neighbors_into(a, Direction::All, &mut buffer), 2);
+    /// assert_eq!(buffer, vec![b, c]);
+    /// assert_eq!(graph.neighbors_into(b, Direction::Outgoing, &mut buffer), 0);
+    /// assert!(buffer.is_empty());
+    /// ```
+    pub fn neighbors_into(&self, vertex_id: VertexId<K>, direction: Direction, out: &mut Vec<VertexId<K>>) -> usize {
+        out.clear();
+        let endpoint = |edge_id: &EdgeId<K>, incoming: bool| {
+            if !self.edge_visible(*edge_id) {
+                return None;
+            }
+            let info = self.edges.get_connection(*edge_id)?;
+            match incoming {
+                // 方向为 All 时自环已在出边中产生
+                true if direction == Direction::All && info.from() == vertex_id => None,
+                true => Some(info.from()),
+                false => Some(info.to()),
+            }
+        };
+        if direction != Direction::Incoming {
+            out.extend(self.outgoing_adjacency(vertex_id).iter().filter_map(|edge_id| endpoint(edge_id, false)));
+        }
+        if direction != Direction::Outgoing {
+            out.extend(self.incoming_adjacency(vertex_id).iter().filter_map(|edge_id| endpoint(edge_id, true)));
+        }
+        out.len()
+    }
+
+    /// 获取顶点的去重无向邻居
+    ///
+    /// 忽略边的方向，平行边和反向边只计一次，不含顶点自身（即使存在自环）。O(degree)。
+    pub fn distinct_neighbors(&self, vertex_id: VertexId<K>) -> HashSet<VertexId<K>> {
+        self.edges
+            .edges_involving(vertex_id)
+            .filter(|edge_id| self.edge_visible(*edge_id))
+            .filter_map(|edge_id| self.edges.endpoints(edge_id))
+            .map(|(from, to)| if from == vertex_id { to } else { from })
+            .filter(|neighbor| *neighbor != vertex_id)
+            .collect()
+    }
+
+    /// 获取所有顶点
+    pub fn all_vertices(&self) -> impl Iterator<Item = (VertexId<K>, &Vertex)> {
+        self.vertices.iter()
+    }
+
+    /// 获取所有边
+    pub fn all_edges(&self) -> impl Iterator<Item = (EdgeId<K>, &Edge, VertexId<K>, VertexId<K>)> {
+        self.edges.iter_with_connections().map(|(id, edge, info)| {
+            (id, edge, info.from(), info.to())
+        })
+    }
+
+    /// 获取所有顶点 ID
+    ///
+    /// 只遍历键，不访问顶点数据，每项 O(1)。
+    pub fn vertex_ids(&self) -> impl Iterator<Item = VertexId<K>> + '_ {
+        self.vertices.keys()
+    }
+
+    /// 获取所有边 ID
+    ///
+    /// 只遍历键，不访问边数据和连接信息，每项 O(1)。
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.edges.keys()
+    }
+
+    /// 获取顶点数量
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// 获取边数量
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// 获取顶点数据
+    ///
+    /// 等价于 `graph.vertex(id).map(|v| v.weight())`，但不构造引用对象。
+    pub fn vertex_weight(&self, vertex_id: VertexId<K>) -> Option<&Vertex> {
+        self.vertices.get(vertex_id)
+    }
+
+    /// 获取顶点数据的可变引用
+    ///
+    /// 顶点标签索引会在下一次修改图时按新的标签重新分组，在此之前的查询也能反映标签变化。
+    pub fn vertex_weight_mut(&mut self, vertex_id: VertexId<K>) -> Option<&mut Vertex> {
+        self.sync_vertex_indexes();
+        let weight = self.vertices.get_mut(vertex_id)?;
+        let cached = self.label_cache.as_mut().and_then(|cache| cache.remove_vertex(vertex_id));
+        self.vertex_labels.mark_dirty(vertex_id, cached.unwrap_or_else(|| weight.label()));
+        self.declared_index.mark_dirty(vertex_id, &*weight);
+        Some(weight)
+    }
+
+    /// 列出图中出现的每个顶点标签及其顶点数量
+    ///
+    /// 数量由顶点标签索引维护，复杂度只与标签种类数有关。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use graph_api_test::{Project, Vertex, VertexLabel};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+    /// graph.add_vertex(Vertex::Project(Project { name: "GraphApi".to_string() }));
+    /// graph.add_vertex(Vertex::Rust);
+    /// graph.add_vertex(Vertex::Rust);
+    ///
+    /// let labels: Vec<_> = graph.labels().collect();
+    /// assert_eq!(labels.len(), 2);
+    /// assert!(labels.contains(&(VertexLabel::Rust, 2)));
+    /// assert_eq!(graph.vertices_with_label(VertexLabel::Project).count(), 1);
+    /// ```
+    pub fn labels(&self) -> impl Iterator<Item = (Vertex::Label, usize)> + '_ {
+        self.vertex_labels
+            .counts(|vertex_id| self.vertices.get(vertex_id).map(Element::label))
+            .into_iter()
+    }
+
+    /// 遍历标签为 `label` 的顶点，按顶点加入该标签的顺序
+    ///
+    /// 直接读取顶点标签索引，无需构造 `VertexSearch`。
+    pub fn vertices_with_label(&self, label: Vertex::Label) -> impl Iterator<Item = VertexReference<'_, Self>> + '_ {
+        let vertices = &self.vertices;
+        self.vertex_labels
+            .vertices(label, move |vertex_id| vertices.get(vertex_id).map(Element::label))
+            .filter_map(move |id| vertices.get(id).map(|weight| VertexReference { id, weight }))
+    }
+
+    /// 遍历标签属于 `labels` 的顶点，`limit` 限制全部标签合计产生的顶点数
+    ///
+    /// 按 `labels` 中首次出现的顺序逐个读取顶点标签索引，重复的标签只读取一次；
+    /// 每个顶点只有一个标签，因此至多出现一次。没有顶点的标签不产生任何结果。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use graph_api_test::{Project, Vertex, VertexLabel};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+    /// graph.add_vertex(Vertex::Project(Project { name: "GraphApi".to_string() }));
+    /// graph.add_vertex(Vertex::Rust);
+    /// graph.add_vertex(Vertex::Rust);
+    ///
+    /// let labels = [VertexLabel::Project, VertexLabel::Rust, VertexLabel::Project];
+    /// assert_eq!(graph.vertices_with_labels(&labels, None).count(), 3);
+    /// assert_eq!(graph.vertices_with_labels(&labels, Some(2)).count(), 2);
+    /// ```
+    pub fn vertices_with_labels(
+        &self,
+        labels: &[Vertex::Label],
+        limit: Option<usize>,
+    ) -> impl Iterator<Item = VertexReference<'_, Self>> + '_ {
+        let mut distinct: Vec<Vertex::Label> = Vec::with_capacity(labels.len());
+        for label in labels {
+            if !distinct.contains(label) {
+                distinct.push(*label);
+            }
+        }
+        distinct
+            .into_iter()
+            .flat_map(move |label| self.vertices_with_label(label))
+            .take(limit.unwrap_or(usize::MAX))
+    }
+
+    /// 按整数索引的键顺序遍历键在 `range` 内的顶点，产生 `(键, 顶点)`
+    ///
+    /// 直接沿有序的整数索引遍历，同一键下的顶点按 [`VertexId::as_ffi`] 排列；
+    /// 需要前几项时用 `take` 截取，无需收集并排序全部结果。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Graph, VertexReference};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    /// use pi_slotmap_graph::index::KeyOrder;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// for age in [31, 67, 45, 72, 19] {
+    ///     let id = graph.add_vertex(age);
+    ///     graph.index_vertex_int(id, age as i64);
+    /// }
+    ///
+    /// let oldest: Vec<_> = graph
+    ///     .vertices_range_ordered(0..150, KeyOrder::Descending)
+    ///     .take(2)
+    ///     .map(|(_, vertex)| *vertex.weight())
+    ///     .collect();
+    /// assert_eq!(oldest, vec![72, 67]);
+    /// ```
+    pub fn vertices_range_ordered(
+        &self,
+        range: Range<i64>,
+        order: KeyOrder,
+    ) -> impl Iterator<Item = (i64, VertexReference<'_, Self>)> + '_ {
+        let ids: Box<dyn Iterator<Item = (i64, VertexId<K>)> + '_> = match order {
+            KeyOrder::Ascending => Box::new(self.vertex_query.range_int_ordered(range)),
+            KeyOrder::Descending => Box::new(self.vertex_query.range_int_ordered_desc(range)),
+        };
+        let vertices = &self.vertices;
+        ids.filter_map(move |(key, id)| vertices.get(id).map(|weight| (key, VertexReference { id, weight })))
+    }
+
+    /// 把经由可变引用修改过的顶点移到新标签的分组中，并按当前字段值写回声明的索引
+    fn sync_vertex_indexes(&mut self) {
+        let vertices = &self.vertices;
+        if !self.vertex_labels.is_synced() {
+            let mut cache = self.label_cache.as_mut();
+            self.vertex_labels.sync(|vertex_id| {
+                let label = vertices.get(vertex_id).map(Element::label);
+                if let (Some(cache), Some(label)) = (cache.as_mut(), label) {
+                    cache.set_vertex(vertex_id, label);
+                }
+                label
+            });
+        }
+        if !self.declared_index.is_synced() {
+            self.declared_index.sync(|vertex_id| vertices.get(vertex_id));
+        }
+    }
+
+    /// 按边数据计算的键对边 ID 稳定排序
+    ///
+    /// 每条边的数据只查找一次（先算出键再排序），键相等的边保持原有顺序。
+    /// 已不存在的边排在末尾，彼此之间也保持原有顺序。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let heavy = graph.add_edge(a, a, 9);
+    /// let light = graph.add_edge(a, a, 1);
+    ///
+    /// let mut ids = vec![heavy, light];
+    /// graph.sort_edges_by(&mut ids, |_, weight| *weight);
+    /// assert_eq!(ids, vec![light, heavy]);
+    /// ```
+    pub fn sort_edges_by<O: Ord>(&self, ids: &mut [EdgeId<K>], key: impl Fn(EdgeId<K>, &Edge) -> O) {
+        sort_decorated(ids, |id| self.edge_weight(id).map(|weight| key(id, weight)));
+    }
+
+    /// 按顶点数据计算的键对顶点 ID 稳定排序，规则同 [`sort_edges_by`](Self::sort_edges_by)
+    pub fn sort_vertices_by<O: Ord>(&self, ids: &mut [VertexId<K>], key: impl Fn(VertexId<K>, &Vertex) -> O) {
+        sort_decorated(ids, |id| self.vertex_weight(id).map(|weight| key(id, weight)));
+    }
+
+    /// 由 [`VertexId::as_ffi`] 得到的整数查找顶点
+    ///
+    /// 整数还原出的ID在本图中不存在（包括槽位已被重用的旧ID）时返回 `None`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Graph, SupportsElementRemoval, VertexReference};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(7);
+    /// let raw = a.as_ffi();
+    /// assert_eq!(graph.vertex_from_ffi(raw).map(|v| *v.weight()), Some(7));
+    ///
+    /// graph.remove_vertex(a);
+    /// graph.add_vertex(8);
+    /// assert!(graph.vertex_from_ffi(raw).is_none());
+    /// ```
+    pub fn vertex_from_ffi(&self, raw: u64) -> Option<VertexReference<'_, Self>> {
+        Graph::vertex(self, VertexId::from_ffi(raw))
+    }
+
+    /// 由 [`EdgeId::as_ffi`] 得到的整数查找边，边不存在时返回 `None`
+    pub fn edge_from_ffi(&self, raw: u64) -> Option<EdgeReference<'_, Self>> {
+        Graph::edge(self, EdgeId::from_ffi(raw))
+    }
+
+    /// 获取边数据
+    pub fn edge_weight(&self, edge_id: EdgeId<K>) -> Option<&Edge> {
+        self.edges.get(edge_id)
+    }
+
+    /// 获取边数据的可变引用
+    ///
+    /// 启用邻接边标签缓存时，无法追踪经由裸引用的标签修改，
+    /// 因此该边两个端点的缓存会被标记为失效；需要改变标签时请使用 `edge_mut`。
+    /// 全图边标签索引不受影响：该边会在下一次修改图时按当前标签重新加入索引。
+    pub fn edge_weight_mut(&mut self, edge_id: EdgeId<K>) -> Option<&mut Edge> {
+        self.sync_edge_labels();
+        let (weight, info) = self.edges.get_mut_with_info(edge_id)?;
+        if let Some(cache) = self.adjacent_labels.as_mut() {
+            cache.invalidate(info.from());
+            cache.invalidate(info.to());
+        }
+        let cached = self.label_cache.as_mut().and_then(|cache| cache.remove_edge(edge_id));
+        self.edge_labels.remove(&cached.unwrap_or_else(|| weight.label()), &edge_id);
+        self.unindexed_edges.insert(edge_id);
+        Some(weight)
+    }
+
+    /// 替换顶点数据，返回旧数据
+    ///
+    /// 标签改变时同步更新顶点标签索引。顶点不存在时返回 `None`，`new` 被丢弃。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(1);
+    /// let b = graph.add_vertex(2);
+    /// let edge = graph.add_edge(a, b, 10);
+    ///
+    /// assert_eq!(graph.replace_vertex_weight(a, 5), Some(1));
+    /// assert_eq!(graph.replace_edge_weight(edge, 20), Some(10));
+    /// assert!(graph.swap_vertex_weights(a, b));
+    /// assert_eq!(graph.vertex_weight(a), Some(&2));
+    /// assert_eq!(graph.vertex_weight(b), Some(&5));
+    /// ```
+    pub fn replace_vertex_weight(&mut self, vertex_id: VertexId<K>, new: Vertex) -> Option<Vertex> {
+        self.sync_vertex_indexes();
+        let new_label = new.label();
+        let slot = self.vertices.get_mut(vertex_id)?;
+        self.declared_index.remove(vertex_id, &*slot);
+        self.declared_index.insert(vertex_id, &new);
+        let old = std::mem::replace(slot, new);
+        let old_label = match self.label_cache.as_mut() {
+            Some(cache) => cache.remove_vertex(vertex_id).unwrap_or_else(|| old.label()),
+            None => old.label(),
+        };
+        if let Some(cache) = self.label_cache.as_mut() {
+            cache.set_vertex(vertex_id, new_label);
+        }
+        if old_label != new_label {
+            self.vertex_labels.remove(vertex_id, old_label);
+            self.vertex_labels.insert(vertex_id, new_label);
+        }
+        Some(old)
+    }
+
+    /// 替换边数据，返回旧数据
+    ///
+    /// 标签改变时同步更新全图边标签索引和邻接边标签缓存。边不存在时返回 `None`，`new` 被丢弃。
+    pub fn replace_edge_weight(&mut self, edge_id: EdgeId<K>, new: Edge) -> Option<Edge> {
+        self.sync_edge_labels();
+        let new_label = new.label();
+        let (weight, info) = self.edges.get_mut_with_info(edge_id)?;
+        let old = std::mem::replace(weight, new);
+        let old_label = match self.label_cache.as_mut() {
+            Some(cache) => cache.remove_edge(edge_id).unwrap_or_else(|| old.label()),
+            None => old.label(),
+        };
+        if let Some(cache) = self.label_cache.as_mut() {
+            cache.set_edge(edge_id, new_label);
+        }
+        if old_label != new_label {
+            self.edge_labels.remove(&old_label, &edge_id);
+            self.edge_labels.insert(new_label, edge_id);
+            if let Some(cache) = self.adjacent_labels.as_mut() {
+                cache.relabel(info.from(), info.to(), old_label, new_label);
+            }
+        }
+        Some(old)
+    }
+
+    /// 交换两个顶点的数据，不改变拓扑
+    ///
+    /// 两个顶点都存在时返回 `true` 并同步顶点标签索引；任一顶点不存在时图保持不变并返回 `false`。
+    pub fn swap_vertex_weights(&mut self, a: VertexId<K>, b: VertexId<K>) -> bool {
+        self.sync_vertex_indexes();
+        if !self.vertices.swap(a, b) {
+            return false;
+        }
+        if let (Some(now_a), Some(now_b)) = (self.vertices.get(a), self.vertices.get(b)) {
+            // 交换前 a 的字段值为 now_b 的，b 的为 now_a 的
+            self.declared_index.remove(a, now_b);
+            self.declared_index.remove(b, now_a);
+            self.declared_index.insert(a, now_a);
+            self.declared_index.insert(b, now_b);
+        }
+        let labels = (self.vertices.get(a).map(Element::label), self.vertices.get(b).map(Element::label));
+        if let (Some(cache), Some(now_a), Some(now_b)) = (self.label_cache.as_mut(), labels.0, labels.1) {
+            cache.set_vertex(a, now_a);
+            cache.set_vertex(b, now_b);
+        }
+        if let (Some(now_a), Some(now_b)) = labels {
+            if now_a != now_b {
+                // 交换前 a 的标签为 now_b，b 的标签为 now_a
+                self.vertex_labels.remove(a, now_b);
+                self.vertex_labels.remove(b, now_a);
+                self.vertex_labels.insert(a, now_a);
+                self.vertex_labels.insert(b, now_b);
+            }
+        }
+        true
+    }
+
+    /// 遍历全图中标签为 `label` 的边
+    ///
+    /// 读取全图边标签索引，不需要扫描全部边。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use graph_api_test::{Edge, EdgeLabel};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), Edge> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// graph.add_edge(a, b, Edge::Created);
+    /// graph.add_edge(b, a, Edge::Created);
+    /// graph.add_edge(a, b, Edge::Knows { since: 2020 });
+    ///
+    /// assert_eq!(graph.edges_with_label(EdgeLabel::Created).count(), 2);
+    /// assert!(graph.edge_label_counts().any(|count| count == (EdgeLabel::Knows, 1)));
+    /// ```
+    pub fn edges_with_label(&self, label: Edge::Label) -> impl Iterator<Item = EdgeReference<'_, Self>> + '_ {
+        let moved = self
+            .unindexed_edges
+            .iter()
+            .copied()
+            .filter(move |id| self.edge_weight(*id).is_some_and(|weight| weight.label() == label));
+        self.edge_labels.get(&label).chain(moved).filter_map(move |id| {
+            self.edges.get_with_info(id).map(|(weight, info)| EdgeReference {
+                id,
+                weight,
+                from: info.from(),
+                to: info.to(),
+            })
+        })
+    }
+
+    /// 在全图范围内按 `EdgeSearch` 查找边，不需要锚定顶点
+    ///
+    /// - `label`：给定时读取全图边标签索引，否则遍历全部边
+    /// - `adjacent_label`：按方向检查端点的标签，`Outgoing` 检查终点，`Incoming` 检查起点，
+    ///   `All` 检查任一端点
+    /// - `direction`：只决定 `adjacent_label` 检查哪一端，不影响其他条件
+    /// - `limit`：最多返回的边数
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{EdgeSearch, Graph};
+    /// use graph_api_test::{Edge, EdgeLabel};
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), Edge> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let b = graph.add_vertex(());
+    /// graph.add_edge(a, b, Edge::Created);
+    /// graph.add_edge(b, a, Edge::Created);
+    /// graph.add_edge(a, b, Edge::Knows { since: 2020 });
+    ///
+    /// let created = EdgeSearch::label(EdgeLabel::Created);
+    /// assert_eq!(graph.global_edges(&created).count(), 2);
+    /// assert_eq!(graph.global_edges(&created.with_limit(1)).count(), 1);
+    /// assert_eq!(graph.global_edges(&EdgeSearch::scan()).count(), 3);
+    /// ```
+    pub fn global_edges<'search>(&self, search: &EdgeSearch<'search, Self>) -> EdgeIter<'search, '_, Vertex, Edge, K> {
+        let candidates: Box<dyn Iterator<Item = EdgeId<K>> + '_> = match search.label {
+            Some(label) => Box::new(self.edges_with_label(label).map(|edge| edge.id)),
+            None => Box::new(self.edges.keys()),
+        };
+        let adjacent = |vertex_id: VertexId<K>| {
+            search.adjacent_label.is_none_or(|label| self.vertex_weight(vertex_id).is_some_and(|weight| self.vertex_label(vertex_id, weight) == label))
+        };
+        let keys: Vec<_> = candidates
+            .filter(|edge_id| {
+                if search.adjacent_label.is_none() {
+                    return true;
+                }
+                let Some(info) = self.edges.get_connection(*edge_id) else {
+                    return false;
+                };
+                match search.direction {
+                    Direction::Outgoing => adjacent(info.to()),
+                    Direction::Incoming => adjacent(info.from()),
+                    Direction::All => adjacent(info.from()) || adjacent(info.to()),
+                }
+            })
+            .take(search.limit())
+            .collect();
+        EdgeIter::<Vertex, Edge, K> {
+            _phantom: PhantomData,
+            edges: &self.edges,
+            keys: keys.into_iter(),
+            count: 0,
+            limit: search.limit(),
+        }
+    }
+
+    /// 列出全图中出现的每个边标签及其边数量
+    pub fn edge_label_counts(&self) -> impl Iterator<Item = (Edge::Label, usize)> + '_ {
+        let mut counts: HashMap<Edge::Label, usize> =
+            self.edge_labels.keys().map(|label| (*label, self.edge_labels.len_of(label))).collect();
+        for weight in self.unindexed_edges.iter().filter_map(|id| self.edge_weight(*id)) {
+            *counts.entry(weight.label()).or_insert(0) += 1;
+        }
+        counts.into_iter()
+    }
+
+    /// 把经由 `edge_weight_mut` 修改过的边按当前标签重新加入索引
+    fn sync_edge_labels(&mut self) {
+        let edges = &self.edges;
+        let mut cache = self.label_cache.as_mut();
+        let moved = std::mem::take(&mut self.unindexed_edges);
+        self.edge_labels.extend(moved.into_iter().filter_map(|edge_id| {
+            let label = edges.get(edge_id)?.label();
+            if let Some(cache) = cache.as_mut() {
+                cache.set_edge(edge_id, label);
+            }
+            Some((label, edge_id))
+        }));
+    }
+
+    /// 检查是否包含指定顶点
+    pub fn contains_vertex(&self, vertex_id: VertexId<K>) -> bool {
+        self.vertices.contains(vertex_id)
+    }
+
+    /// 检查是否包含指定边
+    pub fn contains_edge(&self, edge_id: EdgeId<K>) -> bool {
+        self.edges.contains(edge_id)
+    }
+
+    /// 添加顶点并立即连接到已有顶点
+    ///
+    /// `out_edges` 中的每一项 `(to, edge)` 添加一条新顶点到 `to` 的边，
+    /// `in_edges` 中的每一项 `(from, edge)` 添加一条 `from` 到新顶点的边。
+    /// 返回新顶点 ID 和按出边、入边顺序排列的新边 ID。
+    ///
+    /// # 错误
+    ///
+    /// - 任一端点不存在时返回 [`GraphError::VertexNotFound`]
+    /// - 设置了度数上限且策略为 [`DegreePolicy::Reject`] 时，若插入全部边后某个已有端点的度数
+    ///   超出上限，返回该端点的 [`GraphError::DegreeLimitExceeded`]；新顶点自身的边数超出上限时，
+    ///   返回 [`GraphError::NewVertexDegreeExceeded`]
+    ///
+    /// 返回错误时图不做任何修改。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    /// let parent = graph.add_vertex(1);
+    /// let sibling = graph.add_vertex(2);
+    ///
+    /// let (child, edges) = graph
+    ///     .add_vertex_connected(3, [(sibling, 10)], [(parent, 20)])
+    ///     .unwrap();
+    /// assert_eq!(graph.edge_endpoints(edges[0]), Some((child, sibling)));
+    /// assert_eq!(graph.edge_endpoints(edges[1]), Some((parent, child)));
+    /// ```
+    pub fn add_vertex_connected(
+        &mut self,
+        vertex: Vertex,
+        out_edges: impl IntoIterator<Item = (VertexId<K>, Edge)>,
+        in_edges: impl IntoIterator<Item = (VertexId<K>, Edge)>,
+    ) -> Result<ConnectedVertex<K>, GraphError<K>> {
+        // 先收集并检查全部端点，失败时还没有任何修改
+        let out_edges: Vec<_> = out_edges.into_iter().collect();
+        let in_edges: Vec<_> = in_edges.into_iter().collect();
+        if let Some((missing, _)) = out_edges.iter().chain(&in_edges).find(|(id, _)| !self.vertices.contains(*id)) {
+            return Err(GraphError::VertexNotFound(*missing));
+        }
+        // 按插入顺序推算各端点的度数，Reject 在修改图之前返回错误，Warn 记下要回调的端点（`None` 为新顶点）
+        let mut warnings: Vec<Option<VertexId<K>>> = Vec::new();
+        if let Some(limit) = self.max_degree {
+            let mut added: HashMap<VertexId<K>, usize> = HashMap::new();
+            let outgoing = out_edges.iter().map(|(to, _)| [None, Some(*to)]);
+            let incoming = in_edges.iter().map(|(from, _)| [Some(*from), None]);
+            for (own, endpoints) in outgoing.chain(incoming).enumerate() {
+                for endpoint in endpoints {
+                    let degree = match endpoint {
+                        None => own,
+                        Some(vertex) => self.edges.degree(vertex) + added.get(&vertex).copied().unwrap_or(0),
+                    };
+                    if degree < limit as usize {
+                        continue;
+                    }
+                    match (self.degree_policy, endpoint) {
+                        (DegreePolicy::Reject, Some(vertex)) => return Err(GraphError::DegreeLimitExceeded { vertex, limit }),
+                        (DegreePolicy::Reject, None) => {
+                            let degree = out_edges.len() + in_edges.len();
+                            return Err(GraphError::NewVertexDegreeExceeded { degree, limit });
+                        }
+                        (DegreePolicy::Warn(_), _) => warnings.push(endpoint),
+                    }
+                }
+                for vertex in endpoints.into_iter().flatten() {
+                    *added.entry(vertex).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let vertex_id = self.add_vertex(vertex);
+        if let (Some(limit), DegreePolicy::Warn(warn)) = (self.max_degree, self.degree_policy) {
+            for endpoint in warnings {
+                warn(endpoint.unwrap_or(vertex_id), limit);
+            }
+        }
+        let mut edge_ids = Vec::with_capacity(out_edges.len() + in_edges.len());
+        for (to, edge) in out_edges {
+            edge_ids.push(self.insert_edge(vertex_id, to, edge));
+        }
+        for (from, edge) in in_edges {
+            edge_ids.push(self.insert_edge(from, vertex_id, edge));
+        }
+        Ok((vertex_id, edge_ids))
+    }
+
+    /// 固定顶点，使其不能被删除
+    ///
+    /// 固定后 [`remove_vertex_policy`](Self::remove_vertex_policy) 返回 [`GraphError::VertexPinned`]，
+    /// `SupportsElementRemoval::remove_vertex` 与延迟删除返回 `None` 或忽略该顶点，
+    /// [`retain_vertices`](Self::retain_vertices) 总是保留它。
+    /// `clear` 会删除全部顶点并清空固定标记。
+    ///
+    /// 顶点不存在或已被固定时返回 `false`。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Graph, SupportsElementRemoval};
+    /// use pi_slotmap_graph::{GraphError, RemovePolicy, SlotMapGraph};
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let root = graph.add_vertex(());
+    /// assert!(graph.pin_vertex(root));
+    ///
+    /// assert_eq!(graph.remove_vertex(root), None);
+    /// assert_eq!(
+    ///     graph.remove_vertex_policy(root, RemovePolicy::Cascade),
+    ///     Err(GraphError::VertexPinned(root))
+    /// );
+    ///
+    /// assert!(graph.unpin_vertex(root));
+    /// assert_eq!(graph.remove_vertex(root), Some(()));
+    /// ```
+    pub fn pin_vertex(&mut self, vertex_id: VertexId<K>) -> bool {
+        self.vertices.contains(vertex_id) && self.pinned.insert(vertex_id)
+    }
+
+    /// 取消固定，顶点未被固定时返回 `false`
+    pub fn unpin_vertex(&mut self, vertex_id: VertexId<K>) -> bool {
+        self.pinned.remove(&vertex_id)
+    }
+
+    /// 顶点是否被固定
+    pub fn is_pinned(&self, vertex_id: VertexId<K>) -> bool {
+        self.pinned.contains(&vertex_id)
+    }
+
+    /// 删除 `keep` 返回 `false` 的顶点及其关联边，被固定的顶点总是保留
+    ///
+    /// 返回删除的顶点数量。
+    pub fn retain_vertices(&mut self, mut keep: impl FnMut(VertexId<K>, &Vertex) -> bool) -> usize {
+        let removed: Vec<_> = self
+            .vertices
+            .iter()
+            .filter(|(id, weight)| !self.pinned.contains(id) && !keep(*id, weight))
+            .map(|(id, _)| id)
+            .collect();
+        self.remove_vertices(&removed).len()
+    }
+
+    /// 一次删除多个顶点及其全部关联边，返回被删除的顶点及数据，按 `ids` 中的顺序
+    ///
+    /// 不存在、被固定或重复出现的 ID 被跳过。与逐个调用 `remove_vertex` 结果相同，
+    /// 但所有关联边一次收集、一次删除：被删除顶点的邻接表整体释放，
+    /// 每个存活邻居的邻接表只重写一次（见 [`EdgeContainer::remove_incident`]）。
+    /// 变更记录中先是全部被删除的边，再是各个顶点。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let ids: Vec<_> = (0..4).map(|i| graph.add_vertex(i)).collect();
+    /// for &from in &ids {
+    ///     for &to in &ids {
+    ///         graph.add_edge(from, to, ());
+    ///     }
+    /// }
+    ///
+    /// let removed = graph.remove_vertices(&[ids[2], ids[0], ids[2]]);
+    /// assert_eq!(removed, vec![(ids[2], 2), (ids[0], 0)]);
+    /// assert_eq!(graph.edge_count(), 4);
+    /// ```
+    pub fn remove_vertices(&mut self, ids: &[VertexId<K>]) -> Vec<(VertexId<K>, Vertex)> {
+        let mut victims = self.empty_vertex_set();
+        let ids: Vec<VertexId<K>> = ids
+            .iter()
+            .copied()
+            .filter(|&id| self.vertices.contains(id) && !self.pinned.contains(&id) && victims.insert(id))
+            .collect();
+
+        let removed = self.edges.remove_incident(&ids, |id| victims.contains(id));
+        self.forget_edges(&removed);
+        ids.into_iter()
+            .filter_map(|id| self.detach_vertex(id).map(|vertex| (id, vertex)))
+            .collect()
+    }
+
+    /// 按给定策略删除顶点，返回被删除的顶点数据
+    ///
+    /// # 错误
+    ///
+    /// - 顶点不存在时返回 [`GraphError::VertexNotFound`]
+    /// - 顶点被固定时返回 [`GraphError::VertexPinned`]
+    /// - 策略为 [`RemovePolicy::Restrict`] 且顶点仍有关联边时返回
+    ///   [`GraphError::VertexHasEdges`]，此时图不做任何修改
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::{GraphError, RemovePolicy, SlotMapGraph};
+    ///
+    /// let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(1);
+    /// let b = graph.add_vertex(2);
+    /// graph.add_edge(a, b, ());
+    ///
+    /// assert_eq!(
+    ///     graph.remove_vertex_policy(a, RemovePolicy::Restrict),
+    ///     Err(GraphError::VertexHasEdges { vertex: a, edge_count: 1 })
+    /// );
+    /// assert_eq!(graph.remove_vertex_policy(a, RemovePolicy::Cascade), Ok(1));
+    /// assert_eq!(graph.edge_count(), 0);
+    /// ```
+    pub fn remove_vertex_policy(
+        &mut self,
+        vertex_id: VertexId<K>,
+        policy: RemovePolicy,
+    ) -> Result<Vertex, GraphError<K>> {
+        if !self.vertices.contains(vertex_id) {
+            return Err(GraphError::VertexNotFound(vertex_id));
+        }
+        if self.pinned.contains(&vertex_id) {
+            return Err(GraphError::VertexPinned(vertex_id));
+        }
+
+        if policy == RemovePolicy::Restrict {
+            let edge_count = self.edges.edges_adjacent(vertex_id).count();
+            if edge_count > 0 {
+                return Err(GraphError::VertexHasEdges {
+                    vertex: vertex_id,
+                    edge_count,
+                });
+            }
+        }
+
+        let incident: Vec<_> = self.edges.edges_adjacent(vertex_id).collect();
+        for edge_id in incident {
+            self.remove_edge_internal(edge_id);
+        }
+        debug_assert!(self.edges.adjacency(vertex_id).is_none(), "removed vertex still has edges");
+        self.detach_vertex(vertex_id).ok_or(GraphError::VertexNotFound(vertex_id))
+    }
+
+    /// 删除已没有关联边的顶点，更新索引、计数和变更记录
+    fn detach_vertex(&mut self, vertex_id: VertexId<K>) -> Option<Vertex> {
+        if let Some(cache) = self.adjacent_labels.as_mut() {
+            cache.remove_vertex(vertex_id);
+        }
+        if let Some(marks) = self.marks.as_mut() {
+            marks.remove_vertex(vertex_id);
+        }
+        if let Some(order) = self.creation_order.as_mut() {
+            order.remove_vertex(vertex_id);
+        }
+        if let Some(spatial) = self.spatial.as_mut() {
+            spatial.remove(vertex_id);
+        }
+
+        let vertex = self.vertices.remove(vertex_id)?;
+        let label = self.label_cache.as_mut().and_then(|cache| cache.remove_vertex(vertex_id));
+        self.vertex_labels.remove(vertex_id, label.unwrap_or_else(|| vertex.label()));
+        self.declared_index.remove(vertex_id, &vertex);
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.vertex_removed();
+        }
+        #[cfg(feature = "removal-diagnostics")]
+        self.quarantine.record(RemovedId::Vertex(vertex_id));
+        self.version += 1;
+        self.record_change(Change::VertexRemoved(vertex_id));
+        Some(vertex)
+    }
+
+    /// 检查图的内部一致性
+    ///
+    /// 确认每条边的两个端点都存在，邻接表与边数据一致，度数计数器与邻接表一致，
+    /// 边标签索引恰好包含每条边一次，已开启的可选边索引不再记录被删除的边。
+    /// 按此顺序检查，返回发现的第一个问题。
+    /// 复杂度为 O(V + E)，用于测试和调试；发现问题后可用 [`repair`](Self::repair) 修复。
+    pub fn validate(&self) -> Result<(), GraphError<K>> {
+        for (edge, _, info) in self.edges.iter_with_connections() {
+            for vertex in [info.from(), info.to()] {
+                if !self.vertices.contains(vertex) {
+                    return Err(GraphError::DanglingEdge { edge, vertex });
+                }
+            }
+        }
+        self.edges.validate()?;
+
+        for (&label, mut edges) in self.edge_labels.iter() {
+            let stale = edges.find(|&edge| {
+                self.unindexed_edges.contains(&edge)
+                    || self.edges.get(edge).is_none_or(|weight| weight.label() != label)
+            });
+            if let Some(edge) = stale {
+                return Err(GraphError::LabelIndexMismatch { edge });
+            }
+        }
+        // 索引中的边都有效，再确认没有遗漏
+        let missing = self.edges.iter_with_connections().find(|(edge, weight, _)| {
+            !self.unindexed_edges.contains(edge) && !self.edge_labels.contains(&weight.label(), edge)
+        });
+        if let Some((edge, ..)) = missing {
+            return Err(GraphError::LabelIndexMismatch { edge });
+        }
+        match self.stale_edge_entries().next() {
+            Some(edge) => Err(GraphError::StaleEdgeEntry { edge }),
+            None => Ok(()),
+        }
+    }
+
+    /// 可选边索引中记录着、但已不在边存储中的边，可能重复
+    fn stale_edge_entries(&self) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        let marks = self.marks.iter().flat_map(|marks| marks.edges(u8::MAX));
+        let order = self.creation_order.iter().flat_map(|order| order.edges());
+        let labels = self.label_cache.iter().flat_map(|cache| cache.edge_ids());
+        let pairs = self.edge_pairs.iter().flat_map(|pairs| pairs.edges());
+        marks
+            .chain(order)
+            .chain(labels)
+            .chain(pairs)
+            .chain(self.layers.edges())
+            .chain(self.tags.tagged())
+            .filter(move |edge_id| !self.edges.contains(*edge_id))
+    }
+
+    /// 以顶点存储和边数据为准，修复 [`validate`](Self::validate) 能发现的全部不一致，返回被删除的边
+    ///
+    /// - 端点不存在的边被删除，如同调用 `remove_edge`
+    /// - 边的连接信息和邻接表按边数据修复，邻接表保留原有顺序，度数计数器重新计算
+    /// - 顶点标签索引、声明索引、边标签索引，以及已开启的邻接边标签缓存和端点对索引按当前数据重建
+    /// - 固定标记、坐标索引和创建顺序中不存在的顶点被丢弃
+    /// - 标记、创建顺序、图层和批次标签中不存在的边被丢弃
+    ///
+    /// 图一致时不改变任何查询结果。复杂度为 O(V + E)。
+    pub fn repair(&mut self) -> Vec<EdgeId<K>> {
+        let vertices = &self.vertices;
+        let dangling = self.edges.repair(|vertex| vertices.contains(vertex));
+        self.forget_edges(&dangling);
+
+        self.vertex_labels.clear();
+        self.declared_index.clear();
+        for (vertex_id, weight) in self.vertices.iter() {
+            self.vertex_labels.insert(vertex_id, weight.label());
+            self.declared_index.insert(vertex_id, weight);
+        }
+        let edges = &self.edges;
+        self.unindexed_edges.retain(|edge_id| edges.contains(*edge_id));
+        let unindexed = &self.unindexed_edges;
+        self.edge_labels.clear();
+        self.edge_labels.extend(
+            edges
+                .iter_with_connections()
+                .filter(|(edge_id, ..)| !unindexed.contains(edge_id))
+                .map(|(edge_id, weight, _)| (weight.label(), edge_id)),
+        );
+        if self.adjacent_labels.is_some() {
+            self.enable_adjacent_label_cache();
+        }
+        if self.edge_pairs.is_some() {
+            self.enable_pair_index();
+        }
+        if self.label_cache.is_some() {
+            self.enable_label_cache();
+        }
+
+        let stale: Vec<_> = self.stale_edge_entries().collect();
+        for edge_id in stale {
+            if let Some(marks) = self.marks.as_mut() {
+                marks.remove_edge(edge_id);
+            }
+            if let Some(order) = self.creation_order.as_mut() {
+                order.remove_edge(edge_id);
+            }
+            self.layers.remove(edge_id);
+            self.tags.remove(edge_id);
+        }
+
+        let vertices = &self.vertices;
+        self.pinned.retain(|vertex_id| vertices.contains(*vertex_id));
+        if let Some(order) = self.creation_order.as_mut() {
+            order.retain_vertices(|vertex_id| vertices.contains(vertex_id));
+        }
+        if let Some(spatial) = self.spatial.as_mut() {
+            let missing: Vec<_> =
+                spatial.positions().map(|(vertex_id, _)| vertex_id).filter(|id| !vertices.contains(*id)).collect();
+            for vertex_id in missing {
+                spatial.remove(vertex_id);
+            }
+        }
+        dangling.into_iter().map(|(edge_id, ..)| edge_id).collect()
+    }
+
+    /// 检查是否包含指定元素，按 `ElementId` 的变体分派到
+    /// [`contains_vertex`](Self::contains_vertex) 或 [`contains_edge`](Self::contains_edge)
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+    /// let a = graph.add_vertex(());
+    /// let edge = graph.add_edge(a, a, ());
+    ///
+    /// assert!(graph.contains_element(a));
+    /// assert!(graph.contains_element(edge));
+    /// ```
+    pub fn contains_element(&self, id: impl Into<ElementId<Self>>) -> bool {
+        match id.into() {
+            ElementId::Vertex(vertex_id) => self.contains_vertex(vertex_id),
+            ElementId::Edge(edge_id) => self.contains_edge(edge_id),
+        }
+    }
+
+    /// 检查图是否为空
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() && self.edges.is_empty()
+    }
+
+    /// 结构版本号
+    ///
+    /// 每次增删顶点或边（包括清空）时递增，修改权重或调整边顺序不会改变它。
+    /// 依赖图结构的缓存可以记录该值，在版本变化后失效。
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+/// 先为每个 ID 算出键再稳定排序，键为 `None` 的 ID 排在末尾
+fn sort_decorated<T: Copy, O: Ord>(ids: &mut [T], key: impl Fn(T) -> Option<O>) {
+    let mut decorated: Vec<(Option<O>, T)> = ids.iter().map(|&id| (key(id), id)).collect();
+    decorated.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+    for (slot, (_, id)) in ids.iter_mut().zip(decorated) {
+        *slot = id;
+    }
+}
+
+impl<Vertex, Edge, K> Graph for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    type Vertex = Vertex;
+    type Edge = Edge;
+    type VertexId = VertexId<K>;
+    type EdgeId = EdgeId<K>;
+    type VertexReference<'graph> = VertexReference<'graph, Self> where Self: 'graph;
+    type VertexReferenceMut<'graph> = VertexReferenceMut<'graph, Self> where Self: 'graph;
+    type EdgeReference<'graph> = EdgeReference<'graph, Self> where Self: 'graph;
+    type EdgeReferenceMut<'graph> = EdgeReferenceMut<'graph, Self> where Self: 'graph;
+    type EdgeIter<'search, 'graph> = EdgeIter<'search, 'graph, Vertex, Edge, K> where Self: 'graph;
+    type VertexIter<'search, 'graph> = VertexIter<'search, 'graph, Vertex, Edge, K> where Self: 'graph;
+
+    fn add_vertex(&mut self, vertex: Self::Vertex) -> Self::VertexId {
+        self.sync_vertex_indexes();
+        let label = vertex.label();
+        let vertex_id = self.vertices.insert(vertex);
+        self.vertex_labels.insert(vertex_id, label);
+        if let Some(cache) = self.label_cache.as_mut() {
+            cache.set_vertex(vertex_id, label);
+        }
+        if let Some(order) = self.creation_order.as_mut() {
+            order.push_vertex(vertex_id);
+        }
+        if let Some(weight) = self.vertices.get(vertex_id) {
+            self.declared_index.insert(vertex_id, weight);
+        }
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.vertex_added(self.vertices.len());
+        }
+        self.version += 1;
+        self.record_change(Change::VertexAdded(vertex_id));
+
+        // 自动构建基础索引（如果可能的话）
+        // 注意：由于我们不知道顶点的内部结构，这里无法自动建立索引
+        // 用户需要手动调用索引方法来构建自定义索引
+        // 在实际使用中，可以通过实现特定的顶点类型来自动索引
+
+        vertex_id
+    }
+
+    fn add_edge(
+        &mut self,
+        from: Self::VertexId,
+        to: Self::VertexId,
+        edge: Self::Edge,
+    ) -> Self::EdgeId {
+        // 无法返回错误，Reject 时也照常插入；需要强制上限时使用 try_add_edge
+        if let Err(_error) = self.check_degree(from, to) {
+            #[cfg(debug_assertions)]
+            eprintln!("{_error}; inserted anyway, use try_add_edge to enforce the degree limit");
+        }
+        self.insert_edge(from, to, edge)
+    }
+
+    fn vertex(&self, id: Self::VertexId) -> Option<Self::VertexReference<'_>> {
+        let vertex = self.vertices.get(id).map(|weight| VertexReference { id, weight });
+        #[cfg(feature = "removal-diagnostics")]
+        if vertex.is_none() {
+            self.quarantine.check(RemovedId::Vertex(id));
+        }
+        vertex
+    }
+
+    fn vertex_mut(&mut self, id: Self::VertexId) -> Option<Self::VertexReferenceMut<'_>> {
+        self.vertex_weight_mut(id).map(|weight| VertexReferenceMut { id, weight })
+    }
+
+    fn vertices<'search>(
+        &self,
+        search: &VertexSearch<'search, Self>,
+    ) -> Self::VertexIter<'search, '_> {
+        // 与 explain_vertices 共用策略选择
+        let strategy = self.vertex_strategy(search);
+        let (keys, prefilter) = self.vertex_source(&strategy);
+        VertexIter::<Vertex, Edge, K> {
+            _phantom: PhantomData,
+            vertices: &self.vertices,
+            keys,
+            prefilter,
+            count: 0,
+            limit: search.limit(),
+        }
+    }
+
+    fn edge(&self, id: Self::EdgeId) -> Option<Self::EdgeReference<'_>> {
+        if let Some((weight, conn)) = self.edges.get_with_info(id) {
+            return Some(EdgeReference {
+                id,
+                weight,
+                from: conn.from(),
+                to: conn.to(),
+            });
+        }
+        #[cfg(feature = "removal-diagnostics")]
+        self.quarantine.check(RemovedId::Edge(id));
+        None
+    }
+
+    fn edge_mut(&mut self, edge: Self::EdgeId) -> Option<Self::EdgeReferenceMut<'_>> {
+        self.sync_edge_labels();
+        if let Some((weight, conn)) = self.edges.get_mut_with_info(edge) {
+            let (from, to) = (conn.from(), conn.to());
+            let cached_label = self.label_cache.as_mut().and_then(|cache| cache.edge_mut(edge));
+            let labels = EdgeLabelGuard {
+                old_label: cached_label.as_deref().copied().unwrap_or_else(|| weight.label()),
+                edge_labels: &mut self.edge_labels,
+                adjacent_labels: self.adjacent_labels.as_mut(),
+                cached_label,
+            };
+            return Some(EdgeReferenceMut {
+                id: edge,
+                weight,
+                from,
+                to,
+                labels,
+            });
+        }
+        None
+    }
+
+    /// 按 `search` 查找 `vertex` 的关联边
+    ///
+    /// 每条边至多产生一次：`Outgoing` 按插入顺序产生出边，`Incoming` 产生入边，
+    /// `All` 先产生出边再产生入边，自环只在出边中产生一次。平行边是不同的边，各产生一次。
+    fn edges<'search>(
+        &self,
+        vertex: Self::VertexId,
+        search: &EdgeSearch<'search, Self>,
+    ) -> Self::EdgeIter<'search, '_> {
+        use graph_api_lib::Direction;
+
+        // 缓存确认该顶点没有匹配标签的关联边时，无需读取任何边权重
+        if let (Some(label), Some(cache)) = (search.label, self.adjacent_labels.as_ref()) {
+            if !cache.may_contain(vertex, label) {
+                return EdgeIter::<Vertex, Edge, K> {
+                    _phantom: PhantomData,
+                    edges: &self.edges,
+                    keys: Vec::new().into_iter(),
+                    count: 0,
+                    limit: search.limit(),
+                };
+            }
+        }
+
+        // 首先获取所有符合条件的边（基于方向）
+        let candidate_edges: Vec<EdgeId<K>> = match search.direction {
+            Direction::Outgoing => self.edges.edges_from(vertex).filter(|id| self.edge_visible(*id)).collect(),
+            Direction::Incoming => self.edges.edges_to(vertex).filter(|id| self.edge_visible(*id)).collect(),
+            Direction::All => self.edges.edges_adjacent(vertex).filter(|id| self.edge_visible(*id)).collect(),
+        };
+
+        // 然后过滤掉不符合标签条件的边
+        let filtered_keys: Vec<EdgeId<K>> = if let Some(target_label) = search.label {
+            candidate_edges
+                .into_iter()
+                .filter(|&edge_id| {
+                    if let Some(edge_weight) = self.edges.get(edge_id) {
+                        self.edge_label(edge_id, edge_weight) == target_label
+                    } else {
+                        false
+                    }
+                })
+                .collect()
+        } else {
+            candidate_edges
+        };
+
+        // 再按另一端顶点的标签过滤
+        let filtered_keys: Vec<EdgeId<K>> = if let Some(adjacent_label) = search.adjacent_label {
+            filtered_keys
+                .into_iter()
+                .filter(|&edge_id| {
+                    self.edges.get_connection(edge_id).is_some_and(|conn| {
+                        let other = if conn.from() == vertex { conn.to() } else { conn.from() };
+                        self.vertices.get(other).is_some_and(|weight| self.vertex_label(other, weight) == adjacent_label)
+                    })
+                })
+                .collect()
+        } else {
+            filtered_keys
+        };
+
+        EdgeIter::<Vertex, Edge, K> {
+            _phantom: PhantomData,
+            edges: &self.edges,
+            keys: filtered_keys.into_iter(),
+            count: 0,
+            limit: search.limit(),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.clear_all();
+    }
+}
+
+// 实现所有支持trait
+impl<Vertex, Edge, K> SupportsVertexLabelIndex for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+impl<Vertex, Edge, K> SupportsEdgeLabelIndex for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+impl<Vertex, Edge, K> SupportsVertexHashIndex for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+impl<Vertex, Edge, K> SupportsVertexRangeIndex for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+impl<Vertex, Edge, K> SupportsVertexFullTextIndex for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+impl<Vertex, Edge, K> SupportsEdgeAdjacentLabelIndex for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+}
+
+impl<Vertex, Edge, K> SupportsClear for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    fn clear(&mut self) {
+        self.clear_all();
+    }
+}
+
+impl<Vertex, Edge, K> SupportsElementRemoval for SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    fn remove_vertex(&mut self, id: Self::VertexId) -> Option<Self::Vertex> {
+        // 删除顶点时，也需要删除相关的所有边；顶点不存在或被固定时返回 None
+        self.remove_vertex_policy(id, RemovePolicy::default()).ok()
+    }
+
+    fn remove_edge(&mut self, edge: Self::EdgeId) -> Option<Self::Edge> {
+        self.remove_edge_internal(edge).map(|(edge, _)| edge)
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 清空顶点、边和全部索引，两个 `clear` 的实现共用
+    fn clear_all(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+        self.vertex_query.clear();
+        self.vertex_labels.clear();
+        self.declared_index.clear();
+        self.edge_labels.clear();
+        self.unindexed_edges.clear();
+        if let Some(cache) = self.adjacent_labels.as_mut() {
+            cache.clear();
+        }
+        if let Some(pairs) = self.edge_pairs.as_mut() {
+            pairs.clear();
+        }
+        if let Some(marks) = self.marks.as_mut() {
+            marks.clear();
+        }
+        if let Some(spatial) = self.spatial.as_mut() {
+            spatial.clear();
+        }
+        if let Some(cache) = self.label_cache.as_mut() {
+            cache.clear();
+        }
+        if let Some(order) = self.creation_order.as_mut() {
+            order.clear();
+        }
+        self.layers.clear();
+        self.tags.clear();
+        self.pinned.clear();
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.cleared();
+        }
+        self.version += 1;
+        self.record_change(Change::Cleared);
+    }
+
+    /// 删除单条边的唯一入口，`remove_edge`、级联删除顶点和延迟删除都经过这里
+    ///
+    /// 先从边存储和邻接表中删除，再按 [`forget_unlabelled_edge`](Self::forget_unlabelled_edge)
+    /// 的固定顺序更新其余结构。边不存在时返回 `None`，不做任何修改。
+    pub(crate) fn remove_edge_internal(&mut self, edge_id: EdgeId<K>) -> Option<(Edge, EdgeInfo<K>)> {
+        let (edge, info) = self.edges.remove(edge_id)?;
+        self.forget_edge(edge_id, &edge, &info);
+        Some((edge, info))
+    }
+
+    /// 边已从存储中删除后，更新索引、计数和变更记录
+    fn forget_edge(&mut self, edge_id: EdgeId<K>, edge: &Edge, info: &EdgeInfo<K>) {
+        if !self.unindexed_edges.remove(&edge_id) {
+            self.edge_labels.remove(&self.edge_label(edge_id, edge), &edge_id);
+        }
+        self.forget_unlabelled_edge(edge_id, edge, info);
+    }
+
+    /// 一批边已从存储中删除后，同 [`forget_edge`](Self::forget_edge)
+    ///
+    /// 删除的边超过标签索引的一半时，标签索引整体筛选一次，而不是逐条删除。
+    fn forget_edges(&mut self, removed: &[(EdgeId<K>, Edge, EdgeInfo<K>)]) {
+        let labelled: HashSet<EdgeId<K>> =
+            removed.iter().map(|(edge_id, ..)| *edge_id).filter(|edge_id| !self.unindexed_edges.remove(edge_id)).collect();
+        if labelled.len() * 2 > self.edge_labels.total_values_len() {
+            self.edge_labels.retain(|_, edge_id| !labelled.contains(edge_id));
+        } else {
+            for (edge_id, edge, _) in removed.iter().filter(|(edge_id, ..)| labelled.contains(edge_id)) {
+                self.edge_labels.remove(&self.edge_label(*edge_id, edge), edge_id);
+            }
+        }
+        for (edge_id, edge, info) in removed {
+            self.forget_unlabelled_edge(*edge_id, edge, info);
+        }
+    }
+
+    /// 更新标签索引以外的索引、计数和变更记录
+    ///
+    /// 顺序固定：标签缓存、邻接边标签缓存、端点对索引、标记、创建顺序、图层、批次标签、
+    /// 统计、删除诊断，最后递增版本号并写入变更记录。单条删除和批量删除顺序相同，
+    /// 读取变更记录时边已从全部结构中移除。
+    fn forget_unlabelled_edge(&mut self, edge_id: EdgeId<K>, edge: &Edge, info: &EdgeInfo<K>) {
+        let label = self.label_cache.as_mut().and_then(|cache| cache.remove_edge(edge_id));
+        if let Some(cache) = self.adjacent_labels.as_mut() {
+            cache.remove(info.from(), info.to(), label.unwrap_or_else(|| edge.label()));
+        }
+        if let Some(pairs) = self.edge_pairs.as_mut() {
+            pairs.remove(info.from(), info.to(), edge_id);
+        }
+        if let Some(marks) = self.marks.as_mut() {
+            marks.remove_edge(edge_id);
+        }
+        if let Some(order) = self.creation_order.as_mut() {
+            order.remove_edge(edge_id);
+        }
+        self.layers.remove(edge_id);
+        self.tags.remove(edge_id);
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.edge_removed();
+        }
+        #[cfg(feature = "removal-diagnostics")]
+        self.quarantine.record(RemovedId::Edge(edge_id));
+        self.version += 1;
+        self.record_change(Change::EdgeRemoved(edge_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use graph_api_lib::{Element, Graph, VertexSearch, VertexReference, EdgeReference};
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct TestVertex {
+        name: String,
+        _value: i32,
+    }
+
+    #[derive(Debug, Clone)]
+    struct TestEdge {
+        weight: f64,
+    }
+
+    impl Element for TestVertex {
+        type Label = ();
+
+        fn label(&self) -> Self::Label {
+            ()
+        }
     }
 
     impl Element for TestEdge {
         type Label = ();
 
-        fn label(&self) -> Self::Label {
-            ()
+        fn label(&self) -> Self::Label {
+            ()
+        }
+    }
+
+    #[test]
+    fn test_graph_basic_operations() {
+        let mut graph = SlotMapGraph::<TestVertex, TestEdge>::new();
+
+        // 添加顶点
+        let v1 = graph.add_vertex(TestVertex {
+            name: "A".to_string(),
+            _value: 1,
+        });
+        let v2 = graph.add_vertex(TestVertex {
+            name: "B".to_string(),
+            _value: 2,
+        });
+        let v3 = graph.add_vertex(TestVertex {
+            name: "C".to_string(),
+            _value: 3,
+        });
+
+        // 添加边
+        let e1 = graph.add_edge(v1, v2, TestEdge { weight: 1.5 });
+        let _e2 = graph.add_edge(v2, v3, TestEdge { weight: 2.5 });
+        let _e3 = graph.add_edge(v1, v3, TestEdge { weight: 3.5 });
+
+        // 验证顶点
+        assert_eq!(graph.vertex(v1).unwrap().weight().name, "A");
+        assert_eq!(graph.vertex(v2).unwrap().weight().name, "B");
+        assert_eq!(graph.vertex(v3).unwrap().weight().name, "C");
+
+        // 验证边
+        assert_eq!(graph.edge(e1).unwrap().weight().weight, 1.5);
+        assert_eq!(graph.edge(e1).unwrap().tail(), v1);
+        assert_eq!(graph.edge(e1).unwrap().head(), v2);
+
+        // 测试度数计算
+        assert_eq!(graph.out_degree(v1), 2);
+        assert_eq!(graph.in_degree(v1), 0);
+        assert_eq!(graph.out_degree(v2), 1);
+        assert_eq!(graph.in_degree(v2), 1);
+        assert_eq!(graph.out_degree(v3), 0);
+        assert_eq!(graph.in_degree(v3), 2);
+
+        // 测试边查询
+        assert!(graph.has_edge(v1, v2));
+        assert!(!graph.has_edge(v2, v1));
+        assert!(graph.has_edge(v1, v3));
+
+        // 测试遍历
+        let vertices: Vec<_> = graph.vertices(&VertexSearch::scan()).collect();
+        assert_eq!(vertices.len(), 3);
+    }
+
+    #[test]
+    fn test_edge_removal() {
+        let mut graph = SlotMapGraph::<TestVertex, TestEdge>::new();
+
+        let v1 = graph.add_vertex(TestVertex {
+            name: "A".to_string(),
+            _value: 1,
+        });
+        let v2 = graph.add_vertex(TestVertex {
+            name: "B".to_string(),
+            _value: 2,
+        });
+
+        let e1 = graph.add_edge(v1, v2, TestEdge { weight: 1.5 });
+        assert!(graph.has_edge(v1, v2));
+
+        // 删除边
+        let removed_edge = graph.remove_edge(e1);
+        assert!(removed_edge.is_some());
+        assert!(!graph.has_edge(v1, v2));
+    }
+
+    #[test]
+    fn test_vertex_removal() {
+        let mut graph = SlotMapGraph::<TestVertex, TestEdge>::new();
+
+        let v1 = graph.add_vertex(TestVertex {
+            name: "A".to_string(),
+            _value: 1,
+        });
+        let v2 = graph.add_vertex(TestVertex {
+            name: "B".to_string(),
+            _value: 2,
+        });
+        let v3 = graph.add_vertex(TestVertex {
+            name: "C".to_string(),
+            _value: 3,
+        });
+
+        let e1 = graph.add_edge(v1, v2, TestEdge { weight: 1.5 });
+        let e2 = graph.add_edge(v1, v3, TestEdge { weight: 2.5 });
+
+        // 删除顶点v1应该同时删除相关的边
+        let removed_vertex = graph.remove_vertex(v1);
+        assert!(removed_vertex.is_some());
+        assert_eq!(removed_vertex.unwrap().name, "A");
+
+        // 验证边被删除
+        assert!(!graph.contains_vertex(v1));
+        assert!(!graph.contains_edge(e1));
+        assert!(!graph.contains_edge(e2));
+
+        // 验证其他顶点还在
+        assert!(graph.contains_vertex(v2));
+        assert!(graph.contains_vertex(v3));
+    }
+
+    #[test]
+    fn test_vertex_and_edge_ids() {
+        use std::collections::HashSet;
+
+        let mut graph = SlotMapGraph::<TestVertex, TestEdge>::new();
+        let ids: Vec<_> = (0..4)
+            .map(|i| graph.add_vertex(TestVertex { name: i.to_string(), _value: i }))
+            .collect();
+        let e1 = graph.add_edge(ids[0], ids[1], TestEdge { weight: 1.0 });
+        graph.add_edge(ids[1], ids[2], TestEdge { weight: 2.0 });
+        graph.add_edge(ids[2], ids[3], TestEdge { weight: 3.0 });
+
+        let vertex_ids: HashSet<_> = graph.vertex_ids().collect();
+        assert_eq!(vertex_ids, graph.all_vertices().map(|(id, _)| id).collect());
+        let edge_ids: HashSet<_> = graph.edge_ids().collect();
+        assert_eq!(edge_ids, graph.all_edges().map(|(id, ..)| id).collect());
+
+        // 删除后 ID 集合同步更新
+        graph.remove_edge(e1);
+        graph.remove_vertex(ids[3]);
+        let vertex_ids: HashSet<_> = graph.vertex_ids().collect();
+        assert_eq!(vertex_ids, ids[..3].iter().copied().collect());
+        let edge_ids: Vec<_> = graph.edge_ids().collect();
+        assert_eq!(edge_ids.len(), 1);
+        assert_eq!(graph.edge_to(edge_ids[0]), Some(ids[2]));
+
+        // 一次查找得到的端点与分别查询一致，失效的 ID 返回 None
+        for id in &edge_ids {
+            assert_eq!(graph.edge_endpoints(*id), graph.edge_from(*id).zip(graph.edge_to(*id)));
+        }
+        assert_eq!(graph.edge_endpoints(e1), None);
+        assert_eq!(graph.edge_from(e1), None);
+    }
+
+    #[test]
+    fn test_edge_references_carry_endpoints() {
+        use graph_api_lib::EdgeReferenceMut;
+
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let ab = graph.add_edge(a, b, 10);
+        let bb = graph.add_edge(b, b, 20);
+
+        for (edge, from, to, weight) in [(ab, a, b, 10), (bb, b, b, 20)] {
+            let reference = graph.edge(edge).unwrap();
+            assert_eq!((reference.id(), reference.tail(), reference.head(), *reference.weight()), (edge, from, to, weight));
+            let mut reference = graph.edge_mut(edge).unwrap();
+            assert_eq!((reference.id(), reference.tail(), reference.head()), (edge, from, to));
+            *reference.weight_mut() += 1;
+        }
+        assert_eq!(graph.edge_weight(ab), Some(&11));
+        assert_eq!(graph.edge_weight(bb), Some(&21));
+
+        graph.remove_edge(ab);
+        assert!(graph.edge(ab).is_none());
+        assert!(graph.edge_mut(ab).is_none());
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    /// 不可 Clone 的顶点数据
+    #[derive(Debug, PartialEq)]
+    struct Payload(String);
+
+    impl Element for Payload {
+        type Label = ();
+
+        fn label(&self) -> Self::Label {}
+    }
+
+    #[test]
+    fn test_into_iterator() {
+        let mut graph = SlotMapGraph::<Payload, ()>::new();
+        graph.extend(["a", "b", "c"].map(|name| Payload(name.to_string())));
+        let ids: Vec<_> = (&graph).into_iter().map(|v| v.id()).collect();
+        graph.add_edge(ids[0], ids[1], ());
+
+        // 借用形式访问所有顶点
+        let mut names: Vec<_> = (&graph).into_iter().map(|v| v.weight().0.clone()).collect();
+        names.sort();
+        assert_eq!(names, ["a", "b", "c"]);
+        let mut visited = 0;
+        for vertex in &graph {
+            assert!(ids.contains(&vertex.id()));
+            visited += 1;
+        }
+        assert_eq!(visited, 3);
+
+        // 消费形式按值移出顶点数据，无需 Clone
+        let mut owned: Vec<(VertexId, Payload)> = graph.into_iter().collect();
+        owned.sort_by(|a, b| a.1 .0.cmp(&b.1 .0));
+        assert_eq!(owned.len(), 3);
+        assert_eq!(owned[0], (ids[0], Payload("a".to_string())));
+    }
+
+    /// 包装不可 Clone 的动态类型数据
+    struct AnyNode(Box<dyn std::any::Any>);
+
+    impl Debug for AnyNode {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            f.write_str("AnyNode")
+        }
+    }
+
+    impl Element for AnyNode {
+        type Label = ();
+
+        fn label(&self) -> Self::Label {}
+    }
+
+    #[test]
+    fn test_non_clone_payloads() {
+        let mut graph = SlotMapGraph::<AnyNode, ()>::default();
+        let a = graph.add_vertex(AnyNode(Box::new(1u32)));
+        let b = graph.add_vertex(AnyNode(Box::new("two")));
+        let e = graph.add_edge(a, b, ());
+
+        assert_eq!(graph.vertex_count(), 2);
+        assert_eq!(graph.out_degree(a), 1);
+        let heads: Vec<_> = graph.outgoing_edges(a).map(|edge| edge.head()).collect();
+        assert_eq!(heads, vec![b]);
+        let found = graph.vertex(b).and_then(|v| v.weight().0.downcast_ref::<&str>().copied());
+        assert_eq!(found, Some("two"));
+        assert_eq!(graph.vertices(&VertexSearch::scan()).count(), 2);
+
+        graph.remove_edge(e);
+        assert!(graph.remove_vertex(a).is_some());
+        SupportsClear::clear(&mut graph);
+        assert!(graph.is_empty());
+    }
+
+    #[test]
+    fn test_degree_counters() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let degrees = |graph: &SlotMapGraph<(), u32>, v| (graph.out_degree(v), graph.in_degree(v), graph.degree(v));
+
+        let ab1 = graph.add_edge(a, b, 1);
+        let _ab2 = graph.add_edge(a, b, 2);
+        let aa = graph.add_edge(a, a, 3);
+        let ba = graph.add_edge(b, a, 4);
+        assert_eq!(degrees(&graph, a), (3, 2, 4));
+        assert_eq!(degrees(&graph, b), (1, 2, 3));
+        assert_eq!(graph.validate(), Ok(()));
+        // 平行边、反向边和自环不影响去重邻居
+        assert_eq!(graph.distinct_neighbors(a), HashSet::from([b]));
+        assert_eq!(graph.distinct_neighbors(b), HashSet::from([a]));
+
+        graph.remove_edge(aa);
+        graph.remove_edge(ab1);
+        assert_eq!(degrees(&graph, a), (1, 1, 2));
+        assert_eq!(degrees(&graph, b), (1, 1, 2));
+
+        let c = graph.add_vertex(());
+        graph.add_edge(c, b, 5);
+        graph.remove_vertex(a);
+        assert_eq!(degrees(&graph, a), (0, 0, 0));
+        assert_eq!(degrees(&graph, b), (0, 1, 1));
+        assert_eq!(degrees(&graph, c), (1, 0, 1));
+        assert!(!graph.contains_edge(ba));
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_detects_dangling_edge() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let edge = graph.add_edge(a, b, ());
+
+        // 绕过级联删除，直接移除顶点
+        graph.vertices.remove(b);
+        assert_eq!(graph.validate(), Err(GraphError::DanglingEdge { edge, vertex: b }));
+    }
+
+    #[test]
+    fn test_remove_vertex_policy() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let c = graph.add_vertex(3);
+        let ab = graph.add_edge(a, b, 1);
+        let ca = graph.add_edge(c, a, 2);
+        let aa = graph.add_edge(a, a, 3);
+        let bc = graph.add_edge(b, c, 4);
+
+        // Restrict 拒绝删除仍有边的顶点，图保持不变
+        assert_eq!(
+            graph.remove_vertex_policy(a, RemovePolicy::Restrict),
+            Err(GraphError::VertexHasEdges { vertex: a, edge_count: 3 })
+        );
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (3, 4));
+        assert!([ab, ca, aa, bc].iter().all(|&e| graph.contains_edge(e)));
+
+        // Cascade 只删除该顶点及其关联边
+        assert_eq!(graph.remove_vertex_policy(a, RemovePolicy::Cascade), Ok(1));
+        assert!(!graph.contains_vertex(a));
+        assert!([ab, ca, aa].iter().all(|&e| !graph.contains_edge(e)));
+        assert!(graph.contains_edge(bc));
+        assert_eq!(graph.edge_count(), 1);
+
+        assert_eq!(
+            graph.remove_vertex_policy(a, RemovePolicy::Cascade),
+            Err(GraphError::VertexNotFound(a))
+        );
+
+        // 没有关联边时 Restrict 可以删除
+        graph.remove_edge(bc);
+        assert_eq!(graph.remove_vertex_policy(c, RemovePolicy::Restrict), Ok(3));
+        assert_eq!(RemovePolicy::default(), RemovePolicy::Cascade);
+        assert_eq!(graph.remove_vertex(b), Some(2));
+        assert!(graph.is_empty());
+    }
+
+    #[test]
+    fn test_pinned_vertices_survive_removal() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let root = graph.add_vertex(0);
+        let leaves: Vec<_> = (1..=4).map(|i| graph.add_vertex(i)).collect();
+        for &leaf in &leaves {
+            graph.add_edge(root, leaf, 0);
+        }
+        assert!(graph.pin_vertex(root));
+        assert!(!graph.pin_vertex(root));
+        assert!(graph.is_pinned(root));
+
+        assert_eq!(graph.remove_vertex(root), None);
+        assert_eq!(
+            graph.remove_vertex_policy(root, RemovePolicy::Cascade),
+            Err(GraphError::VertexPinned(root))
+        );
+        graph.with_deferred_removals(|graph| graph.queue_remove_vertex(root));
+        assert_eq!(graph.out_degree(root), 4);
+
+        // 保留权重为偶数的顶点，被固定的根顶点不受回调影响
+        assert_eq!(graph.retain_vertices(|_, weight| *weight % 2 == 1), 2);
+        assert!(graph.contains_vertex(root));
+        assert_eq!(graph.out_degree(root), 2);
+        assert_eq!(graph.retain_vertices(|_, _| false), 2);
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (1, 0));
+
+        assert!(graph.unpin_vertex(root));
+        assert!(!graph.unpin_vertex(root));
+        assert_eq!(graph.remove_vertex(root), Some(0));
+        assert!(!graph.pin_vertex(root));
+
+        // clear 删除固定的顶点并清空固定标记
+        let pinned = graph.add_vertex(5);
+        graph.pin_vertex(pinned);
+        Graph::clear(&mut graph);
+        assert!(graph.is_empty() && !graph.is_pinned(pinned));
+    }
+
+    #[test]
+    fn test_ffi_ids_resolve_only_while_live() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let ab = graph.add_edge(a, b, 3);
+
+        let (raw_a, raw_ab) = (a.as_ffi(), ab.as_ffi());
+        assert_eq!(graph.vertex_from_ffi(raw_a).map(|v| v.id()), Some(a));
+        assert_eq!(graph.edge_from_ffi(raw_ab).map(|e| *e.weight()), Some(3));
+
+        // 旧ID仍能还原为相同的值，但槽位重用后不再解析到任何元素
+        graph.remove_vertex(a);
+        let reused = graph.add_vertex(4);
+        assert_eq!(VertexId::<DefaultKey>::from_ffi(raw_a), a);
+        assert_eq!(reused.key().data().index(), a.key().data().index());
+        assert!(graph.vertex_from_ffi(raw_a).is_none());
+        assert!(graph.edge_from_ffi(raw_ab).is_none());
+        assert_eq!(graph.vertex_from_ffi(reused.as_ffi()).map(|v| *v.weight()), Some(4));
+    }
+
+    #[test]
+    fn test_sort_ids_by_weight() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let vertices: Vec<_> = [3, 1, 2].iter().map(|&w| graph.add_vertex(w)).collect();
+        let a = vertices[0];
+        let edges: Vec<_> = [5, 2, 5, 1, 2].iter().map(|&w| graph.add_edge(a, a, w)).collect();
+
+        let mut ids = edges.clone();
+        graph.sort_edges_by(&mut ids, |_, weight| *weight);
+        // 键相等的边保持原有顺序
+        assert_eq!(ids, vec![edges[3], edges[1], edges[4], edges[0], edges[2]]);
+
+        // 已删除的边排在末尾并保持原有顺序
+        graph.remove_edge(edges[1]);
+        graph.remove_edge(edges[3]);
+        let mut ids = edges.clone();
+        graph.sort_edges_by(&mut ids, |_, weight| std::cmp::Reverse(*weight));
+        assert_eq!(ids, vec![edges[0], edges[2], edges[4], edges[1], edges[3]]);
+
+        let stale = graph.add_vertex(0);
+        graph.remove_vertex(stale);
+        let mut ids = vec![stale, vertices[0], vertices[1], vertices[2]];
+        graph.sort_vertices_by(&mut ids, |_, weight| *weight);
+        assert_eq!(ids, vec![vertices[1], vertices[2], vertices[0], stale]);
+    }
+
+    #[test]
+    fn test_marks_follow_element_lifetime() {
+        const VISITED: u8 = 1;
+        const DIRTY: u8 = 2;
+
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        assert!(!graph.set_mark(a, VISITED));
+        graph.enable_marks();
+
+        let b = graph.add_vertex(2);
+        let c = graph.add_vertex(3);
+        let ab = graph.add_edge(a, b, 1);
+        let bc = graph.add_edge(b, c, 2);
+        for v in [a, b, c] {
+            assert!(graph.set_mark(v, VISITED));
+        }
+        graph.set_mark(b, DIRTY);
+        graph.set_mark(ab, DIRTY);
+        graph.set_mark(bc, DIRTY);
+
+        graph.clear_mark(a, VISITED);
+        assert!(!graph.has_mark(a, VISITED));
+        assert_eq!(graph.marked_vertices(VISITED).collect::<Vec<_>>(), vec![b, c]);
+        assert_eq!(graph.marked_vertices(DIRTY).collect::<Vec<_>>(), vec![b]);
+
+        // 删除顶点时移除其标记以及级联删除的边的标记
+        graph.remove_vertex(b);
+        assert_eq!(graph.marks.as_ref().unwrap().len(), 1);
+        assert_eq!(graph.marked_edges(DIRTY).count(), 0);
+
+        // 槽位被重用后，新元素不带旧标记
+        let reused = graph.add_vertex(4);
+        assert_eq!(reused.key().data().index(), b.key().data().index());
+        assert!(!graph.has_mark(reused, VISITED | DIRTY));
+        let edge = graph.add_edge(a, c, 5);
+        assert!(!graph.has_mark(edge, DIRTY));
+        assert!(!graph.set_mark(b, VISITED));
+
+        graph.clear_all_marks(VISITED);
+        assert!(graph.marks.as_ref().unwrap().is_empty());
+        graph.disable_marks();
+        assert!(!graph.has_marks());
+    }
+
+    #[test]
+    fn test_adjacency_into_reuses_buffer() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let c = graph.add_vertex(3);
+        let ab = graph.add_edge(a, b, 1);
+        let ac = graph.add_edge(a, c, 2);
+        let ca = graph.add_edge(c, a, 3);
+        let cc = graph.add_edge(c, c, 4);
+
+        let mut edges = Vec::with_capacity(8);
+        let capacity = edges.capacity();
+        assert_eq!(graph.out_edges_into(a, &mut edges), 2);
+        assert_eq!(edges, vec![ab, ac]);
+        assert_eq!(graph.out_edges_into(c, &mut edges), 2);
+        assert_eq!(edges, vec![ca, cc]);
+        assert_eq!(graph.out_edges_into(b, &mut edges), 0);
+        assert!(edges.is_empty());
+        assert_eq!(edges.capacity(), capacity);
+
+        let mut neighbors = Vec::new();
+        assert_eq!(graph.neighbors_into(a, Direction::Outgoing, &mut neighbors), 2);
+        assert_eq!(neighbors, vec![b, c]);
+        assert_eq!(graph.neighbors_into(a, Direction::Incoming, &mut neighbors), 1);
+        assert_eq!(neighbors, vec![c]);
+        // 自环在 All 中只产生一次
+        assert_eq!(graph.neighbors_into(c, Direction::All, &mut neighbors), 3);
+        assert_eq!(neighbors, vec![a, c, a]);
+        assert_eq!(graph.neighbors_into(c, Direction::Incoming, &mut neighbors), 2);
+        assert_eq!(neighbors, vec![a, c]);
+
+        graph.remove_vertex(b);
+        assert_eq!(graph.neighbors_into(b, Direction::All, &mut neighbors), 0);
+        assert!(neighbors.is_empty());
+    }
+
+    #[test]
+    fn test_max_degree_rejects_hub_growth() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let hub = graph.add_vertex(0);
+        let leaves: Vec<_> = (1..=4).map(|i| graph.add_vertex(i)).collect();
+
+        // 默认不限制
+        assert_eq!(graph.max_degree(), None);
+        let first = graph.try_add_edge(hub, leaves[0], 0).unwrap();
+        graph.remove_edge(first);
+
+        graph.set_max_degree(Some(3));
+        let edges: Vec<_> = leaves[..3].iter().map(|leaf| graph.try_add_edge(hub, *leaf, 1).unwrap()).collect();
+        assert_eq!(
+            graph.try_add_edge(leaves[3], hub, 2),
+            Err(GraphError::DegreeLimitExceeded { vertex: hub, limit: 3 })
+        );
+        assert_eq!((graph.degree(hub), graph.edge_count()), (3, 3));
+        let version = graph.version();
+        assert!(graph.try_add_edge(hub, hub, 3).is_err());
+        assert_eq!(graph.version(), version);
+
+        // 删除边后腾出名额
+        graph.remove_edge(edges[1]);
+        let edge = graph.try_add_edge(leaves[3], hub, 4).unwrap();
+        assert_eq!(graph.edge(edge).map(|e| *e.weight()), Some(4));
+
+        let missing = graph.add_vertex(9);
+        graph.remove_vertex(missing);
+        assert_eq!(graph.try_add_edge(hub, missing, 5), Err(GraphError::VertexNotFound(missing)));
+    }
+
+    #[test]
+    fn test_max_degree_warn_policy_allows_insert() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        graph.set_max_degree(Some(1));
+        graph.set_degree_policy(DegreePolicy::Warn(|_, limit| {
+            assert_eq!(limit, 1);
+            WARNINGS.fetch_add(1, Ordering::Relaxed);
+        }));
+        graph.add_edge(a, b, 1);
+        graph.add_edge(a, b, 2);
+        assert!(graph.try_add_edge(b, a, 3).is_ok());
+        // 第二条边两端各超出一次，第三条同样
+        assert_eq!(WARNINGS.load(Ordering::Relaxed), 4);
+        assert_eq!(graph.degree(a), 3);
+    }
+
+    #[test]
+    fn test_max_degree_not_enforced_by_add_edge() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        graph.set_max_degree(Some(0));
+        // 无法返回错误的入口照常插入，只有 try_* 强制上限
+        graph.add_edge(a, a, 1);
+        graph.add_edge_tagged(a, b, 2, 7);
+        graph.add_edge_in_layer(b, a, 3, 1);
+        assert_eq!((graph.degree(a), graph.edge_count()), (3, 3));
+        assert_eq!(graph.try_add_edge(b, b, 4), Err(GraphError::DegreeLimitExceeded { vertex: b, limit: 0 }));
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_max_degree_checked_before_connecting_vertex() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let [a, b, c] = [1, 2, 3].map(|i| graph.add_vertex(i));
+        graph.add_edge(b, c, 0);
+        graph.set_max_degree(Some(1));
+        let version = graph.version();
+
+        // b 已达上限，新顶点和出边都没有留下
+        assert_eq!(
+            graph.add_vertex_connected(9, [(a, 1)], [(b, 2)]),
+            Err(GraphError::DegreeLimitExceeded { vertex: b, limit: 1 })
+        );
+        // 同一端点出现两次，合计超出上限
+        assert_eq!(
+            graph.add_vertex_connected(9, [(a, 1)], [(a, 2)]),
+            Err(GraphError::DegreeLimitExceeded { vertex: a, limit: 1 })
+        );
+        assert_eq!((graph.vertex_count(), graph.edge_count(), graph.version()), (3, 1, version));
+
+        // 新顶点自身的边数超出上限
+        graph.set_max_degree(Some(2));
+        let [d, e] = [4, 5].map(|i| graph.add_vertex(i));
+        let (vertex_count, version) = (graph.vertex_count(), graph.version());
+        assert_eq!(
+            graph.add_vertex_connected(9, [(a, 1), (d, 2)], [(e, 3)]),
+            Err(GraphError::NewVertexDegreeExceeded { degree: 3, limit: 2 })
+        );
+        assert_eq!((graph.vertex_count(), graph.edge_count(), graph.version()), (vertex_count, 1, version));
+
+        let (child, edges) = graph.add_vertex_connected(9, [(a, 1)], [(d, 2)]).unwrap();
+        assert_eq!((graph.degree(child), edges.len()), (2, 2));
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_max_degree_warn_policy_connecting_vertex() {
+        use std::sync::Mutex;
+        static WARNED: Mutex<Vec<VertexId>> = Mutex::new(Vec::new());
+
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let [a, b] = [1, 2].map(|i| graph.add_vertex(i));
+        graph.add_edge(a, b, 0);
+        graph.set_max_degree(Some(1));
+        graph.set_degree_policy(DegreePolicy::Warn(|vertex, _| WARNED.lock().unwrap().push(vertex)));
+
+        let (child, edges) = graph.add_vertex_connected(9, [(a, 1)], [(b, 2)]).unwrap();
+        assert_eq!(edges.len(), 2);
+        // a、b 各已有一条边，新顶点在第二条边时达到上限
+        assert_eq!(*WARNED.lock().unwrap(), vec![a, b, child]);
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_max_degree_tagged_and_layered_inserts() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        graph.set_max_degree(Some(1));
+
+        let tagged = graph.try_add_edge_tagged(a, b, 1, 7).unwrap();
+        assert_eq!(graph.edge_tag(tagged), Some(7));
+        assert_eq!(
+            graph.try_add_edge_in_layer(b, a, 2, 1),
+            Err(GraphError::DegreeLimitExceeded { vertex: b, limit: 1 })
+        );
+        assert_eq!(graph.try_add_edge_tagged(a, a, 3, 7), Err(GraphError::DegreeLimitExceeded { vertex: a, limit: 1 }));
+        assert_eq!((graph.edge_count(), graph.edges_with_tag(7).count()), (1, 1));
+
+        graph.remove_edge(tagged);
+        let layered = graph.try_add_edge_in_layer(b, a, 2, 1).unwrap();
+        assert_eq!(graph.edge_layer(layered), Some(1));
+        assert_eq!(graph.edge_tag(layered), None);
+    }
+
+    #[test]
+    fn test_remove_edges_with_tag() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let vertices: Vec<_> = (0..4).map(|i| graph.add_vertex(i)).collect();
+        let first: Vec<_> = (0..5).map(|i| graph.add_edge_tagged(vertices[i % 4], vertices[(i + 1) % 4], i as u32, 1)).collect();
+        let second: Vec<_> = (0..3).map(|i| graph.add_edge_tagged(vertices[i], vertices[3 - i], 10 + i as u32, 2)).collect();
+        let untagged = graph.add_edge(vertices[0], vertices[2], 99);
+        assert_eq!(graph.edge_tag(first[0]), Some(1));
+        assert_eq!(graph.edge_tag(untagged), None);
+
+        // 单独删除的边不再属于批次，也不计入批量删除的数量
+        graph.remove_edge(first[2]);
+        assert_eq!(graph.edges_with_tag(1).count(), 4);
+
+        assert_eq!(graph.remove_edges_with_tag(1), 4);
+        assert_eq!(graph.edges_with_tag(1).count(), 0);
+        assert_eq!(graph.edges_with_tag(2).collect::<Vec<_>>(), second);
+        assert_eq!(graph.edge_count(), 4);
+        assert!(first.iter().all(|edge| graph.edge(*edge).is_none()));
+        assert_eq!(graph.remove_edges_with_tag(1), 0);
+
+        // 槽位被重用后新边不继承旧标签
+        let reused = graph.add_edge(vertices[1], vertices[2], 7);
+        assert_eq!(graph.edge_tag(reused), None);
+
+        // 删除顶点时级联删除的边同样离开批次
+        graph.remove_vertex(vertices[0]);
+        assert_eq!(graph.edges_with_tag(2).collect::<Vec<_>>(), second[1..]);
+        assert_eq!(graph.remove_edges_with_tag(2), 2);
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_edge_layers_filter_adjacency() {
+        const DATA: u32 = 0;
+        const CONTROL: u32 = 3;
+
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let c = graph.add_vertex(3);
+        let ab = graph.add_edge_in_layer(a, b, 1, DATA);
+        let bc = graph.add_edge_in_layer(b, c, 2, DATA);
+        let ac = graph.add_edge_in_layer(a, c, 3, CONTROL);
+        assert_eq!(graph.edge_layer(ac), Some(CONTROL));
+        assert_eq!(graph.out_degree(a), 2);
+
+        // 只显示数据依赖：a -> c 只能经过 b
+        graph.set_visible_layers(1 << DATA);
+        assert_eq!(graph.outgoing_edges(a).map(|e| e.id()).collect::<Vec<_>>(), vec![ab]);
+        assert_eq!((graph.out_degree(a), graph.in_degree(c), graph.degree(c)), (1, 1, 1));
+        assert_eq!(graph.bfs_shortest_path(a, c), Some(vec![a, b, c]));
+        assert_eq!(graph.all_edges().count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+
+        // 只显示控制依赖：a 直达 c，b 不可达
+        graph.set_visible_layers(1 << CONTROL);
+        assert_eq!(graph.bfs_shortest_path(a, c), Some(vec![a, c]));
+        assert_eq!(graph.bfs_shortest_path(a, b), None);
+        assert_eq!(graph.incoming_edges(c).map(|e| e.id()).collect::<Vec<_>>(), vec![ac]);
+
+        // 移动图层后立即生效
+        assert!(graph.set_edge_layer(bc, CONTROL));
+        assert_eq!(graph.in_degree(c), 2);
+
+        graph.set_visible_layers(u64::MAX);
+        assert_eq!(graph.out_degree(a), 2);
+        assert_eq!(graph.bfs(a).count(), 3);
+    }
+
+    #[test]
+    fn test_edge_layers_hide_cached_label_counts() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        graph.add_edge_in_layer(a, b, 1, 0);
+        graph.add_edge_in_layer(b, a, 2, 1);
+        graph.enable_adjacent_label_cache();
+        assert_eq!(graph.adjacent_label_counts(a).collect::<Vec<_>>(), vec![((), 2)]);
+
+        // 缓存包含隐藏图层中的边，部分图层不可见时不读取缓存
+        graph.set_visible_layers(1);
+        assert_eq!(graph.adjacent_label_counts(a).collect::<Vec<_>>(), vec![((), 1)]);
+        graph.set_visible_layers(0);
+        assert_eq!(graph.adjacent_label_counts(a).count(), 0);
+
+        graph.set_visible_layers(u64::MAX);
+        assert_eq!(graph.adjacent_label_counts(a).collect::<Vec<_>>(), vec![((), 2)]);
+    }
+
+    #[test]
+    fn test_edge_layers_follow_edge_lifetime() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let ab = graph.add_edge_in_layer(a, b, 1, 5);
+        assert_eq!(graph.layers.len(), 1);
+
+        graph.remove_edge(ab);
+        assert!(graph.layers.is_empty());
+        assert_eq!(graph.edge_layer(ab), None);
+        assert!(!graph.set_edge_layer(ab, 1));
+
+        // 重用的槽位回到 0 号图层
+        let reused = graph.add_edge(a, b, 2);
+        assert_eq!(reused.key().data().index(), ab.key().data().index());
+        assert_eq!(graph.edge_layer(reused), Some(0));
+
+        graph.add_edge_in_layer(b, a, 3, 1);
+        graph.set_visible_layers(1);
+        Graph::clear(&mut graph);
+        assert!(graph.layers.is_empty());
+        assert_eq!(graph.visible_layers(), 1);
+    }
+
+    #[test]
+    fn test_creation_order_survives_slot_reuse() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        assert_eq!(graph.creation_seq(a), None);
+        assert_eq!(graph.vertices_in_creation_order().count(), 0);
+        graph.enable_creation_order();
+        assert_eq!((graph.creation_seq(a), graph.creation_seq(b)), (Some(0), Some(1)));
+
+        // 交替创建和删除，重用的槽位排在后面
+        let c = graph.add_vertex(3);
+        let ab = graph.add_edge(a, b, 1);
+        graph.remove_vertex(a);
+        let d = graph.add_vertex(4);
+        assert_eq!(d.key().data().index(), a.key().data().index());
+        let bd = graph.add_edge(b, d, 2);
+        let dc = graph.add_edge(d, c, 3);
+        graph.remove_edge(bd);
+        let cb = graph.add_edge(c, b, 4);
+
+        assert_eq!(graph.creation_seq(a), None);
+        assert_eq!(graph.creation_seq(ab), None);
+        assert!(graph.creation_seq(c) < graph.creation_seq(d));
+        assert!(graph.creation_seq(d) < graph.creation_seq(dc));
+        assert_eq!(graph.vertices_in_creation_order().collect::<Vec<_>>(), vec![b, c, d]);
+        assert_eq!(graph.edges_in_creation_order().collect::<Vec<_>>(), vec![dc, cb]);
+        let by_slot: Vec<_> = graph.vertex_ids().collect();
+        assert_eq!(by_slot, vec![d, b, c]);
+
+        // 再次开启不重新编号，清空后序号继续递增
+        graph.enable_creation_order();
+        assert_eq!(graph.creation_seq(b), Some(1));
+        let last = graph.creation_seq(cb).unwrap();
+        Graph::clear(&mut graph);
+        let e = graph.add_vertex(5);
+        assert!(graph.creation_seq(e).unwrap() > last);
+        assert_eq!(graph.creation_order.as_ref().unwrap().len(), (1, 0));
+
+        graph.disable_creation_order();
+        assert!(!graph.has_creation_order());
+        assert_eq!(graph.creation_seq(e), None);
+    }
+
+    thread_local! {
+        /// 当前线程中 `Counted::label` 的调用次数
+        static LABEL_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    fn label_calls() -> usize {
+        LABEL_CALLS.with(std::cell::Cell::get)
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum Kind {
+        Red,
+        Blue,
+    }
+
+    impl graph_api_lib::Label for Kind {
+        type Index = ();
+
+        fn variants() -> &'static [Self] {
+            &[Kind::Red, Kind::Blue]
+        }
+
+        fn indexes(&self) -> &'static [()] {
+            &[]
+        }
+
+        fn ordinal(&self) -> usize {
+            *self as usize
+        }
+
+        fn name(&self) -> &'static str {
+            match self {
+                Kind::Red => "Red",
+                Kind::Blue => "Blue",
+            }
+        }
+    }
+
+    /// 记录 `label()` 调用次数的元素
+    #[derive(Debug)]
+    struct Counted(Kind);
+
+    impl Element for Counted {
+        type Label = Kind;
+
+        fn label(&self) -> Kind {
+            LABEL_CALLS.with(|calls| calls.set(calls.get() + 1));
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_label_cache_avoids_repeat_label_calls() {
+        use graph_api_lib::EdgeReferenceMut;
+
+        let build = |cached: bool| {
+            let mut graph: SlotMapGraph<Counted, Counted> = SlotMapGraph::new();
+            if cached {
+                graph.enable_label_cache();
+            }
+            let hub = graph.add_vertex(Counted(Kind::Red));
+            let leaves: Vec<_> = [Kind::Red, Kind::Blue, Kind::Red, Kind::Blue].map(|kind| graph.add_vertex(Counted(kind))).to_vec();
+            let edges: Vec<_> = [Kind::Red, Kind::Red, Kind::Red, Kind::Blue]
+                .iter()
+                .zip(&leaves)
+                .map(|(&kind, &leaf)| graph.add_edge(hub, leaf, Counted(kind)))
+                .collect();
+            (graph, hub, leaves, edges)
+        };
+        let red = EdgeSearch::label(Kind::Red);
+        let red_to_blue = EdgeSearch::label(Kind::Red).adjacent_labelled(Kind::Blue);
+        let query = |graph: &SlotMapGraph<Counted, Counted>, hub| {
+            (
+                graph.edges(hub, &red).count(),
+                graph.edges(hub, &red_to_blue).count(),
+                graph.adjacent_edges_by_label(hub, Kind::Blue, Direction::Outgoing).count(),
+                graph.global_edges(&EdgeSearch::scan().adjacent_labelled(Kind::Blue)).count(),
+            )
+        };
+
+        // 未开启缓存时每次查询都调用 label()
+        let (graph, hub, ..) = build(false);
+        let before = label_calls();
+        assert_eq!(query(&graph, hub), (3, 1, 1, 2));
+        assert!(label_calls() > before);
+
+        let (mut graph, hub, leaves, edges) = build(true);
+        let before = label_calls();
+        for _ in 0..10 {
+            assert_eq!(query(&graph, hub), (3, 1, 1, 2));
+        }
+        assert_eq!(label_calls(), before);
+
+        // edge_mut 释放时重新计算一次
+        graph.edge_mut(edges[0]).unwrap().weight_mut().0 = Kind::Blue;
+        assert_eq!(label_calls(), before + 1);
+        assert_eq!(query(&graph, hub), (2, 1, 2, 2));
+        assert_eq!(label_calls(), before + 1);
+
+        // 裸可变引用移除缓存项，下一次修改图时重新计算一次
+        graph.edge_weight_mut(edges[3]).unwrap().0 = Kind::Red;
+        let before = label_calls();
+        graph.add_edge(leaves[2], leaves[3], Counted(Kind::Blue));
+        // 被修改的边和新边各一次
+        assert_eq!(label_calls(), before + 2);
+        graph.vertex_weight_mut(leaves[0]).unwrap().0 = Kind::Blue;
+        graph.add_vertex(Counted(Kind::Red));
+        let before = label_calls();
+        for _ in 0..10 {
+            assert_eq!(query(&graph, hub), (3, 2, 1, 4));
+        }
+        assert_eq!(label_calls(), before);
+
+        graph.remove_vertex(leaves[1]);
+        assert_eq!(graph.label_cache.as_ref().unwrap().len(), (5, 4));
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_contains_element() {
+        let mut graph: SlotMapGraph<(), ()> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let ab = graph.add_edge(a, b, ());
+        let ba = graph.add_edge(b, a, ());
+
+        let ids: Vec<ElementId<SlotMapGraph<(), ()>>> = vec![a.into(), b.into(), ab.into(), ba.into()];
+        assert!(ids.iter().all(|&id| graph.contains_element(id)));
+
+        // 删除边只影响边变体
+        graph.remove_edge(ab);
+        assert!(!graph.contains_element(ab));
+        assert!(graph.contains_element(ElementId::Edge(ba)));
+        assert!(graph.contains_element(ElementId::Vertex(a)));
+
+        // 删除顶点时关联的边一起失效
+        graph.remove_vertex(b);
+        assert!(!graph.contains_element(b));
+        assert!(!graph.contains_element(ba));
+        assert!(graph.contains_element(a));
+    }
+
+    #[test]
+    fn test_edge_direction_from_vertex() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let c = graph.add_vertex(());
+
+        let ab = graph.add_edge(a, b, 1);
+        let ca = graph.add_edge(c, a, 2);
+        let aa = graph.add_edge(a, a, 3);
+
+        let edge_ref = graph.edge(ab).unwrap();
+        assert_eq!(edge_ref.direction_from(a), Some(Direction::Outgoing));
+        assert_eq!(edge_ref.direction_from(b), Some(Direction::Incoming));
+        assert_eq!(edge_ref.direction_from(c), None);
+        assert_eq!(graph.edge(aa).unwrap().direction_from(a), Some(Direction::Outgoing));
+
+        let around_a: Vec<_> = graph
+            .adjacent_edges_with_direction(a)
+            .map(|(direction, e)| (direction, e.id()))
+            .collect();
+        assert_eq!(
+            around_a,
+            vec![(Direction::Outgoing, ab), (Direction::Outgoing, aa), (Direction::Incoming, ca)]
+        );
+        assert_eq!(graph.adjacent_edges_with_direction(b).map(|(d, _)| d).collect::<Vec<_>>(), vec![Direction::Incoming]);
+    }
+
+    #[test]
+    fn test_edge_search_yields_each_incident_edge_once() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let [a, b, sink] = [(); 3].map(|_| graph.add_vertex(()));
+        let aa = graph.add_edge(a, a, 0);
+        let ab = graph.add_edge(a, b, 1);
+        let ba = graph.add_edge(b, a, 2);
+        let ab2 = graph.add_edge(a, b, 3);
+        let a_sink = graph.add_edge(a, sink, 4);
+        let b_sink = graph.add_edge(b, sink, 5);
+
+        let yielded = |vertex, direction| {
+            let search = match direction {
+                Direction::Outgoing => EdgeSearch::scan().outgoing(),
+                Direction::Incoming => EdgeSearch::scan().incoming(),
+                Direction::All => EdgeSearch::scan(),
+            };
+            let mut counts: HashMap<EdgeId, usize> = HashMap::new();
+            for edge in graph.edges(vertex, &search) {
+                *counts.entry(edge.id()).or_default() += 1;
+            }
+            counts
+        };
+        let once = |ids: &[EdgeId]| ids.iter().map(|&id| (id, 1)).collect::<HashMap<_, _>>();
+
+        // 自环、互逆边和平行边
+        assert_eq!(yielded(a, Direction::Outgoing), once(&[aa, ab, ab2, a_sink]));
+        assert_eq!(yielded(a, Direction::Incoming), once(&[aa, ba]));
+        assert_eq!(yielded(a, Direction::All), once(&[aa, ab, ba, ab2, a_sink]));
+        assert_eq!(yielded(b, Direction::Outgoing), once(&[ba, b_sink]));
+        assert_eq!(yielded(b, Direction::Incoming), once(&[ab, ab2]));
+        assert_eq!(yielded(b, Direction::All), once(&[ab, ba, ab2, b_sink]));
+        // 只有入边的顶点
+        assert!(yielded(sink, Direction::Outgoing).is_empty());
+        assert_eq!(yielded(sink, Direction::Incoming), once(&[a_sink, b_sink]));
+        assert_eq!(yielded(sink, Direction::All), once(&[a_sink, b_sink]));
+    }
+
+    #[test]
+    fn test_undirected_edge_queries() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(());
+        let b = graph.add_vertex(());
+        let c = graph.add_vertex(());
+
+        let ab = graph.add_edge(a, b, 1);
+        assert!(graph.has_edge(a, b));
+        assert!(!graph.has_edge(b, a));
+        assert!(graph.has_edge_undirected(a, b));
+        assert!(graph.has_edge_undirected(b, a));
+        assert!(!graph.has_edge_undirected(a, c));
+        assert_eq!(graph.edges_between(b, a).count(), 0);
+
+        let ba = graph.add_edge(b, a, 2);
+        let from_a: Vec<_> = graph.edges_between_undirected(a, b).map(|e| e.id()).collect();
+        assert_eq!(from_a, vec![ab, ba]);
+        let from_b: Vec<_> = graph.edges_between_undirected(b, a).map(|e| e.id()).collect();
+        assert_eq!(from_b, vec![ba, ab]);
+
+        let aa = graph.add_edge(a, a, 3);
+        let loops: Vec<_> = graph.edges_between_undirected(a, a).map(|e| e.id()).collect();
+        assert_eq!(loops, vec![aa]);
+    }
+
+    #[test]
+    fn test_edge_insertion_order() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let root = graph.add_vertex(());
+        let child = graph.add_vertex(());
+        let weights = |graph: &SlotMapGraph<(), u32>| -> Vec<u32> {
+            graph.outgoing_edges(root).map(|e| *e.weight()).collect()
+        };
+
+        let a = graph.add_edge(root, child, 1);
+        let b = graph.add_edge(root, child, 2);
+        let c = graph.add_edge(root, child, 3);
+
+        // 删除中间的边后再加入，复用的槽位不影响顺序，新边排在最后
+        graph.remove_edge(b);
+        let b = graph.add_edge(root, child, 2);
+        assert_eq!(weights(&graph), vec![1, 3, 2]);
+        let incoming: Vec<_> = graph.incoming_edges(child).map(|e| e.id()).collect();
+        assert_eq!(incoming, vec![a, c, b]);
+
+        assert!(graph.reorder_edges(root, &[b, a, c]));
+        assert_eq!(weights(&graph), vec![2, 1, 3]);
+        let search = graph_api_lib::EdgeSearch::scan();
+        let via_trait: Vec<_> = graph.edges(root, &search).map(|e| *e.weight()).collect();
+        assert_eq!(via_trait, vec![2, 1, 3]);
+
+        assert!(graph.move_edge_to_front(root, c));
+        assert_eq!(weights(&graph), vec![3, 2, 1]);
+
+        graph.set_edge_removal_order(EdgeRemovalOrder::Swap);
+        graph.remove_edge(c);
+        assert_eq!(weights(&graph), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_adjacency_slices_match_edge_iterators() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add_vertex(()));
+        let ids: Vec<_> = [(a, b), (c, b), (b, b), (a, b), (b, c)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (from, to))| graph.add_edge(from, to, i as u32))
+            .collect();
+        let parity = |graph: &SlotMapGraph<(), u32>| {
+            for v in [a, b, c] {
+                let incoming: Vec<_> = graph.incoming_edges(v).map(|e| e.id()).collect();
+                let outgoing: Vec<_> = graph.outgoing_edges(v).map(|e| e.id()).collect();
+                assert_eq!(graph.incoming_adjacency(v), incoming.as_slice());
+                assert_eq!(graph.outgoing_adjacency(v), outgoing.as_slice());
+            }
+        };
+
+        parity(&graph);
+        assert_eq!(graph.incoming_adjacency(b), &[ids[0], ids[1], ids[2], ids[3]]);
+
+        // 交换删除同时维护出边和入边列表
+        graph.set_edge_removal_order(EdgeRemovalOrder::Swap);
+        graph.remove_edge(ids[0]);
+        parity(&graph);
+        assert_eq!(graph.incoming_adjacency(b), &[ids[3], ids[1], ids[2]]);
+
+        graph.remove_vertex(c);
+        parity(&graph);
+        assert!(graph.incoming_adjacency(c).is_empty());
+        assert!(graph.outgoing_adjacency(c).is_empty());
+    }
+
+    #[test]
+    fn test_pair_index_matches_scan() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add_vertex(()));
+        let ab1 = graph.add_edge(a, b, 1);
+        graph.enable_pair_index();
+        let ab2 = graph.add_edge(a, b, 2);
+        let ba = graph.add_edge(b, a, 3);
+        let cc = graph.add_edge(c, c, 4);
+        graph.add_edge(c, a, 5);
+        graph.add_edge(b, c, 6);
+
+        let between = |graph: &SlotMapGraph<(), u32>, from, to| graph.edges_between(from, to).map(|e| e.id()).collect::<Vec<_>>();
+        assert_eq!(between(&graph, a, b), vec![ab1, ab2]);
+        assert_eq!(graph.find_edge(a, b), Some(ab1));
+        assert_eq!(graph.find_edge(b, a), Some(ba));
+        assert_eq!(graph.find_edge(c, c), Some(cc));
+        assert!(!graph.has_edge(a, c));
+
+        graph.remove_edge(ab1);
+        assert_eq!(between(&graph, a, b), vec![ab2]);
+
+        // 级联删除顶点后索引中不再有其关联边
+        graph.remove_vertex(c);
+        let indexed: Vec<_> = [a, b, c]
+            .iter()
+            .flat_map(|from| [a, b, c].map(|to| (*from, to, graph.has_edge(*from, to), between(&graph, *from, to))))
+            .collect();
+        graph.disable_pair_index();
+        assert!(!graph.has_pair_index());
+        for (from, to, has, edges) in indexed {
+            assert_eq!(graph.has_edge(from, to), has);
+            assert_eq!(between(&graph, from, to), edges);
+        }
+        assert!(!graph.has_edge(c, c));
+
+        graph.enable_pair_index();
+        SupportsClear::clear(&mut graph);
+        let [a, b] = [(); 2].map(|_| graph.add_vertex(()));
+        assert!(!graph.has_edge(a, b));
+        let ab = graph.add_edge(a, b, 7);
+        assert_eq!(graph.find_edge(a, b), Some(ab));
+    }
+
+    #[test]
+    fn test_version_tracks_structural_changes() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let edge = graph.add_edge(a, b, 3);
+        let version = graph.version();
+        assert_eq!(version, 3);
+
+        // 修改权重和边顺序不改变结构版本
+        *graph.vertex_weight_mut(a).unwrap() = 10;
+        graph.replace_edge_weight(edge, 4);
+        graph.move_edge_to_front(a, edge);
+        assert_eq!(graph.version(), version);
+
+        graph.remove_edge(edge);
+        assert!(graph.version() > version);
+        let version = graph.version();
+        SupportsClear::clear(&mut graph);
+        assert!(graph.version() > version);
+    }
+
+    mod vertex_labels {
+        use super::*;
+        use graph_api_lib::VertexReferenceMut as _;
+        use graph_api_test::{Project, Vertex as LabeledVertex, VertexLabel};
+
+        fn project() -> LabeledVertex {
+            LabeledVertex::Project(Project { name: "GraphApi".to_string() })
+        }
+
+        fn brute_force(graph: &SlotMapGraph<LabeledVertex, ()>, label: VertexLabel) -> Vec<VertexId> {
+            let mut ids: Vec<_> = graph
+                .all_vertices()
+                .filter(|(_, weight)| weight.label() == label)
+                .map(|(id, _)| id)
+                .collect();
+            ids.sort_by_key(|id| id.to_string());
+            ids
+        }
+
+        fn indexed(graph: &SlotMapGraph<LabeledVertex, ()>, label: VertexLabel) -> Vec<VertexId> {
+            let mut ids: Vec<_> = graph.vertices_with_label(label).map(|vertex| vertex.id()).collect();
+            ids.sort_by_key(|id| id.to_string());
+            ids
+        }
+
+        fn count(graph: &SlotMapGraph<LabeledVertex, ()>, label: VertexLabel) -> usize {
+            graph.labels().find(|(l, _)| *l == label).map_or(0, |(_, count)| count)
+        }
+
+        #[test]
+        fn test_label_counts_follow_add_and_remove() {
+            let mut graph: SlotMapGraph<LabeledVertex, ()> = SlotMapGraph::new();
+            let first_project = graph.add_vertex(project());
+            graph.add_vertex(project());
+            let rust = graph.add_vertex(LabeledVertex::Rust);
+            for _ in 0..3 {
+                graph.add_vertex(LabeledVertex::Person {
+                    name: "Alice".to_string(),
+                    age: 30,
+                    unique_id: Default::default(),
+                    username: "alice".to_string(),
+                    biography: String::new(),
+                });
+            }
+
+            assert_eq!(graph.labels().count(), 3);
+            assert_eq!(count(&graph, VertexLabel::Project), 2);
+            assert_eq!(count(&graph, VertexLabel::Rust), 1);
+            assert_eq!(count(&graph, VertexLabel::Person), 3);
+
+            graph.remove_vertex(first_project);
+            graph.remove_vertex(rust);
+            assert_eq!(graph.labels().count(), 2);
+            assert_eq!(count(&graph, VertexLabel::Project), 1);
+            assert_eq!(count(&graph, VertexLabel::Rust), 0);
+
+            for label in [VertexLabel::Person, VertexLabel::Project, VertexLabel::Rust] {
+                assert_eq!(indexed(&graph, label), brute_force(&graph, label));
+            }
+
+            SupportsClear::clear(&mut graph);
+            assert_eq!(graph.labels().count(), 0);
+        }
+
+        #[test]
+        fn test_label_change_through_mutable_reference() {
+            let mut graph: SlotMapGraph<LabeledVertex, ()> = SlotMapGraph::new();
+            let changed = graph.add_vertex(LabeledVertex::Rust);
+            graph.add_vertex(LabeledVertex::Rust);
+
+            *graph.vertex_mut(changed).unwrap().weight_mut() = project();
+            // 尚未同步时查询结果也已反映新标签
+            assert_eq!(count(&graph, VertexLabel::Rust), 1);
+            assert_eq!(indexed(&graph, VertexLabel::Project), vec![changed]);
+
+            *graph.vertex_weight_mut(changed).unwrap() = LabeledVertex::Rust;
+            graph.add_vertex(project());
+            assert_eq!(count(&graph, VertexLabel::Rust), 2);
+            for label in [VertexLabel::Person, VertexLabel::Project, VertexLabel::Rust] {
+                assert_eq!(indexed(&graph, label), brute_force(&graph, label));
+            }
+
+            graph.vertex_weight_mut(changed);
+            graph.remove_vertex(changed);
+            assert_eq!(count(&graph, VertexLabel::Rust), 1);
+        }
+
+        fn multi(graph: &SlotMapGraph<LabeledVertex, ()>, labels: &[VertexLabel], limit: Option<usize>) -> Vec<VertexId> {
+            graph.vertices_with_labels(labels, limit).map(|vertex| vertex.id()).collect()
+        }
+
+        #[test]
+        fn test_multiple_labels_union_without_duplicates() {
+            let mut graph: SlotMapGraph<LabeledVertex, ()> = SlotMapGraph::new();
+            graph.add_vertex(project());
+            graph.add_vertex(project());
+            let rust = graph.add_vertex(LabeledVertex::Rust);
+
+            let mut union = multi(&graph, &[VertexLabel::Project, VertexLabel::Rust, VertexLabel::Project], None);
+            // 按标签首次出现的顺序读取，重复的 Project 不再产生顶点
+            assert_eq!(union.len(), 3);
+            assert_eq!(union[2], rust);
+            let mut expected = [indexed(&graph, VertexLabel::Project), vec![rust]].concat();
+            expected.sort_by_key(|id| id.to_string());
+            union.sort_by_key(|id| id.to_string());
+            assert_eq!(union, expected);
+
+            // 标签修改尚未同步时也不会重复
+            *graph.vertex_mut(rust).unwrap().weight_mut() = project();
+            assert_eq!(multi(&graph, &[VertexLabel::Rust, VertexLabel::Project], None).len(), 3);
+        }
+
+        #[test]
+        fn test_multiple_labels_limit_and_missing_label() {
+            let mut graph: SlotMapGraph<LabeledVertex, ()> = SlotMapGraph::new();
+            let rust = graph.add_vertex(LabeledVertex::Rust);
+            graph.add_vertex(project());
+            graph.add_vertex(project());
+
+            // 上限跨越两个标签：Rust 的一个顶点之后只取 Project 的一个
+            let limited = multi(&graph, &[VertexLabel::Rust, VertexLabel::Project], Some(2));
+            assert_eq!(limited.len(), 2);
+            assert_eq!(limited[0], rust);
+            assert_eq!(multi(&graph, &[VertexLabel::Rust, VertexLabel::Project], Some(10)).len(), 3);
+            assert!(multi(&graph, &[VertexLabel::Rust, VertexLabel::Project], Some(0)).is_empty());
+
+            // 没有顶点的标签不产生结果
+            assert_eq!(multi(&graph, &[VertexLabel::Person, VertexLabel::Rust], None), vec![rust]);
+            assert!(multi(&graph, &[VertexLabel::Person], None).is_empty());
+            assert!(multi(&graph, &[], None).is_empty());
+        }
+    }
+
+    mod weight_replacement {
+        use super::*;
+        use graph_api_test::{Edge as LabeledEdge, EdgeLabel, Project, Vertex as LabeledVertex, VertexLabel};
+
+        fn project(name: &str) -> LabeledVertex {
+            LabeledVertex::Project(Project { name: name.to_string() })
+        }
+
+        #[test]
+        fn test_replace_returns_old_and_reindexes() {
+            let mut graph: SlotMapGraph<LabeledVertex, LabeledEdge> = SlotMapGraph::new();
+            let a = graph.add_vertex(project("a"));
+            let b = graph.add_vertex(LabeledVertex::Rust);
+            let edge = graph.add_edge(a, b, LabeledEdge::Created);
+            graph.enable_adjacent_label_cache();
+
+            let old = graph.replace_vertex_weight(a, LabeledVertex::Rust);
+            assert!(matches!(old, Some(LabeledVertex::Project(Project { ref name })) if name == "a"));
+            assert_eq!(graph.vertices_with_label(VertexLabel::Rust).count(), 2);
+            assert_eq!(graph.vertices_with_label(VertexLabel::Project).count(), 0);
+
+            let old = graph.replace_edge_weight(edge, LabeledEdge::Knows { since: 2024 });
+            assert!(matches!(old, Some(LabeledEdge::Created)));
+            assert_eq!(graph.edges_with_label(EdgeLabel::Created).count(), 0);
+            assert_eq!(graph.edges_with_label(EdgeLabel::Knows).count(), 1);
+            assert_eq!(graph.adjacent_label_count(b, EdgeLabel::Knows), Some(1));
+            assert_eq!(graph.adjacent_label_count(a, EdgeLabel::Created), Some(0));
+
+            graph.remove_vertex(b);
+            assert!(graph.replace_vertex_weight(b, LabeledVertex::Rust).is_none());
+            assert!(graph.replace_edge_weight(edge, LabeledEdge::Created).is_none());
+        }
+
+        #[test]
+        fn test_swap_vertex_weights() {
+            let mut graph: SlotMapGraph<LabeledVertex, ()> = SlotMapGraph::new();
+            let a = graph.add_vertex(project("a"));
+            let b = graph.add_vertex(LabeledVertex::Rust);
+            graph.add_edge(a, b, ());
+
+            assert!(graph.swap_vertex_weights(a, b));
+            assert!(matches!(graph.vertex_weight(a), Some(LabeledVertex::Rust)));
+            assert_eq!(graph.vertices_with_label(VertexLabel::Project).map(|v| v.id()).collect::<Vec<_>>(), vec![b]);
+            assert_eq!(graph.vertices_with_label(VertexLabel::Rust).map(|v| v.id()).collect::<Vec<_>>(), vec![a]);
+            // 拓扑不变
+            assert!(graph.has_edge(a, b));
+
+            assert!(graph.swap_vertex_weights(a, a));
+            graph.remove_vertex(b);
+            assert!(!graph.swap_vertex_weights(a, b));
+            assert!(matches!(graph.vertex_weight(a), Some(LabeledVertex::Rust)));
+        }
+    }
+
+    mod edge_labels {
+        use super::*;
+        use graph_api_lib::EdgeReferenceMut as _;
+        use graph_api_test::{Edge as LabeledEdge, EdgeLabel};
+
+        type LabeledGraph = SlotMapGraph<(), LabeledEdge>;
+
+        fn sorted(mut ids: Vec<EdgeId>) -> Vec<EdgeId> {
+            ids.sort_by_key(|id| id.to_string());
+            ids
+        }
+
+        fn indexed(graph: &LabeledGraph, label: EdgeLabel) -> Vec<EdgeId> {
+            sorted(graph.edges_with_label(label).map(|edge| edge.id()).collect())
+        }
+
+        fn brute_force(graph: &LabeledGraph, label: EdgeLabel) -> Vec<EdgeId> {
+            sorted(graph.all_edges().filter(|(_, weight, _, _)| weight.label() == label).map(|(id, ..)| id).collect())
+        }
+
+        fn assert_consistent(graph: &LabeledGraph) {
+            for label in [EdgeLabel::Knows, EdgeLabel::Created, EdgeLabel::Language] {
+                let expected = brute_force(graph, label);
+                assert_eq!(indexed(graph, label), expected);
+                let count = graph.edge_label_counts().find(|(l, _)| *l == label).map_or(0, |(_, count)| count);
+                assert_eq!(count, expected.len());
+            }
+        }
+
+        #[test]
+        fn test_edges_with_label_membership() {
+            let mut graph = LabeledGraph::new();
+            let a = graph.add_vertex(());
+            let b = graph.add_vertex(());
+            let knows = graph.add_edge(a, b, LabeledEdge::Knows { since: 2020 });
+            let created: Vec<_> = (0..3).map(|_| graph.add_edge(b, a, LabeledEdge::Created)).collect();
+
+            assert_eq!(indexed(&graph, EdgeLabel::Knows), vec![knows]);
+            assert_eq!(indexed(&graph, EdgeLabel::Created), sorted(created.clone()));
+            assert_eq!(graph.edges_with_label(EdgeLabel::Language).count(), 0);
+            assert_eq!(graph.edge_label_counts().count(), 2);
+
+            graph.remove_edge(created[0]);
+            graph.remove_vertex(a);
+            assert_consistent(&graph);
+            assert_eq!(graph.edge_label_counts().count(), 0);
+
+            let c = graph.add_vertex(());
+            graph.add_edge(b, c, LabeledEdge::Created);
+            SupportsClear::clear(&mut graph);
+            assert_eq!(graph.edge_label_counts().count(), 0);
+        }
+
+        #[test]
+        fn test_adjacent_edges_by_label() {
+            for cached in [false, true] {
+                let mut graph = LabeledGraph::new();
+                let [a, b, c] = [(); 3].map(|_| graph.add_vertex(()));
+                let ab = graph.add_edge(a, b, LabeledEdge::Knows { since: 2020 });
+                let ca = graph.add_edge(c, a, LabeledEdge::Knows { since: 2021 });
+                let aa = graph.add_edge(a, a, LabeledEdge::Knows { since: 2022 });
+                let ac = graph.add_edge(a, c, LabeledEdge::Created);
+                if cached {
+                    graph.enable_adjacent_label_cache();
+                }
+
+                let by = |direction, label| {
+                    graph.adjacent_edges_by_label(a, label, direction).map(|e| e.id()).collect::<Vec<_>>()
+                };
+                assert_eq!(by(Direction::All, EdgeLabel::Knows), vec![ab, aa, ca]);
+                assert_eq!(by(Direction::Outgoing, EdgeLabel::Knows), vec![ab, aa]);
+                assert_eq!(by(Direction::Incoming, EdgeLabel::Knows), vec![ca, aa]);
+                assert_eq!(by(Direction::All, EdgeLabel::Created), vec![ac]);
+                assert!(by(Direction::Incoming, EdgeLabel::Created).is_empty());
+                assert!(by(Direction::All, EdgeLabel::Language).is_empty());
+
+                let mut counts: Vec<_> = graph.adjacent_label_counts(a).collect();
+                counts.sort_by_key(|(label, _)| *label as u8);
+                assert_eq!(counts, vec![(EdgeLabel::Knows, 3), (EdgeLabel::Created, 1)]);
+                assert_eq!(graph.adjacent_label_counts(b).collect::<Vec<_>>(), vec![(EdgeLabel::Knows, 1)]);
+            }
+        }
+
+        #[test]
+        fn test_edge_label_changes() {
+            let mut graph = LabeledGraph::new();
+            let a = graph.add_vertex(());
+            let first = graph.add_edge(a, a, LabeledEdge::Created);
+            let second = graph.add_edge(a, a, LabeledEdge::Created);
+
+            *graph.edge_mut(first).unwrap().weight_mut() = LabeledEdge::Knows { since: 2021 };
+            assert_consistent(&graph);
+
+            // 裸引用修改的边在重新加入索引前同样可以查到
+            *graph.edge_weight_mut(second).unwrap() = LabeledEdge::Knows { since: 2022 };
+            assert_consistent(&graph);
+            graph.add_edge(a, a, LabeledEdge::Created);
+            assert_consistent(&graph);
+
+            graph.edge_weight_mut(first);
+            graph.remove_edge(first);
+            assert_consistent(&graph);
+        }
+    }
+
+    mod adjacent_label_cache {
+        use super::*;
+        use graph_api_lib::{EdgeReferenceMut, EdgeSearch};
+        use graph_api_test::{Edge as LabeledEdge, EdgeLabel};
+
+        fn hub_graph() -> (SlotMapGraph<(), LabeledEdge>, VertexId, VertexId, EdgeId) {
+            let mut graph = SlotMapGraph::new();
+            let hub = graph.add_vertex(());
+            let leaf = graph.add_vertex(());
+            for _ in 0..3 {
+                graph.add_edge(hub, leaf, LabeledEdge::Created);
+            }
+            let knows = graph.add_edge(leaf, hub, LabeledEdge::Knows { since: 2020 });
+            graph.enable_adjacent_label_cache();
+            (graph, hub, leaf, knows)
+        }
+
+        #[test]
+        fn test_zero_match_returns_empty() {
+            let (graph, hub, leaf, _) = hub_graph();
+            let orphan_search = EdgeSearch::label(EdgeLabel::Language);
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Language), Some(0));
+            assert_eq!(graph.edges(hub, &orphan_search).count(), 0);
+            assert_eq!(graph.edges(leaf, &orphan_search).count(), 0);
+
+            // 有匹配标签时仍按方向过滤
+            assert_eq!(graph.edges(hub, &EdgeSearch::label(EdgeLabel::Created)).count(), 3);
+            assert_eq!(graph.edges(hub, &EdgeSearch::label(EdgeLabel::Knows).outgoing()).count(), 0);
+            assert_eq!(graph.edges(hub, &EdgeSearch::label(EdgeLabel::Knows).incoming()).count(), 1);
+        }
+
+        #[test]
+        fn test_counts_after_removals() {
+            let (mut graph, hub, leaf, knows) = hub_graph();
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Created), Some(3));
+            assert_eq!(graph.adjacent_label_count(leaf, EdgeLabel::Knows), Some(1));
+
+            graph.remove_edge(knows);
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Knows), Some(0));
+            assert_eq!(graph.edges(leaf, &EdgeSearch::label(EdgeLabel::Knows)).count(), 0);
+
+            let self_loop = graph.add_edge(hub, hub, LabeledEdge::Knows { since: 2021 });
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Knows), Some(1));
+            graph.remove_edge(self_loop);
+
+            graph.remove_vertex(leaf);
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Created), Some(0));
+            assert_eq!(graph.adjacent_label_count(leaf, EdgeLabel::Created), Some(0));
+
+            SupportsClear::clear(&mut graph);
+            assert!(graph.has_adjacent_label_cache());
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Created), Some(0));
+        }
+
+        #[test]
+        fn test_label_mutation_keeps_cache_in_sync() {
+            let (mut graph, hub, leaf, knows) = hub_graph();
+
+            // 经由 edge_mut 修改的标签在引用释放时同步
+            *graph.edge_mut(knows).unwrap().weight_mut() = LabeledEdge::Created;
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Knows), Some(0));
+            assert_eq!(graph.adjacent_label_count(hub, EdgeLabel::Created), Some(4));
+
+            // 经由裸引用修改会让端点失效，查询回退到逐边比较
+            *graph.edge_weight_mut(knows).unwrap() = LabeledEdge::Knows { since: 2022 };
+            assert_eq!(graph.adjacent_label_count(leaf, EdgeLabel::Knows), None);
+            assert_eq!(graph.edges(leaf, &EdgeSearch::label(EdgeLabel::Knows)).count(), 1);
+
+            graph.enable_adjacent_label_cache();
+            assert_eq!(graph.adjacent_label_count(leaf, EdgeLabel::Knows), Some(1));
+
+            graph.disable_adjacent_label_cache();
+            assert_eq!(graph.adjacent_label_count(leaf, EdgeLabel::Knows), None);
+            assert_eq!(graph.edges(leaf, &EdgeSearch::label(EdgeLabel::Knows)).count(), 1);
         }
     }
 
     #[test]
-    fn test_graph_basic_operations() {
-        let mut graph = SlotMapGraph::<TestVertex, TestEdge>::new();
+    fn test_global_edges() {
+        use graph_api_test::{Edge, EdgeLabel, Project, Vertex, VertexLabel};
 
-        // 添加顶点
-        let v1 = graph.add_vertex(TestVertex {
-            name: "A".to_string(),
-            _value: 1,
-        });
-        let v2 = graph.add_vertex(TestVertex {
-            name: "B".to_string(),
-            _value: 2,
-        });
-        let v3 = graph.add_vertex(TestVertex {
-            name: "C".to_string(),
-            _value: 3,
-        });
+        let mut graph: SlotMapGraph<Vertex, Edge> = SlotMapGraph::new();
+        let rust = graph.add_vertex(Vertex::Rust);
+        let projects: Vec<_> = (0..4).map(|_| graph.add_vertex(Vertex::Project(Project { name: String::new() }))).collect();
+        for (i, project) in projects.iter().enumerate() {
+            graph.add_edge(*project, rust, Edge::Created);
+            graph.add_edge(rust, *project, Edge::Knows { since: i as i32 });
+        }
+        // 通过可变引用改变标签的边仍然按当前标签被找到
+        let relabeled = graph.add_edge(rust, rust, Edge::Created);
+        if let Some(weight) = graph.edge_weight_mut(relabeled) {
+            *weight = Edge::Knows { since: 0 };
+        }
 
-        // 添加边
-        let e1 = graph.add_edge(v1, v2, TestEdge { weight: 1.5 });
-        let _e2 = graph.add_edge(v2, v3, TestEdge { weight: 2.5 });
-        let _e3 = graph.add_edge(v1, v3, TestEdge { weight: 3.5 });
+        let expected = |label: Option<EdgeLabel>, adjacent: Option<(Direction, VertexLabel)>| -> HashSet<EdgeId> {
+            graph
+                .all_edges()
+                .filter(|(_, weight, from, to)| {
+                    let vertex_label = |id| graph.vertex_weight(id).map(Element::label);
+                    label.is_none_or(|label| weight.label() == label)
+                        && adjacent.is_none_or(|(direction, adjacent)| match direction {
+                            Direction::Outgoing => vertex_label(*to) == Some(adjacent),
+                            Direction::Incoming => vertex_label(*from) == Some(adjacent),
+                            Direction::All => vertex_label(*from) == Some(adjacent) || vertex_label(*to) == Some(adjacent),
+                        })
+                })
+                .map(|(id, ..)| id)
+                .collect()
+        };
+        let found = |search: &EdgeSearch<SlotMapGraph<Vertex, Edge>>| -> HashSet<EdgeId> {
+            graph.global_edges(search).map(|edge| edge.id()).collect()
+        };
 
-        // 验证顶点
-        assert_eq!(graph.vertex(v1).unwrap().weight().name, "A");
-        assert_eq!(graph.vertex(v2).unwrap().weight().name, "B");
-        assert_eq!(graph.vertex(v3).unwrap().weight().name, "C");
+        assert_eq!(found(&EdgeSearch::scan()), expected(None, None));
+        assert_eq!(found(&EdgeSearch::label(EdgeLabel::Knows)).len(), 5);
+        assert_eq!(found(&EdgeSearch::label(EdgeLabel::Knows)), expected(Some(EdgeLabel::Knows), None));
+        assert_eq!(found(&EdgeSearch::label(EdgeLabel::Created)), expected(Some(EdgeLabel::Created), None));
+        assert_eq!(found(&EdgeSearch::label(EdgeLabel::Language)).len(), 0);
 
-        // 验证边
-        assert_eq!(graph.edge(e1).unwrap().weight().weight, 1.5);
-        assert_eq!(graph.edge(e1).unwrap().tail(), v1);
-        assert_eq!(graph.edge(e1).unwrap().head(), v2);
+        let into_rust = EdgeSearch::scan().outgoing().adjacent_labelled(VertexLabel::Rust);
+        assert_eq!(found(&into_rust), expected(None, Some((Direction::Outgoing, VertexLabel::Rust))));
+        let from_rust = EdgeSearch::label(EdgeLabel::Knows).incoming().adjacent_labelled(VertexLabel::Rust);
+        assert_eq!(found(&from_rust), expected(Some(EdgeLabel::Knows), Some((Direction::Incoming, VertexLabel::Rust))));
+        let touching = EdgeSearch::scan().adjacent_labelled(VertexLabel::Project);
+        assert_eq!(found(&touching), expected(None, Some((Direction::All, VertexLabel::Project))));
 
-        // 测试度数计算
-        assert_eq!(graph.out_degree(v1), 2);
-        assert_eq!(graph.in_degree(v1), 0);
-        assert_eq!(graph.out_degree(v2), 1);
-        assert_eq!(graph.in_degree(v2), 1);
-        assert_eq!(graph.out_degree(v3), 0);
-        assert_eq!(graph.in_degree(v3), 2);
+        // 数量限制作用于全图结果
+        let limited = found(&EdgeSearch::label(EdgeLabel::Created).with_limit(2));
+        assert_eq!(limited.len(), 2);
+        assert!(limited.is_subset(&expected(Some(EdgeLabel::Created), None)));
+        assert_eq!(graph.global_edges(&EdgeSearch::scan().with_limit(3)).count(), 3);
+        assert_eq!(graph.global_edges(&EdgeSearch::scan().with_limit(0)).count(), 0);
+    }
 
-        // 测试边查询
-        assert!(graph.has_edge(v1, v2));
-        assert!(!graph.has_edge(v2, v1));
-        assert!(graph.has_edge(v1, v3));
+    #[test]
+    fn test_add_vertex_connected() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
 
-        // 测试遍历
-        let vertices: Vec<_> = graph.vertices(&VertexSearch::scan()).collect();
-        assert_eq!(vertices.len(), 3);
+        let (c, edges) = graph.add_vertex_connected(3, [(a, 10), (b, 20)], [(b, 30)]).unwrap();
+        assert_eq!(graph.vertex_weight(c), Some(&3));
+        let wired: Vec<_> = edges
+            .iter()
+            .map(|&e| (graph.edge_endpoints(e).unwrap(), *graph.edge_weight(e).unwrap()))
+            .collect();
+        assert_eq!(wired, vec![((c, a), 10), ((c, b), 20), ((b, c), 30)]);
+        assert_eq!((graph.out_degree(c), graph.in_degree(c)), (2, 1));
+
+        // 任一端点不存在时图保持不变
+        graph.remove_vertex(a);
+        let version = graph.version();
+        assert_eq!(
+            graph.add_vertex_connected(4, [(b, 40)], [(a, 50)]),
+            Err(GraphError::VertexNotFound(a))
+        );
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (2, 2));
+        assert_eq!(graph.version(), version);
+
+        // 没有边时等同于 add_vertex
+        let (d, edges) = graph.add_vertex_connected(5, [], []).unwrap();
+        assert!(edges.is_empty());
+        assert_eq!(graph.degree(d), 0);
+        assert_eq!(graph.validate(), Ok(()));
     }
 
     #[test]
-    fn test_edge_removal() {
-        let mut graph = SlotMapGraph::<TestVertex, TestEdge>::new();
+    fn test_clear_keep_capacity() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let fill = |graph: &mut SlotMapGraph<u32, u32>| {
+            let vertices: Vec<_> = (0..200).map(|i| graph.add_vertex(i)).collect();
+            for i in 0..1000 {
+                graph.add_edge(vertices[i % 200], vertices[(i * 13 + 7) % 200], i as u32);
+            }
+        };
+        fill(&mut graph);
+        let capacities = |graph: &SlotMapGraph<u32, u32>| {
+            (graph.vertex_capacity(), graph.edge_capacity(), graph.edges.adjacency_capacity())
+        };
+        let before = capacities(&graph);
 
-        let v1 = graph.add_vertex(TestVertex {
-            name: "A".to_string(),
-            _value: 1,
-        });
-        let v2 = graph.add_vertex(TestVertex {
-            name: "B".to_string(),
-            _value: 2,
-        });
+        graph.clear_keep_capacity();
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (0, 0));
+        assert_eq!(capacities(&graph), before);
 
-        let e1 = graph.add_edge(v1, v2, TestEdge { weight: 1.5 });
-        assert!(graph.has_edge(v1, v2));
+        // 重新填充到相同规模不会扩容
+        fill(&mut graph);
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (200, 1000));
+        assert_eq!(capacities(&graph), before);
+        assert_eq!(graph.validate(), Ok(()));
+    }
 
-        // 删除边
-        let removed_edge = graph.remove_edge(e1);
-        assert!(removed_edge.is_some());
-        assert!(!graph.has_edge(v1, v2));
+    #[test]
+    fn test_clear_keep_capacity_resets_vertex_query() {
+        let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let old: Vec<_> = (0..3).map(|i| graph.add_vertex(i)).collect();
+        graph.index_vertex_string(old[0], "root");
+        graph.index_vertex_int(old[1], 7);
+        graph.vertex_query_mut().insert_float(0.5, old[2]);
+
+        graph.clear_keep_capacity();
+        assert_eq!(graph.vertex_query().query_string("root").count(), 0);
+
+        // 重新填充后，旧索引项不会出现，也不会匹配重用槽位的新顶点
+        let new: Vec<_> = (0..3).map(|i| graph.add_vertex(i)).collect();
+        graph.index_vertex_string(new[2], "root");
+        assert_eq!(graph.vertex_query().query_string("root").collect::<Vec<_>>(), vec![new[2]]);
+        assert_eq!(graph.vertex_query().query_int(7).count(), 0);
+        assert_eq!(graph.vertex_query().query_float(0.5).count(), 0);
+        assert_eq!(graph.vertex_query().range_int(0..10).count(), 0);
     }
 
     #[test]
-    fn test_vertex_removal() {
-        let mut graph = SlotMapGraph::<TestVertex, TestEdge>::new();
+    fn test_vertices_range_ordered() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let ages = [40, 25, 60, 25, 90, 60, 33];
+        let ids: Vec<_> = ages
+            .iter()
+            .map(|&age| {
+                let id = graph.add_vertex(age);
+                graph.index_vertex_int(id, age as i64);
+                id
+            })
+            .collect();
+        // 已删除的顶点即使仍在索引中也不会出现
+        graph.remove_vertex(ids[4]);
 
-        let v1 = graph.add_vertex(TestVertex {
-            name: "A".to_string(),
-            _value: 1,
-        });
-        let v2 = graph.add_vertex(TestVertex {
-            name: "B".to_string(),
-            _value: 2,
-        });
-        let v3 = graph.add_vertex(TestVertex {
-            name: "C".to_string(),
-            _value: 3,
-        });
+        let ascending: Vec<_> = graph
+            .vertices_range_ordered(0..100, KeyOrder::Ascending)
+            .map(|(age, vertex)| (age, vertex.id()))
+            .collect();
+        let mut ties = [ids[1], ids[3]];
+        ties.sort_by_key(VertexId::as_ffi);
+        let mut sixties = [ids[2], ids[5]];
+        sixties.sort_by_key(VertexId::as_ffi);
+        assert_eq!(
+            ascending,
+            vec![(25, ties[0]), (25, ties[1]), (33, ids[6]), (40, ids[0]), (60, sixties[0]), (60, sixties[1])]
+        );
 
-        let e1 = graph.add_edge(v1, v2, TestEdge { weight: 1.5 });
-        let e2 = graph.add_edge(v1, v3, TestEdge { weight: 2.5 });
+        // 先排序后截取：前三个最大的键
+        let top: Vec<_> = graph
+            .vertices_range_ordered(0..100, KeyOrder::Descending)
+            .take(3)
+            .map(|(age, vertex)| (age, vertex.id()))
+            .collect();
+        assert_eq!(top, vec![(60, sixties[1]), (60, sixties[0]), (40, ids[0])]);
+        assert_eq!(graph.vertices_range_ordered(26..40, KeyOrder::Descending).count(), 1);
+    }
 
-        // 删除顶点v1应该同时删除相关的边
-        let removed_vertex = graph.remove_vertex(v1);
-        assert!(removed_vertex.is_some());
-        assert_eq!(removed_vertex.unwrap().name, "A");
+    #[test]
+    fn test_spatial_index_follows_removal() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let id = graph.add_vertex(0);
+        assert!(!graph.index_vertex_position(id, [0.0, 0.0]));
+        graph.enable_spatial_index(5.0);
 
-        // 验证边被删除
-        assert!(!graph.contains_vertex(v1));
-        assert!(!graph.contains_edge(e1));
-        assert!(!graph.contains_edge(e2));
+        let mut points = Vec::new();
+        for i in 0..100u32 {
+            let id = graph.add_vertex(i);
+            let point = [(i % 10) as f32 * 3.0, (i / 10) as f32 * 3.0];
+            assert!(graph.index_vertex_position(id, point));
+            points.push((id, point));
+        }
+        for (id, _) in points.iter().step_by(3) {
+            graph.remove_vertex(*id);
+        }
+        // 槽位重用后的新顶点不会继承旧坐标
+        let reused = graph.add_vertex(999);
+        assert_eq!(graph.spatial_index().unwrap().position(reused), None);
 
-        // 验证其他顶点还在
-        assert!(graph.contains_vertex(v2));
-        assert!(graph.contains_vertex(v3));
+        let center = [13.0, 13.0];
+        let mut expected: Vec<_> = points
+            .iter()
+            .filter(|(id, [x, y])| graph.contains_vertex(*id) && (x - center[0]).hypot(y - center[1]) <= 7.0)
+            .map(|(id, _)| id.as_ffi())
+            .collect();
+        let mut found: Vec<_> = graph.vertices_within(center, 7.0).map(|id| id.as_ffi()).collect();
+        expected.sort_unstable();
+        found.sort_unstable();
+        assert_eq!(found, expected);
+        assert!(graph.nearest_vertices(center, 100).iter().all(|(id, _)| graph.contains_vertex(*id)));
+        assert_eq!(graph.nearest_vertices(center, 100).len(), 66);
+
+        // 改变格子边长保留已有坐标
+        graph.enable_spatial_index(1.0);
+        let mut rebuilt: Vec<_> = graph.vertices_within(center, 7.0).map(|id| id.as_ffi()).collect();
+        rebuilt.sort_unstable();
+        assert_eq!(rebuilt, expected);
+
+        SupportsClear::clear(&mut graph);
+        assert!(graph.spatial_index().unwrap().is_empty());
+        graph.disable_spatial_index();
+        assert_eq!(graph.vertices_within(center, 100.0).count(), 0);
+    }
+
+    #[test]
+    fn test_mutual_edges() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let v: Vec<_> = (0..5).map(|_| graph.add_vertex(())).collect();
+        let ab = graph.add_edge(v[0], v[1], 0);
+        let cd = graph.add_edge(v[2], v[3], 1);
+        let ba = graph.add_edge(v[1], v[0], 2);
+        let dc = graph.add_edge(v[3], v[2], 3);
+        // 单向边和自环
+        let one_way = graph.add_edge(v[0], v[2], 4);
+        graph.add_edge(v[3], v[4], 5);
+        graph.add_edge(v[4], v[0], 6);
+        let self_loop = graph.add_edge(v[4], v[4], 7);
+
+        let mutual: Vec<_> = graph.mutual_edges().collect();
+        assert_eq!(mutual.len(), 2);
+        let as_sets: Vec<HashSet<_>> = mutual.iter().map(|&(x, y)| HashSet::from([x, y])).collect();
+        assert!(as_sets.contains(&HashSet::from([ab, ba])));
+        assert!(as_sets.contains(&HashSet::from([cd, dc])));
+        for &(first, second) in &mutual {
+            let (from, to) = graph.edges.endpoints(first).unwrap();
+            assert_eq!(graph.edges.endpoints(second), Some((to, from)));
+        }
+        assert!(graph.is_mutual(ab) && graph.is_mutual(dc));
+        assert!(!graph.is_mutual(one_way) && !graph.is_mutual(self_loop));
+
+        // 平行边按 ID 升序配对，多出的边不参与
+        let ab2 = graph.add_edge(v[0], v[1], 8);
+        let ab3 = graph.add_edge(v[0], v[1], 9);
+        let ba2 = graph.add_edge(v[1], v[0], 10);
+        let mut forward = [ab, ab2, ab3];
+        forward.sort_by_key(EdgeId::as_ffi);
+        let mut backward = [ba, ba2];
+        backward.sort_by_key(EdgeId::as_ffi);
+        let (small, _) = graph.edges.get_connection(ab).unwrap().sorted_endpoints();
+        let expected: HashSet<_> = if small == v[0] {
+            forward.iter().copied().zip(backward).collect()
+        } else {
+            backward.iter().copied().zip(forward).collect()
+        };
+        let pairs: Vec<_> = graph.mutual_edges().filter(|(x, _)| forward.contains(x) || backward.contains(x)).collect();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs.into_iter().collect::<HashSet<_>>(), expected);
+        assert_eq!(graph.mutual_edges().count(), 3);
+    }
+
+    /// 候选 ID 中混有已删除元素时迭代器的行为
+    mod stale_ids {
+        use super::*;
+
+        type G = SlotMapGraph<u32, u32>;
+
+        /// 10 个顶点和 10 条自环，删除下标在 `removed` 中的元素，返回删除前的全部 ID
+        fn graph_with_removed(removed: &[usize]) -> (G, Vec<VertexId>, Vec<EdgeId>) {
+            let mut graph = G::new();
+            let vertices: Vec<_> = (0..10).map(|i| graph.add_vertex(i)).collect();
+            let edges: Vec<_> = vertices.iter().map(|&v| graph.add_edge(v, v, 0)).collect();
+            for &i in removed {
+                graph.remove_edge(edges[i]);
+                graph.remove_vertex(vertices[i]);
+            }
+            // 重用被删除的槽位，旧 ID 仍然失效
+            for _ in removed {
+                graph.add_vertex(99);
+            }
+            (graph, vertices, edges)
+        }
+
+        fn vertex_iter<'graph>(
+            graph: &'graph G,
+            keys: impl Iterator<Item = VertexId> + 'graph,
+            limit: usize,
+        ) -> VertexIter<'static, 'graph, u32, u32, DefaultKey> {
+            VertexIter {
+                _phantom: PhantomData,
+                vertices: &graph.vertices,
+                keys: smallbox!(keys),
+                prefilter: None,
+                count: 0,
+                limit,
+            }
+        }
+
+        fn edge_iter(graph: &G, keys: Vec<EdgeId>, limit: usize) -> EdgeIter<'static, '_, u32, u32, DefaultKey> {
+            EdgeIter {
+                _phantom: PhantomData,
+                edges: &graph.edges,
+                keys: keys.into_iter(),
+                count: 0,
+                limit,
+            }
+        }
+
+        #[test]
+        fn test_limit_against_live_count() {
+            // 交错的失效 ID：0、3、4、9 被删除，剩 6 个存活
+            let removed = [0, 3, 4, 9];
+            let (graph, vertices, edges) = graph_with_removed(&removed);
+            let live: Vec<_> = (0..10).filter(|i| !removed.contains(i)).collect();
+
+            for limit in [0, 1, 5, 6, 7, usize::MAX] {
+                let expected: Vec<_> = live.iter().take(limit).map(|&i| vertices[i]).collect();
+                let ids: Vec<_> = vertex_iter(&graph, vertices.clone().into_iter(), limit).map(|v| v.id()).collect();
+                assert_eq!(ids, expected, "vertex limit {limit}");
+
+                let expected: Vec<_> = live.iter().take(limit).map(|&i| edges[i]).collect();
+                let ids: Vec<_> = edge_iter(&graph, edges.clone(), limit).map(|e| e.id()).collect();
+                assert_eq!(ids, expected, "edge limit {limit}");
+            }
+        }
+
+        #[test]
+        fn test_all_stale() {
+            let all: Vec<_> = (0..10).collect();
+            let (graph, vertices, edges) = graph_with_removed(&all);
+            assert_eq!(graph.vertex_count(), 10);
+            assert_eq!(vertex_iter(&graph, vertices.into_iter(), 3).count(), 0);
+            assert_eq!(edge_iter(&graph, edges, usize::MAX).count(), 0);
+        }
+
+        #[test]
+        fn test_limit_stops_reading_candidates() {
+            let (graph, vertices, _) = graph_with_removed(&[1]);
+            let read = std::cell::Cell::new(0);
+            let keys = vertices.iter().copied().inspect(|_| read.set(read.get() + 1));
+            let mut iter = vertex_iter(&graph, keys, 2);
+            assert_eq!(iter.size_hint(), (0, Some(2)));
+            assert_eq!(iter.by_ref().count(), 2);
+            // 第二个候选已失效，产生 2 个顶点读取了 3 个候选，之后不再读取
+            assert_eq!(read.get(), 3);
+            assert!(iter.next().is_none());
+            assert_eq!(read.get(), 3);
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+
+        #[test]
+        fn test_fused_after_exhaustion() {
+            let (graph, vertices, edges) = graph_with_removed(&[2]);
+            // 耗尽后会再次产生元素的候选迭代器
+            let mut calls = 0;
+            let restarting = std::iter::from_fn(move || {
+                calls += 1;
+                (calls != 2).then_some(vertices[0])
+            });
+            let mut iter = vertex_iter(&graph, restarting, usize::MAX);
+            assert!(iter.next().is_some());
+            assert!(iter.next().is_none());
+            assert!(iter.next().is_none());
+
+            let mut iter = edge_iter(&graph, edges, usize::MAX);
+            assert_eq!(iter.size_hint(), (0, Some(10)));
+            assert_eq!(iter.by_ref().count(), 9);
+            assert!(iter.next().is_none());
+            assert_eq!(iter.size_hint(), (0, Some(0)));
+        }
+    }
+
+    /// 按相同顺序构建的稠密图：含自环和平行边
+    fn dense_graph(n: u32) -> (SlotMapGraph<u32, u32>, Vec<VertexId>) {
+        let mut graph = SlotMapGraph::new();
+        let ids: Vec<_> = (0..n).map(|i| graph.add_vertex(i)).collect();
+        for i in 0..n {
+            for j in 0..n {
+                if (i * 7 + j * 3) % 4 == 0 {
+                    graph.add_edge(ids[i as usize], ids[j as usize], i * n + j);
+                }
+            }
+            graph.add_edge(ids[i as usize], ids[((i + 1) % n) as usize], i);
+        }
+        (graph, ids)
+    }
+
+    fn assert_same_structure(left: &SlotMapGraph<u32, u32>, right: &SlotMapGraph<u32, u32>) {
+        assert!(left.vertex_ids().eq(right.vertex_ids()));
+        assert!(left.edge_ids().eq(right.edge_ids()));
+        for vertex_id in left.vertex_ids() {
+            assert_eq!(left.outgoing_adjacency(vertex_id), right.outgoing_adjacency(vertex_id));
+            assert_eq!(left.incoming_adjacency(vertex_id), right.incoming_adjacency(vertex_id));
+            assert_eq!(left.degree(vertex_id), right.degree(vertex_id));
+        }
+        // 标签索引中恰好是剩余的边
+        assert_eq!(left.edge_label_counts().collect::<Vec<_>>(), vec![((), left.edge_count())]);
+        assert!(left.validate().is_ok());
+    }
+
+    #[test]
+    fn test_remove_vertices_matches_sequential_removal() {
+        let n = 40;
+        // 一半顶点（扫描全部边）与聚集的少量顶点（遍历邻接表）
+        let half: Vec<usize> = (0..n as usize).filter(|i| i.is_multiple_of(2)).collect();
+        let cluster: Vec<usize> = vec![3, 4, 5, 6];
+        for victims in [half, cluster] {
+            let (mut batched, ids) = dense_graph(n);
+            let (mut sequential, _) = dense_graph(n);
+            let victim_ids: Vec<_> = victims.iter().map(|&i| ids[i]).collect();
+
+            let removed = batched.remove_vertices(&victim_ids);
+            let expected: Vec<_> = victim_ids
+                .iter()
+                .map(|&id| (id, SupportsElementRemoval::remove_vertex(&mut sequential, id).unwrap()))
+                .collect();
+            assert_eq!(removed, expected);
+            assert_same_structure(&batched, &sequential);
+            assert_eq!(batched.version(), sequential.version());
+        }
+    }
+
+    #[test]
+    fn test_remove_vertices_skips_missing_pinned_and_duplicates() {
+        let (mut graph, ids) = dense_graph(6);
+        graph.pin_vertex(ids[1]);
+        let gone = ids[5];
+        graph.remove_vertex(gone);
+        let edges = graph.edge_count();
+        let incident = graph.degree(ids[0]);
+
+        let removed = graph.remove_vertices(&[ids[0], ids[1], gone, ids[0]]);
+        assert_eq!(removed, vec![(ids[0], 0)]);
+        assert_eq!(graph.edge_count(), edges - incident);
+        assert!(graph.contains_vertex(ids[1]));
+        assert!(graph.remove_vertices(&[]).is_empty());
+        assert!(graph.validate().is_ok());
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * # EdgeInfo - 边连接信息
  *
  * 存储边的起点和终点顶点ID，用于维护图的结构信息。
@@ -26,14 +26,14 @@
  * ## 使用场景
  *
  * - **边连接查询**: 快速获取边的起点和终点
- * **图遍历**: 实现基于连接信息的邻接表遍历
- * **数据一致性**: 确保边与顶点之间的连接关系
- * **图分析**: 支持度数计算、连通性分析等
+ * - **图遍历**: 实现基于连接信息的邻接表遍历
+ * - **数据一致性**: 确保边与顶点之间的连接关系
+ * - **图分析**: 支持度数计算、连通性分析等
  *
  * ## 使用示例
  *
  * ```rust
- * use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+ * use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
  * use pi_slotmap::DefaultKey;
  *
  * let edge_id = EdgeId::new(DefaultKey::default());
@@ -56,7 +56,8 @@
  */
 
 use super::{EdgeId, VertexId};
-use pi_slotmap::Key;
+use pi_slotmap::{DefaultKey, Key};
+use std::cmp::Ordering;
 
 /// 边连接信息，存储边及其起点和终点顶点
 ///
@@ -77,7 +78,7 @@ use pi_slotmap::Key;
 /// # Examples
 ///
 /// ```rust
-/// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+/// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
 /// use pi_slotmap::DefaultKey;
 ///
 /// // 创建顶点和边ID
@@ -98,13 +99,13 @@ use pi_slotmap::Key;
 /// assert!(edge_info.connects_to(vertex_b));
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct EdgeInfo {
-    edge_id: EdgeId,
-    from: VertexId,
-    to: VertexId,
+pub struct EdgeInfo<K: Key = DefaultKey> {
+    edge_id: EdgeId<K>,
+    from: VertexId<K>,
+    to: VertexId<K>,
 }
 
-impl EdgeInfo {
+impl<K: Key> EdgeInfo<K> {
     /// 创建新的边连接信息
     ///
     /// # Arguments
@@ -116,7 +117,7 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
     /// use pi_slotmap::DefaultKey;
     ///
     /// let edge_id = EdgeId::new(DefaultKey::default());
@@ -126,7 +127,7 @@ impl EdgeInfo {
     /// let edge_info = EdgeInfo::new(edge_id, from, to);
     /// ```
     #[inline]
-    pub const fn new(edge_id: EdgeId, from: VertexId, to: VertexId) -> Self {
+    pub const fn new(edge_id: EdgeId<K>, from: VertexId<K>, to: VertexId<K>) -> Self {
         Self { edge_id, from, to }
     }
 
@@ -135,7 +136,7 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
     /// use pi_slotmap::DefaultKey;
     ///
     /// let edge_id = EdgeId::new(DefaultKey::default());
@@ -143,7 +144,7 @@ impl EdgeInfo {
     /// assert_eq!(edge_info.edge_id(), edge_id);
     /// ```
     #[inline]
-    pub const fn edge_id(&self) -> EdgeId {
+    pub const fn edge_id(&self) -> EdgeId<K> {
         self.edge_id
     }
 
@@ -152,15 +153,16 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
-    /// use pi_slotmap::DefaultKey;
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap::{DefaultKey, SlotMap};
     ///
-    /// let from = VertexId::new(DefaultKey::default());
+    /// let mut keys = SlotMap::<DefaultKey, ()>::new();
+    /// let from = VertexId::new(keys.insert(()));
     /// let edge_info = EdgeInfo::new(EdgeId::default(), from, VertexId::default());
     /// assert_eq!(edge_info.from(), from);
     /// ```
     #[inline]
-    pub const fn from(&self) -> VertexId {
+    pub const fn from(&self) -> VertexId<K> {
         self.from
     }
 
@@ -169,15 +171,16 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
-    /// use pi_slotmap::DefaultKey;
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap::{DefaultKey, SlotMap};
     ///
-    /// let to = VertexId::new(DefaultKey::default());
+    /// let mut keys = SlotMap::<DefaultKey, ()>::new();
+    /// let to = VertexId::new(keys.insert(()));
     /// let edge_info = EdgeInfo::new(EdgeId::default(), VertexId::default(), to);
     /// assert_eq!(edge_info.to(), to);
     /// ```
     #[inline]
-    pub const fn to(&self) -> VertexId {
+    pub const fn to(&self) -> VertexId<K> {
         self.to
     }
 
@@ -194,18 +197,19 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
-    /// use pi_slotmap::DefaultKey;
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap::{DefaultKey, SlotMap};
     ///
-    /// let from = VertexId::new(DefaultKey::default());
-    /// let to = VertexId::new(DefaultKey::default());
+    /// let mut keys = SlotMap::<DefaultKey, ()>::new();
+    /// let from = VertexId::new(keys.insert(()));
+    /// let to = VertexId::new(keys.insert(()));
     /// let edge_info = EdgeInfo::new(EdgeId::default(), from, to);
     ///
     /// assert!(edge_info.connects_from(from));
     /// assert!(!edge_info.connects_from(to));
     /// ```
     #[inline]
-    pub fn connects_from(&self, vertex_id: VertexId) -> bool {
+    pub fn connects_from(&self, vertex_id: VertexId<K>) -> bool {
         self.from == vertex_id
     }
 
@@ -222,18 +226,19 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
-    /// use pi_slotmap::DefaultKey;
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap::{DefaultKey, SlotMap};
     ///
-    /// let from = VertexId::new(DefaultKey::default());
-    /// let to = VertexId::new(DefaultKey::default());
+    /// let mut keys = SlotMap::<DefaultKey, ()>::new();
+    /// let from = VertexId::new(keys.insert(()));
+    /// let to = VertexId::new(keys.insert(()));
     /// let edge_info = EdgeInfo::new(EdgeId::default(), from, to);
     ///
     /// assert!(!edge_info.connects_to(from));
     /// assert!(edge_info.connects_to(to));
     /// ```
     #[inline]
-    pub fn connects_to(&self, vertex_id: VertexId) -> bool {
+    pub fn connects_to(&self, vertex_id: VertexId<K>) -> bool {
         self.to == vertex_id
     }
 
@@ -251,18 +256,19 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
-    /// use pi_slotmap::DefaultKey;
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap::{DefaultKey, SlotMap};
     ///
-    /// let vertex_a = VertexId::new(DefaultKey::default());
-    /// let vertex_b = VertexId::new(DefaultKey::default());
+    /// let mut keys = SlotMap::<DefaultKey, ()>::new();
+    /// let vertex_a = VertexId::new(keys.insert(()));
+    /// let vertex_b = VertexId::new(keys.insert(()));
     /// let edge_info = EdgeInfo::new(EdgeId::default(), vertex_a, vertex_b);
     ///
     /// assert!(edge_info.connects(vertex_a, vertex_b));
     /// assert!(!edge_info.connects(vertex_b, vertex_a));
     /// ```
     #[inline]
-    pub fn connects(&self, from: VertexId, to: VertexId) -> bool {
+    pub fn connects(&self, from: VertexId<K>, to: VertexId<K>) -> bool {
         self.from == from && self.to == to
     }
 
@@ -279,12 +285,13 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
-    /// use pi_slotmap::DefaultKey;
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap::{DefaultKey, SlotMap};
     ///
-    /// let from = VertexId::new(DefaultKey::default());
-    /// let to = VertexId::new(DefaultKey::default());
-    /// let other = VertexId::new(DefaultKey::default());
+    /// let mut keys = SlotMap::<DefaultKey, ()>::new();
+    /// let from = VertexId::new(keys.insert(()));
+    /// let to = VertexId::new(keys.insert(()));
+    /// let other = VertexId::new(keys.insert(()));
     /// let edge_info = EdgeInfo::new(EdgeId::default(), from, to);
     ///
     /// assert!(edge_info.involves(from));
@@ -292,7 +299,7 @@ impl EdgeInfo {
     /// assert!(!edge_info.involves(other));
     /// ```
     #[inline]
-    pub fn involves(&self, vertex_id: VertexId) -> bool {
+    pub fn involves(&self, vertex_id: VertexId<K>) -> bool {
         self.from == vertex_id || self.to == vertex_id
     }
 
@@ -305,7 +312,7 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
     /// use pi_slotmap::DefaultKey;
     ///
     /// let from = VertexId::new(DefaultKey::default());
@@ -335,7 +342,7 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
     /// use pi_slotmap::DefaultKey;
     ///
     /// let from = VertexId::new(DefaultKey::default());
@@ -347,7 +354,7 @@ impl EdgeInfo {
     /// assert_eq!(end, to);
     /// ```
     #[inline]
-    pub const fn endpoints(&self) -> (VertexId, VertexId) {
+    pub const fn endpoints(&self) -> (VertexId<K>, VertexId<K>) {
         (self.from, self.to)
     }
 
@@ -362,7 +369,7 @@ impl EdgeInfo {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
     /// use pi_slotmap::DefaultKey;
     ///
     /// let vertex_a = VertexId::new(DefaultKey::default());
@@ -374,14 +381,52 @@ impl EdgeInfo {
     /// assert_eq!(max, vertex_b);
     /// ```
     #[inline]
-    pub fn sorted_endpoints(&self) -> (VertexId, VertexId) {
-        // 注意：这里需要比较DefaultKey，但由于VertexId是newtype，我们需要转换为底层键进行比较
-        if self.from.key().data().as_ffi() <= self.to.key().data().as_ffi() {
+    pub fn sorted_endpoints(&self) -> (VertexId<K>, VertexId<K>) {
+        if self.from <= self.to {
             (self.from, self.to)
         } else {
             (self.to, self.from)
         }
     }
+
+    /// 无向去重使用的键：排序后端点的 [`as_ffi`](VertexId::as_ffi) 值
+    ///
+    /// 连接相同两个顶点的边，无论方向如何，键都相同。
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pi_slotmap::DefaultKey;
+    /// use pi_slotmap_graph::id::{EdgeInfo, EdgeId, VertexId};
+    ///
+    /// let a = VertexId::<DefaultKey>::from_ffi(1);
+    /// let b = VertexId::from_ffi(2);
+    /// let forward = EdgeInfo::new(EdgeId::from_ffi(3), a, b);
+    /// let backward = EdgeInfo::new(EdgeId::from_ffi(4), b, a);
+    ///
+    /// assert_eq!(forward.canonical_key(), (1, 2));
+    /// assert_eq!(forward.canonical_key(), backward.canonical_key());
+    /// ```
+    #[inline]
+    pub fn canonical_key(&self) -> (u64, u64) {
+        let (min, max) = self.sorted_endpoints();
+        (min.as_ffi(), max.as_ffi())
+    }
+}
+
+/// 依次按起点、终点和边ID排序，ID之间的顺序见 [`VertexId`] 的 `Ord` 实现
+impl<K: Key> PartialOrd for EdgeInfo<K> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Key> Ord for EdgeInfo<K> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.from, self.to, self.edge_id).cmp(&(other.from, other.to, other.edge_id))
+    }
 }
 
 #[cfg(test)]
@@ -519,4 +564,35 @@ mod tests {
 
         assert!(set.contains(&edge_info));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_edge_info_ordering() {
+        use std::collections::BTreeSet;
+
+        let keys = create_test_keys(4);
+        let (a, b, c) = (VertexId::new(keys[0]), VertexId::new(keys[1]), VertexId::new(keys[2]));
+        let (e1, e2) = (EdgeId::new(keys[0]), EdgeId::new(keys[3]));
+
+        // 先比较起点，再比较终点，最后比较边ID
+        let infos = [
+            EdgeInfo::new(e1, b, a),
+            EdgeInfo::new(e2, a, c),
+            EdgeInfo::new(e2, a, b),
+            EdgeInfo::new(e1, a, b),
+        ];
+        let set: BTreeSet<_> = infos.iter().copied().collect();
+        let sorted: Vec<_> = set.into_iter().collect();
+        assert_eq!(sorted, vec![infos[3], infos[2], infos[1], infos[0]]);
+        for x in &infos {
+            for y in &infos {
+                assert_eq!(x.cmp(y) == Ordering::Equal, x == y);
+                assert_eq!(x.cmp(y), y.cmp(x).reverse());
+            }
+        }
+
+        assert_eq!(infos[0].sorted_endpoints(), (a, b));
+        assert_eq!(infos[0].canonical_key(), infos[3].canonical_key());
+        assert_eq!(infos[0].canonical_key(), (a.as_ffi(), b.as_ffi()));
+        assert_ne!(infos[1].canonical_key(), infos[2].canonical_key());
+    }
+}
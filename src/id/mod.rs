@@ -1,7 +1,8 @@
-/**
+/*!
  * # 标识符系统 (ID System)
  *
- * 本模块提供了类型安全的标识符系统，基于 `pi_slotmap::DefaultKey` 实现。
+ * 本模块提供了类型安全的标识符系统，基于 `pi_slotmap` 的键类型实现，
+ * 键类型默认为 `DefaultKey`，也可替换为 `new_key_type!` 定义的自定义键。
  *
  * ## 组件
  *
@@ -19,7 +20,7 @@
  * ## 使用示例
  *
  * ```rust
- * use slotmap_graph::id::{VertexId, EdgeId};
+ * use pi_slotmap_graph::id::{VertexId, EdgeId};
  * use pi_slotmap::DefaultKey;
  *
  * // 创建ID
@@ -29,9 +30,14 @@
  * // 获取底层键
  * let key = vertex_id.key();
  *
+ * assert_eq!(key, DefaultKey::default());
+ *
  * // 与ElementId转换
  * use graph_api_lib::ElementId;
- * let element_id: ElementId<_> = vertex_id.into();
+ * use pi_slotmap_graph::SlotMapGraph;
+ * let element_id: ElementId<SlotMapGraph<u32, u32>> = vertex_id.into();
+ * assert_eq!(element_id, ElementId::Vertex(vertex_id));
+ * # let _ = edge_id;
  * ```
  */
 
@@ -45,23 +51,26 @@ pub use edge_id::EdgeId;
 pub use edge_info::EdgeInfo;
 
 use graph_api_lib::ElementId;
+use pi_slotmap::Key;
 
 /// 为VertexId实现到ElementId的转换
-impl<Graph> From<VertexId> for ElementId<Graph>
+impl<Graph, K> From<VertexId<K>> for ElementId<Graph>
 where
-    Graph: graph_api_lib::Graph<VertexId = VertexId>,
+    K: Key,
+    Graph: graph_api_lib::Graph<VertexId = VertexId<K>>,
 {
-    fn from(id: VertexId) -> Self {
+    fn from(id: VertexId<K>) -> Self {
         ElementId::Vertex(id)
     }
 }
 
 /// 为EdgeId实现到ElementId的转换
-impl<Graph> From<EdgeId> for ElementId<Graph>
+impl<Graph, K> From<EdgeId<K>> for ElementId<Graph>
 where
-    Graph: graph_api_lib::Graph<EdgeId = EdgeId>,
+    K: Key,
+    Graph: graph_api_lib::Graph<EdgeId = EdgeId<K>>,
 {
-    fn from(id: EdgeId) -> Self {
+    fn from(id: EdgeId<K>) -> Self {
         ElementId::Edge(id)
     }
 }
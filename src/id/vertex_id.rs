@@ -1,7 +1,7 @@
-/**
+/*!
  * # VertexId - 顶点标识符
  *
- * 基于 `pi_slotmap::DefaultKey` 的类型安全的顶点标识符实现。
+ * 基于 `pi_slotmap` 键类型（默认为 `DefaultKey`）的类型安全的顶点标识符实现。
  *
  * ## 特性
  *
@@ -13,7 +13,7 @@
  * ## 内部表示
  *
  * ```rust,ignore
- * pub struct VertexId(pub(crate) DefaultKey);
+ * pub struct VertexId<K: Key = DefaultKey>(pub(crate) K);
  * ```
  *
  * 存储为 `DefaultKey` 的newtype包装器，提供类型安全的同时保持零成本抽象。
@@ -21,7 +21,7 @@
  * ## 使用示例
  *
  * ```rust
- * use slotmap_graph::id::VertexId;
+ * use pi_slotmap_graph::id::VertexId;
  * use pi_slotmap::DefaultKey;
  *
  * // 创建顶点ID
@@ -41,18 +41,22 @@
  * ```
  */
 
-use pi_slotmap::{DefaultKey, Key};
+use pi_slotmap::{DefaultKey, Key, KeyData};
+use std::cmp::Ordering;
 use std::fmt;
 
-/// 顶点标识符，基于 `pi_slotmap::DefaultKey` 实现
+/// 顶点标识符，基于 `pi_slotmap` 的键类型实现
+///
+/// 键类型参数 `K` 默认为 `DefaultKey`，也可以使用 `new_key_type!` 定义的自定义键，
+/// 使不同领域的图在类型层面互相隔离。
 ///
 /// 提供类型安全的顶点标识，防止与边ID混淆。
-/// 内部使用 `DefaultKey` 作为底层存储，提供高性能的插入、删除和查找操作。
+/// 内部使用 SlotMap 的键作为底层存储，提供高性能的插入、删除和查找操作。
 ///
 /// # Examples
 ///
 /// ```rust
-/// use slotmap_graph::id::VertexId;
+/// use pi_slotmap_graph::id::VertexId;
 /// use pi_slotmap::DefaultKey;
 ///
 /// // 创建ID
@@ -73,9 +77,9 @@ use std::fmt;
 // - Hash: 支持在HashMap、HashSet等集合中使用
 // - Default: 支持默认值（虽然实际使用中很少用到）
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
-pub struct VertexId(pub(crate) DefaultKey);
+pub struct VertexId<K: Key = DefaultKey>(pub(crate) K);
 
-impl VertexId {
+impl<K: Key> VertexId<K> {
     /// 创建新的顶点ID
     ///
     /// 这是一个包装函数，将底层的 `DefaultKey` 包装成类型安全的 `VertexId`。
@@ -95,7 +99,7 @@ impl VertexId {
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::id::VertexId;
+    /// use pi_slotmap_graph::id::VertexId;
     /// use pi_slotmap::DefaultKey;
     ///
     /// let key = DefaultKey::default();
@@ -108,18 +112,18 @@ impl VertexId {
     /// - **空间复杂度**: O(1) - 8字节存储
     /// - **内存布局**: 与DefaultKey相同，零开销
     #[inline]
-    pub fn new(key: DefaultKey) -> Self {
+    pub fn new(key: K) -> Self {
         Self(key)
     }
 
-    /// 获取底层的 `DefaultKey`
+    /// 获取底层的键
     ///
     /// 用于与底层存储容器交互。
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::VertexId;
+    /// use pi_slotmap_graph::id::VertexId;
     /// use pi_slotmap::DefaultKey;
     ///
     /// let key = DefaultKey::default();
@@ -128,10 +132,28 @@ impl VertexId {
     /// assert_eq!(vertex_id.key(), key);
     /// ```
     #[inline]
-    pub fn key(&self) -> DefaultKey {
+    pub fn key(&self) -> K {
         self.0
     }
 
+    /// 转换为跨 FFI 传递的 64 位整数，高 32 位为版本号，低 32 位为槽位下标
+    ///
+    /// 与 [`from_ffi`](Self::from_ffi) 互逆，在 32 位和 64 位平台上结果相同。
+    /// 版本号使槽位被重用后旧 ID 与新 ID 不相等，需要保留这一区分时不要截断为 `usize`。
+    #[inline]
+    pub fn as_ffi(&self) -> u64 {
+        self.0.data().as_ffi()
+    }
+
+    /// 从 [`as_ffi`](Self::as_ffi) 得到的整数还原ID
+    ///
+    /// 还原只是数值转换，不检查ID在任何图中是否存在；
+    /// 对任意整数调用是安全的，但得到的ID没有意义。
+    #[inline]
+    pub fn from_ffi(raw: u64) -> Self {
+        Self(K::from(KeyData::from_ffi(raw)))
+    }
+
     /// 检查ID是否为默认值
     ///
     /// 默认值表示这是一个未初始化的ID。
@@ -139,27 +161,60 @@ impl VertexId {
     /// # Examples
     ///
     /// ```rust
-    /// use slotmap_graph::id::VertexId;
+    /// use pi_slotmap_graph::id::VertexId;
     ///
-    /// let default_id = VertexId::default();
+    /// let default_id = <VertexId>::default();
     /// assert!(default_id.is_default());
     /// ```
     #[inline]
     pub fn is_default(&self) -> bool {
-        self.0 == DefaultKey::default()
+        self.0 == K::default()
+    }
+}
+
+/// 按 [`as_ffi`](VertexId::as_ffi) 得到的整数排序，即先比较版本号再比较槽位下标
+///
+/// 顺序只用于排序和有序集合，不反映创建先后。
+impl<K: Key> PartialOrd for VertexId<K> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Key> Ord for VertexId<K> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ffi().cmp(&other.as_ffi())
     }
 }
 
-impl fmt::Display for VertexId {
+impl<K: Key> fmt::Display for VertexId<K> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "V{}", self.0.data().as_ffi())
     }
 }
 
-/// 从 `DefaultKey` 转换为 `VertexId`
-impl From<DefaultKey> for VertexId {
+/// 序列化为 [`as_ffi`](VertexId::as_ffi) 得到的整数
+#[cfg(feature = "serde")]
+impl<K: Key> serde::Serialize for VertexId<K> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.as_ffi())
+    }
+}
+
+/// 从 [`as_ffi`](VertexId::as_ffi) 得到的整数反序列化
+#[cfg(feature = "serde")]
+impl<'de, K: Key> serde::Deserialize<'de> for VertexId<K> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <u64 as serde::Deserialize>::deserialize(deserializer).map(Self::from_ffi)
+    }
+}
+
+/// 从底层键转换为 `VertexId`
+impl<K: Key> From<K> for VertexId<K> {
     #[inline]
-    fn from(key: DefaultKey) -> Self {
+    fn from(key: K) -> Self {
         Self(key)
     }
 }
@@ -172,20 +227,22 @@ impl From<VertexId> for DefaultKey {
     }
 }
 
-impl super::IdExt for VertexId {
+impl<K: Key> super::IdExt for VertexId<K> {
     /// 验证VertexId是否有效
     ///
     /// 对于VertexId来说，有效性检查需要在具体的图实例中进行，
     /// 这里返回true表示格式上是有效的。
     #[inline]
     fn is_valid(&self) -> bool {
-        self.0 != DefaultKey::default()
+        self.0 != K::default()
     }
 
     /// 转换为usize用于调试和哈希
+    ///
+    /// 在 32 位平台上只保留 [`as_ffi`](Self::as_ffi) 的低 32 位即槽位下标，版本号被丢弃；
+    /// 需要稳定区分新旧ID时使用 `as_ffi`。
     #[inline]
     fn as_usize(&self) -> usize {
-        // 转换为u64然后转为usize，确保在64位和32位系统上都能工作
         self.0.data().as_ffi() as usize
     }
 }
@@ -282,9 +339,37 @@ mod tests {
         assert_eq!(vertex_id.as_usize(), key.data().as_ffi() as usize);
     }
 
+    #[test]
+    fn test_ffi_round_trip() {
+        for key in create_test_keys(3) {
+            let vertex_id = VertexId::new(key);
+            let raw = vertex_id.as_ffi();
+            assert_eq!(<VertexId>::from_ffi(raw), vertex_id);
+            // as_usize 在 32 位平台上截断为低 32 位，只剩槽位下标
+            assert_eq!(vertex_id.as_usize() as u64, raw & usize::MAX as u64);
+            assert_eq!(raw & 0xffff_ffff, u64::from(key.data().index()));
+        }
+    }
+
     #[test]
     fn test_default_id() {
-        let default_id = VertexId::default();
+        let default_id = <VertexId>::default();
         assert!(default_id.is_default());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_ordering_follows_ffi() {
+        let mut slotmap: SlotMap<DefaultKey, usize> = SlotMap::new();
+        let first = slotmap.insert(0);
+        let second = slotmap.insert(1);
+        slotmap.remove(first);
+        // 重用槽位后版本号更大，排在后面
+        let reused = slotmap.insert(2);
+
+        let mut ids = vec![VertexId::new(reused), VertexId::new(second), VertexId::new(first)];
+        ids.sort();
+        assert_eq!(ids, vec![VertexId::new(first), VertexId::new(second), VertexId::new(reused)]);
+        assert!(ids.windows(2).all(|pair| pair[0].as_ffi() < pair[1].as_ffi()));
+        assert_eq!(ids.iter().max(), Some(&VertexId::new(reused)));
+    }
+}
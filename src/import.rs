@@ -0,0 +1,287 @@
+/*!
+ * # 边列表导入 (Edge List Import)
+ *
+ * 从文本边列表读取图。输入来自外部文件，格式错误或体积异常的输入不能导致 panic，
+ * 也不能让进程按输入声明的规模无限制地分配内存：
+ *
+ * - 每行按 [`ImportLimits::max_line_len`] 限制长度，超长的行不会被完整读入内存
+ * - 声明的顶点数先与 [`ImportLimits::max_vertices`] 比较，通过后才预分配存储
+ * - 顶点和边的数量超过上限时停止读取
+ *
+ * 超出上限返回 [`ImportError::TooLarge`]，格式错误返回 [`ImportError::Parse`]，
+ * 读取失败返回 [`ImportError::Io`]。成功返回的图总能通过 [`validate`](SlotMapGraph::validate)。
+ *
+ * ## 格式
+ *
+ * 每行一条边 `起点 终点 [权重]`，以空白分隔；顶点为非负整数，首次出现时创建，
+ * 顶点数据即该整数；权重为浮点数，省略时为 `1.0`。空行和以 `#` 开头的行被忽略。
+ * 第一条边之前可以有一行 `vertices <数量>` 声明顶点数，仅用于预分配。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::import::{from_edge_list_reader, ImportError, ImportLimits, LimitKind};
+ *
+ * let input = "# 三角形\nvertices 3\n0 1\n1 2 0.5\n2 0\n";
+ * let graph = from_edge_list_reader(input.as_bytes(), &ImportLimits::default()).unwrap();
+ * assert_eq!((graph.vertex_count(), graph.edge_count()), (3, 3));
+ *
+ * let limits = ImportLimits { max_vertices: 2, ..ImportLimits::default() };
+ * match from_edge_list_reader(input.as_bytes(), &limits) {
+ *     Err(ImportError::TooLarge { kind, limit, .. }) => assert_eq!((kind, limit), (LimitKind::Vertices, 2)),
+ *     other => panic!("unexpected {other:?}"),
+ * }
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::Graph;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, Read};
+
+/// 边列表导入得到的图：顶点数据为输入中的顶点编号，边数据为权重
+pub type EdgeListGraph = SlotMapGraph<u64, f64>;
+
+/// 导入时的规模上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportLimits {
+    /// 单行的最大字节数，不含行尾的 `\n`
+    pub max_line_len: usize,
+    /// 最多的顶点数，同时限制声明的顶点数
+    pub max_vertices: usize,
+    /// 最多的边数
+    pub max_edges: usize,
+}
+
+impl Default for ImportLimits {
+    /// 单行 4 KiB，顶点 100 万，边 1000 万
+    fn default() -> Self {
+        Self {
+            max_line_len: 4 * 1024,
+            max_vertices: 1_000_000,
+            max_edges: 10_000_000,
+        }
+    }
+}
+
+/// 被超出的上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    /// [`ImportLimits::max_line_len`]
+    LineLength,
+    /// [`ImportLimits::max_vertices`]
+    Vertices,
+    /// [`ImportLimits::max_edges`]
+    Edges,
+}
+
+/// 导入错误
+#[derive(Debug)]
+pub enum ImportError {
+    /// 读取输入失败
+    Io(io::Error),
+    /// 输入格式错误
+    Parse {
+        /// 出错的行号，从 1 开始
+        line: usize,
+        /// 错误说明
+        message: &'static str,
+    },
+    /// 输入超出了导入上限
+    TooLarge {
+        /// 被超出的上限
+        kind: LimitKind,
+        /// 上限的值
+        limit: usize,
+        /// 超出上限的行号，从 1 开始
+        line: usize,
+    },
+}
+
+impl Display for ImportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io(err) => write!(f, "Failed to read input: {}", err),
+            ImportError::Parse { line, message } => write!(f, "Line {}: {}", line, message),
+            ImportError::TooLarge { kind, limit, line } => {
+                write!(f, "Line {}: input exceeds the {:?} limit of {}", line, kind, limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ImportError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImportError {
+    fn from(err: io::Error) -> Self {
+        ImportError::Io(err)
+    }
+}
+
+/// 从文本边列表读取图，格式见[模块文档](self)
+pub fn from_edge_list_reader<R: BufRead>(mut reader: R, limits: &ImportLimits) -> Result<EdgeListGraph, ImportError> {
+    let mut graph = EdgeListGraph::new();
+    let mut ids: HashMap<u64, VertexId> = HashMap::new();
+    let mut buf = Vec::new();
+    let mut line = 0;
+    let mut seen_edge = false;
+
+    loop {
+        buf.clear();
+        // 最多读入上限加一个字节，足以判断该行是否超长
+        let read = (&mut reader).take(limits.max_line_len as u64 + 1).read_until(b'\n', &mut buf)?;
+        if read == 0 {
+            break;
+        }
+        line += 1;
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+        }
+        if buf.len() > limits.max_line_len {
+            return Err(ImportError::TooLarge { kind: LimitKind::LineLength, limit: limits.max_line_len, line });
+        }
+        let text = std::str::from_utf8(&buf).map_err(|_| ImportError::Parse { line, message: "invalid UTF-8" })?;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = text.split_whitespace();
+        let first = fields.next().unwrap_or_default();
+        if first == "vertices" {
+            if seen_edge {
+                return Err(ImportError::Parse { line, message: "`vertices` must precede all edges" });
+            }
+            let declared = match (fields.next().map(str::parse::<u64>), fields.next()) {
+                (Some(Ok(declared)), None) => declared,
+                _ => return Err(ImportError::Parse { line, message: "expected `vertices <count>`" }),
+            };
+            // 先检查声明的数量，再按它预分配
+            if declared > limits.max_vertices as u64 {
+                return Err(ImportError::TooLarge { kind: LimitKind::Vertices, limit: limits.max_vertices, line });
+            }
+            // 第一条边之前图还是空的，可以直接换成预分配的图
+            graph = EdgeListGraph::with_capacity_and_key(declared as usize, 0);
+            ids.reserve(declared as usize);
+            continue;
+        }
+
+        let to = fields.next().map(str::parse::<u64>);
+        let (from, to, weight) = match (first.parse::<u64>(), to, fields.next(), fields.next()) {
+            (Ok(from), Some(Ok(to)), weight, None) => (from, to, weight),
+            _ => return Err(ImportError::Parse { line, message: "expected `<from> <to> [weight]`" }),
+        };
+        let weight = match weight {
+            Some(weight) => {
+                weight.parse::<f64>().map_err(|_| ImportError::Parse { line, message: "invalid edge weight" })?
+            }
+            None => 1.0,
+        };
+        if graph.edge_count() >= limits.max_edges {
+            return Err(ImportError::TooLarge { kind: LimitKind::Edges, limit: limits.max_edges, line });
+        }
+        seen_edge = true;
+
+        let from = vertex(&mut graph, &mut ids, from, limits, line)?;
+        let to = vertex(&mut graph, &mut ids, to, limits, line)?;
+        graph.add_edge(from, to, weight);
+    }
+    Ok(graph)
+}
+
+/// 编号对应的顶点，首次出现时创建
+fn vertex(
+    graph: &mut EdgeListGraph,
+    ids: &mut HashMap<u64, VertexId>,
+    external: u64,
+    limits: &ImportLimits,
+    line: usize,
+) -> Result<VertexId, ImportError> {
+    if let Some(&vertex_id) = ids.get(&external) {
+        return Ok(vertex_id);
+    }
+    if ids.len() >= limits.max_vertices {
+        return Err(ImportError::TooLarge { kind: LimitKind::Vertices, limit: limits.max_vertices, line });
+    }
+    let vertex_id = graph.add_vertex(external);
+    ids.insert(external, vertex_id);
+    Ok(vertex_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(input: &str, limits: &ImportLimits) -> Result<EdgeListGraph, ImportError> {
+        from_edge_list_reader(input.as_bytes(), limits)
+    }
+
+    fn too_large(result: Result<EdgeListGraph, ImportError>) -> (LimitKind, usize, usize) {
+        match result {
+            Err(ImportError::TooLarge { kind, limit, line }) => (kind, limit, line),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_import_edge_list() {
+        let input = "# comment\n\nvertices 4\n0 1\r\n1 2 0.5\n2 2 -3\n7 0";
+        let graph = import(input, &ImportLimits::default()).unwrap();
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (4, 4));
+        let weights: Vec<_> = graph.all_edges().map(|(_, weight, ..)| *weight).collect();
+        assert_eq!(weights, vec![1.0, 0.5, -3.0, 1.0]);
+        let mut vertices: Vec<_> = graph.all_vertices().map(|(_, external)| *external).collect();
+        vertices.sort();
+        assert_eq!(vertices, vec![0, 1, 2, 7]);
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_import_rejects_malformed_lines() {
+        for (input, line) in [
+            ("0 1\nvertices 3\n", 2),
+            ("vertices\n", 1),
+            ("vertices -1\n", 1),
+            ("0\n", 1),
+            ("0 1 2 3\n", 1),
+            ("0 x\n", 1),
+            ("\n0 1 heavy\n", 2),
+        ] {
+            match import(input, &ImportLimits::default()) {
+                Err(ImportError::Parse { line: actual, .. }) => assert_eq!(actual, line, "{input:?}"),
+                other => panic!("unexpected {other:?} for {input:?}"),
+            }
+        }
+        let invalid_utf8: &[u8] = b"0 1\n\xff\xfe\n";
+        assert!(matches!(
+            from_edge_list_reader(invalid_utf8, &ImportLimits::default()),
+            Err(ImportError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_import_enforces_limits() {
+        let limits = ImportLimits { max_line_len: 8, max_vertices: 3, max_edges: 2 };
+        assert_eq!(too_large(import("0 1\n1 2 0.123456\n", &limits)), (LimitKind::LineLength, 8, 2));
+        // 正好达到行长上限时不报错
+        assert!(import("0 1 1.25\n", &limits).is_ok());
+        // 声明的顶点数超限时不做预分配
+        let few_vertices = ImportLimits { max_vertices: 3, ..ImportLimits::default() };
+        assert_eq!(too_large(import("vertices 1000000000000\n", &few_vertices)), (LimitKind::Vertices, 3, 1));
+        assert_eq!(too_large(import("0 1\n2 3\n", &limits)), (LimitKind::Vertices, 3, 2));
+        assert_eq!(too_large(import("0 1\n1 2\n2 0\n", &limits)), (LimitKind::Edges, 2, 3));
+
+        let err = import("0 1\n1 2\n2 0\n", &limits).unwrap_err();
+        assert_eq!(err.to_string(), "Line 3: input exceeds the Edges limit of 2");
+    }
+}
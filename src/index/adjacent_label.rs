@@ -0,0 +1,203 @@
+/*!
+ * # 邻接边标签缓存 (AdjacentLabelCache)
+ *
+ * 为每个顶点维护其关联边的标签计数 `HashMap<Label, u32>`，
+ * 让按标签过滤邻接边时可以在没有匹配边的顶点上立即返回，
+ * 而无需逐条读取边的权重并调用 `label()`。
+ *
+ * ## 计数规则
+ *
+ * - 每条边在起点和终点上各计一次
+ * - 自环只在其顶点上计一次
+ * - 计数为零的标签会被移除，顶点没有任何标签时整个条目会被移除
+ *
+ * ## 失效
+ *
+ * 通过裸 `&mut` 引用修改边权重时无法得知标签是否改变，
+ * 此时调用方应把两个端点标记为失效（[`invalidate`](AdjacentLabelCache::invalidate)），
+ * 失效顶点的查询结果总是"可能存在"，直到缓存被重建。
+ */
+
+use crate::memory::{hash_map_bytes, hash_set_bytes};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// 顶点关联边的标签计数缓存
+///
+/// - `L`: 边标签类型
+/// - `V`: 顶点ID类型
+#[derive(Debug, Clone)]
+pub struct AdjacentLabelCache<L, V> {
+    /// 每个顶点的关联边标签计数
+    counts: HashMap<V, HashMap<L, u32>>,
+    /// 计数不再可信的顶点
+    invalidated: HashSet<V>,
+}
+
+impl<L, V> Default for AdjacentLabelCache<L, V> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+            invalidated: HashSet::new(),
+        }
+    }
+}
+
+impl<L, V> AdjacentLabelCache<L, V>
+where
+    L: Copy + Eq + Hash,
+    V: Copy + Eq + Hash,
+{
+    /// 创建空缓存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一条从 `from` 到 `to`、标签为 `label` 的边
+    pub fn insert(&mut self, from: V, to: V, label: L) {
+        self.increment(from, label);
+        if from != to {
+            self.increment(to, label);
+        }
+    }
+
+    /// 撤销一条边的记录
+    pub fn remove(&mut self, from: V, to: V, label: L) {
+        self.decrement(from, label);
+        if from != to {
+            self.decrement(to, label);
+        }
+    }
+
+    /// 边的标签从 `old` 变为 `new` 时更新计数
+    pub fn relabel(&mut self, from: V, to: V, old: L, new: L) {
+        if old != new {
+            self.remove(from, to, old);
+            self.insert(from, to, new);
+        }
+    }
+
+    /// 把顶点标记为失效，之后对它的查询总是返回"可能存在"
+    pub fn invalidate(&mut self, vertex: V) {
+        self.invalidated.insert(vertex);
+    }
+
+    /// 顶点是否已失效
+    pub fn is_invalidated(&self, vertex: V) -> bool {
+        self.invalidated.contains(&vertex)
+    }
+
+    /// 顶点被删除后丢弃其全部记录
+    pub fn remove_vertex(&mut self, vertex: V) {
+        self.counts.remove(&vertex);
+        self.invalidated.remove(&vertex);
+    }
+
+    /// 顶点上标签为 `label` 的关联边数量，顶点失效时返回 `None`
+    pub fn count(&self, vertex: V, label: L) -> Option<u32> {
+        if self.is_invalidated(vertex) {
+            return None;
+        }
+        Some(
+            self.counts
+                .get(&vertex)
+                .and_then(|labels| labels.get(&label))
+                .copied()
+                .unwrap_or(0),
+        )
+    }
+
+    /// 顶点上每个标签的关联边数量，顶点失效时返回 `None`
+    pub fn labels(&self, vertex: V) -> Option<impl Iterator<Item = (L, u32)> + '_> {
+        if self.is_invalidated(vertex) {
+            return None;
+        }
+        Some(self.counts.get(&vertex).into_iter().flatten().map(|(label, count)| (*label, *count)))
+    }
+
+    /// 顶点上是否可能存在标签为 `label` 的关联边
+    ///
+    /// 返回 `false` 时可以确定不存在匹配的边。
+    #[inline]
+    pub fn may_contain(&self, vertex: V, label: L) -> bool {
+        self.count(vertex, label) != Some(0)
+    }
+
+    /// 清空缓存
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.invalidated.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节
+    pub fn memory_estimate(&self) -> usize {
+        hash_map_bytes(&self.counts)
+            + self.counts.values().map(hash_map_bytes).sum::<usize>()
+            + hash_set_bytes(&self.invalidated)
+    }
+
+    fn increment(&mut self, vertex: V, label: L) {
+        *self.counts.entry(vertex).or_default().entry(label).or_insert(0) += 1;
+    }
+
+    fn decrement(&mut self, vertex: V, label: L) {
+        let Some(labels) = self.counts.get_mut(&vertex) else {
+            return;
+        };
+        if let Some(count) = labels.get_mut(&label) {
+            *count -= 1;
+            if *count == 0 {
+                labels.remove(&label);
+            }
+        }
+        if labels.is_empty() {
+            self.counts.remove(&vertex);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts_and_self_loops() {
+        let mut cache: AdjacentLabelCache<char, u32> = AdjacentLabelCache::new();
+        cache.insert(1, 2, 'a');
+        cache.insert(1, 2, 'a');
+        cache.insert(3, 3, 'b');
+
+        assert_eq!(cache.count(1, 'a'), Some(2));
+        assert_eq!(cache.count(2, 'a'), Some(2));
+        assert_eq!(cache.count(3, 'b'), Some(1));
+        assert!(!cache.may_contain(1, 'b'));
+        assert_eq!(cache.labels(1).unwrap().collect::<Vec<_>>(), vec![('a', 2)]);
+        assert_eq!(cache.labels(4).unwrap().count(), 0);
+
+        cache.relabel(1, 2, 'a', 'b');
+        assert_eq!(cache.count(2, 'a'), Some(1));
+        assert_eq!(cache.count(2, 'b'), Some(1));
+
+        cache.remove(1, 2, 'a');
+        cache.remove(1, 2, 'b');
+        cache.remove(3, 3, 'b');
+        assert!(cache.counts.is_empty());
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let mut cache: AdjacentLabelCache<char, u32> = AdjacentLabelCache::new();
+        cache.insert(1, 2, 'a');
+        cache.invalidate(2);
+
+        assert_eq!(cache.count(2, 'b'), None);
+        assert!(cache.labels(2).is_none());
+        assert!(cache.may_contain(2, 'b'));
+        assert_eq!(cache.count(1, 'b'), Some(0));
+
+        cache.remove_vertex(2);
+        assert!(!cache.is_invalidated(2));
+        cache.clear();
+        assert_eq!(cache.count(1, 'a'), Some(0));
+    }
+}
@@ -0,0 +1,177 @@
+/*!
+ * # 创建顺序 (CreationOrder)
+ *
+ * 为每个顶点和边记录一个单调递增的 `u64` 创建序号。
+ * 槽位被重用后，新元素可能排在旧元素之前，按槽位顺序无法判断两个元素的先后；
+ * 创建序号只增不减，可以比较任意两个元素（包括顶点与边）的创建先后，并按创建顺序遍历。
+ *
+ * ## 存储
+ *
+ * - 顶点和边各用一个 `SecondaryMap` 按槽位下标存放序号，O(1) 查询某个元素的序号
+ * - 顶点和边各用一个 `BTreeMap` 按序号存放 ID，按创建顺序遍历，删除为 O(log n)
+ *
+ * 顶点和边共用一个计数器，清空后计数器不归零，清空前后的序号不会重复。
+ */
+
+use crate::id::{EdgeId, VertexId};
+use crate::memory::{btree_map_bytes, secondary_map_bytes};
+use pi_slotmap::{DefaultKey, Key, SecondaryMap};
+use std::collections::BTreeMap;
+
+/// 顶点和边的创建序号
+#[derive(Debug, Clone)]
+pub struct CreationOrder<K: Key = DefaultKey> {
+    /// 下一个分配的序号
+    next: u64,
+    /// 顶点的序号
+    vertex_seqs: SecondaryMap<K, u64>,
+    /// 边的序号
+    edge_seqs: SecondaryMap<K, u64>,
+    /// 按序号排列的顶点
+    vertices: BTreeMap<u64, VertexId<K>>,
+    /// 按序号排列的边
+    edges: BTreeMap<u64, EdgeId<K>>,
+}
+
+impl<K: Key> Default for CreationOrder<K> {
+    fn default() -> Self {
+        Self {
+            next: 0,
+            vertex_seqs: SecondaryMap::new(),
+            edge_seqs: SecondaryMap::new(),
+            vertices: BTreeMap::new(),
+            edges: BTreeMap::new(),
+        }
+    }
+}
+
+impl<K: Key> CreationOrder<K> {
+    /// 创建空表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为新顶点分配序号
+    pub fn push_vertex(&mut self, id: VertexId<K>) -> u64 {
+        let seq = self.next;
+        self.next += 1;
+        self.vertex_seqs.insert(id.key(), seq);
+        self.vertices.insert(seq, id);
+        seq
+    }
+
+    /// 为新边分配序号
+    pub fn push_edge(&mut self, id: EdgeId<K>) -> u64 {
+        let seq = self.next;
+        self.next += 1;
+        self.edge_seqs.insert(id.key(), seq);
+        self.edges.insert(seq, id);
+        seq
+    }
+
+    /// 顶点的序号
+    pub fn vertex(&self, id: VertexId<K>) -> Option<u64> {
+        self.vertex_seqs.get(id.key()).copied()
+    }
+
+    /// 边的序号
+    pub fn edge(&self, id: EdgeId<K>) -> Option<u64> {
+        self.edge_seqs.get(id.key()).copied()
+    }
+
+    /// 顶点被删除后丢弃其序号
+    pub fn remove_vertex(&mut self, id: VertexId<K>) {
+        if let Some(seq) = self.vertex_seqs.remove(id.key()) {
+            self.vertices.remove(&seq);
+        }
+    }
+
+    /// 边被删除后丢弃其序号
+    pub fn remove_edge(&mut self, id: EdgeId<K>) {
+        if let Some(seq) = self.edge_seqs.remove(id.key()) {
+            self.edges.remove(&seq);
+        }
+    }
+
+    /// 只保留 `keep` 返回 `true` 的顶点，其余顶点的序号被丢弃
+    pub fn retain_vertices(&mut self, mut keep: impl FnMut(VertexId<K>) -> bool) {
+        let vertex_seqs = &mut self.vertex_seqs;
+        self.vertices.retain(|_, id| {
+            let kept = keep(*id);
+            if !kept {
+                vertex_seqs.remove(id.key());
+            }
+            kept
+        });
+    }
+
+    /// 按创建顺序排列的顶点
+    pub fn vertices(&self) -> impl DoubleEndedIterator<Item = VertexId<K>> + '_ {
+        self.vertices.values().copied()
+    }
+
+    /// 按创建顺序排列的边
+    pub fn edges(&self) -> impl DoubleEndedIterator<Item = EdgeId<K>> + '_ {
+        self.edges.values().copied()
+    }
+
+    /// 记录的顶点数和边数
+    pub fn len(&self) -> (usize, usize) {
+        (self.vertices.len(), self.edges.len())
+    }
+
+    /// 是否没有任何记录
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() && self.edges.is_empty()
+    }
+
+    /// 丢弃全部记录，计数器保持不变
+    pub fn clear(&mut self) {
+        self.vertex_seqs.clear();
+        self.edge_seqs.clear();
+        self.vertices.clear();
+        self.edges.clear();
+    }
+
+    /// 估算顶点序号占用的堆内存，单位为字节
+    pub fn vertex_memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.vertex_seqs) + btree_map_bytes(&self.vertices)
+    }
+
+    /// 估算边序号占用的堆内存，单位为字节
+    pub fn edge_memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.edge_seqs) + btree_map_bytes(&self.edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pi_slotmap::SlotMap;
+
+    #[test]
+    fn test_sequences_survive_slot_reuse() {
+        let mut slots: SlotMap<DefaultKey, ()> = SlotMap::new();
+        let mut order = CreationOrder::new();
+        let a = VertexId::new(slots.insert(()));
+        let b = VertexId::new(slots.insert(()));
+        assert_eq!((order.push_vertex(a), order.push_vertex(b)), (0, 1));
+        let edge = EdgeId::new(a.key());
+        assert_eq!(order.push_edge(edge), 2);
+
+        // a 的槽位被 c 重用，c 排在 b 之后
+        order.remove_vertex(a);
+        slots.remove(a.key());
+        let c = VertexId::new(slots.insert(()));
+        assert_eq!(order.push_vertex(c), 3);
+        assert_eq!(order.vertex(a), None);
+        assert_eq!(order.vertices().collect::<Vec<_>>(), vec![b, c]);
+
+        order.retain_vertices(|id| id != b);
+        assert_eq!(order.vertices().collect::<Vec<_>>(), vec![c]);
+        assert_eq!(order.len(), (1, 1));
+        order.clear();
+        assert!(order.is_empty());
+        assert_eq!(order.push_vertex(b), 4);
+    }
+}
@@ -0,0 +1,441 @@
+/*!
+ * # 声明的字段索引 (DeclaredIndex)
+ *
+ * 元素类型通过 `Label::indexes` 声明的索引（派生宏中的 `#[index(hash)]`、`#[index(range)]`、
+ * `#[index(full_text)]`）由本结构自动维护：顶点写入图时按 `Element::value` 读取字段值建立索引，
+ * 删除、替换权重时同步移除，无需像 [`SimpleVertexQuery`](super::SimpleVertexQuery) 那样手动写入。
+ * `VertexSearch::Index` / `Range` / `FullText` 查询声明过的索引时直接读取这里的结构。
+ *
+ * ## 待同步顶点
+ *
+ * 与 [`VertexLabelIndex`](super::VertexLabelIndex) 相同，交出顶点的可变引用前调用
+ * [`mark_dirty`](DeclaredIndex::mark_dirty)，按修改前的字段值把顶点移出索引；
+ * 查询时对这些顶点读取当前字段值进行修正，下一次 [`sync`](DeclaredIndex::sync) 时再按当前值写回。
+ *
+ * ## 值的比较
+ *
+ * - 整数不区分宽度和符号，按数值比较；浮点数按数值比较，NaN 不会被索引
+ * - 范围查询只在同一类值之间比较，例如整数区间不会匹配字符串字段
+ * - 全文索引把文本按非字母数字字符拆成小写的词，查询要求每个词都完整出现
+ */
+
+use crate::memory::{btree_map_bytes, hash_map_bytes, hash_set_bytes};
+use graph_api_lib::{Element, Index, IndexType, Label, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Range;
+
+/// 声明索引中的字段值
+///
+/// 变体的声明顺序决定了不同类值之间的顺序，范围查询只在同一类值内有意义。
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IndexKey {
+    /// 布尔值
+    Bool(bool),
+    /// 任意宽度的整数，超出 `i128` 的 `u128` 不会被索引
+    Int(i128),
+    /// 浮点数，保存为保持数值顺序的位模式
+    Float(u64),
+    /// UUID
+    Uuid(u128),
+    /// 字符串
+    Str(String),
+}
+
+impl IndexKey {
+    /// 转换字段值，无法索引的值返回 `None`
+    pub fn from_value(value: &Value) -> Option<Self> {
+        Some(match value {
+            Value::USize(v) => IndexKey::Int(*v as i128),
+            Value::U128(v) => IndexKey::Int(i128::try_from(*v).ok()?),
+            Value::U64(v) => IndexKey::Int(*v as i128),
+            Value::U32(v) => IndexKey::Int(*v as i128),
+            Value::U16(v) => IndexKey::Int(*v as i128),
+            Value::U8(v) => IndexKey::Int(*v as i128),
+            Value::I128(v) => IndexKey::Int(*v),
+            Value::I64(v) => IndexKey::Int(*v as i128),
+            Value::I32(v) => IndexKey::Int(*v as i128),
+            Value::I16(v) => IndexKey::Int(*v as i128),
+            Value::I8(v) => IndexKey::Int(*v as i128),
+            Value::F64(v) => return Self::from_float(*v),
+            Value::F32(v) => return Self::from_float(*v as f64),
+            Value::Bool(v) => IndexKey::Bool(*v),
+            Value::Uuid(v) => IndexKey::Uuid(v.as_u128()),
+            Value::Str(v) => IndexKey::Str(v.to_string()),
+            #[allow(unreachable_patterns)]
+            _ => return None,
+        })
+    }
+
+    fn from_float(value: f64) -> Option<Self> {
+        if value.is_nan() {
+            return None;
+        }
+        // 0.0 和 -0.0 相等，统一位模式；负数取反、正数置符号位，使无符号比较与数值顺序一致
+        let bits = if value == 0.0 { 0.0f64 } else { value }.to_bits();
+        let ordered = if bits >> 63 == 1 { !bits } else { bits | 1 << 63 };
+        Some(IndexKey::Float(ordered))
+    }
+}
+
+/// 把文本拆成全文索引使用的小写词
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// 按元素声明的索引保存字段值
+///
+/// - `L`: 顶点标签类型，声明的索引来自 `L::indexes`
+/// - `V`: 顶点ID类型
+#[derive(Debug, Clone)]
+pub struct DeclaredIndex<L: Label, V> {
+    /// 哈希索引：索引 -> 字段值 -> 顶点
+    hash: HashMap<L::Index, HashMap<IndexKey, HashSet<V>>>,
+    /// 范围索引：索引 -> 有序字段值 -> 顶点
+    range: HashMap<L::Index, BTreeMap<IndexKey, HashSet<V>>>,
+    /// 全文索引：索引 -> 词 -> 顶点
+    full_text: HashMap<L::Index, HashMap<String, HashSet<V>>>,
+    /// 已移出索引、等待按当前值写回的顶点
+    dirty: HashSet<V>,
+}
+
+impl<L: Label, V> Default for DeclaredIndex<L, V> {
+    fn default() -> Self {
+        Self {
+            hash: HashMap::new(),
+            range: HashMap::new(),
+            full_text: HashMap::new(),
+            dirty: HashSet::new(),
+        }
+    }
+}
+
+impl<L, V> DeclaredIndex<L, V>
+where
+    L: Label + 'static,
+    V: Copy + Eq + Hash,
+{
+    /// 创建空索引
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 是否有标签声明了该索引
+    pub fn is_declared(&self, index: &L::Index) -> bool {
+        L::variants().iter().any(|label| label.indexes().contains(index))
+    }
+
+    /// 按元素声明的索引记录顶点
+    pub fn insert<E>(&mut self, vertex: V, element: &E)
+    where
+        E: Element<Label = L>,
+    {
+        for index in element.label().indexes() {
+            let Some(value) = element.value(index) else {
+                continue;
+            };
+            match index.index_type() {
+                IndexType::Hash => {
+                    if let Some(key) = IndexKey::from_value(&value) {
+                        self.hash.entry(*index).or_default().entry(key).or_default().insert(vertex);
+                    }
+                }
+                IndexType::Range => {
+                    if let Some(key) = IndexKey::from_value(&value) {
+                        self.range.entry(*index).or_default().entry(key).or_default().insert(vertex);
+                    }
+                }
+                IndexType::FullText => {
+                    if let Value::Str(text) = value {
+                        let tokens = self.full_text.entry(*index).or_default();
+                        for token in tokenize(text) {
+                            tokens.entry(token).or_default().insert(vertex);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 移除顶点，`element` 为顶点当前的数据
+    pub fn remove<E>(&mut self, vertex: V, element: &E)
+    where
+        E: Element<Label = L>,
+    {
+        // 待同步的顶点已经按修改前的值移出
+        if self.dirty.remove(&vertex) {
+            return;
+        }
+        for index in element.label().indexes() {
+            let Some(value) = element.value(index) else {
+                continue;
+            };
+            match index.index_type() {
+                IndexType::Hash => {
+                    if let (Some(values), Some(key)) = (self.hash.get_mut(index), IndexKey::from_value(&value)) {
+                        remove_from(values, &key, vertex);
+                        if values.is_empty() {
+                            self.hash.remove(index);
+                        }
+                    }
+                }
+                IndexType::Range => {
+                    if let (Some(values), Some(key)) = (self.range.get_mut(index), IndexKey::from_value(&value)) {
+                        if let Some(set) = values.get_mut(&key) {
+                            set.remove(&vertex);
+                            if set.is_empty() {
+                                values.remove(&key);
+                            }
+                        }
+                        if values.is_empty() {
+                            self.range.remove(index);
+                        }
+                    }
+                }
+                IndexType::FullText => {
+                    if let (Some(tokens), Value::Str(text)) = (self.full_text.get_mut(index), value) {
+                        for token in tokenize(text) {
+                            remove_from(tokens, &token, vertex);
+                        }
+                        if tokens.is_empty() {
+                            self.full_text.remove(index);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 顶点即将通过可变引用被修改，`element` 为修改前的数据
+    pub fn mark_dirty<E>(&mut self, vertex: V, element: &E)
+    where
+        E: Element<Label = L>,
+    {
+        if !self.dirty.contains(&vertex) {
+            self.remove(vertex, element);
+            self.dirty.insert(vertex);
+        }
+    }
+
+    /// 是否存在待同步的顶点
+    pub fn is_synced(&self) -> bool {
+        self.dirty.is_empty()
+    }
+
+    /// 按顶点当前的数据写回全部待同步顶点
+    ///
+    /// `current` 返回顶点当前的数据，顶点不存在时返回 `None`。
+    pub fn sync<'a, E>(&mut self, current: impl Fn(V) -> Option<&'a E>)
+    where
+        E: Element<Label = L> + 'a,
+    {
+        for vertex in std::mem::take(&mut self.dirty) {
+            if let Some(element) = current(vertex) {
+                self.insert(vertex, element);
+            }
+        }
+    }
+
+    /// 哈希索引中字段值等于 `key` 的顶点
+    pub fn get<'a, E>(
+        &'a self,
+        index: L::Index,
+        key: &IndexKey,
+        current: impl Fn(V) -> Option<&'a E> + 'a,
+    ) -> impl Iterator<Item = V> + 'a
+    where
+        E: Element<Label = L> + 'a,
+    {
+        let indexed = self.hash.get(&index).and_then(|values| values.get(key));
+        let key = key.clone();
+        indexed
+            .into_iter()
+            .flatten()
+            .copied()
+            .chain(self.dirty_matching(index, current, move |value| IndexKey::from_value(value).as_ref() == Some(&key)))
+    }
+
+    /// 范围索引中字段值位于 `bounds` 内的顶点，按字段值升序
+    pub fn range<'a, E>(
+        &'a self,
+        index: L::Index,
+        bounds: &Range<IndexKey>,
+        current: impl Fn(V) -> Option<&'a E> + 'a,
+    ) -> impl Iterator<Item = V> + 'a
+    where
+        E: Element<Label = L> + 'a,
+    {
+        // BTreeMap::range 在起点大于终点时 panic
+        let indexed = self
+            .range
+            .get(&index)
+            .filter(|_| bounds.start <= bounds.end)
+            .map(|values| values.range(bounds.clone()));
+        let bounds = bounds.clone();
+        indexed
+            .into_iter()
+            .flatten()
+            .flat_map(|(_, set)| set.iter().copied())
+            .chain(self.dirty_matching(index, current, move |value| {
+                IndexKey::from_value(value).is_some_and(|key| bounds.contains(&key))
+            }))
+    }
+
+    /// 全文索引中包含全部 `tokens` 的顶点
+    ///
+    /// 从顶点最少的词出发，逐个检查其余的词。
+    pub fn full_text<'a, E>(&'a self, index: L::Index, tokens: &[String], current: impl Fn(V) -> Option<&'a E> + 'a) -> Vec<V>
+    where
+        E: Element<Label = L> + 'a,
+    {
+        let mut matched = Vec::new();
+        if let Some(words) = self.full_text.get(&index) {
+            let sets: Option<Vec<_>> = tokens.iter().map(|token| words.get(token)).collect();
+            if let Some(mut sets) = sets.filter(|sets| !sets.is_empty()) {
+                sets.sort_unstable_by_key(|set| set.len());
+                let (first, rest) = sets.split_first().expect("非空");
+                matched.extend(first.iter().copied().filter(|vertex| rest.iter().all(|set| set.contains(vertex))));
+            }
+        }
+        let tokens = tokens.to_vec();
+        matched.extend(self.dirty_matching(index, current, move |value| match value {
+            Value::Str(text) => {
+                let words: HashSet<_> = tokenize(text).collect();
+                tokens.iter().all(|token| words.contains(token))
+            }
+            _ => false,
+        }));
+        matched
+    }
+
+    /// 清空索引
+    pub fn clear(&mut self) {
+        self.hash.clear();
+        self.range.clear();
+        self.full_text.clear();
+        self.dirty.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节，全文索引计入词的长度，其余字段值自身持有的堆内存不计入
+    pub fn memory_estimate(&self) -> usize {
+        let hash: usize = self
+            .hash
+            .values()
+            .map(|values| hash_map_bytes(values) + values.values().map(hash_set_bytes).sum::<usize>())
+            .sum();
+        let range: usize = self
+            .range
+            .values()
+            .map(|values| btree_map_bytes(values) + values.values().map(hash_set_bytes).sum::<usize>())
+            .sum();
+        let full_text: usize = self
+            .full_text
+            .values()
+            .map(|words| {
+                hash_map_bytes(words)
+                    + words.iter().map(|(word, ids)| word.capacity() + hash_set_bytes(ids)).sum::<usize>()
+            })
+            .sum();
+        hash_map_bytes(&self.hash)
+            + hash_map_bytes(&self.range)
+            + hash_map_bytes(&self.full_text)
+            + hash
+            + range
+            + full_text
+            + hash_set_bytes(&self.dirty)
+    }
+
+    /// 当前字段值满足 `matches` 的待同步顶点
+    fn dirty_matching<'a, E>(
+        &'a self,
+        index: L::Index,
+        current: impl Fn(V) -> Option<&'a E> + 'a,
+        matches: impl Fn(&Value) -> bool + 'a,
+    ) -> impl Iterator<Item = V> + 'a
+    where
+        E: Element<Label = L> + 'a,
+    {
+        self.dirty.iter().copied().filter(move |vertex| {
+            current(*vertex)
+                .and_then(|element| element.value(&index))
+                .is_some_and(|value| matches(&value))
+        })
+    }
+}
+
+fn remove_from<Q: Eq + Hash, V: Eq + Hash>(values: &mut HashMap<Q, HashSet<V>>, key: &Q, vertex: V) {
+    if let Some(set) = values.get_mut(key) {
+        set.remove(&vertex);
+        if set.is_empty() {
+            values.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_test::{Vertex, VertexIndex};
+
+    fn person(name: &str, age: u64, biography: &str) -> Vertex {
+        Vertex::Person {
+            name: name.to_string(),
+            age,
+            unique_id: Default::default(),
+            username: name.to_lowercase(),
+            biography: biography.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_dirty_vertices_are_corrected() {
+        let mut vertices = HashMap::from([(1u32, person("Bryn", 28, "Did some graph stuff")), (2, person("Julia", 45, "English"))]);
+        let mut index = DeclaredIndex::new();
+        for (id, vertex) in &vertices {
+            index.insert(*id, vertex);
+        }
+        assert!(index.is_declared(&VertexIndex::PersonName));
+
+        let name = IndexKey::Str("Bryn".to_string());
+        let graph = vec!["graph".to_string()];
+        {
+            let current = |id| vertices.get(&id);
+            assert_eq!(index.get(VertexIndex::PersonName, &name, current).collect::<Vec<_>>(), vec![1]);
+            assert_eq!(index.range(VertexIndex::PersonAge, &(IndexKey::Int(20)..IndexKey::Int(46)), current).count(), 2);
+            assert_eq!(index.full_text(VertexIndex::PersonBiography, &graph, current), vec![1]);
+        }
+
+        // 顶点 1 在可变引用中被修改
+        index.mark_dirty(1, &vertices[&1]);
+        vertices.insert(1, person("Bryn", 100, "Developed a graphql proxy"));
+        {
+            let current = |id| vertices.get(&id);
+            assert_eq!(index.get(VertexIndex::PersonName, &name, current).count(), 1);
+            assert_eq!(index.range(VertexIndex::PersonAge, &(IndexKey::Int(20)..IndexKey::Int(46)), current).collect::<Vec<_>>(), vec![2]);
+            assert!(index.full_text(VertexIndex::PersonBiography, &graph, current).is_empty());
+            assert_eq!(index.full_text(VertexIndex::PersonBiography, &["proxy".to_string()], current), vec![1]);
+        }
+
+        index.sync(|id| vertices.get(&id));
+        assert!(index.is_synced());
+        assert_eq!(index.range[&VertexIndex::PersonAge].len(), 2);
+
+        index.remove(1, &vertices[&1]);
+        index.remove(2, &vertices[&2]);
+        assert!(index.hash.is_empty() && index.range.is_empty() && index.full_text.is_empty());
+    }
+
+    #[test]
+    fn test_key_ordering() {
+        let floats: Vec<_> = [-2.5, -0.0, 0.0, 1.0, 3.5].iter().map(|v| IndexKey::from_value(&Value::F64(*v)).unwrap()).collect();
+        assert!(floats.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(floats[1], floats[2]);
+        assert_eq!(IndexKey::from_value(&Value::F64(f64::NAN)), None);
+        assert_eq!(IndexKey::from_value(&Value::U8(7)), IndexKey::from_value(&Value::I64(7)));
+        assert_eq!(tokenize("Did some-graph STUFF").collect::<Vec<_>>(), vec!["did", "some", "graph", "stuff"]);
+    }
+}
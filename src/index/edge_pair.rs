@@ -0,0 +1,133 @@
+/*!
+ * # 端点对索引 (EdgePairIndex)
+ *
+ * 按 `(起点, 终点)` 记录两点之间的有向边，使 `has_edge` 与 `edges_between`
+ * 在出度很大的中心顶点上也是 O(1) 查找，而不是扫描起点的出边列表。
+ *
+ * ## 存储
+ *
+ * 绝大多数端点对之间只有一条边，因此每个端点对的边列表使用内联一个元素的 `SmallVec`，
+ * 只有出现平行边时才会分配堆内存。列表内按插入顺序排列，删除时保持其余边的顺序。
+ * 边列表清空后整个条目会被移除。
+ */
+
+use crate::memory::hash_map_bytes;
+use smallvec::SmallVec;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// 有向端点对到边的索引
+///
+/// - `V`: 顶点ID类型
+/// - `E`: 边ID类型
+#[derive(Debug, Clone)]
+pub struct EdgePairIndex<V, E> {
+    /// 每个端点对之间的边，按插入顺序排列
+    pairs: HashMap<(V, V), SmallVec<[E; 1]>>,
+}
+
+impl<V, E> Default for EdgePairIndex<V, E> {
+    fn default() -> Self {
+        Self { pairs: HashMap::new() }
+    }
+}
+
+impl<V, E> EdgePairIndex<V, E>
+where
+    V: Copy + Eq + Hash,
+    E: Copy + Eq,
+{
+    /// 创建空索引
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一条 `from -> to` 的边
+    pub fn insert(&mut self, from: V, to: V, edge: E) {
+        self.pairs.entry((from, to)).or_default().push(edge);
+    }
+
+    /// 撤销一条 `from -> to` 的边
+    pub fn remove(&mut self, from: V, to: V, edge: E) {
+        let Some(edges) = self.pairs.get_mut(&(from, to)) else {
+            return;
+        };
+        if let Some(position) = edges.iter().position(|e| *e == edge) {
+            edges.remove(position);
+        }
+        if edges.is_empty() {
+            self.pairs.remove(&(from, to));
+        }
+    }
+
+    /// `from -> to` 的全部边
+    pub fn get(&self, from: V, to: V) -> &[E] {
+        self.pairs.get(&(from, to)).map_or(&[], |edges| edges.as_slice())
+    }
+
+    /// 是否存在 `from -> to` 的边
+    pub fn contains(&self, from: V, to: V) -> bool {
+        self.pairs.contains_key(&(from, to))
+    }
+
+    /// 索引中的全部边，顺序不确定
+    pub(crate) fn edges(&self) -> impl Iterator<Item = E> + '_ {
+        self.pairs.values().flatten().copied()
+    }
+
+    /// 至少有一条边的端点对数量
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// 是否没有任何边
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// 清空索引
+    pub fn clear(&mut self) {
+        self.pairs.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节
+    ///
+    /// 同一端点对只有一条边时内联存放，不另占堆内存。
+    pub fn memory_estimate(&self) -> usize {
+        let spilled: usize = self
+            .pairs
+            .values()
+            .filter(|edges| edges.spilled())
+            .map(|edges| edges.capacity() * std::mem::size_of::<E>())
+            .sum();
+        hash_map_bytes(&self.pairs) + spilled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_edges_keep_order() {
+        let mut index: EdgePairIndex<u32, char> = EdgePairIndex::new();
+        index.insert(1, 2, 'a');
+        index.insert(1, 2, 'b');
+        index.insert(1, 2, 'c');
+        index.insert(2, 1, 'd');
+
+        assert_eq!(index.get(1, 2), &['a', 'b', 'c']);
+        assert_eq!(index.len(), 2);
+        assert!(!index.contains(3, 1));
+
+        index.remove(1, 2, 'b');
+        assert_eq!(index.get(1, 2), &['a', 'c']);
+        index.remove(1, 2, 'a');
+        index.remove(1, 2, 'c');
+        assert!(!index.contains(1, 2));
+        assert!(index.get(1, 2).is_empty());
+
+        index.clear();
+        assert!(index.is_empty());
+    }
+}
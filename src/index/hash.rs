@@ -1,4 +1,4 @@
-/**
+/*!
  * # 哈希索引 (HashIndex)
  *
  * 提供高性能的精确匹配查询功能，基于 HashMap 实现。
@@ -15,6 +15,10 @@
  * - **自动清理**：删除空键值对
  * - **紧凑存储**：最小化内存开销
  *
+ * ### 批量操作
+ * - **extend**：按迭代器长度预留容量后批量插入
+ * - **retain**：按条件筛选键值对，一次遍历，删除变空的键
+ *
  * ## 使用场景
  *
  * - **标签索引**：按顶点/边类型快速查找
@@ -31,6 +35,7 @@
  */
 
 use crate::id::{VertexId, EdgeId};
+use crate::memory::{hash_map_bytes, hash_set_bytes};
 use std::borrow::Borrow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
@@ -48,15 +53,16 @@ use std::hash::Hash;
 /// # 示例
 ///
 /// ```rust
-/// use slotmap_graph::index::HashIndex;
-/// use slotmap_graph::VertexId;
-/// use pi_slotmap::DefaultKey;
+/// use pi_slotmap_graph::index::HashIndex;
+/// use pi_slotmap_graph::VertexId;
+/// use pi_slotmap::{DefaultKey, SlotMap};
 ///
 /// let mut index = HashIndex::new();
 ///
 /// // 添加索引项
-/// let id1 = VertexId::new(DefaultKey::default());
-/// let id2 = VertexId::new(DefaultKey::default());
+/// let mut keys = SlotMap::<DefaultKey, ()>::new();
+/// let id1 = VertexId::new(keys.insert(()));
+/// let id2 = VertexId::new(keys.insert(()));
 ///
 /// index.insert("engineer", id1);
 /// index.insert("engineer", id2);
@@ -76,6 +82,8 @@ where
     map: HashMap<K, HashSet<V>>,
     /// 空集合，用于 get() 方法返回空迭代器
     empty: HashSet<V>,
+    /// 新键的值集合预留的容量
+    value_capacity: usize,
 }
 
 impl<K, V> Default for HashIndex<K, V>
@@ -102,7 +110,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::HashIndex;
+    /// use pi_slotmap_graph::index::HashIndex;
     ///
     /// let index: HashIndex<String, u32> = HashIndex::new();
     /// assert!(index.is_empty());
@@ -111,6 +119,7 @@ where
         Self {
             map: HashMap::new(),
             empty: HashSet::new(),
+            value_capacity: 0,
         }
     }
 
@@ -123,7 +132,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::HashIndex;
+    /// use pi_slotmap_graph::index::HashIndex;
     ///
     /// let index: HashIndex<String, u32> = HashIndex::with_capacity(100);
     /// ```
@@ -131,6 +140,28 @@ where
         Self {
             map: HashMap::with_capacity(capacity),
             empty: HashSet::new(),
+            value_capacity: 0,
+        }
+    }
+
+    /// 预分配键和值集合容量的哈希索引
+    ///
+    /// 预留 `keys` 个键，此后每个新键的值集合创建时预留 `values` 个值，
+    /// 键和每个键下的值都不超过预留数量时不会重新分配。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use pi_slotmap_graph::index::HashIndex;
+    ///
+    /// let mut index: HashIndex<&str, u32> = HashIndex::with_capacities(4, 100);
+    /// index.insert("engineer", 1);
+    /// assert_eq!(index.len_of("engineer"), 1);
+    /// ```
+    pub fn with_capacities(keys: usize, values: usize) -> Self {
+        Self {
+            value_capacity: values,
+            ..Self::with_capacity(keys)
         }
     }
 
@@ -150,7 +181,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::HashIndex;
+    /// use pi_slotmap_graph::index::HashIndex;
     ///
     /// let mut index = HashIndex::new();
     ///
@@ -161,7 +192,32 @@ where
     /// assert!(!inserted2);
     /// ```
     pub fn insert(&mut self, key: K, value: V) -> bool {
-        self.map.entry(key).or_default().insert(value)
+        let capacity = self.value_capacity;
+        self.map.entry(key).or_insert_with(|| HashSet::with_capacity(capacity)).insert(value)
+    }
+
+    /// 批量插入键值对
+    ///
+    /// 按迭代器长度的下限预留容量（与 `HashMap::extend` 的估计相同），重复的键值对同样被忽略。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use pi_slotmap_graph::index::HashIndex;
+    ///
+    /// let mut index = HashIndex::new();
+    /// index.extend([("a", 1), ("a", 2), ("b", 1), ("a", 1)]);
+    /// assert_eq!(index.len_of("a"), 2);
+    /// assert_eq!(index.total_values_len(), 3);
+    /// ```
+    pub fn extend(&mut self, iter: impl IntoIterator<Item = (K, V)>) {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        let additional = if self.map.is_empty() { lower } else { lower.div_ceil(2) };
+        self.map.reserve(additional);
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
     }
 
     /// 移除键值对
@@ -180,7 +236,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::HashIndex;
+    /// use pi_slotmap_graph::index::HashIndex;
     ///
     /// let mut index = HashIndex::new();
     /// index.insert("key", 1);
@@ -208,6 +264,28 @@ where
         }
     }
 
+    /// 只保留 `f` 返回 `true` 的键值对
+    ///
+    /// 一次遍历全部键值对，筛选后值集合为空的键一并删除。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use pi_slotmap_graph::index::HashIndex;
+    ///
+    /// let mut index = HashIndex::new();
+    /// index.extend([("a", 1), ("a", 2), ("b", 3)]);
+    /// index.retain(|_, value| *value != 3);
+    /// assert!(!index.contains_key("b"));
+    /// assert_eq!(index.len_of("a"), 2);
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(&K, &V) -> bool) {
+        self.map.retain(|key, values| {
+            values.retain(|value| f(key, value));
+            !values.is_empty()
+        });
+    }
+
     /// 移除键对应的所有值
     ///
     /// # 参数
@@ -221,7 +299,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::HashIndex;
+    /// use pi_slotmap_graph::index::HashIndex;
     /// use std::collections::HashSet;
     ///
     /// let mut index = HashIndex::new();
@@ -254,7 +332,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::HashIndex;
+    /// use pi_slotmap_graph::index::HashIndex;
     ///
     /// let mut index = HashIndex::new();
     /// index.insert("key", 1);
@@ -285,7 +363,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::HashIndex;
+    /// use pi_slotmap_graph::index::HashIndex;
     ///
     /// let mut index = HashIndex::new();
     /// index.insert("key", 1);
@@ -355,6 +433,17 @@ where
         self.map.clear();
     }
 
+    /// 估算占用的堆内存，单位为字节，不含键自身持有的堆内存
+    pub fn memory_estimate(&self) -> usize {
+        hash_map_bytes(&self.map) + self.map.values().map(hash_set_bytes).sum::<usize>() + hash_set_bytes(&self.empty)
+    }
+
+    /// 释放映射和各值集合中多余的容量
+    pub fn shrink_to_fit(&mut self) {
+        self.map.values_mut().for_each(HashSet::shrink_to_fit);
+        self.map.shrink_to_fit();
+    }
+
     /// 获取所有键的迭代器
     pub fn keys(&self) -> impl Iterator<Item = &K> {
         self.map.keys()
@@ -473,4 +562,25 @@ mod tests {
         assert_eq!(index.len_of("friendship"), 1);
         assert_eq!(index.len_of("follows"), 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bulk_operations() {
+        let mut index = HashIndex::new();
+        index.extend([("a", 1), ("a", 2), ("b", 1), ("b", 3), ("c", 1), ("a", 1)]);
+        assert_eq!((index.keys_len(), index.total_values_len()), (3, 5));
+
+        // 值 1 从全部键下移除，"c" 随之变空被删除
+        index.retain(|_, value| *value != 1);
+        assert!(!index.contains_key("c"));
+        assert_eq!(index.get("a").collect::<Vec<_>>(), vec![2]);
+
+        index.extend([("c", 4), ("c", 5), ("d", 6)]);
+        index.retain(|key, value| *key != "b" && *value != 6);
+        assert!(!index.contains_key("b") && !index.contains_key("d"));
+        assert_eq!((index.keys_len(), index.total_values_len()), (2, 3));
+        assert!(index.iter().all(|(_, mut values)| values.next().is_some()));
+
+        index.retain(|_, _| false);
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+/*!
+ * # 字符串驻留 (Interner)
+ *
+ * 把重复出现的短字符串（如类别、标签名）映射为 4 字节的 [`Symbol`]，
+ * 每个不同的字符串只保存一份。顶点数据保存 `Symbol` 而不是 `String` 时，
+ * 每个顶点省去一次堆分配和 24 字节的 `String` 头，哈希和比较也只涉及一个 `u32`。
+ *
+ * ## 约定
+ *
+ * - 同一个驻留器中，相同字符串总是得到相同的符号，符号按首次驻留的顺序从 0 开始编号
+ * - 已驻留的字符串不会被释放，符号在驻留器的生命周期内始终有效
+ * - 符号只对产生它的驻留器有意义，用其他驻留器解析时 [`resolve`](Interner::resolve) 可能返回 `None` 或其他字符串
+ */
+
+use crate::memory::hash_map_bytes;
+use std::collections::HashMap;
+
+/// 驻留字符串的句柄
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// 符号在驻留器中的编号
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
+
+/// 字符串驻留器
+///
+/// # 示例
+///
+/// ```rust
+/// use pi_slotmap_graph::index::Interner;
+///
+/// let mut interner = Interner::new();
+/// let engineer = interner.intern("Engineer");
+/// assert_eq!(interner.intern("Engineer"), engineer);
+/// assert_eq!(interner.resolve(engineer), Some("Engineer"));
+/// assert_eq!(interner.get("Designer"), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    /// 字符串到符号的映射
+    symbols: HashMap<Box<str>, Symbol>,
+    /// 按符号编号排列的字符串
+    strings: Vec<Box<str>>,
+}
+
+impl Interner {
+    /// 创建空驻留器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 驻留字符串，已驻留时返回原有符号
+    ///
+    /// # Panics
+    ///
+    /// 不同字符串的数量超过 `u32::MAX` 时 panic。
+    pub fn intern(&mut self, value: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(value) {
+            return *symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.strings.len()).expect("interner symbol space exhausted"));
+        self.strings.push(value.into());
+        self.symbols.insert(value.into(), symbol);
+        symbol
+    }
+
+    /// 查找已驻留字符串的符号，不驻留新字符串
+    pub fn get(&self, value: &str) -> Option<Symbol> {
+        self.symbols.get(value).copied()
+    }
+
+    /// 符号对应的字符串
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.strings.get(symbol.0 as usize).map(|s| &**s)
+    }
+
+    /// 已驻留的不同字符串数量
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// 是否没有驻留任何字符串
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// 估算占用的堆内存，单位为字节，每个字符串在映射和列表中各保存一份
+    pub fn memory_estimate(&self) -> usize {
+        let text: usize = self.strings.iter().map(|s| s.len()).sum();
+        hash_map_bytes(&self.symbols) + self.strings.capacity() * std::mem::size_of::<Box<str>>() + 2 * text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_labels_share_symbols() {
+        let labels = ["Engineer", "Designer", "Manager"];
+        let mut interner = Interner::new();
+        let symbols: Vec<Symbol> = (0..100_000).map(|i| interner.intern(labels[i % labels.len()])).collect();
+
+        assert_eq!(interner.len(), 3);
+        assert_eq!(symbols[0].index(), 0);
+        assert_eq!(symbols[3], symbols[0]);
+        assert_eq!(interner.resolve(symbols[4]), Some("Designer"));
+        assert_eq!(interner.resolve(Symbol(3)), None);
+
+        // 每个顶点保存 String 与保存 Symbol 的内存估算
+        let as_strings: usize = (0..symbols.len())
+            .map(|i| std::mem::size_of::<String>() + labels[i % labels.len()].len())
+            .sum();
+        let as_symbols = symbols.len() * std::mem::size_of::<Symbol>()
+            + labels.iter().map(|l| 2 * (std::mem::size_of::<Box<str>>() + l.len())).sum::<usize>();
+        assert!(as_symbols * 5 < as_strings);
+    }
+}
@@ -0,0 +1,146 @@
+/*!
+ * # 标签缓存 (LabelCache)
+ *
+ * 为每个顶点和边保存一份 `Element::label()` 的计算结果。
+ * 标签由用户实现，可能涉及字符串格式化、枚举映射等开销；
+ * 按标签过滤关联边、按另一端顶点的标签过滤时，每次查询对每条候选边都要计算一次标签，
+ * 开启缓存后这些过滤直接读取缓存的值。
+ *
+ * ## 维护
+ *
+ * 元素加入图时计算一次标签并写入缓存，删除时移除。
+ * 经由 `edge_mut` 修改边时，可变引用释放时重新计算一次并写回；
+ * 经由 `edge_weight_mut`、`vertex_weight_mut` 等裸可变引用修改时，缓存项先被移除，
+ * 下一次修改图时随标签索引同步重新计算，在此之前的查询直接调用 `label()`。
+ *
+ * ## 存储
+ *
+ * 顶点和边各用一个 `SecondaryMap` 按槽位下标存放标签，槽位被重用后旧元素的标签不会出现在新元素上。
+ */
+
+use crate::id::{EdgeId, VertexId};
+use crate::memory::secondary_map_bytes;
+use pi_slotmap::{DefaultKey, Key, SecondaryMap};
+
+/// 顶点和边的标签缓存
+///
+/// - `VL`: 顶点标签类型
+/// - `EL`: 边标签类型
+#[derive(Debug, Clone)]
+pub struct LabelCache<VL, EL, K: Key = DefaultKey> {
+    /// 顶点的标签
+    vertices: SecondaryMap<K, VL>,
+    /// 边的标签
+    edges: SecondaryMap<K, EL>,
+}
+
+impl<VL, EL, K: Key> Default for LabelCache<VL, EL, K> {
+    fn default() -> Self {
+        Self {
+            vertices: SecondaryMap::new(),
+            edges: SecondaryMap::new(),
+        }
+    }
+}
+
+impl<VL: Copy, EL: Copy, K: Key> LabelCache<VL, EL, K> {
+    /// 创建空缓存
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 缓存的顶点标签
+    pub fn vertex(&self, id: VertexId<K>) -> Option<VL> {
+        self.vertices.get(id.key()).copied()
+    }
+
+    /// 缓存的边标签
+    pub fn edge(&self, id: EdgeId<K>) -> Option<EL> {
+        self.edges.get(id.key()).copied()
+    }
+
+    /// 缓存的边标签的可变引用，供可变边引用释放时写回新标签
+    pub(crate) fn edge_mut(&mut self, id: EdgeId<K>) -> Option<&mut EL> {
+        self.edges.get_mut(id.key())
+    }
+
+    /// 记录顶点的标签
+    pub fn set_vertex(&mut self, id: VertexId<K>, label: VL) {
+        self.vertices.insert(id.key(), label);
+    }
+
+    /// 记录边的标签
+    pub fn set_edge(&mut self, id: EdgeId<K>, label: EL) {
+        self.edges.insert(id.key(), label);
+    }
+
+    /// 移除顶点的缓存项，返回缓存的标签
+    pub fn remove_vertex(&mut self, id: VertexId<K>) -> Option<VL> {
+        self.vertices.remove(id.key())
+    }
+
+    /// 移除边的缓存项，返回缓存的标签
+    pub fn remove_edge(&mut self, id: EdgeId<K>) -> Option<EL> {
+        self.edges.remove(id.key())
+    }
+
+    /// 有缓存项的边，按槽位下标升序
+    pub(crate) fn edge_ids(&self) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.edges.iter().map(|(key, _)| EdgeId::new(key))
+    }
+
+    /// 缓存的顶点数和边数
+    pub fn len(&self) -> (usize, usize) {
+        (self.vertices.len(), self.edges.len())
+    }
+
+    /// 是否没有任何缓存项
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() && self.edges.is_empty()
+    }
+
+    /// 清空全部缓存项
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+    }
+
+    /// 估算顶点标签占用的堆内存，单位为字节
+    pub fn vertex_memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.vertices)
+    }
+
+    /// 估算边标签占用的堆内存，单位为字节
+    pub fn edge_memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pi_slotmap::SlotMap;
+
+    #[test]
+    fn test_entries_follow_slot_versions() {
+        let mut slots: SlotMap<DefaultKey, ()> = SlotMap::new();
+        let old = slots.insert(());
+        let mut cache: LabelCache<u8, char> = LabelCache::new();
+        cache.set_vertex(VertexId::new(old), 1);
+        cache.set_edge(EdgeId::new(old), 'a');
+        assert_eq!(cache.len(), (1, 1));
+        *cache.edge_mut(EdgeId::new(old)).unwrap() = 'b';
+        assert_eq!(cache.edge(EdgeId::new(old)), Some('b'));
+
+        // 槽位重用后旧键查不到缓存项
+        slots.remove(old);
+        let new = slots.insert(());
+        cache.set_vertex(VertexId::new(new), 2);
+        assert_eq!(cache.vertex(VertexId::new(old)), None);
+        assert_eq!(cache.vertex(VertexId::new(new)), Some(2));
+
+        assert_eq!(cache.remove_vertex(VertexId::new(new)), Some(2));
+        assert_eq!(cache.remove_edge(EdgeId::new(old)), Some('b'));
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+/*!
+ * # 边图层 (EdgeLayers)
+ *
+ * 为每条边保存一个图层编号，并记录当前可见图层的位掩码，
+ * 使同一张图中的多种关系（数据依赖、控制依赖、调试注解等）可以分别显示或隐藏，
+ * 不必为每种关系维护单独的图。
+ *
+ * ## 存储
+ *
+ * 图层编号存放在 `SecondaryMap` 中，只保存非零的编号，未记录的边属于 0 号图层。
+ * 编号必须小于 64，第 `n` 号图层对应掩码的第 `n` 位。
+ * 默认掩码为全部可见，此时判断可见性不读取编号，未使用图层的图行为不变。
+ *
+ * 修改编号或掩码时递增 [`generation`](EdgeLayers::generation)，
+ * 按图版本号缓存遍历结果的结构（如可达性缓存）据此判断可见的边是否改变。
+ */
+
+use crate::id::EdgeId;
+use crate::memory::secondary_map_bytes;
+use pi_slotmap::{DefaultKey, Key, SecondaryMap};
+
+/// 图层编号的上限（不含）
+pub const MAX_LAYERS: u32 = u64::BITS;
+
+/// 边的图层编号及可见图层掩码
+#[derive(Debug, Clone)]
+pub struct EdgeLayers<K: Key = DefaultKey> {
+    /// 非零的图层编号
+    layers: SecondaryMap<K, u32>,
+    /// 可见图层的位掩码
+    visible: u64,
+    /// 编号和掩码的修改次数
+    generation: u64,
+}
+
+impl<K: Key> Default for EdgeLayers<K> {
+    fn default() -> Self {
+        Self {
+            layers: SecondaryMap::new(),
+            visible: u64::MAX,
+            generation: 0,
+        }
+    }
+}
+
+impl<K: Key> EdgeLayers<K> {
+    /// 创建全部图层可见的空表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置边的图层
+    ///
+    /// # Panics
+    ///
+    /// `layer` 不小于 [`MAX_LAYERS`] 时 panic。
+    pub fn set(&mut self, id: EdgeId<K>, layer: u32) {
+        assert!(layer < MAX_LAYERS, "edge layer {layer} out of range, must be below {MAX_LAYERS}");
+        if layer == 0 {
+            self.layers.remove(id.key());
+        } else {
+            self.layers.insert(id.key(), layer);
+        }
+        self.generation += 1;
+    }
+
+    /// 边的图层，未记录的边属于 0 号图层
+    pub fn layer(&self, id: EdgeId<K>) -> u32 {
+        self.layers.get(id.key()).copied().unwrap_or(0)
+    }
+
+    /// 边被删除后丢弃其图层
+    pub fn remove(&mut self, id: EdgeId<K>) {
+        self.layers.remove(id.key());
+    }
+
+    /// 记录了图层的边，按槽位下标升序
+    pub(crate) fn edges(&self) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.layers.iter().map(|(key, _)| EdgeId::new(key))
+    }
+
+    /// 可见图层的位掩码
+    pub fn visible(&self) -> u64 {
+        self.visible
+    }
+
+    /// 设置可见图层的位掩码
+    pub fn set_visible(&mut self, mask: u64) {
+        self.visible = mask;
+        self.generation += 1;
+    }
+
+    /// 编号和掩码的修改次数
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// 是否全部图层可见
+    #[inline]
+    pub fn all_visible(&self) -> bool {
+        self.visible == u64::MAX
+    }
+
+    /// 边所在的图层是否可见
+    #[inline]
+    pub fn is_visible(&self, id: EdgeId<K>) -> bool {
+        self.all_visible() || self.visible & (1 << self.layer(id)) != 0
+    }
+
+    /// 记录了非零图层的边数量
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// 是否所有边都在 0 号图层
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// 清空全部图层编号，保留可见掩码
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节
+    pub fn memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.layers)
+    }
+}
@@ -0,0 +1,171 @@
+/*!
+ * # 元素标记 (MarkTable)
+ *
+ * 为顶点和边各保存一个 `u8` 位标记，供算法原型使用"已访问"、"脏"、"选中"等临时标记，
+ * 不必每次调用都分配额外的 `HashMap`。
+ *
+ * ## 存储
+ *
+ * 顶点和边各用一个 `SecondaryMap` 按槽位下标存放标记，只保存非零的标记。
+ * `SecondaryMap` 按版本号区分同一槽位上的新旧键，槽位被重用后旧元素的标记不会出现在新元素上；
+ * 图在删除元素时也会主动移除其标记。
+ *
+ * ## 位掩码
+ *
+ * 各方法的 `mask` 参数是位掩码，可以一次设置或清除多个位，
+ * 查询时只要任一位被设置即视为带有标记。
+ */
+
+use crate::id::{EdgeId, VertexId};
+use crate::memory::secondary_map_bytes;
+use pi_slotmap::{DefaultKey, Key, SecondaryMap};
+
+/// 顶点和边的位标记表
+#[derive(Debug, Clone)]
+pub struct MarkTable<K: Key = DefaultKey> {
+    /// 顶点的非零标记
+    vertices: SecondaryMap<K, u8>,
+    /// 边的非零标记
+    edges: SecondaryMap<K, u8>,
+}
+
+impl<K: Key> Default for MarkTable<K> {
+    fn default() -> Self {
+        Self {
+            vertices: SecondaryMap::new(),
+            edges: SecondaryMap::new(),
+        }
+    }
+}
+
+/// 在 `map` 中为 `key` 设置或清除 `mask` 中的位，标记变为零时移除条目
+fn update<K: Key>(map: &mut SecondaryMap<K, u8>, key: K, mask: u8, set: bool) {
+    let current = map.get(key).copied().unwrap_or(0);
+    let marks = if set { current | mask } else { current & !mask };
+    if marks == 0 {
+        map.remove(key);
+    } else {
+        map.insert(key, marks);
+    }
+}
+
+/// 清除 `map` 中所有条目的 `mask` 位
+fn clear_all<K: Key>(map: &mut SecondaryMap<K, u8>, mask: u8) {
+    map.retain(|_, marks| {
+        *marks &= !mask;
+        *marks != 0
+    });
+}
+
+impl<K: Key> MarkTable<K> {
+    /// 创建空标记表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置顶点的 `mask` 位
+    pub fn set_vertex(&mut self, id: VertexId<K>, mask: u8) {
+        update(&mut self.vertices, id.key(), mask, true);
+    }
+
+    /// 清除顶点的 `mask` 位
+    pub fn clear_vertex(&mut self, id: VertexId<K>, mask: u8) {
+        update(&mut self.vertices, id.key(), mask, false);
+    }
+
+    /// 顶点是否带有 `mask` 中的任一位
+    pub fn vertex_has(&self, id: VertexId<K>, mask: u8) -> bool {
+        self.vertices.get(id.key()).is_some_and(|marks| marks & mask != 0)
+    }
+
+    /// 设置边的 `mask` 位
+    pub fn set_edge(&mut self, id: EdgeId<K>, mask: u8) {
+        update(&mut self.edges, id.key(), mask, true);
+    }
+
+    /// 清除边的 `mask` 位
+    pub fn clear_edge(&mut self, id: EdgeId<K>, mask: u8) {
+        update(&mut self.edges, id.key(), mask, false);
+    }
+
+    /// 边是否带有 `mask` 中的任一位
+    pub fn edge_has(&self, id: EdgeId<K>, mask: u8) -> bool {
+        self.edges.get(id.key()).is_some_and(|marks| marks & mask != 0)
+    }
+
+    /// 顶点被删除后丢弃其标记
+    pub fn remove_vertex(&mut self, id: VertexId<K>) {
+        self.vertices.remove(id.key());
+    }
+
+    /// 边被删除后丢弃其标记
+    pub fn remove_edge(&mut self, id: EdgeId<K>) {
+        self.edges.remove(id.key());
+    }
+
+    /// 清除所有顶点和边的 `mask` 位
+    pub fn clear_all(&mut self, mask: u8) {
+        clear_all(&mut self.vertices, mask);
+        clear_all(&mut self.edges, mask);
+    }
+
+    /// 带有 `mask` 中任一位的顶点，按槽位下标升序
+    pub fn vertices(&self, mask: u8) -> impl Iterator<Item = VertexId<K>> + '_ {
+        self.vertices.iter().filter(move |(_, marks)| **marks & mask != 0).map(|(key, _)| VertexId::new(key))
+    }
+
+    /// 带有 `mask` 中任一位的边，按槽位下标升序
+    pub fn edges(&self, mask: u8) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.edges.iter().filter(move |(_, marks)| **marks & mask != 0).map(|(key, _)| EdgeId::new(key))
+    }
+
+    /// 带有任意标记的顶点和边的总数
+    pub fn len(&self) -> usize {
+        self.vertices.len() + self.edges.len()
+    }
+
+    /// 是否没有任何标记
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty() && self.edges.is_empty()
+    }
+
+    /// 清空全部标记
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+        self.edges.clear();
+    }
+
+    /// 估算顶点标记占用的堆内存，单位为字节
+    pub fn vertex_memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.vertices)
+    }
+
+    /// 估算边标记占用的堆内存，单位为字节
+    pub fn edge_memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.edges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pi_slotmap::SlotMap;
+
+    #[test]
+    fn test_masks_and_empty_entries() {
+        let mut slots: SlotMap<DefaultKey, ()> = SlotMap::new();
+        let [a, b] = [(); 2].map(|_| VertexId::new(slots.insert(())));
+        let mut marks = MarkTable::new();
+
+        marks.set_vertex(a, 0b011);
+        marks.set_vertex(b, 0b100);
+        assert!(marks.vertex_has(a, 0b001) && marks.vertex_has(a, 0b110));
+        assert!(!marks.vertex_has(b, 0b011));
+        assert_eq!(marks.vertices(0b100).collect::<Vec<_>>(), vec![b]);
+
+        marks.clear_vertex(a, 0b001);
+        assert!(marks.vertex_has(a, 0b010));
+        marks.clear_all(0b110);
+        assert!(marks.is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * # 索引元数据 (Index Metadata)
  *
  * 定义索引的元数据结构和管理接口，支持动态索引创建和管理。
@@ -127,6 +127,18 @@ impl From<i64> for IndexValue {
     }
 }
 
+impl From<f32> for IndexValue {
+    fn from(value: f32) -> Self {
+        IndexValue::F32(value)
+    }
+}
+
+impl From<f64> for IndexValue {
+    fn from(value: f64) -> Self {
+        IndexValue::F64(value)
+    }
+}
+
 impl From<bool> for IndexValue {
     fn from(value: bool) -> Self {
         IndexValue::Bool(value)
@@ -176,14 +188,17 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::{GenericIndexDefinition, IndexType};
+    /// use graph_api_lib::IndexType;
+    /// use pi_slotmap_graph::index::metadata::{GenericIndexDefinition, IndexDefinition};
     ///
     /// // 按年龄索引
     /// let age_index = GenericIndexDefinition::new(
-    ///     "age",
+    ///     "age".to_string(),
     ///     IndexType::Range,
     ///     |person: &(String, u32)| person.1
     /// );
+    /// assert_eq!(age_index.name(), "age");
+    /// assert_eq!(age_index.extract(&("Alice".to_string(), 25)), 25);
     /// ```
     pub fn new(name: String, index_type: IndexType, extract_fn: F) -> Self {
         Self {
@@ -1,4 +1,4 @@
-/**
+/*!
  * # 索引系统 (Index System)
  *
  * 本模块为 SlotMapGraph 提供高性能的索引功能，支持多种查询优化策略。
@@ -32,6 +32,37 @@
  * - **性能**：O(log n) 插入，O(log n + m) 范围查询
  * - **内存**：较高开销，但提供强大的查询能力
  *
+ * ### 字符串驻留 (Interner)
+ * - **适用场景**：大量重复的短字符串（类别、标签名）
+ * - **数据结构**：`HashMap<Box<str>, Symbol>` + `Vec<Box<str>>`
+ * - **性能**：O(1) 驻留和解析，每个不同字符串只保存一份
+ * - **内存**：顶点数据保存 4 字节的 `Symbol` 代替 `String`
+ *
+ * ### 空间索引 (SpatialIndex)
+ * - **适用场景**：二维坐标上的半径查询和 k 近邻查询
+ * - **数据结构**：均匀网格 `HashMap<(i32, i32), Vec<VertexId>>`，格子边长可配置
+ * - **性能**：半径查询只检查覆盖范围内的格子，k 近邻从中心格子逐圈向外扩展
+ *
+ * ### 标签缓存 (LabelCache)
+ * - **适用场景**：`Element::label()` 开销较大、频繁按标签过滤关联边
+ * - **数据结构**：顶点和边各一个 `SecondaryMap<K, Label>`
+ * - **性能**：过滤时 O(1) 读取缓存，代替每次调用 `label()`
+ *
+ * ### 创建顺序 (CreationOrder)
+ * - **适用场景**：比较元素的创建先后、按创建顺序遍历（槽位重用后槽位顺序不再反映创建顺序）
+ * - **数据结构**：`SecondaryMap<K, u64>` 存放序号，`BTreeMap<u64, Id>` 按序号排列
+ * - **性能**：查询序号 O(1)，增删 O(log n)
+ *
+ * ### 边图层 (EdgeLayers)
+ * - **适用场景**：同一张图中的多种关系按图层分别显示或隐藏
+ * - **数据结构**：`SecondaryMap<K, u32>` 存放非零图层编号，加一个 64 位可见掩码
+ * - **性能**：全部可见时不读取编号；部分可见时每条边 O(1) 判断
+ *
+ * ### 边批次标签 (EdgeTags)
+ * - **适用场景**：按导入批次列出或整批删除边
+ * - **数据结构**：`SecondaryMap<K, u64>` 存放标签，`HashMap<u64, Vec<EdgeId>>` 倒排表延迟清理
+ * - **性能**：设置和单独删除 O(1)，按标签列出或删除 O(批次大小)
+ *
  * ## 架构设计
  *
  * ```text,ignore
@@ -56,8 +87,8 @@
  *
  * ### 内存效率
  * - **紧凑存储**：使用高效的集合数据结构
- * **自动清理**：删除元素时自动维护索引一致性
- * **延迟初始化**：按需创建索引，节省内存
+ * - **自动清理**：删除元素时自动维护索引一致性
+ * - **延迟初始化**：按需创建索引，节省内存
  *
  * ### 查询优化
  * - **智能选择**：根据查询类型自动选择最优索引
@@ -69,38 +100,51 @@
  * ### 基本索引操作
  *
  * ```rust
- * use slotmap_graph::{SlotMapGraph, index::{IndexManager, IndexType}};
- *
- * let mut graph = SlotMapGraph::new();
- * let index_manager = graph.index_manager();
- *
- * // 创建顶点
- * let alice = graph.add_vertex(("Alice", 25, "Engineer"));
- * let bob = graph.add_vertex(("Bob", 30, "Designer"));
- * let charlie = graph.add_vertex(("Charlie", 35, "Manager"));
- *
- * // 创建哈希索引 - 按职业查询
- * index_manager.create_vertex_index(
- *     "profession",
- *     |vertex: &(String, u32, String)| vertex.2.clone(),
- *     IndexType::Hash
- * );
- *
- * // 创建范围索引 - 按年龄查询
- * index_manager.create_vertex_index(
- *     "age",
- *     |vertex: &(String, u32, String)| vertex.1,
- *     IndexType::Range
- * );
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::index::{HashIndex, RangeIndex};
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ *
+ * // 创建顶点（权重为年龄）
+ * let alice = graph.add_vertex(25);
+ * let bob = graph.add_vertex(30);
+ * let charlie = graph.add_vertex(35);
+ *
+ * // 哈希索引 - 按职业查询
+ * let mut profession = HashIndex::new();
+ * profession.insert("Engineer", alice);
+ * profession.insert("Designer", bob);
+ * profession.insert("Manager", charlie);
+ *
+ * // 范围索引 - 按年龄查询
+ * let mut age = RangeIndex::new();
+ * for (id, weight) in [(alice, 25u32), (bob, 30), (charlie, 35)] {
+ *     age.insert(weight, id);
+ * }
  *
  * // 使用索引查询
- * let engineers: Vec<_> = index_manager
- *     .query_vertex_hash("profession", "Engineer")
- *     .collect();
+ * let engineers: Vec<_> = profession.get("Engineer").collect();
+ * assert_eq!(engineers, vec![alice]);
+ *
+ * let senior_people: Vec<_> = age.range(30..=40).collect();
+ * assert_eq!(senior_people.len(), 2);
+ * assert!(senior_people.contains(&bob) && senior_people.contains(&charlie));
+ * ```
+ *
+ * ### 图内置的简单查询
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * let alice = graph.add_vertex(25);
+ * graph.index_vertex_string(alice, "Engineer");
+ * graph.index_vertex_int(alice, 25);
  *
- * let senior_people: Vec<_> = index_manager
- *     .query_vertex_range("age", 30..=40)
- *     .collect();
+ * assert_eq!(graph.vertex_query().query_string("Engineer").count(), 1);
+ * assert_eq!(graph.vertex_query().range_int(20..30).count(), 1);
  * ```
  *
  * ## 性能特征
@@ -127,18 +171,41 @@
  * | 复杂度 | 简化版 | 功能完整 |
  */
 
+pub mod adjacent_label;
+pub mod creation_order;
+pub mod declared;
+pub mod edge_pair;
 pub mod hash;
+pub mod interner;
+pub mod label_cache;
+pub mod layers;
+pub mod marks;
 pub mod range;
 // pub mod manager;
 pub mod metadata;
+pub mod persist;
 pub mod simple_query;
+pub mod spatial;
+pub mod tags;
+pub mod vertex_label;
 // pub mod smart_query_test;
 // pub mod test_basic;
 
 
 // 重新导出主要类型
+pub use adjacent_label::AdjacentLabelCache;
+pub use creation_order::CreationOrder;
+pub use declared::{DeclaredIndex, IndexKey};
+pub use edge_pair::EdgePairIndex;
 pub use hash::HashIndex;
+pub use interner::{Interner, Symbol};
+pub use label_cache::LabelCache;
+pub use layers::EdgeLayers;
+pub use marks::MarkTable;
 pub use range::RangeIndex;
+pub use tags::EdgeTags;
 // pub use manager::{IndexManager, QueryResult};
 pub use metadata::{IndexMetadata, IndexDefinition, IndexValue};
-pub use simple_query::SimpleVertexQuery;
\ No newline at end of file
+pub use simple_query::{IndexEntry, KeyOrder, SimpleVertexQuery};
+pub use spatial::SpatialIndex;
+pub use vertex_label::VertexLabelIndex;
\ No newline at end of file
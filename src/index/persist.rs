@@ -0,0 +1,262 @@
+/*!
+ * # 索引定义的持久化 (Index Persistence)
+ *
+ * 图内置的简单查询索引（[`SimpleVertexQuery`](super::SimpleVertexQuery)）由调用方提供的
+ * 提取函数建立，函数本身无法序列化，从磁盘加载图之后索引需要手动重建。
+ * 本模块把"用哪个提取函数建立哪个索引"记录为可序列化的 [`IndexSpec`]：
+ *
+ * - 提取函数以名字登记在 [`ExtractorRegistry`] 中
+ * - [`register_index`](SlotMapGraph::register_index) 按定义建立索引并记录定义，
+ *   [`index_specs`](SlotMapGraph::index_specs) 取出全部定义，与图的数据一起保存
+ * - 加载后用同样登记了提取函数的注册表调用 [`rebuild_indexes`](SlotMapGraph::rebuild_indexes)，
+ *   按名字找回提取函数重建索引；有找不到的名字时返回列出全部缺失名字的 [`IndexLoadError`]，
+ *   图不做任何修改
+ *
+ * 开启 `serde` 特性后 [`IndexSpec`] 实现 `Serialize` / `Deserialize`。
+ * 元素类型声明的字段索引（`declared_index`）由类型本身决定，加载后自动可用，不需要定义。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::index::IndexEntry;
+ * use pi_slotmap_graph::index::persist::{ExtractorRegistry, IndexSpec};
+ * use smallvec::smallvec;
+ *
+ * let mut registry: ExtractorRegistry<u32> = ExtractorRegistry::new();
+ * registry.register("age", |_, age| smallvec![IndexEntry::Int(*age as i64)]);
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * graph.add_vertex(42);
+ * graph.register_index(IndexSpec::new("by_age", "age"), &registry).unwrap();
+ * let specs = graph.index_specs().to_vec();
+ *
+ * // 加载：重新插入数据后按保存的定义重建索引
+ * let mut loaded: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * loaded.add_vertex(42);
+ * loaded.rebuild_indexes(&specs, &registry).unwrap();
+ * assert_eq!(loaded.vertex_query().query_int(42).count(), 1);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use crate::index::IndexEntry;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+use smallvec::SmallVec;
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter};
+
+/// 一个索引的定义：索引名和建立它的提取函数名
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexSpec {
+    /// 索引名，同名的定义只保留最后注册的一个
+    pub name: String,
+    /// [`ExtractorRegistry`] 中提取函数的名字
+    pub extractor: String,
+}
+
+impl IndexSpec {
+    /// 创建索引定义
+    pub fn new(name: impl Into<String>, extractor: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            extractor: extractor.into(),
+        }
+    }
+}
+
+/// 为顶点产生索引项的提取函数
+type Extractor<V, K> = Box<dyn for<'a> Fn(VertexId<K>, &'a V) -> SmallVec<[IndexEntry<'a>; 2]> + Send + Sync>;
+
+/// 按名字登记的提取函数
+pub struct ExtractorRegistry<V, K: Key = DefaultKey> {
+    extractors: HashMap<String, Extractor<V, K>>,
+}
+
+impl<V, K: Key> Debug for ExtractorRegistry<V, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.extractors.keys()).finish()
+    }
+}
+
+impl<V, K: Key> Default for ExtractorRegistry<V, K> {
+    fn default() -> Self {
+        Self {
+            extractors: HashMap::new(),
+        }
+    }
+}
+
+impl<V, K: Key> ExtractorRegistry<V, K> {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 以 `name` 登记提取函数，替换同名的函数
+    pub fn register<F>(&mut self, name: impl Into<String>, extractor: F) -> &mut Self
+    where
+        F: for<'a> Fn(VertexId<K>, &'a V) -> SmallVec<[IndexEntry<'a>; 2]> + Send + Sync + 'static,
+    {
+        self.extractors.insert(name.into(), Box::new(extractor));
+        self
+    }
+
+    /// 是否登记了名为 `name` 的提取函数
+    pub fn contains(&self, name: &str) -> bool {
+        self.extractors.contains_key(name)
+    }
+}
+
+/// 索引定义引用了注册表中不存在的提取函数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexLoadError {
+    /// 缺失的提取函数名，按定义中首次出现的顺序，不重复
+    pub unknown: Vec<String>,
+}
+
+impl Display for IndexLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Unknown index extractors: {}", self.unknown.join(", "))
+    }
+}
+
+impl std::error::Error for IndexLoadError {}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 按定义为所有顶点建立索引并记录定义，同名的旧定义被替换
+    ///
+    /// 提取函数不在注册表中时返回错误，索引和定义都不做修改。
+    pub fn register_index(
+        &mut self,
+        spec: IndexSpec,
+        registry: &ExtractorRegistry<Vertex, K>,
+    ) -> Result<(), IndexLoadError> {
+        let Some(extractor) = registry.extractors.get(&spec.extractor) else {
+            return Err(IndexLoadError { unknown: vec![spec.extractor] });
+        };
+        self.index_all_vertices(extractor);
+        self.index_specs.retain(|existing| existing.name != spec.name);
+        self.index_specs.push(spec);
+        Ok(())
+    }
+
+    /// 经由 [`register_index`](Self::register_index) 或 [`rebuild_indexes`](Self::rebuild_indexes) 记录的索引定义
+    pub fn index_specs(&self) -> &[IndexSpec] {
+        &self.index_specs
+    }
+
+    /// 按保存的定义重建索引，通常在加载图之后调用
+    ///
+    /// 先检查全部提取函数都已登记，有缺失时返回列出所有缺失名字的错误且不做任何修改；
+    /// 之后依次建立每个索引并记录定义。
+    pub fn rebuild_indexes(
+        &mut self,
+        specs: &[IndexSpec],
+        registry: &ExtractorRegistry<Vertex, K>,
+    ) -> Result<(), IndexLoadError> {
+        let mut unknown: Vec<String> = Vec::new();
+        for spec in specs {
+            if !registry.contains(&spec.extractor) && !unknown.contains(&spec.extractor) {
+                unknown.push(spec.extractor.clone());
+            }
+        }
+        if !unknown.is_empty() {
+            return Err(IndexLoadError { unknown });
+        }
+        for spec in specs {
+            self.register_index(spec.clone(), registry)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::Graph;
+    use smallvec::smallvec;
+
+    fn registry() -> ExtractorRegistry<u32> {
+        let mut registry = ExtractorRegistry::new();
+        registry
+            .register("weight", |_, weight: &u32| smallvec![IndexEntry::Int(*weight as i64)])
+            .register("parity", |_, weight: &u32| {
+                smallvec![IndexEntry::Str(if weight.is_multiple_of(2) { "even" } else { "odd" })]
+            });
+        registry
+    }
+
+    /// 保存顶点数据和索引定义
+    fn saved() -> (Vec<u32>, Vec<IndexSpec>) {
+        let registry = registry();
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        for weight in 0..10 {
+            graph.add_vertex(weight);
+        }
+        graph.register_index(IndexSpec::new("by_weight", "weight"), &registry).unwrap();
+        graph.register_index(IndexSpec::new("by_parity", "parity"), &registry).unwrap();
+        // 同名定义被替换
+        graph.register_index(IndexSpec::new("by_parity", "parity"), &registry).unwrap();
+        assert_eq!(graph.index_specs().len(), 2);
+        (graph.vertices.values().copied().collect(), graph.index_specs().to_vec())
+    }
+
+    fn load(weights: &[u32]) -> SlotMapGraph<u32, ()> {
+        let mut graph = SlotMapGraph::new();
+        for &weight in weights {
+            graph.add_vertex(weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_rebuild_after_load() {
+        let (weights, specs) = saved();
+        let mut graph = load(&weights);
+        assert_eq!(graph.vertex_query().query_string("even").count(), 0);
+
+        graph.rebuild_indexes(&specs, &registry()).unwrap();
+        assert_eq!(graph.vertex_query().query_string("even").count(), 5);
+        assert_eq!(graph.vertex_query().range_int(3..6).count(), 3);
+        assert_eq!(graph.index_specs(), specs.as_slice());
+    }
+
+    #[test]
+    fn test_missing_extractor() {
+        let (weights, mut specs) = saved();
+        specs.push(IndexSpec::new("by_name", "name"));
+        specs.push(IndexSpec::new("by_name_again", "name"));
+        let mut partial: ExtractorRegistry<u32> = ExtractorRegistry::new();
+        partial.register("weight", |_, weight: &u32| smallvec![IndexEntry::Int(*weight as i64)]);
+
+        let mut graph = load(&weights);
+        let err = graph.rebuild_indexes(&specs, &partial).unwrap_err();
+        assert_eq!(err.unknown, vec!["parity".to_string(), "name".to_string()]);
+        assert_eq!(err.to_string(), "Unknown index extractors: parity, name");
+        // 出错时不建立任何索引
+        assert_eq!(graph.vertex_query().range_int(0..10).count(), 0);
+        assert!(graph.index_specs().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_specs_serde_roundtrip() {
+        let (weights, specs) = saved();
+        let json = serde_json::to_string(&(&weights, &specs)).unwrap();
+        let (weights, specs): (Vec<u32>, Vec<IndexSpec>) = serde_json::from_str(&json).unwrap();
+
+        let mut graph = load(&weights);
+        graph.rebuild_indexes(&specs, &registry()).unwrap();
+        assert_eq!(graph.vertex_query().query_string("odd").count(), 5);
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * # 范围索引 (RangeIndex)
  *
  * 提供高效的有序范围查询功能，基于 BTreeMap 实现。
@@ -15,6 +15,10 @@
  * - **范围查询**：O(log n + k) 时间复杂度，k为结果数量
  * - **边界支持**：包含/排除上下界的完整支持
  *
+ * ### 批量操作
+ * - **extend**：批量插入，重复的键值对被忽略
+ * - **retain**：按条件筛选键值对，一次遍历，删除变空的键
+ *
  * ## 使用场景
  *
  * - **时间范围**：按时间戳查询事件
@@ -50,8 +54,8 @@ use std::ops::{Bound, RangeBounds};
 /// # 示例
 ///
 /// ```rust
-/// use slotmap_graph::index::RangeIndex;
-/// use slotmap_graph::VertexId;
+/// use pi_slotmap_graph::index::RangeIndex;
+/// use pi_slotmap_graph::VertexId;
 /// use pi_slotmap::DefaultKey;
 /// use std::ops::Bound;
 ///
@@ -104,7 +108,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     ///
     /// let index: RangeIndex<u32, u32> = RangeIndex::new();
     /// assert!(index.is_empty());
@@ -132,7 +136,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     ///
     /// let mut index = RangeIndex::new();
     ///
@@ -146,6 +150,26 @@ where
         self.map.entry(key).or_default().insert(value)
     }
 
+    /// 批量插入键值对
+    ///
+    /// `BTreeMap` 无法预留容量，逐个插入。重复的键值对同样被忽略。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use pi_slotmap_graph::index::RangeIndex;
+    ///
+    /// let mut index = RangeIndex::new();
+    /// index.extend([(25, 1), (25, 2), (30, 1), (25, 1)]);
+    /// assert_eq!(index.get(&25).count(), 2);
+    /// assert_eq!(index.total_values_len(), 3);
+    /// ```
+    pub fn extend(&mut self, iter: impl IntoIterator<Item = (K, V)>) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+
     /// 移除键值对
     ///
     /// 如果删除后键对应的值集合为空，会自动删除该键。
@@ -162,7 +186,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     ///
     /// let mut index = RangeIndex::new();
     /// index.insert(25, 1);
@@ -187,6 +211,27 @@ where
         }
     }
 
+    /// 只保留 `f` 返回 `true` 的键值对
+    ///
+    /// 一次遍历全部键值对，筛选后值集合为空的键一并删除。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use pi_slotmap_graph::index::RangeIndex;
+    ///
+    /// let mut index = RangeIndex::new();
+    /// index.extend([(10, 1), (20, 2), (30, 3)]);
+    /// index.retain(|age, _| *age >= 20);
+    /// assert_eq!(index.range_bounds(), Some((&20, &30)));
+    /// ```
+    pub fn retain(&mut self, mut f: impl FnMut(&K, &V) -> bool) {
+        self.map.retain(|key, values| {
+            values.retain(|value| f(key, value));
+            !values.is_empty()
+        });
+    }
+
     /// 查询键对应的所有值
     ///
     /// 返回值的迭代器，如果键不存在则返回空迭代器。
@@ -202,7 +247,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     ///
     /// let mut index = RangeIndex::new();
     /// index.insert(25, 1);
@@ -234,7 +279,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     /// use std::ops::Bound;
     ///
     /// let mut index = RangeIndex::new();
@@ -274,7 +319,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     ///
     /// let mut index = RangeIndex::new();
     /// for i in 10..20 {
@@ -301,7 +346,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     ///
     /// let mut index = RangeIndex::new();
     /// for i in 10..20 {
@@ -352,7 +397,7 @@ where
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::index::RangeIndex;
+    /// use pi_slotmap_graph::index::RangeIndex;
     ///
     /// let mut index = RangeIndex::new();
     /// index.insert(10, 1);
@@ -552,4 +597,24 @@ mod tests {
         let end_unbounded: Vec<_> = index.range(20..).collect();
         assert_eq!(end_unbounded.len(), 8); // 20, 30, 40, 50, 60, 70, 80, 90
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bulk_operations() {
+        let mut index = RangeIndex::new();
+        index.extend([(10, 1), (10, 2), (20, 1), (20, 3), (30, 1), (10, 1)]);
+        assert_eq!((index.keys_len(), index.total_values_len()), (3, 5));
+
+        // 值 1 从全部键下移除，30 随之变空被删除
+        index.retain(|_, value| *value != 1);
+        assert_eq!(index.range_bounds(), Some((&10, &20)));
+        assert_eq!(index.range(..).collect::<HashSet<_>>(), HashSet::from([2, 3]));
+
+        index.extend([(30, 4), (30, 5), (40, 6)]);
+        index.retain(|key, value| *key != 20 && *value != 6);
+        assert_eq!(index.keys().copied().collect::<Vec<_>>(), vec![10, 30]);
+        assert_eq!(index.total_values_len(), 3);
+
+        index.retain(|_, _| false);
+        assert!(index.is_empty() && index.range_bounds().is_none());
+    }
+}
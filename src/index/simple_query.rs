@@ -1,49 +1,188 @@
-/**
+/*!
  * # 简化查询系统 (Simple Query System)
  *
  * 提供基础的查询功能，支持基本的哈希和范围查询。
  * 这个模块避免了复杂的 trait 对象，提供了简单实用的查询接口。
+ *
+ * 大量重复的短字符串可以改用符号索引（`insert_symbol` / `query_symbol`）：
+ * 字符串经内置的 [`Interner`] 驻留为 [`Symbol`]，调用方在顶点数据中保存符号，
+ * 需要时再通过 [`resolve`](SimpleVertexQuery::resolve) 取回字符串。
+ *
+ * 批量导入后可以用 [`IndexEntry`] 描述每个顶点的索引项，
+ * 一次遍历写入所有索引（见 `SlotMapGraph::index_all_vertices`）。
  */
 
+use crate::index::{IndexValue, Interner, Symbol};
+use crate::memory::{btree_map_bytes, hash_map_bytes, hash_set_bytes};
 use crate::VertexId;
 use graph_api_lib::Value;
+use pi_slotmap::{DefaultKey, Key};
+use smallvec::SmallVec;
 use std::collections::HashMap;
 use std::ops::Range;
 
+/// 有序范围查询的方向
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum KeyOrder {
+    /// 按键升序
+    #[default]
+    Ascending,
+    /// 按键降序
+    Descending,
+}
+
+/// 把同一键下的顶点按 `as_ffi` 排序
+fn sorted_ids<K: Key>(set: &std::collections::HashSet<VertexId<K>>) -> Vec<VertexId<K>> {
+    let mut ids: Vec<_> = set.iter().copied().collect();
+    ids.sort_unstable_by_key(VertexId::as_ffi);
+    ids
+}
+
+/// 属性索引的键，把不同宽度的数值统一为同一种表示
+///
+/// 所有整数统一为 `i128`，浮点数统一为 `f64` 的位模式（`-0.0` 视为 `0.0`）。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum PropertyKey {
+    Str(String),
+    Int(i128),
+    Float(u64),
+    Bool(bool),
+}
+
+impl PropertyKey {
+    /// 无法索引的值（NaN、超出 `i128` 的 `u128`）返回 `None`
+    fn from_value(value: &IndexValue) -> Option<Self> {
+        Some(match value {
+            IndexValue::String(v) => PropertyKey::Str(v.clone()),
+            IndexValue::U32(v) => PropertyKey::Int(*v as i128),
+            IndexValue::U64(v) => PropertyKey::Int(*v as i128),
+            IndexValue::U128(v) => PropertyKey::Int(i128::try_from(*v).ok()?),
+            IndexValue::I32(v) => PropertyKey::Int(*v as i128),
+            IndexValue::I64(v) => PropertyKey::Int(*v as i128),
+            IndexValue::I128(v) => PropertyKey::Int(*v),
+            IndexValue::F32(v) => return Self::from_float(*v as f64),
+            IndexValue::F64(v) => return Self::from_float(*v),
+            IndexValue::Bool(v) => PropertyKey::Bool(*v),
+        })
+    }
+
+    fn from_float(value: f64) -> Option<Self> {
+        if value.is_nan() {
+            return None;
+        }
+        // 0.0 和 -0.0 相等，统一位模式
+        let value = if value == 0.0 { 0.0 } else { value };
+        Some(PropertyKey::Float(value.to_bits()))
+    }
+}
+
+/// 批量建立索引时顶点的一个索引项
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexEntry<'a> {
+    /// 写入字符串索引
+    Str(&'a str),
+    /// 写入整数索引及范围索引
+    Int(i64),
+    /// 写入浮点数索引，NaN 被忽略
+    Float(f64),
+}
+
 /// 简单顶点索引查询器
 ///
 /// 这个结构体提供了基础的顶点查询功能，
-/// 支持按字符串和整数进行查询和范围查询。
+/// 支持按字符串和整数进行查询和范围查询，以及按属性名和值的精确查询。
 #[derive(Debug, Clone)]
-pub struct SimpleVertexQuery {
+pub struct SimpleVertexQuery<K: Key = DefaultKey> {
     /// 字符串哈希索引
-    string_index: HashMap<String, std::collections::HashSet<VertexId>>,
+    string_index: HashMap<String, std::collections::HashSet<VertexId<K>>>,
     /// 整数哈希索引
-    int_index: HashMap<i64, std::collections::HashSet<VertexId>>,
+    int_index: HashMap<i64, std::collections::HashSet<VertexId<K>>>,
     /// 整数范围索引
-    int_range_index: std::collections::BTreeMap<i64, std::collections::HashSet<VertexId>>,
+    int_range_index: std::collections::BTreeMap<i64, std::collections::HashSet<VertexId<K>>>,
+    /// 浮点数哈希索引，键为规范化后的位模式
+    float_index: HashMap<u64, std::collections::HashSet<VertexId<K>>>,
+    /// 属性索引：属性名 -> 属性值 -> 顶点集合
+    property_index: HashMap<String, HashMap<PropertyKey, std::collections::HashSet<VertexId<K>>>>,
+    /// 符号索引
+    symbol_index: HashMap<Symbol, std::collections::HashSet<VertexId<K>>>,
+    /// 符号索引使用的字符串驻留器
+    interner: Interner,
 }
 
-impl SimpleVertexQuery {
+impl<K: Key> SimpleVertexQuery<K> {
     /// 创建新的简单顶点查询器
     pub fn new() -> Self {
         Self {
             string_index: HashMap::new(),
             int_index: HashMap::new(),
             int_range_index: std::collections::BTreeMap::new(),
+            float_index: HashMap::new(),
+            property_index: HashMap::new(),
+            symbol_index: HashMap::new(),
+            interner: Interner::new(),
         }
     }
 
     /// 插入字符串值
-    pub fn insert_string(&mut self, value: &str, vertex_id: VertexId) {
+    pub fn insert_string(&mut self, value: &str, vertex_id: VertexId<K>) {
         self.string_index
             .entry(value.to_string())
             .or_default()
             .insert(vertex_id);
     }
 
+    /// 移除字符串值
+    pub fn remove_string(&mut self, value: &str, vertex_id: VertexId<K>) {
+        if let Some(set) = self.string_index.get_mut(value) {
+            set.remove(&vertex_id);
+            if set.is_empty() {
+                self.string_index.remove(value);
+            }
+        }
+    }
+
+    /// 驻留字符串并按其符号索引顶点，返回该符号
+    pub fn insert_symbol(&mut self, value: &str, vertex_id: VertexId<K>) -> Symbol {
+        let symbol = self.interner.intern(value);
+        self.symbol_index.entry(symbol).or_default().insert(vertex_id);
+        symbol
+    }
+
+    /// 从符号索引中移除顶点，符号本身保持有效
+    pub fn remove_symbol(&mut self, symbol: Symbol, vertex_id: VertexId<K>) {
+        if let Some(set) = self.symbol_index.get_mut(&symbol) {
+            set.remove(&vertex_id);
+            if set.is_empty() {
+                self.symbol_index.remove(&symbol);
+            }
+        }
+    }
+
+    /// 根据符号查询顶点
+    pub fn query_symbol(&self, symbol: Symbol) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
+        match self.symbol_index.get(&symbol) {
+            Some(set) => Box::new(set.iter().copied()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    /// 已驻留字符串的符号，未驻留时返回 `None`
+    pub fn symbol(&self, value: &str) -> Option<Symbol> {
+        self.interner.get(value)
+    }
+
+    /// 符号对应的字符串
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.interner.resolve(symbol)
+    }
+
+    /// 符号索引使用的字符串驻留器
+    pub fn interner(&self) -> &Interner {
+        &self.interner
+    }
+
     /// 插入整数值
-    pub fn insert_int(&mut self, value: i64, vertex_id: VertexId) {
+    pub fn insert_int(&mut self, value: i64, vertex_id: VertexId<K>) {
         // 插入到哈希索引
         self.int_index.entry(value).or_default().insert(vertex_id);
         // 插入到范围索引
@@ -53,31 +192,154 @@ impl SimpleVertexQuery {
             .insert(vertex_id);
     }
 
+    /// 插入浮点数值
+    ///
+    /// `-0.0` 与 `0.0` 视为同一个值，NaN 不会被索引。
+    pub fn insert_float(&mut self, value: f64, vertex_id: VertexId<K>) {
+        if let Some(PropertyKey::Float(bits)) = PropertyKey::from_float(value) {
+            self.float_index.entry(bits).or_default().insert(vertex_id);
+        }
+    }
+
+    /// 插入一个索引项
+    pub fn insert_entry(&mut self, entry: IndexEntry<'_>, vertex_id: VertexId<K>) {
+        match entry {
+            IndexEntry::Str(value) => self.insert_string(value, vertex_id),
+            IndexEntry::Int(value) => self.insert_int(value, vertex_id),
+            IndexEntry::Float(value) => self.insert_float(value, vertex_id),
+        }
+    }
+
+    /// 批量插入索引项
+    ///
+    /// `additional` 是预计的顶点数，每种索引在第一次出现对应的索引项时按它预留空间，
+    /// 未出现的索引不会分配。与逐个插入一样，重复的 (值, 顶点) 只保存一次。
+    pub fn insert_entries<'a, I>(&mut self, additional: usize, entries: I)
+    where
+        I: IntoIterator<Item = (VertexId<K>, SmallVec<[IndexEntry<'a>; 2]>)>,
+    {
+        let mut reserved = [false; 3];
+        for (vertex_id, vertex_entries) in entries {
+            for entry in vertex_entries {
+                let kind = match entry {
+                    IndexEntry::Str(_) => 0,
+                    IndexEntry::Int(_) => 1,
+                    IndexEntry::Float(_) => 2,
+                };
+                if !reserved[kind] {
+                    reserved[kind] = true;
+                    match kind {
+                        0 => self.string_index.reserve(additional),
+                        1 => self.int_index.reserve(additional),
+                        _ => self.float_index.reserve(additional),
+                    }
+                }
+                self.insert_entry(entry, vertex_id);
+            }
+        }
+    }
+
+    /// 插入命名属性值
+    ///
+    /// 整数按数值索引，不区分宽度和符号（`U64(30)` 与 `I32(30)` 相同），浮点数同理；
+    /// NaN 和超出 `i128` 的 `u128` 不会被索引。
+    pub fn insert_property(&mut self, name: &str, value: &IndexValue, vertex_id: VertexId<K>) {
+        if let Some(key) = PropertyKey::from_value(value) {
+            self.property_index
+                .entry(name.to_string())
+                .or_default()
+                .entry(key)
+                .or_default()
+                .insert(vertex_id);
+        }
+    }
+
+    /// 移除命名属性值
+    pub fn remove_property(&mut self, name: &str, value: &IndexValue, vertex_id: VertexId<K>) {
+        let (Some(values), Some(key)) = (self.property_index.get_mut(name), PropertyKey::from_value(value)) else {
+            return;
+        };
+        if let Some(set) = values.get_mut(&key) {
+            set.remove(&vertex_id);
+            if set.is_empty() {
+                values.remove(&key);
+            }
+        }
+        if values.is_empty() {
+            self.property_index.remove(name);
+        }
+    }
+
+    /// 根据属性名和值查询顶点
+    pub fn query_property(&self, name: &str, value: &IndexValue) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
+        let set = PropertyKey::from_value(value)
+            .and_then(|key| self.property_index.get(name)?.get(&key));
+        match set {
+            Some(set) => Box::new(set.iter().copied()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
     /// 根据字符串查询顶点
-    pub fn query_string(&self, value: &str) -> Box<dyn Iterator<Item = VertexId> + '_> {
+    pub fn query_string(&self, value: &str) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
         match self.string_index.get(value) {
             Some(set) => Box::new(set.iter().copied()),
             None => Box::new(std::iter::empty()),
         }
     }
 
+    /// 顶点是否以该字符串被索引
+    pub fn contains_string(&self, value: &str, vertex_id: VertexId<K>) -> bool {
+        self.string_index.get(value).is_some_and(|set| set.contains(&vertex_id))
+    }
+
     /// 根据整数查询顶点
-    pub fn query_int(&self, value: i64) -> Box<dyn Iterator<Item = VertexId> + '_> {
+    pub fn query_int(&self, value: i64) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
         match self.int_index.get(&value) {
             Some(set) => Box::new(set.iter().copied()),
             None => Box::new(std::iter::empty()),
         }
     }
 
+    /// 根据浮点数查询顶点
+    pub fn query_float(&self, value: f64) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
+        let set = match PropertyKey::from_float(value) {
+            Some(PropertyKey::Float(bits)) => self.float_index.get(&bits),
+            _ => None,
+        };
+        match set {
+            Some(set) => Box::new(set.iter().copied()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
     /// 整数范围查询
-    pub fn range_int(&self, range: Range<i64>) -> impl Iterator<Item = VertexId> + '_ {
+    pub fn range_int(&self, range: Range<i64>) -> impl Iterator<Item = VertexId<K>> + '_ {
         self.int_range_index
             .range(range)
             .flat_map(|(_, set)| set.iter().copied())
     }
 
+    /// 整数范围查询，按键升序产生 `(键, 顶点)`
+    ///
+    /// 同一键下的顶点按 [`VertexId::as_ffi`] 升序排列，结果与插入顺序无关。
+    /// 排序只在遍历到某个键时进行，配合 `take` 取前几项时不会排序全部结果。
+    pub fn range_int_ordered(&self, range: Range<i64>) -> impl Iterator<Item = (i64, VertexId<K>)> + '_ {
+        self.int_range_index
+            .range(range)
+            .flat_map(|(&key, set)| sorted_ids(set).into_iter().map(move |id| (key, id)))
+    }
+
+    /// 整数范围查询，按键降序产生 `(键, 顶点)`，恰为 [`range_int_ordered`](Self::range_int_ordered) 的逆序
+    pub fn range_int_ordered_desc(&self, range: Range<i64>) -> impl Iterator<Item = (i64, VertexId<K>)> + '_ {
+        self.int_range_index
+            .range(range)
+            .rev()
+            .flat_map(|(&key, set)| sorted_ids(set).into_iter().rev().map(move |id| (key, id)))
+    }
+
     /// 从 Value 枚举查询
-    pub fn query_value(&self, value: &Value) -> Box<dyn Iterator<Item = VertexId> + '_> {
+    pub fn query_value(&self, value: &Value) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
         match value {
             Value::Str(s) => Box::new(self.query_string(s)),
             Value::I8(v) => Box::new(self.query_int(*v as i64)),
@@ -88,13 +350,15 @@ impl SimpleVertexQuery {
             Value::U16(v) => Box::new(self.query_int(*v as i64)),
             Value::U32(v) => Box::new(self.query_int(*v as i64)),
             Value::U64(v) => Box::new(self.query_int(*v as i64)),
+            Value::F32(v) => Box::new(self.query_float(*v as f64)),
+            Value::F64(v) => Box::new(self.query_float(*v)),
             // 其他类型暂不支持
             _ => Box::new(std::iter::empty()),
         }
     }
 
     /// 从 Value 范围查询
-    pub fn range_value(&self, range: &Range<Value>) -> Box<dyn Iterator<Item = VertexId> + '_> {
+    pub fn range_value(&self, range: &Range<Value>) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
         match (&range.start, &range.end) {
             (Value::I8(start), Value::I8(end)) => {
                 Box::new(self.range_int(*start as i64..*end as i64))
@@ -125,7 +389,7 @@ impl SimpleVertexQuery {
     }
 
     /// 移除顶点
-    pub fn remove_vertex(&mut self, vertex_id: VertexId) {
+    pub fn remove_vertex(&mut self, vertex_id: VertexId<K>) {
         // 从字符串索引中移除
         self.string_index.retain(|_, set| {
             set.remove(&vertex_id);
@@ -143,27 +407,85 @@ impl SimpleVertexQuery {
             set.remove(&vertex_id);
             !set.is_empty()
         });
+
+        // 从浮点数索引中移除
+        self.float_index.retain(|_, set| {
+            set.remove(&vertex_id);
+            !set.is_empty()
+        });
+
+        // 从符号索引中移除
+        self.symbol_index.retain(|_, set| {
+            set.remove(&vertex_id);
+            !set.is_empty()
+        });
+
+        // 从属性索引中移除
+        self.property_index.retain(|_, values| {
+            values.retain(|_, set| {
+                set.remove(&vertex_id);
+                !set.is_empty()
+            });
+            !values.is_empty()
+        });
     }
 
     /// 获取统计信息
     pub fn stats(&self) -> String {
         format!(
-            "String Index: {} keys\nInteger Index: {} keys\nRange Index: {} keys",
+            "String Index: {} keys\nInteger Index: {} keys\nRange Index: {} keys\nFloat Index: {} keys\nProperty Index: {} names\nSymbol Index: {} keys",
             self.string_index.len(),
             self.int_index.len(),
-            self.int_range_index.len()
+            self.int_range_index.len(),
+            self.float_index.len(),
+            self.property_index.len(),
+            self.symbol_index.len()
         )
     }
 
     /// 清空所有索引
+    ///
+    /// 驻留器不会被清空，顶点数据中已保存的符号仍可解析。
     pub fn clear(&mut self) {
+        self.symbol_index.clear();
         self.string_index.clear();
         self.int_index.clear();
         self.int_range_index.clear();
+        self.float_index.clear();
+        self.property_index.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节，计入字符串键和属性名的长度
+    pub fn memory_estimate(&self) -> usize {
+        let strings: usize = self
+            .string_index
+            .iter()
+            .map(|(value, ids)| value.capacity() + hash_set_bytes(ids))
+            .sum();
+        let properties: usize = self
+            .property_index
+            .iter()
+            .map(|(name, values)| {
+                name.capacity() + hash_map_bytes(values) + values.values().map(hash_set_bytes).sum::<usize>()
+            })
+            .sum();
+        hash_map_bytes(&self.string_index)
+            + strings
+            + hash_map_bytes(&self.int_index)
+            + self.int_index.values().map(hash_set_bytes).sum::<usize>()
+            + btree_map_bytes(&self.int_range_index)
+            + self.int_range_index.values().map(hash_set_bytes).sum::<usize>()
+            + hash_map_bytes(&self.float_index)
+            + self.float_index.values().map(hash_set_bytes).sum::<usize>()
+            + hash_map_bytes(&self.property_index)
+            + properties
+            + hash_map_bytes(&self.symbol_index)
+            + self.symbol_index.values().map(hash_set_bytes).sum::<usize>()
+            + self.interner.memory_estimate()
     }
 }
 
-impl Default for SimpleVertexQuery {
+impl<K: Key> Default for SimpleVertexQuery<K> {
     fn default() -> Self {
         Self::new()
     }
@@ -189,6 +511,10 @@ mod tests {
         let results: Vec<_> = query.query_string("test").collect();
         assert!(!results.is_empty()); // At least one result should be found
 
+        query.remove_string("test", id1);
+        query.remove_string("test", id2);
+        assert_eq!(query.query_string("test").count(), 0);
+
         // 插入整数值
         query.insert_int(42, id1);
 
@@ -200,6 +526,88 @@ mod tests {
         assert_eq!(range_results.len(), 1);
     }
 
+    #[test]
+    fn test_range_int_ordered() {
+        let mut vertices: pi_slotmap::SlotMap<DefaultKey, ()> = pi_slotmap::SlotMap::new();
+        let ids: Vec<_> = (0..6).map(|_| VertexId::new(vertices.insert(()))).collect();
+        let mut query = SimpleVertexQuery::new();
+        // 同一键下按逆序插入，结果仍按 ID 排列
+        for (age, &id) in [30, 50, 40, 50, 30, 50].iter().zip(&ids).rev() {
+            query.insert_int(*age, id);
+        }
+
+        let ascending: Vec<_> = query.range_int_ordered(0..100).collect();
+        assert_eq!(
+            ascending,
+            vec![(30, ids[0]), (30, ids[4]), (40, ids[2]), (50, ids[1]), (50, ids[3]), (50, ids[5])]
+        );
+        let mut descending: Vec<_> = query.range_int_ordered_desc(0..100).collect();
+        descending.reverse();
+        assert_eq!(descending, ascending);
+
+        let range: Vec<_> = query.range_int_ordered_desc(30..50).map(|(age, _)| age).collect();
+        assert_eq!(range, vec![40, 30, 30]);
+        assert_eq!(query.range_int_ordered(60..100).count(), 0);
+    }
+
+    #[test]
+    fn test_symbol_index() {
+        let mut graph: crate::SlotMapGraph<(), ()> = crate::SlotMapGraph::new();
+        let labels = ["Engineer", "Designer", "Manager"];
+        let ids: Vec<_> = (0..100_000)
+            .map(|i| {
+                let id = graph_api_lib::Graph::add_vertex(&mut graph, ());
+                graph.vertex_query_mut().insert_symbol(labels[i % labels.len()], id);
+                id
+            })
+            .collect();
+
+        let query = graph.vertex_query_mut();
+        assert_eq!(query.interner().len(), 3);
+        let engineer = query.symbol("Engineer").unwrap();
+        assert_eq!(query.resolve(engineer), Some("Engineer"));
+        assert_eq!(query.query_symbol(engineer).count(), 33_334);
+        assert_eq!(query.symbol("Unknown"), None);
+
+        query.remove_symbol(engineer, ids[0]);
+        query.remove_vertex(ids[3]);
+        assert_eq!(query.query_symbol(engineer).count(), 33_332);
+
+        // 清空索引后符号仍可解析
+        query.clear();
+        assert_eq!(query.query_symbol(engineer).count(), 0);
+        assert_eq!(query.resolve(engineer), Some("Engineer"));
+    }
+
+    #[test]
+    fn test_index_all_vertices() {
+        use graph_api_lib::Graph;
+        use smallvec::smallvec;
+
+        let mut graph: crate::SlotMapGraph<u32, ()> = crate::SlotMapGraph::new();
+        let ids: Vec<_> = (0..10_000).map(|i| graph.add_vertex(i)).collect();
+        fn extract(_: VertexId, value: &u32) -> SmallVec<[IndexEntry<'_>; 2]> {
+            smallvec![
+                IndexEntry::Str(if value.is_multiple_of(2) { "even" } else { "odd" }),
+                IndexEntry::Int(*value as i64),
+                IndexEntry::Float(*value as f64 / 2.0),
+            ]
+        }
+
+        graph.index_all_vertices(extract);
+        // 重复建立索引不会产生重复的项
+        graph.index_all_vertices(extract);
+
+        let query = graph.vertex_query();
+        assert_eq!(query.query_string("even").count(), 5_000);
+        assert_eq!(query.query_int(1234).collect::<Vec<_>>(), vec![ids[1234]]);
+        assert_eq!(query.range_int(100..200).count(), 100);
+        assert_eq!(query.query_float(617.0).collect::<Vec<_>>(), vec![ids[1234]]);
+        assert_eq!(query.query_value(&Value::F64(0.5)).collect::<Vec<_>>(), vec![ids[1]]);
+        assert_eq!(query.query_float(-0.0).collect::<Vec<_>>(), vec![ids[0]]);
+        assert_eq!(query.query_float(f64::NAN).count(), 0);
+    }
+
     #[test]
     fn test_query_from_value() {
         let mut query = SimpleVertexQuery::new();
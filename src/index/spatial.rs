@@ -0,0 +1,301 @@
+/*!
+ * # 空间索引 (SpatialIndex)
+ *
+ * 为带有二维坐标的顶点提供"某点附近的顶点"查询，例如为寻路选取起点。
+ * 平面被划分为边长固定的正方形网格，每个格子记录落在其中的顶点：
+ *
+ * - [`query_radius`](SpatialIndex::query_radius)：只检查与圆的外接正方形相交的格子
+ * - [`nearest`](SpatialIndex::nearest)：从中心所在的格子向外逐圈扩展，
+ *   已找到的第 k 近距离不超过尚未检查的格子的最小距离时停止；
+ *   一圈的格子数超过非空格子数时改为检查全部顶点
+ *
+ * 格子边长接近常见查询半径时效果最好；点分布极不均匀时退化为检查较多格子。
+ * 坐标不能是 NaN 或无穷大，这样的点不会被加入索引。
+ */
+
+use crate::id::VertexId;
+use crate::memory::hash_map_bytes;
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::HashMap;
+
+/// 二维坐标
+pub type Point = [f32; 2];
+
+/// 格子坐标
+type Cell = (i32, i32);
+
+/// 两点距离的平方
+#[inline]
+fn distance_squared(a: Point, b: Point) -> f32 {
+    let dx = a[0] - b[0];
+    let dy = a[1] - b[1];
+    dx * dx + dy * dy
+}
+
+/// 基于均匀网格的顶点空间索引
+#[derive(Debug, Clone)]
+pub struct SpatialIndex<K: Key = DefaultKey> {
+    /// 格子边长
+    cell_size: f32,
+    /// 每个非空格子中的顶点
+    cells: HashMap<Cell, Vec<VertexId<K>>>,
+    /// 每个顶点的坐标
+    positions: HashMap<VertexId<K>, Point>,
+}
+
+impl<K: Key> SpatialIndex<K> {
+    /// 创建格子边长为 `cell_size` 的空索引
+    ///
+    /// # Panics
+    ///
+    /// `cell_size` 不是正的有限数时 panic。
+    pub fn new(cell_size: f32) -> Self {
+        assert!(cell_size.is_finite() && cell_size > 0.0, "cell size must be positive and finite");
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// 格子边长
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// 已索引的顶点数量
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// 是否没有任何顶点
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// 所有顶点及其坐标，顺序不确定
+    pub fn positions(&self) -> impl Iterator<Item = (VertexId<K>, Point)> + '_ {
+        self.positions.iter().map(|(&id, &point)| (id, point))
+    }
+
+    /// 顶点的坐标
+    pub fn position(&self, id: VertexId<K>) -> Option<Point> {
+        self.positions.get(&id).copied()
+    }
+
+    /// 设置顶点的坐标，替换之前的坐标；坐标含 NaN 或无穷大时返回 `false` 且不做修改
+    pub fn insert(&mut self, point: Point, id: VertexId<K>) -> bool {
+        if !point.iter().all(|c| c.is_finite()) {
+            return false;
+        }
+        self.remove(id);
+        self.cells.entry(self.cell_of(point)).or_default().push(id);
+        self.positions.insert(id, point);
+        true
+    }
+
+    /// 移除顶点，返回其坐标
+    pub fn remove(&mut self, id: VertexId<K>) -> Option<Point> {
+        let point = self.positions.remove(&id)?;
+        let cell = self.cell_of(point);
+        if let Some(ids) = self.cells.get_mut(&cell) {
+            if let Some(pos) = ids.iter().position(|&other| other == id) {
+                ids.swap_remove(pos);
+            }
+            if ids.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+        Some(point)
+    }
+
+    /// 清空索引，保留格子边长
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.positions.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节
+    pub fn memory_estimate(&self) -> usize {
+        let members: usize = self.cells.values().map(|ids| ids.capacity() * std::mem::size_of::<VertexId<K>>()).sum();
+        hash_map_bytes(&self.cells) + members + hash_map_bytes(&self.positions)
+    }
+
+    /// 与 `center` 的距离不超过 `radius` 的顶点，顺序不确定
+    pub fn query_radius(&self, center: Point, radius: f32) -> impl Iterator<Item = VertexId<K>> + '_ {
+        let radius_squared = radius * radius;
+        let (min, max) = if radius >= 0.0 && center.iter().all(|c| c.is_finite()) {
+            (
+                self.cell_of([center[0] - radius, center[1] - radius]),
+                self.cell_of([center[0] + radius, center[1] + radius]),
+            )
+        } else {
+            // 空范围
+            ((0, 0), (-1, -1))
+        };
+        // 外接正方形覆盖的格子多于非空格子时改为遍历非空格子
+        let spans = (max.0 as i64 - min.0 as i64 + 1).max(0) * (max.1 as i64 - min.1 as i64 + 1).max(0);
+        let cells: Box<dyn Iterator<Item = &Vec<VertexId<K>>> + '_> = if spans > self.cells.len() as i64 {
+            Box::new(
+                self.cells
+                    .iter()
+                    .filter(move |(cell, _)| (min.0..=max.0).contains(&cell.0) && (min.1..=max.1).contains(&cell.1))
+                    .map(|(_, ids)| ids),
+            )
+        } else {
+            Box::new((min.0..=max.0).flat_map(move |x| (min.1..=max.1).filter_map(move |y| self.cells.get(&(x, y)))))
+        };
+        cells
+            .flat_map(|ids| ids.iter().copied())
+            .filter(move |id| distance_squared(self.positions[id], center) <= radius_squared)
+    }
+
+    /// 距 `center` 最近的 `k` 个顶点及其距离，按距离升序，距离相等时按 [`VertexId::as_ffi`] 升序
+    pub fn nearest(&self, center: Point, k: usize) -> Vec<(VertexId<K>, f32)> {
+        if k == 0 || self.is_empty() || !center.iter().all(|c| c.is_finite()) {
+            return Vec::new();
+        }
+        let origin = self.cell_of(center);
+        let max_ring = self
+            .cells
+            .keys()
+            .map(|cell| (cell.0 as i64 - origin.0 as i64).abs().max((cell.1 as i64 - origin.1 as i64).abs()))
+            .max()
+            .unwrap_or(0);
+
+        let by_distance = |a: &(f32, VertexId<K>), b: &(f32, VertexId<K>)| {
+            a.0.total_cmp(&b.0).then_with(|| a.1.as_ffi().cmp(&b.1.as_ffi()))
+        };
+        let mut found: Vec<(f32, VertexId<K>)> = Vec::new();
+        for ring in 0..=max_ring {
+            // 一圈的格子数超过非空格子总数时（例如远处有离群点），直接检查全部顶点
+            if 8 * ring > self.cells.len() as i64 {
+                found = self.positions.iter().map(|(&id, &p)| (distance_squared(p, center), id)).collect();
+                break;
+            }
+            for cell in ring_cells(origin, ring) {
+                if let Some(ids) = self.cells.get(&cell) {
+                    found.extend(ids.iter().map(|&id| (distance_squared(self.positions[&id], center), id)));
+                }
+            }
+            // 第 ring + 1 圈及更外的格子与中心的距离至少为 ring 个格子边长
+            let reach = ring as f32 * self.cell_size;
+            if found.len() >= k {
+                found.sort_by(by_distance);
+                if found[k - 1].0 <= reach * reach {
+                    break;
+                }
+            }
+        }
+        found.sort_by(by_distance);
+        found.truncate(k);
+        found.into_iter().map(|(d, id)| (id, d.sqrt())).collect()
+    }
+
+    /// 坐标所在的格子，超出 `i32` 范围的坐标被截断到边界格子
+    fn cell_of(&self, point: Point) -> Cell {
+        let axis = |c: f32| (c / self.cell_size).floor() as i32;
+        (axis(point[0]), axis(point[1]))
+    }
+}
+
+/// 与 `origin` 的切比雪夫距离恰为 `ring` 的格子
+fn ring_cells(origin: Cell, ring: i64) -> impl Iterator<Item = Cell> {
+    let (ox, oy) = (origin.0 as i64, origin.1 as i64);
+    let cells: Box<dyn Iterator<Item = (i64, i64)>> = if ring == 0 {
+        Box::new(std::iter::once((ox, oy)))
+    } else {
+        let top_bottom = (-ring..=ring).flat_map(move |dx| [(ox + dx, oy - ring), (ox + dx, oy + ring)]);
+        let sides = (-ring + 1..ring).flat_map(move |dy| [(ox - ring, oy + dy), (ox + ring, oy + dy)]);
+        Box::new(top_bottom.chain(sides))
+    };
+    cells
+        .filter(|&(x, y)| i32::try_from(x).is_ok() && i32::try_from(y).is_ok())
+        .map(|(x, y)| (x as i32, y as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pi_slotmap::SlotMap;
+
+    /// 线性同余随机数，范围 [0, 1)
+    fn lcg(state: &mut u64) -> f32 {
+        *state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (*state >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    fn random_index(count: usize, cell_size: f32) -> (SpatialIndex, Vec<(VertexId, Point)>) {
+        let mut keys: SlotMap<DefaultKey, ()> = SlotMap::new();
+        let mut index = SpatialIndex::new(cell_size);
+        let mut state = 7;
+        let points: Vec<_> = (0..count)
+            .map(|_| {
+                let id = VertexId::new(keys.insert(()));
+                let point = [lcg(&mut state) * 200.0 - 100.0, lcg(&mut state) * 200.0 - 100.0];
+                assert!(index.insert(point, id));
+                (id, point)
+            })
+            .collect();
+        (index, points)
+    }
+
+    fn sorted(mut ids: Vec<VertexId>) -> Vec<VertexId> {
+        ids.sort_by_key(VertexId::as_ffi);
+        ids
+    }
+
+    #[test]
+    fn test_radius_matches_brute_force() {
+        let (index, points) = random_index(500, 7.5);
+        for (center, radius) in [([0.0, 0.0], 10.0), ([-90.0, 95.0], 25.0), ([13.3, -40.1], 0.5), ([0.0, 0.0], 500.0)] {
+            let expected: Vec<_> = points
+                .iter()
+                .filter(|(_, p)| distance_squared(*p, center) <= radius * radius)
+                .map(|(id, _)| *id)
+                .collect();
+            assert_eq!(sorted(index.query_radius(center, radius).collect()), sorted(expected));
+        }
+        assert_eq!(index.query_radius([0.0, 0.0], -1.0).count(), 0);
+    }
+
+    #[test]
+    fn test_nearest_ordering() {
+        let (index, points) = random_index(300, 4.0);
+        for center in [[0.0, 0.0], [99.0, -99.0], [500.0, 500.0]] {
+            let mut expected: Vec<_> = points.iter().map(|(id, p)| (distance_squared(*p, center), *id)).collect();
+            expected.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.as_ffi().cmp(&b.1.as_ffi())));
+            let nearest = index.nearest(center, 10);
+            let ids: Vec<_> = nearest.iter().map(|(id, _)| *id).collect();
+            let expected_ids: Vec<_> = expected.iter().take(10).map(|(_, id)| *id).collect();
+            assert_eq!(ids, expected_ids);
+            assert!(nearest.windows(2).all(|w| w[0].1 <= w[1].1));
+        }
+        assert_eq!(index.nearest([0.0, 0.0], 1000).len(), 300);
+        assert!(index.nearest([0.0, 0.0], 0).is_empty());
+    }
+
+    #[test]
+    fn test_insert_replace_and_remove() {
+        let mut keys: SlotMap<DefaultKey, ()> = SlotMap::new();
+        let a = VertexId::new(keys.insert(()));
+        let b = VertexId::new(keys.insert(()));
+        let mut index = SpatialIndex::new(1.0);
+        assert!(index.insert([0.5, 0.5], a));
+        assert!(index.insert([3.0, 3.0], b));
+        assert!(!index.insert([f32::NAN, 0.0], b));
+        assert_eq!(index.position(b), Some([3.0, 3.0]));
+
+        // 重新插入会移动顶点
+        assert!(index.insert([-5.0, -5.0], a));
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.query_radius([0.5, 0.5], 1.0).count(), 0);
+        assert_eq!(index.nearest([-4.0, -4.0], 1)[0].0, a);
+
+        assert_eq!(index.remove(a), Some([-5.0, -5.0]));
+        assert_eq!(index.remove(a), None);
+        assert_eq!(index.nearest([-4.0, -4.0], 2).iter().map(|(id, _)| *id).collect::<Vec<_>>(), vec![b]);
+        index.clear();
+        assert!(index.is_empty() && index.cells.is_empty());
+    }
+}
@@ -0,0 +1,167 @@
+/*!
+ * # 边批次标签 (EdgeTags)
+ *
+ * 为边记录一个 `u64` 批次标签（例如导入任务的编号），
+ * 用于列出或一次性删除某个批次插入的全部边，标签不必写入边数据。
+ *
+ * ## 存储
+ *
+ * 标签存放在 `SecondaryMap` 中，另按标签保存边 ID 列表作为倒排表。
+ * 单独删除边时只移除 `SecondaryMap` 中的标签，O(1)；倒排表中的旧 ID 延迟清理：
+ * 遍历时按 `SecondaryMap` 跳过，某个标签的失效 ID 超过一半时整理该标签的列表。
+ * 槽位被重用后旧 ID 的版本号不再匹配，不会误认新边。
+ */
+
+use crate::id::EdgeId;
+use crate::memory::{hash_map_bytes, secondary_map_bytes};
+use pi_slotmap::{DefaultKey, Key, SecondaryMap};
+use std::collections::HashMap;
+use std::mem::size_of;
+
+/// 同一标签的边
+#[derive(Debug, Clone)]
+struct TagGroup<K: Key> {
+    /// 按插入顺序的边 ID，可能含有失效的 ID
+    edges: Vec<EdgeId<K>>,
+    /// `edges` 中失效 ID 的数量
+    stale: usize,
+}
+
+/// 边的批次标签及按标签的倒排表
+#[derive(Debug, Clone)]
+pub struct EdgeTags<K: Key = DefaultKey> {
+    /// 每条带标签的边的标签
+    tags: SecondaryMap<K, u64>,
+    /// 标签到边的倒排表
+    groups: HashMap<u64, TagGroup<K>>,
+}
+
+impl<K: Key> Default for EdgeTags<K> {
+    fn default() -> Self {
+        Self {
+            tags: SecondaryMap::new(),
+            groups: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Key> EdgeTags<K> {
+    /// 创建空表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为边设置标签，替换原有的标签
+    pub fn insert(&mut self, id: EdgeId<K>, tag: u64) {
+        if self.tags.get(id.key()) == Some(&tag) {
+            return;
+        }
+        self.remove(id);
+        self.tags.insert(id.key(), tag);
+        self.groups.entry(tag).or_insert_with(|| TagGroup { edges: Vec::new(), stale: 0 }).edges.push(id);
+    }
+
+    /// 边的标签
+    pub fn tag(&self, id: EdgeId<K>) -> Option<u64> {
+        self.tags.get(id.key()).copied()
+    }
+
+    /// 移除边的标签，倒排表中的 ID 延迟清理
+    pub fn remove(&mut self, id: EdgeId<K>) -> Option<u64> {
+        let tag = self.tags.remove(id.key())?;
+        if let Some(group) = self.groups.get_mut(&tag) {
+            group.stale += 1;
+            if group.stale * 2 > group.edges.len() {
+                let tags = &self.tags;
+                group.edges.retain(|edge| tags.get(edge.key()) == Some(&tag));
+                group.stale = 0;
+                if group.edges.is_empty() {
+                    self.groups.remove(&tag);
+                }
+            }
+        }
+        Some(tag)
+    }
+
+    /// 有标签的边，按槽位下标升序
+    pub(crate) fn tagged(&self) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.tags.iter().map(|(key, _)| EdgeId::new(key))
+    }
+
+    /// 标签为 `tag` 的边，按设置标签的顺序
+    pub fn edges(&self, tag: u64) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.groups
+            .get(&tag)
+            .into_iter()
+            .flat_map(|group| group.edges.iter().copied())
+            .filter(move |edge| self.tags.get(edge.key()) == Some(&tag))
+    }
+
+    /// 取出标签为 `tag` 的全部边并移除它们的标签
+    pub fn take(&mut self, tag: u64) -> Vec<EdgeId<K>> {
+        let Some(group) = self.groups.remove(&tag) else {
+            return Vec::new();
+        };
+        let mut edges = group.edges;
+        edges.retain(|edge| self.tags.get(edge.key()) == Some(&tag));
+        for edge in &edges {
+            self.tags.remove(edge.key());
+        }
+        edges
+    }
+
+    /// 带标签的边数量
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// 是否没有带标签的边
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// 清空全部标签
+    pub fn clear(&mut self) {
+        self.tags.clear();
+        self.groups.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节
+    pub fn memory_estimate(&self) -> usize {
+        secondary_map_bytes(&self.tags)
+            + hash_map_bytes(&self.groups)
+            + self.groups.values().map(|group| group.edges.capacity() * size_of::<EdgeId<K>>()).sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pi_slotmap::SlotMap;
+
+    #[test]
+    fn test_lazy_cleanup_keeps_live_edges() {
+        let mut slots: SlotMap<DefaultKey, ()> = SlotMap::new();
+        let ids: Vec<EdgeId> = (0..6).map(|_| EdgeId::new(slots.insert(()))).collect();
+        let mut tags = EdgeTags::new();
+        for (i, id) in ids.iter().enumerate() {
+            tags.insert(*id, (i % 2) as u64);
+        }
+
+        // 第一个失效 ID 延迟清理，第二个触发整理
+        tags.remove(ids[0]);
+        assert_eq!(tags.groups[&0].edges.len(), 3);
+        tags.remove(ids[2]);
+        assert_eq!(tags.groups[&0].edges, vec![ids[4]]);
+        assert_eq!(tags.edges(0).collect::<Vec<_>>(), vec![ids[4]]);
+
+        // 改标签后旧标签下不再列出
+        tags.insert(ids[1], 0);
+        assert_eq!(tags.edges(0).collect::<Vec<_>>(), vec![ids[4], ids[1]]);
+        assert_eq!(tags.edges(1).collect::<Vec<_>>(), vec![ids[3], ids[5]]);
+
+        assert_eq!(tags.take(1), vec![ids[3], ids[5]]);
+        assert_eq!((tags.len(), tags.tag(ids[3])), (2, None));
+        assert!(tags.take(1).is_empty());
+    }
+}
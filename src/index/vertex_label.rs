@@ -0,0 +1,348 @@
+/*!
+ * # 顶点标签索引 (VertexLabelIndex)
+ *
+ * 按顶点标签分组保存顶点ID，用于列出图中出现的标签及其顶点数量，
+ * 以及不经 `VertexSearch` 直接遍历某个标签下的顶点。
+ *
+ * ## 待同步顶点
+ *
+ * 通过 `&mut` 引用修改顶点权重时，索引无法立即得知标签是否改变。
+ * 调用方在交出可变引用前用 [`mark_dirty`](VertexLabelIndex::mark_dirty) 记录顶点修改前的标签，
+ * 查询时对这些顶点读取当前标签进行修正，
+ * 下一次 [`sync`](VertexLabelIndex::sync) 时再把它们移到正确的分组中。
+ * 因此查询结果始终准确，额外开销只与待同步顶点的数量有关。
+ *
+ * ## 顺序
+ *
+ * 每个分组按顶点加入分组的顺序遍历，`VertexSearch` 的 `limit` 因此返回最早加入的顶点。
+ * 分组以列表保存顶点，移除时只留下空位，空位超过一半或列表已满时整理，
+ * 整理不改变剩余顶点的相对顺序。标签改变的顶点同步后排在新分组的末尾；
+ * 同步前，它们排在新标签分组的已有顶点之后，彼此之间的顺序不固定。
+ */
+
+use crate::memory::hash_map_bytes;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem::size_of;
+
+/// 同一标签的顶点，按加入分组的顺序排列
+#[derive(Debug, Clone)]
+struct Group<V> {
+    /// 按加入顺序的顶点，`None` 为已移除的空位
+    order: Vec<Option<V>>,
+    /// 顶点在 `order` 中的位置
+    positions: HashMap<V, usize>,
+}
+
+impl<V: Copy + Eq + Hash> Group<V> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            order: Vec::with_capacity(capacity),
+            positions: HashMap::with_capacity(capacity),
+        }
+    }
+
+    fn insert(&mut self, vertex: V) {
+        if self.positions.contains_key(&vertex) {
+            return;
+        }
+        // 列表已满时先回收空位，顶点数不超过预留容量时不会重新分配
+        if self.order.len() == self.order.capacity() && self.order.len() > self.positions.len() {
+            self.compact();
+        }
+        self.positions.insert(vertex, self.order.len());
+        self.order.push(Some(vertex));
+    }
+
+    fn remove(&mut self, vertex: &V) {
+        let Some(position) = self.positions.remove(vertex) else {
+            return;
+        };
+        self.order[position] = None;
+        if (self.order.len() - self.positions.len()) * 2 > self.order.len() {
+            self.compact();
+        }
+    }
+
+    /// 去掉空位并更新位置，保持顶点的相对顺序
+    fn compact(&mut self) {
+        self.order.retain(Option::is_some);
+        for (position, vertex) in self.order.iter().flatten().enumerate() {
+            if let Some(recorded) = self.positions.get_mut(vertex) {
+                *recorded = position;
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    fn contains(&self, vertex: &V) -> bool {
+        self.positions.contains_key(vertex)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = V> + '_ {
+        self.order.iter().flatten().copied()
+    }
+
+    fn memory_estimate(&self) -> usize {
+        self.order.capacity() * size_of::<Option<V>>() + hash_map_bytes(&self.positions)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.compact();
+        self.order.shrink_to_fit();
+        self.positions.shrink_to_fit();
+    }
+}
+
+/// 按标签分组的顶点索引
+///
+/// - `L`: 顶点标签类型
+/// - `V`: 顶点ID类型
+#[derive(Debug, Clone)]
+pub struct VertexLabelIndex<L, V> {
+    /// 每个标签下的顶点，按记录时的标签分组
+    groups: HashMap<L, Group<V>>,
+    /// 标签可能已改变的顶点及其记录时的标签
+    dirty: HashMap<V, L>,
+    /// 新分组预留的顶点数
+    group_capacity: usize,
+}
+
+impl<L, V> Default for VertexLabelIndex<L, V> {
+    fn default() -> Self {
+        Self {
+            groups: HashMap::new(),
+            dirty: HashMap::new(),
+            group_capacity: 0,
+        }
+    }
+}
+
+impl<L, V> VertexLabelIndex<L, V>
+where
+    L: Copy + Eq + Hash,
+    V: Copy + Eq + Hash,
+{
+    /// 创建空索引
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建为 `capacity` 个顶点预留容量的索引
+    ///
+    /// 分组表、待同步记录和此后创建的每个分组都预留 `capacity` 个顶点，
+    /// 顶点数不超过 `capacity` 时不会重新分配。
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            groups: HashMap::with_capacity(capacity),
+            dirty: HashMap::with_capacity(capacity),
+            group_capacity: capacity,
+        }
+    }
+
+    /// 记录一个标签为 `label` 的新顶点
+    pub fn insert(&mut self, vertex: V, label: L) {
+        let capacity = self.group_capacity;
+        self.groups.entry(label).or_insert_with(|| Group::with_capacity(capacity)).insert(vertex);
+    }
+
+    /// 移除顶点，`label` 为顶点当前的标签
+    pub fn remove(&mut self, vertex: V, label: L) {
+        let recorded = self.dirty.remove(&vertex).unwrap_or(label);
+        self.remove_from_group(vertex, recorded);
+    }
+
+    /// 顶点即将通过可变引用被修改，`label` 为修改前的标签
+    pub fn mark_dirty(&mut self, vertex: V, label: L) {
+        self.dirty.entry(vertex).or_insert(label);
+    }
+
+    /// 分组中记录的顶点数量，不修正待同步顶点，只作为估计值
+    pub fn group_len(&self, label: L) -> usize {
+        self.groups.get(&label).map_or(0, Group::len)
+    }
+
+    /// 是否存在待同步的顶点
+    pub fn is_synced(&self) -> bool {
+        self.dirty.is_empty()
+    }
+
+    /// 按顶点当前的标签重新分组全部待同步顶点
+    ///
+    /// `current` 返回顶点当前的标签，顶点不存在时返回 `None`。
+    pub fn sync(&mut self, mut current: impl FnMut(V) -> Option<L>) {
+        for (vertex, recorded) in std::mem::take(&mut self.dirty) {
+            let Some(label) = current(vertex) else {
+                self.remove_from_group(vertex, recorded);
+                continue;
+            };
+            if label != recorded {
+                self.remove_from_group(vertex, recorded);
+                self.insert(vertex, label);
+            }
+        }
+    }
+
+    /// 每个标签及其顶点数量
+    pub fn counts(&self, current: impl Fn(V) -> Option<L>) -> HashMap<L, usize> {
+        let mut counts: HashMap<L, usize> = self.groups.iter().map(|(label, group)| (*label, group.len())).collect();
+        for (vertex, recorded) in &self.dirty {
+            let label = current(*vertex);
+            if label == Some(*recorded) {
+                continue;
+            }
+            if let Some(count) = counts.get_mut(recorded) {
+                *count -= 1;
+                if *count == 0 {
+                    counts.remove(recorded);
+                }
+            }
+            if let Some(label) = label {
+                *counts.entry(label).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// 标签为 `label` 的顶点，按加入分组的顺序
+    pub fn vertices<'a>(&'a self, label: L, current: impl Fn(V) -> Option<L> + Copy + 'a) -> impl Iterator<Item = V> + 'a {
+        let indexed = self
+            .groups
+            .get(&label)
+            .into_iter()
+            .flat_map(Group::iter)
+            .filter(move |vertex| !self.dirty.contains_key(vertex) || current(*vertex) == Some(label));
+        let moved = self
+            .dirty
+            .iter()
+            .filter(move |(vertex, recorded)| **recorded != label && current(**vertex) == Some(label))
+            .map(|(vertex, _)| *vertex);
+        indexed.chain(moved)
+    }
+
+    /// 可能带有标签 `label` 的顶点：分组中的顶点，以及记录为其他标签的待同步顶点
+    ///
+    /// 不读取任何顶点的当前标签，需要再用 [`contains`](Self::contains) 确认。
+    pub fn candidates(&self, label: L) -> impl Iterator<Item = V> + '_ {
+        let indexed = self.groups.get(&label).into_iter().flat_map(Group::iter);
+        let moved = self
+            .dirty
+            .iter()
+            .filter(move |(_, recorded)| **recorded != label)
+            .map(|(vertex, _)| *vertex);
+        indexed.chain(moved)
+    }
+
+    /// 顶点的标签是否为 `label`，只对待同步顶点调用 `current`
+    pub fn contains(&self, label: L, vertex: V, current: impl Fn(V) -> Option<L>) -> bool {
+        if self.dirty.contains_key(&vertex) {
+            current(vertex) == Some(label)
+        } else {
+            self.groups.get(&label).is_some_and(|group| group.contains(&vertex))
+        }
+    }
+
+    /// 清空索引
+    pub fn clear(&mut self) {
+        self.groups.clear();
+        self.dirty.clear();
+    }
+
+    /// 估算占用的堆内存，单位为字节
+    pub fn memory_estimate(&self) -> usize {
+        hash_map_bytes(&self.groups)
+            + self.groups.values().map(Group::memory_estimate).sum::<usize>()
+            + hash_map_bytes(&self.dirty)
+    }
+
+    /// 释放分组和待同步记录中多余的容量
+    pub fn shrink_to_fit(&mut self) {
+        self.groups.values_mut().for_each(Group::shrink_to_fit);
+        self.groups.shrink_to_fit();
+        self.dirty.shrink_to_fit();
+    }
+
+    fn remove_from_group(&mut self, vertex: V, label: L) {
+        if let Some(group) = self.groups.get_mut(&label) {
+            group.remove(&vertex);
+            if group.is_empty() {
+                self.groups.remove(&label);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dirty_vertices_are_corrected() {
+        let mut labels: HashMap<u32, char> = HashMap::from([(1, 'a'), (2, 'a'), (3, 'b')]);
+        let mut index = VertexLabelIndex::new();
+        for (vertex, label) in &labels {
+            index.insert(*vertex, *label);
+        }
+
+        // 顶点 2 的标签在可变引用中由 a 改为 b
+        index.mark_dirty(2, 'a');
+        labels.insert(2, 'b');
+        {
+            let current = |vertex| labels.get(&vertex).copied();
+            assert_eq!(index.counts(current), HashMap::from([('a', 1), ('b', 2)]));
+            assert_eq!(index.vertices('a', current).collect::<Vec<_>>(), vec![1]);
+            assert_eq!(index.vertices('b', current).count(), 2);
+        }
+
+        {
+            let current = |vertex| labels.get(&vertex).copied();
+            let mut b: Vec<_> = index.candidates('b').filter(|vertex| index.contains('b', *vertex, current)).collect();
+            b.sort();
+            assert_eq!(b, vec![2, 3]);
+            assert!(!index.contains('a', 2, current));
+            assert!(!index.contains('c', 1, current));
+        }
+
+        index.sync(|vertex| labels.get(&vertex).copied());
+        assert!(index.is_synced());
+        assert_eq!(index.groups[&'b'].len(), 2);
+
+        index.remove(1, 'a');
+        assert!(!index.groups.contains_key(&'a'));
+    }
+
+    #[test]
+    fn test_groups_keep_insertion_order() {
+        let mut index = VertexLabelIndex::with_capacity(4);
+        for vertex in [5, 3, 9, 1] {
+            index.insert(vertex, 'a');
+        }
+        let current = |_| Some('a');
+        assert_eq!(index.vertices('a', current).collect::<Vec<_>>(), vec![5, 3, 9, 1]);
+
+        // 移除留下空位，列表已满时回收空位，剩余顶点的顺序不变
+        index.remove(3, 'a');
+        index.insert(7, 'a');
+        assert_eq!(index.candidates('a').collect::<Vec<_>>(), vec![5, 9, 1, 7]);
+        assert_eq!(index.groups[&'a'].order.capacity(), 4);
+        index.remove(5, 'a');
+        index.remove(9, 'a');
+        index.remove(1, 'a');
+        assert_eq!(index.candidates('a').collect::<Vec<_>>(), vec![7]);
+        assert_eq!(index.groups[&'a'].positions[&7], 0);
+
+        // 标签改变的顶点同步后排在新分组的末尾
+        index.insert(2, 'b');
+        index.mark_dirty(7, 'a');
+        index.sync(|vertex| Some(if vertex == 7 { 'b' } else { 'a' }));
+        assert_eq!(index.candidates('b').collect::<Vec<_>>(), vec![2, 7]);
+        assert!(!index.groups.contains_key(&'a'));
+    }
+}
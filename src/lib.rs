@@ -1,4 +1,4 @@
-/**
+/*!
  * # SlotMapGraph - 基于 pi_slotmap 的高性能图库
  *
  * 本模块提供了一个基于 `pi_slotmap::SlotMap` 实现的高性能图数据结构。
@@ -45,7 +45,7 @@
  * └─────────────────────────────────────────────────┘
  *                       │
  * ┌─────────────────────────────────────────────────┐
- │                 ID Layer                         │
+ * │                 ID Layer                         │
  * │        类型安全的 ID 系统                        │
  * └─────────────────────────────────────────────────┘
  * ```
@@ -65,108 +65,177 @@
  *
  * ### 迭代器优化
  * - **零成本抽象**: 迭代器无运行时开销
- * **懒加载**: 按需生成元素，不预计算
- * **链式操作**: 支持迭代器的链式组合
+ * - **懒加载**: 按需生成元素，不预计算
+ * - **链式操作**: 支持迭代器的链式组合
  *
  * ## 使用示例
  *
+ * 顶点和边的数据类型需要实现 `graph_api_lib::Element`。临时建图时可以使用 [`prelude`]
+ * 中现成的 [`prelude::StringGraph`]，顶点和边都是 [`prelude::Labeled<String>`]，`&str` 可直接 `into()`。
+ *
  * ### 基本操作
  * ```rust
- * use slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::prelude::*;
  *
  * // 创建图
- * let mut graph = SlotMapGraph::new();
+ * let mut graph = StringGraph::new();
  *
  * // 添加顶点
- * let alice = graph.add_vertex("Alice");
- * let bob = graph.add_vertex("Bob");
- * let charlie = graph.add_vertex("Charlie");
- *
- * // 添加边
- * let friendship = graph.add_edge("friends", alice, bob);
- * let acquaintance = graph.add_edge("acquaintances", bob, charlie);
- *
- * // 查询顶点
- * if let Some(alice_name) = graph.vertex_weight(alice) {
- *     println!("Alice: {}", alice_name);
- * }
- *
- * // 查询边
- * if let Some(edge_label) = graph.edge_weight(friendship) {
- *     println!("Relationship: {}", edge_label);
- * }
+ * let alice = graph.add_vertex("Alice".into());
+ * let bob = graph.add_vertex("Bob".into());
+ * let charlie = graph.add_vertex("Charlie".into());
+ *
+ * // 添加边：add_edge(起点, 终点, 边数据)
+ * let friendship = graph.add_edge(alice, bob, "friend".into());
+ * graph.add_edge(bob, charlie, "colleague".into());
+ *
+ * // 查询顶点和边的数据
+ * assert_eq!(graph.vertex_weight(alice).map(|name| name.as_str()), Some("Alice"));
+ * assert_eq!(graph.edge_weight(friendship).map(|label| label.as_str()), Some("friend"));
+ * assert!(graph.has_edge(alice, bob));
+ * assert!(!graph.has_edge(bob, alice));
  * ```
  *
  * ### 遍历操作
  * ```rust
- * use graph_api_lib::Direction;
+ * use pi_slotmap_graph::prelude::*;
+ *
+ * let mut graph = StringGraph::new();
+ * let alice = graph.add_vertex("Alice".into());
+ * let bob = graph.add_vertex("Bob".into());
+ * graph.add_edge(alice, bob, "friend".into());
+ * graph.add_edge(bob, alice, "friend".into());
  *
  * // 遍历所有顶点
- * for vertex_ref in graph.vertex_iter() {
- *     println!("Vertex: {}", vertex_ref.value());
+ * for vertex in &graph {
+ *     println!("Vertex {}: {}", vertex.id(), vertex.weight());
  * }
  *
  * // 遍历所有边
- * for edge_ref in graph.edge_iter() {
- *     println!("Edge: {} -> {}", edge_ref.from(), edge_ref.to());
+ * for (id, weight, from, to) in graph.all_edges() {
+ *     println!("Edge {}: {} -> {} ({})", id, from, to, weight);
  * }
  *
- * // 查询顶点的邻接边
- * for edge_ref in graph.vertex_edges(alice) {
- *     match edge_ref.direction() {
- *         Direction::Outgoing => {
- *             println!("{} -> {}", alice, edge_ref.to());
- *         },
- *         Direction::Incoming => {
- *             println!("{} <- {}", alice, edge_ref.from());
- *         },
- *     }
+ * // 查询顶点的出边和入边
+ * for edge in graph.outgoing_edges(alice) {
+ *     println!("{} -> {}", alice, edge.head());
+ * }
+ * for edge in graph.incoming_edges(alice) {
+ *     println!("{} <- {}", alice, edge.tail());
  * }
+ * assert_eq!(graph.degree(alice), 2);
  * ```
  *
  * ### 可变操作
  * ```rust
- * // 修改顶点数据
- * if let Some(mut alice_ref) = graph.vertex_reference_mut(alice) {
- *     *alice_ref.value_mut = "Alice Smith".to_string();
- * }
+ * use pi_slotmap_graph::prelude::*;
+ *
+ * let mut graph = StringGraph::new();
+ * let alice = graph.add_vertex("Alice".into());
+ * let bob = graph.add_vertex("Bob".into());
+ * let friendship = graph.add_edge(alice, bob, "friend".into());
  *
- * // 修改边数据
- * if let Some(mut friend_ref) = graph.edge_reference_mut(friendship) {
- *     *friend_ref.value_mut = "best friends".to_string();
+ * // 修改顶点数据，Labeled 通过 DerefMut 直接修改内部的 String
+ * if let Some(name) = graph.vertex_weight_mut(alice) {
+ *     name.push_str(" Smith");
  * }
  *
- * // 批量修改
- * for mut vertex_ref in graph.vertex_iter_mut() {
- *     vertex_ref.value_mut().push_str(" (modified)");
+ * // 通过 graph-api 的可变引用修改边数据
+ * if let Some(mut edge) = graph.edge_mut(friendship) {
+ *     *edge.weight_mut() = "best friend".into();
  * }
+ *
+ * assert_eq!(graph.vertex_weight(alice).map(|name| name.as_str()), Some("Alice Smith"));
+ * assert_eq!(graph.edge_weight(friendship).map(|label| label.as_str()), Some("best friend"));
  * ```
  *
+ * 需要按标签查询时，再为自己的类型实现 `Element`，见下文的高级用法。
+ *
  * ## 模块导出
  *
  * ### 公共接口
- * - [`SlotMapGraph`]: 核心图实现
- * - [`VertexId`]: 顶点唯一标识符
- * - [`EdgeId`]: 边唯一标识符
- * - [`VertexContainer`]: 顶点存储容器
- * - [`EdgeContainer`]: 边存储容器
- *
- * ### 引用系统
- * - [`VertexReference`]: 顶点引用
- * - [`EdgeReference`]: 边引用
- *
- * ### 迭代器系统
- * - [`VertexIter`]: 顶点迭代器
- * - [`EdgeIter`]: 边迭代器
- * - [`VertexIterMut`]: 可变顶点迭代器
- * - [`EdgeIterMut`]: 可变边迭代器
+ * - [`SlotMapGraph`]：核心图实现
+ * - [`VertexId`]：顶点唯一标识符
+ * - [`EdgeId`]：边唯一标识符
+ * - [`VertexContainer`]：顶点存储容器
+ * - [`EdgeContainer`]：边存储容器
+ * - [`RemovePolicy`]：删除顶点时对关联边的处理策略
+ * - [`DegreePolicy`]：插入边超出 `set_max_degree` 设置的度数上限时的处理方式
+ * - [`GraphError`]：可能失败的图操作返回的错误
+ * - [`DeferredGraph`]：延迟删除模式下的图视图，允许边遍历边登记删除
+ * - [`TopologySnapshot`]：只记录边 ID 与端点的拓扑快照，用于撤销重新连线
+ * - [`BoundedSlotMapGraph`]：顶点和边数量有上限、槽位存储预先分配的图
+ *
+ * ### 快速上手
+ * - [`prelude`]：`Labeled<T>` 包装类型、[`prelude::StringGraph`] / [`prelude::UnitGraph`] 别名和常用 trait
+ *
+ * ### 引用与迭代器
+ * - [`graph::VertexReference`] / [`graph::EdgeReference`]：顶点和边的只读引用
+ * - [`graph::VertexReferenceMut`] / [`graph::EdgeReferenceMut`]：顶点和边的可变引用
+ * - [`graph::VertexIter`] / [`graph::EdgeIter`]：`Graph::vertices` / `Graph::edges` 返回的迭代器
  *
  * ### 索引系统
- * - [`IndexManager`]: 索引管理器
- * - [`IndexType`]: 索引类型（Hash, Range, FullText）
- * - [`HashIndex`]: 哈希索引实现
- * - [`RangeIndex`]: 范围索引实现
- * - [`IndexValue`]: 索引值类型
+ * - [`index::DeclaredIndex`]：按顶点类型声明的 `#[index(...)]` 自动维护的哈希/范围/全文索引
+ * - [`index::SimpleVertexQuery`]：图内置的字符串/整数/浮点数顶点索引
+ * - [`QueryPlan`]：`explain_vertices` 报告的顶点查询策略，见 [`plan`] 模块
+ * - [`VertexSelection`]：记录图版本的顶点查询结果，可检查是否失效并做集合运算
+ * - [`HashIndex`]：哈希索引实现
+ * - [`RangeIndex`]：范围索引实现
+ * - [`IndexValue`]：索引值类型
+ *
+ * ### ID 集合
+ * - [`collections::VertexIdSet`] / [`collections::EdgeIdSet`]：按槽位下标存放的 ID 集合，用作遍历的访问标记
+ *
+ * ### 只读邻接
+ * - [`FrozenGraph`]：`to_static` 构建的有序出边邻接表，支持二分查找的 `has_edge` 和归并求共同邻居
+ *
+ * ### 操作计数
+ * - [`GraphMetrics`]：`enable_metrics` 开启的增删次数、清空次数与顶点/边数量峰值
+ *
+ * ### 图比较
+ * - [`diff::GraphDiff`] / [`diff::StructureDiff`]：按 ID 或忽略 ID 比较两张图，
+ *   供 [`assert_graph_eq!`] 和 [`assert_same_structure!`] 在失败时输出差异
+ * - [`canonical`]：与 ID 和插入顺序无关的内容哈希 `content_hash` / `content_digest`
+ *
+ * ### 可视化
+ * - [`dot`]：`to_dot` / `to_dot_with` 输出 Graphviz DOT 文本
+ *
+ * ### 线程共享
+ * - [`SyncGraph`]：以 `RwLock` 保护的图，通过闭包或单次操作访问，引用不会逃出锁的作用域
+ *
+ * ### 共享边数据
+ * - [`shared::Shared`] / [`shared::PayloadTable`]：去重后在边之间共享的 `Arc` 数据，修改时写时复制
+ *
+ * ### 分批构建
+ * - [`loader::GraphLoader`]：按批接收以外部 ID 相互引用、顺序不定的顶点和边记录，暂存端点未到达的边
+ * - [`import::from_edge_list_reader`]：从文本边列表读取图，按 [`import::ImportLimits`] 限制行长和顶点、边数量
+ *
+ * ### 增量同步
+ * - [`changelog::ChangeSet`]：`enable_change_log` 开启后由 `changes_since` 汇总的某版本之后的净结构变化，可应用到副本
+ *
+ * ### 数据内引用
+ * - [`refs::ValidateRefs`]：声明顶点或边数据中保存的顶点 ID，供 `validate_refs` 检查悬空引用、`remove_vertex_checked` 拒绝删除仍被引用的顶点
+ *
+ * ### 存储复用
+ * - [`recycle::GraphStorage`]：`into_storage` 清空图后交出的存储，`from_storage` 用它构建新图，可放入池中反复使用
+ *
+ * ### 内存占用
+ * - [`memory::MemoryBreakdown`]：`memory_usage` 按顶点、边、连接信息、邻接表和索引分别估算的堆内存，`compact` 释放邻接表和标签索引的多余容量
+ *
+ * ### 类型擦除
+ * - [`dynamic::DynGraph`]：顶点和边数据以 [`dynamic::DynValue`] 保存的图，供插件存放各自的类型并按类型读取
+ *
+ * ### 删除诊断
+ * - `quarantine::StaleIdWarning`：开启 `removal-diagnostics` 特性后，`vertex()` / `edge()` 查询刚删除的 ID 时传给回调的诊断信息
+ *
+ * ### C 接口
+ * - `ffi::SmgGraph`：开启 `ffi` 特性后经由 `smg_*` 系列 `extern "C"` 函数创建和查看的 `SlotMapGraph<u64, u64>`
+ *
+ * ### 破坏注入
+ * - `corruption::CorruptionKind`：仅供测试，开启 `test-internals` 特性后由 `corrupt_for_test` 注入的不一致状态，用于测试 `validate` / `repair`
+ *
+ * ### 属性图
+ * - [`property::PropertyGraph`]：以 [`property::PropertyVertex`] / [`property::PropertyEdge`] 属性包为元素、自动维护索引的图
  *
  * ## 性能特征
  *
@@ -201,56 +270,77 @@
  *
  * ### 构建复杂网络
  * ```rust
- * use slotmap_graph::SlotMapGraph;
+ * use graph_api_lib::{EdgeReference, Element, Graph, VertexReference};
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * #[derive(Debug)]
+ * struct User {
+ *     name: &'static str,
+ *     age: u32,
+ * }
+ *
+ * impl Element for User {
+ *     type Label = ();
+ *     fn label(&self) {}
+ * }
  *
- * // 社交网络示例
- * let mut social_graph = SlotMapGraph::new();
+ * // 社交网络示例，边权重表示关系强度
+ * let mut social_graph: SlotMapGraph<User, u32> = SlotMapGraph::new();
  *
  * // 添加用户节点
- * let alice = social_graph.add_vertex(("Alice", 25));
- * let bob = social_graph.add_vertex(("Bob", 30));
- * let charlie = social_graph.add_vertex(("Charlie", 28));
+ * let alice = social_graph.add_vertex(User { name: "Alice", age: 25 });
+ * let bob = social_graph.add_vertex(User { name: "Bob", age: 30 });
+ * let charlie = social_graph.add_vertex(User { name: "Charlie", age: 28 });
  *
  * // 添加关系边
- * let friendship_ab = social_graph.add_edge("friend", alice, bob);
- * let friendship_bc = social_graph.add_edge("friend", bob, charlie);
- * let follows_ac = social_graph.add_edge("follows", alice, charlie);
+ * social_graph.add_edge(alice, bob, 3);
+ * social_graph.add_edge(bob, charlie, 2);
+ * social_graph.add_edge(alice, charlie, 1);
  *
  * // 查找Alice的朋友
+ * let mut friends = Vec::new();
  * for edge_ref in social_graph.outgoing_edges(alice) {
  *     let friend_id = edge_ref.head();
  *     if let Some(friend_ref) = social_graph.vertex(friend_id) {
- *         let (name, age) = friend_ref.weight();
- *         println!("Alice的朋友: {} ({}岁)", name, age);
+ *         let user = friend_ref.weight();
+ *         println!("Alice的朋友: {} ({}岁)", user.name, user.age);
+ *         friends.push(user.name);
  *     }
  * }
+ * friends.sort();
+ * assert_eq!(friends, ["Bob", "Charlie"]);
  * ```
  *
  * ### 索引系统使用
  * ```rust
- * use slotmap_graph::SlotMapGraph;
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
  *
- * let mut graph: SlotMapGraph<(String, u32), String> = SlotMapGraph::new();
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
  *
- * // 获取索引管理器
- * let manager = graph.index_manager_mut();
+ * // 添加数据，并为顶点建立年龄（整数）和职业（字符串）索引
+ * let alice = graph.add_vertex(28);
+ * let bob = graph.add_vertex(32);
+ * graph.index_vertex_int(alice, 28);
+ * graph.index_vertex_int(bob, 32);
+ * graph.index_vertex_string(alice, "Engineer");
+ * graph.index_vertex_string(bob, "Designer");
  *
- * // 创建索引
- * manager.create_vertex_range_index::<u32>("age".to_string()).unwrap();
- * manager.create_vertex_hash_index::<String>("profession".to_string()).unwrap();
+ * // 精确查询与范围查询
+ * let engineers: Vec<_> = graph.vertex_query().query_string("Engineer").collect();
+ * assert_eq!(engineers, vec![alice]);
  *
- * // 添加数据
- * let alice = graph.add_vertex(("Alice".to_string(), 28, "Engineer".to_string()));
- * let bob = graph.add_vertex(("Bob".to_string(), 32, "Designer".to_string()));
- *
- * // 查看索引统计
- * println!("{}", manager.stats_summary());
+ * let thirties: Vec<_> = graph.vertex_query().range_int(30..40).collect();
+ * assert_eq!(thirties, vec![bob]);
  * ```
  *
  * ### 图算法示例
  * ```rust
+ * use graph_api_lib::{EdgeReference, Element, Graph};
+ * use pi_slotmap_graph::{SlotMapGraph, VertexId};
+ *
  * // 简单的广度优先搜索
- * fn bfs_shortest_path<V, E>(
+ * fn bfs_shortest_path<V: Element, E: Element>(
  *     graph: &SlotMapGraph<V, E>,
  *     start: VertexId,
  *     goal: VertexId,
@@ -266,7 +356,7 @@
  *         if current == goal {
  *             // 重建路径
  *             let mut path = vec![goal];
- *             while let Some(&p) = parent.get(&path.last().unwrap()) {
+ *             while let Some(&p) = parent.get(path.last().unwrap()) {
  *                 path.push(p);
  *             }
  *             path.reverse();
@@ -275,8 +365,7 @@
  *
  *         for edge_ref in graph.outgoing_edges(current) {
  *             let neighbor = edge_ref.head();
- *             if !visited.contains(&neighbor) {
- *                 visited.insert(neighbor);
+ *             if visited.insert(neighbor) {
  *                 parent.insert(neighbor, current);
  *                 queue.push_back(neighbor);
  *             }
@@ -285,30 +374,174 @@
  *
  *     None
  * }
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * let a = graph.add_vertex(1);
+ * let b = graph.add_vertex(2);
+ * let c = graph.add_vertex(3);
+ * graph.add_edge(a, b, ());
+ * graph.add_edge(b, c, ());
+ *
+ * assert_eq!(bfs_shortest_path(&graph, a, c), Some(vec![a, b, c]));
+ * assert_eq!(bfs_shortest_path(&graph, c, a), None);
  * ```
  */
 
 // 核心图实现
 pub mod graph;
 
+// 声明式构建宏
+mod macros;
+
+// 错误类型
+pub mod error;
+
 // 分层模块结构
 pub mod id;
 pub mod storage;
 // pub mod reference;
 // pub mod iteration;
 pub mod index;
+pub mod algo;
+pub mod property;
+pub mod deferred;
+pub mod collections;
+pub mod snapshot;
+pub mod bounded;
+pub mod plan;
+pub mod selection;
+pub mod frozen;
+pub mod counters;
+pub mod diff;
+pub mod canonical;
+pub mod dot;
+pub mod sync;
+pub mod refs;
+pub mod changelog;
+pub mod shared;
+pub mod loader;
+pub mod import;
+pub mod recycle;
+pub mod memory;
+pub mod dynamic;
+pub mod prelude;
+#[cfg(feature = "removal-diagnostics")]
+pub mod quarantine;
+#[cfg(feature = "test-internals")]
+pub mod corruption;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 
 // 主要类型导出
-pub use graph::SlotMapGraph;
+pub use error::GraphError;
+pub use deferred::DeferredGraph;
+pub use snapshot::TopologySnapshot;
+pub use bounded::BoundedSlotMapGraph;
+pub use plan::QueryPlan;
+pub use selection::VertexSelection;
+pub use frozen::FrozenGraph;
+pub use counters::GraphMetrics;
+pub use sync::SyncGraph;
+pub use graph::{DegreePolicy, RemovePolicy, SlotMapGraph};
 pub use id::{EdgeId, EdgeInfo, VertexId};
-pub use storage::{EdgeContainer, VertexContainer};
+pub use storage::{EdgeContainer, EdgeRemovalOrder, VertexContainer};
 // pub use reference::{VertexReference, VertexReferenceMut, EdgeReference, EdgeReferenceMut};
 pub use index::{IndexValue, HashIndex, RangeIndex};
 
+// 供导出宏内部使用，非公共接口
+#[doc(hidden)]
+pub use graph_api_lib as __graph_api_lib;
+
 #[cfg(test)]
 mod test {
     use crate::SlotMapGraph;
     use graph_api_test::test_suite;
 
     test_suite!(SlotMapGraph::new());
+
+    /// 使用自定义键类型运行同一套测试
+    mod custom_key {
+        use crate::SlotMapGraph;
+        use graph_api_test::test_suite;
+        use pi_slotmap::new_key_type;
+
+        new_key_type! { struct NodeKey; }
+
+        test_suite!(SlotMapGraph::<_, _, NodeKey>::with_key());
+    }
+
+    /// 每个声明的 `Supports*` 能力都由索引执行，而不是退化为全扫描
+    mod capabilities {
+        use crate::plan::VertexStrategy;
+        use crate::SlotMapGraph;
+        use graph_api_lib::{EdgeReference, EdgeSearch, Graph, VertexSearch};
+        use graph_api_test::{populate_graph, Edge, EdgeLabel, Refs, Vertex, VertexLabel};
+
+        type TestGraph = SlotMapGraph<Vertex, Edge>;
+
+        fn populated() -> (TestGraph, Refs<TestGraph>) {
+            let mut graph = SlotMapGraph::new();
+            let refs = populate_graph(&mut graph);
+            (graph, refs)
+        }
+
+        /// 查询的策略不是全扫描，且结果数量为 `expected`
+        fn assert_indexed(graph: &TestGraph, search: VertexSearch<'_, TestGraph>, expected: usize) {
+            assert_ne!(graph.explain_vertices(&search).strategy, VertexStrategy::Scan);
+            assert_eq!(graph.vertices(&search).count(), expected);
+        }
+
+        #[test]
+        fn test_vertex_label_index() {
+            let (graph, _) = populated();
+            assert_indexed(&graph, Vertex::person(), 2);
+        }
+
+        #[test]
+        fn test_vertex_hash_index() {
+            let (graph, refs) = populated();
+            assert_indexed(&graph, Vertex::person_by_name("Bryn"), 1);
+            let Some(Vertex::Person { unique_id, .. }) = graph.vertex_weight(refs.julia) else {
+                panic!("julia must be a person");
+            };
+            assert_indexed(&graph, Vertex::person_by_unique_id(*unique_id), 1);
+        }
+
+        #[test]
+        fn test_vertex_range_index() {
+            let (graph, _) = populated();
+            assert_indexed(&graph, Vertex::person_by_age_range(20..46), 1);
+            assert_indexed(&graph, Vertex::person_by_username_range("a".."z"), 2);
+        }
+
+        #[test]
+        fn test_vertex_full_text_index() {
+            let (graph, _) = populated();
+            assert_indexed(&graph, Vertex::person_by_biography("graph"), 1);
+        }
+
+        #[test]
+        fn test_edge_label_index() {
+            let (mut graph, refs) = populated();
+            // 邻接标签缓存确认没有匹配的边时不读取任何边
+            graph.enable_adjacent_label_cache();
+            let knows = EdgeSearch::<TestGraph>::label(EdgeLabel::Knows);
+            assert_eq!(graph.edges(refs.rust, &knows).count(), 0);
+            let mut edges: Vec<_> = graph.edges(refs.bryn, &knows).map(|edge| edge.id()).collect();
+            edges.sort_by_key(|edge| edge.as_ffi());
+            let mut expected = vec![refs.bryn_knows_julia, refs.julia_knows_bryn];
+            expected.sort_by_key(|edge| edge.as_ffi());
+            assert_eq!(edges, expected);
+            assert_eq!(graph.edges_with_label(EdgeLabel::Knows).count(), 2);
+        }
+
+        #[test]
+        fn test_edge_adjacent_label_index() {
+            let (graph, refs) = populated();
+            let mut search = EdgeSearch::<TestGraph>::scan();
+            search.adjacent_label = Some(VertexLabel::Project);
+            let edges: Vec<_> = graph.edges(refs.bryn, &search).map(|edge| edge.id()).collect();
+            assert_eq!(edges, vec![refs.bryn_created_graph_api]);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,343 @@
+/*!
+ * # 分批构建 (Streamed Construction)
+ *
+ * 从外部数据源导入时，顶点和边通常以外部 ID 相互引用，且到达顺序不定：
+ * 边可能先于它的端点到达。[`GraphLoader`] 按批接收交错的 [`LoadItem`]，
+ * 顶点到达即插入图中，端点都已到达的边立即插入，其余的边按缺失的端点暂存，
+ * 等端点到达后再插入。调用方控制每批的大小，除图本身外只需保存暂存的边。
+ *
+ * [`finish`](GraphLoader::finish) 返回构建好的图、外部 ID 到 [`VertexId`] 的映射，
+ * 以及端点始终没有到达的边（[`UnresolvedEdge`]，按到达顺序），不把它们视为错误。
+ *
+ * ## 重复的外部 ID
+ *
+ * 由 [`OnDuplicate`] 决定：默认 [`Reject`](OnDuplicate::Reject) 返回 [`DuplicateVertex`] 错误；
+ * [`KeepFirst`](OnDuplicate::KeepFirst) 与 [`KeepLast`](OnDuplicate::KeepLast) 把它们合并为同一个顶点，
+ * 分别保留先到或后到的数据。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::loader::{GraphLoader, LoadItem};
+ *
+ * let mut loader: GraphLoader<u32, u32> = GraphLoader::new();
+ * // 边先于端点到达
+ * loader.push_batch([LoadItem::Edge { from_ext: 1, to_ext: 2, weight: 7 }]).unwrap();
+ * loader
+ *     .push_batch([
+ *         LoadItem::Vertex { external_id: 1, weight: 10 },
+ *         LoadItem::Vertex { external_id: 2, weight: 20 },
+ *         LoadItem::Edge { from_ext: 2, to_ext: 3, weight: 8 },
+ *     ])
+ *     .unwrap();
+ *
+ * let (graph, ids, unresolved) = loader.finish();
+ * assert_eq!(graph.edge_count(), 1);
+ * assert_eq!(graph.find_edge(ids[&1], ids[&2]).map(|edge| *graph.edge_weight(edge).unwrap()), Some(7));
+ * assert_eq!(unresolved.len(), 1);
+ * assert_eq!(unresolved[0].to_ext, 3);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::VertexId;
+use graph_api_lib::{Element, Graph};
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::hash::Hash;
+
+/// 导入的一条记录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadItem<V, E, X = u64> {
+    /// 外部 ID 为 `external_id` 的顶点
+    Vertex {
+        /// 外部 ID
+        external_id: X,
+        /// 顶点数据
+        weight: V,
+    },
+    /// 外部 ID `from_ext -> to_ext` 的边
+    Edge {
+        /// 起点的外部 ID
+        from_ext: X,
+        /// 终点的外部 ID
+        to_ext: X,
+        /// 边数据
+        weight: E,
+    },
+}
+
+/// 外部 ID 重复的顶点的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnDuplicate {
+    /// 返回 [`DuplicateVertex`] 错误，图不变
+    #[default]
+    Reject,
+    /// 合并为同一顶点，保留先到的数据
+    KeepFirst,
+    /// 合并为同一顶点，以后到的数据替换
+    KeepLast,
+}
+
+/// 端点始终没有到达的边
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedEdge<E, X = u64> {
+    /// 起点的外部 ID
+    pub from_ext: X,
+    /// 终点的外部 ID
+    pub to_ext: X,
+    /// 边数据
+    pub weight: E,
+}
+
+/// [`OnDuplicate::Reject`] 时遇到重复的外部 ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateVertex<X = u64> {
+    /// 重复的外部 ID
+    pub external_id: X,
+    /// 在所在批次中的位置，之前的记录已经处理，之后的没有处理
+    pub position: usize,
+}
+
+impl<X: Debug> Display for DuplicateVertex<X> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Duplicate external vertex id {:?} at batch position {}", self.external_id, self.position)
+    }
+}
+
+impl<X: Debug> std::error::Error for DuplicateVertex<X> {}
+
+/// [`GraphLoader::finish`] 的结果：图、外部 ID 映射和未解析的边
+pub type LoadOutput<V, E, X = u64, K = DefaultKey> = (SlotMapGraph<V, E, K>, HashMap<X, VertexId<K>>, Vec<UnresolvedEdge<E, X>>);
+
+/// 暂存的边，`sequence` 为到达顺序
+#[derive(Debug)]
+struct PendingEdge<E, X> {
+    sequence: usize,
+    edge: UnresolvedEdge<E, X>,
+}
+
+/// 按批接收顶点和边记录、构建图的加载器
+#[derive(Debug)]
+pub struct GraphLoader<V, E, X = u64, K = DefaultKey>
+where
+    V: Element,
+    E: Element,
+    K: Key,
+{
+    graph: SlotMapGraph<V, E, K>,
+    ids: HashMap<X, VertexId<K>>,
+    /// 按缺失的端点暂存的边，两个端点都缺失时挂在起点下
+    pending: HashMap<X, Vec<PendingEdge<E, X>>>,
+    pending_len: usize,
+    next_sequence: usize,
+    on_duplicate: OnDuplicate,
+}
+
+impl<V, E, X, K> Default for GraphLoader<V, E, X, K>
+where
+    V: Element,
+    E: Element,
+    X: Hash + Eq + Clone,
+    K: Key,
+{
+    fn default() -> Self {
+        Self {
+            graph: SlotMapGraph::with_key(),
+            ids: HashMap::new(),
+            pending: HashMap::new(),
+            pending_len: 0,
+            next_sequence: 0,
+            on_duplicate: OnDuplicate::default(),
+        }
+    }
+}
+
+impl<V, E, X, K> GraphLoader<V, E, X, K>
+where
+    V: Element,
+    E: Element,
+    X: Hash + Eq + Clone,
+    K: Key,
+{
+    /// 创建加载器，重复的外部 ID 返回错误
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置重复外部 ID 的处理方式
+    pub fn on_duplicate(mut self, on_duplicate: OnDuplicate) -> Self {
+        self.on_duplicate = on_duplicate;
+        self
+    }
+
+    /// 处理一批记录
+    ///
+    /// [`OnDuplicate::Reject`] 时遇到重复的外部 ID 立即返回错误，
+    /// 错误中的位置之前的记录已经处理，其余记录被丢弃，加载器仍可继续使用。
+    pub fn push_batch(&mut self, items: impl IntoIterator<Item = LoadItem<V, E, X>>) -> Result<(), DuplicateVertex<X>> {
+        for (position, item) in items.into_iter().enumerate() {
+            match item {
+                LoadItem::Vertex { external_id, weight } => self.push_vertex(external_id, weight, position)?,
+                LoadItem::Edge { from_ext, to_ext, weight } => {
+                    let sequence = self.next_sequence;
+                    self.next_sequence += 1;
+                    self.push_edge(PendingEdge {
+                        sequence,
+                        edge: UnresolvedEdge { from_ext, to_ext, weight },
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 已插入的顶点数
+    pub fn vertex_count(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// 暂存中等待端点的边数
+    pub fn pending_edges(&self) -> usize {
+        self.pending_len
+    }
+
+    /// 外部 ID 对应的顶点
+    pub fn vertex_id(&self, external_id: &X) -> Option<VertexId<K>> {
+        self.ids.get(external_id).copied()
+    }
+
+    /// 结束加载，返回图、外部 ID 映射和端点未到达的边（按到达顺序）
+    pub fn finish(self) -> LoadOutput<V, E, X, K> {
+        let mut unresolved: Vec<PendingEdge<E, X>> = self.pending.into_values().flatten().collect();
+        unresolved.sort_by_key(|pending| pending.sequence);
+        (self.graph, self.ids, unresolved.into_iter().map(|pending| pending.edge).collect())
+    }
+
+    fn push_vertex(&mut self, external_id: X, weight: V, position: usize) -> Result<(), DuplicateVertex<X>> {
+        if let Some(&vertex_id) = self.ids.get(&external_id) {
+            return match self.on_duplicate {
+                OnDuplicate::Reject => Err(DuplicateVertex { external_id, position }),
+                OnDuplicate::KeepFirst => Ok(()),
+                OnDuplicate::KeepLast => {
+                    self.graph.replace_vertex_weight(vertex_id, weight);
+                    Ok(())
+                }
+            };
+        }
+        let vertex_id = self.graph.add_vertex(weight);
+        self.ids.insert(external_id.clone(), vertex_id);
+        if let Some(waiting) = self.pending.remove(&external_id) {
+            self.pending_len -= waiting.len();
+            for pending in waiting {
+                self.push_edge(pending);
+            }
+        }
+        Ok(())
+    }
+
+    /// 两个端点都已到达时插入边，否则挂在缺失的端点下
+    fn push_edge(&mut self, pending: PendingEdge<E, X>) {
+        let from = self.ids.get(&pending.edge.from_ext).copied();
+        let to = self.ids.get(&pending.edge.to_ext).copied();
+        match (from, to) {
+            (Some(from), Some(to)) => {
+                self.graph.add_edge(from, to, pending.edge.weight);
+            }
+            (None, _) => self.park(pending.edge.from_ext.clone(), pending),
+            (Some(_), None) => self.park(pending.edge.to_ext.clone(), pending),
+        }
+    }
+
+    fn park(&mut self, missing: X, pending: PendingEdge<E, X>) {
+        self.pending.entry(missing).or_default().push(pending);
+        self.pending_len += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex(external_id: u64, weight: u32) -> LoadItem<u32, u32> {
+        LoadItem::Vertex { external_id, weight }
+    }
+
+    fn edge(from_ext: u64, to_ext: u64, weight: u32) -> LoadItem<u32, u32> {
+        LoadItem::Edge { from_ext, to_ext, weight }
+    }
+
+    #[test]
+    fn test_out_of_order_edges_resolve() {
+        let mut loader: GraphLoader<u32, u32> = GraphLoader::new();
+        loader.push_batch([edge(1, 2, 12), edge(3, 3, 33), edge(2, 1, 21)]).unwrap();
+        assert_eq!(loader.pending_edges(), 3);
+
+        loader.push_batch([vertex(2, 200), edge(2, 2, 22)]).unwrap();
+        // 2 -> 2 立即插入，其余仍缺 1 或 3
+        assert_eq!(loader.pending_edges(), 3);
+        loader.push_batch([vertex(1, 100)]).unwrap();
+        assert_eq!(loader.pending_edges(), 1);
+        loader.push_batch([vertex(3, 300)]).unwrap();
+        assert_eq!(loader.pending_edges(), 0);
+
+        let (graph, ids, unresolved) = loader.finish();
+        assert!(unresolved.is_empty());
+        assert_eq!(graph.vertex_count(), 3);
+        assert_eq!(graph.edge_count(), 4);
+        for (from, to, weight) in [(1, 2, 12), (2, 1, 21), (2, 2, 22), (3, 3, 33)] {
+            let edge = graph.find_edge(ids[&from], ids[&to]).unwrap();
+            assert_eq!(graph.edge_weight(edge), Some(&weight));
+        }
+        assert_eq!(graph.vertex_weight(ids[&3]), Some(&300));
+        assert!(graph.validate().is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_external_ids() {
+        let mut loader: GraphLoader<u32, u32> = GraphLoader::new();
+        let err = loader.push_batch([vertex(1, 10), vertex(2, 20), vertex(1, 11), vertex(3, 30)]).unwrap_err();
+        assert_eq!(err, DuplicateVertex { external_id: 1, position: 2 });
+        assert_eq!(err.to_string(), "Duplicate external vertex id 1 at batch position 2");
+        // 错误之前的记录已处理，之后的被丢弃
+        assert_eq!(loader.vertex_count(), 2);
+        assert!(loader.vertex_id(&3).is_none());
+
+        for (policy, expected) in [(OnDuplicate::KeepFirst, 10), (OnDuplicate::KeepLast, 11)] {
+            let mut loader: GraphLoader<u32, u32> = GraphLoader::new().on_duplicate(policy);
+            loader.push_batch([vertex(1, 10), edge(1, 2, 0), vertex(2, 20), vertex(1, 11)]).unwrap();
+            let (graph, ids, _) = loader.finish();
+            assert_eq!(graph.vertex_count(), 2);
+            assert_eq!(graph.vertex_weight(ids[&1]), Some(&expected), "{policy:?}");
+            assert!(graph.find_edge(ids[&1], ids[&2]).is_some());
+        }
+    }
+
+    #[test]
+    fn test_unresolved_edges_reported() {
+        let mut loader: GraphLoader<u32, u32, &str> = GraphLoader::new();
+        loader
+            .push_batch([
+                LoadItem::Edge { from_ext: "a", to_ext: "x", weight: 1 },
+                LoadItem::Vertex { external_id: "a", weight: 0 },
+                LoadItem::Edge { from_ext: "y", to_ext: "z", weight: 2 },
+                LoadItem::Edge { from_ext: "z", to_ext: "a", weight: 3 },
+                LoadItem::Edge { from_ext: "a", to_ext: "a", weight: 4 },
+            ])
+            .unwrap();
+
+        let (graph, ids, unresolved) = loader.finish();
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(ids.len(), 1);
+        assert_eq!(
+            unresolved,
+            vec![
+                UnresolvedEdge { from_ext: "a", to_ext: "x", weight: 1 },
+                UnresolvedEdge { from_ext: "y", to_ext: "z", weight: 2 },
+                UnresolvedEdge { from_ext: "z", to_ext: "a", weight: 3 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,219 @@
+/*!
+ * # 图字面量宏 (Graph Literal Macros)
+ *
+ * 提供声明式构建 `SlotMapGraph` 的宏，主要用于测试和示例代码，
+ * 以及在失败时输出差异说明的图比较断言。
+ */
+
+/// 以声明式语法构建 `SlotMapGraph`
+///
+/// 宏展开为一系列 `add_vertex` / `add_edge` 调用，返回 `(graph, ids)`，
+/// 其中 `ids` 是以标识符名称（字符串）为键的 `HashMap<&'static str, VertexId>`。
+///
+/// # 语法
+///
+/// - 完整形式：`graph! { vertices: { a: w1, b: w2 }, edges: { a -> b: e1 } }`
+/// - 省略权重时使用单元类型 `()`：`graph! { vertices: { a, b }, edges: { a -> b } }`
+/// - 仅边形式：`graph! { a -> b, b -> c }`，顶点按首次出现自动创建，权重均为 `()`
+///
+/// 在完整形式中，边引用了未在 `vertices` 中声明的标识符会 panic；
+/// 省略 `edges` 时边权重类型由上下文推断。
+///
+/// # 示例
+///
+/// ```rust
+/// use pi_slotmap_graph::graph;
+///
+/// let (g, ids) = graph! {
+///     vertices: { a: 1u32, b: 2u32, c: 3u32 },
+///     edges: { a -> b: 10u32, b -> c: 20u32, c -> a: 30u32 }
+/// };
+///
+/// assert_eq!(g.vertex_count(), 3);
+/// assert_eq!(g.edge_count(), 3);
+/// assert!(g.has_edge(ids["a"], ids["b"]));
+/// assert!(!g.has_edge(ids["b"], ids["a"]));
+///
+/// // 仅边形式，顶点与边的权重均为 ()
+/// let (chain, ids) = graph! { a -> b, b -> c };
+/// assert_eq!(chain.vertex_count(), 3);
+/// assert!(chain.has_edge(ids["b"], ids["c"]));
+/// ```
+#[macro_export]
+macro_rules! graph {
+    (@weight $w:expr) => { $w };
+    (@weight) => { () };
+    (
+        vertices: { $($v:ident $(: $vw:expr)?),* $(,)? }
+        $(, edges: { $($from:ident -> $to:ident $(: $ew:expr)?),* $(,)? })?
+        $(,)?
+    ) => {{
+        #[allow(unused_mut)]
+        let mut graph = $crate::SlotMapGraph::new();
+        #[allow(unused_mut)]
+        let mut ids: ::std::collections::HashMap<&'static str, $crate::VertexId> =
+            ::std::collections::HashMap::new();
+        $(
+            ids.insert(
+                stringify!($v),
+                $crate::__graph_api_lib::Graph::add_vertex(&mut graph, $crate::graph!(@weight $($vw)?)),
+            );
+        )*
+        $($(
+            $crate::__graph_api_lib::Graph::add_edge(
+                &mut graph,
+                *ids.get(stringify!($from))
+                    .unwrap_or_else(|| panic!("graph!: undeclared vertex `{}`", stringify!($from))),
+                *ids.get(stringify!($to))
+                    .unwrap_or_else(|| panic!("graph!: undeclared vertex `{}`", stringify!($to))),
+                $crate::graph!(@weight $($ew)?),
+            );
+        )*)?
+        (graph, ids)
+    }};
+    ($($from:ident -> $to:ident),+ $(,)?) => {{
+        let mut graph = $crate::SlotMapGraph::<(), ()>::new();
+        let mut ids: ::std::collections::HashMap<&'static str, $crate::VertexId> =
+            ::std::collections::HashMap::new();
+        $(
+            let from = *ids
+                .entry(stringify!($from))
+                .or_insert_with(|| $crate::__graph_api_lib::Graph::add_vertex(&mut graph, ()));
+            let to = *ids
+                .entry(stringify!($to))
+                .or_insert_with(|| $crate::__graph_api_lib::Graph::add_vertex(&mut graph, ()));
+            $crate::__graph_api_lib::Graph::add_edge(&mut graph, from, to, ());
+        )+
+        (graph, ids)
+    }};
+}
+
+/// 断言两张图按 ID 对齐后完全相同
+///
+/// 失败时逐行列出 [`GraphDiff`](crate::diff::GraphDiff) 中的差异：
+/// 只在一侧出现的顶点和边（含 ID、数据和端点），以及数据或端点不同的元素。
+/// 顶点和边的数据类型需要实现 `PartialEq` 和 `Debug`。可以像 `assert_eq!` 一样附加说明。
+///
+/// # 示例
+///
+/// ```rust
+/// use pi_slotmap_graph::{assert_graph_eq, graph};
+///
+/// let (left, _) = graph! { a -> b };
+/// let (right, _) = graph! { a -> b };
+/// assert_graph_eq!(left, right);
+/// ```
+#[macro_export]
+macro_rules! assert_graph_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let diff = $crate::SlotMapGraph::diff(&$left, &$right);
+        if !diff.is_empty() {
+            panic!("assertion `left == right` failed: graphs differ\n{}", diff);
+        }
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let diff = $crate::SlotMapGraph::diff(&$left, &$right);
+        if !diff.is_empty() {
+            panic!("assertion `left == right` failed: graphs differ: {}\n{}", format_args!($($arg)+), diff);
+        }
+    }};
+}
+
+/// 断言两张图忽略 ID 后结构相同
+///
+/// 按 [`structure_diff`](crate::SlotMapGraph::structure_diff) 比较，
+/// 失败时列出只在一侧出现的顶点和边描述。顶点和边的数据类型需要实现 `Debug`。
+///
+/// # 示例
+///
+/// ```rust
+/// use pi_slotmap_graph::{assert_same_structure, graph};
+///
+/// let (left, _) = graph! { a -> b, b -> c };
+/// let (right, _) = graph! { x -> y, y -> z };
+/// assert_same_structure!(left, right);
+/// ```
+#[macro_export]
+macro_rules! assert_same_structure {
+    ($left:expr, $right:expr $(,)?) => {{
+        let diff = $crate::SlotMapGraph::structure_diff(&$left, &$right);
+        if !diff.is_empty() {
+            panic!("assertion failed: graphs differ in structure\n{}", diff);
+        }
+    }};
+    ($left:expr, $right:expr, $($arg:tt)+) => {{
+        let diff = $crate::SlotMapGraph::structure_diff(&$left, &$right);
+        if !diff.is_empty() {
+            panic!("assertion failed: graphs differ in structure: {}\n{}", format_args!($($arg)+), diff);
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use graph_api_lib::{EdgeReference, Graph, VertexReference};
+
+    #[test]
+    fn test_graph_macro_with_weights() {
+        let (g, ids) = graph! {
+            vertices: { a: 1u32, b: 2u32 },
+            edges: { a -> b: 7u32, b -> a: 8u32 },
+        };
+
+        assert_eq!(g.vertex_count(), 2);
+        assert_eq!(g.edge_count(), 2);
+        assert_eq!(ids.len(), 2);
+        assert_eq!(*g.vertex(ids["a"]).unwrap().weight(), 1);
+        assert_eq!(*g.vertex(ids["b"]).unwrap().weight(), 2);
+
+        let ab: Vec<_> = g.edges_between(ids["a"], ids["b"]).collect();
+        assert_eq!(ab.len(), 1);
+        assert_eq!(*ab[0].weight(), 7);
+        assert_eq!(ab[0].tail(), ids["a"]);
+        assert_eq!(ab[0].head(), ids["b"]);
+    }
+
+    #[test]
+    fn test_graph_macro_unit_weights() {
+        let (g, ids) = graph! {
+            vertices: { a, b, c },
+            edges: { a -> b, b -> c }
+        };
+
+        assert_eq!(g.vertex_count(), 3);
+        assert_eq!(g.edge_count(), 2);
+        assert!(g.has_edge(ids["a"], ids["b"]));
+        assert!(g.has_edge(ids["b"], ids["c"]));
+        assert!(!g.has_edge(ids["a"], ids["c"]));
+    }
+
+    #[test]
+    fn test_graph_macro_vertices_only() {
+        // 未声明边时，边权重类型由上下文推断
+        let (g, ids): (crate::SlotMapGraph<u64, ()>, _) = graph! { vertices: { x: 1u64, y: 2u64 } };
+
+        assert_eq!(g.vertex_count(), 2);
+        assert_eq!(g.edge_count(), 0);
+        assert!(g.contains_vertex(ids["x"]));
+    }
+
+    #[test]
+    fn test_graph_macro_edges_only() {
+        let (g, ids) = graph! { a -> b, b -> c, c -> a, a -> a };
+
+        // 顶点按标识符去重
+        assert_eq!(g.vertex_count(), 3);
+        assert_eq!(g.edge_count(), 4);
+        assert!(g.has_edge(ids["c"], ids["a"]));
+        assert!(g.has_edge(ids["a"], ids["a"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "undeclared vertex `z`")]
+    fn test_graph_macro_undeclared_vertex() {
+        let _ = graph! {
+            vertices: { a: 1u32 },
+            edges: { a -> z: 1u32 }
+        };
+    }
+}
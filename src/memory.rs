@@ -0,0 +1,276 @@
+/*!
+ * # 内存占用 (Memory Usage)
+ *
+ * [`memory_usage`](SlotMapGraph::memory_usage) 按子系统估算图占用的堆内存，
+ * 返回 [`MemoryBreakdown`]。估算按各容器的容量乘以元素大小计算，
+ * 已分配但未使用的容量同样计入，反映的是实际占用而不是元素数量。
+ *
+ * ## 统计范围
+ *
+ * - `vertices`: 顶点槽位存储
+ * - `edges`: 边槽位中的边数据
+ * - `connections`: 边槽位中的连接信息
+ * - `adjacency`: 邻接表及各顶点的出边/入边列表
+ * - `vertex_indexes`: 简单查询器、顶点标签索引、声明索引、固定顶点集合，
+ *   以及已开启的坐标索引和标签缓存、创建顺序、元素位标记的顶点部分
+ * - `edge_indexes`: 边标签索引、边图层、边批次标签，以及已开启的端点对索引、邻接边标签缓存和标签缓存、创建顺序、元素位标记的边部分
+ *
+ * ## 注意事项
+ *
+ * 顶点和边数据自身持有的堆内存（如顶点类型中的 `String`）不会被统计，
+ * 需要时使用 [`memory_usage_deep`](SlotMapGraph::memory_usage_deep) 提供每个顶点的深度大小。
+ * 哈希表按容量和每项一个控制字节估算，B 树按元素数量估算，不计节点内的空位；
+ * 变更日志、操作计数等诊断设施不计入。结果只用于观察量级和趋势。
+ *
+ * [`compact`](SlotMapGraph::compact) 释放邻接表、标签索引和固定顶点集合中多余的容量。
+ * 顶点和边的槽位存储不会缩小，已删除元素的槽位仍用于区分过期的 ID。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * let empty = graph.memory_usage();
+ * let a = graph.add_vertex(1);
+ * let b = graph.add_vertex(2);
+ * graph.add_edge(a, b, ());
+ *
+ * let usage = graph.memory_usage();
+ * assert!(usage.total > empty.total);
+ * assert_eq!(usage.total, usage.vertices + usage.edges + usage.connections
+ *     + usage.adjacency + usage.vertex_indexes + usage.edge_indexes);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::EdgeInfo;
+use graph_api_lib::Element;
+use pi_slotmap::{Key, SecondaryMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::mem::size_of;
+
+/// 图占用的堆内存估算，单位为字节
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryBreakdown {
+    /// 顶点槽位存储
+    pub vertices: usize,
+    /// 边槽位中的边数据
+    pub edges: usize,
+    /// 边槽位中的连接信息和槽位版本号
+    pub connections: usize,
+    /// 邻接表
+    pub adjacency: usize,
+    /// 顶点相关的索引
+    pub vertex_indexes: usize,
+    /// 边相关的索引
+    pub edge_indexes: usize,
+    /// 以上各项之和
+    pub total: usize,
+}
+
+/// 哈希表按容量估算，每项另加一个控制字节
+pub(crate) fn hash_map_bytes<K, V>(map: &HashMap<K, V>) -> usize {
+    map.capacity() * (size_of::<(K, V)>() + 1)
+}
+
+/// 哈希集合按容量估算，每项另加一个控制字节
+pub(crate) fn hash_set_bytes<T>(set: &HashSet<T>) -> usize {
+    set.capacity() * (size_of::<T>() + 1)
+}
+
+/// B 树按元素数量估算，不计节点内的空位
+pub(crate) fn btree_map_bytes<K, V>(map: &BTreeMap<K, V>) -> usize {
+    map.len() * size_of::<(K, V)>()
+}
+
+/// 副表按槽位容量估算，每个槽位保存可选的值和版本号
+pub(crate) fn secondary_map_bytes<K: Key, V>(map: &SecondaryMap<K, V>) -> usize {
+    map.capacity() * size_of::<(Option<V>, u32)>()
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 按子系统估算图占用的堆内存
+    ///
+    /// 顶点和边数据自身持有的堆内存不计入，见[模块文档](crate::memory)。
+    pub fn memory_usage(&self) -> MemoryBreakdown {
+        let vertex_slots = self.vertices.capacity();
+        let edge_slots = self.edges.capacity();
+        let edge_slot = size_of::<((Edge, EdgeInfo<K>), u32)>();
+
+        let mut vertex_indexes = self.vertex_query.memory_estimate()
+            + self.vertex_labels.memory_estimate()
+            + self.declared_index.memory_estimate()
+            + hash_set_bytes(&self.pinned);
+        let mut edge_indexes = self.edge_labels.memory_estimate()
+            + hash_set_bytes(&self.unindexed_edges)
+            + self.adjacent_labels.as_ref().map_or(0, |cache| cache.memory_estimate())
+            + self.edge_pairs.as_ref().map_or(0, |pairs| pairs.memory_estimate())
+            + self.layers.memory_estimate()
+            + self.tags.memory_estimate();
+        if let Some(spatial) = &self.spatial {
+            vertex_indexes += spatial.memory_estimate();
+        }
+        if let Some(marks) = &self.marks {
+            vertex_indexes += marks.vertex_memory_estimate();
+            edge_indexes += marks.edge_memory_estimate();
+        }
+        if let Some(cache) = &self.label_cache {
+            vertex_indexes += cache.vertex_memory_estimate();
+            edge_indexes += cache.edge_memory_estimate();
+        }
+        if let Some(order) = &self.creation_order {
+            vertex_indexes += order.vertex_memory_estimate();
+            edge_indexes += order.edge_memory_estimate();
+        }
+
+        let mut usage = MemoryBreakdown {
+            vertices: vertex_slots * size_of::<(Vertex, u32)>(),
+            edges: edge_slots * size_of::<Edge>(),
+            connections: edge_slots * (edge_slot - size_of::<Edge>()),
+            adjacency: self.edges.adjacency_memory_estimate(),
+            vertex_indexes,
+            edge_indexes,
+            total: 0,
+        };
+        usage.total = usage.vertices
+            + usage.edges
+            + usage.connections
+            + usage.adjacency
+            + usage.vertex_indexes
+            + usage.edge_indexes;
+        usage
+    }
+
+    /// 同 [`memory_usage`](Self::memory_usage)，另把 `deep_size` 对每个顶点返回的堆内存计入 `vertices`
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::Graph;
+    /// use pi_slotmap_graph::property::{PropertyGraph, PropertyVertex};
+    ///
+    /// let mut graph = PropertyGraph::new();
+    /// graph.add_vertex(PropertyVertex::new("a".repeat(100)));
+    /// let shallow = graph.memory_usage();
+    /// let deep = graph.memory_usage_deep(|vertex| vertex.label.capacity());
+    /// assert_eq!(deep.vertices, shallow.vertices + 100);
+    /// assert_eq!(deep.total, shallow.total + 100);
+    /// ```
+    pub fn memory_usage_deep(&self, deep_size: impl Fn(&Vertex) -> usize) -> MemoryBreakdown {
+        let mut usage = self.memory_usage();
+        let payload: usize = self.vertices.iter().map(|(_, vertex)| deep_size(vertex)).sum();
+        usage.vertices += payload;
+        usage.total += payload;
+        usage
+    }
+
+    /// 释放邻接表、标签索引和固定顶点集合中多余的容量，不改变任何 ID
+    ///
+    /// 顶点和边的槽位存储不会缩小，见[模块文档](crate::memory)。
+    pub fn compact(&mut self) {
+        self.edges.shrink_to_fit();
+        self.vertex_labels.shrink_to_fit();
+        self.edge_labels.shrink_to_fit();
+        self.unindexed_edges.shrink_to_fit();
+        self.pinned.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::property::{PropertyGraph, PropertyVertex};
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+
+    type TestGraph = SlotMapGraph<u32, u32>;
+
+    fn sum(usage: &MemoryBreakdown) -> usize {
+        usage.vertices + usage.edges + usage.connections + usage.adjacency + usage.vertex_indexes + usage.edge_indexes
+    }
+
+    #[test]
+    fn test_total_is_sum_of_parts() {
+        let mut graph = TestGraph::new();
+        graph.enable_pair_index();
+        graph.enable_marks();
+        graph.enable_creation_order();
+        let vertices: Vec<_> = (0..50).map(|i| graph.add_vertex(i)).collect();
+        for i in 0..200 {
+            graph.add_edge(vertices[i % 50], vertices[(i * 7 + 3) % 50], i as u32);
+        }
+        graph.index_vertex_int(vertices[0], 7);
+        graph.index_vertex_string(vertices[1], "root");
+
+        let usage = graph.memory_usage();
+        assert_eq!(usage.total, sum(&usage));
+        assert!(usage.vertices > 0 && usage.edges > 0 && usage.connections > 0);
+        assert!(usage.adjacency > 0 && usage.vertex_indexes > 0 && usage.edge_indexes > 0);
+        assert_eq!(TestGraph::new().memory_usage().total, sum(&TestGraph::new().memory_usage()));
+    }
+
+    #[test]
+    fn test_usage_grows_after_inserts() {
+        let mut graph = TestGraph::new();
+        let empty = graph.memory_usage();
+        let vertices: Vec<_> = (0..100).map(|i| graph.add_vertex(i)).collect();
+        let with_vertices = graph.memory_usage();
+        assert!(with_vertices.vertices > empty.vertices);
+        assert!(with_vertices.total > empty.total);
+
+        for i in 0..500 {
+            graph.add_edge(vertices[i % 100], vertices[(i * 3 + 1) % 100], i as u32);
+        }
+        let with_edges = graph.memory_usage();
+        assert!(with_edges.edges > with_vertices.edges);
+        assert!(with_edges.connections > with_vertices.connections);
+        assert!(with_edges.adjacency > with_vertices.adjacency);
+        assert!(with_edges.edge_indexes > with_vertices.edge_indexes);
+        assert!(with_edges.total > with_vertices.total);
+    }
+
+    #[test]
+    fn test_usage_drops_after_compact() {
+        let mut graph = TestGraph::new();
+        let vertices: Vec<_> = (0..200).map(|i| graph.add_vertex(i)).collect();
+        let edges: Vec<_> = (0..1000)
+            .map(|i| graph.add_edge(vertices[i % 200], vertices[(i * 13 + 7) % 200], i as u32))
+            .collect();
+        graph.pin_vertex(vertices[0]);
+        for &edge in &edges[1..] {
+            graph.remove_edge(edge);
+        }
+        let before = graph.memory_usage();
+
+        graph.compact();
+        let after = graph.memory_usage();
+        assert!(after.adjacency < before.adjacency);
+        assert!(after.edge_indexes < before.edge_indexes);
+        assert!(after.total < before.total);
+        assert_eq!(after.total, sum(&after));
+        // 槽位存储不缩小，ID 保持有效
+        assert_eq!((after.vertices, after.edges), (before.vertices, before.edges));
+        assert!(graph.edge(edges[0]).is_some());
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_deep_usage_adds_payload() {
+        let mut graph = PropertyGraph::new();
+        for i in 0..10 {
+            graph.add_vertex(PropertyVertex::new(format!("vertex-{i}")));
+        }
+        let shallow = graph.memory_usage();
+        let deep = graph.memory_usage_deep(|vertex| vertex.label.capacity());
+        let payload: usize = (0..10).map(|i| format!("vertex-{i}").len()).sum();
+        assert!(deep.vertices >= shallow.vertices + payload);
+        assert_eq!(deep.total - shallow.total, deep.vertices - shallow.vertices);
+        assert_eq!(deep.total, sum(&deep));
+    }
+}
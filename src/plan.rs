@@ -0,0 +1,632 @@
+/*!
+ * # 查询计划 (Query Plan)
+ *
+ * `Graph::vertices` 按 `VertexSearch` 的类型选择候选顶点的来源，
+ * [`SlotMapGraph::explain_vertices`] 用同一套选择逻辑报告将要使用的策略，但不执行查询，
+ * 用于判断一次查询是否命中了索引。
+ *
+ * ## 策略
+ *
+ * | 查询 | 策略 | 条件 |
+ * |------|------|------|
+ * | `Scan` | [`VertexStrategy::Scan`] | 总是 |
+ * | `Label` | [`VertexStrategy::LabelIndex`] | 总是（顶点标签索引随增删自动维护） |
+ * | `Index` | [`VertexStrategy::DeclaredHash`] | 查询的索引由顶点类型声明为哈希索引 |
+ * | `Range` | [`VertexStrategy::DeclaredRange`] | 查询的索引由顶点类型声明为范围索引 |
+ * | `FullText` | [`VertexStrategy::DeclaredFullText`] | 查询的索引由顶点类型声明为全文索引，且文本含有词 |
+ * | `Index` | [`VertexStrategy::HashIndex`] | 值为字符串、64 位以内的整数或浮点数 |
+ * | `Range` | [`VertexStrategy::RangeIndex`] | 两端为同一种 64 位以内的整数 |
+ * | `FullText` | [`VertexStrategy::FullText`] | 值为含非空白字符的字符串 |
+ *
+ * 顶点类型通过 `Label::indexes` 声明的索引（派生宏的 `#[index(...)]`）优先，
+ * 读取随顶点增删和修改自动维护的 [`DeclaredIndex`](crate::index::DeclaredIndex)，
+ * 不受 [`SlotMapGraph::disable_vertex_query`] 影响；声明的索引类型与查询不符时退化为全扫描。
+ *
+ * 未声明索引的顶点类型（例如 `u32`）使用最后三种策略，读取手动维护的
+ * [`SimpleVertexQuery`](crate::index::SimpleVertexQuery)，
+ * 不满足条件或通过 [`SlotMapGraph::disable_vertex_query`] 关闭后退化为全扫描。
+ * 全扫描返回所有顶点，不按查询条件过滤。
+ *
+ * 全文查询把字符串按空白拆成词，返回以每个词都建立过字符串索引的顶点。
+ *
+ * 标签查询遍历标签索引的分组，并用索引的成员关系作为预筛选：
+ * 只有标签确认匹配的候选顶点才会读取权重、构造引用，
+ * 标签在可变引用中被修改、尚未同步的顶点才需要读取当前标签。
+ *
+ * ## 谓词查询
+ *
+ * 条件无法表达为 `VertexSearch` 时，[`SlotMapGraph::find_vertices`] 先用可选的预筛选查询
+ * 经上述策略缩小候选范围，再对候选顶点惰性地执行谓词；没有预筛选时对全部顶点执行谓词。
+ * 预筛选查询的数量限制作用于候选顶点，而不是谓词过滤后的结果。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::{Graph, VertexSearch};
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::plan::VertexStrategy;
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * let alice = graph.add_vertex(25);
+ * graph.index_vertex_int(alice, 25);
+ *
+ * let search = VertexSearch::Range { index: (), range: 20i64.into()..30i64.into(), limit: Some(5) };
+ * let plan = graph.explain_vertices(&search);
+ * assert_eq!(plan.strategy, VertexStrategy::RangeIndex { bounds: 20..30 });
+ * assert_eq!((plan.estimated_candidates, plan.limit), (1, Some(5)));
+ *
+ * graph.disable_vertex_query();
+ * assert_eq!(graph.explain_vertices(&search).strategy, VertexStrategy::Scan);
+ * ```
+ */
+
+use crate::graph::{SlotMapGraph, VertexReference};
+use crate::id::VertexId;
+use crate::index::{declared, IndexEntry, IndexKey};
+use graph_api_lib::{Element, Graph, Index, IndexType, Label, Value, VertexReference as _, VertexSearch};
+use pi_slotmap::Key;
+use smallbox::space::{S4, S8};
+use smallbox::{smallbox, SmallBox};
+use std::ops::Range;
+
+/// 候选顶点的来源
+#[derive(Debug, Clone, PartialEq)]
+pub enum VertexStrategy<'search, L: Label> {
+    /// 遍历全部顶点
+    Scan,
+    /// 读取顶点标签索引
+    LabelIndex {
+        /// 查询的标签
+        label: L,
+    },
+    /// 读取顶点类型声明的哈希索引
+    DeclaredHash {
+        /// 查询的索引
+        index: L::Index,
+        /// 查询的字段值
+        key: IndexKey,
+    },
+    /// 读取顶点类型声明的范围索引
+    DeclaredRange {
+        /// 查询的索引
+        index: L::Index,
+        /// 查询的半开区间
+        bounds: Range<IndexKey>,
+    },
+    /// 读取顶点类型声明的全文索引，要求每个词都出现
+    DeclaredFullText {
+        /// 查询的索引
+        index: L::Index,
+        /// 拆分出的小写词
+        tokens: Vec<String>,
+    },
+    /// 读取字符串、整数或浮点数哈希索引
+    HashIndex {
+        /// 查询的索引值
+        key: IndexEntry<'search>,
+    },
+    /// 读取整数范围索引
+    RangeIndex {
+        /// 查询的半开区间
+        bounds: Range<i64>,
+    },
+    /// 读取字符串索引，要求顶点以每个词都建立过索引
+    FullText {
+        /// 按空白拆分出的词
+        tokens: Vec<&'search str>,
+    },
+}
+
+/// `explain_vertices` 的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan<'search, L: Label> {
+    /// 选择的策略
+    pub strategy: VertexStrategy<'search, L>,
+    /// 估计的候选顶点数量，不考虑数量限制
+    pub estimated_candidates: usize,
+    /// 查询的数量限制
+    pub limit: Option<usize>,
+}
+
+/// 候选顶点迭代器
+pub(crate) type Candidates<'graph, K> = SmallBox<dyn Iterator<Item = VertexId<K>> + 'graph, S8>;
+
+/// 在读取顶点权重之前判断候选顶点是否匹配，只使用 ID 和索引
+pub(crate) type Prefilter<'graph, K> = SmallBox<dyn Fn(VertexId<K>) -> bool + 'graph, S4>;
+
+/// 哈希索引支持的值
+fn hash_key<'search>(value: &Value<'search>) -> Option<IndexEntry<'search>> {
+    Some(match value {
+        Value::Str(v) => IndexEntry::Str(v),
+        Value::I8(v) => IndexEntry::Int(*v as i64),
+        Value::I16(v) => IndexEntry::Int(*v as i64),
+        Value::I32(v) => IndexEntry::Int(*v as i64),
+        Value::I64(v) => IndexEntry::Int(*v),
+        Value::U8(v) => IndexEntry::Int(*v as i64),
+        Value::U16(v) => IndexEntry::Int(*v as i64),
+        Value::U32(v) => IndexEntry::Int(*v as i64),
+        Value::U64(v) => IndexEntry::Int(*v as i64),
+        Value::F32(v) => IndexEntry::Float(*v as f64),
+        Value::F64(v) => IndexEntry::Float(*v),
+        _ => return None,
+    })
+}
+
+/// 范围索引支持的区间，两端必须是同一种整数
+fn int_bounds(range: &Range<Value>) -> Option<Range<i64>> {
+    Some(match (&range.start, &range.end) {
+        (Value::I8(start), Value::I8(end)) => *start as i64..*end as i64,
+        (Value::I16(start), Value::I16(end)) => *start as i64..*end as i64,
+        (Value::I32(start), Value::I32(end)) => *start as i64..*end as i64,
+        (Value::I64(start), Value::I64(end)) => *start..*end,
+        (Value::U8(start), Value::U8(end)) => *start as i64..*end as i64,
+        (Value::U16(start), Value::U16(end)) => *start as i64..*end as i64,
+        (Value::U32(start), Value::U32(end)) => *start as i64..*end as i64,
+        (Value::U64(start), Value::U64(end)) => *start as i64..*end as i64,
+        _ => return None,
+    })
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 允许 `vertices()` 使用手动建立的顶点索引（默认开启）
+    pub fn enable_vertex_query(&mut self) {
+        self.vertex_query_enabled = true;
+    }
+
+    /// 禁止 `vertices()` 使用手动建立的顶点索引，索引查询退化为全扫描
+    ///
+    /// 已建立的索引项保留，仍可通过 [`vertex_query`](Self::vertex_query) 直接查询。
+    pub fn disable_vertex_query(&mut self) {
+        self.vertex_query_enabled = false;
+    }
+
+    /// `vertices()` 是否使用手动建立的顶点索引
+    pub fn is_vertex_query_enabled(&self) -> bool {
+        self.vertex_query_enabled
+    }
+
+    /// 报告 `vertices(search)` 将使用的策略，不执行查询
+    ///
+    /// 候选数量从索引的分组大小得出，全文查询取各个词中最少的一个，
+    /// 因此只是估计值：实际结果还受数量限制、全文求交和待同步标签的影响。
+    pub fn explain_vertices<'search>(
+        &self,
+        search: &VertexSearch<'search, Self>,
+    ) -> QueryPlan<'search, Vertex::Label> {
+        let strategy = self.vertex_strategy(search);
+        let estimated_candidates = match &strategy {
+            VertexStrategy::Scan => self.vertices.len(),
+            VertexStrategy::LabelIndex { label } => self.vertex_labels.group_len(*label),
+            VertexStrategy::HashIndex { .. }
+            | VertexStrategy::RangeIndex { .. }
+            | VertexStrategy::DeclaredHash { .. }
+            | VertexStrategy::DeclaredRange { .. }
+            | VertexStrategy::DeclaredFullText { .. } => {
+                let mut candidates = self.vertex_candidates(&strategy);
+                (&mut *candidates).count()
+            }
+            VertexStrategy::FullText { tokens } => tokens
+                .iter()
+                .map(|token| self.vertex_query.query_string(token).count())
+                .min()
+                .unwrap_or(0),
+        };
+        let limit = match search {
+            VertexSearch::Scan { limit }
+            | VertexSearch::Label { limit, .. }
+            | VertexSearch::Index { limit, .. }
+            | VertexSearch::Range { limit, .. }
+            | VertexSearch::FullText { limit, .. } => *limit,
+            _ => None,
+        };
+        QueryPlan {
+            strategy,
+            estimated_candidates,
+            limit,
+        }
+    }
+
+    /// 用可选的预筛选查询缩小候选范围，再按谓词过滤顶点
+    ///
+    /// 预筛选经 `vertices()` 相同的策略执行，谓词只对候选顶点惰性调用；
+    /// `prefilter` 为 `None` 时对全部顶点调用谓词。
+    ///
+    /// # 示例
+    ///
+    /// ```rust
+    /// use graph_api_lib::{Graph, VertexReference};
+    /// use graph_api_test::Vertex;
+    /// use pi_slotmap_graph::SlotMapGraph;
+    ///
+    /// let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+    /// for (name, age) in [("Bryn", 45), ("Julia", 48), ("Sam", 19)] {
+    ///     graph.add_vertex(Vertex::Person {
+    ///         name: name.to_string(),
+    ///         age,
+    ///         unique_id: Default::default(),
+    ///         username: name.to_lowercase(),
+    ///         biography: String::new(),
+    ///     });
+    /// }
+    ///
+    /// // 先按年龄范围索引筛选，再检查名字
+    /// let adults = Vertex::person_by_age_range(40..50);
+    /// let found: Vec<_> = graph
+    ///     .find_vertices(Some(&adults), |_, vertex| matches!(vertex, Vertex::Person { name, .. } if name.starts_with('J')))
+    ///     .collect();
+    /// assert_eq!(found.len(), 1);
+    /// assert!(graph.find_first_vertex(None, |_, vertex| matches!(vertex, Vertex::Person { age: 19, .. })).is_some());
+    /// ```
+    pub fn find_vertices<'graph, P>(
+        &'graph self,
+        prefilter: Option<&VertexSearch<'_, Self>>,
+        pred: P,
+    ) -> impl Iterator<Item = VertexReference<'graph, Self>> + 'graph
+    where
+        P: Fn(VertexId<K>, &Vertex) -> bool + 'graph,
+    {
+        self.prefiltered(prefilter).filter(move |vertex| pred(vertex.id(), vertex.weight()))
+    }
+
+    /// 第一个满足谓词的顶点，参数同 [`find_vertices`](Self::find_vertices)
+    pub fn find_first_vertex(
+        &self,
+        prefilter: Option<&VertexSearch<'_, Self>>,
+        pred: impl Fn(VertexId<K>, &Vertex) -> bool,
+    ) -> Option<VertexReference<'_, Self>> {
+        self.prefiltered(prefilter).find(|vertex| pred(vertex.id(), vertex.weight()))
+    }
+
+    /// 预筛选查询的候选顶点，跳过已删除的顶点
+    fn prefiltered(&self, prefilter: Option<&VertexSearch<'_, Self>>) -> impl Iterator<Item = VertexReference<'_, Self>> + '_ {
+        let ((mut candidates, check), limit) = match prefilter {
+            Some(search) => (self.vertex_source(&self.vertex_strategy(search)), search.limit()),
+            None => (self.vertex_source(&VertexStrategy::Scan), usize::MAX),
+        };
+        std::iter::from_fn(move || candidates.next())
+            .filter(move |vertex_id| check.as_ref().is_none_or(|check| check(*vertex_id)))
+            .filter_map(move |vertex_id| self.vertex(vertex_id))
+            .take(limit)
+    }
+
+    /// 为查询选择候选顶点的来源，`vertices()` 与 `explain_vertices()` 共用
+    pub(crate) fn vertex_strategy<'search>(
+        &self,
+        search: &VertexSearch<'search, Self>,
+    ) -> VertexStrategy<'search, Vertex::Label> {
+        let indexed = self.vertex_query_enabled;
+        // 查询的索引由顶点类型声明时的索引类型
+        let declared = match search {
+            VertexSearch::Index { index, .. } | VertexSearch::Range { index, .. } | VertexSearch::FullText { index, .. }
+                if self.declared_index.is_declared(index) =>
+            {
+                Some(index.index_type())
+            }
+            _ => None,
+        };
+        match search {
+            VertexSearch::Label { label, .. } => VertexStrategy::LabelIndex { label: *label },
+            VertexSearch::Index { index, value, .. } if declared.is_some() => match IndexKey::from_value(value) {
+                Some(key) if declared == Some(IndexType::Hash) => VertexStrategy::DeclaredHash { index: *index, key },
+                _ => VertexStrategy::Scan,
+            },
+            VertexSearch::Range { index, range, .. } if declared.is_some() => {
+                match (IndexKey::from_value(&range.start), IndexKey::from_value(&range.end)) {
+                    (Some(start), Some(end)) if declared == Some(IndexType::Range) => {
+                        VertexStrategy::DeclaredRange { index: *index, bounds: start..end }
+                    }
+                    _ => VertexStrategy::Scan,
+                }
+            }
+            VertexSearch::FullText { index, search, .. } if declared.is_some() => {
+                let tokens: Vec<_> = match search {
+                    Value::Str(text) if declared == Some(IndexType::FullText) => declared::tokenize(text).collect(),
+                    _ => Vec::new(),
+                };
+                if tokens.is_empty() {
+                    VertexStrategy::Scan
+                } else {
+                    VertexStrategy::DeclaredFullText { index: *index, tokens }
+                }
+            }
+            VertexSearch::Index { value, .. } if indexed => match hash_key(value) {
+                Some(key) => VertexStrategy::HashIndex { key },
+                None => VertexStrategy::Scan,
+            },
+            VertexSearch::Range { range, .. } if indexed => match int_bounds(range) {
+                Some(bounds) => VertexStrategy::RangeIndex { bounds },
+                None => VertexStrategy::Scan,
+            },
+            VertexSearch::FullText {
+                search: Value::Str(text),
+                ..
+            } if indexed => {
+                let tokens: Vec<_> = text.split_whitespace().collect();
+                if tokens.is_empty() {
+                    VertexStrategy::Scan
+                } else {
+                    VertexStrategy::FullText { tokens }
+                }
+            }
+            _ => VertexStrategy::Scan,
+        }
+    }
+
+    /// 按策略产生候选顶点及其预筛选，`vertices()` 与谓词查询共用
+    ///
+    /// 标签查询直接遍历标签索引的分组，由预筛选按索引成员关系排除标签已改变的待同步顶点，
+    /// 只有通过预筛选的顶点才会读取权重；其余策略的候选顶点已按条件产生，不需要预筛选。
+    pub(crate) fn vertex_source(
+        &self,
+        strategy: &VertexStrategy<'_, Vertex::Label>,
+    ) -> (Candidates<'_, K>, Option<Prefilter<'_, K>>) {
+        match strategy {
+            VertexStrategy::LabelIndex { label } => {
+                let (label, labels, vertices) = (*label, &self.vertex_labels, &self.vertices);
+                let prefilter: Prefilter<'_, K> = smallbox!(move |vertex_id: VertexId<K>| {
+                    labels.contains(label, vertex_id, |vertex_id| vertices.get(vertex_id).map(Element::label))
+                });
+                (smallbox!(labels.candidates(label)), Some(prefilter))
+            }
+            _ => (self.vertex_candidates(strategy), None),
+        }
+    }
+
+    /// 按策略产生候选顶点，可能包含已删除的顶点，由调用方过滤
+    pub(crate) fn vertex_candidates(&self, strategy: &VertexStrategy<'_, Vertex::Label>) -> Candidates<'_, K> {
+        let query = &self.vertex_query;
+        match strategy {
+            VertexStrategy::Scan => smallbox!(self.vertices.keys()),
+            VertexStrategy::LabelIndex { label } => {
+                let vertices = &self.vertices;
+                smallbox!(self
+                    .vertex_labels
+                    .vertices(*label, move |vertex_id| vertices.get(vertex_id).map(Element::label)))
+            }
+            VertexStrategy::HashIndex { key } => match *key {
+                IndexEntry::Str(value) => smallbox!(query.query_string(value)),
+                IndexEntry::Int(value) => smallbox!(query.query_int(value)),
+                IndexEntry::Float(value) => smallbox!(query.query_float(value)),
+            },
+            VertexStrategy::RangeIndex { bounds } => smallbox!(query.range_int(bounds.clone())),
+            VertexStrategy::DeclaredHash { index, key } => {
+                let vertices = &self.vertices;
+                smallbox!(self.declared_index.get(*index, key, move |vertex_id| vertices.get(vertex_id)))
+            }
+            VertexStrategy::DeclaredRange { index, bounds } => {
+                let vertices = &self.vertices;
+                smallbox!(self.declared_index.range(*index, bounds, move |vertex_id| vertices.get(vertex_id)))
+            }
+            VertexStrategy::DeclaredFullText { index, tokens } => {
+                let vertices = &self.vertices;
+                smallbox!(self
+                    .declared_index
+                    .full_text(*index, tokens, move |vertex_id| vertices.get(vertex_id))
+                    .into_iter())
+            }
+            VertexStrategy::FullText { tokens } => {
+                // 从最少的词出发求交，结果不再借用查询字符串
+                let (first, rest) = tokens
+                    .iter()
+                    .copied()
+                    .min_by_key(|token| query.query_string(token).count())
+                    .map(|first| (first, tokens.iter().copied().filter(|token| *token != first).collect::<Vec<_>>()))
+                    .unwrap_or_default();
+                let matched: Vec<_> = query
+                    .query_string(first)
+                    .filter(|id| rest.iter().all(|token| query.contains_string(token, *id)))
+                    .collect();
+                smallbox!(matched.into_iter())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsClear, SupportsElementRemoval, VertexReference};
+    use graph_api_test::{Vertex, VertexIndex, VertexLabel};
+    use std::collections::HashSet;
+
+    fn person(name: &str, age: u64, biography: &str) -> Vertex {
+        Vertex::Person {
+            name: name.to_string(),
+            age,
+            unique_id: Default::default(),
+            username: name.to_lowercase(),
+            biography: biography.to_string(),
+        }
+    }
+
+    fn ids<'a>(iter: impl Iterator<Item = impl VertexReference<'a, SlotMapGraph<Vertex, ()>>>) -> HashSet<VertexId> {
+        iter.map(|vertex| vertex.id()).collect()
+    }
+
+    #[test]
+    fn test_plan_per_search_variant() {
+        let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+        let bryn = graph.add_vertex(person("Bryn", 28, "graph databases"));
+        let julia = graph.add_vertex(person("Julia", 45, "Graph theory"));
+        let rust = graph.add_vertex(Vertex::Rust);
+
+        let scan = VertexSearch::scan().with_limit(1);
+        let plan = graph.explain_vertices(&scan);
+        assert_eq!(plan.strategy, VertexStrategy::Scan);
+        assert_eq!((plan.estimated_candidates, plan.limit), (3, Some(1)));
+        assert_eq!(graph.vertices(&scan).count(), 1);
+
+        let label = Vertex::person();
+        let plan = graph.explain_vertices(&label);
+        assert_eq!(plan.strategy, VertexStrategy::LabelIndex { label: VertexLabel::Person });
+        assert_eq!((plan.estimated_candidates, plan.limit), (2, None));
+        assert_eq!(ids(graph.vertices(&label)), HashSet::from([bryn, julia]));
+        assert_eq!(ids(graph.vertices(&Vertex::rust())), HashSet::from([rust]));
+
+        // 声明的索引随顶点写入自动建立
+        let by_name = Vertex::person_by_name("Bryn");
+        let plan = graph.explain_vertices(&by_name);
+        assert_eq!(
+            plan.strategy,
+            VertexStrategy::DeclaredHash { index: VertexIndex::PersonName, key: IndexKey::Str("Bryn".to_string()) }
+        );
+        assert_eq!(plan.estimated_candidates, 1);
+        assert_eq!(ids(graph.vertices(&by_name)), HashSet::from([bryn]));
+
+        let by_age = Vertex::person_by_age_range(40..50);
+        let plan = graph.explain_vertices(&by_age);
+        assert_eq!(
+            plan.strategy,
+            VertexStrategy::DeclaredRange { index: VertexIndex::PersonAge, bounds: IndexKey::Int(40)..IndexKey::Int(50) }
+        );
+        assert_eq!(ids(graph.vertices(&by_age)), HashSet::from([julia]));
+
+        let by_username = Vertex::person_by_username_range("a".."c");
+        assert!(matches!(graph.explain_vertices(&by_username).strategy, VertexStrategy::DeclaredRange { .. }));
+        assert_eq!(ids(graph.vertices(&by_username)), HashSet::from([bryn]));
+
+        let by_biography = Vertex::person_by_biography("graph  theory");
+        let plan = graph.explain_vertices(&by_biography);
+        assert_eq!(
+            plan.strategy,
+            VertexStrategy::DeclaredFullText {
+                index: VertexIndex::PersonBiography,
+                tokens: vec!["graph".to_string(), "theory".to_string()],
+            }
+        );
+        assert_eq!(plan.estimated_candidates, 1);
+        assert_eq!(ids(graph.vertices(&by_biography)), HashSet::from([julia]));
+
+        // 空白文本没有词，退化为全扫描
+        let blank = Vertex::person_by_biography(" ");
+        assert_eq!(graph.explain_vertices(&blank).strategy, VertexStrategy::Scan);
+    }
+
+    #[test]
+    fn test_declared_index_follows_mutation() {
+        let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+        let bryn = graph.add_vertex(person("Bryn", 28, "graph databases"));
+        let julia = graph.add_vertex(person("Julia", 45, "graph theory"));
+
+        // 可变引用修改后查询立即反映新值，下一次修改图时写回索引
+        if let Some(Vertex::Person { age, .. }) = graph.vertex_weight_mut(bryn) {
+            *age = 50;
+        }
+        assert_eq!(ids(graph.vertices(&Vertex::person_by_age_range(40..60))), HashSet::from([bryn, julia]));
+        assert_eq!(graph.vertices(&Vertex::person_by_age_range(20..30)).count(), 0);
+        graph.add_vertex(Vertex::Rust);
+        assert_eq!(graph.explain_vertices(&Vertex::person_by_age_range(40..60)).estimated_candidates, 2);
+
+        graph.replace_vertex_weight(julia, person("Julia", 45, "compilers"));
+        assert_eq!(ids(graph.vertices(&Vertex::person_by_biography("graph"))), HashSet::from([bryn]));
+        assert!(graph.swap_vertex_weights(bryn, julia));
+        assert_eq!(ids(graph.vertices(&Vertex::person_by_name("Bryn"))), HashSet::from([julia]));
+
+        graph.remove_vertex(julia);
+        assert_eq!(graph.vertices(&Vertex::person_by_name("Bryn")).count(), 0);
+        SupportsClear::clear(&mut graph);
+        assert_eq!(graph.vertices(&Vertex::person_by_name("Julia")).count(), 0);
+    }
+
+    #[test]
+    fn test_find_vertices_with_prefilter() {
+        use std::cell::Cell;
+
+        let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+        for i in 0..200u64 {
+            graph.add_vertex(person(&format!("p{i}"), i % 100, ""));
+        }
+        graph.add_vertex(Vertex::Rust);
+
+        let accesses = Cell::new(0);
+        let pred = |_: VertexId, vertex: &Vertex| {
+            accesses.set(accesses.get() + 1);
+            matches!(vertex, Vertex::Person { age: 30..=39, name, .. } if name.ends_with('5'))
+        };
+
+        let scanned = ids(graph.find_vertices(None, pred));
+        let scan_accesses = accesses.replace(0);
+        let prefilter = Vertex::person_by_age_range(30..40);
+        let prefiltered = ids(graph.find_vertices(Some(&prefilter), pred));
+        let prefilter_accesses = accesses.replace(0);
+
+        assert_eq!(scanned.len(), 2);
+        assert_eq!(scanned, prefiltered);
+        assert_eq!(scan_accesses, 201);
+        assert_eq!(prefilter_accesses, 20);
+
+        let first = graph.find_first_vertex(Some(&prefilter), pred).unwrap();
+        assert!(scanned.contains(&first.id()));
+        assert!(graph.find_first_vertex(Some(&prefilter), |_, _| false).is_none());
+    }
+
+    #[test]
+    fn test_disabled_query_degrades_to_scan() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let alice = graph.add_vertex(25);
+        graph.add_vertex(30);
+        graph.index_vertex_string(alice, "Alice");
+
+        let by_name = VertexSearch::Index { index: (), value: Value::Str("Alice"), limit: None };
+        assert_eq!(graph.explain_vertices(&by_name).strategy, VertexStrategy::HashIndex { key: IndexEntry::Str("Alice") });
+        graph.disable_vertex_query();
+        assert!(!graph.is_vertex_query_enabled());
+        let plan = graph.explain_vertices(&by_name);
+        assert_eq!(plan.strategy, VertexStrategy::Scan);
+        assert_eq!(plan.estimated_candidates, 2);
+        assert_eq!(graph.vertices(&by_name).count(), 2);
+
+        graph.enable_vertex_query();
+        assert_eq!(graph.vertices(&by_name).map(|vertex| vertex.id()).collect::<Vec<_>>(), vec![alice]);
+
+        // 声明的索引不受影响
+        let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+        let bryn = graph.add_vertex(person("Bryn", 28, "graph"));
+        graph.disable_vertex_query();
+        assert!(matches!(graph.explain_vertices(&Vertex::person_by_name("Bryn")).strategy, VertexStrategy::DeclaredHash { .. }));
+        assert_eq!(ids(graph.vertices(&Vertex::person_by_name("Bryn"))), HashSet::from([bryn]));
+    }
+
+    #[test]
+    fn test_label_search_matches_naive_scan() {
+        use graph_api_test::Project;
+
+        let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+        let vertices: Vec<_> = (0..300)
+            .map(|i| match i % 3 {
+                0 => graph.add_vertex(person(&format!("p{i}"), i, "")),
+                1 => graph.add_vertex(Vertex::Project(Project { name: format!("x{i}") })),
+                _ => graph.add_vertex(Vertex::Rust),
+            })
+            .collect();
+        for vertex in vertices.iter().step_by(7) {
+            graph.remove_vertex(*vertex);
+        }
+        // 最后一次修改的顶点尚未同步，由预筛选读取当前标签
+        for vertex in vertices.iter().skip(1).step_by(11) {
+            if let Some(weight) = graph.vertex_weight_mut(*vertex) {
+                *weight = person("moved", 1, "");
+            }
+        }
+        assert!(!graph.vertex_labels.is_synced());
+
+        for label in [VertexLabel::Person, VertexLabel::Project, VertexLabel::Rust] {
+            let naive: HashSet<_> =
+                graph.vertices(&VertexSearch::scan()).filter(|vertex| vertex.weight().label() == label).map(|vertex| vertex.id()).collect();
+            let search = VertexSearch::Label { label, limit: None };
+            assert_eq!(ids(graph.vertices(&search)), naive);
+            assert_eq!(ids(graph.find_vertices(Some(&search), |_, _| true)), naive);
+
+            // 数量限制只计入匹配的顶点
+            let limited = VertexSearch::Label { label, limit: Some(5) };
+            let found = ids(graph.vertices(&limited));
+            assert_eq!(found.len(), 5);
+            assert!(found.is_subset(&naive));
+        }
+    }
+}
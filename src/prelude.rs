@@ -0,0 +1,185 @@
+/*!
+ * # 快速上手 (Prelude)
+ *
+ * 顶点和边的数据类型需要实现 `graph_api_lib::Element`，临时用字符串或空数据建图时，
+ * 为此专门定义类型和实现显得繁琐。本模块提供现成的包装类型和图的别名，
+ * 并重新导出常用的 trait，`use pi_slotmap_graph::prelude::*` 即可开始使用。
+ *
+ * ## 组件
+ *
+ * - [`Labeled`]：把任意 `T: Clone + Debug` 包装为标签为 `()` 的元素，通过 `Deref` 透明读取
+ * - [`StringGraph`]：顶点和边都是 `Labeled<String>` 的图，`&str` 可直接 `into()`
+ * - [`UnitGraph`]：顶点和边都不带数据的图，只关心拓扑结构时使用
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use pi_slotmap_graph::prelude::*;
+ *
+ * let mut graph = StringGraph::new();
+ * let alice = graph.add_vertex("Alice".into());
+ * let bob = graph.add_vertex("Bob".into());
+ * let knows = graph.add_edge(alice, bob, "knows".into());
+ *
+ * assert_eq!(graph.vertex_weight(alice).map(|name| name.as_str()), Some("Alice"));
+ * assert_eq!(graph.edge_weight(knows).map(|label| label.len()), Some(5));
+ * assert!(graph.has_edge(alice, bob));
+ *
+ * let mut topology = UnitGraph::new();
+ * let a = topology.add_vertex(Labeled(()));
+ * let b = topology.add_vertex(Labeled::default());
+ * topology.add_edge(a, b, Labeled(()));
+ * assert_eq!(topology.out_degree(a), 1);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use graph_api_lib::Element;
+use pi_slotmap::DefaultKey;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
+pub use crate::id::{EdgeId, VertexId};
+pub use graph_api_lib::{
+    Direction, EdgeReference, EdgeReferenceMut, Graph, SupportsClear, SupportsElementRemoval, VertexReference,
+    VertexReferenceMut,
+};
+
+/// 顶点和边都是字符串的图
+pub type StringGraph<K = DefaultKey> = SlotMapGraph<Labeled<String>, Labeled<String>, K>;
+
+/// 顶点和边都不带数据的图
+pub type UnitGraph<K = DefaultKey> = SlotMapGraph<Labeled<()>, Labeled<()>, K>;
+
+/// 标签为 `()` 的元素包装
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Labeled<T>(pub T);
+
+impl<T> Labeled<T> {
+    /// 取出内部的数据
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Labeled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Labeled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Labeled<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Labeled<String> {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+impl<T: Display> Display for Labeled<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Clone + Debug> Element for Labeled<T> {
+    type Label = ();
+
+    fn label(&self) -> Self::Label {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{EdgeSearch, VertexSearch};
+
+    /// Alice -> Bob -> Carol，Alice -> Carol
+    fn people() -> (StringGraph, [VertexId; 3], [EdgeId; 3]) {
+        let mut graph = StringGraph::new();
+        let [alice, bob, carol] = ["Alice", "Bob", "Carol"].map(|name| graph.add_vertex(name.into()));
+        let edges = [(alice, bob, "knows"), (bob, carol, "likes"), (alice, carol, "follows")]
+            .map(|(from, to, label)| graph.add_edge(from, to, label.into()));
+        (graph, [alice, bob, carol], edges)
+    }
+
+    #[test]
+    fn test_string_graph_add_and_query() {
+        let (graph, [alice, bob, carol], [knows, likes, _]) = people();
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (3, 3));
+        assert_eq!(graph.vertex_weight(bob).map(|name| name.as_str()), Some("Bob"));
+        assert_eq!(graph.edge_weight(likes).map(|label| label.to_string()), Some("likes".to_owned()));
+        assert_eq!(graph.vertex(carol).map(|vertex| vertex.weight().len()), Some(5));
+        assert_eq!(graph.edge(knows).map(|edge| (edge.tail(), edge.head())), Some((alice, bob)));
+        assert_eq!(graph.edge_endpoints(likes), Some((bob, carol)));
+        assert!(graph.has_edge(alice, carol));
+        assert!(!graph.has_edge(carol, alice));
+    }
+
+    #[test]
+    fn test_string_graph_traversal() {
+        let (graph, [alice, bob, carol], _) = people();
+        let mut names: Vec<_> = graph.vertices(&VertexSearch::scan()).map(|vertex| vertex.weight().0.clone()).collect();
+        names.sort();
+        assert_eq!(names, ["Alice", "Bob", "Carol"]);
+
+        let mut heads: Vec<_> = graph.edges(alice, &EdgeSearch::scan().outgoing()).map(|edge| edge.head()).collect();
+        heads.sort();
+        let mut expected = vec![bob, carol];
+        expected.sort();
+        assert_eq!(heads, expected);
+
+        let incoming: Vec<_> = graph.incoming_edges(carol).map(|edge| edge.weight().0.clone()).collect();
+        assert_eq!(incoming.len(), 2);
+        assert!(incoming.contains(&"likes".to_owned()) && incoming.contains(&"follows".to_owned()));
+        assert_eq!(graph.degree(bob), 2);
+    }
+
+    #[test]
+    fn test_string_graph_mutation() {
+        let (mut graph, [alice, ..], [knows, ..]) = people();
+        graph.vertex_weight_mut(alice).unwrap().push_str(" Smith");
+        assert_eq!(graph.vertex_weight(alice).map(|name| name.as_str()), Some("Alice Smith"));
+
+        if let Some(mut edge) = graph.edge_mut(knows) {
+            *edge.weight_mut() = "trusts".into();
+        }
+        assert_eq!(graph.edge_weight(knows).map(|label| label.as_str()), Some("trusts"));
+    }
+
+    #[test]
+    fn test_string_graph_removal_and_clear() {
+        let (mut graph, [alice, bob, carol], [knows, likes, follows]) = people();
+        assert_eq!(graph.remove_edge(knows).map(Labeled::into_inner), Some("knows".to_owned()));
+        assert!(!graph.has_edge(alice, bob));
+
+        assert_eq!(graph.remove_vertex(carol), Some(Labeled::from("Carol")));
+        assert!(graph.edge(likes).is_none() && graph.edge(follows).is_none());
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (2, 0));
+        assert_eq!(graph.validate(), Ok(()));
+
+        Graph::clear(&mut graph);
+        assert_eq!(graph.vertex_count(), 0);
+    }
+
+    #[test]
+    fn test_unit_graph_topology() {
+        let mut graph = UnitGraph::new();
+        let [a, b, c] = [(); 3].map(|unit| graph.add_vertex(unit.into()));
+        graph.add_edge(a, b, Labeled(()));
+        graph.add_edge(b, c, Labeled::default());
+        assert_eq!(graph.bfs(a).map(|(vertex, _)| vertex).collect::<Vec<_>>(), vec![a, b, c]);
+        assert!(graph.topological_sort().is_ok());
+    }
+}
@@ -0,0 +1,222 @@
+/*!
+ * # 属性图 (Property Graph)
+ *
+ * 不想为每张图定义顶点和边结构体时，可以使用弱类型的属性包：
+ * 每个元素带一个字符串标签和 `HashMap<String, IndexValue>` 属性表。
+ *
+ * ## 组件
+ *
+ * - [`PropertyVertex`] / [`PropertyEdge`]：带标签和属性表的顶点与边
+ * - [`PropertyGraph`]：`SlotMapGraph<PropertyVertex, PropertyEdge>` 的别名
+ *
+ * ## 自动索引
+ *
+ * 通过 [`add_property_vertex`](crate::SlotMapGraph::add_property_vertex) 添加的顶点，
+ * 其标签会写入字符串索引，所有属性会按属性名写入 [`SimpleVertexQuery`](crate::index::SimpleVertexQuery)
+ * 的属性索引；之后经由 `set_prop` / `remove_prop` 修改属性会同步更新索引。
+ * 直接修改 `props` 字段的变化不会被索引追踪。
+ *
+ * `graph_api_lib::Element` 要求标签为 `Copy` 类型，因此元素的 `Element::Label` 为 `()`，
+ * 字符串标签只作为普通字段保存。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use pi_slotmap_graph::property::{PropertyGraph, PropertyVertex};
+ *
+ * let mut graph = PropertyGraph::new();
+ * let alice = graph.add_property_vertex(
+ *     PropertyVertex::new("person").with_prop("name", "Alice").with_prop("age", 30u32),
+ * );
+ *
+ * assert_eq!(graph.query_prop("age", 30i64).collect::<Vec<_>>(), vec![alice]);
+ *
+ * graph.set_prop(alice, "age", 31u32).unwrap();
+ * assert_eq!(graph.query_prop("age", 30i64).count(), 0);
+ * assert_eq!(graph.query_prop("age", 31i64).count(), 1);
+ * ```
+ */
+
+use crate::error::GraphError;
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use crate::index::IndexValue;
+use graph_api_lib::{Element, Graph, SupportsElementRemoval};
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::HashMap;
+
+/// 以属性包作为顶点和边的图
+pub type PropertyGraph<K = DefaultKey> = SlotMapGraph<PropertyVertex, PropertyEdge, K>;
+
+/// 带字符串标签和属性表的顶点
+#[derive(Debug, Clone, Default)]
+pub struct PropertyVertex {
+    /// 顶点标签
+    pub label: String,
+    /// 属性表
+    pub props: HashMap<String, IndexValue>,
+}
+
+/// 带字符串标签和属性表的边
+#[derive(Debug, Clone, Default)]
+pub struct PropertyEdge {
+    /// 边标签
+    pub label: String,
+    /// 属性表
+    pub props: HashMap<String, IndexValue>,
+}
+
+macro_rules! impl_property_element {
+    ($ty:ident) => {
+        impl $ty {
+            /// 创建没有属性的元素
+            pub fn new(label: impl Into<String>) -> Self {
+                Self {
+                    label: label.into(),
+                    props: HashMap::new(),
+                }
+            }
+
+            /// 添加一个属性，用于链式构建
+            pub fn with_prop(mut self, name: impl Into<String>, value: impl Into<IndexValue>) -> Self {
+                self.props.insert(name.into(), value.into());
+                self
+            }
+
+            /// 获取属性值
+            pub fn prop(&self, name: &str) -> Option<&IndexValue> {
+                self.props.get(name)
+            }
+        }
+
+        impl Element for $ty {
+            type Label = ();
+
+            fn label(&self) -> Self::Label {}
+        }
+    };
+}
+
+impl_property_element!(PropertyVertex);
+impl_property_element!(PropertyEdge);
+
+impl<K: Key> SlotMapGraph<PropertyVertex, PropertyEdge, K> {
+    /// 添加属性顶点，并把标签和全部属性写入顶点索引
+    pub fn add_property_vertex(&mut self, vertex: PropertyVertex) -> VertexId<K> {
+        let index_entries: Vec<(String, IndexValue)> =
+            vertex.props.iter().map(|(name, value)| (name.clone(), value.clone())).collect();
+        let label = vertex.label.clone();
+
+        let vertex_id = self.add_vertex(vertex);
+        self.index_vertex_string(vertex_id, &label);
+        for (name, value) in &index_entries {
+            self.vertex_query_mut().insert_property(name, value, vertex_id);
+        }
+        vertex_id
+    }
+
+    /// 添加属性边
+    pub fn add_property_edge(&mut self, from: VertexId<K>, to: VertexId<K>, edge: PropertyEdge) -> EdgeId<K> {
+        self.add_edge(from, to, edge)
+    }
+
+    /// 设置顶点属性并同步索引，返回旧值
+    ///
+    /// # 错误
+    ///
+    /// 顶点不存在时返回 [`GraphError::VertexNotFound`]。
+    pub fn set_prop(
+        &mut self,
+        vertex_id: VertexId<K>,
+        name: &str,
+        value: impl Into<IndexValue>,
+    ) -> Result<Option<IndexValue>, GraphError<K>> {
+        let value = value.into();
+        let vertex = self.vertex_weight_mut(vertex_id).ok_or(GraphError::VertexNotFound(vertex_id))?;
+        let old = vertex.props.insert(name.to_string(), value.clone());
+
+        let query = self.vertex_query_mut();
+        if let Some(old) = &old {
+            query.remove_property(name, old, vertex_id);
+        }
+        query.insert_property(name, &value, vertex_id);
+        Ok(old)
+    }
+
+    /// 删除顶点属性并同步索引，返回旧值
+    ///
+    /// # 错误
+    ///
+    /// 顶点不存在时返回 [`GraphError::VertexNotFound`]。
+    pub fn remove_prop(&mut self, vertex_id: VertexId<K>, name: &str) -> Result<Option<IndexValue>, GraphError<K>> {
+        let vertex = self.vertex_weight_mut(vertex_id).ok_or(GraphError::VertexNotFound(vertex_id))?;
+        let old = vertex.props.remove(name);
+        if let Some(old) = &old {
+            self.vertex_query_mut().remove_property(name, old, vertex_id);
+        }
+        Ok(old)
+    }
+
+    /// 按属性名和值查询顶点
+    ///
+    /// 数值按值比较，不区分整数宽度，见 [`SimpleVertexQuery::insert_property`](crate::index::SimpleVertexQuery::insert_property)。
+    pub fn query_prop(&self, name: &str, value: impl Into<IndexValue>) -> Box<dyn Iterator<Item = VertexId<K>> + '_> {
+        self.vertex_query().query_property(name, &value.into())
+    }
+
+    /// 删除属性顶点及其关联边，并从索引中移除它的标签和属性
+    pub fn remove_property_vertex(&mut self, vertex_id: VertexId<K>) -> Option<PropertyVertex> {
+        let vertex = self.remove_vertex(vertex_id)?;
+        let query = self.vertex_query_mut();
+        for (name, value) in &vertex.props {
+            query.remove_property(name, value, vertex_id);
+        }
+        query.remove_string(&vertex.label, vertex_id);
+        Some(vertex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_graph_indexing() {
+        let mut graph = PropertyGraph::new();
+        let alice = graph.add_property_vertex(
+            PropertyVertex::new("person")
+                .with_prop("name", "Alice")
+                .with_prop("age", 30u32)
+                .with_prop("score", 9.5f64),
+        );
+        let bob = graph.add_property_vertex(
+            PropertyVertex::new("person").with_prop("name", "Bob").with_prop("age", 30i64),
+        );
+        let acme = graph.add_property_vertex(PropertyVertex::new("company").with_prop("name", "Acme"));
+        graph.add_property_edge(alice, acme, PropertyEdge::new("works_at").with_prop("since", 2020i32));
+
+        // 整数属性不区分宽度
+        let thirty: Vec<_> = graph.query_prop("age", 30u64).collect();
+        assert_eq!(thirty.len(), 2);
+        assert!(thirty.contains(&alice) && thirty.contains(&bob));
+
+        assert_eq!(graph.query_prop("name", "Acme").collect::<Vec<_>>(), vec![acme]);
+        assert_eq!(graph.query_prop("score", 9.5f64).collect::<Vec<_>>(), vec![alice]);
+        assert_eq!(graph.query_prop("name", 30u32).count(), 0);
+        assert_eq!(graph.vertex_query().query_string("company").collect::<Vec<_>>(), vec![acme]);
+
+        // 修改属性后索引同步更新
+        let old = graph.set_prop(bob, "age", 31u32).unwrap();
+        assert!(matches!(old, Some(IndexValue::I64(30))));
+        assert_eq!(graph.query_prop("age", 30i32).collect::<Vec<_>>(), vec![alice]);
+        assert_eq!(graph.query_prop("age", 31i32).collect::<Vec<_>>(), vec![bob]);
+
+        graph.remove_prop(alice, "age").unwrap();
+        assert_eq!(graph.query_prop("age", 30i32).count(), 0);
+
+        graph.remove_property_vertex(acme);
+        assert_eq!(graph.query_prop("name", "Acme").count(), 0);
+        assert_eq!(graph.vertex_query().query_string("company").count(), 0);
+        assert!(matches!(graph.set_prop(acme, "name", "Gone"), Err(GraphError::VertexNotFound(v)) if v == acme));
+    }
+}
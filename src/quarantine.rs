@@ -0,0 +1,243 @@
+/*!
+ * # 已删除 ID 诊断 (Removed Id Quarantine)
+ *
+ * slotmap 的版本号保证删除后的 ID 不会解析到新元素，但使用已删除 ID 的错误
+ * 只表现为 `vertex()` / `edge()` 悄悄返回 `None`。开启 `removal-diagnostics` 特性后，
+ * 图在一个固定容量的环形缓冲区中记录最近删除的顶点和边 ID 及删除时间：
+ *
+ * - [`recently_removed`](SlotMapGraph::recently_removed) 列出缓冲区中的 ID
+ * - `vertex()` / `edge()` 未命中且 ID 在缓冲区中时，调用
+ *   [`set_stale_id_hook`](SlotMapGraph::set_stale_id_hook) 设置的回调，
+ *   传入可直接打印的 [`StaleIdWarning`]（例如 "vertex V4294967299 was removed 120ms ago"）
+ *
+ * 缓冲区默认保留最近 [`DEFAULT_CAPACITY`] 个 ID，更早的记录被覆盖。
+ * `clear()` 不逐个记录被清空的元素。特性关闭时本模块和图中的相关字段都不会编译。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::{Graph, SupportsElementRemoval};
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use std::sync::atomic::{AtomicUsize, Ordering};
+ *
+ * static HITS: AtomicUsize = AtomicUsize::new(0);
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * graph.set_stale_id_hook(|warning| {
+ *     eprintln!("{warning}");
+ *     HITS.fetch_add(1, Ordering::Relaxed);
+ * });
+ * let a = graph.add_vertex(1);
+ * graph.remove_vertex(a);
+ *
+ * assert!(graph.vertex(a).is_none());
+ * assert_eq!(HITS.load(Ordering::Relaxed), 1);
+ * assert_eq!(graph.recently_removed().count(), 1);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, ElementId};
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::time::{Duration, Instant};
+
+/// 默认保留的已删除 ID 数量
+pub const DEFAULT_CAPACITY: usize = 64;
+
+/// 已删除的顶点或边 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RemovedId<K: Key = DefaultKey> {
+    /// 顶点
+    Vertex(VertexId<K>),
+    /// 边
+    Edge(EdgeId<K>),
+}
+
+/// 查询命中已删除 ID 时传给回调的诊断信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleIdWarning<K: Key = DefaultKey> {
+    /// 被查询的 ID
+    pub id: RemovedId<K>,
+    /// 距删除经过的时间
+    pub elapsed: Duration,
+}
+
+impl<K: Key> Display for StaleIdWarning<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.id {
+            RemovedId::Vertex(id) => write!(f, "vertex {id}")?,
+            RemovedId::Edge(id) => write!(f, "edge {id}")?,
+        }
+        write!(f, " was removed {}ms ago", self.elapsed.as_millis())
+    }
+}
+
+/// 命中已删除 ID 时的回调
+type StaleIdHook<K> = Box<dyn Fn(&StaleIdWarning<K>) + Send + Sync>;
+
+/// 最近删除的 ID 环形缓冲区
+pub(crate) struct Quarantine<K: Key> {
+    /// 按删除顺序排列，最早的在前
+    removed: VecDeque<(RemovedId<K>, Instant)>,
+    capacity: usize,
+    hook: Option<StaleIdHook<K>>,
+}
+
+impl<K: Key> Debug for Quarantine<K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Quarantine")
+            .field("removed", &self.removed.len())
+            .field("capacity", &self.capacity)
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+impl<K: Key> Default for Quarantine<K> {
+    fn default() -> Self {
+        Self {
+            removed: VecDeque::new(),
+            capacity: DEFAULT_CAPACITY,
+            hook: None,
+        }
+    }
+}
+
+impl<K: Key> Quarantine<K> {
+    /// 记录一个被删除的 ID，缓冲区满时覆盖最早的记录
+    pub(crate) fn record(&mut self, id: RemovedId<K>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.removed.len() == self.capacity {
+            self.removed.pop_front();
+        }
+        self.removed.push_back((id, Instant::now()));
+    }
+
+    /// 查询未命中时检查 ID 是否刚被删除，是则调用回调
+    pub(crate) fn check(&self, id: RemovedId<K>) {
+        let Some(hook) = self.hook.as_ref() else {
+            return;
+        };
+        if let Some((_, removed_at)) = self.removed.iter().rev().find(|(removed, _)| *removed == id) {
+            hook(&StaleIdWarning {
+                id,
+                elapsed: removed_at.elapsed(),
+            });
+        }
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 最近删除的顶点和边 ID 及删除时间，最早删除的在前
+    pub fn recently_removed(&self) -> impl Iterator<Item = (ElementId<Self>, Instant)> + '_ {
+        self.quarantine.removed.iter().map(|(id, removed_at)| {
+            let id = match *id {
+                RemovedId::Vertex(id) => ElementId::Vertex(id),
+                RemovedId::Edge(id) => ElementId::Edge(id),
+            };
+            (id, *removed_at)
+        })
+    }
+
+    /// 设置 `vertex()` / `edge()` 命中已删除 ID 时的回调，替换之前的回调
+    pub fn set_stale_id_hook(&mut self, hook: impl Fn(&StaleIdWarning<K>) + Send + Sync + 'static) {
+        self.quarantine.hook = Some(Box::new(hook));
+    }
+
+    /// 移除命中已删除 ID 时的回调
+    pub fn clear_stale_id_hook(&mut self) {
+        self.quarantine.hook = None;
+    }
+
+    /// 修改保留的已删除 ID 数量，`0` 表示不再记录
+    pub fn set_removed_id_capacity(&mut self, capacity: usize) {
+        let quarantine = &mut self.quarantine;
+        quarantine.capacity = capacity;
+        while quarantine.removed.len() > capacity {
+            quarantine.removed.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+    use std::sync::{Arc, Mutex};
+
+    type Warnings = Arc<Mutex<Vec<StaleIdWarning>>>;
+
+    fn recording_graph() -> (SlotMapGraph<u32, u32>, Warnings) {
+        let warnings: Warnings = Arc::default();
+        let mut graph = SlotMapGraph::new();
+        let sink = Arc::clone(&warnings);
+        graph.set_stale_id_hook(move |warning| sink.lock().unwrap().push(*warning));
+        (graph, warnings)
+    }
+
+    #[test]
+    fn test_stale_lookup_fires_hook() {
+        let (mut graph, warnings) = recording_graph();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        let c = graph.add_vertex(3);
+        let ab = graph.add_edge(a, b, 10);
+        let bc = graph.add_edge(b, c, 20);
+
+        graph.remove_edge(ab);
+        // 级联删除的边也会被记录
+        graph.remove_vertex(c);
+        assert!(graph.vertex(a).is_some());
+        assert!(warnings.lock().unwrap().is_empty());
+
+        assert!(graph.edge(ab).is_none());
+        assert!(graph.vertex(c).is_none());
+        assert!(graph.edge(bc).is_none());
+        let ids: Vec<_> = warnings.lock().unwrap().iter().map(|warning| warning.id).collect();
+        assert_eq!(ids, vec![RemovedId::Edge(ab), RemovedId::Vertex(c), RemovedId::Edge(bc)]);
+        let message = warnings.lock().unwrap()[1].to_string();
+        assert!(message.starts_with("vertex ") && message.ends_with("ms ago"), "{message}");
+
+        let removed: Vec<_> = graph.recently_removed().map(|(id, _)| id).collect();
+        assert_eq!(removed.len(), 3);
+        assert!(removed.contains(&ElementId::Vertex(c)));
+
+        // 从未存在过的 ID 不会触发回调
+        warnings.lock().unwrap().clear();
+        let mut other: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+        let foreign = (0..4).map(|i| other.add_vertex(i)).last().unwrap();
+        assert!(graph.vertex(foreign).is_none());
+        assert!(warnings.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let (mut graph, warnings) = recording_graph();
+        graph.set_removed_id_capacity(2);
+        let ids: Vec<_> = (0..3).map(|i| graph.add_vertex(i)).collect();
+        for id in &ids {
+            graph.remove_vertex(*id);
+        }
+        assert_eq!(graph.recently_removed().count(), 2);
+        assert!(graph.vertex(ids[0]).is_none());
+        assert!(warnings.lock().unwrap().is_empty());
+        assert!(graph.vertex(ids[2]).is_none());
+        assert_eq!(warnings.lock().unwrap().len(), 1);
+
+        graph.clear_stale_id_hook();
+        assert!(graph.vertex(ids[2]).is_none());
+        assert_eq!(warnings.lock().unwrap().len(), 1);
+        graph.set_removed_id_capacity(0);
+        assert_eq!(graph.recently_removed().count(), 0);
+    }
+}
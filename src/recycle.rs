@@ -0,0 +1,194 @@
+/*!
+ * # 存储复用 (Storage Recycling)
+ *
+ * [`into_storage`](SlotMapGraph::into_storage) 清空图并交出其已分配的存储 [`GraphStorage`]，
+ * [`from_storage`](SlotMapGraph::from_storage) 用它构建新的空图。
+ * 频繁创建、丢弃同类临时图时，可以把 `GraphStorage` 放入池中反复使用，避免每次重新分配。
+ *
+ * ## 复用的存储
+ *
+ * - 顶点和边的槽位存储
+ * - 邻接表及各顶点出边/入边列表的缓冲区
+ * - 顶点标签索引、声明索引、边标签索引和固定顶点集合
+ * - 已开启的端点对索引、邻接边标签缓存、标签缓存、创建顺序、元素位标记和坐标索引
+ *
+ * ## 不会带入新图的内容
+ *
+ * 所有元素和索引项在交出前被清空，槽位版本号随之递增，旧图的顶点和边 ID 在新图中查不到。
+ * 简单查询器的索引项被清空，操作计数归零，变更日志丢弃全部条目。
+ * 结构版本号继续递增而不是归零，旧图上取得的 [`VertexSelection`](crate::VertexSelection)
+ * 等按版本号判断失效的结果不会被误认为仍然有效。
+ *
+ * 已开启的可选索引、变更日志容量等设置保持不变，池中的存储按同一配置复用。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::SlotMapGraph;
+ * use pi_slotmap_graph::recycle::GraphStorage;
+ *
+ * let mut pool: Vec<GraphStorage<u32, ()>> = Vec::new();
+ * for frame in 0..3 {
+ *     let mut graph = pool.pop().map_or_else(SlotMapGraph::new, SlotMapGraph::from_storage);
+ *     let root = graph.add_vertex(frame);
+ *     for i in 0..100 {
+ *         let child = graph.add_vertex(i);
+ *         graph.add_edge(root, child, ());
+ *     }
+ *     assert_eq!(graph.vertex_count(), 101);
+ *     pool.push(graph.into_storage());
+ * }
+ * assert!(pool[0].vertex_capacity() >= 101);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+
+/// 从图中回收的已清空存储，由 [`SlotMapGraph::from_storage`] 重新使用
+#[derive(Debug)]
+pub struct GraphStorage<Vertex, Edge, K = DefaultKey>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 已清空、保留容量的图
+    graph: SlotMapGraph<Vertex, Edge, K>,
+}
+
+impl<Vertex, Edge, K> GraphStorage<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 不重新分配时可容纳的顶点数量
+    pub fn vertex_capacity(&self) -> usize {
+        self.graph.vertex_capacity()
+    }
+
+    /// 不重新分配时可容纳的边数量
+    pub fn edge_capacity(&self) -> usize {
+        self.graph.edge_capacity()
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 清空图并交出已分配的存储
+    ///
+    /// 清空方式同 [`clear_keep_capacity`](Self::clear_keep_capacity)（包括简单查询器），
+    /// 此外计数归零、丢弃变更日志条目。
+    pub fn into_storage(mut self) -> GraphStorage<Vertex, Edge, K> {
+        self.clear_keep_capacity();
+        self.reset_metrics();
+        self.trim_change_log(self.version());
+        GraphStorage { graph: self }
+    }
+
+    /// 使用回收的存储构建空图
+    ///
+    /// 重新填充到不超过回收前的规模时，顶点、边和邻接表不会重新分配。
+    pub fn from_storage(storage: GraphStorage<Vertex, Edge, K>) -> Self {
+        storage.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id::VertexId;
+    use graph_api_lib::{Graph, VertexSearch};
+
+    type TestGraph = SlotMapGraph<u32, u32>;
+
+    fn fill(graph: &mut TestGraph) -> Vec<VertexId> {
+        let vertices: Vec<_> = (0..200).map(|i| graph.add_vertex(i)).collect();
+        for i in 0..1000 {
+            graph.add_edge(vertices[i % 200], vertices[(i * 13 + 7) % 200], i as u32);
+        }
+        vertices
+    }
+
+    fn capacities(graph: &TestGraph) -> (usize, usize, usize) {
+        (graph.vertex_capacity(), graph.edge_capacity(), graph.edges.adjacency_capacity())
+    }
+
+    #[test]
+    fn test_recycled_storage_is_empty_and_keeps_capacity() {
+        let mut graph = TestGraph::new();
+        graph.enable_pair_index();
+        graph.enable_metrics();
+        graph.enable_change_log(None);
+        let old = fill(&mut graph);
+        graph.index_vertex_int(old[0], 7);
+        let old_edge = graph.find_edge(old[0], old[7]).unwrap();
+        let selection = graph.select_vertices(&VertexSearch::scan());
+        let before = capacities(&graph);
+        let version = graph.version();
+
+        let storage = graph.into_storage();
+        assert_eq!((storage.vertex_capacity(), storage.edge_capacity()), (before.0, before.1));
+        let mut graph = TestGraph::from_storage(storage);
+
+        assert_eq!((graph.vertex_count(), graph.edge_count()), (0, 0));
+        assert_eq!(capacities(&graph), before);
+        assert!(graph.version() > version);
+        assert!(selection.is_stale(&graph));
+        assert_eq!(graph.vertex_query().query_int(7).count(), 0);
+        assert_eq!(graph.metrics().unwrap().vertices_added, 0);
+        assert!(graph.change_log().unwrap().is_empty());
+
+        // 旧 ID 和旧索引项都不会出现在新图中
+        let new = fill(&mut graph);
+        assert_eq!(capacities(&graph), before);
+        assert!(old.iter().all(|&id| !graph.contains_vertex(id)));
+        assert!(graph.edge(old_edge).is_none());
+        assert_eq!(graph.edges_with_label(()).count(), 1000);
+        assert_eq!(graph.vertices_with_label(()).count(), 200);
+        assert_eq!(graph.find_edge(old[0], old[7]), None);
+        assert!(graph.find_edge(new[0], new[7]).is_some());
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_recycled_storage_has_no_stale_query_entries() {
+        let mut graph = TestGraph::new();
+        let old = fill(&mut graph);
+        graph.index_vertex_string(old[0], "root");
+        graph.index_vertex_int(old[1], 7);
+
+        let mut graph = TestGraph::from_storage(graph.into_storage());
+        let new = fill(&mut graph);
+        // 新顶点重用旧槽位，但不继承旧的索引项
+        assert_eq!(graph.vertex_query().query_string("root").count(), 0);
+        assert_eq!(graph.vertex_query().query_int(7).count(), 0);
+        graph.index_vertex_string(new[1], "root");
+        assert_eq!(graph.vertex_query().query_string("root").collect::<Vec<_>>(), vec![new[1]]);
+        assert_eq!(graph.vertex_query().range_int(0..100).count(), 0);
+    }
+
+    #[test]
+    fn test_repeated_recycling_does_not_grow() {
+        let mut graph = TestGraph::new();
+        fill(&mut graph);
+        let mut storage = graph.into_storage();
+        let mut graph = TestGraph::from_storage(storage);
+        fill(&mut graph);
+        let expected = capacities(&graph);
+        for _ in 0..1000 {
+            storage = graph.into_storage();
+            graph = TestGraph::from_storage(storage);
+            fill(&mut graph);
+            assert_eq!(capacities(&graph), expected);
+        }
+        assert_eq!(graph.validate(), Ok(()));
+    }
+}
@@ -0,0 +1,256 @@
+/*!
+ * # 数据内的顶点引用 (Weight References)
+ *
+ * 顶点或边的数据有时直接保存其他顶点的 [`VertexId`]（例如独立于边的 "parent" 指针），
+ * 这些 ID 不受图维护，删除顶点后会悄悄悬空。实现 [`ValidateRefs`] 声明数据中引用的顶点后：
+ *
+ * - [`validate_refs`](SlotMapGraph::validate_refs)：在 [`validate`](SlotMapGraph::validate)
+ *   的基础上检查所有引用都存在，否则返回 [`GraphError::DanglingWeightRef`]
+ * - [`remove_vertex_checked`](SlotMapGraph::remove_vertex_checked)：按 [`RefPolicy`]
+ *   决定顶点仍被引用时是否拒绝删除
+ *
+ * `()` 和 `u32`、`u64`、`f32`、`f64` 已实现 [`ValidateRefs`]（不引用任何顶点），以它们为数据时无需额外实现。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::{Element, Graph, SupportsElementRemoval};
+ * use pi_slotmap_graph::{GraphError, SlotMapGraph, VertexId};
+ * use pi_slotmap_graph::error::WeightHolder;
+ * use pi_slotmap_graph::refs::ValidateRefs;
+ *
+ * #[derive(Debug, Clone)]
+ * struct Node {
+ *     parent: Option<VertexId>,
+ * }
+ *
+ * impl Element for Node {
+ *     type Label = ();
+ *
+ *     fn label(&self) -> Self::Label {}
+ * }
+ *
+ * impl ValidateRefs for Node {
+ *     fn referenced_vertices(&self) -> impl Iterator<Item = VertexId> {
+ *         self.parent.into_iter()
+ *     }
+ * }
+ *
+ * let mut graph: SlotMapGraph<Node, ()> = SlotMapGraph::new();
+ * let root = graph.add_vertex(Node { parent: None });
+ * let child = graph.add_vertex(Node { parent: Some(root) });
+ * assert_eq!(graph.validate_refs(), Ok(()));
+ *
+ * graph.remove_vertex(root);
+ * assert_eq!(
+ *     graph.validate_refs(),
+ *     Err(GraphError::DanglingWeightRef { holder: WeightHolder::Vertex(child), missing: root })
+ * );
+ * ```
+ */
+
+use crate::error::{GraphError, WeightHolder};
+use crate::graph::{RemovePolicy, SlotMapGraph};
+use crate::id::VertexId;
+use graph_api_lib::Element;
+use pi_slotmap::{DefaultKey, Key};
+
+/// 数据中保存了顶点 ID 的元素类型
+pub trait ValidateRefs<K: Key = DefaultKey> {
+    /// 数据中引用的顶点，不包括通过边表达的连接
+    fn referenced_vertices(&self) -> impl Iterator<Item = VertexId<K>>;
+}
+
+macro_rules! impl_no_refs {
+    ($($ty:ty),*) => {
+        $(
+            impl<K: Key> ValidateRefs<K> for $ty {
+                fn referenced_vertices(&self) -> impl Iterator<Item = VertexId<K>> {
+                    std::iter::empty()
+                }
+            }
+        )*
+    };
+}
+
+// 与 graph_api_lib 为其实现了 Element 的基础类型一致
+impl_no_refs!((), u32, u64, f32, f64);
+
+/// 删除仍被数据引用的顶点时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RefPolicy {
+    /// 照常删除，引用随之悬空，可由 `validate_refs` 发现
+    #[default]
+    Ignore,
+    /// 拒绝删除并返回 [`GraphError::VertexReferenced`]，图保持不变
+    Refuse,
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element + ValidateRefs<K>,
+    Edge: Element + ValidateRefs<K>,
+    K: Key,
+{
+    /// 先执行 [`validate`](Self::validate)，再检查顶点和边数据中引用的顶点都存在
+    ///
+    /// 复杂度为 O(V + E + R)，R 为引用总数。
+    pub fn validate_refs(&self) -> Result<(), GraphError<K>> {
+        self.validate()?;
+        match self.weight_refs().find(|(_, referenced)| !self.vertices.contains(*referenced)) {
+            Some((holder, missing)) => Err(GraphError::DanglingWeightRef { holder, missing }),
+            None => Ok(()),
+        }
+    }
+
+    /// 按 `policy` 删除顶点，`refs` 为 [`RefPolicy::Refuse`] 时顶点仍被引用则拒绝删除
+    ///
+    /// 顶点自身的数据和将随顶点一起删除的关联边不计为引用。
+    pub fn remove_vertex_checked(
+        &mut self,
+        vertex_id: VertexId<K>,
+        policy: RemovePolicy,
+        refs: RefPolicy,
+    ) -> Result<Vertex, GraphError<K>> {
+        if refs == RefPolicy::Refuse && self.vertices.contains(vertex_id) {
+            let holder = self
+                .weight_refs()
+                .filter(|(_, referenced)| *referenced == vertex_id)
+                .map(|(holder, _)| holder)
+                .find(|holder| match *holder {
+                    WeightHolder::Vertex(vertex) => vertex != vertex_id,
+                    WeightHolder::Edge(edge) => self
+                        .edges
+                        .get_connection(edge)
+                        .is_some_and(|info| info.from() != vertex_id && info.to() != vertex_id),
+                });
+            if let Some(holder) = holder {
+                return Err(GraphError::VertexReferenced { vertex: vertex_id, holder });
+            }
+        }
+        self.remove_vertex_policy(vertex_id, policy)
+    }
+
+    /// 所有元素数据中的引用，按顶点、边的顺序
+    fn weight_refs(&self) -> impl Iterator<Item = (WeightHolder<K>, VertexId<K>)> + '_ {
+        let vertex_refs = self.vertices.iter().flat_map(|(id, weight)| {
+            weight
+                .referenced_vertices()
+                .map(move |referenced| (WeightHolder::Vertex(id), referenced))
+        });
+        let edge_refs = self.edges.iter_with_connections().flat_map(|(id, weight, _)| {
+            weight
+                .referenced_vertices()
+                .map(move |referenced| (WeightHolder::Edge(id), referenced))
+        });
+        vertex_refs.chain(edge_refs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::{Graph, SupportsElementRemoval};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Node {
+        name: &'static str,
+        parent: Option<VertexId>,
+    }
+
+    impl Element for Node {
+        type Label = ();
+
+        fn label(&self) -> Self::Label {}
+    }
+
+    impl ValidateRefs for Node {
+        fn referenced_vertices(&self) -> impl Iterator<Item = VertexId> {
+            self.parent.into_iter()
+        }
+    }
+
+    /// 边数据记录经由的顶点
+    #[derive(Debug, Clone, PartialEq)]
+    struct Via(Vec<VertexId>);
+
+    impl Element for Via {
+        type Label = ();
+
+        fn label(&self) -> Self::Label {}
+    }
+
+    impl ValidateRefs for Via {
+        fn referenced_vertices(&self) -> impl Iterator<Item = VertexId> {
+            self.0.iter().copied()
+        }
+    }
+
+    fn node(name: &'static str, parent: Option<VertexId>) -> Node {
+        Node { name, parent }
+    }
+
+    #[test]
+    fn test_valid_and_dangling_refs() {
+        let mut graph: SlotMapGraph<Node, Via> = SlotMapGraph::new();
+        let root = graph.add_vertex(node("root", None));
+        let a = graph.add_vertex(node("a", Some(root)));
+        let b = graph.add_vertex(node("b", Some(a)));
+        let hub = graph.add_vertex(node("hub", None));
+        let edge = graph.add_edge(a, b, Via(vec![hub]));
+        assert_eq!(graph.validate_refs(), Ok(()));
+
+        graph.remove_vertex(hub);
+        assert_eq!(
+            graph.validate_refs(),
+            Err(GraphError::DanglingWeightRef { holder: WeightHolder::Edge(edge), missing: hub })
+        );
+        graph.remove_edge(edge);
+        assert_eq!(graph.validate_refs(), Ok(()));
+
+        graph.remove_vertex(root);
+        let err = graph.validate_refs().unwrap_err();
+        assert_eq!(err, GraphError::DanglingWeightRef { holder: WeightHolder::Vertex(a), missing: root });
+        assert!(err.to_string().starts_with("Weight of vertex "), "{err}");
+    }
+
+    #[test]
+    fn test_refuse_referenced_vertex() {
+        let mut graph: SlotMapGraph<Node, Via> = SlotMapGraph::new();
+        let root = graph.add_vertex(node("root", None));
+        let child = graph.add_vertex(node("child", Some(root)));
+        let lone = graph.add_vertex(node("lone", None));
+        // 自引用和随顶点删除的关联边不阻止删除
+        let selfish = graph.add_vertex(node("selfish", None));
+        graph.vertex_weight_mut(selfish).unwrap().parent = Some(selfish);
+        graph.add_edge(selfish, lone, Via(vec![selfish]));
+
+        assert_eq!(
+            graph.remove_vertex_checked(root, RemovePolicy::Cascade, RefPolicy::Refuse),
+            Err(GraphError::VertexReferenced { vertex: root, holder: WeightHolder::Vertex(child) })
+        );
+        assert!(graph.contains_vertex(root));
+        assert_eq!(
+            graph.remove_vertex_checked(selfish, RemovePolicy::Cascade, RefPolicy::Refuse).map(|n| n.name),
+            Ok("selfish")
+        );
+        assert_eq!(graph.validate_refs(), Ok(()));
+
+        // 边数据中的引用同样阻止删除
+        let edge = graph.add_edge(child, root, Via(vec![lone]));
+        assert_eq!(
+            graph.remove_vertex_checked(lone, RemovePolicy::Cascade, RefPolicy::Refuse),
+            Err(GraphError::VertexReferenced { vertex: lone, holder: WeightHolder::Edge(edge) })
+        );
+
+        assert_eq!(
+            graph.remove_vertex_checked(root, RemovePolicy::Cascade, RefPolicy::Ignore).map(|n| n.name),
+            Ok("root")
+        );
+        assert!(matches!(graph.validate_refs(), Err(GraphError::DanglingWeightRef { .. })));
+        assert_eq!(
+            graph.remove_vertex_checked(root, RemovePolicy::Cascade, RefPolicy::Refuse),
+            Err(GraphError::VertexNotFound(root))
+        );
+    }
+}
@@ -0,0 +1,216 @@
+/*!
+ * # 顶点选择 (Vertex Selection)
+ *
+ * [`VertexSelection`] 保存一次顶点查询的结果以及查询时图的 [`version`](SlotMapGraph::version)，
+ * 用于在之后的读取中复用查询结果，而不是持有一个可能悄悄失效的 `Vec<VertexId>`。
+ *
+ * ## 失效
+ *
+ * 选择不借用图，图可以在此期间被修改：
+ *
+ * - [`is_stale`](VertexSelection::is_stale) 在图版本变化后返回 `true`，调用方据此决定是否重新查询
+ * - [`iter`](VertexSelection::iter) 跳过已删除的顶点，其余顶点按选择时的顺序产生
+ *
+ * 选择不会补上之后新增的匹配顶点。
+ *
+ * ## 集合运算
+ *
+ * 并集、交集和差集保持左侧选择的顺序（并集中右侧独有的顶点追加在末尾），
+ * 结果记录两侧较早的版本，因此只要任一侧已失效，结果也视为失效。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::{Graph, SupportsElementRemoval, VertexReference, VertexSearch};
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+ * let a = graph.add_vertex(1);
+ * let b = graph.add_vertex(2);
+ *
+ * let selection = graph.select_vertices(&VertexSearch::scan());
+ * assert_eq!(selection.len(), 2);
+ *
+ * graph.remove_vertex(a);
+ * assert!(selection.is_stale(&graph));
+ * let ids: Vec<_> = selection.iter(&graph).map(|vertex| vertex.id()).collect();
+ * assert_eq!(ids, vec![b]);
+ * ```
+ */
+
+use crate::graph::{SlotMapGraph, VertexReference};
+use crate::id::VertexId;
+use graph_api_lib::{Element, Graph, VertexReference as _, VertexSearch};
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::HashSet;
+
+/// 带图版本的顶点查询结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexSelection<K: Key = DefaultKey> {
+    /// 选择的顶点，不重复
+    ids: Vec<VertexId<K>>,
+    /// 选择时图的结构版本
+    version: u64,
+}
+
+impl<K: Key> VertexSelection<K> {
+    /// 选择的顶点数量，包括已删除的顶点
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// 是否没有选择任何顶点
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// 是否选择了该顶点
+    pub fn contains(&self, vertex_id: VertexId<K>) -> bool {
+        self.ids.contains(&vertex_id)
+    }
+
+    /// 选择的顶点 ID，不检查是否仍然存在
+    pub fn ids(&self) -> &[VertexId<K>] {
+        &self.ids
+    }
+
+    /// 选择时图的结构版本
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// 图结构自选择以来是否改变过
+    pub fn is_stale<Vertex, Edge>(&self, graph: &SlotMapGraph<Vertex, Edge, K>) -> bool
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        self.version != graph.version()
+    }
+
+    /// 按选择时的顺序遍历仍然存在的顶点
+    pub fn iter<'a, Vertex, Edge>(
+        &'a self,
+        graph: &'a SlotMapGraph<Vertex, Edge, K>,
+    ) -> impl Iterator<Item = VertexReference<'a, SlotMapGraph<Vertex, Edge, K>>> + 'a
+    where
+        Vertex: Element,
+        Edge: Element,
+    {
+        self.ids.iter().filter_map(move |id| graph.vertex(*id))
+    }
+
+    /// 并集，右侧独有的顶点追加在末尾
+    pub fn union(&self, other: &Self) -> Self {
+        let mut seen: HashSet<_> = self.ids.iter().copied().collect();
+        let mut ids = self.ids.clone();
+        ids.extend(other.ids.iter().copied().filter(|id| seen.insert(*id)));
+        self.combine(other, ids)
+    }
+
+    /// 交集
+    pub fn intersection(&self, other: &Self) -> Self {
+        let right: HashSet<_> = other.ids.iter().copied().collect();
+        let ids = self.ids.iter().copied().filter(|id| right.contains(id)).collect();
+        self.combine(other, ids)
+    }
+
+    /// 差集，只保留不在右侧中的顶点
+    pub fn difference(&self, other: &Self) -> Self {
+        let right: HashSet<_> = other.ids.iter().copied().collect();
+        let ids = self.ids.iter().copied().filter(|id| !right.contains(id)).collect();
+        self.combine(other, ids)
+    }
+
+    fn combine(&self, other: &Self, ids: Vec<VertexId<K>>) -> Self {
+        Self {
+            ids,
+            version: self.version.min(other.version),
+        }
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 执行顶点查询并保存结果
+    ///
+    /// 结果与 [`vertices`](Graph::vertices) 相同（包括数量限制），并记录当前的图版本。
+    pub fn select_vertices(&self, search: &VertexSearch<'_, Self>) -> VertexSelection<K> {
+        let mut seen = HashSet::new();
+        let ids = self
+            .vertices(search)
+            .map(|vertex| vertex.id())
+            .filter(|id| seen.insert(*id))
+            .collect();
+        VertexSelection {
+            ids,
+            version: self.version(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::SupportsElementRemoval;
+    use graph_api_test::{Project, Vertex, VertexLabel};
+
+    fn project() -> Vertex {
+        Vertex::Project(Project { name: String::new() })
+    }
+
+    #[test]
+    fn test_stale_selection_skips_removed_vertices() {
+        let mut graph: SlotMapGraph<Vertex, ()> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..3).map(|_| graph.add_vertex(project())).collect();
+        let rust = graph.add_vertex(Vertex::Rust);
+
+        let projects = graph.select_vertices(&Vertex::project());
+        assert_eq!(projects.len(), 3);
+        assert!(!projects.is_stale(&graph));
+        assert!(projects.iter(&graph).all(|vertex| vertex.weight().label() == VertexLabel::Project));
+
+        // 修改权重不改变结构版本
+        graph.vertex_weight_mut(rust);
+        assert!(!projects.is_stale(&graph));
+
+        graph.remove_vertex(ids[1]);
+        assert!(projects.is_stale(&graph));
+        assert_eq!(projects.len(), 3);
+        let remaining: HashSet<_> = projects.iter(&graph).map(|vertex| vertex.id()).collect();
+        assert_eq!(remaining, HashSet::from([ids[0], ids[2]]));
+
+        // 新增的匹配顶点不会出现在旧选择中，重新查询即可
+        graph.add_vertex(project());
+        assert_eq!(projects.iter(&graph).count(), 2);
+        assert_eq!(graph.select_vertices(&Vertex::project()).len(), 3);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let mut graph: SlotMapGraph<u32, ()> = SlotMapGraph::new();
+        let ids: Vec<_> = (0..4).map(|i| graph.add_vertex(i)).collect();
+        let all = graph.select_vertices(&VertexSearch::scan());
+        let first = graph.select_vertices(&VertexSearch::scan().with_limit(2));
+
+        let rest = all.difference(&first);
+        assert_eq!(rest.len(), 2);
+        assert!(first.intersection(&rest).is_empty());
+        assert_eq!(rest.union(&first).len(), 4);
+        assert_eq!(all.intersection(&first).ids(), first.ids());
+        assert!(ids.iter().all(|id| all.contains(*id)));
+
+        // 结果记录较早的版本
+        graph.add_vertex(9);
+        let fresh = graph.select_vertices(&VertexSearch::scan());
+        let combined = fresh.union(&all);
+        assert_eq!(combined.len(), 5);
+        assert_eq!(combined.version(), all.version());
+        assert!(combined.is_stale(&graph));
+        assert!(!fresh.intersection(&fresh).is_stale(&graph));
+    }
+}
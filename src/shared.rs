@@ -0,0 +1,250 @@
+/*!
+ * # 共享边数据 (Shared Edge Payloads)
+ *
+ * 大量边携带相同数据时，每条边各存一份副本会浪费内存。以 [`Shared<E>`] 作为边类型后，
+ * 每条边只保存一个 `Arc<E>`，相同的数据由 [`PayloadTable`] 去重后在边之间共享，
+ * 内存随不同数据的个数而不是边数增长。
+ *
+ * ## 组件
+ *
+ * - [`Shared`]：`Arc` 包装的边数据，通过 `Deref` 透明读取，标签取自内部数据
+ * - [`PayloadTable`]：要求 `E: Hash + Eq` 的去重表，相同的值返回同一个 `Arc`
+ * - [`SharedGraph`]：`SlotMapGraph<V, Shared<E>>` 的别名
+ *
+ * ## 修改策略
+ *
+ * 共享数据采用写时复制：[`edge_payload_mut`](SlotMapGraph::edge_payload_mut)
+ * （以及对 `Shared` 的 `DerefMut`）在数据仍被其他边或去重表引用时先复制一份，
+ * 只修改这一条边，其他共享同一数据的边保持不变。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::shared::{PayloadTable, SharedGraph};
+ *
+ * let mut graph: SharedGraph<(), u32> = SharedGraph::new();
+ * let mut payloads = PayloadTable::new();
+ * let a = graph.add_vertex(());
+ * let b = graph.add_vertex(());
+ *
+ * let first = graph.add_edge_shared(a, b, payloads.intern(1));
+ * let second = graph.add_edge_shared(b, a, payloads.intern(1));
+ * assert_eq!(payloads.len(), 1);
+ * assert_eq!(graph.distinct_edge_payloads(), 1);
+ * assert_eq!(**graph.edge_weight(first).unwrap(), 1);
+ *
+ * // 写时复制：只有这一条边改变
+ * *graph.edge_payload_mut(second).unwrap() = 2;
+ * assert_eq!(**graph.edge_weight(first).unwrap(), 1);
+ * assert_eq!(graph.distinct_edge_payloads(), 2);
+ * ```
+ */
+
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, Graph};
+use pi_slotmap::{DefaultKey, Key};
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+/// 以共享数据作为边的图
+pub type SharedGraph<V, E, K = DefaultKey> = SlotMapGraph<V, Shared<E>, K>;
+
+/// 在边之间共享的数据
+#[derive(Debug, Default, PartialEq, Eq, Hash)]
+pub struct Shared<T>(Arc<T>);
+
+impl<T> Shared<T> {
+    /// 包装一份独占的数据
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// 内部的 `Arc`
+    pub fn arc(&self) -> &Arc<T> {
+        &self.0
+    }
+
+    /// 两份数据是否为同一个分配
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> From<Arc<T>> for Shared<T> {
+    fn from(arc: Arc<T>) -> Self {
+        Self(arc)
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// 写时复制：数据被共享时先复制再修改
+impl<T: Clone> DerefMut for Shared<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.0)
+    }
+}
+
+impl<T: Element> Element for Shared<T> {
+    type Label = T::Label;
+
+    fn label(&self) -> Self::Label {
+        self.0.label()
+    }
+}
+
+/// 按值去重的共享数据表
+#[derive(Debug)]
+pub struct PayloadTable<T> {
+    payloads: HashSet<Arc<T>>,
+}
+
+impl<T> Default for PayloadTable<T> {
+    fn default() -> Self {
+        Self { payloads: HashSet::new() }
+    }
+}
+
+impl<T: Hash + Eq> PayloadTable<T> {
+    /// 创建空表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回与 `value` 相等的共享数据，表中没有时加入
+    pub fn intern(&mut self, value: T) -> Arc<T> {
+        if let Some(existing) = self.payloads.get(&value) {
+            return Arc::clone(existing);
+        }
+        let payload = Arc::new(value);
+        self.payloads.insert(Arc::clone(&payload));
+        payload
+    }
+
+    /// 不同数据的个数
+    pub fn len(&self) -> usize {
+        self.payloads.len()
+    }
+
+    /// 表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.payloads.is_empty()
+    }
+
+    /// 丢弃只被表自身引用的数据，返回丢弃的个数
+    pub fn purge_unused(&mut self) -> usize {
+        let before = self.payloads.len();
+        self.payloads.retain(|payload| Arc::strong_count(payload) > 1);
+        before - self.payloads.len()
+    }
+}
+
+impl<Vertex, E, K> SlotMapGraph<Vertex, Shared<E>, K>
+where
+    Vertex: Element,
+    E: Element,
+    K: Key,
+{
+    /// 添加一条 `from -> to` 的边，与其他边共享 `payload`
+    pub fn add_edge_shared(&mut self, from: VertexId<K>, to: VertexId<K>, payload: Arc<E>) -> EdgeId<K> {
+        self.add_edge(from, to, Shared(payload))
+    }
+
+    /// 边数据的可变引用，数据被共享时先复制，其他边不受影响
+    pub fn edge_payload_mut(&mut self, edge_id: EdgeId<K>) -> Option<&mut E>
+    where
+        E: Clone,
+    {
+        self.edge_weight_mut(edge_id).map(|shared| &mut **shared)
+    }
+
+    /// 所有边引用的不同数据分配的个数
+    pub fn distinct_edge_payloads(&self) -> usize {
+        self.edges
+            .iter()
+            .map(|(_, shared)| Arc::as_ptr(shared.arc()))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_api_lib::SupportsElementRemoval;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    enum Dep {
+        Normal,
+        Dev,
+        Build(String),
+    }
+
+    impl Element for Dep {
+        type Label = ();
+
+        fn label(&self) -> Self::Label {}
+    }
+
+    #[test]
+    fn test_payloads_are_deduplicated() {
+        let mut graph: SharedGraph<u32, Dep> = SharedGraph::new();
+        let mut payloads = PayloadTable::new();
+        let vertices: Vec<_> = (0..100).map(|i| graph.add_vertex(i)).collect();
+
+        let kinds = [Dep::Normal, Dep::Dev, Dep::Build("cc".to_string())];
+        for i in 0..10_000 {
+            let payload = payloads.intern(kinds[i % 3].clone());
+            graph.add_edge_shared(vertices[i % 100], vertices[(i * 7 + 1) % 100], payload);
+        }
+        assert_eq!(graph.edge_count(), 10_000);
+        assert_eq!(payloads.len(), 3);
+        assert_eq!(graph.distinct_edge_payloads(), 3);
+        let first = graph.edge_ids().next().unwrap();
+        assert_eq!(**graph.edge_weight(first).unwrap(), Dep::Normal);
+    }
+
+    #[test]
+    fn test_mutation_copies_on_write() {
+        let mut graph: SharedGraph<u32, Dep> = SharedGraph::new();
+        let mut payloads = PayloadTable::new();
+        let a = graph.add_vertex(0);
+        let b = graph.add_vertex(1);
+        let ab = graph.add_edge_shared(a, b, payloads.intern(Dep::Normal));
+        let ba = graph.add_edge_shared(b, a, payloads.intern(Dep::Normal));
+        assert!(graph.edge_weight(ab).unwrap().ptr_eq(graph.edge_weight(ba).unwrap()));
+
+        *graph.edge_payload_mut(ab).unwrap() = Dep::Dev;
+        assert_eq!(**graph.edge_weight(ab).unwrap(), Dep::Dev);
+        assert_eq!(**graph.edge_weight(ba).unwrap(), Dep::Normal);
+        assert_eq!(graph.distinct_edge_payloads(), 2);
+        // 去重表中的值不受影响
+        assert_eq!(*payloads.intern(Dep::Normal), Dep::Normal);
+        assert_eq!(payloads.len(), 1);
+
+        // 独占的数据原地修改，不再复制
+        let before = Arc::as_ptr(graph.edge_weight(ab).unwrap().arc());
+        *graph.edge_payload_mut(ab).unwrap() = Dep::Build("ld".to_string());
+        assert_eq!(Arc::as_ptr(graph.edge_weight(ab).unwrap().arc()), before);
+
+        graph.remove_edge(ba);
+        assert_eq!(payloads.purge_unused(), 1);
+        assert!(payloads.is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+/*!
+ * # 拓扑快照 (Topology Snapshot)
+ *
+ * 只记录图的连接关系（有哪些边、各自的端点），不复制顶点和边的数据，
+ * 用于低成本地保存和恢复连接结构，例如布局优化中先尝试重新连线、效果不好再撤销。
+ *
+ * ## 恢复策略
+ *
+ * [`SlotMapGraph::restore_topology_with`] 让图的边集合回到快照时的状态：
+ *
+ * - 快照之后新增的边被删除
+ * - 快照之后被删除的边重新添加，其数据由回调提供；回调返回 `None` 时恢复失败
+ * - 顶点不受影响：快照之后新增的顶点保留，被删除的顶点不会恢复
+ *
+ * 快照不保存边数据，因此 [`SlotMapGraph::restore_topology`] 只能撤销新增的边，
+ * 遇到被删除的边会返回 [`GraphError::MissingEdgePayload`]。
+ * 需要恢复已删除的边时，调用方自行保留其数据（例如在删除时收集返回值），
+ * 或在 `Edge: Clone` 时另外克隆一份。
+ *
+ * 恢复前先检查全部条件，出错时图不做任何修改。
+ *
+ * ## 边 ID
+ *
+ * SlotMap 无法在指定的键上插入元素，重新添加的边会得到新的 [`EdgeId`]，
+ * 并排在端点邻接表的末尾。恢复返回 `(快照中的 ID, 新 ID)` 对照表，
+ * 其余边的 ID 和相对顺序不变。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use graph_api_lib::{Graph, SupportsElementRemoval};
+ * use pi_slotmap_graph::SlotMapGraph;
+ *
+ * let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+ * let a = graph.add_vertex(());
+ * let b = graph.add_vertex(());
+ * let c = graph.add_vertex(());
+ * let ab = graph.add_edge(a, b, 1);
+ *
+ * let snapshot = graph.topology_snapshot();
+ * let removed = graph.remove_edge(ab).unwrap();
+ * graph.add_edge(a, c, 2);
+ *
+ * let remap = graph.restore_topology_with(&snapshot, |_, _, _| Some(removed)).unwrap();
+ * assert_eq!(remap.len(), 1);
+ * assert!(graph.has_edge(a, b) && !graph.has_edge(a, c));
+ * ```
+ */
+
+use crate::collections::EdgeIdSet;
+use crate::error::GraphError;
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, Graph, SupportsElementRemoval};
+use pi_slotmap::{DefaultKey, Key};
+
+/// 重新添加的边：(快照中的 ID, 新 ID)
+pub type EdgeRemap<K = DefaultKey> = Vec<(EdgeId<K>, EdgeId<K>)>;
+
+/// 图的边 ID 与端点表
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopologySnapshot<K: Key = DefaultKey> {
+    /// 快照时的全部边：(边, 起点, 终点)
+    edges: Vec<(EdgeId<K>, VertexId<K>, VertexId<K>)>,
+}
+
+impl<K: Key> TopologySnapshot<K> {
+    /// 快照中的边数量
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// 快照中的全部边及其端点
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId<K>, VertexId<K>, VertexId<K>)> + '_ {
+        self.edges.iter().copied()
+    }
+}
+
+impl<Vertex, Edge, K> SlotMapGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 记录当前的边 ID 与端点，不复制任何数据
+    pub fn topology_snapshot(&self) -> TopologySnapshot<K> {
+        TopologySnapshot {
+            edges: self
+                .edges
+                .iter_with_connections()
+                .map(|(id, _, info)| (id, info.from(), info.to()))
+                .collect(),
+        }
+    }
+
+    /// 恢复快照时的边集合，只能撤销快照之后新增的边
+    ///
+    /// 等价于回调总是返回 `None` 的 [`restore_topology_with`](Self::restore_topology_with)。
+    pub fn restore_topology(&mut self, snapshot: &TopologySnapshot<K>) -> Result<(), GraphError<K>> {
+        self.restore_topology_with(snapshot, |_, _, _| None).map(|_| ())
+    }
+
+    /// 恢复快照时的边集合
+    ///
+    /// `payload` 以 (快照中的边 ID, 起点, 终点) 为参数，为每条需要重新添加的边提供数据。
+    /// 成功时返回重新添加的边的 `(快照中的 ID, 新 ID)`，按快照中的顺序排列。
+    ///
+    /// # 错误
+    ///
+    /// - 需要重新添加的边的端点已被删除时返回 [`GraphError::VertexNotFound`]
+    /// - `payload` 返回 `None` 时返回 [`GraphError::MissingEdgePayload`]
+    ///
+    /// 出错时图不做任何修改。
+    pub fn restore_topology_with<F>(
+        &mut self,
+        snapshot: &TopologySnapshot<K>,
+        mut payload: F,
+    ) -> Result<EdgeRemap<K>, GraphError<K>>
+    where
+        F: FnMut(EdgeId<K>, VertexId<K>, VertexId<K>) -> Option<Edge>,
+    {
+        let mut restored = Vec::new();
+        for &(edge, from, to) in &snapshot.edges {
+            if self.edges.contains(edge) {
+                continue;
+            }
+            for vertex in [from, to] {
+                if !self.vertices.contains(vertex) {
+                    return Err(GraphError::VertexNotFound(vertex));
+                }
+            }
+            let weight = payload(edge, from, to).ok_or(GraphError::MissingEdgePayload(edge))?;
+            restored.push((edge, from, to, weight));
+        }
+
+        let kept: EdgeIdSet<K> = snapshot.edges.iter().map(|(edge, _, _)| *edge).collect();
+        let added: Vec<_> = self.edge_ids().filter(|edge| !kept.contains(*edge)).collect();
+        for edge in added {
+            self.remove_edge(edge);
+        }
+
+        Ok(restored
+            .into_iter()
+            .map(|(edge, from, to, weight)| (edge, self.add_edge(from, to, weight)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按端点排序的边表，用于比较拓扑
+    fn wiring(graph: &SlotMapGraph<(), u32>) -> Vec<(VertexId, VertexId, u32)> {
+        let mut edges: Vec<_> = graph.all_edges().map(|(_, weight, from, to)| (from, to, *weight)).collect();
+        edges.sort_by_key(|(from, to, weight)| (from.key(), to.key(), *weight));
+        edges
+    }
+
+    #[test]
+    fn test_rewire_and_restore() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let [a, b, c, d] = [(); 4].map(|_| graph.add_vertex(()));
+        let ab = graph.add_edge(a, b, 1);
+        let bc = graph.add_edge(b, c, 2);
+        let cd = graph.add_edge(c, d, 3);
+        graph.add_edge(c, d, 4);
+
+        let before = wiring(&graph);
+        let snapshot = graph.topology_snapshot();
+        assert_eq!(snapshot.edge_count(), 4);
+
+        // 重新连线：删除两条边、新增三条边
+        let mut removed = std::collections::HashMap::new();
+        for edge in [ab, cd] {
+            removed.insert(edge, graph.remove_edge(edge).unwrap());
+        }
+        graph.add_edge(a, c, 5);
+        graph.add_edge(d, a, 6);
+        graph.add_edge(b, b, 7);
+        assert_ne!(wiring(&graph), before);
+
+        let remap = graph
+            .restore_topology_with(&snapshot, |edge, _, _| removed.remove(&edge))
+            .unwrap();
+        assert_eq!(remap.iter().map(|(old, _)| *old).collect::<Vec<_>>(), vec![ab, cd]);
+        assert_eq!(graph.edge_endpoints(remap[0].1), Some((a, b)));
+        assert_eq!(wiring(&graph), before);
+        assert!(graph.contains_edge(bc));
+        assert_eq!(graph.validate(), Ok(()));
+
+        // 只新增边时无需提供数据
+        let snapshot = graph.topology_snapshot();
+        graph.add_edge(d, c, 8);
+        graph.restore_topology(&snapshot).unwrap();
+        assert_eq!(wiring(&graph), before);
+    }
+
+    #[test]
+    fn test_restore_errors_leave_graph_untouched() {
+        let mut graph: SlotMapGraph<(), u32> = SlotMapGraph::new();
+        let [a, b, c] = [(); 3].map(|_| graph.add_vertex(()));
+        let ab = graph.add_edge(a, b, 1);
+        let bc = graph.add_edge(b, c, 2);
+        let snapshot = graph.topology_snapshot();
+
+        graph.remove_edge(ab);
+        let extra = graph.add_edge(c, a, 3);
+        assert_eq!(graph.restore_topology(&snapshot), Err(GraphError::MissingEdgePayload(ab)));
+        assert!(graph.contains_edge(extra));
+
+        // 被删除的边的端点已不存在
+        graph.remove_vertex(a);
+        assert!(!graph.contains_edge(extra));
+        assert_eq!(
+            graph.restore_topology_with(&snapshot, |_, _, _| Some(1)),
+            Err(GraphError::VertexNotFound(a))
+        );
+        assert!(graph.contains_edge(bc));
+        assert_eq!(graph.edge_count(), 1);
+    }
+}
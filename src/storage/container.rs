@@ -1,4 +1,4 @@
-/**
+/*!
  * # 通用容器接口
  *
  * 为存储容器提供统一的接口和通用功能。
@@ -1,4 +1,4 @@
-/**
+/*!
  * # EdgeContainer - 边存储容器
  *
  * 基于 `pi_slotmap::SlotMap` 的高性能边存储实现。
@@ -14,24 +14,160 @@
  *
  * ### 存储性能
  * - **插入**: O(1) 平均时间复杂度
- * - **删除**: O(1) 平均时间复杂度
+ * - **删除**: O(d) 时间复杂度，d为两个端点的度数（维护邻接表）
  * - **查找**: O(1) 时间复杂度
- * - **内存**: 紧凑存储，无额外开销
+ * - **邻接查询**: O(d) 时间复杂度，基于每个顶点的出边/入边列表
+ *
+ * ## 存储布局
+ *
+ * 边数据与连接信息以 `(E, EdgeInfo)` 的形式存放在同一个 SlotMap 槽位中，
+ * 读取权重、端点或两者都只需一次查找，小的 `Copy` 边类型（如 `u32` 权重）与连接信息紧邻存放。
+ *
+ * ## 邻接顺序
+ *
+ * 每个顶点的出边和入边列表按插入顺序排列，新边追加到末尾，
+ * 与 SlotMap 的槽位复用无关。删除边时默认平移后续元素（[`EdgeRemovalOrder::Shift`]），
+ * 保持其余边的相对顺序；也可切换为 [`EdgeRemovalOrder::Swap`]，
+ * 以 O(1) 的交换删除换取顺序的改变。`move_to_front` 和 `reorder` 用于显式调整顺序。
  */
 
 use super::{Storage, StorageKey};
+use super::super::error::GraphError;
 use super::super::id::{EdgeId, EdgeInfo, VertexId};
+use super::super::memory::hash_map_bytes;
 use graph_api_lib::Element;
-use pi_slotmap::{DefaultKey, SlotMap};
+use pi_slotmap::{DefaultKey, Key, SlotMap};
+use std::collections::{HashMap, HashSet};
+use std::mem::size_of;
+
+/// 删除边时邻接表的维护方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EdgeRemovalOrder {
+    /// 平移后续元素，保持其余边的插入顺序
+    #[default]
+    Shift,
+    /// 与列表末尾的边交换后删除，末尾的边会移动到被删除边的位置
+    Swap,
+}
+
+/// 单个顶点的邻接表
+///
+/// 分别记录以该顶点为起点和终点的边，列表内默认按插入顺序排列。
+/// 自环边同时出现在同一顶点的 `outgoing` 和 `incoming` 中。
+///
+/// 度数计数器随边的增删同步维护，使度数查询为 O(1)。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Adjacency<K: Key = DefaultKey> {
+    outgoing: Vec<EdgeId<K>>,
+    incoming: Vec<EdgeId<K>>,
+    /// 出度
+    out_degree: u32,
+    /// 入度
+    in_degree: u32,
+    /// 自环数量，计算总度数时自环只计一次
+    self_loops: u32,
+}
+
+impl<K: Key> Adjacency<K> {
+    /// 出边列表
+    #[inline]
+    pub fn outgoing(&self) -> &[EdgeId<K>] {
+        &self.outgoing
+    }
+
+    /// 入边列表
+    #[inline]
+    pub fn incoming(&self) -> &[EdgeId<K>] {
+        &self.incoming
+    }
+
+    /// 出边和入边是否都为空
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.outgoing.is_empty() && self.incoming.is_empty()
+    }
+
+    /// 出度
+    #[inline]
+    pub fn out_degree(&self) -> usize {
+        self.out_degree as usize
+    }
+
+    /// 入度
+    #[inline]
+    pub fn in_degree(&self) -> usize {
+        self.in_degree as usize
+    }
+
+    /// 总度数，自环只计一次
+    #[inline]
+    pub fn degree(&self) -> usize {
+        (self.out_degree + self.in_degree - self.self_loops) as usize
+    }
+
+    /// 清空列表和计数，保留列表的缓冲区
+    fn reset(&mut self) {
+        self.outgoing.clear();
+        self.incoming.clear();
+        self.out_degree = 0;
+        self.in_degree = 0;
+        self.self_loops = 0;
+    }
+}
+
+/// 从列表中删除指定边
+#[inline]
+fn remove_from_list<K: Key>(list: &mut Vec<EdgeId<K>>, id: EdgeId<K>, order: EdgeRemovalOrder) {
+    if let Some(pos) = list.iter().position(|&e| e == id) {
+        match order {
+            EdgeRemovalOrder::Shift => {
+                list.remove(pos);
+            }
+            EdgeRemovalOrder::Swap => {
+                list.swap_remove(pos);
+            }
+        }
+    }
+}
+
+/// 把列表中的指定边移到最前面，其余元素保持顺序；边不在列表中时返回 `false`
+#[inline]
+fn move_to_front_in_list<K: Key>(list: &mut [EdgeId<K>], id: EdgeId<K>) -> bool {
+    match list.iter().position(|&e| e == id) {
+        Some(pos) => {
+            list[..=pos].rotate_right(1);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 按 `order` 重排列表：出现在 `order` 中的边按其顺序排在最前，其余边保持原有顺序排在后面
+#[inline]
+fn reorder_list<K: Key>(list: &mut Vec<EdgeId<K>>, order: &[EdgeId<K>], listed: &HashSet<EdgeId<K>>) {
+    let mut reordered: Vec<EdgeId<K>> = order.iter().copied().filter(|id| list.contains(id)).collect();
+    reordered.extend(list.iter().copied().filter(|id| !listed.contains(id)));
+    *list = reordered;
+}
 
 /// 边存储容器，基于 `pi_slotmap::SlotMap` 实现
+///
+/// 除边数据外还维护每个顶点的邻接表，使出边/入边查询为 O(度数)
+/// 而不是 O(边总数)。邻接表在 `insert`、`remove`、`retain` 和 `clear` 中同步更新。
 #[derive(Debug)]
-pub struct EdgeContainer<E>
+pub struct EdgeContainer<E, K = DefaultKey>
 where
     E: Element,
+    K: Key,
 {
     /// 使用 SlotMap 存储边数据
-    data: SlotMap<DefaultKey, (E, EdgeInfo)>,
+    data: SlotMap<K, (E, EdgeInfo<K>)>,
+    /// 顶点邻接表，没有任何关联边的顶点不占用条目
+    adjacency: HashMap<VertexId<K>, Adjacency<K>>,
+    /// `clear_keep_capacity` 回收的空邻接表，新条目优先复用其中的列表缓冲区
+    spare: Vec<Adjacency<K>>,
+    /// 删除边时邻接表的维护方式
+    removal_order: EdgeRemovalOrder,
 }
 
 impl<E> EdgeContainer<E>
@@ -43,7 +179,9 @@ where
     pub fn new() -> Self {
         Self {
             data: SlotMap::new(),
-            // connections: SlotMap::new(),
+            adjacency: HashMap::new(),
+            spare: Vec::new(),
+            removal_order: EdgeRemovalOrder::Shift,
         }
     }
 
@@ -52,50 +190,322 @@ where
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             data: SlotMap::with_capacity(capacity),
-            // connections: SlotMap::with_capacity(capacity),
+            adjacency: HashMap::new(),
+            spare: Vec::new(),
+            removal_order: EdgeRemovalOrder::Shift,
+        }
+    }
+}
+
+impl<E, K> EdgeContainer<E, K>
+where
+    E: Element,
+    K: Key,
+{
+    /// 创建使用自定义键类型的空边容器
+    #[inline]
+    pub fn with_key() -> Self {
+        Self {
+            data: SlotMap::with_key(),
+            adjacency: HashMap::new(),
+            spare: Vec::new(),
+            removal_order: EdgeRemovalOrder::Shift,
+        }
+    }
+
+    /// 创建使用自定义键类型、带有预设容量的边容器
+    #[inline]
+    pub fn with_capacity_and_key(capacity: usize) -> Self {
+        Self {
+            data: SlotMap::with_capacity_and_key(capacity),
+            adjacency: HashMap::new(),
+            spare: Vec::new(),
+            removal_order: EdgeRemovalOrder::Shift,
         }
     }
 
     /// 插入边数据和连接信息
+    ///
+    /// `edge_info` 中的边 ID 被忽略，保存的连接信息记录实际分配的 ID。
     #[inline]
-    pub fn insert(&mut self, edge: E, edge_info: EdgeInfo) -> EdgeId {
-        let key = self.data.insert((edge, edge_info));
+    pub fn insert(&mut self, edge: E, edge_info: EdgeInfo<K>) -> EdgeId<K> {
+        let key = self
+            .data
+            .insert_with_key(|key| (edge, EdgeInfo::new(EdgeId::new(key), edge_info.from(), edge_info.to())));
+        let id = EdgeId::new(key);
 
-        EdgeId::new(key)
+        let spare = &mut self.spare;
+        let from = self.adjacency.entry(edge_info.from()).or_insert_with(|| spare.pop().unwrap_or_default());
+        from.outgoing.push(id);
+        from.out_degree += 1;
+        if edge_info.from() == edge_info.to() {
+            from.self_loops += 1;
+        }
+        let to = self.adjacency.entry(edge_info.to()).or_insert_with(|| spare.pop().unwrap_or_default());
+        to.incoming.push(id);
+        to.in_degree += 1;
+
+        id
+    }
+
+    /// 在顶点的出边列表末尾加入 `id`，不检查边是否存在，也不更新计数器
+    #[cfg(feature = "test-internals")]
+    pub(crate) fn push_unchecked_outgoing(&mut self, vertex_id: VertexId<K>, id: EdgeId<K>) {
+        self.adjacency.entry(vertex_id).or_default().outgoing.push(id);
+    }
+
+    /// 改写连接信息中记录的边 ID
+    #[cfg(feature = "test-internals")]
+    pub(crate) fn overwrite_info_id(&mut self, id: EdgeId<K>, recorded: EdgeId<K>) -> bool {
+        let Some((_, info)) = self.data.get_mut(id.key()) else {
+            return false;
+        };
+        *info = EdgeInfo::new(recorded, info.from(), info.to());
+        true
     }
 
     /// 获取边数据的不可变引用
     #[inline]
-    pub fn get(&self, id: EdgeId) -> Option<&(E, EdgeInfo)> {
-        self.data.get(id.key())
+    pub fn get(&self, id: EdgeId<K>) -> Option<&E> {
+        self.data.get(id.key()).map(|(weight, _)| weight)
     }
 
     /// 获取边数据的可变引用
     #[inline]
-    pub fn get_mut(&mut self, id: EdgeId) -> Option<&mut (E, EdgeInfo)> {
-        self.data.get_mut(id.key())
+    pub fn get_mut(&mut self, id: EdgeId<K>) -> Option<&mut E> {
+        self.data.get_mut(id.key()).map(|(weight, _)| weight)
     }
 
     /// 获取连接信息的不可变引用
     #[inline]
-    pub fn get_connection(&self, id: EdgeId) -> Option<&EdgeInfo> {
-        self.data.get(id.key()).map(|v|&v.1)
+    pub fn get_connection(&self, id: EdgeId<K>) -> Option<&EdgeInfo<K>> {
+        self.data.get(id.key()).map(|(_, info)| info)
+    }
+
+    /// 一次查找同时获取边数据和连接信息
+    #[inline]
+    pub fn get_with_info(&self, id: EdgeId<K>) -> Option<(&E, &EdgeInfo<K>)> {
+        self.data.get(id.key()).map(|(weight, info)| (weight, info))
+    }
+
+    /// 一次查找同时获取边数据的可变引用和连接信息
+    ///
+    /// 连接信息只读：端点由邻接表维护，不能经由该引用修改。
+    #[inline]
+    pub fn get_mut_with_info(&mut self, id: EdgeId<K>) -> Option<(&mut E, &EdgeInfo<K>)> {
+        self.data.get_mut(id.key()).map(|(weight, info)| (weight, &*info))
+    }
+
+    /// 获取边的 `(起点, 终点)`，只做一次查找
+    #[inline]
+    pub fn endpoints(&self, id: EdgeId<K>) -> Option<(VertexId<K>, VertexId<K>)> {
+        self.get_connection(id).map(|info| (info.from(), info.to()))
     }
 
     /// 删除边
     #[inline]
-    pub fn remove(&mut self, id: EdgeId) -> Option<(E, EdgeInfo)> {
-        let edge = self.data.remove(id.key());
+    pub fn remove(&mut self, id: EdgeId<K>) -> Option<(E, EdgeInfo<K>)> {
+        let (edge, info) = self.data.remove(id.key())?;
+        self.unlink(id, &info);
+        Some((edge, info))
+    }
 
-        match edge {
-            Some((e, i)) => Some((e, i)),
-            _ => None,
+    /// 从两个端点的邻接表中摘除边，端点不再有关联边时释放其条目
+    fn unlink(&mut self, id: EdgeId<K>, info: &EdgeInfo<K>) {
+        if let Some(adj) = self.adjacency.get_mut(&info.from()) {
+            remove_from_list(&mut adj.outgoing, id, self.removal_order);
+            adj.out_degree -= 1;
+            if info.from() == info.to() {
+                adj.self_loops -= 1;
+            }
+            if adj.is_empty() {
+                self.adjacency.remove(&info.from());
+            }
         }
+        if let Some(adj) = self.adjacency.get_mut(&info.to()) {
+            remove_from_list(&mut adj.incoming, id, self.removal_order);
+            adj.in_degree -= 1;
+            if adj.is_empty() {
+                self.adjacency.remove(&info.to());
+            }
+        }
+    }
+
+    /// 删除边时邻接表的维护方式
+    #[inline]
+    pub fn removal_order(&self) -> EdgeRemovalOrder {
+        self.removal_order
+    }
+
+    /// 设置删除边时邻接表的维护方式，只影响之后的删除
+    #[inline]
+    pub fn set_removal_order(&mut self, order: EdgeRemovalOrder) {
+        self.removal_order = order;
+    }
+
+    /// 把边移到顶点出边和/或入边列表的最前面
+    ///
+    /// 边是 `vertex_id` 的出边时调整出边列表，是入边时调整入边列表，自环两者都调整。
+    /// 边与该顶点无关时返回 `false`，不做任何修改。
+    pub fn move_to_front(&mut self, vertex_id: VertexId<K>, id: EdgeId<K>) -> bool {
+        let Some(adj) = self.adjacency.get_mut(&vertex_id) else {
+            return false;
+        };
+        let moved_out = move_to_front_in_list(&mut adj.outgoing, id);
+        let moved_in = move_to_front_in_list(&mut adj.incoming, id);
+        moved_out || moved_in
+    }
+
+    /// 按给定顺序重排顶点的邻接表
+    ///
+    /// `order` 中的出边按给定顺序排在出边列表最前，入边同理；
+    /// 未列出的边保持原有相对顺序排在其后。
+    /// `order` 含有与该顶点无关的边或重复的边时返回 `false`，不做任何修改。
+    pub fn reorder(&mut self, vertex_id: VertexId<K>, order: &[EdgeId<K>]) -> bool {
+        let Some(adj) = self.adjacency.get_mut(&vertex_id) else {
+            return order.is_empty();
+        };
+        let mut listed = HashSet::with_capacity(order.len());
+        for &id in order {
+            if !listed.insert(id) || !(adj.outgoing.contains(&id) || adj.incoming.contains(&id)) {
+                return false;
+            }
+        }
+        reorder_list(&mut adj.outgoing, order, &listed);
+        reorder_list(&mut adj.incoming, order, &listed);
+        true
+    }
+
+    /// 获取顶点的邻接表
+    ///
+    /// 顶点没有任何关联边时返回 `None`。
+    #[inline]
+    pub fn adjacency(&self, vertex_id: VertexId<K>) -> Option<&Adjacency<K>> {
+        self.adjacency.get(&vertex_id)
+    }
+
+    /// 顶点的出度，O(1)
+    #[inline]
+    pub fn out_degree(&self, vertex_id: VertexId<K>) -> usize {
+        self.adjacency.get(&vertex_id).map_or(0, Adjacency::out_degree)
+    }
+
+    /// 顶点的入度，O(1)
+    #[inline]
+    pub fn in_degree(&self, vertex_id: VertexId<K>) -> usize {
+        self.adjacency.get(&vertex_id).map_or(0, Adjacency::in_degree)
+    }
+
+    /// 顶点的总度数，自环只计一次，O(1)
+    #[inline]
+    pub fn degree(&self, vertex_id: VertexId<K>) -> usize {
+        self.adjacency.get(&vertex_id).map_or(0, Adjacency::degree)
+    }
+
+    /// 检查邻接表和度数计数器与边数据是否一致
+    ///
+    /// 每条边必须恰好出现在起点的出边列表和终点的入边列表中各一次，
+    /// 列表中不能有已删除或方向不符的边，计数器必须与列表一致，
+    /// 连接信息中记录的边 ID 必须与其槽位一致。
+    /// 复杂度为 O(E)，用于测试和调试。
+    pub fn validate(&self) -> Result<(), GraphError<K>> {
+        for (&vertex, adj) in &self.adjacency {
+            let mismatch = || GraphError::AdjacencyMismatch { vertex };
+            let mut seen = HashSet::with_capacity(adj.outgoing.len());
+            for &id in &adj.outgoing {
+                if !seen.insert(id) || self.get_connection(id).is_none_or(|info| info.from() != vertex) {
+                    return Err(mismatch());
+                }
+            }
+            seen.clear();
+            for &id in &adj.incoming {
+                if !seen.insert(id) || self.get_connection(id).is_none_or(|info| info.to() != vertex) {
+                    return Err(mismatch());
+                }
+            }
+
+            let self_loops = adj
+                .outgoing
+                .iter()
+                .filter(|&&id| self.get_connection(id).is_some_and(|info| info.to() == vertex))
+                .count();
+            if adj.out_degree() != adj.outgoing.len()
+                || adj.in_degree() != adj.incoming.len()
+                || adj.self_loops as usize != self_loops
+            {
+                return Err(GraphError::DegreeMismatch { vertex });
+            }
+        }
+
+        // 列表中的边都已确认存在且不重复，再确认每条边都没有遗漏
+        for (key, (_, info)) in self.data.iter() {
+            let id = EdgeId::new(key);
+            if info.edge_id() != id {
+                return Err(GraphError::EdgeInfoMismatch { edge: id });
+            }
+            let in_from = self.adjacency.get(&info.from()).is_some_and(|adj| adj.outgoing.contains(&id));
+            if !in_from {
+                return Err(GraphError::AdjacencyMismatch { vertex: info.from() });
+            }
+            let in_to = self.adjacency.get(&info.to()).is_some_and(|adj| adj.incoming.contains(&id));
+            if !in_to {
+                return Err(GraphError::AdjacencyMismatch { vertex: info.to() });
+            }
+        }
+        Ok(())
+    }
+
+    /// 删除端点不满足 `vertex_exists` 的边，再按边数据修复连接信息和邻接表，返回被删除的边
+    ///
+    /// 连接信息中的边 ID 按槽位改正。邻接表保留原有顺序：先去掉不存在、方向不符和重复的项，
+    /// 再按槽位顺序补上遗漏的边，度数计数器按列表重新计算。O(V + E)。
+    pub(crate) fn repair(&mut self, vertex_exists: impl Fn(VertexId<K>) -> bool) -> Vec<(EdgeId<K>, E, EdgeInfo<K>)> {
+        let dangling: Vec<K> = self
+            .data
+            .iter()
+            .filter(|(_, (_, info))| !vertex_exists(info.from()) || !vertex_exists(info.to()))
+            .map(|(key, _)| key)
+            .collect();
+        let removed = dangling
+            .into_iter()
+            .filter_map(|key| self.data.remove(key).map(|(edge, info)| (EdgeId::new(key), edge, info)))
+            .collect();
+        for (key, (_, info)) in self.data.iter_mut() {
+            *info = EdgeInfo::new(EdgeId::new(key), info.from(), info.to());
+        }
+
+        let data = &self.data;
+        let (mut listed_out, mut listed_in) = (HashSet::new(), HashSet::new());
+        for (&vertex, adj) in self.adjacency.iter_mut() {
+            adj.outgoing.retain(|&id| {
+                data.get(id.key()).is_some_and(|(_, info)| info.from() == vertex) && listed_out.insert(id)
+            });
+            adj.incoming.retain(|&id| {
+                data.get(id.key()).is_some_and(|(_, info)| info.to() == vertex) && listed_in.insert(id)
+            });
+        }
+        for (key, (_, info)) in data.iter() {
+            let id = EdgeId::new(key);
+            if !listed_out.contains(&id) {
+                self.adjacency.entry(info.from()).or_default().outgoing.push(id);
+            }
+            if !listed_in.contains(&id) {
+                self.adjacency.entry(info.to()).or_default().incoming.push(id);
+            }
+        }
+        self.adjacency.retain(|_, adj| !adj.is_empty());
+        for (&vertex, adj) in self.adjacency.iter_mut() {
+            adj.out_degree = adj.outgoing.len() as u32;
+            adj.in_degree = adj.incoming.len() as u32;
+            adj.self_loops =
+                adj.outgoing.iter().filter(|id| data.get(id.key()).is_some_and(|(_, info)| info.to() == vertex)).count() as u32;
+        }
+        removed
     }
 
     /// 检查是否包含指定边
     #[inline]
-    pub fn contains(&self, id: EdgeId) -> bool {
+    pub fn contains(&self, id: EdgeId<K>) -> bool {
         self.data.contains_key(id.key())
     }
 
@@ -105,6 +515,32 @@ where
         self.data.len()
     }
 
+    /// 不重新分配时可用的槽位下标上界（不含）
+    ///
+    /// SlotMap 的 0 号槽位是哨兵，因此比容量多 1。
+    #[inline]
+    pub fn slot_capacity(&self) -> usize {
+        self.data.capacity() + 1
+    }
+
+    /// 不重新分配时可容纳的边数量
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// 为至少 `additional` 个顶点的邻接表条目预留容量
+    #[inline]
+    pub fn reserve_vertices(&mut self, additional: usize) {
+        self.adjacency.reserve(additional);
+    }
+
+    /// 不重新分配时邻接表可容纳的顶点数量
+    #[inline]
+    pub fn adjacency_capacity(&self) -> usize {
+        self.adjacency.capacity()
+    }
+
     /// 检查容器是否为空
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -115,42 +551,63 @@ where
     #[inline]
     pub fn clear(&mut self) {
         self.data.clear();
-        // self.connections.clear();
+        self.adjacency.clear();
+    }
+
+    /// 清空所有边，保留槽位存储、邻接表和各顶点出边/入边列表的缓冲区
+    ///
+    /// 被清空的邻接表条目进入备用池，之后顶点第一次关联边时优先复用，
+    /// 列表在不超过原有长度时不会重新分配。
+    pub fn clear_keep_capacity(&mut self) {
+        self.data.clear();
+        self.spare.extend(self.adjacency.drain().map(|(_, mut adj)| {
+            adj.reset();
+            adj
+        }));
+    }
+
+    /// 估算邻接表占用的堆内存，单位为字节，包括备用池中的列表缓冲区
+    pub fn adjacency_memory_estimate(&self) -> usize {
+        let lists = |adj: &Adjacency<K>| (adj.outgoing.capacity() + adj.incoming.capacity()) * size_of::<EdgeId<K>>();
+        hash_map_bytes(&self.adjacency)
+            + self.adjacency.values().map(lists).sum::<usize>()
+            + self.spare.capacity() * size_of::<Adjacency<K>>()
+            + self.spare.iter().map(lists).sum::<usize>()
+    }
+
+    /// 释放邻接表中多余的容量，丢弃备用池
+    ///
+    /// 槽位存储不会缩小，已删除的边ID仍然查不到。
+    pub fn shrink_to_fit(&mut self) {
+        for adj in self.adjacency.values_mut() {
+            adj.outgoing.shrink_to_fit();
+            adj.incoming.shrink_to_fit();
+        }
+        self.adjacency.shrink_to_fit();
+        self.spare = Vec::new();
     }
 
     /// 获取所有边ID
     #[inline]
-    pub fn keys(&self) -> impl Iterator<Item = EdgeId> + '_ {
+    pub fn keys(&self) -> impl Iterator<Item = EdgeId<K>> + '_ {
         self.data.keys().map(EdgeId::new)
     }
 
-    // /// 获取所有边数据的不可变引用
-    // #[inline]
-    // pub fn values(&self) -> impl Iterator<Item = &E> + '_ {
-    //     self.data.values()
-    // }
-
-    // /// 获取所有边数据的可变引用
-    // #[inline]
-    // pub fn values_mut(&mut self) -> impl Iterator<Item = &mut E> + '_ {
-    //     self.data.values_mut()
-    // }
-
     /// 迭代所有边的键值对
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = (EdgeId, &E)> + '_ {
+    pub fn iter(&self) -> impl Iterator<Item = (EdgeId<K>, &E)> + '_ {
         self.data.iter().map(|(key, (value, _))| (EdgeId::new(key), value))
     }
 
     /// 迭代所有边的键值对（可变）
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EdgeId, &mut E)> + '_ {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EdgeId<K>, &mut E)> + '_ {
         self.data.iter_mut().map(|(key, (value, _))| (EdgeId::new(key), value))
     }
 
     /// 迭代所有边和连接信息
     #[inline]
-    pub fn iter_with_connections(&self) -> impl Iterator<Item = (EdgeId, &E, &EdgeInfo)> + '_ {
+    pub fn iter_with_connections(&self) -> impl Iterator<Item = (EdgeId<K>, &E, &EdgeInfo<K>)> + '_ {
         self.data.iter()
             .map(|(key, (edge, info))| (EdgeId::new(key), edge, info))
     }
@@ -173,7 +630,7 @@ where
     #[inline]
     pub fn retain<F>(&mut self, mut predicate: F)
     where
-        F: FnMut(EdgeId, &E, &EdgeInfo) -> bool,
+        F: FnMut(EdgeId<K>, &E, &EdgeInfo<K>) -> bool,
     {
         // 第一阶段：收集需要删除的键
         // 使用单独的Vec来存储键，避免在迭代过程中修改SlotMap
@@ -192,15 +649,82 @@ where
         // 第二阶段：批量删除收集到的键
         // 现在可以安全地删除元素，因为迭代已经完成
         for key in keys_to_remove {
-            self.data.remove(key);
+            self.remove(EdgeId::new(key));
+        }
+    }
+
+    /// 删除 `victims` 的全部关联边，返回被删除的边，按收集顺序排列
+    ///
+    /// `is_victim` 必须与 `victims` 一致，`victims` 中不能有重复。
+    /// 先收集关联边：关联边总数（各顶点度数之和）不超过边总数的一半时遍历这些顶点的邻接表，
+    /// 否则扫描一遍全部边。之后删除边数据，存活端点的邻接表各只重写一次（保持其余边的顺序），
+    /// `victims` 的邻接表条目整体释放，不逐条摘除。
+    ///
+    /// 复杂度为 O(关联边数 + 受影响的存活端点的度数之和)，扫描全部边时另加 O(E)。
+    pub fn remove_incident(
+        &mut self,
+        victims: &[VertexId<K>],
+        is_victim: impl Fn(VertexId<K>) -> bool,
+    ) -> Vec<(EdgeId<K>, E, EdgeInfo<K>)> {
+        let incident: usize = victims.iter().map(|&vertex_id| self.degree(vertex_id)).sum();
+        let ids: Vec<EdgeId<K>> = if incident * 2 > self.data.len() {
+            self.data
+                .iter()
+                .filter(|(_, (_, info))| is_victim(info.from()) || is_victim(info.to()))
+                .map(|(key, _)| EdgeId::new(key))
+                .collect()
+        } else {
+            let mut ids = Vec::with_capacity(incident);
+            for adj in victims.iter().filter_map(|vertex_id| self.adjacency.get(vertex_id)) {
+                ids.extend_from_slice(&adj.outgoing);
+                // 起点也是被删除顶点的边（含自环）已在其出边中收集
+                ids.extend(adj.incoming.iter().copied().filter(|&id| {
+                    self.data.get(id.key()).is_some_and(|(_, info)| !is_victim(info.from()))
+                }));
+            }
+            ids
+        };
+
+        let mut survivors: Vec<VertexId<K>> = Vec::new();
+        let mut removed = Vec::with_capacity(ids.len());
+        for id in ids {
+            let Some((edge, info)) = self.data.remove(id.key()) else {
+                continue;
+            };
+            for endpoint in [info.from(), info.to()] {
+                if !is_victim(endpoint) {
+                    survivors.push(endpoint);
+                }
+            }
+            removed.push((id, edge, info));
+        }
+        survivors.sort_unstable_by_key(|vertex_id| vertex_id.as_ffi());
+        survivors.dedup();
+
+        let data = &self.data;
+        for vertex_id in survivors {
+            if let Some(adj) = self.adjacency.get_mut(&vertex_id) {
+                adj.outgoing.retain(|id| data.contains_key(id.key()));
+                adj.incoming.retain(|id| data.contains_key(id.key()));
+                // 存活顶点的自环不会被删除，自环数不变
+                adj.out_degree = adj.outgoing.len() as u32;
+                adj.in_degree = adj.incoming.len() as u32;
+                if adj.is_empty() {
+                    self.adjacency.remove(&vertex_id);
+                }
+            }
+        }
+        for vertex_id in victims {
+            self.adjacency.remove(vertex_id);
         }
+        removed
     }
 
     /// 批量插入边
     #[inline]
-    pub fn insert_iter<I>(&mut self, iter: I) -> Vec<EdgeId>
+    pub fn insert_iter<I>(&mut self, iter: I) -> Vec<EdgeId<K>>
     where
-        I: IntoIterator<Item = (E, EdgeInfo)>,
+        I: IntoIterator<Item = (E, EdgeInfo<K>)>,
     {
         iter.into_iter()
             .map(|(edge, info)| self.insert(edge, info))
@@ -209,7 +733,7 @@ where
 
     /// 获取从指定顶点出发的所有边ID
     ///
-    /// 实现了高效的邻接查询功能，通过闭包捕获顶点ID来过滤边集合。
+    /// 基于顶点的出边列表实现，按边的插入顺序产生结果。
     /// 返回一个懒迭代器，按需生成结果，避免预计算所有邻接边。
     ///
     /// # 参数
@@ -222,22 +746,21 @@ where
     ///
     /// # 实现细节
     ///
-    /// 1. **迭代器链**: 使用函数式编程风格构建处理链
-    /// 2. **过滤操作**: 通过闭包比较边的源顶点
-    /// 3. **映射转换**: 将SlotMap的键转换为EdgeId
-    /// 4. **懒求值**: 只有在迭代时才进行实际计算
+    /// 1. **邻接表查找**: 一次哈希查找定位顶点的邻接表
+    /// 2. **顺序遍历**: 直接遍历出边列表，无需扫描全部边
+    /// 3. **懒求值**: 只有在迭代时才进行实际计算
     ///
     /// # 性能特征
     ///
-    /// - **时间复杂度**: O(E) - E为边的总数，但只计算满足条件的边
+    /// - **时间复杂度**: O(k) - k为顶点的出度
     /// - **空间复杂度**: O(1) - 迭代器状态为常数大小
-    /// - **缓存友好**: 顺序访问内存模式
+    /// - **缓存友好**: 连续内存访问模式
     #[inline]
-    pub fn edges_from(&self, vertex_id: VertexId) -> impl Iterator<Item = EdgeId> + '_ {
-        self.data
-            .iter()  // 迭代所有边
-            .filter(move |(_, (_, info))| info.from() == vertex_id)  // 过滤出从指定顶点出发的边
-            .map(|(key, _)| EdgeId::new(key))  // 将SlotMap键转换为EdgeId
+    pub fn edges_from(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.adjacency
+            .get(&vertex_id)  // 查找顶点的邻接表
+            .into_iter()
+            .flat_map(|adj| adj.outgoing.iter().copied())  // 按插入顺序产生出边
     }
 
     /// 获取到达指定顶点的所有边ID
@@ -256,13 +779,12 @@ where
     /// # 实现细节
     ///
     /// 1. **懒迭代**: 使用迭代器适配器避免中间集合
-    /// 2. **闭包捕获**: 通过 `move` 关键字捕获 vertex_id
-    /// 3. **高效过滤**: 直接比较边的目标顶点
-    /// 4. **类型转换**: 统一返回 EdgeId 类型
+    /// 2. **邻接表查找**: 直接遍历顶点的入边列表
+    /// 3. **插入顺序**: 结果按边的插入顺序产生
     ///
     /// # 性能特征
     ///
-    /// - **时间复杂度**: O(E) - E为边的总数，但只计算满足条件的边
+    /// - **时间复杂度**: O(k) - k为顶点的入度
     /// - **空间复杂度**: O(1) - 迭代器状态为常数大小
     /// - **内存效率**: 无额外内存分配
     ///
@@ -273,58 +795,73 @@ where
     /// - 查找引用关系
     /// - 图算法中的预处理步骤
     #[inline]
-    pub fn edges_to(&self, vertex_id: VertexId) -> impl Iterator<Item = EdgeId> + '_ {
-        self.data
-            .iter()  // 迭代所有边
-            .filter(move |(_, (_, info))| info.to() == vertex_id)  // 过滤出指向指定顶点的边
-            .map(|(key, _)| EdgeId::new(key))  // 将SlotMap键转换为EdgeId
+    pub fn edges_to(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.adjacency
+            .get(&vertex_id)  // 查找顶点的邻接表
+            .into_iter()
+            .flat_map(|adj| adj.incoming.iter().copied())  // 按插入顺序产生入边
     }
 
     /// 获取涉及指定顶点的所有边ID
+    ///
+    /// 先产生出边，再产生入边；自环边只产生一次。
     #[inline]
-    pub fn edges_involving(&self, vertex_id: VertexId) -> impl Iterator<Item = EdgeId> + '_ {
-        self.data
-            .iter()
-            .filter(move |(_, (_, info))| info.involves(vertex_id))
-            .map(|(key, _)| EdgeId::new(key))
+    pub fn edges_involving(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.edges_from(vertex_id).chain(
+            self.edges_to(vertex_id)
+                .filter(move |&id| self.get_connection(id).is_some_and(|info| info.from() != vertex_id)),
+        )
     }
 
     /// 检查两个顶点之间是否有边
     #[inline]
-    pub fn has_edge_between(&self, from: VertexId, to: VertexId) -> bool {
-        self.data
-            .iter()
-            .any(|(_, (_, info))| info.connects(from, to))
+    pub fn has_edge_between(&self, from: VertexId<K>, to: VertexId<K>) -> bool {
+        self.edges_between(from, to).next().is_some()
     }
 
     /// 获取两个顶点之间的边
     #[inline]
-    pub fn get_edge_between(&self, from: VertexId, to: VertexId) -> Option<EdgeId> {
-        self.data
-            .iter()
-            .find(|(_, (_, info))| info.connects(from, to))
-            .map(|(key, _)| EdgeId::new(key))
+    pub fn get_edge_between(&self, from: VertexId<K>, to: VertexId<K>) -> Option<EdgeId<K>> {
+        self.edges_between(from, to).next()
     }
 
     /// 获取与指定顶点相邻的所有边
+    ///
+    /// 同 [`edges_involving`](Self::edges_involving)：每条关联边恰好产生一次，自环不会因同时位于出边和入边列表而重复。
     #[inline]
-    pub fn edges_adjacent(&self, vertex_id: VertexId) -> impl Iterator<Item = EdgeId> + '_ {
+    pub fn edges_adjacent(&self, vertex_id: VertexId<K>) -> impl Iterator<Item = EdgeId<K>> + '_ {
         self.edges_involving(vertex_id)
     }
 
     /// 获取两个顶点之间的所有边
     #[inline]
-    pub fn edges_between(&self, from: VertexId, to: VertexId) -> impl Iterator<Item = EdgeId> + '_ {
-        self.data
-            .iter()
-            .filter(move |(_, (_, info))| info.connects(from, to))
-            .map(|(key, _)| EdgeId::new(key))
+    pub fn edges_between(&self, from: VertexId<K>, to: VertexId<K>) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.edges_from(from)
+            .filter(move |&id| self.get_connection(id).is_some_and(|info| info.to() == to))
+    }
+
+    /// 检查两个顶点之间是否有任一方向的边
+    #[inline]
+    pub fn has_edge_between_undirected(&self, a: VertexId<K>, b: VertexId<K>) -> bool {
+        self.edges_between_undirected(a, b).next().is_some()
+    }
+
+    /// 获取两个顶点之间任一方向的所有边
+    ///
+    /// 先产生 `a -> b` 的边，再产生 `b -> a` 的边；`a == b` 时每条自环只产生一次。
+    #[inline]
+    pub fn edges_between_undirected(&self, a: VertexId<K>, b: VertexId<K>) -> impl Iterator<Item = EdgeId<K>> + '_ {
+        self.edges_involving(a).filter(move |&id| {
+            self.get_connection(id)
+                .is_some_and(|info| info.connects(a, b) || info.connects(b, a))
+        })
     }
 }
 
-impl<E> Storage<E> for EdgeContainer<E>
+impl<E, K> Storage<E> for EdgeContainer<E, K>
 where
     E: Element,
+    K: Key,
 {
     fn len(&self) -> usize {
         self.len()
@@ -351,11 +888,226 @@ where
     }
 }
 
-impl<E> Default for EdgeContainer<E>
+impl<E, K> Default for EdgeContainer<E, K>
 where
     E: Element,
+    K: Key,
 {
     fn default() -> Self {
-        Self::new()
+        Self::with_key()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex_ids(count: usize) -> Vec<VertexId> {
+        let mut vertices = super::super::VertexContainer::new();
+        (0..count).map(|i| vertices.insert(i)).collect()
+    }
+
+    #[test]
+    fn test_adjacency_maintained_on_insert_and_remove() {
+        let v = vertex_ids(3);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+
+        let e1 = edges.insert(1, EdgeInfo::new(EdgeId::default(), v[0], v[1]));
+        let e2 = edges.insert(2, EdgeInfo::new(EdgeId::default(), v[0], v[2]));
+        let e3 = edges.insert(3, EdgeInfo::new(EdgeId::default(), v[2], v[0]));
+
+        assert_eq!(edges.edges_from(v[0]).collect::<Vec<_>>(), vec![e1, e2]);
+        assert_eq!(edges.edges_to(v[0]).collect::<Vec<_>>(), vec![e3]);
+        assert_eq!(edges.edges_involving(v[0]).collect::<Vec<_>>(), vec![e1, e2, e3]);
+        assert!(edges.has_edge_between(v[0], v[2]));
+        assert_eq!(edges.get_edge_between(v[2], v[0]), Some(e3));
+        assert_eq!(edges.endpoints(e3), Some((v[2], v[0])));
+
+        edges.remove(e2);
+        assert_eq!(edges.endpoints(e2), None);
+        assert_eq!(edges.edges_from(v[0]).collect::<Vec<_>>(), vec![e1]);
+        assert!(!edges.has_edge_between(v[0], v[2]));
+        assert_eq!(edges.adjacency(v[2]).unwrap().outgoing(), &[e3]);
+        assert!(edges.adjacency(v[2]).unwrap().incoming().is_empty());
+
+        // 没有关联边的顶点不保留邻接表条目
+        edges.remove(e1);
+        assert!(edges.adjacency(v[1]).is_none());
+    }
+
+    #[test]
+    fn test_self_loop_adjacency() {
+        let v = vertex_ids(1);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+
+        let e = edges.insert(1, EdgeInfo::new(EdgeId::default(), v[0], v[0]));
+        assert_eq!(edges.edges_from(v[0]).count(), 1);
+        assert_eq!(edges.edges_to(v[0]).count(), 1);
+        assert_eq!(edges.edges_involving(v[0]).collect::<Vec<_>>(), vec![e]);
+
+        edges.remove(e);
+        assert!(edges.adjacency(v[0]).is_none());
+    }
+
+    #[test]
+    fn test_accessors() {
+        let v = vertex_ids(2);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+        let e = edges.insert(1, EdgeInfo::new(EdgeId::default(), v[0], v[1]));
+
+        assert_eq!(edges.get(e), Some(&1));
+        *edges.get_mut(e).unwrap() = 2;
+        let (weight, info) = edges.get_with_info(e).unwrap();
+        assert_eq!((*weight, info.edge_id(), info.from(), info.to()), (2, e, v[0], v[1]));
+        let (weight, info) = edges.get_mut_with_info(e).unwrap();
+        *weight = 3;
+        assert_eq!((info.from(), info.to()), (v[0], v[1]));
+        assert_eq!(edges.get(e), Some(&3));
+        assert_eq!(edges.get_connection(e).map(|info| (info.from(), info.to())), edges.endpoints(e));
+
+        edges.remove(e);
+        assert_eq!(edges.get(e), None);
+        assert_eq!(edges.get_mut(e), None);
+        assert!(edges.get_with_info(e).is_none());
+        assert!(edges.get_mut_with_info(e).is_none());
+        assert!(edges.get_connection(e).is_none());
+    }
+
+    #[test]
+    fn test_retain_and_clear_update_adjacency() {
+        let v = vertex_ids(2);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+
+        for i in 0..4 {
+            edges.insert(i, EdgeInfo::new(EdgeId::default(), v[0], v[1]));
+        }
+        edges.retain(|_, weight, _| weight % 2 == 0);
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges.edges_from(v[0]).count(), 2);
+        assert_eq!(edges.edges_to(v[1]).count(), 2);
+
+        edges.clear();
+        assert_eq!(edges.edges_from(v[0]).count(), 0);
+        assert!(edges.adjacency(v[1]).is_none());
+    }
+
+    #[test]
+    fn test_clear_keep_capacity_reuses_lists() {
+        let v = vertex_ids(4);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+        for i in 0..8 {
+            edges.insert(i, EdgeInfo::new(EdgeId::default(), v[0], v[1 + i as usize % 3]));
+        }
+        let capacities = (edges.capacity(), edges.adjacency_capacity());
+        let list_capacity = |lists: &mut dyn Iterator<Item = &Adjacency>| {
+            lists.map(|adj| adj.outgoing.capacity() + adj.incoming.capacity()).sum::<usize>()
+        };
+        let lists = list_capacity(&mut edges.adjacency.values());
+
+        edges.clear_keep_capacity();
+        assert!(edges.is_empty());
+        assert!(edges.adjacency(v[0]).is_none());
+        assert_eq!((edges.capacity(), edges.adjacency_capacity()), capacities);
+        assert_eq!(edges.spare.len(), 4);
+        assert_eq!(list_capacity(&mut edges.spare.iter()), lists);
+
+        // 新条目复用回收的列表，计数从零开始
+        edges.insert(0, EdgeInfo::new(EdgeId::default(), v[2], v[2]));
+        let adj = edges.adjacency(v[2]).unwrap();
+        assert_eq!((adj.out_degree(), adj.in_degree(), adj.degree()), (1, 1, 1));
+        assert_eq!(edges.spare.len(), 3);
+        assert_eq!(edges.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_removal_order() {
+        let v = vertex_ids(2);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+        let ids: Vec<_> = (0..4).map(|i| edges.insert(i, EdgeInfo::new(EdgeId::default(), v[0], v[1]))).collect();
+
+        assert_eq!(edges.removal_order(), EdgeRemovalOrder::Shift);
+        edges.remove(ids[0]);
+        assert_eq!(edges.edges_from(v[0]).collect::<Vec<_>>(), vec![ids[1], ids[2], ids[3]]);
+
+        edges.set_removal_order(EdgeRemovalOrder::Swap);
+        edges.remove(ids[1]);
+        assert_eq!(edges.edges_from(v[0]).collect::<Vec<_>>(), vec![ids[3], ids[2]]);
+        assert_eq!(edges.edges_to(v[1]).collect::<Vec<_>>(), vec![ids[3], ids[2]]);
+    }
+
+    #[test]
+    fn test_move_to_front_and_reorder() {
+        let v = vertex_ids(3);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+        let e1 = edges.insert(1, EdgeInfo::new(EdgeId::default(), v[0], v[1]));
+        let e2 = edges.insert(2, EdgeInfo::new(EdgeId::default(), v[0], v[2]));
+        let e3 = edges.insert(3, EdgeInfo::new(EdgeId::default(), v[0], v[0]));
+        let e4 = edges.insert(4, EdgeInfo::new(EdgeId::default(), v[2], v[0]));
+
+        assert!(edges.move_to_front(v[0], e3));
+        assert_eq!(edges.edges_from(v[0]).collect::<Vec<_>>(), vec![e3, e1, e2]);
+        assert_eq!(edges.edges_to(v[0]).collect::<Vec<_>>(), vec![e3, e4]);
+        assert!(!edges.move_to_front(v[1], e2));
+
+        assert!(edges.reorder(v[0], &[e4, e2]));
+        assert_eq!(edges.edges_from(v[0]).collect::<Vec<_>>(), vec![e2, e3, e1]);
+        assert_eq!(edges.edges_to(v[0]).collect::<Vec<_>>(), vec![e4, e3]);
+
+        // 无关或重复的边使重排整体失败
+        assert!(!edges.reorder(v[0], &[e1, e1]));
+        assert!(!edges.reorder(v[1], &[e1, e2]));
+        assert_eq!(edges.edges_from(v[0]).collect::<Vec<_>>(), vec![e2, e3, e1]);
+    }
+
+    #[test]
+    fn test_degree_counters() {
+        let v = vertex_ids(2);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+        let loop_edge = edges.insert(0, EdgeInfo::new(EdgeId::default(), v[0], v[0]));
+        let parallel: Vec<_> = (1..3).map(|i| edges.insert(i, EdgeInfo::new(EdgeId::default(), v[0], v[1]))).collect();
+
+        assert_eq!((edges.out_degree(v[0]), edges.in_degree(v[0]), edges.degree(v[0])), (3, 1, 3));
+        assert_eq!((edges.out_degree(v[1]), edges.in_degree(v[1]), edges.degree(v[1])), (0, 2, 2));
+        assert_eq!(edges.validate(), Ok(()));
+
+        edges.remove(loop_edge);
+        edges.remove(parallel[0]);
+        assert_eq!((edges.out_degree(v[0]), edges.in_degree(v[0]), edges.degree(v[0])), (1, 0, 1));
+        assert_eq!(edges.validate(), Ok(()));
+
+        // 人为破坏计数器
+        edges.adjacency.get_mut(&v[1]).unwrap().in_degree += 1;
+        assert_eq!(edges.validate(), Err(GraphError::DegreeMismatch { vertex: v[1] }));
+    }
+
+    #[test]
+    fn test_data_and_connection_never_diverge() {
+        // 固定种子的 xorshift，保证测试可复现
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = move |n: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize % n
+        };
+
+        let v = vertex_ids(8);
+        let mut edges: EdgeContainer<u32> = EdgeContainer::new();
+        // 保留所有发出过的 ID，包括已删除、槽位已被复用的旧 ID
+        let mut issued = Vec::new();
+        for i in 0..5_000 {
+            if next(3) == 0 && !issued.is_empty() {
+                edges.remove(issued[next(issued.len())]);
+            } else {
+                issued.push(edges.insert(i, EdgeInfo::new(EdgeId::default(), v[next(8)], v[next(8)])));
+            }
+            let id = issued[next(issued.len())];
+            assert_eq!(edges.get(id).is_some(), edges.get_connection(id).is_some());
+            assert_eq!(edges.get_with_info(id).map(|(_, info)| info), edges.get_connection(id));
+        }
+        for &id in &issued {
+            assert_eq!(edges.get(id).is_some(), edges.get_connection(id).is_some());
+        }
+        assert_eq!(edges.validate(), Ok(()));
     }
 }
@@ -1,4 +1,4 @@
-/**
+/*!
  * # 存储层 (Storage Layer)
  *
  * 本模块提供高性能的存储容器实现，基于 `pi_slotmap::SlotMap`。
@@ -32,8 +32,8 @@
  * ## 使用示例
  *
  * ```rust
- * use slotmap_graph::storage::{VertexContainer, EdgeContainer};
- * use slotmap_graph::id::{VertexId, EdgeId};
+ * use pi_slotmap_graph::storage::{VertexContainer, EdgeContainer};
+ * use pi_slotmap_graph::id::EdgeInfo;
  *
  * // 创建存储容器
  * let mut vertices = VertexContainer::new();
@@ -43,8 +43,9 @@
  * let alice = vertices.insert("Alice");
  * let bob = vertices.insert("Bob");
  *
- * // 添加边
- * let friendship = edges.insert("friends", alice, bob);
+ * // 添加边（与 `SlotMapGraph::add_edge` 一致，连接信息中的边ID以默认值占位）
+ * let friendship = edges.insert(1u32, EdgeInfo::new(Default::default(), alice, bob));
+ * assert_eq!(edges.edges_from(alice).collect::<Vec<_>>(), vec![friendship]);
  *
  * // 查询顶点
  * if let Some(name) = vertices.get(alice) {
@@ -64,7 +65,7 @@ pub mod container;
 
 // 重新导出主要类型
 pub use vertex::VertexContainer;
-pub use edge::EdgeContainer;
+pub use edge::{EdgeContainer, EdgeRemovalOrder};
 pub use container::{Container, ContainerIter};
 
 /// 存储容器的通用操作接口
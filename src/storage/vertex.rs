@@ -1,4 +1,4 @@
-/**
+/*!
  * # VertexContainer - 顶点存储容器
  *
  * 基于 `pi_slotmap::SlotMap` 的高性能顶点存储实现。
@@ -21,7 +21,7 @@
 
 use super::{Storage, StorageKey};
 use super::super::id::VertexId;
-use pi_slotmap::{DefaultKey, SlotMap};
+use pi_slotmap::{DefaultKey, Key, SlotMap};
 
 /// 顶点存储容器，基于 `pi_slotmap::SlotMap` 实现
 ///
@@ -50,15 +50,15 @@ use pi_slotmap::{DefaultKey, SlotMap};
 /// - 连续内存布局，提高缓存命中率
 ///
 /// ## 泛型设计
-/// - 支持任意实现了 `Clone` 的顶点类型
+/// - 支持任意顶点类型，不要求实现 `Clone`
 /// - 编译时类型检查，防止类型混淆
 /// - 零成本抽象，运行时无额外开销
 ///
 /// # 使用示例
 ///
 /// ```rust
-/// use slotmap_graph::storage::VertexContainer;
-/// use slotmap_graph::id::VertexId;
+/// use pi_slotmap_graph::storage::VertexContainer;
+/// use pi_slotmap_graph::id::VertexId;
 ///
 /// // 创建存储容器
 /// let mut vertices = VertexContainer::new();
@@ -75,10 +75,7 @@ use pi_slotmap::{DefaultKey, SlotMap};
 /// assert_eq!(removed, Some("Alice"));
 /// ```
 #[derive(Debug)]
-pub struct VertexContainer<V>
-// where
-//     V: Clone,
-{
+pub struct VertexContainer<V, K: Key = DefaultKey> {
     /// 使用 SlotMap 存储顶点数据
     ///
     /// `SlotMap` 提供了以下关键特性：
@@ -86,13 +83,10 @@ pub struct VertexContainer<V>
     /// - 稳定的键引用，删除后不会重用
     /// - 紧凑的内存布局，自动重用删除的槽位
     /// - 迭代器安全，支持并发遍历
-    data: SlotMap<DefaultKey, V>,
+    data: SlotMap<K, V>,
 }
 
-impl<V> VertexContainer<V>
-// where
-//     V: Clone,
-{
+impl<V> VertexContainer<V> {
     /// 创建新的空顶点容器
     ///
     /// 创建一个不包含任何元素的空容器。内部SlotMap会根据需要进行初始分配。
@@ -104,7 +98,7 @@ impl<V> VertexContainer<V>
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::storage::VertexContainer;
+    /// use pi_slotmap_graph::storage::VertexContainer;
     ///
     /// let container: VertexContainer<String> = VertexContainer::new();
     /// assert_eq!(container.len(), 0);
@@ -139,7 +133,7 @@ impl<V> VertexContainer<V>
     /// # 示例
     ///
     /// ```rust
-    /// use slotmap_graph::storage::VertexContainer;
+    /// use pi_slotmap_graph::storage::VertexContainer;
     ///
     /// // 预分配1000个顶点的空间
     /// let container: VertexContainer<i32> = VertexContainer::with_capacity(1000);
@@ -163,35 +157,69 @@ impl<V> VertexContainer<V>
             data: SlotMap::with_capacity(capacity),
         }
     }
+}
+
+impl<V, K: Key> VertexContainer<V, K> {
+    /// 创建使用自定义键类型的空顶点容器
+    #[inline]
+    pub fn with_key() -> Self {
+        Self {
+            data: SlotMap::with_key(),
+        }
+    }
+
+    /// 创建使用自定义键类型、带有预设容量的顶点容器
+    #[inline]
+    pub fn with_capacity_and_key(capacity: usize) -> Self {
+        Self {
+            data: SlotMap::with_capacity_and_key(capacity),
+        }
+    }
 
     /// 插入顶点数据
     #[inline]
-    pub fn insert(&mut self, vertex: V) -> VertexId {
+    pub fn insert(&mut self, vertex: V) -> VertexId<K> {
         let key = self.data.insert(vertex);
         VertexId::new(key)
     }
 
     /// 获取顶点数据的不可变引用
     #[inline]
-    pub fn get(&self, id: VertexId) -> Option<&V> {
+    pub fn get(&self, id: VertexId<K>) -> Option<&V> {
         self.data.get(id.key())
     }
 
     /// 获取顶点数据的可变引用
     #[inline]
-    pub fn get_mut(&mut self, id: VertexId) -> Option<&mut V> {
+    pub fn get_mut(&mut self, id: VertexId<K>) -> Option<&mut V> {
         self.data.get_mut(id.key())
     }
 
+    /// 交换两个顶点的数据
+    ///
+    /// 两个ID都存在时返回 `true`；ID相同时不做任何修改。
+    pub fn swap(&mut self, a: VertexId<K>, b: VertexId<K>) -> bool {
+        if a == b {
+            return self.contains(a);
+        }
+        match self.data.get_disjoint_mut([a.key(), b.key()]) {
+            Some([a, b]) => {
+                std::mem::swap(a, b);
+                true
+            }
+            None => false,
+        }
+    }
+
     /// 删除顶点
     #[inline]
-    pub fn remove(&mut self, id: VertexId) -> Option<V> {
+    pub fn remove(&mut self, id: VertexId<K>) -> Option<V> {
         self.data.remove(id.key())
     }
 
     /// 检查是否包含指定顶点
     #[inline]
-    pub fn contains(&self, id: VertexId) -> bool {
+    pub fn contains(&self, id: VertexId<K>) -> bool {
         self.data.contains_key(id.key())
     }
 
@@ -201,6 +229,20 @@ impl<V> VertexContainer<V>
         self.data.len()
     }
 
+    /// 不重新分配时可用的槽位下标上界（不含）
+    ///
+    /// SlotMap 的 0 号槽位是哨兵，因此比容量多 1。
+    #[inline]
+    pub fn slot_capacity(&self) -> usize {
+        self.data.capacity() + 1
+    }
+
+    /// 不重新分配时可容纳的顶点数量
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
     /// 检查容器是否为空
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -215,7 +257,7 @@ impl<V> VertexContainer<V>
 
     /// 获取所有顶点ID
     #[inline]
-    pub fn keys(&self) -> impl Iterator<Item = VertexId> + '_ {
+    pub fn keys(&self) -> impl Iterator<Item = VertexId<K>> + '_ {
         self.data.keys().map(VertexId::new)
     }
 
@@ -233,13 +275,13 @@ impl<V> VertexContainer<V>
 
     /// 迭代所有顶点的键值对
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = (VertexId, &V)> + '_ {
+    pub fn iter(&self) -> impl Iterator<Item = (VertexId<K>, &V)> + '_ {
         self.data.iter().map(|(key, value)| (VertexId::new(key), value))
     }
 
     /// 迭代所有顶点的键值对（可变）
     #[inline]
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = (VertexId, &mut V)> + '_ {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (VertexId<K>, &mut V)> + '_ {
         self.data.iter_mut().map(|(key, value)| (VertexId::new(key), value))
     }
 
@@ -247,14 +289,14 @@ impl<V> VertexContainer<V>
     #[inline]
     pub fn retain<F>(&mut self, mut predicate: F)
     where
-        F: FnMut(VertexId, &V) -> bool,
+        F: FnMut(VertexId<K>, &V) -> bool,
     {
         self.data.retain(|key, value| predicate(VertexId::new(key), value));
     }
 
     /// 批量插入顶点
     #[inline]
-    pub fn insert_iter<I>(&mut self, iter: I) -> Vec<VertexId>
+    pub fn insert_iter<I>(&mut self, iter: I) -> Vec<VertexId<K>>
     where
         I: IntoIterator<Item = V>,
     {
@@ -262,10 +304,7 @@ impl<V> VertexContainer<V>
     }
 }
 
-impl<V> Storage<V> for VertexContainer<V>
-where
-    V: Clone,
-{
+impl<V, K: Key> Storage<V> for VertexContainer<V, K> {
     fn len(&self) -> usize {
         self.len()
     }
@@ -279,7 +318,7 @@ where
     }
 
     fn contains(&self, id: impl Into<StorageKey>) -> bool {
-        self.contains(VertexId(id.into().0))
+        self.contains(VertexId::new(K::from(id.into().0.data())))
     }
 
     fn iter(&self) -> super::container::ContainerIter<'_, V, Self>
@@ -290,12 +329,38 @@ where
     }
 }
 
-impl<V> Default for VertexContainer<V>
-where
-    V: Clone,
-{
+/// 顶点容器的消费迭代器，按槽位顺序产生 `(VertexId, V)`
+pub struct IntoIter<V, K: Key = DefaultKey> {
+    inner: pi_slotmap::basic::IntoIter<K, V>,
+}
+
+impl<V, K: Key> Iterator for IntoIter<V, K> {
+    type Item = (VertexId<K>, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, value)| (VertexId::new(key), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<V, K: Key> IntoIterator for VertexContainer<V, K> {
+    type Item = (VertexId<K>, V);
+    type IntoIter = IntoIter<V, K>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { inner: self.data.into_iter() }
+    }
+}
+
+impl<V, K: Key> Default for VertexContainer<V, K> {
     fn default() -> Self {
-        Self::new()
+        Self::with_key()
     }
 }
 
@@ -358,4 +423,20 @@ mod tests {
         let values: Vec<_> = vertices.values().cloned().collect();
         assert!(values.iter().any(|v| v.starts_with("Vertex")));
     }
+
+    #[test]
+    fn test_vertex_container_swap() {
+        let mut vertices = VertexContainer::new();
+        let alice = vertices.insert("Alice");
+        let bob = vertices.insert("Bob");
+
+        assert!(vertices.swap(alice, bob));
+        assert_eq!(vertices.get(alice), Some(&"Bob"));
+        assert_eq!(vertices.get(bob), Some(&"Alice"));
+        assert!(vertices.swap(alice, alice));
+
+        vertices.remove(bob);
+        assert!(!vertices.swap(alice, bob));
+        assert_eq!(vertices.get(alice), Some(&"Bob"));
+    }
 }
\ No newline at end of file
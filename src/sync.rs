@@ -0,0 +1,257 @@
+/*!
+ * # 线程安全的图 (Sync Graph)
+ *
+ * [`SyncGraph`] 用 `RwLock` 包装 [`SlotMapGraph`]，供多个线程共享同一张图。
+ *
+ * ## 访问方式
+ *
+ * 不对外暴露锁守卫，所有访问都在闭包或单次操作内完成：
+ *
+ * - [`read`](SyncGraph::read) / [`write`](SyncGraph::write)：持锁期间执行闭包，返回闭包的结果
+ * - [`add_vertex`](SyncGraph::add_vertex)、[`add_edge`](SyncGraph::add_edge)、
+ *   [`remove_vertex`](SyncGraph::remove_vertex)、[`vertex_weight_cloned`](SyncGraph::vertex_weight_cloned) 等：
+ *   内部加锁、完成操作后立即释放
+ *
+ * 闭包的返回类型不能借用图，因此顶点、边的引用和迭代器都无法逃出锁的作用域，
+ * 需要带出数据时先克隆或收集：
+ *
+ * ```rust,compile_fail
+ * use graph_api_lib::Graph;
+ * use pi_slotmap_graph::sync::SyncGraph;
+ *
+ * let graph: SyncGraph<u32, ()> = SyncGraph::new();
+ * let a = graph.add_vertex(1);
+ * // 错误：返回的引用借用了锁内的图
+ * let vertex = graph.read(|g| g.vertex(a));
+ * ```
+ *
+ * ## 锁中毒
+ *
+ * 持有写锁的闭包 panic 时，图可能停留在修改了一半的状态，
+ * 之后对同一个 `SyncGraph` 的任何访问都会 panic，而不是读取不一致的数据。
+ *
+ * ## 使用示例
+ *
+ * ```rust
+ * use pi_slotmap_graph::sync::SyncGraph;
+ * use std::sync::Arc;
+ * use std::thread;
+ *
+ * let graph: Arc<SyncGraph<u32, ()>> = Arc::new(SyncGraph::new());
+ * let hub = graph.add_vertex(0);
+ * let workers: Vec<_> = (1..=4)
+ *     .map(|i| {
+ *         let graph = Arc::clone(&graph);
+ *         thread::spawn(move || {
+ *             let v = graph.add_vertex(i);
+ *             graph.add_edge(hub, v, ()).unwrap();
+ *         })
+ *     })
+ *     .collect();
+ * workers.into_iter().for_each(|worker| worker.join().unwrap());
+ *
+ * assert_eq!(graph.read(|g| g.out_degree(hub)), 4);
+ * ```
+ */
+
+use crate::error::GraphError;
+use crate::graph::SlotMapGraph;
+use crate::id::{EdgeId, VertexId};
+use graph_api_lib::{Element, Graph, SupportsElementRemoval};
+use pi_slotmap::{DefaultKey, Key};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// 以读写锁保护、可在线程间共享的图
+#[derive(Debug, Default)]
+pub struct SyncGraph<Vertex, Edge, K = DefaultKey>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 被保护的图
+    inner: RwLock<SlotMapGraph<Vertex, Edge, K>>,
+}
+
+impl<Vertex, Edge> SyncGraph<Vertex, Edge>
+where
+    Vertex: Element,
+    Edge: Element,
+{
+    /// 创建空图
+    pub fn new() -> Self {
+        Self::from_graph(SlotMapGraph::new())
+    }
+}
+
+impl<Vertex, Edge, K> SyncGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    /// 包装已有的图
+    pub fn from_graph(graph: SlotMapGraph<Vertex, Edge, K>) -> Self {
+        Self {
+            inner: RwLock::new(graph),
+        }
+    }
+
+    /// 取出内部的图
+    pub fn into_inner(self) -> SlotMapGraph<Vertex, Edge, K> {
+        self.inner.into_inner().expect("SyncGraph lock poisoned")
+    }
+
+    /// 持有读锁执行 `f`
+    pub fn read<R>(&self, f: impl FnOnce(&SlotMapGraph<Vertex, Edge, K>) -> R) -> R {
+        f(&self.read_guard())
+    }
+
+    /// 持有写锁执行 `f`
+    pub fn write<R>(&self, f: impl FnOnce(&mut SlotMapGraph<Vertex, Edge, K>) -> R) -> R {
+        f(&mut self.write_guard())
+    }
+
+    /// 添加顶点
+    pub fn add_vertex(&self, vertex: Vertex) -> VertexId<K> {
+        self.write_guard().add_vertex(vertex)
+    }
+
+    /// 添加一条 `from -> to` 的边
+    ///
+    /// 检查端点和插入在同一次加锁内完成，其他线程无法在两者之间删除端点。
+    ///
+    /// # 错误
+    ///
+    /// 端点不存在时返回 [`GraphError::VertexNotFound`]。
+    pub fn add_edge(&self, from: VertexId<K>, to: VertexId<K>, edge: Edge) -> Result<EdgeId<K>, GraphError<K>> {
+        let mut graph = self.write_guard();
+        for vertex in [from, to] {
+            if !graph.contains_vertex(vertex) {
+                return Err(GraphError::VertexNotFound(vertex));
+            }
+        }
+        Ok(graph.add_edge(from, to, edge))
+    }
+
+    /// 删除顶点及其关联边，顶点不存在或被固定时返回 `None`
+    pub fn remove_vertex(&self, vertex_id: VertexId<K>) -> Option<Vertex> {
+        self.write_guard().remove_vertex(vertex_id)
+    }
+
+    /// 删除边
+    pub fn remove_edge(&self, edge_id: EdgeId<K>) -> Option<Edge> {
+        self.write_guard().remove_edge(edge_id)
+    }
+
+    /// 克隆顶点数据
+    pub fn vertex_weight_cloned(&self, vertex_id: VertexId<K>) -> Option<Vertex>
+    where
+        Vertex: Clone,
+    {
+        self.read_guard().vertex_weight(vertex_id).cloned()
+    }
+
+    /// 克隆边数据
+    pub fn edge_weight_cloned(&self, edge_id: EdgeId<K>) -> Option<Edge>
+    where
+        Edge: Clone,
+    {
+        self.read_guard().edge_weight(edge_id).cloned()
+    }
+
+    /// 顶点数量
+    pub fn vertex_count(&self) -> usize {
+        self.read_guard().vertex_count()
+    }
+
+    /// 边数量
+    pub fn edge_count(&self) -> usize {
+        self.read_guard().edge_count()
+    }
+
+    fn read_guard(&self) -> RwLockReadGuard<'_, SlotMapGraph<Vertex, Edge, K>> {
+        self.inner.read().expect("SyncGraph lock poisoned")
+    }
+
+    fn write_guard(&self) -> RwLockWriteGuard<'_, SlotMapGraph<Vertex, Edge, K>> {
+        self.inner.write().expect("SyncGraph lock poisoned")
+    }
+}
+
+impl<Vertex, Edge, K> From<SlotMapGraph<Vertex, Edge, K>> for SyncGraph<Vertex, Edge, K>
+where
+    Vertex: Element,
+    Edge: Element,
+    K: Key,
+{
+    fn from(graph: SlotMapGraph<Vertex, Edge, K>) -> Self {
+        Self::from_graph(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_concurrent_writers_and_readers() {
+        const WRITERS: usize = 4;
+        const PER_WRITER: usize = 500;
+
+        let graph: Arc<SyncGraph<u32, u32>> = Arc::new(SyncGraph::new());
+        let root = graph.add_vertex(0);
+
+        let writers: Vec<_> = (0..WRITERS)
+            .map(|w| {
+                let graph = Arc::clone(&graph);
+                thread::spawn(move || {
+                    for i in 0..PER_WRITER {
+                        let v = graph.add_vertex((w * PER_WRITER + i) as u32);
+                        graph.add_edge(root, v, i as u32).unwrap();
+                        // 每隔一个顶点删除一次，级联删除其入边
+                        if i % 2 == 1 {
+                            let weight = graph.vertex_weight_cloned(v);
+                            assert_eq!(graph.remove_vertex(v), weight);
+                        }
+                    }
+                })
+            })
+            .collect();
+        let readers: Vec<_> = (0..2)
+            .map(|_| {
+                let graph = Arc::clone(&graph);
+                thread::spawn(move || {
+                    for _ in 0..200 {
+                        // 读锁内看到的总是一致的图
+                        graph.read(|g| assert_eq!(g.validate(), Ok(())));
+                        assert!(graph.vertex_count() >= 1);
+                    }
+                })
+            })
+            .collect();
+        for handle in writers.into_iter().chain(readers) {
+            handle.join().unwrap();
+        }
+
+        // 每个写线程留下 PER_WRITER / 2 个顶点，每个都有一条来自根的入边
+        assert_eq!(graph.vertex_count(), 1 + WRITERS * PER_WRITER / 2);
+        assert_eq!(graph.edge_count(), WRITERS * PER_WRITER / 2);
+        assert_eq!(graph.read(|g| g.out_degree(root)), WRITERS * PER_WRITER / 2);
+        let graph = Arc::try_unwrap(graph).unwrap().into_inner();
+        assert_eq!(graph.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_add_edge_to_removed_vertex() {
+        let graph: SyncGraph<u32, ()> = SyncGraph::new();
+        let a = graph.add_vertex(1);
+        let b = graph.add_vertex(2);
+        assert_eq!(graph.remove_vertex(b), Some(2));
+        assert_eq!(graph.add_edge(a, b, ()), Err(GraphError::VertexNotFound(b)));
+        assert_eq!(graph.vertex_weight_cloned(a), Some(1));
+        assert_eq!(graph.write(|g| g.add_edge(a, a, ())), graph.read(|g| g.find_edge(a, a)).unwrap());
+    }
+}
@@ -0,0 +1,112 @@
+/*!
+ * # 删除边的一致性测试
+ *
+ * 单独删除边、级联删除顶点和延迟删除都经过同一个内部入口，
+ * 这里开启全部可选结构，确认删除后没有任何结构仍记录被删除的边，
+ * 且变更记录中关联边先于顶点删除。
+ */
+
+use graph_api_lib::{EdgeReference, Graph, SupportsElementRemoval};
+use pi_slotmap_graph::changelog::Change;
+use pi_slotmap_graph::{EdgeId, SlotMapGraph, VertexId};
+use std::collections::HashSet;
+
+/// 开启端点对索引、标记、创建顺序、标签缓存、邻接边标签缓存、统计和变更日志
+///
+/// 中心顶点 `hub` 有三条关联边，`a -> b` 与其中一条边同属批次 7。
+fn instrumented() -> (SlotMapGraph<u32, u32>, VertexId, [VertexId; 3], [EdgeId; 3], EdgeId) {
+    let mut graph = SlotMapGraph::new();
+    graph.enable_pair_index();
+    graph.enable_marks();
+    graph.enable_creation_order();
+    graph.enable_label_cache();
+    graph.enable_adjacent_label_cache();
+    graph.enable_metrics();
+    graph.enable_change_log(None);
+
+    let hub = graph.add_vertex(0);
+    let [a, b, c] = [1, 2, 3].map(|weight| graph.add_vertex(weight));
+    let incident = [
+        graph.add_edge_in_layer(hub, a, 10, 1),
+        graph.add_edge_tagged(b, hub, 11, 7),
+        graph.add_edge(c, hub, 12),
+    ];
+    let survivor = graph.add_edge_tagged(a, b, 13, 7);
+    for edge in incident.into_iter().chain([survivor]) {
+        assert!(graph.set_mark(edge, 1));
+    }
+    (graph, hub, [a, b, c], incident, survivor)
+}
+
+#[test]
+fn test_cascade_leaves_no_stale_entries() {
+    let (mut graph, hub, [a, b, c], incident, survivor) = instrumented();
+    assert_eq!(graph.validate(), Ok(()));
+
+    assert_eq!(graph.remove_vertex(hub), Some(0));
+    for edge in incident {
+        assert!(graph.edge(edge).is_none());
+        assert!(!graph.has_mark(edge, 1));
+        assert_eq!(graph.creation_seq(edge), None);
+        assert_eq!(graph.edge_tag(edge), None);
+        assert_eq!(graph.edge_layer(edge), None);
+    }
+    assert_eq!(graph.marked_edges(1).collect::<Vec<_>>(), vec![survivor]);
+    assert_eq!(graph.edges_in_creation_order().collect::<Vec<_>>(), vec![survivor]);
+    assert_eq!(graph.edges_with_tag(7).collect::<Vec<_>>(), vec![survivor]);
+    assert_eq!(graph.find_edge(a, b), Some(survivor));
+    assert_eq!(graph.edges_between(c, hub).count(), 0);
+    assert_eq!(graph.adjacent_label_counts(a).collect::<Vec<_>>(), vec![((), 1)]);
+    assert_eq!(graph.adjacent_label_counts(c).count(), 0);
+
+    let metrics = graph.metrics().unwrap();
+    assert_eq!((metrics.edges_removed, metrics.vertices_removed), (3, 1));
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn test_cascade_records_edges_before_vertex() {
+    let (mut graph, hub, _, incident, _) = instrumented();
+    let version = graph.version();
+    graph.remove_vertex(hub);
+    // 每条边和顶点各递增一次版本号
+    assert_eq!(graph.version(), version + 4);
+
+    let changes: Vec<_> =
+        graph.change_log().unwrap().iter().filter(|(entry, _)| *entry > version).map(|(_, change)| change).collect();
+    assert_eq!(changes.len(), 4);
+    assert_eq!(changes[3], Change::VertexRemoved(hub));
+    let removed: HashSet<_> = changes[..3]
+        .iter()
+        .map(|change| match change {
+            Change::EdgeRemoved(edge) => *edge,
+            other => panic!("unexpected change {other:?}"),
+        })
+        .collect();
+    assert_eq!(removed, HashSet::from(incident));
+}
+
+#[test]
+fn test_single_and_deferred_removal_match_cascade() {
+    let (mut single, hub, ..) = instrumented();
+    let edges: Vec<_> = single.adjacent_edges(hub).map(|edge| edge.id()).collect();
+    for edge in &edges {
+        assert!(single.remove_edge(*edge).is_some());
+    }
+
+    let (mut deferred, ..) = instrumented();
+    deferred.with_deferred_removals(|graph| {
+        for edge in &edges {
+            graph.queue_remove_edge(*edge);
+        }
+    });
+
+    for graph in [&single, &deferred] {
+        assert_eq!(graph.degree(hub), 0);
+        assert_eq!(graph.marked_edges(1).count(), 1);
+        assert_eq!(graph.edges_with_tag(7).count(), 1);
+        assert_eq!(graph.metrics().unwrap().edges_removed, 3);
+        assert_eq!(graph.validate(), Ok(()));
+    }
+    assert_eq!(single.version(), deferred.version());
+}
@@ -0,0 +1,57 @@
+/*!
+ * # 示例冒烟测试
+ *
+ * 直接编译 `examples/` 中的源文件，调用其中的核心函数并检查输出，
+ * 确保示例随库的接口变化保持可用。
+ */
+
+#[path = "../examples/dependency_resolver.rs"]
+#[allow(dead_code)]
+mod dependency_resolver;
+
+#[path = "../examples/social_network.rs"]
+#[allow(dead_code)]
+mod social_network;
+
+use dependency_resolver::{Dependencies, CYCLIC_MANIFEST, MANIFEST};
+
+#[test]
+fn test_install_order_puts_dependencies_first() {
+    let deps = Dependencies::parse(MANIFEST);
+    let order = deps.install_order().unwrap();
+    assert_eq!(order.len(), 7);
+    let position = |name: &str| order.iter().position(|package| package == name).unwrap();
+    for (package, dependency) in [("app", "http"), ("http", "net"), ("net", "alloc"), ("log", "alloc"), ("bench", "app")] {
+        assert!(position(dependency) < position(package), "{dependency} before {package} in {order:?}");
+    }
+}
+
+#[test]
+fn test_cycle_is_reported() {
+    let cycle = Dependencies::parse(CYCLIC_MANIFEST).install_order().unwrap_err();
+    assert_eq!(cycle, ["http", "tls", "crypto"]);
+}
+
+#[test]
+fn test_closure_dot() {
+    let deps = Dependencies::parse(MANIFEST);
+    let dot = deps.closure_dot("http").unwrap();
+    assert_eq!(
+        dot,
+        "digraph {\n    n0 [label=\"http\"];\n    n1 [label=\"alloc\"];\n    n2 [label=\"log\"];\n    n3 [label=\"net\"];\n    \
+         n0 -> n3;\n    n0 -> n2;\n    n2 -> n1;\n    n3 -> n1;\n    n3 -> n2;\n}\n"
+    );
+    assert!(deps.closure_dot("missing").is_none());
+}
+
+#[test]
+fn test_social_network_queries() {
+    let graph = social_network::build();
+    assert_eq!(social_network::cities(&graph), ["Lyon", "Paris"]);
+    assert_eq!(social_network::people_aged(&graph, 20..40), ["Alice", "Bob"]);
+    assert!(social_network::find_person(&graph, "Eve").is_none());
+
+    let alice = social_network::find_person(&graph, "Alice").unwrap();
+    assert_eq!(social_network::friends_of(&graph, alice), [("Bob".to_string(), 2015), ("Carol".to_string(), 2020)]);
+    assert_eq!(social_network::neighbours_of(&graph, alice), ["Carol"]);
+}
@@ -0,0 +1,221 @@
+/*!
+ * # 基于模型的属性测试
+ *
+ * 随机生成操作序列，同时作用于 `SlotMapGraph` 和一个朴素的参考模型，
+ * 每一步之后比较两者的顶点数、边数、邻居多重集合以及 `has_edge` 结果，
+ * 并用 `assert_same_structure!` 比较被测图与按模型重建的图。
+ */
+
+use graph_api_lib::{EdgeReference, Graph, SupportsClear, SupportsElementRemoval, VertexReference};
+use pi_slotmap_graph::{assert_same_structure, EdgeId, SlotMapGraph, VertexId};
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+/// 作用于图和模型的操作
+#[derive(Debug, Clone)]
+enum Op {
+    AddVertex(u32),
+    RemoveVertex(Index),
+    AddEdge(Index, Index, u32),
+    RemoveEdge(Index),
+    Clear,
+    /// 删除权重为奇数的顶点
+    RetainEvenVertices,
+    /// 删除权重能被 `n` 整除的边
+    RetainEdges(u32),
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => any::<u32>().prop_map(Op::AddVertex),
+        2 => any::<Index>().prop_map(Op::RemoveVertex),
+        6 => (any::<Index>(), any::<Index>(), any::<u32>()).prop_map(|(a, b, w)| Op::AddEdge(a, b, w)),
+        2 => any::<Index>().prop_map(Op::RemoveEdge),
+        1 => Just(Op::Clear),
+        1 => Just(Op::RetainEvenVertices),
+        1 => (2u32..5).prop_map(Op::RetainEdges),
+    ]
+}
+
+/// 参考模型：以槽位下标作为 ID，删除后置为 `None`
+#[derive(Default)]
+struct Model {
+    vertices: Vec<Option<u32>>,
+    edges: Vec<Option<(usize, usize, u32)>>,
+}
+
+impl Model {
+    fn live_vertices(&self) -> Vec<usize> {
+        (0..self.vertices.len()).filter(|&i| self.vertices[i].is_some()).collect()
+    }
+
+    fn live_edges(&self) -> Vec<usize> {
+        (0..self.edges.len()).filter(|&i| self.edges[i].is_some()).collect()
+    }
+
+    fn remove_vertex(&mut self, v: usize) {
+        self.vertices[v] = None;
+        for edge in &mut self.edges {
+            if matches!(edge, Some((from, to, _)) if *from == v || *to == v) {
+                *edge = None;
+            }
+        }
+    }
+
+    /// 顶点的出边 `(终点, 权重)`，已排序
+    fn outgoing(&self, v: usize) -> Vec<(usize, u32)> {
+        let mut out: Vec<_> = self.edges.iter().flatten().filter(|e| e.0 == v).map(|e| (e.1, e.2)).collect();
+        out.sort_unstable();
+        out
+    }
+
+    /// 顶点的入边 `(起点, 权重)`，已排序
+    fn incoming(&self, v: usize) -> Vec<(usize, u32)> {
+        let mut inc: Vec<_> = self.edges.iter().flatten().filter(|e| e.1 == v).map(|e| (e.0, e.2)).collect();
+        inc.sort_unstable();
+        inc
+    }
+}
+
+/// 被测图及其与模型的 ID 映射
+#[derive(Default)]
+struct Harness {
+    graph: SlotMapGraph<u32, u32>,
+    model: Model,
+    vertex_ids: Vec<VertexId>,
+    edge_ids: Vec<EdgeId>,
+}
+
+impl Harness {
+    fn apply(&mut self, op: &Op) {
+        match op {
+            Op::AddVertex(w) => {
+                self.vertex_ids.push(self.graph.add_vertex(*w));
+                self.model.vertices.push(Some(*w));
+            }
+            Op::RemoveVertex(index) => {
+                let live = self.model.live_vertices();
+                if live.is_empty() {
+                    return;
+                }
+                let v = live[index.index(live.len())];
+                let removed = self.graph.remove_vertex(self.vertex_ids[v]);
+                assert_eq!(removed, self.model.vertices[v]);
+                self.model.remove_vertex(v);
+            }
+            Op::AddEdge(a, b, w) => {
+                let live = self.model.live_vertices();
+                if live.is_empty() {
+                    return;
+                }
+                let from = live[a.index(live.len())];
+                let to = live[b.index(live.len())];
+                self.edge_ids.push(self.graph.add_edge(self.vertex_ids[from], self.vertex_ids[to], *w));
+                self.model.edges.push(Some((from, to, *w)));
+            }
+            Op::RemoveEdge(index) => {
+                let live = self.model.live_edges();
+                if live.is_empty() {
+                    return;
+                }
+                let e = live[index.index(live.len())];
+                let removed = self.graph.remove_edge(self.edge_ids[e]);
+                assert_eq!(removed, self.model.edges[e].map(|(_, _, w)| w));
+                self.model.edges[e] = None;
+            }
+            Op::Clear => {
+                SupportsClear::clear(&mut self.graph);
+                self.model.vertices.iter_mut().for_each(|v| *v = None);
+                self.model.edges.iter_mut().for_each(|e| *e = None);
+            }
+            Op::RetainEvenVertices => {
+                for v in self.model.live_vertices() {
+                    if self.model.vertices[v].is_some_and(|w| w % 2 == 1) {
+                        self.graph.remove_vertex(self.vertex_ids[v]);
+                        self.model.remove_vertex(v);
+                    }
+                }
+            }
+            Op::RetainEdges(n) => {
+                for e in self.model.live_edges() {
+                    if self.model.edges[e].is_some_and(|(_, _, w)| w % n == 0) {
+                        self.graph.remove_edge(self.edge_ids[e]);
+                        self.model.edges[e] = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 把图中的顶点 ID 映射回模型下标
+    fn model_index(&self, id: VertexId) -> usize {
+        self.vertex_ids.iter().position(|&v| v == id).expect("graph returned an unknown vertex id")
+    }
+
+    fn check(&self) {
+        let live = self.model.live_vertices();
+        assert_eq!(self.graph.vertex_count(), live.len());
+        assert_eq!(self.graph.edge_count(), self.model.live_edges().len());
+
+        for &v in &live {
+            let id = self.vertex_ids[v];
+            assert_eq!(self.graph.vertex(id).map(|r| *r.weight()), self.model.vertices[v]);
+
+            let mut outgoing: Vec<_> = self
+                .graph
+                .outgoing_edges(id)
+                .map(|e| (self.model_index(e.head()), *e.weight()))
+                .collect();
+            outgoing.sort_unstable();
+            assert_eq!(outgoing, self.model.outgoing(v));
+
+            let mut incoming: Vec<_> = self
+                .graph
+                .incoming_edges(id)
+                .map(|e| (self.model_index(e.tail()), *e.weight()))
+                .collect();
+            incoming.sort_unstable();
+            assert_eq!(incoming, self.model.incoming(v));
+
+            assert_eq!(self.graph.out_degree(id), outgoing.len());
+            assert_eq!(self.graph.in_degree(id), incoming.len());
+        }
+
+        for &a in &live {
+            for &b in &live {
+                let expected = self.model.edges.iter().flatten().any(|e| e.0 == a && e.1 == b);
+                assert_eq!(self.graph.has_edge(self.vertex_ids[a], self.vertex_ids[b]), expected);
+            }
+        }
+
+        // 按模型重建的图与被测图结构相同
+        let mut rebuilt = SlotMapGraph::new();
+        let mut rebuilt_ids = vec![None; self.model.vertices.len()];
+        for &v in &live {
+            rebuilt_ids[v] = self.model.vertices[v].map(|w| rebuilt.add_vertex(w));
+        }
+        for &(from, to, w) in self.model.edges.iter().flatten() {
+            rebuilt.add_edge(rebuilt_ids[from].unwrap(), rebuilt_ids[to].unwrap(), w);
+        }
+        assert_same_structure!(rebuilt, self.graph);
+
+        // 已删除的元素不可再访问
+        for (v, &id) in self.vertex_ids.iter().enumerate() {
+            assert_eq!(self.graph.contains_vertex(id), self.model.vertices[v].is_some());
+        }
+        for (e, &id) in self.edge_ids.iter().enumerate() {
+            assert_eq!(self.graph.contains_edge(id), self.model.edges[e].is_some());
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn graph_matches_reference_model(ops in prop::collection::vec(op_strategy(), 1..64)) {
+        let mut harness = Harness::default();
+        for op in &ops {
+            harness.apply(op);
+            harness.check();
+        }
+    }
+}
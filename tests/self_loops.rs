@@ -0,0 +1,222 @@
+/*!
+ * # 自环测试
+ *
+ * 自环同时位于顶点的出边列表和入边列表中，容易在度数、邻接遍历和删除时被重复计算。
+ * 这里逐项检查各接口对自环的约定：
+ *
+ * - 出度和入度各计一次，总度数只计一次
+ * - 不区分方向的遍历（`adjacent_edges`、`Direction::All`、无向端点查询）每条自环只产生一次
+ * - 有向的遍历（出边、入边、`edges_between(v, v)`）各产生一次
+ * - 删除顶点时自环只删除一次，删除后邻接表与计数器保持一致
+ */
+
+use graph_api_lib::{Direction, EdgeReference, EdgeSearch, Graph, SupportsElementRemoval};
+use pi_slotmap_graph::{EdgeId, EdgeInfo, GraphError, RemovePolicy, SlotMapGraph, VertexId};
+use std::collections::HashSet;
+
+/// 顶点 `v` 上有两条自环，另有 `v -> w` 和 `u -> v` 各一条
+fn looped() -> (SlotMapGraph<u32, u32>, VertexId, [EdgeId; 2], VertexId, VertexId) {
+    let mut graph = SlotMapGraph::new();
+    let v = graph.add_vertex(0);
+    let w = graph.add_vertex(1);
+    let u = graph.add_vertex(2);
+    let first = graph.add_edge(v, v, 10);
+    graph.add_edge(v, w, 11);
+    let second = graph.add_edge(v, v, 12);
+    graph.add_edge(u, v, 13);
+    (graph, v, [first, second], w, u)
+}
+
+fn ids<'a>(edges: impl Iterator<Item = impl EdgeReference<'a, SlotMapGraph<u32, u32>>>) -> Vec<EdgeId> {
+    edges.map(|edge| edge.id()).collect()
+}
+
+#[test]
+fn test_degree_counts_loop_once() {
+    let (graph, v, _, w, u) = looped();
+    assert_eq!(graph.out_degree(v), 3);
+    assert_eq!(graph.in_degree(v), 3);
+    assert_eq!(graph.degree(v), 4);
+    assert_eq!((graph.degree(w), graph.degree(u)), (1, 1));
+    assert_eq!(graph.average_degree(), 2.0);
+}
+
+#[test]
+fn test_directed_adjacency_includes_loop_in_both_directions() {
+    let (graph, v, loops, ..) = looped();
+    let outgoing = ids(graph.outgoing_edges(v));
+    let incoming = ids(graph.incoming_edges(v));
+    assert_eq!(outgoing.len(), 3);
+    assert_eq!(incoming.len(), 3);
+    for edge in loops {
+        assert!(outgoing.contains(&edge) && incoming.contains(&edge));
+    }
+    assert_eq!(graph.outgoing_adjacency(v).len(), 3);
+    assert_eq!(graph.incoming_adjacency(v).len(), 3);
+}
+
+#[test]
+fn test_undirected_adjacency_yields_loop_once() {
+    let (graph, v, loops, ..) = looped();
+    let adjacent = ids(graph.adjacent_edges(v));
+    assert_eq!(adjacent.len(), 4);
+    assert_eq!(adjacent.iter().collect::<HashSet<_>>().len(), 4);
+
+    let directions: Vec<_> = graph
+        .adjacent_edges_with_direction(v)
+        .filter(|(_, edge)| loops.contains(&edge.id()))
+        .map(|(direction, _)| direction)
+        .collect();
+    assert_eq!(directions, vec![Direction::Outgoing, Direction::Outgoing]);
+
+    assert_eq!(graph.edges(v, &EdgeSearch::scan()).count(), 4);
+    assert_eq!(graph.edges(v, &EdgeSearch::scan().outgoing()).count(), 3);
+    assert_eq!(graph.edges(v, &EdgeSearch::scan().incoming()).count(), 3);
+}
+
+#[test]
+fn test_endpoint_queries_on_loop() {
+    let (mut graph, v, loops, w, _) = looped();
+    for indexed in [false, true] {
+        if indexed {
+            graph.enable_pair_index();
+        }
+        assert!(graph.has_edge(v, v));
+        assert_eq!(graph.find_edge(v, v), Some(loops[0]));
+        assert_eq!(ids(graph.edges_between(v, v)), loops);
+        assert!(!graph.has_edge(w, w));
+    }
+    assert!(graph.has_edge_undirected(v, v));
+    assert_eq!(ids(graph.edges_between_undirected(v, v)), loops);
+}
+
+#[test]
+fn test_neighbors_exclude_self() {
+    let (graph, v, _, w, u) = looped();
+    assert_eq!(graph.distinct_neighbors(v), HashSet::from([w, u]));
+
+    let mut only_loop: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    let a = only_loop.add_vertex(0);
+    only_loop.add_edge(a, a, 0);
+    assert!(only_loop.distinct_neighbors(a).is_empty());
+    assert_eq!(only_loop.bfs(a).collect::<Vec<_>>(), vec![(a, 0)]);
+    assert_eq!(only_loop.weakly_connected_components(), vec![vec![a]]);
+}
+
+#[test]
+fn test_label_counts_loop_once() {
+    let (mut graph, v, ..) = looped();
+    assert_eq!(graph.adjacent_label_counts(v).collect::<Vec<_>>(), vec![((), 4)]);
+    assert_eq!(graph.adjacent_edges_by_label(v, (), Direction::All).count(), 4);
+    assert_eq!(graph.adjacent_edges_by_label(v, (), Direction::Outgoing).count(), 3);
+
+    graph.enable_adjacent_label_cache();
+    assert_eq!(graph.adjacent_label_counts(v).collect::<Vec<_>>(), vec![((), 4)]);
+}
+
+#[test]
+fn test_remove_loop_restores_counters() {
+    let (mut graph, v, loops, ..) = looped();
+    assert_eq!(graph.remove_edge(loops[0]), Some(10));
+    assert_eq!((graph.out_degree(v), graph.in_degree(v), graph.degree(v)), (2, 2, 3));
+    assert_eq!(graph.remove_edge(loops[0]), None);
+    graph.remove_edge(loops[1]);
+    assert_eq!((graph.out_degree(v), graph.in_degree(v), graph.degree(v)), (1, 1, 2));
+    assert!(!graph.has_edge(v, v));
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn test_remove_vertex_cascades_loop_once() {
+    let (mut graph, v, loops, w, u) = looped();
+    assert_eq!(
+        graph.remove_vertex_policy(v, RemovePolicy::Restrict),
+        Err(GraphError::VertexHasEdges { vertex: v, edge_count: 4 })
+    );
+    assert_eq!(graph.remove_vertex(v), Some(0));
+    assert_eq!(graph.edge_count(), 0);
+    assert!(loops.iter().all(|edge| graph.edge(*edge).is_none()));
+    assert_eq!((graph.degree(w), graph.degree(u)), (0, 0));
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn test_batch_removal_with_loops() {
+    let (mut graph, v, _, w, u) = looped();
+    graph.add_edge(w, w, 20);
+    let removed = graph.remove_vertices(&[v, w]);
+    assert_eq!(removed.len(), 2);
+    assert_eq!(graph.edge_count(), 0);
+    assert_eq!(graph.degree(u), 0);
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn test_reorder_moves_loop_in_both_lists() {
+    let (mut graph, v, loops, ..) = looped();
+    assert!(graph.move_edge_to_front(v, loops[1]));
+    assert_eq!(graph.outgoing_adjacency(v)[0], loops[1]);
+    assert_eq!(graph.incoming_adjacency(v)[0], loops[1]);
+
+    assert!(graph.reorder_edges(v, &[loops[0]]));
+    assert_eq!(graph.outgoing_adjacency(v)[0], loops[0]);
+    assert_eq!(graph.incoming_adjacency(v)[0], loops[0]);
+    assert_eq!(graph.validate(), Ok(()));
+}
+
+#[test]
+fn test_reverse_of_loop_is_identity() {
+    let (graph, v, loops, ..) = looped();
+    let (from, to) = graph.edge_endpoints(loops[0]).unwrap();
+    let info = EdgeInfo::new(loops[0], from, to);
+    assert_eq!(info.reverse(), info);
+    assert_eq!(info.sorted_endpoints(), (v, v));
+    // 自环不与自身配成互为反向的边对
+    assert_eq!(graph.mutual_edges().count(), 0);
+}
+
+#[test]
+fn test_loop_in_algorithms() {
+    let (graph, v, ..) = looped();
+    assert!(graph.topological_sort().is_err());
+    assert_eq!(graph.self_loop_ratio(), 0.5);
+    assert!(graph.to_static().has_edge(v, v));
+
+    let mut single: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    let a = single.add_vertex(0);
+    let edge = single.add_edge(a, a, 0);
+    assert_eq!(single.find_cycle(), Some(vec![a]));
+    assert_eq!(single.eulerian_path(), Some(vec![edge]));
+}
+
+#[test]
+fn test_degree_limit_counts_loop_once() {
+    let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    let a = graph.add_vertex(0);
+    graph.set_max_degree(Some(2));
+    assert!(graph.try_add_edge(a, a, 1).is_ok());
+    assert!(graph.try_add_edge(a, a, 2).is_ok());
+    assert_eq!(graph.degree(a), 2);
+    assert_eq!(graph.try_add_edge(a, a, 3), Err(GraphError::DegreeLimitExceeded { vertex: a, limit: 2 }));
+}
+
+#[test]
+fn test_dot_renders_loop() {
+    let mut graph: SlotMapGraph<u32, u32> = SlotMapGraph::new();
+    let a = graph.add_vertex(0);
+    graph.add_edge(a, a, 7);
+    let dot = graph.to_dot_with(|_, _| String::new(), |_, weight| format!("w{}", weight));
+    assert_eq!(dot, "digraph {\n    n0;\n    n0 -> n0 [label=\"w7\"];\n}\n");
+}
+
+#[test]
+fn test_topology_snapshot_restores_loop() {
+    let (mut graph, v, loops, ..) = looped();
+    let snapshot = graph.topology_snapshot();
+    graph.add_edge(v, v, 99);
+    assert_eq!(graph.degree(v), 5);
+    assert_eq!(graph.restore_topology(&snapshot), Ok(()));
+    assert_eq!(ids(graph.edges_between(v, v)), loops);
+    assert_eq!(graph.degree(v), 4);
+    assert_eq!(graph.validate(), Ok(()));
+}